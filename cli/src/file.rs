@@ -1 +1,4 @@
+pub mod block_store;
+pub mod check_store;
+pub mod norm_store;
 pub mod store;
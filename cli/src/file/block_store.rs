@@ -0,0 +1,77 @@
+use sp_cid::Cid;
+use std::{
+  fs,
+  io,
+  path::PathBuf,
+  process,
+};
+use yatima_core::ipld::{
+  Block,
+  BlockStore,
+};
+
+/// A block store keeping one file per block in a directory, named by CID.
+/// Blocks are verified against their CIDs when read, and written to a
+/// temporary file first so that readers never see a partial block.
+#[derive(Debug, Clone)]
+pub struct DirStore {
+  pub dir: PathBuf,
+}
+
+impl DirStore {
+  pub fn new(dir: PathBuf) -> Self { DirStore { dir } }
+
+  fn path(&self, cid: &Cid) -> PathBuf { self.dir.join(cid.to_string()) }
+
+  /// Reads a block, failing with `InvalidData` if the file doesn't hash to
+  /// its name
+  pub fn try_get(&self, cid: &Cid) -> io::Result<Option<Block>> {
+    let data = match fs::read(self.path(cid)) {
+      Ok(data) => data,
+      Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+      Err(e) => return Err(e),
+    };
+    Block::new(*cid, data)
+      .map(Some)
+      .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))
+  }
+
+  /// Writes a block unless it's already present
+  pub fn try_put(&self, block: &Block) -> io::Result<Cid> {
+    let cid = block.cid();
+    let path = self.path(&cid);
+    if path.exists() {
+      return Ok(cid);
+    }
+    let tmp = self.dir.join(format!(".{}.{}.tmp", cid, process::id()));
+    fs::write(&tmp, block.data())?;
+    fs::rename(&tmp, &path).inspect_err(|_| {
+      let _ = fs::remove_file(&tmp);
+    })?;
+    Ok(cid)
+  }
+}
+
+impl BlockStore for DirStore {
+  fn get(&self, cid: &Cid) -> Option<Block> {
+    self.try_get(cid).unwrap_or_else(|e| {
+      eprintln!("Ignoring block {} in {:?}: {}", cid, self.dir, e);
+      None
+    })
+  }
+
+  fn put(&self, block: Block) -> Cid {
+    self.try_put(&block).unwrap_or_else(|e| {
+      panic!(
+        "Error: cannot write block {} to {:?}: {}. Please open an issue at \
+         \"https://github.com/yatima-inc/yatima/issues\" if you see this \
+         message",
+        block.cid(),
+        self.dir,
+        e
+      )
+    })
+  }
+
+  fn has(&self, cid: &Cid) -> bool { self.path(cid).exists() }
+}
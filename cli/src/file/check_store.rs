@@ -0,0 +1,29 @@
+use sp_cid::Cid;
+use std::{
+  fs,
+  path::PathBuf,
+};
+use yatima_utils::cache::CheckStore;
+
+/// The defs that passed type checking, as empty files in `dir` named by their
+/// check keys
+#[derive(Debug, Clone)]
+pub struct CheckDir {
+  pub dir: PathBuf,
+}
+
+impl CheckDir {
+  pub fn new(dir: PathBuf) -> Self { CheckDir { dir } }
+
+  fn path(&self, key: &Cid) -> PathBuf { self.dir.join(key.to_string()) }
+}
+
+impl CheckStore for CheckDir {
+  fn is_checked(&self, key: &Cid) -> bool { self.path(key).is_file() }
+
+  fn set_checked(&self, key: Cid) {
+    if let Err(e) = fs::write(self.path(&key), "") {
+      eprintln!("Cannot record that {} checked: {}", key, e);
+    }
+  }
+}
@@ -0,0 +1,66 @@
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+use std::{
+  convert::TryFrom,
+  fs,
+  path::PathBuf,
+};
+use yatima_core::{
+  anon::Anon,
+  ipld::{
+    hash::{
+      Hasher,
+      DAG_CBOR,
+    },
+    Block,
+    BlockStore,
+    FloatPolicy,
+  },
+  memo::NormStore,
+  meta::Meta,
+  term::Term,
+};
+
+use crate::file::block_store::DirStore;
+
+/// Normal forms kept on disk. Each normal form is a block in `blocks` holding
+/// its anonymous and metadata trees, and the file in `dir` named by the CID of
+/// the normalized term holds the CID of that block.
+#[derive(Debug, Clone)]
+pub struct NormDir {
+  pub dir: PathBuf,
+  pub blocks: DirStore,
+}
+
+impl NormDir {
+  pub fn new(dir: PathBuf, blocks: DirStore) -> Self { NormDir { dir, blocks } }
+
+  fn path(&self, cid: &Cid) -> PathBuf { self.dir.join(cid.to_string()) }
+}
+
+impl NormStore for NormDir {
+  fn get(&self, cid: &Cid) -> Option<Term> {
+    let link = fs::read_to_string(self.path(cid)).ok()?;
+    let link = Cid::try_from(link.trim()).ok()?;
+    match self.blocks.get(&link)?.decode().ok()? {
+      Ipld::List(xs) if xs.len() == 2 => {
+        let anon = Anon::from_ipld(&xs[0]).ok()?;
+        let meta = Meta::from_ipld(&xs[1]).ok()?;
+        Term::unembed(&anon, &meta).ok()
+      }
+      _ => None,
+    }
+  }
+
+  fn put(&self, cid: Cid, norm: &Term) {
+    let (anon, meta) = norm.embed();
+    let ipld = Ipld::List(vec![anon.to_ipld(), meta.to_ipld()]);
+    let block =
+      Block::encode(DAG_CBOR, Hasher::default(), FloatPolicy::Strict, &ipld)
+        .expect("encodable ipld");
+    let link = self.blocks.put(block);
+    if let Err(e) = fs::write(self.path(&cid), link.to_string()) {
+      eprintln!("Cannot record the normal form of {}: {}", cid, e);
+    }
+  }
+}
@@ -1,23 +1,21 @@
 use directories_next::ProjectDirs;
 
-use bytecursor::ByteCursor;
+use crate::file::{
+  block_store::DirStore,
+  check_store::CheckDir,
+  norm_store::NormDir,
+};
+
 use multiaddr::Multiaddr;
 use sp_cid::Cid;
 use sp_ipld::{
-  dag_cbor::{
-    cid,
-    DagCborCodec,
-  },
-  Codec,
+  dag_cbor::cid,
   Ipld,
 };
 use std::{
   collections::HashMap,
   fs,
-  path::{
-    Path,
-    PathBuf,
-  },
+  path::PathBuf,
   rc::Rc,
   sync::{
     Arc,
@@ -29,7 +27,18 @@ use tokio::{
   runtime::Handle,
   task,
 };
-use yatima_core::defs::Defs;
+use yatima_core::{
+  defs::Defs,
+  ipld::{
+    hash::{
+      Hasher,
+      DAG_CBOR,
+    },
+    Block,
+    BlockStore,
+    FloatPolicy,
+  },
+};
 use yatima_utils::{
   debug,
   file::parse,
@@ -88,29 +97,40 @@ pub fn hashspace_directory() -> PathBuf {
   PathBuf::from(path)
 }
 
+/// The hashspace as a block store, which verifies blocks on read and writes
+/// them atomically
+pub fn hashspace_store() -> DirStore { DirStore::new(hashspace_directory()) }
+
+/// The normal forms cached in the hashspace, indexed in its `norms`
+/// subdirectory
+pub fn norm_store() -> NormDir {
+  let dir = hashspace_directory().join("norms");
+  fs::create_dir_all(&dir).unwrap_or_else(|e| {
+    panic!("Error: cannot create normal form directory {:?}: {}", dir, e)
+  });
+  NormDir::new(dir, hashspace_store())
+}
+
+/// The defs checked in earlier sessions, recorded in the hashspace's
+/// `checked` subdirectory
+pub fn check_store() -> CheckDir {
+  let dir = hashspace_directory().join("checked");
+  fs::create_dir_all(&dir).unwrap_or_else(|e| {
+    panic!("Error: cannot create checked defs directory {:?}: {}", dir, e)
+  });
+  CheckDir::new(dir)
+}
+
 pub fn fs_get(link: Cid) -> Option<Ipld> {
-  let dir = hashspace_directory();
-  let path = dir.as_path().join(Path::new(&link.to_string()));
-  let file: Vec<u8> = fs::read(path).ok()?;
-  let res: Ipld =
-    DagCborCodec.decode(ByteCursor::new(file)).expect("valid cbor bytes");
-  Some(res)
+  let block = hashspace_store().get(&link)?;
+  block.decode().ok()
 }
 
 pub fn fs_put(expr: Ipld) -> Cid {
-  let dir = hashspace_directory();
-  let link = cid(&expr);
-  let path = dir.as_path().join(Path::new(&link.to_string()));
-  fs::write(path, DagCborCodec.encode(&expr).unwrap().into_inner())
-    .unwrap_or_else(|_| {
-      panic!(
-    "Error: cannot write to hashspace path {}. \
-     Please open an issue at \
-     \"https://github.com/yatima-inc/yatima/issues\" \
-     if you see this message",
-    link)
-    });
-  link
+  let block =
+    Block::encode(DAG_CBOR, Hasher::default(), FloatPolicy::Strict, &expr)
+      .expect("encodable ipld");
+  hashspace_store().put(block)
 }
 
 #[derive(Debug, Clone)]
@@ -1,28 +1,56 @@
+use multibase::Base;
 use sp_cid::Cid;
 use sp_ipld::Ipld;
 use std::{
+  io::Write,
   path::PathBuf,
   rc::Rc,
 };
 use structopt::StructOpt;
 use yatima_cli::file::store::{
+  check_store,
   FileStore,
   FileStoreOpts,
 };
 #[cfg(not(target_arch = "wasm32"))]
 use yatima_cli::repl;
 use yatima_core::{
+  certificate::{
+    self,
+    verify::verify,
+    Certificate,
+  },
+  debugger::{
+    Debugger,
+    Stop,
+    DEFAULT_HISTORY,
+  },
+  defs::Defs,
+  fuel::Limits,
+  ipld::link::{
+    cid_to_base,
+    parse_base,
+  },
+  kernel,
   name::Name,
-  parse::parse_cid,
+  optimize,
+  parse::parse_cid_str,
+  position::Pos,
+  term::Term,
 };
 use yatima_runtime::transform::StdIORuntime;
 use yatima_utils::{
+  cache,
+  changelog,
+  checkout,
   file,
   ipfs::IpfsApi,
+  json,
   store::{
     show,
     Store,
   },
+  upgrade,
 };
 
 #[derive(Debug, StructOpt)]
@@ -45,6 +73,14 @@ struct Cli {
   )]
   root: Option<PathBuf>,
 
+  #[structopt(
+    long,
+    default_value = "base32",
+    parse(try_from_str = parse_base),
+    help = "The multibase encoding CIDs are printed in."
+  )]
+  base: Base,
+
   /// Command to execute
   #[structopt(subcommand)]
   command: Command,
@@ -60,6 +96,23 @@ enum Command {
   Check {
     #[structopt(parse(from_os_str))]
     path: PathBuf,
+    #[structopt(
+      long,
+      help = "Store a certificate of each typing, checkable by `verify`."
+    )]
+    certify: bool,
+    #[structopt(
+      long,
+      help = "Check each definition again with the independent kernel."
+    )]
+    double_check: bool,
+  },
+  /// Checks a certificate stored by `check --certify`
+  Verify {
+    #[structopt(parse(from_os_str))]
+    path: PathBuf,
+    #[structopt(parse(try_from_str = parse_cid_str))]
+    cid: Cid,
   },
   Show {
     #[structopt(subcommand)]
@@ -68,6 +121,66 @@ enum Command {
   Run {
     #[structopt(parse(from_os_str))]
     path: PathBuf,
+    #[structopt(
+      long,
+      help = "Run on the bytecode VM instead of by graph reduction."
+    )]
+    vm: bool,
+    #[structopt(
+      short = "O",
+      parse(from_occurrences),
+      help = "Inline and simplify definitions first, harder when repeated."
+    )]
+    opt: u64,
+    #[structopt(long, help = "Stop after this many reduction steps.")]
+    max_steps: Option<u64>,
+    #[structopt(long, help = "Stop after this many seconds.")]
+    max_seconds: Option<u64>,
+    #[structopt(
+      long,
+      help = "Stop once reduction allocates this many MiB of graph nodes."
+    )]
+    max_memory: Option<u64>,
+    #[structopt(last = true, help = "The arguments #IO.get_args gives main.")]
+    args: Vec<String>,
+  },
+  Compile {
+    #[structopt(parse(from_os_str))]
+    path: PathBuf,
+    #[structopt(
+      long,
+      default_value = "wasm",
+      help = "The compilation target. Only wasm is supported."
+    )]
+    target: String,
+    #[structopt(
+      long,
+      help = "A definition to export, `main` if none are given."
+    )]
+    export: Vec<String>,
+    #[structopt(
+      short,
+      long,
+      parse(from_os_str),
+      help = "The output file, the input with a .wasm extension by default."
+    )]
+    output: Option<PathBuf>,
+    #[structopt(
+      short = "O",
+      parse(from_occurrences),
+      help = "Inline and simplify definitions first, harder when repeated."
+    )]
+    opt: u64,
+  },
+  Debug {
+    #[structopt(parse(from_os_str))]
+    path: PathBuf,
+    def: String,
+    #[structopt(
+      long = "break",
+      help = "Stop continuing when this definition is unfolded."
+    )]
+    breakpoints: Vec<String>,
   },
   Repl,
   Pin {
@@ -75,7 +188,40 @@ enum Command {
     path: PathBuf,
   },
   Clone {
-    cid: String,
+    #[structopt(parse(try_from_str = parse_cid_str))]
+    cid: Cid,
+  },
+  Upgrade {
+    #[structopt(parse(from_os_str))]
+    path: PathBuf,
+    dep: String,
+    #[structopt(parse(try_from_str = parse_cid_str))]
+    cid: Cid,
+    #[structopt(
+      long,
+      help = "Report the upgrade without rewriting the package source."
+    )]
+    dry_run: bool,
+  },
+  Changelog {
+    #[structopt(parse(try_from_str = parse_cid_str))]
+    old: Cid,
+    #[structopt(parse(try_from_str = parse_cid_str))]
+    new: Cid,
+    #[structopt(long, help = "Render the changelog as markdown.")]
+    markdown: bool,
+  },
+  Src {
+    #[structopt(parse(try_from_str = parse_cid_str))]
+    cid: Cid,
+    defs: Vec<String>,
+    #[structopt(short = "o", long = "out", parse(from_os_str))]
+    out: PathBuf,
+  },
+  /// Stores a block written as DAG-JSON
+  Put {
+    #[structopt(parse(from_os_str))]
+    path: PathBuf,
   },
 }
 
@@ -86,25 +232,30 @@ enum ShowType {
     path: PathBuf,
   },
   Graph {
-    #[structopt(parse(try_from_str = parse_cid))]
+    #[structopt(parse(try_from_str = parse_cid_str))]
     input: Cid,
   },
   Package {
-    #[structopt(parse(try_from_str = parse_cid))]
+    #[structopt(parse(try_from_str = parse_cid_str))]
     input: Cid,
   },
   Entry {
-    #[structopt(parse(try_from_str = parse_cid))]
+    #[structopt(parse(try_from_str = parse_cid_str))]
     input: Cid,
     #[structopt(name = "var", long, short)]
     var: bool,
   },
   Anon {
-    #[structopt(parse(try_from_str = parse_cid))]
+    #[structopt(parse(try_from_str = parse_cid_str))]
     input: Cid,
   },
   Raw {
-    #[structopt(parse(try_from_str = parse_cid))]
+    #[structopt(parse(try_from_str = parse_cid_str))]
+    input: Cid,
+  },
+  /// Any block, as DAG-JSON
+  Json {
+    #[structopt(parse(try_from_str = parse_cid_str))]
     input: Cid,
   },
 }
@@ -128,6 +279,62 @@ fn repl(_store: Rc<dyn Store>) -> std::io::Result<()> {
   Ok(())
 }
 
+fn print_step(debugger: &Debugger) {
+  let step = debugger.steps() - debugger.offset() as u64;
+  println!("{}: {}", step, debugger.current());
+  if let Some(step) = debugger.last_step() {
+    println!("  redex: {}", step.redex);
+    println!("  result: {}", step.result);
+    if let Some(context) = &step.context {
+      println!("  context: {}", context);
+    }
+    println!("  allocations: {}", step.allocations);
+  }
+}
+
+fn debug(mut debugger: Debugger, defs: &Defs) -> std::io::Result<()> {
+  print_step(&debugger);
+  let stdin = std::io::stdin();
+  let mut line = String::new();
+  loop {
+    print!("debug> ");
+    std::io::stdout().flush()?;
+    line.clear();
+    if stdin.read_line(&mut line)? == 0 {
+      return Ok(());
+    }
+    let mut words = line.split_whitespace();
+    match (words.next(), words.next()) {
+      (None, _) | (Some("step"), _) => match debugger.forward(defs) {
+        Some(_) => print_step(&debugger),
+        None => println!("Normal form reached"),
+      },
+      (Some("back"), _) => match debugger.back() {
+        Some(_) => print_step(&debugger),
+        None => println!("No earlier step in the history"),
+      },
+      (Some("continue"), _) => {
+        match debugger.run(defs, u64::MAX) {
+          Stop::Breakpoint(nam) => println!("Breakpoint on {}", nam),
+          _ => println!("Normal form reached"),
+        }
+        print_step(&debugger);
+      }
+      (Some("break"), Some(nam)) => debugger.add_breakpoint(Name::from(nam)),
+      (Some("delete"), Some(nam)) => {
+        if !debugger.remove_breakpoint(&Name::from(nam)) {
+          println!("No breakpoint on {}", nam);
+        }
+      }
+      (Some("quit"), _) => return Ok(()),
+      _ => println!(
+        "Commands: step (or an empty line), back, continue, break <def>, \
+         delete <def>, quit"
+      ),
+    }
+  }
+}
+
 fn run_cli() -> std::io::Result<()> {
   let cli = Cli::from_args();
   let root = cli.root.unwrap_or_else(|| std::env::current_dir().unwrap());
@@ -208,6 +415,16 @@ fn run_cli() -> std::io::Result<()> {
         }
       }
     }
+    Command::Show { typ: ShowType::Json { input } } => {
+      let ipld = store.get(input).ok_or_else(|| {
+        eprintln!("Block {} not found", input);
+        std::io::Error::from(std::io::ErrorKind::NotFound)
+      })?;
+      let value = json::ipld_to_json(&ipld, &json::JsonPolicy::default())
+        .map_err(|e| handle_error_string(e.into()))?;
+      println!("{}", serde_json::to_string_pretty(&value)?);
+      Ok(())
+    }
     Command::Parse { path } => {
       let env = file::parse::PackageEnv::new(root, path, store.clone());
       let (cid, p, d) = file::parse::parse_file(env).map_err(|e| {
@@ -216,15 +433,49 @@ fn run_cli() -> std::io::Result<()> {
       })?;
       store.put(p.to_ipld());
 
-      println!("Package parsed:\n{}", cid);
+      println!("Package parsed:\n{}", cid_to_base(&cid, cli.base));
       println!("{}", d);
       Ok(())
     }
-    Command::Check { path } => {
+    Command::Check { path, certify: false, double_check: false } => {
+      cache::session().borrow_mut().set_store(Some(Rc::new(check_store())));
       file::check_all_in_file(root, path, store)?;
       Ok(())
     }
-    Command::Run { path } => {
+    Command::Check { path, certify, double_check } => {
+      let env = file::parse::PackageEnv::new(root, path, store.clone());
+      let (_, p, defs) = file::parse::parse_file(env).map_err(handle_error_string)?;
+      let p = Rc::new(p);
+      let checked = file::check_all(p.clone(), Rc::new(defs), store.clone())
+        .map_err(handle_error_string)?;
+      for (nam, _) in &p.index.0 {
+        if double_check {
+          kernel::check_def(&checked, nam)
+            .map_err(|e| handle_error_string(format!("{}: {}", nam, e)))?;
+          println!("✓ {}", nam);
+        }
+        if certify {
+          let cert = certificate::certify_def(checked.clone(), nam, false)
+            .map_err(|e| handle_error_string(e.to_string()))?;
+          let (root, blocks) = cert.to_blocks();
+          for (_, ipld) in blocks {
+            store.put(ipld);
+          }
+          println!("{}: {}", nam, cid_to_base(&root, cli.base));
+        }
+      }
+      Ok(())
+    }
+    Command::Verify { path, cid } => {
+      let env = file::parse::PackageEnv::new(root, path, store.clone());
+      let (_, _, defs) = file::parse::parse_file(env).map_err(handle_error_string)?;
+      let cert = Certificate::from_blocks(&cid, &mut |link| store.get(*link))
+        .map_err(|e| handle_error_string(e.into()))?;
+      verify(&defs, &cert).map_err(|e| handle_error_string(e.to_string()))?;
+      println!("✓ {}", cert.name);
+      Ok(())
+    }
+    Command::Run { path, vm, opt, max_steps, max_seconds, max_memory, args } => {
       let env = file::parse::PackageEnv::new(root, path.clone(), store.clone());
       let (_, p, defs) = file::parse::parse_file(env).map_err(handle_error_string)?;
       let p = Rc::new(p);
@@ -233,28 +484,123 @@ fn run_cli() -> std::io::Result<()> {
       let _cid = store.put(p.to_ipld());
 
       let checked = file::check_all(p.clone(), defs, store).map_err(handle_error_string)?;
-      let def = checked.get(&Name::from("main")).unwrap_or_else(|| {
+      let checked = Rc::new(optimize::optimize_defs(&checked, optimize::Opts::level(opt)));
+      let mut term = checked.get(&Name::from("main")).map(|def| def.term.clone()).unwrap_or_else(|| {
         panic!("No `main` expression in package {} from file {:?}", p.name, path)
       });
-      let runtime_io = Rc::new(StdIORuntime::new());
+      let runtime_io = Rc::new(StdIORuntime::with_args(args));
+      let limits = Limits {
+        steps: max_steps,
+        seconds: max_seconds,
+        bytes: max_memory.map(|mib| (mib as usize) << 20),
+      };
 
-      yatima_runtime::run(&mut def.to_owned().term, checked, runtime_io);
+      if vm {
+        yatima_runtime::run_vm(&term, checked, runtime_io, limits)
+          .map_err(|e| handle_error_string(e.to_string()))?;
+      }
+      else {
+        yatima_runtime::run_io(&mut term, checked, runtime_io, limits)
+          .map_err(|e| handle_error_string(e.to_string()))?;
+      }
       Ok(())
     }
+    Command::Compile { path, target, export, output, opt } => {
+      if target != "wasm" {
+        return Err(handle_error_string(format!(
+          "Unsupported compilation target {}",
+          target
+        )));
+      }
+      let checked = file::check_all_in_file(root, path.clone(), store)?;
+      let checked = optimize::optimize_defs(&checked, optimize::Opts::level(opt));
+      let entries: Vec<Name> = if export.is_empty() {
+        vec![Name::from("main")]
+      }
+      else {
+        export.iter().map(|x| Name::from(x.as_str())).collect()
+      };
+      let module = yatima_runtime::wasm::compile(&checked, &entries)
+        .map_err(|e| handle_error_string(e.to_string()))?;
+      let output = output.unwrap_or_else(|| path.with_extension("wasm"));
+      std::fs::write(&output, module)?;
+      println!("Compiled to {}", output.display());
+      Ok(())
+    }
+    Command::Debug { path, def, breakpoints } => {
+      let checked = file::check_all_in_file(root, path.clone(), store)?;
+      let nam = Name::from(def.as_str());
+      let def = checked.get(&nam).ok_or_else(|| {
+        handle_error_string(format!("No `{}` definition in {:?}", nam, path))
+      })?;
+      let term = Term::Ref(Pos::None, nam, def.def_cid, def.ast_cid);
+      let mut debugger = Debugger::new(&term, DEFAULT_HISTORY);
+      for nam in breakpoints {
+        debugger.add_breakpoint(Name::from(nam.as_str()));
+      }
+      debug(debugger, &checked)
+    }
     Command::Pin { path } => {
-      pin(path, root, store);
+      pin(path, root, store, cli.base);
       Ok(())
     }
     Command::Clone { cid } => {
-      let cid = parse_cid(&cid).unwrap();
       clone(cid, root, store);
       println!("Cloned directory from IPFS");
       Ok(())
     }
+    Command::Upgrade { path, dep, cid, dry_run } => {
+      let upgrade =
+        upgrade::upgrade(root.clone(), path.clone(), &Name::from(dep), cid, store)
+          .map_err(handle_error_string)?;
+      print!("{}", upgrade);
+      if !dry_run {
+        let mut file = root;
+        file.push(path);
+        std::fs::write(&file, &upgrade.source)?;
+        println!("Wrote {:?}", file);
+      }
+      Ok(())
+    }
+    Command::Changelog { old, new, markdown } => {
+      let changelog =
+        changelog::changelog(store, old, new).map_err(handle_error_string)?;
+      if markdown {
+        print!("{}", changelog.to_markdown());
+      }
+      else {
+        print!("{}", changelog);
+      }
+      Ok(())
+    }
+    Command::Src { cid, defs, out } => {
+      let names: Vec<Name> = defs.into_iter().map(Name::from).collect();
+      let checkout = checkout::checkout(store, cid, &names)
+        .map_err(handle_error_string)?;
+      let mut path = out.clone();
+      path.push(&checkout.path);
+      if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+      }
+      std::fs::write(&path, &checkout.source)?;
+      println!("Wrote {:?} with {} definitions", path, checkout.local.len());
+      println!("Check edits with: yatima --root {:?} check {:?}", out, checkout.path);
+      Ok(())
+    }
+    Command::Put { path } => {
+      let mut file = root;
+      file.push(path);
+      let text = std::fs::read_to_string(&file)?;
+      let value: serde_json::Value = serde_json::from_str(&text)?;
+      let ipld =
+        json::json_to_ipld(&value).map_err(|e| handle_error_string(e.into()))?;
+      println!("{}", cid_to_base(&store.put(ipld), cli.base));
+      Ok(())
+    }
   }
 }
 
-fn pin(path: PathBuf, root: PathBuf, store: Rc<FileStore>) {
+fn pin(path: PathBuf, root: PathBuf, store: Rc<FileStore>, base: Base) {
   let env =
     file::parse::PackageEnv::new(root.clone(), path.clone(), store.clone());
   let info = file::parse::parse_file(env).unwrap();
@@ -266,7 +612,7 @@ fn pin(path: PathBuf, root: PathBuf, store: Rc<FileStore>) {
       import_path.push(n);
     }
     import_path.set_extension("ya");
-    pin(import_path, root.clone(), store.clone());
+    pin(import_path, root.clone(), store.clone(), base);
   }
   let source = std::fs::read_to_string(&path)
     .map_err(|e| format!("file {:?} not found {:?}", &path, e))
@@ -285,13 +631,10 @@ fn pin(path: PathBuf, root: PathBuf, store: Rc<FileStore>) {
   // let cid = store.put(Ipld::String(source.clone()));
   // println!("Pinned {} with CID {}", source, cid.to_string());
   let cid = store.put(pkg.to_ipld());
-  println!("Pinned {} with CID {}", pkg, cid.to_string());
+  println!("Pinned {} with CID {}", pkg, cid_to_base(&cid, base));
 }
 
-use yatima_core::{
-  package::Package,
-  position::Pos,
-};
+use yatima_core::package::Package;
 
 // Convert a package and each import into files and write to flat directory
 fn clone(cid: Cid, root: PathBuf, store: Rc<FileStore>) {
@@ -18,8 +18,12 @@ use std::{
   },
 };
 
-use crate::file::store::FileStore;
+use crate::file::store::{
+  norm_store,
+  FileStore,
+};
 use yatima_utils::{
+  cache,
   repl::{
     error::ReplError,
     run_repl,
@@ -76,4 +80,7 @@ impl Repl for RustyLineRepl {
   fn get_store(&self) -> Rc<dyn Store> { self.store.clone() }
 }
 
-pub fn main(store: Rc<FileStore>) { run_repl(&mut RustyLineRepl::new(store)); }
+pub fn main(store: Rc<FileStore>) {
+  cache::session_norms().borrow_mut().set_store(Some(Rc::new(norm_store())));
+  run_repl(&mut RustyLineRepl::new(store));
+}
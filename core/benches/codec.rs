@@ -0,0 +1,141 @@
+use bytecursor::ByteCursor;
+use criterion::{
+  black_box,
+  criterion_group,
+  criterion_main,
+  BenchmarkId,
+  Criterion,
+  Throughput,
+};
+use nom_locate::LocatedSpan;
+use sp_ipld::{
+  dag_cbor::DagCborCodec,
+  Codec,
+  Ipld,
+};
+use std::collections::BTreeMap;
+use yatima_core::{
+  defs::Defs,
+  ipld::{
+    writer,
+    IpldRef,
+  },
+  parse::{
+    package::parse_defs,
+    term::input_cid,
+  },
+};
+
+const SOURCE: &str = "
+def id (A: Type) (x: A): A = x
+
+def const (A: Type) (B: Type) (x: A) (y: B): A = x
+
+def fact (x: #Nat): #Nat = (case x) (λ _ => #Nat) 1 (λ x' => #Nat.mul x (fact \
+                      x'))
+
+def compose (A: Type) (B: Type) (C: Type) (g: ∀ B -> C) (f: ∀ A -> B) (x: A): \
+                      C = g (f x)
+
+def greeting: #Text = \"the quick brown fox jumps over the lazy dog\"
+";
+
+fn encode(ipld: &Ipld) -> Vec<u8> {
+  DagCborCodec.encode(ipld).unwrap().into_inner()
+}
+
+/// Entry, type and term blocks of real definitions
+fn package_blocks() -> Vec<(String, Ipld)> {
+  let (_, (defs, index)) =
+    parse_defs(input_cid(SOURCE), Defs::new())(LocatedSpan::from(SOURCE))
+      .unwrap();
+  let mut blocks = vec![("index".to_owned(), index.to_ipld())];
+  for (name, def) in defs.named_defs() {
+    let (entry, type_anon, term_anon) = def.embed();
+    blocks.push((format!("{}/entry", name), entry.to_ipld()));
+    blocks.push((format!("{}/type", name), type_anon.to_ipld()));
+    blocks.push((format!("{}/term", name), term_anon.to_ipld()));
+  }
+  blocks
+}
+
+/// Blocks of shapes that stress particular parts of the codec
+fn synthetic_blocks() -> Vec<(String, Ipld)> {
+  let wide = Ipld::List((0..4096).map(Ipld::Integer).collect());
+  let deep =
+    (0..256).fold(Ipld::Null, |acc, i| Ipld::List(vec![Ipld::Integer(i), acc]));
+  let strings = Ipld::StringMap(
+    (0..1024)
+      .map(|i| (format!("key{}", i), Ipld::String(format!("value {}", i))))
+      .collect::<BTreeMap<_, _>>(),
+  );
+  let bytes = Ipld::Bytes((0..65536).map(|i| i as u8).collect());
+  vec![
+    ("wide".to_owned(), wide),
+    ("deep".to_owned(), deep),
+    ("strings".to_owned(), strings),
+    ("bytes".to_owned(), bytes),
+  ]
+}
+
+fn bench_blocks(c: &mut Criterion, group: &str, blocks: Vec<(String, Ipld)>) {
+  let mut group = c.benchmark_group(group);
+  for (name, ipld) in blocks {
+    let bytes = encode(&ipld);
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_with_input(BenchmarkId::new("encode", &name), &ipld, |b, i| {
+      b.iter(|| encode(black_box(i)))
+    });
+    group.bench_with_input(
+      BenchmarkId::new("encode_writer", &name),
+      &ipld,
+      |b, i| b.iter(|| writer::encode(black_box(i)).unwrap()),
+    );
+    #[cfg(feature = "rayon")]
+    group.bench_with_input(
+      BenchmarkId::new("encode_par", &name),
+      &ipld,
+      |b, i| b.iter(|| writer::par_encode(black_box(i)).unwrap()),
+    );
+    group.bench_with_input(
+      BenchmarkId::new("decode", &name),
+      &bytes,
+      |b, x| {
+        b.iter(|| {
+          let ipld: Ipld =
+            DagCborCodec.decode(ByteCursor::new(x.clone())).unwrap();
+          ipld
+        })
+      },
+    );
+    group.bench_with_input(
+      BenchmarkId::new("decode_borrowed", &name),
+      &bytes,
+      |b, x| b.iter(|| IpldRef::decode(black_box(x)).unwrap()),
+    );
+    group.bench_with_input(
+      BenchmarkId::new("libipld_encode", &name),
+      &libipld_ipld(&bytes),
+      |b, i| b.iter(|| serde_ipld_dagcbor::to_vec(black_box(i)).unwrap()),
+    );
+    group.bench_with_input(
+      BenchmarkId::new("libipld_decode", &name),
+      &bytes,
+      |b, x| b.iter(|| libipld_ipld(black_box(x))),
+    );
+  }
+  group.finish();
+}
+
+fn libipld_ipld(bytes: &[u8]) -> libipld_core::ipld::Ipld {
+  serde_ipld_dagcbor::from_slice(bytes).unwrap()
+}
+
+fn package(c: &mut Criterion) { bench_blocks(c, "package", package_blocks()) }
+
+fn synthetic(c: &mut Criterion) {
+  bench_blocks(c, "synthetic", synthetic_blocks())
+}
+
+criterion_group!(codec, package, synthetic);
+criterion_main!(codec);
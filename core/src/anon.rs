@@ -6,6 +6,7 @@ use sp_ipld::{
 };
 
 use crate::{
+  level::Level,
   literal::{
     LitType,
     Literal,
@@ -33,7 +34,7 @@ pub enum Anon {
   Cse(Box<Anon>),
   Ref(Cid),
   Let(bool, Uses, Box<(Anon, Anon, Anon)>),
-  Typ,
+  Typ(Level),
   Ann(Box<(Anon, Anon)>),
   Lit(Literal),
   LTy(LitType),
@@ -81,7 +82,9 @@ impl Anon {
           bod.to_ipld(),
         ])
       }
-      Self::Typ => Ipld::List(vec![Ipld::Integer(9)]),
+      // The lowest universe keeps the encoding it had as the only one
+      Self::Typ(lvl) if lvl.is_zero() => Ipld::List(vec![Ipld::Integer(9)]),
+      Self::Typ(lvl) => Ipld::List(vec![Ipld::Integer(9), lvl.to_ipld()]),
       Self::Ann(typ_exp) => {
         let (typ, exp) = (*typ_exp).as_ref();
         Ipld::List(vec![Ipld::Integer(10), typ.to_ipld(), exp.to_ipld()])
@@ -141,7 +144,8 @@ impl Anon {
           let bod = Anon::from_ipld(bod)?;
           Ok(Anon::Let(*rec, uses, Box::new((typ, exp, bod))))
         }
-        [Ipld::Integer(9)] => Ok(Anon::Typ),
+        [Ipld::Integer(9)] => Ok(Anon::Typ(Level::zero())),
+        [Ipld::Integer(9), lvl] => Ok(Anon::Typ(Level::from_ipld(lvl)?)),
         [Ipld::Integer(10), typ, exp] => {
           let typ = Anon::from_ipld(typ)?;
           let exp = Anon::from_ipld(exp)?;
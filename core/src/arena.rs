@@ -0,0 +1,533 @@
+use core::{
+  ptr::NonNull,
+  sync::atomic::{
+    AtomicUsize,
+    Ordering,
+  },
+};
+
+#[cfg(any(feature = "std", test))]
+use crate::dag::forget_live;
+
+#[cfg(any(feature = "std", test))]
+use alloc::alloc::{
+  alloc,
+  dealloc,
+  handle_alloc_error,
+  Layout,
+};
+
+#[cfg(any(feature = "std", test))]
+use core::{
+  cell::RefCell,
+  mem,
+  ptr,
+};
+
+#[cfg(any(feature = "std", test))]
+use sp_std::vec::Vec;
+
+#[cfg(all(any(feature = "std", test), debug_assertions))]
+use std::{
+  collections::BTreeMap,
+  sync::Mutex,
+  thread::{
+    self,
+    ThreadId,
+  },
+};
+
+/// The size in bytes of an arena's first chunk. Every chunk after it is
+/// twice the size of the one before.
+#[cfg(any(feature = "std", test))]
+const FIRST_CHUNK: usize = 1 << 16;
+
+/// How chunks are aligned, which covers every DAG node
+#[cfg(any(feature = "std", test))]
+const CHUNK_ALIGN: usize = 16;
+
+static VALUES: AtomicUsize = AtomicUsize::new(0);
+static REUSED: AtomicUsize = AtomicUsize::new(0);
+static FREED: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static CHUNKS: AtomicUsize = AtomicUsize::new(0);
+static SCOPES: AtomicUsize = AtomicUsize::new(0);
+
+/// The chunks of every arena, by start address, with their end and the
+/// thread whose arena they are in, or `None` once its scope has ended. A
+/// chunk stays here after its scope until its memory holds a heap value, so
+/// that a node freed late or on another thread is caught rather than taken
+/// for a heap value. Only kept with debug assertions.
+#[cfg(all(any(feature = "std", test), debug_assertions))]
+static OWNERS: Mutex<BTreeMap<usize, (usize, Option<ThreadId>)>> =
+  Mutex::new(BTreeMap::new());
+
+/// What arenas have allocated since the program started, across threads
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct ArenaStats {
+  /// Values allocated in an arena instead of on their own
+  pub values: usize,
+  /// Of those, the ones put in the slot of a value freed before them
+  pub reused: usize,
+  /// Values freed before their scope ended
+  pub freed: usize,
+  /// The bytes of the slots handed out, headers and padding included
+  pub bytes: usize,
+  /// The chunks requested from the global allocator
+  pub chunks: usize,
+  /// The scopes ended, each freeing its arena at once
+  pub scopes: usize,
+}
+
+pub fn arena_stats() -> ArenaStats {
+  ArenaStats {
+    values: VALUES.load(Ordering::Relaxed),
+    reused: REUSED.load(Ordering::Relaxed),
+    freed: FREED.load(Ordering::Relaxed),
+    bytes: BYTES.load(Ordering::Relaxed),
+    chunks: CHUNKS.load(Ordering::Relaxed),
+    scopes: SCOPES.load(Ordering::Relaxed),
+  }
+}
+
+/// A live value with a destructor, and the slot it is in
+#[cfg(any(feature = "std", test))]
+struct Live {
+  slot: NonNull<usize>,
+  value: NonNull<u8>,
+  drop: unsafe fn(*mut u8),
+}
+
+/// A free-list allocator for the DAG nodes of one scope. Each value sits in
+/// a slot after a header word, which holds the value's index in `live` if it
+/// has a destructor. A value freed during the scope is dropped at once, and
+/// its slot goes to the next value of the same layout, so reduction that
+/// frees as it goes runs in bounded memory. Whatever is left is dropped, and
+/// every chunk returned, when the scope ends.
+#[cfg(any(feature = "std", test))]
+struct Arena {
+  chunks: Vec<(NonNull<u8>, usize)>,
+  /// The free addresses at the end of the last chunk
+  next: usize,
+  end: usize,
+  /// The slots of freed values, by slot layout
+  free: Vec<(Layout, Vec<NonNull<u8>>)>,
+  /// The values with a destructor, to run when the arena is dropped
+  live: Vec<Live>,
+  /// The values allocated and not yet freed, and their size
+  values: usize,
+  bytes: usize,
+}
+
+/// The header marking a value without a destructor
+#[cfg(any(feature = "std", test))]
+const NO_DROP: usize = usize::MAX;
+
+#[cfg(any(feature = "std", test))]
+unsafe fn drop_value<T>(ptr: *mut u8) { ptr::drop_in_place(ptr as *mut T) }
+
+/// The layout of a slot holding a `T`, and the offset of the `T` in it
+#[cfg(any(feature = "std", test))]
+fn slot_layout<T>() -> (Layout, usize) {
+  let (slot, offset) =
+    Layout::new::<usize>().extend(Layout::new::<T>()).unwrap();
+  (slot.pad_to_align(), offset)
+}
+
+#[cfg(any(feature = "std", test))]
+impl Arena {
+  fn new() -> Self {
+    Arena {
+      chunks: Vec::new(),
+      next: 0,
+      end: 0,
+      free: Vec::new(),
+      live: Vec::new(),
+      values: 0,
+      bytes: 0,
+    }
+  }
+
+  fn alloc<T>(&mut self, val: T) -> NonNull<T> {
+    let (layout, offset) = slot_layout::<T>();
+    let slot = match self.reuse(layout) {
+      Some(slot) => {
+        REUSED.fetch_add(1, Ordering::Relaxed);
+        slot.as_ptr() as usize
+      }
+      None => self.bump(layout),
+    };
+    self.values += 1;
+    self.bytes += mem::size_of::<T>();
+    VALUES.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+    let header = NonNull::new(slot as *mut usize).unwrap();
+    let link = NonNull::new((slot + offset) as *mut T).unwrap();
+    unsafe { link.as_ptr().write(val) };
+    let index = if mem::needs_drop::<T>() {
+      self.live.push(Live {
+        slot: header,
+        value: link.cast(),
+        drop: drop_value::<T>,
+      });
+      self.live.len() - 1
+    }
+    else {
+      NO_DROP
+    };
+    unsafe { header.as_ptr().write(index) };
+    link
+  }
+
+  /// Takes the slot of a freed value of the same layout, if there is one
+  fn reuse(&mut self, layout: Layout) -> Option<NonNull<u8>> {
+    self.free.iter_mut().find(|(l, _)| *l == layout)?.1.pop()
+  }
+
+  /// Takes a new slot from the end of the last chunk
+  fn bump(&mut self, layout: Layout) -> usize {
+    let mut start = align_up(self.next, layout.align());
+    if self.chunks.is_empty() || start + layout.size() > self.end {
+      self.grow(layout);
+      start = align_up(self.next, layout.align());
+    }
+    self.next = start + layout.size();
+    start
+  }
+
+  /// Moves a value out of its slot and makes the slot free for reuse. The
+  /// value is handed back for the caller to drop, once the arenas are no
+  /// longer borrowed.
+  ///
+  /// # Safety
+  ///
+  /// `link` must come from `alloc` on this arena and not have been released.
+  unsafe fn release<T>(&mut self, link: NonNull<T>) -> T {
+    let (layout, offset) = slot_layout::<T>();
+    let slot = (link.as_ptr() as *mut u8).sub(offset);
+    let index = *(slot as *const usize);
+    if index != NO_DROP {
+      self.live.swap_remove(index);
+      if let Some(moved) = self.live.get(index) {
+        moved.slot.as_ptr().write(index);
+      }
+    }
+    let val = link.as_ptr().read();
+    let slot = NonNull::new(slot).unwrap();
+    match self.free.iter_mut().find(|(l, _)| *l == layout) {
+      Some((_, slots)) => slots.push(slot),
+      None => self.free.push((layout, vec![slot])),
+    }
+    self.values -= 1;
+    self.bytes -= mem::size_of::<T>();
+    FREED.fetch_add(1, Ordering::Relaxed);
+    val
+  }
+
+  /// Starts a new chunk with room for at least one value of `layout`
+  fn grow(&mut self, layout: Layout) {
+    let size = self
+      .chunks
+      .last()
+      .map_or(FIRST_CHUNK, |(_, size)| size * 2)
+      .max(layout.size() + layout.align());
+    let chunk = Layout::from_size_align(size, CHUNK_ALIGN).unwrap();
+    let ptr = NonNull::new(unsafe { alloc(chunk) })
+      .unwrap_or_else(|| handle_alloc_error(chunk));
+    CHUNKS.fetch_add(1, Ordering::Relaxed);
+    #[cfg(debug_assertions)]
+    set_owner(ptr.as_ptr() as usize, size, Some(thread::current().id()));
+    self.chunks.push((ptr, size));
+    self.next = ptr.as_ptr() as usize;
+    self.end = self.next + size;
+  }
+
+  fn owns(&self, addr: usize) -> bool {
+    self.chunks.iter().any(|(ptr, size)| {
+      let start = ptr.as_ptr() as usize;
+      start <= addr && addr < start + size
+    })
+  }
+}
+
+#[cfg(any(feature = "std", test))]
+impl Drop for Arena {
+  fn drop(&mut self) {
+    for live in self.live.drain(..) {
+      unsafe { (live.drop)(live.value.as_ptr()) };
+    }
+    for (ptr, size) in self.chunks.drain(..) {
+      #[cfg(debug_assertions)]
+      set_owner(ptr.as_ptr() as usize, size, None);
+      let chunk = Layout::from_size_align(size, CHUNK_ALIGN).unwrap();
+      unsafe { dealloc(ptr.as_ptr(), chunk) };
+    }
+    forget_live(self.values, self.bytes);
+    SCOPES.fetch_add(1, Ordering::Relaxed);
+  }
+}
+
+/// Records the owner of a chunk, forgetting the chunks of ended scopes that
+/// shared its memory
+#[cfg(all(any(feature = "std", test), debug_assertions))]
+fn set_owner(start: usize, size: usize, owner: Option<ThreadId>) {
+  let mut owners = OWNERS.lock().unwrap_or_else(|e| e.into_inner());
+  forget_owners(&mut owners, start, size);
+  owners.insert(start, (start + size, owner));
+}
+
+/// Forgets the chunks overlapping some memory. Chunks never overlap one
+/// another, so they're found backwards from its end.
+#[cfg(all(any(feature = "std", test), debug_assertions))]
+fn forget_owners(
+  owners: &mut BTreeMap<usize, (usize, Option<ThreadId>)>,
+  start: usize,
+  size: usize,
+) {
+  let overlapping: Vec<usize> = owners
+    .range(..start + size.max(1))
+    .rev()
+    .take_while(|(_, (end, _))| *end > start)
+    .map(|(start, _)| *start)
+    .collect();
+  for start in overlapping {
+    owners.remove(&start);
+  }
+}
+
+#[cfg(any(feature = "std", test))]
+fn align_up(addr: usize, align: usize) -> usize {
+  (addr + align - 1) & !(align - 1)
+}
+
+#[cfg(any(feature = "std", test))]
+std::thread_local! {
+  /// The arenas of the scopes open on this thread, innermost last
+  static ARENAS: RefCell<Vec<Arena>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Ends the innermost scope, even if it unwinds
+#[cfg(any(feature = "std", test))]
+struct Scope;
+
+#[cfg(any(feature = "std", test))]
+impl Drop for Scope {
+  fn drop(&mut self) {
+    let arena = ARENAS.with(|arenas| arenas.borrow_mut().pop());
+    drop(arena);
+  }
+}
+
+/// Runs `f` with the DAG nodes it allocates on this thread taken from an
+/// arena, which is freed as a whole once `f` returns. Nodes freed inside the
+/// scope have their slots reused by the nodes allocated after them. Scopes
+/// nest, each with an arena of its own. Without the `std` feature there are
+/// no arenas, and nodes are allocated one by one as usual.
+///
+/// # Safety
+///
+/// Nothing `f` allocates with `alloc_val` may be used once the scope ends,
+/// so `f` must read back whatever it keeps, as a `Term` for instance, and
+/// must not link what it allocates into a DAG built outside the scope.
+///
+/// Nodes allocated in the scope must also be freed in it, and on this
+/// thread, since `free_val` only finds the arenas open on the thread it runs
+/// on and takes any other node for one on the heap. Jobs run on other
+/// threads must not free them. With debug assertions, freeing a node of an
+/// arena whose scope has ended or that is open on another thread panics,
+/// rather than handing the node to the global allocator.
+#[cfg(any(feature = "std", test))]
+pub unsafe fn with_arena<R, F: FnOnce() -> R>(f: F) -> R {
+  ARENAS.with(|arenas| arenas.borrow_mut().push(Arena::new()));
+  let _scope = Scope;
+  f()
+}
+
+/// Runs `f`, as `with_arena` does where there are arenas
+///
+/// # Safety
+///
+/// The same as with arenas, so that callers hold for either.
+#[cfg(not(any(feature = "std", test)))]
+pub unsafe fn with_arena<R, F: FnOnce() -> R>(f: F) -> R { f() }
+
+/// Allocates a value in the innermost arena, or hands it back if no scope
+/// is open
+#[cfg(any(feature = "std", test))]
+pub(crate) fn alloc_in_arena<T>(val: T) -> Result<NonNull<T>, T> {
+  ARENAS.with(|arenas| match arenas.borrow_mut().last_mut() {
+    Some(arena) => Ok(arena.alloc(val)),
+    None => Err(val),
+  })
+}
+
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) fn alloc_in_arena<T>(val: T) -> Result<NonNull<T>, T> { Err(val) }
+
+/// Frees a value if it was allocated in an arena still open on this thread,
+/// dropping it and making its slot free for reuse, and returns whether it
+/// was.
+///
+/// # Safety
+///
+/// `link` must come from `alloc_val`, and must not be used again.
+#[cfg(any(feature = "std", test))]
+pub(crate) unsafe fn free_in_arena<T>(link: NonNull<T>) -> bool {
+  let addr = link.as_ptr() as usize;
+  let val = ARENAS.with(|arenas| {
+    let mut arenas = arenas.borrow_mut();
+    let arena = arenas.iter_mut().rev().find(|arena| arena.owns(addr))?;
+    Some(arena.release(link))
+  });
+  // The value is dropped here, with the arenas no longer borrowed
+  val.is_some()
+}
+
+#[cfg(not(any(feature = "std", test)))]
+pub(crate) unsafe fn free_in_arena<T>(_link: NonNull<T>) -> bool { false }
+
+/// Notes that a value was put on the heap, whose memory may have been an
+/// arena chunk once
+#[cfg(all(any(feature = "std", test), debug_assertions))]
+pub(crate) fn on_heap<T>(link: NonNull<T>) {
+  let mut owners = OWNERS.lock().unwrap_or_else(|e| e.into_inner());
+  forget_owners(&mut owners, link.as_ptr() as usize, mem::size_of::<T>());
+}
+
+#[cfg(not(all(any(feature = "std", test), debug_assertions)))]
+pub(crate) fn on_heap<T>(_link: NonNull<T>) {}
+
+/// Checks that a value not found in the arenas open on this thread is on the
+/// heap, and not in an arena open on another thread or whose scope ended
+#[cfg(all(any(feature = "std", test), debug_assertions))]
+pub(crate) fn debug_assert_on_heap<T>(link: NonNull<T>) {
+  let addr = link.as_ptr() as usize;
+  let owners = OWNERS.lock().unwrap_or_else(|e| e.into_inner());
+  let owner = owners
+    .range(..=addr)
+    .next_back()
+    .filter(|(_, (end, _))| addr < *end)
+    .map(|(_, (_, owner))| *owner);
+  drop(owners);
+  match owner {
+    Some(Some(_)) => panic!("Freed a node of an arena open on another thread"),
+    Some(None) => panic!("Freed a node of an arena whose scope has ended"),
+    None => (),
+  }
+}
+
+#[cfg(not(all(any(feature = "std", test), debug_assertions)))]
+pub(crate) fn debug_assert_on_heap<T>(_link: NonNull<T>) {}
+
+/// Whether a value was allocated in an arena still open on this thread
+#[cfg(test)]
+fn in_arena<T>(link: NonNull<T>) -> bool {
+  let addr = link.as_ptr() as usize;
+  ARENAS.with(|arenas| arenas.borrow().iter().any(|arena| arena.owns(addr)))
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    dag::{
+      alloc_val,
+      free_val,
+      DAGPtr,
+      DAG,
+    },
+    defs::Defs,
+    parse::term::parse,
+  };
+  use sp_std::rc::Rc;
+
+  #[test]
+  fn test_with_arena() {
+    let (_, term) =
+      parse("(λ x y => #U64.add x y) 1u64 2u64", Defs::new()).unwrap();
+    let before = arena_stats();
+    let outer = alloc_val(0u64);
+    let norm = unsafe {
+      with_arena(|| {
+        let mut dag = DAG::from_term(&term);
+        assert!(matches!(dag.head, DAGPtr::App(link) if in_arena(link)));
+        // Values from before the scope are still freed one by one
+        assert!(!in_arena(outer));
+        free_val(outer);
+        let inner = with_arena(|| {
+          let link = alloc_val(1u64);
+          assert!(in_arena(link));
+          *link.as_ptr()
+        });
+        assert_eq!(inner, 1);
+        dag.norm(&Defs::new(), false);
+        dag.to_term(false)
+      })
+    };
+    assert_eq!(format!("{}", norm), "3u64");
+    let after = arena_stats();
+    assert!(after.values > before.values);
+    assert!(after.freed > before.freed);
+    assert!(after.scopes >= before.scopes + 2);
+  }
+
+  #[test]
+  #[cfg(debug_assertions)]
+  #[should_panic(expected = "scope has ended")]
+  fn test_free_after_scope() {
+    let link = unsafe { with_arena(|| alloc_val(0u64)) };
+    unsafe { free_val(link) };
+  }
+
+  #[test]
+  #[cfg(debug_assertions)]
+  fn test_free_on_other_thread() {
+    let rc = Rc::new(());
+    unsafe {
+      with_arena(|| {
+        let link = alloc_val(rc.clone());
+        let addr = link.as_ptr() as usize;
+        let res = std::thread::spawn(move || {
+          free_val(NonNull::new(addr as *mut Rc<()>).unwrap())
+        })
+        .join();
+        // The node is left to its arena, rather than freed as if on the heap
+        assert!(res.is_err());
+        assert!(in_arena(link));
+        assert_eq!(Rc::strong_count(&rc), 2);
+      })
+    };
+    assert_eq!(Rc::strong_count(&rc), 1);
+  }
+
+  #[test]
+  fn test_arena_reuse() {
+    let rc = Rc::new(());
+    unsafe {
+      with_arena(|| {
+        let a = alloc_val(rc.clone());
+        let b = alloc_val(rc.clone());
+        let _c = alloc_val(rc.clone());
+        assert_eq!(Rc::strong_count(&rc), 4);
+        // A freed value is dropped at once, and the next value of its layout
+        // takes its slot
+        free_val(a);
+        assert_eq!(Rc::strong_count(&rc), 3);
+        let d = alloc_val(rc.clone());
+        assert_eq!(d, a);
+        free_val(b);
+        assert_eq!(Rc::strong_count(&rc), 3);
+        // Allocating and freeing in turn keeps to a single slot
+        let first = alloc_val([0u64; 8]);
+        free_val(first);
+        for i in 0..1 << 16 {
+          let link = alloc_val([i; 8]);
+          assert_eq!(link, first);
+          free_val(link);
+        }
+      })
+    };
+    // The values still live are dropped with the arena
+    assert_eq!(Rc::strong_count(&rc), 1);
+  }
+}
@@ -0,0 +1,731 @@
+pub mod verify;
+
+use crate::{
+  anon::Anon,
+  arena::with_arena,
+  check::{
+    check_def,
+    ctx::ErrCtx,
+    error::CheckError,
+    for_each_level,
+    infer_lit,
+    infer_ref,
+  },
+  dag::{
+    alloc_val,
+    ParentPtr,
+    DAG,
+  },
+  defs,
+  defs::Defs,
+  dll::DLL,
+  ipld_error::IpldError,
+  level::Level,
+  literal::{
+    LitType,
+    Literal,
+  },
+  meta::Meta,
+  name::Name,
+  optimize::subst,
+  parse,
+  position::Pos,
+  prim::host::HostRef,
+  term::{
+    Op,
+    Term,
+  },
+  uses::Uses,
+  yatima,
+};
+
+use sp_cid::Cid;
+use sp_ipld::{
+  dag_cbor::cid,
+  Ipld,
+};
+
+use sp_std::{
+  borrow::ToOwned,
+  boxed::Box,
+  collections::btree_map::BTreeMap,
+  convert::TryInto,
+  rc::Rc,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// An inference rule, with what it takes besides its premises to rebuild the
+/// term it types
+#[derive(PartialEq, Clone, Debug)]
+pub enum Rule {
+  Var(Name, u64),
+  Ref(Name, Cid, Cid),
+  /// A recursive reference to the def the certificate is for
+  Rec,
+  Typ(Level),
+  Lit(Literal),
+  LTy(LitType),
+  Opr(Op),
+  /// A λ, from its body typed under the domain of its `∀`
+  Lam(Name),
+  /// An application, from the function and then the argument
+  App,
+  /// A λ applied on the spot, from the argument and then the body, whose
+  /// variable takes the type of the argument
+  Beta(Name),
+  All(bool, Uses, Name),
+  Slf(Name),
+  Dat,
+  Cse,
+  /// An annotation, with the type it gives
+  Ann(Term),
+  /// A local definition, with the type of its expression. Its premises are
+  /// the expression and then the body.
+  Let(bool, Uses, Name, Term),
+}
+
+/// One application of a rule: the type concluded for a term from the steps
+/// typing its subterms
+#[derive(PartialEq, Clone, Debug)]
+pub struct Step {
+  pub rule: Rule,
+  pub typ: Term,
+  pub premises: Vec<Step>,
+}
+
+/// The derivation of a def's type, which anyone holding the defs it refers
+/// to can check with `verify::verify`
+#[derive(PartialEq, Clone, Debug)]
+pub struct Certificate {
+  pub name: Name,
+  /// The CID of the def's package entry
+  pub def: Cid,
+  pub root: Step,
+}
+
+impl Rule {
+  pub fn name(&self) -> &'static str {
+    match self {
+      Self::Var(..) => "var",
+      Self::Ref(..) => "ref",
+      Self::Rec => "rec",
+      Self::Typ(_) => "type",
+      Self::Lit(_) => "lit",
+      Self::LTy(_) => "lit type",
+      Self::Opr(_) => "opr",
+      Self::Lam(_) => "lam",
+      Self::App => "app",
+      Self::Beta(_) => "beta",
+      Self::All(..) => "all",
+      Self::Slf(_) => "self",
+      Self::Dat => "data",
+      Self::Cse => "case",
+      Self::Ann(_) => "ann",
+      Self::Let(..) => "let",
+    }
+  }
+
+  /// How many premises the rule takes
+  pub fn arity(&self) -> usize {
+    match self {
+      Self::Lam(_) | Self::Slf(_) | Self::Dat | Self::Cse | Self::Ann(_) => 1,
+      Self::App | Self::Beta(_) | Self::All(..) | Self::Let(..) => 2,
+      _ => 0,
+    }
+  }
+
+  /// The tag of the rule and its data, which start its step's block
+  fn to_ipld(&self) -> Vec<Ipld> {
+    let nam = |nam: &Name| Ipld::String(nam.to_string());
+    match self {
+      Self::Var(n, idx) => vec![tag(0), nam(n), Ipld::Integer(*idx as i128)],
+      Self::Ref(n, def, ast) => {
+        vec![tag(1), nam(n), Ipld::Link(*def), Ipld::Link(*ast)]
+      }
+      Self::Rec => vec![tag(2)],
+      Self::Typ(lvl) => vec![tag(3), lvl.to_ipld()],
+      Self::Lit(lit) => vec![tag(4), lit.to_ipld()],
+      Self::LTy(lty) => vec![tag(5), lty.to_ipld()],
+      Self::Opr(opr) => vec![tag(6), opr.to_ipld()],
+      Self::Lam(n) => vec![tag(7), nam(n)],
+      Self::App => vec![tag(8)],
+      Self::Beta(n) => vec![tag(9), nam(n)],
+      Self::All(imp, uses, n) => {
+        vec![tag(10), Ipld::Bool(*imp), uses.to_ipld(), nam(n)]
+      }
+      Self::Slf(n) => vec![tag(11), nam(n)],
+      Self::Dat => vec![tag(12)],
+      Self::Cse => vec![tag(13)],
+      Self::Ann(typ) => {
+        let (anon, meta) = embed(typ);
+        vec![tag(14), anon, meta]
+      }
+      Self::Let(rec, uses, n, typ) => {
+        let (anon, meta) = embed(typ);
+        vec![tag(15), Ipld::Bool(*rec), uses.to_ipld(), nam(n), anon, meta]
+      }
+    }
+  }
+
+  fn from_ipld(tag: i128, data: &[Ipld]) -> Result<Self, IpldError> {
+    let nam = |nam: &String| Name::from(nam.as_str());
+    match (tag, data) {
+      (0, [Ipld::String(n), Ipld::Integer(idx)]) => {
+        let idx: u64 = (*idx).try_into().map_err(IpldError::U64)?;
+        Ok(Self::Var(nam(n), idx))
+      }
+      (1, [Ipld::String(n), Ipld::Link(def), Ipld::Link(ast)]) => {
+        Ok(Self::Ref(nam(n), *def, *ast))
+      }
+      (2, []) => Ok(Self::Rec),
+      (3, [lvl]) => Ok(Self::Typ(Level::from_ipld(lvl)?)),
+      (4, [lit]) => Ok(Self::Lit(Literal::from_ipld(lit)?)),
+      (5, [lty]) => Ok(Self::LTy(LitType::from_ipld(lty)?)),
+      (6, [opr]) => Ok(Self::Opr(Op::from_ipld(opr)?)),
+      (7, [Ipld::String(n)]) => Ok(Self::Lam(nam(n))),
+      (8, []) => Ok(Self::App),
+      (9, [Ipld::String(n)]) => Ok(Self::Beta(nam(n))),
+      (10, [Ipld::Bool(imp), uses, Ipld::String(n)]) => {
+        Ok(Self::All(*imp, Uses::from_ipld(uses)?, nam(n)))
+      }
+      (11, [Ipld::String(n)]) => Ok(Self::Slf(nam(n))),
+      (12, []) => Ok(Self::Dat),
+      (13, []) => Ok(Self::Cse),
+      (14, [anon, meta]) => Ok(Self::Ann(unembed(anon, meta)?)),
+      (15, [Ipld::Bool(rec), uses, Ipld::String(n), anon, meta]) => {
+        let uses = Uses::from_ipld(uses)?;
+        Ok(Self::Let(*rec, uses, nam(n), unembed(anon, meta)?))
+      }
+      _ => {
+        let mut xs = vec![Ipld::Integer(tag)];
+        xs.extend_from_slice(data);
+        Err(IpldError::Certificate(Ipld::List(xs)))
+      }
+    }
+  }
+}
+
+fn tag(tag: i128) -> Ipld { Ipld::Integer(tag) }
+
+fn embed(term: &Term) -> (Ipld, Ipld) {
+  let (anon, meta) = term.embed();
+  (anon.to_ipld(), meta.to_ipld())
+}
+
+fn unembed(anon: &Ipld, meta: &Ipld) -> Result<Term, IpldError> {
+  Term::unembed(&Anon::from_ipld(anon)?, &Meta::from_ipld(meta)?)
+    .map_err(|_| IpldError::Certificate(Ipld::List(vec![anon.clone()])))
+}
+
+impl Step {
+  /// The term the step types, rebuilt from its rule and premises
+  pub fn term(&self) -> Term {
+    let pos = Pos::None;
+    let sub = |i: usize| self.premises[i].term();
+    match &self.rule {
+      Rule::Var(nam, idx) => Term::Var(pos, nam.clone(), *idx),
+      Rule::Ref(nam, def, ast) => Term::Ref(pos, nam.clone(), *def, *ast),
+      Rule::Rec => Term::Rec(pos),
+      Rule::Typ(lvl) => Term::Typ(pos, lvl.clone()),
+      Rule::Lit(lit) => Term::Lit(pos, lit.clone()),
+      Rule::LTy(lty) => Term::LTy(pos, *lty),
+      Rule::Opr(opr) => Term::Opr(pos, opr.clone()),
+      Rule::Lam(nam) => Term::Lam(pos, nam.clone(), Box::new(sub(0))),
+      Rule::App => Term::App(pos, Box::new((sub(0), sub(1)))),
+      Rule::Beta(nam) => {
+        let fun = Term::Lam(pos, nam.clone(), Box::new(sub(1)));
+        Term::App(pos, Box::new((fun, sub(0))))
+      }
+      Rule::All(imp, uses, nam) => {
+        Term::All(pos, *imp, *uses, nam.clone(), Box::new((sub(0), sub(1))))
+      }
+      Rule::Slf(nam) => Term::Slf(pos, nam.clone(), Box::new(sub(0))),
+      Rule::Dat => Term::Dat(pos, Box::new(sub(0))),
+      Rule::Cse => Term::Cse(pos, Box::new(sub(0))),
+      Rule::Ann(typ) => Term::Ann(pos, Box::new((typ.clone(), sub(0)))),
+      Rule::Let(rec, uses, nam, typ) => Term::Let(
+        pos,
+        *rec,
+        *uses,
+        nam.clone(),
+        Box::new((typ.clone(), sub(0), sub(1))),
+      ),
+    }
+  }
+
+  /// Adds the step and those under it to `blocks`, one block each, which
+  /// link to the blocks of their premises. Returns the CID of the step's.
+  pub fn to_blocks(&self, blocks: &mut BTreeMap<Cid, Ipld>) -> Cid {
+    let links =
+      self.premises.iter().map(|x| Ipld::Link(x.to_blocks(blocks))).collect();
+    let (anon, meta) = embed(&self.typ);
+    let mut xs = self.rule.to_ipld();
+    xs.extend(vec![anon, meta, Ipld::List(links)]);
+    let ipld = Ipld::List(xs);
+    let res = cid(&ipld);
+    blocks.insert(res, ipld);
+    res
+  }
+
+  /// Reads back a step from its block and those it links to, as found by
+  /// `get`
+  pub fn from_blocks(
+    link: &Cid,
+    get: &mut dyn FnMut(&Cid) -> Option<Ipld>,
+  ) -> Result<Self, IpldError> {
+    let ipld =
+      get(link).ok_or_else(|| IpldError::Certificate(Ipld::Link(*link)))?;
+    let err = || IpldError::Certificate(ipld.to_owned());
+    match &ipld {
+      Ipld::List(xs) => match xs.as_slice() {
+        [Ipld::Integer(tag), data @ .., anon, meta, Ipld::List(links)] => {
+          let rule = Rule::from_ipld(*tag, data)?;
+          if links.len() != rule.arity() {
+            return Err(err());
+          }
+          let mut premises = Vec::new();
+          for link in links {
+            match link {
+              Ipld::Link(link) => premises.push(Step::from_blocks(link, get)?),
+              _ => return Err(err()),
+            }
+          }
+          Ok(Step { rule, typ: unembed(anon, meta)?, premises })
+        }
+        _ => Err(err()),
+      },
+      _ => Err(err()),
+    }
+  }
+}
+
+impl Certificate {
+  /// Encodes the certificate as blocks, returning the CID of the one at its
+  /// root along with all of them
+  pub fn to_blocks(&self) -> (Cid, BTreeMap<Cid, Ipld>) {
+    let mut blocks = BTreeMap::new();
+    let root = self.root.to_blocks(&mut blocks);
+    let ipld = Ipld::List(vec![
+      Ipld::String(self.name.to_string()),
+      Ipld::Link(self.def),
+      Ipld::Link(root),
+    ]);
+    let res = cid(&ipld);
+    blocks.insert(res, ipld);
+    (res, blocks)
+  }
+
+  pub fn cid(&self) -> Cid { self.to_blocks().0 }
+
+  /// Decodes a certificate from the block at `link`, fetching the blocks it
+  /// links to with `get`
+  pub fn from_blocks(
+    link: &Cid,
+    get: &mut dyn FnMut(&Cid) -> Option<Ipld>,
+  ) -> Result<Self, IpldError> {
+    let ipld =
+      get(link).ok_or_else(|| IpldError::Certificate(Ipld::Link(*link)))?;
+    match &ipld {
+      Ipld::List(xs) => match xs.as_slice() {
+        [Ipld::String(name), Ipld::Link(def), Ipld::Link(root)] => {
+          Ok(Certificate {
+            name: Name::from(name.as_str()),
+            def: *def,
+            root: Step::from_blocks(root, get)?,
+          })
+        }
+        _ => Err(IpldError::Certificate(ipld.to_owned())),
+      },
+      _ => Err(IpldError::Certificate(ipld.to_owned())),
+    }
+  }
+}
+
+/// Checks a def and, once it's found well typed, derives its type again as a
+/// certificate. The derivation records every rule the checker relies on but
+/// the quantities of variables.
+pub fn certify_def(
+  defs: Rc<Defs>,
+  name: &str,
+  should_count: bool,
+) -> Result<Certificate, CheckError> {
+  check_def(defs.clone(), name, should_count)?;
+  let name = Name::from(name);
+  let def = defs.get(&name).unwrap();
+  let env = Env {
+    defs: &defs,
+    rec: Some((name.clone(), def.def_cid, def.ast_cid)),
+    typ: &def.typ_,
+  };
+  // The derivation holds terms read back from the DAGs, not the DAGs
+  let root = unsafe {
+    with_arena(|| env.derive(&mut Vec::new(), &def.term, Some(&def.typ_)))?
+  };
+  Ok(Certificate { name, def: def.def_cid, root })
+}
+
+/// What deriving the type of a def and verifying it have in common: the defs
+/// it refers to, itself as a recursive reference, and its type
+struct Env<'a> {
+  defs: &'a Defs,
+  rec: Option<(Name, Cid, Cid)>,
+  typ: &'a Term,
+}
+
+fn conclude(
+  rule: Rule,
+  typ: Term,
+  premises: Vec<Step>,
+) -> Result<Step, CheckError> {
+  Ok(Step { rule, typ, premises })
+}
+
+/// A term the certifier can't derive a type for, though it was checked
+fn underivable(term: &Term, why: &str) -> CheckError {
+  CheckError::GenericError(term.pos(), ErrCtx::new(), why.to_owned())
+}
+
+impl<'a> Env<'a> {
+  /// Derives the type of a term whose context holds the types in `ctx`,
+  /// checking it against `expected` only if it's a λ or data, which have no
+  /// type of their own
+  fn derive(
+    &self,
+    ctx: &mut Vec<Term>,
+    term: &Term,
+    expected: Option<&Term>,
+  ) -> Result<Step, CheckError> {
+    let dep = ctx.len() as u64;
+    match term {
+      Term::Var(_, nam, idx) => {
+        let typ = ctx
+          .iter()
+          .rev()
+          .nth(*idx as usize)
+          .ok_or_else(|| underivable(term, "unbound variable"))?;
+        let typ = typ.clone().shift(*idx as i64 + 1, Some(0));
+        conclude(Rule::Var(nam.clone(), *idx), typ, vec![])
+      }
+      Term::Ref(pos, nam, def, ast) => {
+        // Levels are made fresh, as at any reference in the checker
+        let typ = infer_ref(self.defs, pos, nam, def)?;
+        let res = typ.to_term(false);
+        typ.free();
+        conclude(Rule::Ref(nam.clone(), *def, *ast), res, vec![])
+      }
+      Term::Rec(_) => conclude(Rule::Rec, self.typ.clone(), vec![]),
+      Term::Typ(_, lvl) => conclude(
+        Rule::Typ(lvl.clone()),
+        Term::Typ(Pos::None, lvl.succ()),
+        vec![],
+      ),
+      Term::Lit(_, lit) => {
+        conclude(Rule::Lit(lit.clone()), infer_lit(lit.clone()), vec![])
+      }
+      Term::LTy(_, lty) => conclude(Rule::LTy(*lty), yatima!("Type"), vec![]),
+      Term::Opr(pos, opr) => {
+        let typ = self
+          .opr_type(opr)
+          .ok_or_else(|| CheckError::UndefinedReference(*pos, opr.symbol()))?;
+        conclude(Rule::Opr(opr.clone()), typ, vec![])
+      }
+      Term::Lam(_, nam, bod) => {
+        let typ =
+          expected.ok_or_else(|| underivable(term, "λ without a type"))?;
+        match self.whnf(typ, dep) {
+          Term::All(_, _, _, _, dom_img) => {
+            let (dom, img) = *dom_img;
+            ctx.push(dom);
+            let bod = self.derive(ctx, bod, Some(&img));
+            ctx.pop();
+            conclude(Rule::Lam(nam.clone()), typ.clone(), vec![bod?])
+          }
+          _ => Err(underivable(term, "λ whose type isn't a ∀")),
+        }
+      }
+      Term::Dat(_, bod) => {
+        let typ =
+          expected.ok_or_else(|| underivable(term, "data without a type"))?;
+        match self.whnf(typ, dep) {
+          Term::Slf(_, _, slf_bod) => {
+            let bod = self.derive(ctx, bod, Some(&subst(*slf_bod, 0, term)))?;
+            conclude(Rule::Dat, typ.clone(), vec![bod])
+          }
+          _ => Err(underivable(term, "data whose type isn't a self type")),
+        }
+      }
+      Term::App(_, fun_arg) => match fun_arg.as_ref() {
+        (Term::Lam(_, nam, bod), arg_term) => {
+          let arg = self.derive(ctx, arg_term, None)?;
+          ctx.push(arg.typ.clone());
+          let expected = expected.map(|x| x.clone().shift(1, Some(0)));
+          let bod = self.derive(ctx, bod, expected.as_ref());
+          ctx.pop();
+          let bod = bod?;
+          let typ = subst(bod.typ.clone(), 0, arg_term);
+          conclude(Rule::Beta(nam.clone()), typ, vec![arg, bod])
+        }
+        (fun_term, arg_term) => {
+          let fun = self.derive(ctx, fun_term, None)?;
+          let all = self.whnf(&fun.typ, dep);
+          let dom = match &all {
+            Term::All(_, _, _, _, dom_img) => dom_img.0.clone(),
+            _ => {
+              return Err(underivable(term, "function whose type isn't a ∀"))
+            }
+          };
+          let arg = self.derive(ctx, arg_term, Some(&dom))?;
+          let (_, img) = self.instantiate(all, &arg.typ, dep).unwrap();
+          conclude(Rule::App, subst(img, 0, arg_term), vec![fun, arg])
+        }
+      },
+      Term::Cse(_, exp_term) => {
+        let exp = self.derive(ctx, exp_term, None)?;
+        let typ = match self.whnf(&exp.typ, dep) {
+          Term::Slf(_, _, bod) => subst(*bod, 0, exp_term),
+          Term::LTy(_, lty) => {
+            lty.induction((**exp_term).clone()).ok_or_else(|| {
+              underivable(term, "case of a non-inductive literal")
+            })?
+          }
+          _ => {
+            return Err(underivable(term, "case of a term without self type"))
+          }
+        };
+        conclude(Rule::Cse, typ, vec![exp])
+      }
+      Term::All(_, imp, uses, nam, dom_img) => {
+        let (dom_term, img_term) = dom_img.as_ref();
+        let dom = self.derive(ctx, dom_term, None)?;
+        ctx.push(dom_term.clone());
+        let img = self.derive(ctx, img_term, None);
+        ctx.pop();
+        let img = img?;
+        let lvl = match (self.whnf(&dom.typ, dep), self.whnf(&img.typ, dep + 1))
+        {
+          (Term::Typ(_, dom_lvl), Term::Typ(_, img_lvl)) => {
+            Level::imax(&dom_lvl, &img_lvl)
+          }
+          _ => {
+            return Err(underivable(term, "∀ over a term that isn't a type"))
+          }
+        };
+        let rule = Rule::All(*imp, *uses, nam.clone());
+        conclude(rule, Term::Typ(Pos::None, lvl), vec![dom, img])
+      }
+      Term::Slf(_, nam, bod) => {
+        // The variable of a self type stands for a term of that type
+        ctx.push(term.clone());
+        let bod = self.derive(ctx, bod, None);
+        ctx.pop();
+        let bod = bod?;
+        match self.whnf(&bod.typ, dep + 1) {
+          Term::Typ(_, lvl) => {
+            let typ = Term::Typ(Pos::None, lvl);
+            conclude(Rule::Slf(nam.clone()), typ, vec![bod])
+          }
+          _ => Err(underivable(term, "self type of a term that isn't a type")),
+        }
+      }
+      Term::Ann(_, typ_exp) => {
+        let (typ, exp) = typ_exp.as_ref();
+        let exp = self.derive(ctx, exp, Some(typ))?;
+        conclude(Rule::Ann(typ.clone()), typ.clone(), vec![exp])
+      }
+      Term::Let(_, rec, uses, nam, typ_exp_bod) => {
+        let (typ, exp_term, bod_term) = typ_exp_bod.as_ref();
+        let expected = expected.map(|x| x.clone().shift(1, Some(0)));
+        let (exp, bod) = if *rec {
+          ctx.push(typ.clone());
+          let exp_typ = typ.clone().shift(1, Some(0));
+          let exp = self.derive(ctx, exp_term, Some(&exp_typ));
+          let bod = self.derive(ctx, bod_term, expected.as_ref());
+          ctx.pop();
+          (exp?, bod?)
+        }
+        else {
+          let exp = self.derive(ctx, exp_term, Some(typ))?;
+          ctx.push(typ.clone());
+          let bod = self.derive(ctx, bod_term, expected.as_ref());
+          ctx.pop();
+          (exp, bod?)
+        };
+        let val =
+          if *rec { fix(*uses, nam, typ, exp_term) } else { exp_term.clone() };
+        let rule = Rule::Let(*rec, *uses, nam.clone(), typ.clone());
+        conclude(rule, subst(bod.typ.clone(), 0, &val), vec![exp, bod])
+      }
+    }
+  }
+
+  /// Builds a type DAG with `dep` variables in scope
+  fn dag(&self, term: &Term, dep: u64) -> DAG {
+    let root = alloc_val(DLL::singleton(ParentPtr::Root));
+    DAG::new(DAG::from_term_inner(
+      term,
+      dep,
+      BTreeMap::new(),
+      Some(root),
+      self.rec.clone(),
+    ))
+  }
+
+  /// Reads a DAG back as a term, keeping the indices of the variables bound
+  /// outside of it
+  fn read_back(dag: DAG, dep: u64) -> Term {
+    let mut map = BTreeMap::new();
+    for var in DAG::free_vars(dag.head) {
+      map.insert(var.as_ptr(), unsafe { var.as_ref().dep });
+    }
+    let res = DAG::dag_ptr_to_term(&dag.head, &mut map, dep, false);
+    dag.free();
+    res
+  }
+
+  fn whnf(&self, term: &Term, dep: u64) -> Term {
+    let mut dag = self.dag(term, dep);
+    dag.whnf(self.defs, false);
+    Self::read_back(dag, dep)
+  }
+
+  /// Applies `f` to every universe level in a term
+  fn map_levels(
+    &self,
+    term: &Term,
+    dep: u64,
+    f: &mut impl FnMut(&mut Level),
+  ) -> Term {
+    let dag = self.dag(term, dep);
+    for_each_level(dag.head, f);
+    Self::read_back(dag, dep)
+  }
+
+  /// The domain and image of a `∀`, where a universe domain at a fresh
+  /// level, as at a reference to a universe-polymorphic def, takes the level
+  /// of the type of the argument
+  fn instantiate(
+    &self,
+    all: Term,
+    arg_typ: &Term,
+    dep: u64,
+  ) -> Option<(Term, Term)> {
+    let sub = match &all {
+      Term::All(_, _, _, _, dom_img) => match &dom_img.0 {
+        Term::Typ(_, lvl) if lvl.vars.keys().any(Level::is_fresh) => {
+          match self.whnf(arg_typ, dep) {
+            Term::Typ(_, arg_lvl) => lvl.solve(&arg_lvl),
+            _ => None,
+          }
+        }
+        _ => None,
+      },
+      _ => return None,
+    };
+    let all = match sub {
+      Some((var, val)) => {
+        let mut sub = BTreeMap::new();
+        sub.insert(var, val);
+        self.map_levels(&all, dep, &mut |lvl| *lvl = lvl.subst(&sub))
+      }
+      None => all,
+    };
+    match all {
+      Term::All(_, _, _, _, dom_img) => Some(*dom_img),
+      _ => None,
+    }
+  }
+
+  fn opr_type(&self, opr: &Op) -> Option<Term> {
+    match self.defs.host.resolve(opr.clone()) {
+      Op::Host(HostRef { op: None, .. }) => None,
+      opr => Some(opr.type_of()),
+    }
+  }
+}
+
+/// The value a recursive let binds, as a term: the let itself, returning
+/// its variable
+fn fix(uses: Uses, nam: &Name, typ: &Term, exp: &Term) -> Term {
+  let var = Term::Var(Pos::None, nam.clone(), 0);
+  let typ_exp_bod = (typ.clone(), exp.clone(), var);
+  Term::Let(Pos::None, true, uses, nam.clone(), Box::new(typ_exp_bod))
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::{
+    verify::{
+      verify,
+      VerifyError,
+    },
+    *,
+  };
+  use crate::parse::{
+    package,
+    span::Span,
+    term::input_cid,
+  };
+
+  fn defs(src: &str) -> Rc<Defs> {
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    Rc::new(defs)
+  }
+
+  #[test]
+  fn test_certify_def() {
+    let src = "type Nat { Z, S (pred: Nat) }\n\
+               def add (m n: Nat): Nat = (case m) (λ _ => Nat) n (λ p => \
+               Nat.S (add p n))\n\
+               def id {A: Type{u}} (x: A): A = x\n\
+               def ty: Type{1} = id Type\n\
+               def num: #U64 = let x: #U64 = id 1u64; #U64.add x x";
+    let checked = defs(src);
+    for nam in ["Nat", "Nat.S", "add", "id", "ty", "num"].iter() {
+      let cert = certify_def(checked.clone(), nam, false).unwrap();
+      let (link, blocks) = cert.to_blocks();
+      let read =
+        Certificate::from_blocks(&link, &mut |cid| blocks.get(cid).cloned())
+          .unwrap();
+      assert_eq!(read, cert);
+      // Verified without the conversions the checker cached
+      assert_eq!(verify(&defs(src), &read), Ok(()), "{}", nam);
+    }
+  }
+
+  #[test]
+  fn test_verify_tampered() {
+    let src = "def two: #U64 = #U64.add 1u64 1u64";
+    let cert = certify_def(defs(src), "two", false).unwrap();
+    assert_eq!(verify(&defs(src), &cert), Ok(()));
+    let mut bad = cert.clone();
+    bad.root.typ = yatima!("#Text");
+    // The root application no longer concludes what its premises give
+    assert!(matches!(
+      verify(&defs(src), &bad),
+      Err(VerifyError::Invalid("app", ..))
+    ));
+    // The argument claimed to be text
+    let mut bad = cert.clone();
+    bad.root.premises[1].typ = yatima!("#Text");
+    assert!(matches!(
+      verify(&defs(src), &bad),
+      Err(VerifyError::Invalid("lit", ..))
+    ));
+    // A derivation for another term
+    let mut bad = cert.clone();
+    bad.root.premises[1].rule = Rule::Lit(Literal::U64(2));
+    assert!(matches!(
+      verify(&defs(src), &bad),
+      Err(VerifyError::WrongTerm(..))
+    ));
+    let mut bad = cert;
+    bad.root.premises.pop();
+    assert_eq!(verify(&defs(src), &bad), Err(VerifyError::Arity("app", 1)));
+  }
+}
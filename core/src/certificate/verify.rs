@@ -0,0 +1,337 @@
+use super::{
+  fix,
+  Certificate,
+  Env,
+  Rule,
+  Step,
+};
+
+use crate::{
+  arena::with_arena,
+  check::{
+    cumulative,
+    equal,
+    infer_lit,
+  },
+  defs,
+  defs::Defs,
+  level::Level,
+  name::Name,
+  parse,
+  position::Pos,
+  term::Term,
+  yatima,
+};
+
+use sp_cid::Cid;
+
+use sp_std::{
+  boxed::Box,
+  collections::btree_map::BTreeMap,
+  fmt,
+  vec::Vec,
+};
+
+/// Reasons a certificate doesn't hold
+#[derive(PartialEq, Clone, Debug)]
+pub enum VerifyError {
+  /// The def the certificate is for isn't among the defs given
+  UndefinedDef(Cid),
+  UndefinedReference(Name, Cid),
+  /// The derivation types another term than the def, by the CIDs of the
+  /// def's term and of the derived one
+  WrongTerm(Cid, Cid),
+  /// A rule applied to the wrong number of premises
+  Arity(&'static str, usize),
+  /// A rule whose conclusion doesn't follow, with the term it types and the
+  /// condition that failed
+  Invalid(&'static str, Term, &'static str),
+}
+
+impl fmt::Display for VerifyError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UndefinedDef(cid) => write!(f, "No def with CID {}", cid),
+      Self::UndefinedReference(nam, cid) => {
+        write!(f, "Undefined reference {} to {}", nam, cid)
+      }
+      Self::WrongTerm(def, derived) => write!(
+        f,
+        "The certificate derives a type for {}, not for the def's term {}",
+        derived, def
+      ),
+      Self::Arity(rule, n) => write!(f, "Rule {} given {} premises", rule, n),
+      Self::Invalid(rule, term, why) => {
+        write!(f, "Rule {} doesn't hold for {}: {}", rule, term, why)
+      }
+    }
+  }
+}
+
+/// Checks a certificate against the defs it was made with. Each step is
+/// checked on its own, by comparing the type it concludes with the one its
+/// rule gives from the types of its premises, up to definitional equality.
+/// No type is inferred, so only evaluation is trusted, not the checker.
+/// The defs shouldn't come with the conversions the checker cached, for the
+/// verifier to compare terms by itself. Quantities aren't in the
+/// derivation, and aren't checked.
+pub fn verify(defs: &Defs, cert: &Certificate) -> Result<(), VerifyError> {
+  let def =
+    defs.defs.get(&cert.def).ok_or(VerifyError::UndefinedDef(cert.def))?;
+  arity(&cert.root)?;
+  let derived = cert.root.term().embed().0.cid();
+  if derived != def.ast_cid {
+    return Err(VerifyError::WrongTerm(def.ast_cid, derived));
+  }
+  let env = Env {
+    defs,
+    rec: Some((cert.name.clone(), cert.def, def.ast_cid)),
+    typ: &def.typ_,
+  };
+  // Verifying only keeps the terms it reads back from the DAGs it builds
+  unsafe {
+    with_arena(|| {
+      env.verify(&mut Vec::new(), &cert.root)?;
+      if env.conv(&def.typ_, &cert.root.typ, 0) {
+        Ok(())
+      }
+      else {
+        Err(VerifyError::Invalid(
+          cert.root.rule.name(),
+          def.term.clone(),
+          "the type derived isn't the def's",
+        ))
+      }
+    })
+  }
+}
+
+fn arity(step: &Step) -> Result<(), VerifyError> {
+  if step.premises.len() != step.rule.arity() {
+    return Err(VerifyError::Arity(step.rule.name(), step.premises.len()));
+  }
+  step.premises.iter().try_for_each(arity)
+}
+
+/// The application of `λ nam => bod` to `arg`, standing for the
+/// substitution of `arg` in `bod`, which conversion carries out
+fn redex(nam: &Name, bod: &Term, arg: Term) -> Term {
+  let fun = Term::Lam(Pos::None, nam.clone(), Box::new(bod.clone()));
+  Term::App(Pos::None, Box::new((fun, arg)))
+}
+
+impl<'a> Env<'a> {
+  /// Verifies a step whose context holds the types in `ctx`
+  fn verify(
+    &self,
+    ctx: &mut Vec<Term>,
+    step: &Step,
+  ) -> Result<(), VerifyError> {
+    let dep = ctx.len() as u64;
+    let invalid =
+      |why| VerifyError::Invalid(step.rule.name(), step.term(), why);
+    let prem = &step.premises;
+    match &step.rule {
+      Rule::Var(_, idx) => {
+        let typ = ctx
+          .iter()
+          .rev()
+          .nth(*idx as usize)
+          .ok_or_else(|| invalid("unbound variable"))?;
+        self.concludes(step, &typ.clone().shift(*idx as i64 + 1, Some(0)), dep)
+      }
+      Rule::Ref(nam, def, _) => {
+        let def =
+          self.defs.defs.get(def).ok_or_else(|| {
+            VerifyError::UndefinedReference(nam.clone(), *def)
+          })?;
+        // The checker gives each reference fresh levels, named after those
+        // of the def
+        let typ = self.map_levels(&step.typ, dep, &mut |lvl| {
+          let mut sub = BTreeMap::new();
+          for v in lvl.vars.keys().filter(|v| Level::is_fresh(v)) {
+            let nam = v[1..].rsplitn(2, '.').last().unwrap_or_default();
+            sub.insert(v.clone(), Level::var(Name::from(nam)));
+          }
+          *lvl = lvl.subst(&sub);
+        });
+        if self.conv(&typ, &def.typ_, dep) {
+          Ok(())
+        }
+        else {
+          Err(invalid("not the type of the def"))
+        }
+      }
+      Rule::Rec => self.concludes(step, self.typ, dep),
+      Rule::Typ(lvl) => {
+        self.concludes(step, &Term::Typ(Pos::None, lvl.succ()), dep)
+      }
+      Rule::Lit(lit) => self.concludes(step, &infer_lit(lit.clone()), dep),
+      Rule::LTy(_) => self.concludes(step, &yatima!("Type"), dep),
+      Rule::Opr(opr) => {
+        let typ =
+          self.opr_type(opr).ok_or_else(|| invalid("unknown operation"))?;
+        self.concludes(step, &typ, dep)
+      }
+      Rule::Lam(_) => match self.whnf(&step.typ, dep) {
+        Term::All(_, _, _, _, dom_img) => {
+          let (dom, img) = *dom_img;
+          ctx.push(dom);
+          let res = self.verify(ctx, &prem[0]);
+          ctx.pop();
+          res?;
+          self.premise(step, &img, &prem[0], dep + 1)
+        }
+        _ => Err(invalid("the type isn't a ∀")),
+      },
+      Rule::Dat => match self.whnf(&step.typ, dep) {
+        Term::Slf(_, nam, bod) => {
+          self.verify(ctx, &prem[0])?;
+          self.premise(step, &redex(&nam, &bod, step.term()), &prem[0], dep)
+        }
+        _ => Err(invalid("the type isn't a self type")),
+      },
+      Rule::Beta(nam) => {
+        let (arg, bod) = (&prem[0], &prem[1]);
+        self.verify(ctx, arg)?;
+        ctx.push(arg.typ.clone());
+        let res = self.verify(ctx, bod);
+        ctx.pop();
+        res?;
+        self.concludes(step, &redex(nam, &bod.typ, arg.term()), dep)
+      }
+      Rule::App => {
+        let (fun, arg) = (&prem[0], &prem[1]);
+        self.verify(ctx, fun)?;
+        self.verify(ctx, arg)?;
+        let all = self.whnf(&fun.typ, dep);
+        let (dom, img) = self
+          .instantiate(all, &arg.typ, dep)
+          .ok_or_else(|| invalid("the function's type isn't a ∀"))?;
+        self.premise(step, &dom, arg, dep)?;
+        self.concludes(step, &redex(&Name::from("_"), &img, arg.term()), dep)
+      }
+      Rule::All(..) => {
+        let (dom, img) = (&prem[0], &prem[1]);
+        self.verify(ctx, dom)?;
+        ctx.push(dom.term());
+        let res = self.verify(ctx, img);
+        ctx.pop();
+        res?;
+        match (self.whnf(&dom.typ, dep), self.whnf(&img.typ, dep + 1)) {
+          (Term::Typ(_, dom_lvl), Term::Typ(_, img_lvl)) => {
+            let lvl = Level::imax(&dom_lvl, &img_lvl);
+            self.concludes(step, &Term::Typ(Pos::None, lvl), dep)
+          }
+          _ => Err(invalid("the domain or image isn't a type")),
+        }
+      }
+      Rule::Slf(_) => {
+        ctx.push(step.term());
+        let res = self.verify(ctx, &prem[0]);
+        ctx.pop();
+        res?;
+        match self.whnf(&prem[0].typ, dep + 1) {
+          Term::Typ(_, lvl) => {
+            self.concludes(step, &Term::Typ(Pos::None, lvl), dep)
+          }
+          _ => Err(invalid("the body isn't a type")),
+        }
+      }
+      Rule::Cse => {
+        let exp = &prem[0];
+        self.verify(ctx, exp)?;
+        let typ = match self.whnf(&exp.typ, dep) {
+          Term::Slf(_, nam, bod) => redex(&nam, &bod, exp.term()),
+          Term::LTy(_, lty) => lty
+            .induction(exp.term())
+            .ok_or_else(|| invalid("the literal type isn't inductive"))?,
+          _ => {
+            return Err(invalid("the type of the scrutinee isn't a self type"))
+          }
+        };
+        self.concludes(step, &typ, dep)
+      }
+      Rule::Ann(typ) => {
+        self.verify(ctx, &prem[0])?;
+        self.premise(step, typ, &prem[0], dep)?;
+        self.concludes(step, typ, dep)
+      }
+      Rule::Let(rec, uses, nam, typ) => {
+        let (exp, bod) = (&prem[0], &prem[1]);
+        if *rec {
+          ctx.push(typ.clone());
+          let exp_typ = typ.clone().shift(1, Some(0));
+          let res = self
+            .verify(ctx, exp)
+            .and_then(|_| self.premise(step, &exp_typ, exp, dep + 1))
+            .and_then(|_| self.verify(ctx, bod));
+          ctx.pop();
+          res?;
+        }
+        else {
+          self.verify(ctx, exp)?;
+          self.premise(step, typ, exp, dep)?;
+          ctx.push(typ.clone());
+          let res = self.verify(ctx, bod);
+          ctx.pop();
+          res?;
+        }
+        let val =
+          if *rec { fix(*uses, nam, typ, &exp.term()) } else { exp.term() };
+        self.concludes(step, &redex(nam, &bod.typ, val), dep)
+      }
+    }
+  }
+
+  /// Whether a term of type `detected` also has type `expected`
+  fn conv(&self, expected: &Term, detected: &Term, dep: u64) -> bool {
+    let mut a = self.dag(expected, dep);
+    let mut b = self.dag(detected, dep);
+    let res =
+      equal(self.defs, &mut a, &mut b, dep, false) || cumulative(&a, &b);
+    a.free();
+    b.free();
+    res
+  }
+
+  /// Checks that the type a step concludes follows from `typ`, the one its
+  /// rule gives
+  fn concludes(
+    &self,
+    step: &Step,
+    typ: &Term,
+    dep: u64,
+  ) -> Result<(), VerifyError> {
+    if self.conv(&step.typ, typ, dep) {
+      Ok(())
+    }
+    else {
+      Err(VerifyError::Invalid(
+        step.rule.name(),
+        step.term(),
+        "the conclusion doesn't follow from the premises",
+      ))
+    }
+  }
+
+  /// Checks that a premise has the type its step's rule asks of it
+  fn premise(
+    &self,
+    step: &Step,
+    expected: &Term,
+    prem: &Step,
+    dep: u64,
+  ) -> Result<(), VerifyError> {
+    if self.conv(expected, &prem.typ, dep) {
+      Ok(())
+    }
+    else {
+      Err(VerifyError::Invalid(
+        step.rule.name(),
+        step.term(),
+        "a premise has the wrong type",
+      ))
+    }
+  }
+}
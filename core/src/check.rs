@@ -1,3 +1,4 @@
+pub mod conv;
 pub mod ctx;
 pub mod error;
 
@@ -5,15 +6,21 @@ use ctx::*;
 use error::CheckError;
 
 use crate::{
+  arena::with_arena,
   dag::*,
   defs,
   defs::Defs,
   dll::*,
+  level::Level,
   literal::Literal,
   name::Name,
   parse,
   position::Pos,
-  term::Term,
+  prim::host::HostRef,
+  term::{
+    Op,
+    Term,
+  },
   uses::*,
   yatima,
 };
@@ -33,13 +40,26 @@ use sp_std::{
   rc::Rc,
 };
 
+/// The state threaded through typechecking the parts of a term: the
+/// recursive definition being checked, the definitions it may refer to, the
+/// typing context and whether uses are counted
+pub struct Env<'a> {
+  pub rec: &'a Option<(Name, Cid, Cid)>,
+  pub defs: &'a Defs,
+  pub ctx: &'a mut Ctx,
+  pub should_count: bool,
+}
+
 /// Generates a content id for a DAG pointer's anonymous term
 pub fn hash(dag: DAGPtr, dep: u64) -> Cid {
   let mut map = BTreeMap::new();
   DAG::dag_ptr_to_term(&dag, &mut map, dep, true).embed().0.cid()
 }
 
-/// Lazily checks if two DAGs are beta equivalent. 
+/// Lazily checks if two DAGs are beta equivalent. Terms with the same CID
+/// are equal without reducing either, and the outcome is cached in
+/// `defs.conv`, so comparing the same large types again is a lookup. Defs
+/// that don't unfold, per their attributes, are equal only to themselves.
 pub fn equal(
   defs: &Defs,
   a: &mut DAG,
@@ -47,22 +67,58 @@ pub fn equal(
   dep: u64,
   should_count: bool,
 ) -> bool {
-  a.whnf(defs, should_count);
-  b.whnf(defs, should_count);
-  let mut triples = vec![(a.head, b.head, dep)];
+  let hash_a = hash(a.head, dep);
+  let hash_b = hash(b.head, dep);
+  if hash_a == hash_b {
+    return true;
+  }
+  if let Some(eq) = defs.conv.get(&hash_a, &hash_b) {
+    return eq;
+  }
+  a.whnf_conv(defs, should_count);
+  b.whnf_conv(defs, should_count);
   let mut set: BTreeSet<(Cid, Cid)> = BTreeSet::new();
+  let eq = congruent(defs, a.head, b.head, dep, &mut set, should_count);
+  defs.conv.insert(hash_a, hash_b, eq);
+  // Every pair compared along the way was found equal too
+  if eq {
+    for (a, b) in set {
+      defs.conv.insert(a, b, true);
+    }
+  }
+  eq
+}
+
+/// Compares two DAGs in weak head normal form, and their subterms in turn,
+/// recording in `set` the pairs assumed equal while they're compared
+fn congruent(
+  defs: &Defs,
+  a: DAGPtr,
+  b: DAGPtr,
+  dep: u64,
+  set: &mut BTreeSet<(Cid, Cid)>,
+  should_count: bool,
+) -> bool {
+  let mut triples = vec![(a, b, dep)];
   while let Some((a, b, dep)) = triples.pop() {
+    if a == b {
+      continue;
+    }
     let mut a = DAG::new(a);
     let mut b = DAG::new(b);
-    a.whnf(defs, should_count);
-    b.whnf(defs, should_count);
+    a.whnf_conv(defs, should_count);
+    b.whnf_conv(defs, should_count);
     let hash_a = hash(a.head, dep);
     let hash_b = hash(b.head, dep);
     let eq = hash_a == hash_b
       || set.contains(&(hash_a, hash_b))
       || set.contains(&(hash_b, hash_a));
+    let cached = if eq { None } else { defs.conv.get(&hash_a, &hash_b) };
+    if cached == Some(false) {
+      return false;
+    }
     set.insert((hash_a, hash_b));
-    if !eq {
+    if !eq && cached.is_none() {
       match (a.head, b.head) {
         (DAGPtr::Lam(a_link), DAGPtr::Lam(b_link)) => unsafe {
           let Lam { bod: a_bod, .. } = *a_link.as_ptr();
@@ -127,29 +183,212 @@ pub fn check(
     Term::Dat(pos, bod) => {
       check_dat(rec, defs, ctx, uses, term, typ, pos, &**bod, should_count)
     }
-    _ => {
-      let depth = ctx.len();
-      // TODO Should we clone ctx?
-      let mut detected_typ = infer(rec, defs, ctx, uses, term, should_count)?;
-      if equal(defs, typ, &mut detected_typ, depth as u64, should_count) {
-        detected_typ.free();
-        Ok(())
+    // Lets and redexes whose bodies can't be inferred push the type inward
+    Term::Let(pos, false, exp_uses, nam, triple) if checks_only(&triple.2) => {
+      let env = &mut Env { rec, defs, ctx, should_count };
+      check_let(env, uses, pos, *exp_uses, nam, triple, typ)
+    }
+    Term::App(_, fun_arg) => match &fun_arg.0 {
+      Term::Lam(_, nam, bod) if checks_only(bod) => {
+        let env = &mut Env { rec, defs, ctx, should_count };
+        check_redex(env, uses, nam, bod, &fun_arg.1, typ)
       }
-      else {
-        let expected = typ.to_term(false);
-        let detected = detected_typ.to_term(false);
-        detected_typ.free();
-        Err(CheckError::TypeMismatch(
-          term.pos(),
-          error_context(ctx),
-          expected,
-          detected,
-        ))
+      _ => check_inferred(rec, defs, ctx, uses, term, typ, should_count),
+    },
+    _ => check_inferred(rec, defs, ctx, uses, term, typ, should_count),
+  }
+}
+
+/// Typechecks a term by inferring its type and comparing it with the expected
+/// one
+pub fn check_inferred(
+  rec: &Option<(Name, Cid, Cid)>,
+  defs: &Defs,
+  ctx: &mut Ctx,
+  uses: Uses,
+  term: &Term,
+  typ: &mut DAG,
+  should_count: bool,
+) -> Result<(), CheckError> {
+  let depth = ctx.len();
+  // TODO Should we clone ctx?
+  let mut detected_typ = infer(rec, defs, ctx, uses, term, should_count)?;
+  if equal(defs, typ, &mut detected_typ, depth as u64, should_count)
+    || cumulative(typ, &detected_typ)
+  {
+    detected_typ.free();
+    Ok(())
+  }
+  else {
+    let expected = typ.to_term(false);
+    let detected = detected_typ.to_term(false);
+    detected_typ.free();
+    Err(CheckError::TypeMismatch(
+      term.pos(),
+      error_context(ctx),
+      expected,
+      detected,
+    ))
+  }
+}
+
+/// Whether `detected` and `expected` are universes, with the detected one
+/// contained in the expected one. Universes are cumulative, so a type in
+/// `Type` is also in `Type{1}`. Both are in weak head normal form after
+/// `equal`.
+pub(crate) fn cumulative(expected: &DAG, detected: &DAG) -> bool {
+  match (expected.head, detected.head) {
+    (DAGPtr::Typ(a), DAGPtr::Typ(b)) => unsafe {
+      (*b.as_ptr()).lvl.leq(&(*a.as_ptr()).lvl)
+    },
+    _ => false,
+  }
+}
+
+/// Applies `f` to the level of every universe in a DAG
+pub(crate) fn for_each_level(node: DAGPtr, f: &mut impl FnMut(&mut Level)) {
+  let mut seen = BTreeSet::new();
+  let mut stack = vec![node];
+  while let Some(node) = stack.pop() {
+    if !seen.insert(node) {
+      continue;
+    }
+    unsafe {
+      match node {
+        DAGPtr::Typ(link) => f(&mut (*link.as_ptr()).lvl),
+        DAGPtr::Lam(link) => stack.push((*link.as_ptr()).bod),
+        DAGPtr::Slf(link) => stack.push((*link.as_ptr()).bod),
+        DAGPtr::Fix(link) => stack.push((*link.as_ptr()).bod),
+        DAGPtr::Dat(link) => stack.push((*link.as_ptr()).bod),
+        DAGPtr::Cse(link) => stack.push((*link.as_ptr()).bod),
+        DAGPtr::App(link) => {
+          stack.push((*link.as_ptr()).fun);
+          stack.push((*link.as_ptr()).arg);
+        }
+        DAGPtr::All(link) => {
+          stack.push((*link.as_ptr()).dom);
+          stack.push(DAGPtr::Lam((*link.as_ptr()).img));
+        }
+        DAGPtr::Ann(link) => {
+          stack.push((*link.as_ptr()).typ);
+          stack.push((*link.as_ptr()).exp);
+        }
+        DAGPtr::Let(link) => {
+          stack.push((*link.as_ptr()).typ);
+          stack.push((*link.as_ptr()).exp);
+          stack.push(DAGPtr::Lam((*link.as_ptr()).bod));
+        }
+        _ => (),
       }
     }
   }
 }
 
+/// Infers the universe level of a term that must be a type
+pub fn infer_level(
+  rec: &Option<(Name, Cid, Cid)>,
+  defs: &Defs,
+  ctx: &mut Ctx,
+  term: &Term,
+  should_count: bool,
+) -> Result<Level, CheckError> {
+  let mut typ = infer(rec, defs, ctx, Uses::None, term, should_count)?;
+  typ.whnf(defs, should_count);
+  let res = match typ.head {
+    DAGPtr::Typ(link) => Ok(unsafe { (*link.as_ptr()).lvl.clone() }),
+    _ => Err(CheckError::TypeMismatch(
+      term.pos(),
+      error_context(ctx),
+      Term::Typ(Pos::None, Level::zero()),
+      typ.to_term(false),
+    )),
+  };
+  typ.free();
+  res
+}
+
+/// Whether a term can only be checked against a type, so that checking a
+/// let or redex around it must push the expected type inward rather than
+/// infer its own
+pub fn checks_only(term: &Term) -> bool {
+  match term {
+    Term::Lam(..) | Term::Dat(..) => true,
+    Term::Let(_, false, _, _, triple) => checks_only(&triple.2),
+    Term::App(_, fun_arg) => match &fun_arg.0 {
+      Term::Lam(_, _, bod) => checks_only(bod),
+      _ => false,
+    },
+    _ => false,
+  }
+}
+
+/// Typechecks a local definition by checking its body against the type
+#[inline]
+pub fn check_let(
+  env: &mut Env,
+  uses: Uses,
+  pos: &Pos,
+  exp_uses: Uses,
+  nam: &Name,
+  (exp_typ, exp, bod): &(Term, Term, Term),
+  typ: &mut DAG,
+) -> Result<(), CheckError> {
+  let Env { rec, defs, ref mut ctx, should_count } = *env;
+  let root = alloc_val(DLL::singleton(ParentPtr::Root));
+  let exp_typ_dag = &mut DAG::new(DAG::from_term_inner(
+    exp_typ,
+    ctx.len() as u64,
+    BTreeMap::new(),
+    Some(root),
+    rec.clone(),
+  ));
+  check(rec, defs, ctx, exp_uses * uses, exp, exp_typ_dag, should_count)?;
+  let rest_ctx = div_ctx(uses, ctx);
+  ctx.push((nam.to_string(), exp_uses, &mut exp_typ_dag.head));
+  check(rec, defs, ctx, Uses::Once, bod, typ, should_count)?;
+  let (_, rest, _) = ctx.last().unwrap();
+  if !Uses::lte(Uses::None, *rest) {
+    Err(CheckError::QuantityTooLittle(
+      *pos,
+      error_context(ctx),
+      nam.to_string(),
+      exp_uses,
+      *rest,
+    ))
+  }
+  else {
+    ctx.pop();
+    DAG::new(exp_typ_dag.head).free();
+    add_mul_ctx(uses, ctx, rest_ctx);
+    Ok(())
+  }
+}
+
+/// Typechecks a λ term applied to an argument, by binding the inferred type
+/// of the argument and checking the body against the type
+#[inline]
+pub fn check_redex(
+  env: &mut Env,
+  uses: Uses,
+  nam: &Name,
+  bod: &Term,
+  arg: &Term,
+  typ: &mut DAG,
+) -> Result<(), CheckError> {
+  let Env { rec, defs, ref mut ctx, should_count } = *env;
+  // The λ says nothing about how often it uses its variable, so the argument
+  // is counted as used any number of times
+  let mut arg_typ =
+    infer(rec, defs, ctx, Uses::Many * uses, arg, should_count)?;
+  let rest_ctx = div_ctx(uses, ctx);
+  ctx.push((nam.to_string(), Uses::Many, &mut arg_typ.head));
+  check(rec, defs, ctx, Uses::Once, bod, typ, should_count)?;
+  ctx.pop();
+  arg_typ.free();
+  add_mul_ctx(uses, ctx, rest_ctx);
+  Ok(())
+}
+
 /// Typechecks a λ term
 #[inline]
 pub fn check_lam(
@@ -293,14 +532,14 @@ pub fn infer(
     Term::Cse(pos, exp) => {
       infer_cse(rec, defs, ctx, uses, pos, exp, should_count)
     }
-    Term::All(_, _, nam, dom_img) => {
+    Term::All(_, _, _, nam, dom_img) => {
       infer_all(rec, defs, ctx, nam, &dom_img.0, &dom_img.1, should_count)
     }
     Term::Slf(_, nam, bod) => {
       infer_slf(rec, defs, ctx, term, nam, bod, should_count)
     }
     Term::Ann(_, typ_exp) => {
-      infer_ann(rec, defs, ctx, uses, &typ_exp.0, &typ_exp.1, should_count)
+      infer_ann(rec, defs, ctx, uses, &typ_exp.1, &typ_exp.0, should_count)
     }
     Term::Let(pos, false, exp_uses, nam, triple) => infer_let(
       rec,
@@ -328,13 +567,15 @@ pub fn infer(
       &triple.2,
       should_count,
     ),
-    Term::Typ(_) => {
-      let typ = DAG::from_term(&Term::Typ(Pos::None));
-      Ok(typ)
-    }
+    Term::Typ(_, lvl) => Ok(DAG::from_term(&Term::Typ(Pos::None, lvl.succ()))),
     Term::Lit(_, lit) => Ok(DAG::from_term(&infer_lit(lit.to_owned()))),
     Term::LTy(..) => Ok(DAG::from_term(&yatima!("Type"))),
-    Term::Opr(_, opr) => Ok(DAG::from_term(&opr.type_of())),
+    Term::Opr(pos, opr) => match defs.host.resolve(opr.clone()) {
+      Op::Host(HostRef { op: None, .. }) => {
+        Err(CheckError::UndefinedReference(*pos, opr.symbol()))
+      }
+      opr => Ok(DAG::from_term(&opr.type_of())),
+    },
     Term::Lam(..) => {
       Err(CheckError::UntypedLambda(term.pos(), error_context(ctx)))
     }
@@ -415,6 +656,17 @@ pub fn infer_ref(
     .get(def_link)
     .ok_or_else(|| CheckError::UndefinedReference(*pos, nam.to_string()))?;
   let typ = DAG::from_term(&def.typ_);
+  // The def is polymorphic in the level variables of its type, so each
+  // reference gets its own, solved by the arguments given for them
+  let mut fresh = BTreeMap::new();
+  for_each_level(typ.head, &mut |lvl| {
+    for v in lvl.vars.keys() {
+      fresh.entry(v.clone()).or_insert_with(|| Level::var(Level::fresh(v)));
+    }
+  });
+  if !fresh.is_empty() {
+    for_each_level(typ.head, &mut |lvl| *lvl = lvl.subst(&fresh));
+  }
   Ok(typ)
 }
 
@@ -430,11 +682,16 @@ pub fn infer_app(
   arg: &Term,
   should_count: bool,
 ) -> Result<DAG, CheckError> {
+  if let Term::Lam(_, nam, bod) = fun {
+    let env = &mut Env { rec, defs, ctx, should_count };
+    return infer_redex(env, uses, nam, bod, arg);
+  }
   let mut fun_typ = infer(rec, defs, ctx, uses, fun, should_count)?;
   fun_typ.whnf(defs, should_count);
   match fun_typ.head {
     DAGPtr::All(link) => {
       let All { uses: lam_uses, dom, img, .. } = unsafe { &mut *link.as_ptr() };
+      solve_level(rec, defs, ctx, arg, *dom, fun_typ.head, should_count)?;
       let Lam { var, bod: img, .. } = unsafe { &mut *img.as_ptr() };
       check(
         rec,
@@ -472,6 +729,66 @@ pub fn infer_app(
   }
 }
 
+/// Solves a fresh level variable for the level of an argument, when the
+/// domain of the applied function is a universe at that variable, as in the
+/// `Type{?u}` of `∀ (A: Type{?u}) -> A`. The solution is substituted through
+/// the whole type of the function.
+fn solve_level(
+  rec: &Option<(Name, Cid, Cid)>,
+  defs: &Defs,
+  ctx: &mut Ctx,
+  arg: &Term,
+  dom: DAGPtr,
+  fun_typ: DAGPtr,
+  should_count: bool,
+) -> Result<(), CheckError> {
+  let lvl = match dom {
+    DAGPtr::Typ(link) => unsafe { (*link.as_ptr()).lvl.clone() },
+    _ => return Ok(()),
+  };
+  if !lvl.vars.keys().any(Level::is_fresh) {
+    return Ok(());
+  }
+  let arg_lvl = infer_level(rec, defs, ctx, arg, should_count)?;
+  if let Some((var, val)) = lvl.solve(&arg_lvl) {
+    let mut sub = BTreeMap::new();
+    sub.insert(var, val);
+    for_each_level(fun_typ, &mut |lvl| *lvl = lvl.subst(&sub));
+  }
+  Ok(())
+}
+
+/// Infers the type of a λ term applied to an argument. The λ has no type to
+/// infer, so its variable takes the inferred type of the argument, as in a
+/// let without a type annotation.
+#[inline]
+pub fn infer_redex(
+  env: &mut Env,
+  uses: Uses,
+  nam: &Name,
+  bod: &Term,
+  arg: &Term,
+) -> Result<DAG, CheckError> {
+  let Env { rec, defs, ref mut ctx, should_count } = *env;
+  let mut arg_typ =
+    infer(rec, defs, ctx, Uses::Many * uses, arg, should_count)?;
+  let arg_dag = DAG::new(DAG::from_term_inner(
+    arg,
+    ctx.len() as u64,
+    BTreeMap::new(),
+    None,
+    rec.clone(),
+  ));
+  let rest_ctx = div_ctx(uses, ctx);
+  ctx.push((nam.to_string(), Uses::Many, &mut arg_typ.head));
+  let mut bod_typ = infer(rec, defs, ctx, Uses::Once, bod, should_count)?;
+  ctx.pop();
+  arg_typ.free();
+  add_mul_ctx(uses, ctx, rest_ctx);
+  bod_typ.subst(ctx.len() as u64, arg_dag.head);
+  Ok(bod_typ)
+}
+
 /// Infers the type of a self type destructor
 #[inline]
 pub fn infer_cse(
@@ -545,8 +862,7 @@ pub fn infer_all(
   img: &Term,
   should_count: bool,
 ) -> Result<DAG, CheckError> {
-  let mut typ = DAG::from_term(&Term::Typ(Pos::None));
-  check(rec, defs, ctx, Uses::None, dom, &mut typ, should_count)?;
+  let dom_lvl = infer_level(rec, defs, ctx, dom, should_count)?;
   let mut dom_dag = DAG::from_term_inner(
     dom,
     ctx.len() as u64,
@@ -555,10 +871,11 @@ pub fn infer_all(
     rec.clone(),
   );
   ctx.push((nam.to_string(), Uses::None, &mut dom_dag));
-  check(rec, defs, ctx, Uses::None, img, &mut typ, should_count)?;
+  let img_lvl = infer_level(rec, defs, ctx, img, should_count)?;
   ctx.pop();
   free_dead_node(dom_dag);
-  Ok(typ)
+  let lvl = Level::imax(&dom_lvl, &img_lvl);
+  Ok(DAG::from_term(&Term::Typ(Pos::None, lvl)))
 }
 
 /// Infers the type of a self type
@@ -572,7 +889,6 @@ pub fn infer_slf(
   bod: &Term,
   should_count: bool,
 ) -> Result<DAG, CheckError> {
-  let mut typ = DAG::from_term(&Term::Typ(Pos::None));
   let mut term_dag = DAG::from_term_inner(
     term,
     ctx.len() as u64,
@@ -581,10 +897,10 @@ pub fn infer_slf(
     rec.clone(),
   );
   ctx.push((nam.to_string(), Uses::None, &mut term_dag));
-  check(rec, defs, ctx, Uses::None, bod, &mut typ, should_count)?;
+  let lvl = infer_level(rec, defs, ctx, bod, should_count)?;
   ctx.pop();
   free_dead_node(term_dag);
-  Ok(typ)
+  Ok(DAG::from_term(&Term::Typ(Pos::None, lvl)))
 }
 
 /// Infers the type of a local definition
@@ -754,6 +1070,15 @@ pub fn infer_lit(lit: Literal) -> Term {
     Literal::I32(_) => yatima!("#I32"),
     Literal::I64(_) => yatima!("#I64"),
     Literal::I128(_) => yatima!("#I128"),
+    Literal::F32(_) => yatima!("#F32"),
+    Literal::F64(_) => yatima!("#F64"),
+    Literal::Map(_) => yatima!("#Map"),
+    Literal::Set(_) => yatima!("#Set"),
+    Literal::Vector(_) => yatima!("#Vector"),
+    Literal::Cid(_) => yatima!("#Cid"),
+    Literal::Exception(..) => yatima!("#Exception"),
+    Literal::Rng(_) => yatima!("#Rng"),
+    Literal::U256(_) => yatima!("#U256"),
   }
 }
 
@@ -783,16 +1108,125 @@ pub fn check_def(
   let def_cid = d.cid();
   let ast_cid = a.cid();
   let rec = Some((Name::from(name), def_cid, ast_cid));
-  let mut typ = DAG::from_term(&def.typ_);
-  check(
-    &rec,
-    &defs,
-    &mut vec![],
-    Uses::Once,
-    &def.term,
-    &mut typ,
-    should_count,
-  )?;
-  typ.free();
-  Ok(def.typ_.clone())
+  // Every DAG built while checking is freed with the arena, even on errors,
+  // and none of it is kept. The type is hash-consed once, so that the
+  // subterms it repeats are found equal by address whenever it's compared.
+  unsafe {
+    with_arena(|| {
+      let mut typ = DAG::from_term(&def.typ_);
+      typ.hash_cons();
+      check(
+        &rec,
+        &defs,
+        &mut vec![],
+        Uses::Once,
+        &def.term,
+        &mut typ,
+        should_count,
+      )?;
+      Ok(def.typ_.clone())
+    })
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::parse::{
+    package,
+    span::Span,
+    term::input_cid,
+  };
+
+  fn check_src(src: &str, name: &str) -> Result<Term, CheckError> {
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    check_def(Rc::new(defs), name, false)
+  }
+
+  #[test]
+  fn test_check_redex() {
+    // The variable of an applied λ takes the type of the argument
+    let src = "def two: #U64 = (λ x => #U64.add x 1u64) 1u64";
+    assert!(check_src(src, "two").is_ok());
+    let src = "def two: #U64 = let x = 1u64; #U64.add x x";
+    assert!(check_src(src, "two").is_ok());
+    let src = "def bad: #Text = let x = 1u64; x";
+    assert!(check_src(src, "bad").is_err());
+  }
+
+  #[test]
+  fn test_check_inward() {
+    let src =
+      "def inc: ∀ #U64 -> #U64 = (λ x => #U64.add x 1u64) :: ∀ #U64 -> #U64";
+    assert!(check_src(src, "inc").is_ok());
+    // Lets and redexes push the expected type into a λ body
+    let src =
+      "def inc: ∀ #U64 -> #U64 = let one: #U64 = 1u64; λ x => #U64.add x one";
+    assert!(check_src(src, "inc").is_ok());
+    let src = "def inc: ∀ #U64 -> #U64 = let one = 1u64; λ x => #U64.add x one";
+    assert!(check_src(src, "inc").is_ok());
+  }
+
+  #[test]
+  fn test_conv_cache() {
+    let src = "def ty: Type = ∀ #U64 -> #U64\n\
+               def inc: ty = λ x => #U64.add x 1u64\n\
+               def good: ∀ #U64 -> #U64 = inc\n\
+               def bad: ∀ #U64 -> #Text = inc";
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    let defs = Rc::new(defs);
+    assert!(check_def(defs.clone(), "good", false).is_ok());
+    assert!(check_def(defs.clone(), "bad", false).is_err());
+    let (len, hits) = (defs.conv.len(), defs.conv.hits());
+    assert!(len >= 2);
+    // Checking again finds both outcomes in the cache
+    assert!(check_def(defs.clone(), "good", false).is_ok());
+    assert!(check_def(defs.clone(), "bad", false).is_err());
+    assert_eq!((defs.conv.len(), defs.conv.hits()), (len, hits + 2));
+  }
+
+  #[test]
+  fn test_check_attrs() {
+    assert!(check_src("def Id: Type = #U64\ndef two: Id = 2u64", "two").is_ok());
+    // Conversion doesn't unfold opaque defs, nor partial ones by default
+    let src = "opaque def Id: Type = #U64\ndef two: Id = 2u64";
+    assert!(check_src(src, "two").is_err());
+    let src = "partial def Id: Type = #U64\ndef two: Id = 2u64";
+    assert!(check_src(src, "two").is_err());
+    let src = "partial unfold def Id: Type = #U64\ndef two: Id = 2u64";
+    assert!(check_src(src, "two").is_ok());
+    // An opaque def is still equal to itself
+    let src = "opaque def Id: Type = #U64\n\
+               def id: ∀ Id -> Id = λ x => x\n\
+               def id2: ∀ Id -> Id = id";
+    assert!(check_src(src, "id2").is_ok());
+    let src = "opaque unfold def Id: Type = #U64";
+    let res = package::parse_defs(input_cid(src), Defs::new())(Span::new(src));
+    assert!(res.is_err());
+  }
+
+  #[test]
+  fn test_check_universes() {
+    assert!(check_src("def big: Type{1} = Type", "big").is_ok());
+    assert!(check_src("def bad: Type = Type", "bad").is_err());
+    // Universes are cumulative
+    assert!(check_src("def num: Type{2} = #U64", "num").is_ok());
+    assert!(check_src("def arr: Type{1} = ∀ Type -> Type", "arr").is_ok());
+    assert!(check_src("def arr: Type = ∀ Type -> Type", "arr").is_err());
+    // ...but a ∀ into the lowest universe stays in it
+    let src = "def poly: Type = ∀ (A: Type) (x: A) -> A";
+    assert!(check_src(src, "poly").is_ok());
+    // A level variable is solved afresh at each reference
+    let src = "def id {A: Type{u}} (x: A): A = x
+               def ty: Type{1} = id Type
+               def num: #U64 = id 1u64";
+    for nam in ["id", "ty", "num"].iter() {
+      assert!(check_src(src, nam).is_ok(), "{}", nam);
+    }
+    let src = "def id {A: Type} (x: A): A = x
+def ty: Type{1} = id Type";
+    assert!(check_src(src, "ty").is_err());
+  }
 }
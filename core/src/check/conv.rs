@@ -0,0 +1,85 @@
+use sp_cid::Cid;
+
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  fmt,
+  rc::Rc,
+};
+
+use core::cell::RefCell;
+
+#[derive(Default)]
+struct Conv {
+  results: BTreeMap<(Cid, Cid), bool>,
+  hits: u64,
+  misses: u64,
+}
+
+/// Remembers the outcomes of conversion checks, keyed by the anonymous CIDs
+/// of the two terms compared. Conversion doesn't depend on the typing
+/// context, and references are to defs by content, so an outcome holds
+/// wherever the same pair of terms meets again. Clones share their entries,
+/// so a cache attached to `Defs` lasts as long as any copy of them.
+#[derive(Clone, Default)]
+pub struct ConvCache {
+  conv: Rc<RefCell<Conv>>,
+}
+
+impl ConvCache {
+  pub fn new() -> Self { Self::default() }
+
+  /// Whether the terms with CIDs `a` and `b` were found convertible, if they
+  /// were compared before
+  pub fn get(&self, a: &Cid, b: &Cid) -> Option<bool> {
+    let mut conv = self.conv.borrow_mut();
+    let res = conv
+      .results
+      .get(&(*a, *b))
+      .or_else(|| conv.results.get(&(*b, *a)))
+      .copied();
+    match res {
+      Some(_) => conv.hits += 1,
+      None => conv.misses += 1,
+    }
+    res
+  }
+
+  pub fn insert(&self, a: Cid, b: Cid, eq: bool) {
+    self.conv.borrow_mut().results.insert((a, b), eq);
+  }
+
+  pub fn clear(&self) { self.conv.borrow_mut().results.clear() }
+
+  /// The number of pairs of terms with a known outcome
+  pub fn len(&self) -> usize { self.conv.borrow().results.len() }
+
+  pub fn is_empty(&self) -> bool { self.conv.borrow().results.is_empty() }
+
+  pub fn hits(&self) -> u64 { self.conv.borrow().hits }
+
+  pub fn misses(&self) -> u64 { self.conv.borrow().misses }
+}
+
+/// Two defs are equal whatever their caches hold
+impl PartialEq for ConvCache {
+  fn eq(&self, _: &Self) -> bool { true }
+}
+
+impl fmt::Debug for ConvCache {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "ConvCache {{ {} pairs }}", self.len())
+  }
+}
+
+impl fmt::Display for ConvCache {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let conv = self.conv.borrow();
+    write!(
+      f,
+      "{} pairs, {} hits, {} misses",
+      conv.results.len(),
+      conv.hits,
+      conv.misses
+    )
+  }
+}
@@ -35,9 +35,38 @@ impl CheckError {
   /// Returns the source position of a typecheck error
   pub fn pos(&self) -> Pos {
     match self {
-      Self::TypeMismatch(pos, ..) => *pos,
-      Self::GenericError(pos, ..) => *pos,
-      _ => Pos::None,
+      Self::UndefinedReference(pos, ..)
+      | Self::UnboundVariable(pos, ..)
+      | Self::UntypedLambda(pos, ..)
+      | Self::UntypedData(pos, ..)
+      | Self::QuantityTooLittle(pos, ..)
+      | Self::QuantityTooMuch(pos, ..)
+      | Self::TypeMismatch(pos, ..)
+      | Self::LamAllMismatch(pos, ..)
+      | Self::DatSlfMismatch(pos, ..)
+      | Self::AppFunMismatch(pos, ..)
+      | Self::CseDatMismatch(pos, ..)
+      | Self::NonInductiveLitType(pos, ..)
+      | Self::GenericError(pos, ..) => *pos,
+    }
+  }
+
+  /// Returns the local context at the point where typechecking failed
+  pub fn context(&self) -> Option<&ErrCtx> {
+    match self {
+      Self::UndefinedReference(..) => None,
+      Self::UnboundVariable(_, ctx, ..)
+      | Self::UntypedLambda(_, ctx)
+      | Self::UntypedData(_, ctx)
+      | Self::QuantityTooLittle(_, ctx, ..)
+      | Self::QuantityTooMuch(_, ctx, ..)
+      | Self::TypeMismatch(_, ctx, ..)
+      | Self::LamAllMismatch(_, ctx, ..)
+      | Self::DatSlfMismatch(_, ctx, ..)
+      | Self::AppFunMismatch(_, ctx, ..)
+      | Self::CseDatMismatch(_, ctx, ..)
+      | Self::NonInductiveLitType(_, ctx, ..)
+      | Self::GenericError(_, ctx, ..) => Some(ctx),
     }
   }
 }
@@ -55,47 +84,75 @@ pub fn pretty_pos(pos: Pos) -> String {
   }
 }
 
+/// Shared prefixes at least this long are elided when printing two terms
+pub const ELIDE_PREFIX: usize = 16;
+
+/// Elides the prefix two printed terms share, if it is long, so that a
+/// mismatch deep inside a large type is easy to spot. The prefix is cut at
+/// the last space in it so that no name is split.
+pub fn elide(a: &str, b: &str) -> (String, String) {
+  let shared = a
+    .char_indices()
+    .zip(b.chars())
+    .take_while(|((_, x), y)| x == y)
+    .last()
+    .map_or(0, |((i, x), _)| i + x.len_utf8());
+  let cut = match a[..shared].rfind(' ') {
+    Some(cut) if shared < a.len() || shared < b.len() => cut,
+    _ => 0,
+  };
+  if cut < ELIDE_PREFIX {
+    (a.to_owned(), b.to_owned())
+  }
+  else {
+    (format!("…{}", &a[cut..]), format!("…{}", &b[cut..]))
+  }
+}
+
+fn write_context(f: &mut fmt::Formatter, ctx: &ErrCtx) -> fmt::Result {
+  if !ctx.is_empty() {
+    writeln!(f, "• Context:")?;
+    for (n, uses, typ) in ctx {
+      writeln!(f, "  - {} {}: {}", uses, n, typ)?;
+    }
+  }
+  Ok(())
+}
+
+fn write_terms(
+  f: &mut fmt::Formatter,
+  labels: (&str, &str),
+  a: &Term,
+  b: &Term,
+) -> fmt::Result {
+  let (a, b) = elide(&format!("{}", a), &format!("{}", b));
+  writeln!(f, "• {}: {}", labels.0, a)?;
+  writeln!(f, "• {}: {}", labels.1, b)
+}
+
 impl fmt::Display for CheckError {
   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
     match self {
       CheckError::UnboundVariable(pos, ctx, name, dep) => {
-        write!(
+        writeln!(
           f,
           "Unbound free variable \"{}\" with depth {} {}",
           name,
           dep,
           pretty_pos(*pos)
         )?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
-        Ok(())
+        write_context(f, ctx)
       }
       CheckError::UndefinedReference(pos, name) => {
         write!(f, "Undefined reference \"{}\" {}", name, pretty_pos(*pos))
       }
       CheckError::UntypedLambda(pos, ctx) => {
-        write!(f, "Untyped lambda {}", pretty_pos(*pos))?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
-        Ok(())
+        writeln!(f, "Untyped lambda {}", pretty_pos(*pos))?;
+        write_context(f, ctx)
       }
       CheckError::UntypedData(pos, ctx) => {
-        write!(f, "Untyped data expression {}", pretty_pos(*pos))?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
-        Ok(())
+        writeln!(f, "Untyped data expression {}", pretty_pos(*pos))?;
+        write_context(f, ctx)
       }
       CheckError::LamAllMismatch(pos, ctx, trm, typ) => {
         writeln!(
@@ -103,15 +160,9 @@ impl fmt::Display for CheckError {
           "The type of a lambda (λ) is not a forall (∀) {}",
           pretty_pos(*pos)
         )?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
+        write_context(f, ctx)?;
         writeln!(f, "• Checked: {}", trm)?;
-        writeln!(f, "• Against: {}", typ)?;
-        Ok(())
+        writeln!(f, "• Against: {}", typ)
       }
       CheckError::AppFunMismatch(pos, ctx, fun, typ) => {
         writeln!(
@@ -119,15 +170,9 @@ impl fmt::Display for CheckError {
           "Tried to apply an expression which is not a function {}",
           pretty_pos(*pos)
         )?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
+        write_context(f, ctx)?;
         writeln!(f, "• Checked: {}", fun)?;
-        writeln!(f, "• Against: {}", typ)?;
-        Ok(())
+        writeln!(f, "• Against: {}", typ)
       }
       CheckError::CseDatMismatch(pos, ctx, dat, typ) => {
         writeln!(
@@ -136,15 +181,9 @@ impl fmt::Display for CheckError {
            datatype or literal {}",
           pretty_pos(*pos)
         )?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
+        write_context(f, ctx)?;
         writeln!(f, "• Checked: {}", dat)?;
-        writeln!(f, "• Against: {}", typ)?;
-        Ok(())
+        writeln!(f, "• Against: {}", typ)
       }
       CheckError::NonInductiveLitType(pos, ctx, typ) => {
         writeln!(
@@ -153,13 +192,7 @@ impl fmt::Display for CheckError {
           typ,
           pretty_pos(*pos)
         )?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
-        Ok(())
+        write_context(f, ctx)
       }
       CheckError::DatSlfMismatch(pos, ctx, trm, typ) => {
         writeln!(
@@ -167,51 +200,26 @@ impl fmt::Display for CheckError {
           "The type of a data constructor is not a self-type {}",
           pretty_pos(*pos)
         )?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
+        write_context(f, ctx)?;
         writeln!(f, "• Checked: {}", trm)?;
-        writeln!(f, "• Against: {}", typ)?;
-        Ok(())
+        writeln!(f, "• Against: {}", typ)
       }
       CheckError::QuantityTooLittle(pos, ctx, nam, exp, det) => {
         writeln!(f, "Variable `{}` not used enough {}", nam, pretty_pos(*pos))?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
+        write_context(f, ctx)?;
         writeln!(f, "• Expected: {}", exp)?;
-        writeln!(f, "• Detected: {}", det)?;
-        Ok(())
+        writeln!(f, "• Detected: {}", det)
       }
       CheckError::QuantityTooMuch(pos, ctx, nam, exp, det) => {
         writeln!(f, "Variable `{}` used too much {}", nam, pretty_pos(*pos))?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
+        write_context(f, ctx)?;
         writeln!(f, "• Expected: {}", exp)?;
-        writeln!(f, "• Detected: {}", det)?;
-        Ok(())
+        writeln!(f, "• Detected: {}", det)
       }
       CheckError::TypeMismatch(pos, ctx, exp, det) => {
         writeln!(f, "Type Mismatch {}", pretty_pos(*pos))?;
-        if !ctx.is_empty() {
-          writeln!(f, "• Context:")?;
-          for (n, uses, typ) in ctx {
-            writeln!(f, "  - {} {}: {}", uses, n, typ)?;
-          }
-        }
-        writeln!(f, "• Expected: {}", exp)?;
-        writeln!(f, "• Detected: {}", det)?;
-        Ok(())
+        write_context(f, ctx)?;
+        write_terms(f, ("Expected", "Detected"), exp, det)
       }
       CheckError::GenericError(pos, ctx, msg) => {
         writeln!(f, "{} {} ", msg, pretty_pos(*pos))?;
@@ -223,3 +231,49 @@ impl fmt::Display for CheckError {
     }
   }
 }
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    check::check_def,
+    defs::Defs,
+    parse::{
+      package,
+      span::Span,
+      term::input_cid,
+    },
+  };
+  use sp_std::rc::Rc;
+
+  #[test]
+  fn test_elide() {
+    let a = "∀ (A: Type) (B: Type) (x: A) -> #Nat";
+    let b = "∀ (A: Type) (B: Type) (x: A) -> #Int";
+    assert_eq!(elide(a, b), ("… #Nat".to_owned(), "… #Int".to_owned()));
+    assert_eq!(elide("#Nat", "#Int"), ("#Nat".to_owned(), "#Int".to_owned()));
+    let same = "∀ (A: Type) (B: Type) -> A";
+    assert_eq!(elide(same, same), (same.to_owned(), same.to_owned()));
+    let (a, b) = elide("∀ (A: Type) (xs: #Nat) -> A", "∀ (A: Type) (xs: #Int)");
+    assert_eq!((a.as_str(), b.as_str()), ("… #Nat) -> A", "… #Int)"));
+  }
+
+  #[test]
+  fn test_mismatch_pos() {
+    let src = "def f (A: Type) (x: #Nat): #Text = x";
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    let err = check_def(Rc::new(defs), "f", false).unwrap_err();
+    match err.pos() {
+      Pos::Some(pos) => {
+        assert_eq!((pos.from_line, pos.from_column), (1, 36));
+        assert_eq!(&src[pos.from_offset as usize..], "x");
+      }
+      Pos::None => panic!("no position for {}", err),
+    }
+    let ctx = err.context().unwrap();
+    let names: Vec<&str> = ctx.iter().map(|(n, ..)| n.as_str()).collect();
+    assert_eq!(names, vec!["A", "x"]);
+    assert!(format!("{}", err).contains("• Expected: #Text"));
+  }
+}
@@ -1,8 +1,15 @@
 /// Bottom-up reduction of lambda DAGs. Based on the paper by Olin Shivers
 /// and Mitchel Wand "Bottom-up β-reduction: uplinks and λ-DAGs" (https://www.brics.dk/RS/04/38/BRICS-RS-04-38.pdf)
 use crate::{
+  arena::{
+    alloc_in_arena,
+    debug_assert_on_heap,
+    free_in_arena,
+    on_heap,
+  },
   defs::Def,
   dll::*,
+  level::Level,
   literal::{
     LitType,
     Literal,
@@ -14,7 +21,13 @@ use crate::{
   uses::Uses,
 };
 
-use core::ptr::NonNull;
+use core::{
+  ptr::NonNull,
+  sync::atomic::{
+    AtomicUsize,
+    Ordering,
+  },
+};
 
 use sp_std::{
   boxed::Box,
@@ -24,6 +37,7 @@ use sp_std::{
   },
   fmt,
   mem,
+  vec::Vec,
 };
 
 use alloc::string::String;
@@ -199,6 +213,7 @@ pub struct Ref {
 #[repr(C)]
 pub struct Typ {
   pub parents: Option<NonNull<Parents>>,
+  pub lvl: Level,
 }
 
 #[repr(C)]
@@ -219,10 +234,61 @@ pub struct Opr {
   pub parents: Option<NonNull<Parents>>,
 }
 
-/// Auxiliary allocation functions
+/// The number of nodes and parent links allocated so far
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of values allocated by `alloc_val` and not yet freed, and
+/// their size in bytes
+static LIVE: AtomicUsize = AtomicUsize::new(0);
+static LIVE_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// Gets the number of allocations made by `alloc_val` since the program
+/// started. Freed values aren't subtracted
+pub fn allocations() -> usize { ALLOCATIONS.load(Ordering::Relaxed) }
+
+/// The number of nodes and parent links currently allocated, in every
+/// thread
+pub fn live_nodes() -> usize { LIVE.load(Ordering::Relaxed) }
+
+/// The size in bytes of the nodes and parent links currently allocated, not
+/// counting what their literals and names hold on the heap
+pub fn live_bytes() -> usize { LIVE_BYTES.load(Ordering::Relaxed) }
+
+/// Auxiliary allocation functions. Values go in the innermost arena open on
+/// this thread, if any, and on the heap otherwise
 #[inline]
 pub fn alloc_val<T>(val: T) -> NonNull<T> {
-  NonNull::new(Box::leak(Box::new(val))).unwrap()
+  ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+  LIVE.fetch_add(1, Ordering::Relaxed);
+  LIVE_BYTES.fetch_add(mem::size_of::<T>(), Ordering::Relaxed);
+  alloc_in_arena(val).unwrap_or_else(|val| {
+    let link = NonNull::new(Box::leak(Box::new(val))).unwrap();
+    on_heap(link);
+    link
+  })
+}
+
+/// Frees a value allocated by `alloc_val`. A value in an arena leaves its
+/// slot to the next value of the same layout allocated there.
+///
+/// # Safety
+///
+/// `link` must come from `alloc_val`, and must not be used again.
+#[inline]
+pub unsafe fn free_val<T>(link: NonNull<T>) {
+  LIVE.fetch_sub(1, Ordering::Relaxed);
+  LIVE_BYTES.fetch_sub(mem::size_of::<T>(), Ordering::Relaxed);
+  if !free_in_arena(link) {
+    debug_assert_on_heap(link);
+    drop(Box::from_raw(link.as_ptr()));
+  }
+}
+
+/// Stops counting the values left in an arena being freed
+#[cfg(any(feature = "std", test))]
+pub(crate) fn forget_live(values: usize, bytes: usize) {
+  LIVE.fetch_sub(values, Ordering::Relaxed);
+  LIVE_BYTES.fetch_sub(bytes, Ordering::Relaxed);
 }
 
 #[inline]
@@ -541,7 +607,7 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_bod_parents.is_none() {
           free_dead_node(*bod)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Slf(mut link) => {
         let Slf { bod, bod_ref, .. } = &link.as_mut();
@@ -550,7 +616,7 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_bod_parents.is_none() {
           free_dead_node(*bod)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Fix(mut link) => {
         let Fix { bod, bod_ref, .. } = &link.as_mut();
@@ -559,7 +625,7 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_bod_parents.is_none() {
           free_dead_node(*bod)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Cse(link) => {
         let Cse { bod, bod_ref, .. } = link.as_ref();
@@ -568,7 +634,7 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_bod_parents.is_none() {
           free_dead_node(*bod)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Dat(link) => {
         let Dat { bod, bod_ref, .. } = &link.as_ref();
@@ -577,7 +643,7 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_bod_parents.is_none() {
           free_dead_node(*bod)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::All(link) => {
         let All { dom, img, dom_ref, img_ref, .. } = link.as_ref();
@@ -592,7 +658,7 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_img_parents.is_none() {
           free_dead_node(img)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::App(link) => {
         let App { fun, arg, fun_ref, arg_ref, .. } = link.as_ref();
@@ -606,7 +672,7 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_arg_parents.is_none() {
           free_dead_node(*arg)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Ann(link) => {
         let Ann { exp, typ, exp_ref, typ_ref, .. } = link.as_ref();
@@ -620,7 +686,7 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_typ_parents.is_none() {
           free_dead_node(*typ)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Let(link) => {
         let Let { exp, typ, exp_ref, typ_ref, bod, bod_ref, .. } =
@@ -641,29 +707,29 @@ pub fn free_dead_node(node: DAGPtr) {
         if new_bod_parents.is_none() {
           free_dead_node(bod)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Var(link) => {
         let Var { binder, .. } = link.as_ref();
         // only free Free variables, bound variables are freed with their binder
         if let BinderPtr::Free = binder {
-          Box::from_raw(link.as_ptr());
+          free_val(link);
         }
       }
       DAGPtr::Ref(link) => {
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Typ(link) => {
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Lit(link) => {
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::LTy(link) => {
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAGPtr::Opr(link) => {
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
     }
   }
@@ -678,7 +744,7 @@ impl DAG {
     match get_parents(self.head) {
       None => (),
       Some(pref) => unsafe {
-        Box::from_raw(pref.as_ptr());
+        free_val(pref);
         set_parents(self.head, None);
       },
     }
@@ -705,7 +771,10 @@ impl DAG {
           Term::Var(Pos::None, nam.clone(), *var_depth)
         }
       }
-      DAGPtr::Typ(_) => Term::Typ(Pos::None),
+      DAGPtr::Typ(link) => {
+        let Typ { lvl, .. } = unsafe { link.as_ref() };
+        Term::Typ(Pos::None, lvl.clone())
+      }
       DAGPtr::LTy(link) => {
         let LTy { lty, .. } = unsafe { link.as_ref() };
         Term::LTy(Pos::None, *lty)
@@ -789,6 +858,7 @@ impl DAG {
         map.insert(var, depth);
         Term::All(
           Pos::None,
+          false,
           *uses,
           nam,
           Box::new((
@@ -923,7 +993,9 @@ impl DAG {
           }
         }
       }
-      Term::Typ(_) => DAGPtr::Typ(alloc_val(Typ { parents })),
+      Term::Typ(_, lvl) => {
+        DAGPtr::Typ(alloc_val(Typ { parents, lvl: lvl.clone() }))
+      }
       Term::LTy(_, lty) => DAGPtr::LTy(alloc_val(LTy { lty: *lty, parents })),
       Term::Lit(_, lit) => {
         DAGPtr::Lit(alloc_val(Lit { lit: lit.clone(), parents }))
@@ -990,7 +1062,7 @@ impl DAG {
         (*cse.as_ptr()).bod = bod;
         DAGPtr::Cse(cse)
       },
-      Term::All(_, uses, nam, dom_img) => unsafe {
+      Term::All(_, _, uses, nam, dom_img) => unsafe {
         let (dom, img) = &**dom_img;
         let all = alloc_all(*uses, mem::zeroed(), NonNull::dangling(), parents);
         let All { dom_ref, img_ref, .. } = &mut *all.as_ptr();
@@ -1300,16 +1372,90 @@ impl DAG {
         let node = alloc_val(Opr { opr: opr.clone(), parents });
         DAGPtr::Opr(node)
       },
-      DAGPtr::Typ(_) => {
-        let node = alloc_val(Typ { parents });
+      DAGPtr::Typ(link) => unsafe {
+        let Typ { lvl, .. } = &*link.as_ptr();
+        let node = alloc_val(Typ { parents, lvl: lvl.clone() });
         DAGPtr::Typ(node)
-      } // _ => panic!("TODO"),
+      }, // _ => panic!("TODO"),
     };
     // Map `node` to `new_node`
     map.insert(node, new_node);
     new_node
   }
 
+  /// Gets the variables that occur in a subsection but are bound outside of
+  /// it
+  pub fn free_vars(node: DAGPtr) -> Vec<NonNull<Var>> {
+    let mut bound = BTreeSet::new();
+    let mut vars = BTreeSet::new();
+    let mut visited = BTreeSet::new();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+      if !visited.insert(node) {
+        continue;
+      }
+      match node {
+        DAGPtr::Var(link) => {
+          vars.insert(link);
+        }
+        DAGPtr::Lam(link) => unsafe {
+          let Lam { var, bod, .. } = &mut *link.as_ptr();
+          bound.insert(NonNull::from(var));
+          stack.push(*bod);
+        },
+        DAGPtr::Slf(link) => unsafe {
+          let Slf { var, bod, .. } = &mut *link.as_ptr();
+          bound.insert(NonNull::from(var));
+          stack.push(*bod);
+        },
+        DAGPtr::Fix(link) => unsafe {
+          let Fix { var, bod, .. } = &mut *link.as_ptr();
+          bound.insert(NonNull::from(var));
+          stack.push(*bod);
+        },
+        DAGPtr::Dat(link) => unsafe { stack.push(link.as_ref().bod) },
+        DAGPtr::Cse(link) => unsafe { stack.push(link.as_ref().bod) },
+        DAGPtr::App(link) => unsafe {
+          let App { fun, arg, .. } = link.as_ref();
+          stack.push(*fun);
+          stack.push(*arg);
+        },
+        DAGPtr::All(link) => unsafe {
+          let All { dom, img, .. } = link.as_ref();
+          stack.push(*dom);
+          stack.push(DAGPtr::Lam(*img));
+        },
+        DAGPtr::Ann(link) => unsafe {
+          let Ann { typ, exp, .. } = link.as_ref();
+          stack.push(*typ);
+          stack.push(*exp);
+        },
+        DAGPtr::Let(link) => unsafe {
+          let Let { typ, exp, bod, .. } = link.as_ref();
+          stack.push(*typ);
+          stack.push(*exp);
+          stack.push(DAGPtr::Lam(*bod));
+        },
+        _ => (),
+      }
+    }
+    vars.difference(&bound).copied().collect()
+  }
+
+  /// Copies a subsection that may have free variables, which stay shared
+  /// with the original rather than becoming free in the copy
+  pub fn from_open_subdag(
+    node: DAGPtr,
+    free: &[NonNull<Var>],
+    parents: Option<NonNull<Parents>>,
+  ) -> DAGPtr {
+    let mut map = BTreeMap::new();
+    for var in free {
+      map.insert(DAGPtr::Var(*var), DAGPtr::Var(*var));
+    }
+    DAG::from_subdag(node, &mut map, parents)
+  }
+
   /// Substitution of free variable
   pub fn subst(&mut self, idx: u64, val: DAGPtr) {
     pub fn go(node: DAGPtr, idx: u64, val: DAGPtr) {
@@ -0,0 +1,317 @@
+use crate::{
+  dag::{
+    allocations,
+    DAG,
+  },
+  defs::Defs,
+  name::Name,
+  term::Term,
+};
+
+use sp_std::{
+  collections::{
+    btree_set::BTreeSet,
+    vec_deque::VecDeque,
+  },
+  vec::Vec,
+};
+
+/// Default number of reduction steps kept in a debugger's history
+pub const DEFAULT_HISTORY: usize = 64;
+
+/// A single reduction performed by the debugger
+#[derive(Clone, Debug)]
+pub struct Step {
+  /// The smallest subterm changed by the reduction
+  pub redex: Term,
+  /// What the redex was replaced with
+  pub result: Term,
+  /// The subterm immediately enclosing the redex, if it isn't the whole term
+  pub context: Option<Term>,
+  /// How many graph nodes were allocated during the reduction
+  pub allocations: usize,
+}
+
+/// Why `Debugger::run` stopped
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Stop {
+  /// A definition with a breakpoint was unfolded
+  Breakpoint(Name),
+  /// The term is in normal form or stuck
+  Done,
+  /// The step limit was reached
+  Limit,
+}
+
+/// Single-steps the normalization of a term, recording a bounded history of
+/// the intermediate terms so that stepping can be reversed
+pub struct Debugger {
+  dag: DAG,
+  history: VecDeque<(Term, Option<Step>)>,
+  capacity: usize,
+  cursor: usize,
+  steps: u64,
+  breakpoints: BTreeSet<Name>,
+}
+
+impl Debugger {
+  /// Starts debugging a term, keeping at most `capacity` steps of history
+  pub fn new(term: &Term, capacity: usize) -> Self {
+    let dag = DAG::from_term(term);
+    let mut history = VecDeque::new();
+    history.push_back((dag.to_term(false), None));
+    Debugger {
+      dag,
+      history,
+      capacity: capacity.max(1),
+      cursor: 0,
+      steps: 0,
+      breakpoints: BTreeSet::new(),
+    }
+  }
+
+  /// The term at the current position in the history
+  pub fn current(&self) -> &Term { &self.history[self.cursor].0 }
+
+  /// The reduction that produced the current term, if it isn't the first
+  pub fn last_step(&self) -> Option<&Step> {
+    self.history[self.cursor].1.as_ref()
+  }
+
+  /// The number of reductions performed since the debugger started
+  pub fn steps(&self) -> u64 { self.steps }
+
+  /// How many steps back from the newest term the cursor is
+  pub fn offset(&self) -> usize { self.history.len() - 1 - self.cursor }
+
+  /// Changes the history length, discarding the oldest steps if it shrinks
+  pub fn set_capacity(&mut self, capacity: usize) {
+    self.capacity = capacity.max(1);
+    while self.history.len() > self.capacity {
+      self.history.pop_front();
+      self.cursor = self.cursor.saturating_sub(1);
+    }
+  }
+
+  /// Stops `run` whenever the definition named `nam` is unfolded
+  pub fn add_breakpoint(&mut self, nam: Name) { self.breakpoints.insert(nam); }
+
+  /// Removes a breakpoint, returning whether it was set
+  pub fn remove_breakpoint(&mut self, nam: &Name) -> bool {
+    self.breakpoints.remove(nam)
+  }
+
+  pub fn breakpoints(&self) -> impl Iterator<Item = &Name> {
+    self.breakpoints.iter()
+  }
+
+  /// Moves one step back in the history. Returns `None` once the oldest
+  /// recorded step is reached.
+  pub fn back(&mut self) -> Option<&Term> {
+    if self.cursor == 0 {
+      None
+    }
+    else {
+      self.cursor -= 1;
+      Some(self.current())
+    }
+  }
+
+  /// Moves one step forward, replaying the history if the cursor is behind
+  /// and otherwise performing a new reduction. Returns `None` if the term is
+  /// already in normal form or stuck.
+  pub fn forward(&mut self, defs: &Defs) -> Option<&Term> {
+    if self.offset() > 0 {
+      self.cursor += 1;
+      return Some(self.current());
+    }
+    let mut budget = 1;
+    let allocated = allocations();
+    self.dag.norm_steps(defs, false, &mut budget);
+    if budget != 0 {
+      return None;
+    }
+    let allocated = allocations() - allocated;
+    self.steps += 1;
+    let term = self.dag.to_term(false);
+    let step = diff(&self.history[self.cursor].0, &term).map(
+      |(redex, result, context)| Step {
+        redex: redex.clone(),
+        result: result.clone(),
+        context: context.cloned(),
+        allocations: allocated,
+      },
+    );
+    self.history.push_back((term, step));
+    if self.history.len() > self.capacity {
+      self.history.pop_front();
+    }
+    self.cursor = self.history.len() - 1;
+    Some(self.current())
+  }
+
+  /// Steps forward until a definition with a breakpoint is unfolded, the
+  /// term can't be reduced further, or `limit` steps were taken
+  pub fn run(&mut self, defs: &Defs, limit: u64) -> Stop {
+    for _ in 0..limit {
+      if self.forward(defs).is_none() {
+        return Stop::Done;
+      }
+      if let Some(step) = self.last_step() {
+        for nam in self.breakpoints.iter() {
+          if count_refs(&step.redex, nam) > count_refs(&step.result, nam) {
+            return Stop::Breakpoint(nam.clone());
+          }
+        }
+      }
+    }
+    Stop::Limit
+  }
+}
+
+impl Drop for Debugger {
+  fn drop(&mut self) { DAG::new(self.dag.head).free() }
+}
+
+fn children(term: &Term) -> Vec<&Term> {
+  match term {
+    Term::Lam(_, _, bod)
+    | Term::Slf(_, _, bod)
+    | Term::Dat(_, bod)
+    | Term::Cse(_, bod) => vec![&**bod],
+    Term::App(_, xs) | Term::All(_, _, _, _, xs) | Term::Ann(_, xs) => {
+      vec![&xs.0, &xs.1]
+    }
+    Term::Let(_, _, _, _, xs) => vec![&xs.0, &xs.1, &xs.2],
+    _ => vec![],
+  }
+}
+
+/// Whether two terms have the same constructor, ignoring their children
+fn same_node(a: &Term, b: &Term) -> bool {
+  match (a, b) {
+    (Term::Lam(..), Term::Lam(..))
+    | (Term::Slf(..), Term::Slf(..))
+    | (Term::Dat(..), Term::Dat(..))
+    | (Term::Cse(..), Term::Cse(..))
+    | (Term::App(..), Term::App(..))
+    | (Term::Ann(..), Term::Ann(..)) => true,
+    (Term::All(_, ia, ua, ..), Term::All(_, ib, ub, ..)) => {
+      ia == ib && ua == ub
+    }
+    (Term::Let(_, ra, ua, ..), Term::Let(_, rb, ub, ..)) => {
+      ra == rb && ua == ub
+    }
+    _ => a == b,
+  }
+}
+
+/// Finds the smallest subterm in which two terms differ, returning it from
+/// both terms along with the subterm of `old` that encloses it. A reduction
+/// of a shared subterm changes each of its occurrences, so the result is
+/// the smallest subterm containing all of them
+pub fn diff<'a>(
+  old: &'a Term,
+  new: &'a Term,
+) -> Option<(&'a Term, &'a Term, Option<&'a Term>)> {
+  if old == new {
+    return None;
+  }
+  let (mut old, mut new, mut context) = (old, new, None);
+  while same_node(old, new) {
+    let mut changed =
+      children(old).into_iter().zip(children(new)).filter(|(a, b)| a != b);
+    match (changed.next(), changed.next()) {
+      (Some((a, b)), None) => {
+        context = Some(old);
+        old = a;
+        new = b;
+      }
+      _ => break,
+    }
+  }
+  Some((old, new, context))
+}
+
+/// Counts the references to the definition `nam` in a term
+pub fn count_refs(term: &Term, nam: &Name) -> usize {
+  match term {
+    Term::Ref(_, ref_nam, ..) => (ref_nam == nam) as usize,
+    _ => children(term).into_iter().map(|x| count_refs(x, nam)).sum(),
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::parse::{
+    package,
+    span::Span,
+    term::{
+      input_cid,
+      parse,
+    },
+  };
+
+  fn debugger(input: &str, capacity: usize) -> Debugger {
+    let (_, term) = parse(input, Defs::new()).unwrap();
+    Debugger::new(&term, capacity)
+  }
+
+  #[test]
+  fn test_forward_back() {
+    let defs = Defs::new();
+    let mut dbg = debugger("(λ x y => x) ((λ z => z) Type) Type", 10);
+    let start = format!("{}", dbg.current());
+    let mut seen = vec![start.clone()];
+    while let Some(term) = dbg.forward(&defs) {
+      seen.push(format!("{}", term));
+    }
+    assert_eq!(seen.last().unwrap(), "Type");
+    assert_eq!(dbg.steps() as usize, seen.len() - 1);
+    for expected in seen.iter().rev().skip(1) {
+      assert_eq!(&format!("{}", dbg.back().unwrap()), expected);
+    }
+    assert!(dbg.back().is_none());
+    assert_eq!(format!("{}", dbg.forward(&defs).unwrap()), seen[1]);
+  }
+
+  #[test]
+  fn test_history_capacity() {
+    let defs = Defs::new();
+    let mut dbg = debugger("(λ x y => x) ((λ z => z) Type) Type", 1);
+    while dbg.forward(&defs).is_some() {}
+    assert!(dbg.back().is_none());
+    assert_eq!(format!("{}", dbg.current()), "Type");
+  }
+
+  #[test]
+  fn test_step_redex() {
+    let defs = Defs::new();
+    let mut dbg = debugger("λ a => a ((λ x => x) Type)", 10);
+    assert!(dbg.last_step().is_none());
+    dbg.forward(&defs).unwrap();
+    let step = dbg.last_step().unwrap();
+    assert_eq!(format!("{}", step.redex), "(λ x => x) Type");
+    assert_eq!(format!("{}", step.result), "Type");
+    assert_eq!(
+      format!("{}", step.context.as_ref().unwrap()),
+      "a ((λ x => x) Type)"
+    );
+  }
+
+  #[test]
+  fn test_breakpoint() {
+    let src = "def two: #U64 = #U64.add 1u64 1u64";
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    let (_, term) = parse("#U64.mul two 3u64", defs.clone()).unwrap();
+    let mut dbg = Debugger::new(&term, 10);
+    dbg.add_breakpoint(Name::from("two"));
+    assert_eq!(dbg.run(&defs, 100), Stop::Breakpoint(Name::from("two")));
+    assert!(dbg.remove_breakpoint(&Name::from("two")));
+    assert_eq!(dbg.run(&defs, 100), Stop::Done);
+    assert_eq!(format!("{}", dbg.current()), "6u64");
+    assert_eq!(dbg.run(&defs, 100), Stop::Done);
+  }
+}
@@ -1,13 +1,16 @@
 use crate::{
   anon::Anon,
+  check::conv::ConvCache,
   embed_error::EmbedError,
   name::Name,
   package::{
     import_alias,
+    Attrs,
     Entry,
     Import,
   },
   position::Pos,
+  prim::host::HostOps,
   term::Term,
 };
 
@@ -34,6 +37,7 @@ pub struct Def {
   pub ast_cid: Cid,
   pub typ_: Term,
   pub term: Term,
+  pub attrs: Attrs,
 }
 
 impl PartialEq for Def {
@@ -42,19 +46,35 @@ impl PartialEq for Def {
       && self.ast_cid == other.ast_cid
       && self.typ_ == other.typ_
       && self.term == other.term
+      && self.attrs == other.attrs
   }
 }
 
-/// A map of content ids to defs, with content ids for the def
+/// A map of content ids to defs, with content ids for the def, the host
+/// operations they may refer to, and the outcomes of conversion checks
+/// between terms over them
 #[derive(PartialEq, Clone, Debug)]
 pub struct Defs {
   pub defs: BTreeMap<Cid, Def>,
   pub names: BTreeMap<Name, Cid>,
+  pub host: HostOps,
+  pub conv: ConvCache,
 }
 
 impl Def {
   /// Creates a def and a corresponding package entry
   pub fn make(pos: Pos, typ_: Term, term: Term) -> (Self, Entry) {
+    Self::make_with(pos, typ_, term, Attrs::default())
+  }
+
+  /// Creates a def with the given attributes and a corresponding package
+  /// entry
+  pub fn make_with(
+    pos: Pos,
+    typ_: Term,
+    term: Term,
+    attrs: Attrs,
+  ) -> (Self, Entry) {
     let (type_anon, type_meta) = typ_.embed();
     let (term_anon, term_meta) = term.embed();
     let ast_cid = term_anon.cid();
@@ -64,11 +84,15 @@ impl Def {
       type_meta,
       term_anon: ast_cid,
       term_meta,
+      attrs,
     };
-    let def = Def { pos, def_cid: defn.cid(), ast_cid, typ_, term };
+    let def = Def { pos, def_cid: defn.cid(), ast_cid, typ_, term, attrs };
     (def, defn)
   }
 
+  /// The content id of the def's package entry
+  pub fn cid(&self) -> Cid { self.def_cid }
+
   /// Embeds the def's data and type into a package entry
   pub fn embed(&self) -> (Entry, Anon, Anon) {
     let (type_anon, type_meta) = self.typ_.embed();
@@ -79,6 +103,7 @@ impl Def {
       term_anon: self.ast_cid,
       type_meta,
       term_meta,
+      attrs: self.attrs,
     };
     (d, type_anon, term_anon)
   }
@@ -97,13 +122,15 @@ impl Def {
       ast_cid: def.term_anon,
       typ_,
       term,
+      attrs: def.attrs,
     })
   }
 
   /// Formats the def for pretty-printing
   pub fn pretty(&self, name: String, ind: bool) -> String {
     format!(
-      "def {} : {} = {}",
+      "{}def {} : {} = {}",
+      self.attrs,
       name,
       self.typ_.pretty(Some(&name), ind),
       self.term.pretty(Some(&name), ind)
@@ -114,7 +141,12 @@ impl Def {
 impl Defs {
   /// Creates a new map of content ids to defs and names to content ids
   pub fn new() -> Self {
-    Defs { defs: BTreeMap::new(), names: BTreeMap::new() }
+    Defs {
+      defs: BTreeMap::new(),
+      names: BTreeMap::new(),
+      host: HostOps::new(),
+      conv: ConvCache::new(),
+    }
   }
 
   /// Gets a list of the name keys in sorted order
@@ -155,7 +187,9 @@ impl Defs {
       let v = other.names.get(&k).unwrap();
       names.insert(import_alias(k, import), *v);
     }
-    Defs { defs, names }
+    let mut host = self.host;
+    host.extend(&other.host);
+    Defs { defs, names, host, conv: self.conv }
   }
 
   /// Merges Defs mutably at the same level like in a REPL env
@@ -166,6 +200,7 @@ impl Defs {
     for (k, v) in other.names.iter() {
       self.names.insert(k.clone(), *v);
     }
+    self.host.extend(&other.host);
   }
 
   /// Merges Defs at the same level like in a REPL env
@@ -178,7 +213,9 @@ impl Defs {
     for (k, v) in other.names.iter() {
       names.insert(k.clone(), *v);
     }
-    Defs { defs, names }
+    let mut host = self.host;
+    host.extend(&other.host);
+    Defs { defs, names, host, conv: self.conv }
   }
 }
 
@@ -198,7 +235,8 @@ impl fmt::Display for Defs {
       writeln!(f, "{}:", def.def_cid)?;
       writeln!(
         f,
-        "def {} : {} = {}",
+        "{}def {} : {} = {}",
+        def.attrs,
         k.clone(),
         def.typ_.pretty(Some(&k.to_string()), false),
         def.term.pretty(Some(&k.to_string()), false),
@@ -225,7 +263,7 @@ pub mod tests {
   pub fn arbitrary_def(g: &mut Gen) -> (Def, Entry) {
     let typ_: Term = Arbitrary::arbitrary(g);
     let term = arbitrary_term(g, true, test_defs(), Vector::new());
-    Def::make(Pos::None, typ_, term)
+    Def::make_with(Pos::None, typ_, term, Arbitrary::arbitrary(g))
   }
 
   impl Arbitrary for Def {
@@ -0,0 +1,458 @@
+use crate::{
+  check::infer_lit,
+  defs::Defs,
+  name::Name,
+  position::Pos,
+  term::{
+    Level,
+    Term,
+  },
+};
+
+use sp_std::{
+  boxed::Box,
+  fmt,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// An error raised while inserting implicit arguments
+#[derive(PartialEq, Clone, Debug)]
+pub enum ElabError {
+  /// No argument could be found for the named implicit binder of the applied
+  /// function
+  Unsolved(Pos, String, Name),
+}
+
+impl fmt::Display for ElabError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Unsolved(_, fun, arg) => {
+        write!(f, "Cannot infer the implicit argument {{{}}} of {}", arg, fun)
+      }
+    }
+  }
+}
+
+/// Inserts the implicit arguments in the body of a definition of type `typ`
+pub fn elaborate(
+  defs: &Defs,
+  typ: &Term,
+  term: Term,
+) -> Result<Term, ElabError> {
+  let elab = Elab { defs, rec: Some(typ) };
+  Ok(elab.term(&mut Vec::new(), term, Some(typ))?.0)
+}
+
+/// Inserts the implicit arguments in a term with no expected type, such as an
+/// expression entered in the REPL
+pub fn elaborate_term(defs: &Defs, term: Term) -> Result<Term, ElabError> {
+  let elab = Elab { defs, rec: None };
+  Ok(elab.term(&mut Vec::new(), term, None)?.0)
+}
+
+/// The types of the variables in scope, innermost last. Variables bound where
+/// no type is known, as by a lambda with no expected type, have none.
+type Ctx = Vec<Option<Term>>;
+
+/// A binder of the function type at the head of an application, with the
+/// argument given or inferred for it
+struct Binder {
+  imp: bool,
+  nam: Name,
+  pos: Pos,
+  arg: Option<Term>,
+}
+
+/// Implicit arguments are solved by first-order matching. At each
+/// application the domains of the function type are matched against the
+/// types of the explicit arguments, left to right, and what remains of the
+/// function type against the expected type of the application. Implicit
+/// binders act as the metavariables, solved by the first subterm they meet.
+/// Whatever else doesn't match is left for the typechecker to report.
+struct Elab<'a> {
+  defs: &'a Defs,
+  /// The type of the definition, for its recursive calls
+  rec: Option<&'a Term>,
+}
+
+impl<'a> Elab<'a> {
+  /// Elaborates a term in a context, returning it along with its type if
+  /// that's easily known
+  fn term(
+    &self,
+    ctx: &mut Ctx,
+    term: Term,
+    expected: Option<&Term>,
+  ) -> Result<(Term, Option<Term>), ElabError> {
+    match term {
+      Term::App(..) | Term::Var(..) | Term::Ref(..) | Term::Rec(_) => {
+        self.spine(ctx, term, expected)
+      }
+      Term::Lam(pos, nam, bod) => {
+        let (dom, img) = match expected {
+          Some(Term::All(_, _, _, _, dom_img)) => {
+            (Some(dom_img.0.clone()), Some(&dom_img.1))
+          }
+          _ => (None, None),
+        };
+        ctx.push(dom);
+        let (bod, _) = self.term(ctx, *bod, img)?;
+        ctx.pop();
+        Ok((Term::Lam(pos, nam, Box::new(bod)), expected.cloned()))
+      }
+      Term::All(pos, imp, uses, nam, dom_img) => {
+        let (dom, img) = *dom_img;
+        let (dom, dom_typ) = self.term(ctx, dom, None)?;
+        ctx.push(Some(dom.clone()));
+        let (img, img_typ) = self.term(ctx, img, None)?;
+        ctx.pop();
+        let typ = match (dom_typ, img_typ) {
+          (Some(Term::Typ(_, dom_lvl)), Some(Term::Typ(_, img_lvl))) => {
+            Some(Term::Typ(Pos::None, Level::imax(&dom_lvl, &img_lvl)))
+          }
+          _ => None,
+        };
+        Ok((Term::All(pos, imp, uses, nam, Box::new((dom, img))), typ))
+      }
+      Term::Slf(pos, nam, bod) => {
+        ctx.push(None);
+        let (bod, typ) = self.term(ctx, *bod, None)?;
+        ctx.pop();
+        Ok((Term::Slf(pos, nam, Box::new(bod)), typ))
+      }
+      Term::Dat(pos, bod) => {
+        let (bod, _) = self.term(ctx, *bod, None)?;
+        Ok((Term::Dat(pos, Box::new(bod)), None))
+      }
+      Term::Cse(pos, bod) => {
+        let (bod, _) = self.term(ctx, *bod, None)?;
+        Ok((Term::Cse(pos, Box::new(bod)), None))
+      }
+      Term::Ann(pos, typ_exp) => {
+        let (typ, exp) = *typ_exp;
+        let (typ, _) = self.term(ctx, typ, None)?;
+        let (exp, _) = self.term(ctx, exp, Some(&typ))?;
+        Ok((Term::Ann(pos, Box::new((typ.clone(), exp))), Some(typ)))
+      }
+      Term::Let(pos, rec, uses, nam, typ_exp_bod) => {
+        let (typ, exp, bod) = *typ_exp_bod;
+        let (typ, _) = self.term(ctx, typ, None)?;
+        // A recursive let's expression is under its own binder
+        let exp = if rec {
+          ctx.push(Some(typ.clone()));
+          let (exp, _) =
+            self.term(ctx, exp, Some(&typ.clone().shift(1, Some(0))))?;
+          ctx.pop();
+          exp
+        }
+        else {
+          self.term(ctx, exp, Some(&typ))?.0
+        };
+        ctx.push(Some(typ.clone()));
+        let expected = expected.map(|x| x.clone().shift(1, Some(0)));
+        let (bod, _) = self.term(ctx, bod, expected.as_ref())?;
+        ctx.pop();
+        Ok((Term::Let(pos, rec, uses, nam, Box::new((typ, exp, bod))), None))
+      }
+      Term::Lit(_, ref lit) => {
+        let typ = infer_lit(lit.clone());
+        Ok((term, Some(typ)))
+      }
+      Term::Opr(_, ref opr) => {
+        let typ = opr.type_of();
+        Ok((term, Some(typ)))
+      }
+      Term::LTy(..) => Ok((term, Some(Term::Typ(Pos::None, Level::zero())))),
+      Term::Typ(_, ref lvl) => {
+        let typ = Term::Typ(Pos::None, lvl.succ());
+        Ok((term, Some(typ)))
+      }
+    }
+  }
+
+  /// The type of a variable, reference or recursive call, if known
+  fn head_type(&self, ctx: &Ctx, head: &Term) -> Option<Term> {
+    match head {
+      Term::Var(_, _, idx) => {
+        let typ = ctx.iter().rev().nth(*idx as usize).cloned().flatten()?;
+        Some(typ.shift(*idx as i64 + 1, Some(0)))
+      }
+      Term::Ref(_, _, def_cid, _) => {
+        self.defs.defs.get(def_cid).map(|def| def.typ_.clone())
+      }
+      Term::Rec(_) => self.rec.cloned(),
+      _ => None,
+    }
+  }
+
+  /// Elaborates an application spine, inserting an argument for each
+  /// implicit binder of the function type that comes before an explicit
+  /// argument, or before the end of the spine if its type is expected
+  fn spine(
+    &self,
+    ctx: &mut Ctx,
+    term: Term,
+    expected: Option<&Term>,
+  ) -> Result<(Term, Option<Term>), ElabError> {
+    let mut args = Vec::new();
+    let mut head = term;
+    while let Term::App(pos, fun_arg) = head {
+      let (fun, arg) = *fun_arg;
+      args.push((pos, arg));
+      head = fun;
+    }
+    let pos = args.first().map_or(head.pos(), |(pos, _)| *pos);
+    args.reverse();
+    let (head, typ) = match head {
+      Term::Var(..) | Term::Ref(..) | Term::Rec(_) => {
+        let typ = self.head_type(ctx, &head);
+        (head, typ)
+      }
+      // A redex, such as a let without a type, binds its variable to the
+      // type of its argument
+      Term::Lam(lam_pos, nam, bod) if !args.is_empty() => {
+        let (arg_pos, arg) = args.remove(0);
+        let (arg, arg_typ) = self.term(ctx, arg, None)?;
+        let bod_expected = if args.is_empty() {
+          expected.map(|x| x.clone().shift(1, Some(0)))
+        }
+        else {
+          None
+        };
+        ctx.push(arg_typ);
+        let (bod, bod_typ) = self.term(ctx, *bod, bod_expected.as_ref())?;
+        ctx.pop();
+        let typ =
+          bod_typ.filter(|x| !mentions(x, 1, 0)).map(|x| x.shift(-1, Some(0)));
+        let lam = Term::Lam(lam_pos, nam, Box::new(bod));
+        (Term::App(arg_pos, Box::new((lam, arg))), typ)
+      }
+      head => self.term(ctx, head, None)?,
+    };
+    let mut args = args.into_iter().peekable();
+    let mut tele: Vec<Binder> = Vec::new();
+    let mut rest = typ;
+    loop {
+      match rest {
+        Some(Term::All(_, true, _, nam, dom_img))
+          if args.peek().is_some() || expected.is_some() =>
+        {
+          tele.push(Binder { imp: true, nam, pos: head.pos(), arg: None });
+          rest = Some(dom_img.1);
+        }
+        Some(Term::All(_, false, _, nam, dom_img)) if args.peek().is_some() => {
+          let (pos, arg) = args.next().unwrap();
+          let (dom, img) = *dom_img;
+          let arg_dom = instantiate(&dom, &tele, 0);
+          let (arg, arg_typ) = self.term(ctx, arg, arg_dom.as_ref())?;
+          if let Some(arg_typ) = arg_typ {
+            solve(&dom, &arg_typ, &mut tele, 0);
+          }
+          tele.push(Binder { imp: false, nam, pos, arg: Some(arg) });
+          rest = Some(img);
+        }
+        other => {
+          rest = other;
+          break;
+        }
+      }
+    }
+    let done = args.peek().is_none();
+    if let (Some(rest), Some(expected), true) = (&rest, expected, done) {
+      solve(rest, expected, &mut tele, 0);
+    }
+    if let Some(b) = tele.iter().find(|b| b.arg.is_none()) {
+      return Err(ElabError::Unsolved(pos, head.to_string(), b.nam.clone()));
+    }
+    let typ = match rest {
+      Some(rest) if done => instantiate(&rest, &tele, 0),
+      _ => None,
+    };
+    let mut term = head;
+    for Binder { pos, arg, .. } in tele {
+      term = Term::App(pos, Box::new((term, arg.unwrap())));
+    }
+    for (pos, arg) in args {
+      let (arg, _) = self.term(ctx, arg, None)?;
+      term = Term::App(pos, Box::new((term, arg)));
+    }
+    Ok((term, typ))
+  }
+}
+
+/// Substitutes the arguments of the binders in `tele` for their variables in
+/// `term`, which is under `local` binders of its own. Returns `None` if an
+/// argument it needs isn't known yet.
+fn instantiate(term: &Term, tele: &[Binder], local: u64) -> Option<Term> {
+  let len = tele.len() as u64;
+  match term {
+    Term::Var(pos, nam, idx) => {
+      if *idx < local {
+        Some(term.clone())
+      }
+      else if *idx < local + len {
+        let binder = &tele[(len - 1 - (idx - local)) as usize];
+        binder.arg.clone().map(|arg| arg.shift(local as i64, Some(0)))
+      }
+      else {
+        Some(Term::Var(*pos, nam.clone(), idx - len))
+      }
+    }
+    Term::Lam(pos, nam, bod) => {
+      let bod = instantiate(bod, tele, local + 1)?;
+      Some(Term::Lam(*pos, nam.clone(), Box::new(bod)))
+    }
+    Term::Slf(pos, nam, bod) => {
+      let bod = instantiate(bod, tele, local + 1)?;
+      Some(Term::Slf(*pos, nam.clone(), Box::new(bod)))
+    }
+    Term::Dat(pos, bod) => {
+      Some(Term::Dat(*pos, Box::new(instantiate(bod, tele, local)?)))
+    }
+    Term::Cse(pos, bod) => {
+      Some(Term::Cse(*pos, Box::new(instantiate(bod, tele, local)?)))
+    }
+    Term::App(pos, fun_arg) => {
+      let fun = instantiate(&fun_arg.0, tele, local)?;
+      let arg = instantiate(&fun_arg.1, tele, local)?;
+      Some(Term::App(*pos, Box::new((fun, arg))))
+    }
+    Term::Ann(pos, typ_exp) => {
+      let typ = instantiate(&typ_exp.0, tele, local)?;
+      let exp = instantiate(&typ_exp.1, tele, local)?;
+      Some(Term::Ann(*pos, Box::new((typ, exp))))
+    }
+    Term::All(pos, imp, uses, nam, dom_img) => {
+      let dom = instantiate(&dom_img.0, tele, local)?;
+      let img = instantiate(&dom_img.1, tele, local + 1)?;
+      Some(Term::All(*pos, *imp, *uses, nam.clone(), Box::new((dom, img))))
+    }
+    Term::Let(pos, rec, uses, nam, typ_exp_bod) => {
+      let typ = instantiate(&typ_exp_bod.0, tele, local)?;
+      let exp = instantiate(&typ_exp_bod.1, tele, local + *rec as u64)?;
+      let bod = instantiate(&typ_exp_bod.2, tele, local + 1)?;
+      let xs = Box::new((typ, exp, bod));
+      Some(Term::Let(*pos, *rec, *uses, nam.clone(), xs))
+    }
+    _ => Some(term.clone()),
+  }
+}
+
+/// Matches the pattern `pat`, which is under the binders of `tele`, against
+/// `typ`, solving the implicit binders of `tele` that occur in the pattern.
+/// Both are under `local` binders of their own.
+fn solve(pat: &Term, typ: &Term, tele: &mut [Binder], local: u64) {
+  let len = tele.len() as u64;
+  match (pat, typ) {
+    (Term::Var(_, _, idx), _) if *idx >= local && *idx < local + len => {
+      let binder = &mut tele[(len - 1 - (idx - local)) as usize];
+      if binder.imp && binder.arg.is_none() && !mentions(typ, local, 0) {
+        binder.arg = Some(typ.clone().shift(-(local as i64), Some(0)));
+      }
+    }
+    (Term::App(_, p), Term::App(_, t)) | (Term::Ann(_, p), Term::Ann(_, t)) => {
+      solve(&p.0, &t.0, tele, local);
+      solve(&p.1, &t.1, tele, local);
+    }
+    (Term::All(_, _, _, _, p), Term::All(_, _, _, _, t)) => {
+      solve(&p.0, &t.0, tele, local);
+      solve(&p.1, &t.1, tele, local + 1);
+    }
+    (Term::Lam(_, _, p), Term::Lam(_, _, t))
+    | (Term::Slf(_, _, p), Term::Slf(_, _, t)) => solve(p, t, tele, local + 1),
+    (Term::Dat(_, p), Term::Dat(_, t)) | (Term::Cse(_, p), Term::Cse(_, t)) => {
+      solve(p, t, tele, local)
+    }
+    _ => (),
+  }
+}
+
+/// Whether `term`, under `dep` binders of its own, refers to any of the
+/// innermost `local` variables of its context
+fn mentions(term: &Term, local: u64, dep: u64) -> bool {
+  match term {
+    Term::Var(_, _, idx) => *idx >= dep && *idx < dep + local,
+    Term::Lam(_, _, bod) | Term::Slf(_, _, bod) => {
+      mentions(bod, local, dep + 1)
+    }
+    Term::Dat(_, bod) | Term::Cse(_, bod) => mentions(bod, local, dep),
+    Term::App(_, xs) | Term::Ann(_, xs) => {
+      mentions(&xs.0, local, dep) || mentions(&xs.1, local, dep)
+    }
+    Term::All(_, _, _, _, xs) => {
+      mentions(&xs.0, local, dep) || mentions(&xs.1, local, dep + 1)
+    }
+    Term::Let(_, rec, _, _, xs) => {
+      mentions(&xs.0, local, dep)
+        || mentions(&xs.1, local, dep + *rec as u64)
+        || mentions(&xs.2, local, dep + 1)
+    }
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    check::check_def,
+    parse::{
+      package,
+      span::Span,
+      term::input_cid,
+    },
+  };
+  use sp_std::rc::Rc;
+
+  fn defs(src: &str) -> Defs {
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    defs
+  }
+
+  #[test]
+  fn test_implicit_args() {
+    let defs = defs(
+      "def id {A: Type} (x: A): A = x\n\
+       def pick: ∀ {A: Type} (x y: A) -> A = λ A x y => x\n\
+       def two: #U64 = id 2u64\n\
+       def three: #U64 = pick (id 3u64) 4u64\n\
+       def text: ∀ #Text -> #Text = id\n\
+       def twice {A: Type} (f: ∀ A -> A) (x: A): A = f (f x)\n\
+       def four: #U64 = twice (λ x => #U64.add x 1u64) (id 2u64)",
+    );
+    let term =
+      |nam: &str| format!("{}", defs.get(&Name::from(nam)).unwrap().term);
+    assert_eq!(term("two"), "id #U64 2u64");
+    assert_eq!(term("three"), "pick #U64 (id #U64 3u64) 4u64");
+    assert_eq!(term("text"), "id #Text");
+    assert_eq!(term("twice"), "λ A f x => f (f x)");
+    assert_eq!(
+      term("four"),
+      "twice #U64 (λ x => #U64.add x 1u64) (id #U64 2u64)"
+    );
+    let defs = Rc::new(defs);
+    for nam in ["id", "pick", "two", "three", "text", "twice", "four"].iter() {
+      assert!(check_def(defs.clone(), nam, false).is_ok(), "{}", nam);
+    }
+  }
+
+  #[test]
+  fn test_implicit_binders() {
+    let defs = defs("def pick {A B: Type} (x: A) (y: B): A = x");
+    let typ = &defs.get(&Name::from("pick")).unwrap().typ_;
+    assert_eq!(format!("{}", typ), "∀ {A: Type} {B: Type} (x: A) (y: B) -> A");
+    // Implicitness is metadata, and survives embedding
+    let (anon, meta) = typ.embed();
+    let meta = crate::meta::Meta::from_ipld(&meta.to_ipld()).unwrap();
+    assert_eq!(&Term::unembed(&anon, &meta).unwrap(), typ);
+    let src = "def id {A: Type} (x: A): A = x\ndef bad: Type = λ y => id y";
+    let res = package::parse_defs(input_cid(src), Defs::new())(Span::new(src));
+    assert!(res.is_err());
+  }
+}
@@ -0,0 +1,306 @@
+use crate::{
+  defs::{
+    Def,
+    Defs,
+  },
+  literal::Literal,
+  name::Name,
+  term::{
+    Op,
+    Term,
+  },
+  uses::Uses,
+};
+
+use sp_cid::Cid;
+
+use sp_std::{
+  boxed::Box,
+  collections::btree_set::BTreeSet,
+  fmt,
+  vec::Vec,
+};
+
+/// An untyped term, with its types and the arguments of `Uses::None` binders
+/// replaced by `Erased`. Variables keep their de Bruijn indices, since no
+/// binder is removed: an erased argument still fills its binder, which the
+/// body can't use.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Erased {
+  Var(Name, u64),
+  Lam(Name, Box<Erased>),
+  App(Box<(Erased, Erased)>),
+  Let(bool, Name, Box<(Erased, Erased)>),
+  /// A reference to a def, by the CID of its entry
+  Ref(Name, Cid),
+  /// A recursive reference to the def being erased
+  Rec,
+  Lit(Literal),
+  Opr(Op),
+  /// A type, or an argument only types or proofs depend on
+  Erased,
+}
+
+/// Erases a def, whose type tells which of its binders are irrelevant
+pub fn erase_def(defs: &Defs, def: &Def) -> Erased {
+  let eraser = Eraser { defs, rec: Some(&def.typ_) };
+  eraser.term(&mut Vec::new(), &def.term, Some(&def.typ_))
+}
+
+/// Erases a term with no known type, such as an expression to evaluate.
+/// Only the types within it, and the arguments of the defs it applies, are
+/// known to be irrelevant.
+pub fn erase_term(defs: &Defs, term: &Term) -> Erased {
+  let eraser = Eraser { defs, rec: None };
+  eraser.term(&mut Vec::new(), term, None)
+}
+
+/// The types of the variables in scope, innermost last, where known
+type Ctx = Vec<Option<Term>>;
+
+struct Eraser<'a> {
+  defs: &'a Defs,
+  /// The type of the def being erased, for its recursive calls
+  rec: Option<&'a Term>,
+}
+
+impl<'a> Eraser<'a> {
+  fn term(
+    &self,
+    ctx: &mut Ctx,
+    term: &Term,
+    expected: Option<&Term>,
+  ) -> Erased {
+    match term {
+      Term::Var(_, nam, idx) => Erased::Var(nam.clone(), *idx),
+      Term::Lam(_, nam, bod) => {
+        let (dom, img) = match expected.and_then(|x| self.unfold(x)) {
+          Some(Term::All(_, _, _, _, dom_img)) => {
+            let (dom, img) = *dom_img;
+            (Some(dom), Some(img))
+          }
+          _ => (None, None),
+        };
+        ctx.push(dom);
+        let bod = self.term(ctx, bod, img.as_ref());
+        ctx.pop();
+        Erased::Lam(nam.clone(), Box::new(bod))
+      }
+      Term::App(..) => self.spine(ctx, term),
+      Term::Ann(_, typ_exp) => self.term(ctx, &typ_exp.1, Some(&typ_exp.0)),
+      Term::Dat(_, bod) | Term::Cse(_, bod) => self.term(ctx, bod, None),
+      Term::Let(_, rec, uses, nam, typ_exp_bod) => {
+        let (typ, exp, bod) = typ_exp_bod.as_ref();
+        let exp = if *uses == Uses::None {
+          Erased::Erased
+        }
+        else if *rec {
+          ctx.push(Some(typ.clone()));
+          let exp = self.term(ctx, exp, Some(&typ.clone().shift(1, Some(0))));
+          ctx.pop();
+          exp
+        }
+        else {
+          self.term(ctx, exp, Some(typ))
+        };
+        ctx.push(Some(typ.clone()));
+        let expected = expected.map(|x| x.clone().shift(1, Some(0)));
+        let bod = self.term(ctx, bod, expected.as_ref());
+        ctx.pop();
+        Erased::Let(*rec, nam.clone(), Box::new((exp, bod)))
+      }
+      Term::Ref(_, nam, def_cid, _) => Erased::Ref(nam.clone(), *def_cid),
+      Term::Rec(_) => Erased::Rec,
+      Term::Lit(_, lit) => Erased::Lit(lit.clone()),
+      Term::Opr(_, opr) => Erased::Opr(opr.clone()),
+      Term::Typ(..) | Term::All(..) | Term::Slf(..) | Term::LTy(..) => {
+        Erased::Erased
+      }
+    }
+  }
+
+  /// Erases an application spine, dropping the arguments of the binders of
+  /// the head's type that have no uses
+  fn spine(&self, ctx: &mut Ctx, term: &Term) -> Erased {
+    let mut args = Vec::new();
+    let mut head = term;
+    while let Term::App(_, fun_arg) = head {
+      args.push(&fun_arg.1);
+      head = &fun_arg.0;
+    }
+    args.reverse();
+    // Only the quantities of the binders matter, so the image of each `∀`
+    // is walked into without substituting the argument
+    let mut typ = self.head_type(ctx, head);
+    let mut res = self.term(ctx, head, None);
+    for arg in args {
+      let uses = match typ.take().and_then(|x| self.unfold(&x)) {
+        Some(Term::All(_, _, uses, _, dom_img)) => {
+          typ = Some(dom_img.1);
+          Some(uses)
+        }
+        _ => None,
+      };
+      let arg = match uses {
+        Some(Uses::None) => Erased::Erased,
+        _ => self.term(ctx, arg, None),
+      };
+      res = Erased::App(Box::new((res, arg)));
+    }
+    res
+  }
+
+  /// The type of a variable, reference or recursive call, if known
+  fn head_type(&self, ctx: &Ctx, head: &Term) -> Option<Term> {
+    match head {
+      Term::Var(_, _, idx) => {
+        let typ = ctx.iter().rev().nth(*idx as usize).cloned().flatten()?;
+        Some(typ.shift(*idx as i64 + 1, Some(0)))
+      }
+      Term::Ref(_, _, def_cid, _) => {
+        self.defs.defs.get(def_cid).map(|def| def.typ_.clone())
+      }
+      Term::Rec(_) => self.rec.cloned(),
+      _ => None,
+    }
+  }
+
+  /// Unfolds the references and annotations at the head of a type, such as
+  /// an alias for a function type
+  fn unfold(&self, typ: &Term) -> Option<Term> {
+    let mut seen = BTreeSet::new();
+    let mut typ = typ;
+    loop {
+      match typ {
+        Term::Ref(_, _, def_cid, _) if seen.insert(*def_cid) => {
+          typ = &self.defs.defs.get(def_cid)?.term;
+        }
+        Term::Ann(_, typ_exp) => typ = &typ_exp.1,
+        _ => return Some(typ.clone()),
+      }
+    }
+  }
+}
+
+impl Erased {
+  fn is_atom(&self) -> bool {
+    matches!(
+      self,
+      Self::Var(..)
+        | Self::Ref(..)
+        | Self::Rec
+        | Self::Lit(_)
+        | Self::Opr(_)
+        | Self::Erased
+    )
+  }
+}
+
+impl fmt::Display for Erased {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    fn name(nam: &str) -> &str {
+      if nam.is_empty() {
+        "_"
+      }
+      else {
+        nam
+      }
+    }
+    match self {
+      Self::Var(nam, _) | Self::Ref(nam, _) => write!(f, "{}", nam),
+      Self::Rec => write!(f, "#^"),
+      Self::Lit(lit) => write!(f, "{}", lit),
+      Self::Opr(opr) => write!(f, "{}", opr),
+      Self::Erased => write!(f, "□"),
+      Self::Lam(nam, bod) => {
+        write!(f, "λ {}", name(nam))?;
+        let mut bod = bod;
+        while let Self::Lam(nam, inner) = bod.as_ref() {
+          write!(f, " {}", name(nam))?;
+          bod = inner;
+        }
+        write!(f, " => {}", bod)
+      }
+      Self::App(fun_arg) => {
+        let (fun, arg) = fun_arg.as_ref();
+        match fun {
+          Self::App(_) => write!(f, "{}", fun)?,
+          fun if fun.is_atom() => write!(f, "{}", fun)?,
+          fun => write!(f, "({})", fun)?,
+        }
+        if arg.is_atom() {
+          write!(f, " {}", arg)
+        }
+        else {
+          write!(f, " ({})", arg)
+        }
+      }
+      Self::Let(rec, nam, exp_bod) => write!(
+        f,
+        "let{} {} = {}; {}",
+        if *rec { "rec" } else { "" },
+        name(nam),
+        exp_bod.0,
+        exp_bod.1
+      ),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::parse::{
+    package,
+    span::Span,
+    term::{
+      input_cid,
+      parse,
+    },
+  };
+
+  fn defs(src: &str) -> Defs {
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    defs
+  }
+
+  fn erased(defs: &Defs, nam: &str) -> String {
+    format!("{}", erase_def(defs, defs.get(&Name::from(nam)).unwrap()))
+  }
+
+  #[test]
+  fn test_erase_def() {
+    let defs = defs(
+      "def id (0 A: Type) (x: A): A = x\n\
+       def two: #U64 = id #U64 2u64\n\
+       def const (0 n: #U64) (x: #U64): #U64 = x\n\
+       def five: #U64 = const (#U64.add 1u64 2u64) 5u64\n\
+       def Fun: Type = ∀ (0 n: #U64) (x: #U64) -> #U64\n\
+       def apply (f: Fun): #U64 = f 1u64 2u64\n\
+       def loop (0 n: #U64) (x: #U64): #U64 = loop (#U64.add n 1u64) x",
+    );
+    assert_eq!(erased(&defs, "id"), "λ A x => x");
+    assert_eq!(erased(&defs, "two"), "id □ 2u64");
+    assert_eq!(erased(&defs, "five"), "const □ 5u64");
+    // Binder quantities are found through aliases of function types
+    assert_eq!(erased(&defs, "apply"), "λ f => f □ 2u64");
+    assert_eq!(erased(&defs, "loop"), "λ n x => #^ □ x");
+  }
+
+  #[test]
+  fn test_erase_term() {
+    let defs = defs("def id (0 A: Type) (x: A): A = x");
+    let erase = |src: &str| {
+      let (_, term) = parse(src, defs.clone()).unwrap();
+      format!("{}", erase_term(&defs, &term))
+    };
+    assert_eq!(erase("id (∀ #U64 -> #U64) (λ x => x)"), "id □ (λ x => x)");
+    assert_eq!(erase("(λ x => x) :: ∀ #U64 -> #U64"), "λ x => x");
+    assert_eq!(
+      erase("let 0 x: #U64 = 1u64; (λ y => y) x"),
+      "let x = □; (λ y => y) x"
+    );
+    assert_eq!(erase("(case (data 1u64)) Type"), "1u64 □");
+  }
+}
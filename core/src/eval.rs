@@ -2,11 +2,28 @@ use core::ptr::NonNull;
 
 use crate::{
   dag::*,
-  defs::Defs,
+  defs::{
+    Def,
+    Defs,
+  },
   dll::*,
+  literal::Literal,
+  name::Name,
+  prim::{
+    cost::CostModel,
+    exception::ExceptionOp,
+  },
+  term::Op,
+  trace::{
+    NoTrace,
+    StepKind,
+    TraceSink,
+  },
   upcopy::*,
 };
 
+use sp_ropey::Rope;
+
 use sp_std::{
   collections::btree_map::BTreeMap,
   mem,
@@ -15,6 +32,16 @@ use sp_std::{
 
 use alloc::string::String;
 
+/// Keeps the evaluator from unfolding the defs that conversion checking
+/// compares by reference
+struct Conv;
+
+impl TraceSink for Conv {
+  fn step(&mut self, _: &StepKind, _: Option<&Name>) {}
+
+  fn unfolds(&mut self, def: &Def) -> bool { def.attrs.unfolds() }
+}
+
 enum Single {
   Lam(Var),
   Slf(Var),
@@ -301,6 +328,130 @@ pub fn reduce_let(redex: NonNull<Let>, should_count: bool) -> DAGPtr {
   top_node
 }
 
+/// Gets the tag and payload of an exception in weak head normal form. A
+/// primitive operation that is stuck on literal arguments counts as an
+/// exception tagged with the operation's name
+pub fn caught(node: DAGPtr) -> Option<(Rope, Vec<u8>)> {
+  let mut head = node;
+  let mut args = 0;
+  while let DAGPtr::App(link) = head {
+    let App { fun, arg, .. } = unsafe { link.as_ref() };
+    match arg {
+      DAGPtr::Lit(_) => args += 1,
+      _ => return None,
+    }
+    head = *fun;
+  }
+  match head {
+    DAGPtr::Lit(link) => match unsafe { &link.as_ref().lit } {
+      Literal::Exception(tag, payload) if args == 0 => {
+        Some((tag.clone(), payload.clone()))
+      }
+      _ => None,
+    },
+    DAGPtr::Opr(link) => {
+      let opr = unsafe { &link.as_ref().opr };
+      if args > 0 && opr.arity() == args {
+        Some((Rope::from(opr.symbol()), vec![]))
+      }
+      else {
+        None
+      }
+    }
+    _ => None,
+  }
+}
+
+/// Checks whether a literal is an exception
+pub fn is_exception(link: NonNull<Lit>) -> bool {
+  matches!(unsafe { &link.as_ref().lit }, Literal::Exception(..))
+}
+
+/// Applies a handler to the tag and payload of a caught exception
+pub fn handle(handler: DAGPtr, tag: Rope, payload: Vec<u8>) -> DAGPtr {
+  let tag =
+    DAGPtr::Lit(alloc_val(Lit { lit: Literal::Text(tag), parents: None }));
+  let payload =
+    DAGPtr::Lit(alloc_val(Lit { lit: Literal::Bytes(payload), parents: None }));
+  let fun = alloc_app(handler, tag, None);
+  let app = alloc_app(DAGPtr::App(fun), payload, None);
+  unsafe {
+    let App { fun_ref, arg_ref, .. } = &mut *fun.as_ptr();
+    add_to_parents(handler, NonNull::new_unchecked(fun_ref));
+    add_to_parents(tag, NonNull::new_unchecked(arg_ref));
+    let App { fun_ref, arg_ref, .. } = &mut *app.as_ptr();
+    add_to_parents(DAGPtr::App(fun), NonNull::new_unchecked(fun_ref));
+    add_to_parents(payload, NonNull::new_unchecked(arg_ref));
+  }
+  DAGPtr::App(app)
+}
+
+/// When the evaluator reduces the arguments of a function
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum EvalStrategy {
+  /// Call-by-need: an argument is reduced when it is first used, and the
+  /// result is shared by its other uses
+  Lazy,
+  /// Call-by-value: an argument is reduced to weak head normal form before
+  /// it is substituted, even if it is never used
+  Strict,
+  /// Call-by-name: an argument is reduced anew each time it is used
+  CallByName,
+}
+
+impl Default for EvalStrategy {
+  fn default() -> Self { EvalStrategy::Lazy }
+}
+
+/// Gives a shared lambda its own copy for the parent at `link`, so that it
+/// can be reduced without affecting its other parents. Returns the lambda
+/// that `link` now points to
+pub fn unshare_lam(lam: NonNull<Lam>, link: NonNull<Parents>) -> NonNull<Lam> {
+  unsafe {
+    if DLL::is_singleton((*lam.as_ptr()).parents) {
+      return lam;
+    }
+    let node = &mut *link.as_ptr();
+    (*lam.as_ptr()).parents = node.unlink_node();
+    node.prev = None;
+    node.next = None;
+    let free = DAG::free_vars(DAGPtr::Lam(lam));
+    match DAG::from_open_subdag(DAGPtr::Lam(lam), &free, Some(link)) {
+      DAGPtr::Lam(copy) => {
+        install_child(&mut node.elem, DAGPtr::Lam(copy));
+        copy
+      }
+      _ => panic!("Copy of a lambda is not a lambda"),
+    }
+  }
+}
+
+/// Gives each use of an unshared lambda's variable but the first its own
+/// copy of `arg`, so that substituting `arg` doesn't share its reduction
+pub fn unshare_arg(lam: NonNull<Lam>, arg: DAGPtr) {
+  unsafe {
+    let var = &mut (*lam.as_ptr()).var;
+    let mut uses = vec![];
+    let mut iter = DLL::iter_option(var.parents);
+    while iter.next().is_some() {
+      uses.extend(iter.this());
+    }
+    if uses.len() < 2 {
+      return;
+    }
+    let free = DAG::free_vars(arg);
+    for link in &uses[1..] {
+      let node = &mut *link.as_ptr();
+      node.unlink_node();
+      node.prev = None;
+      node.next = None;
+      let copy = DAG::from_open_subdag(arg, &free, Some(*link));
+      install_child(&mut node.elem, copy);
+    }
+    var.parents = Some(uses[0]);
+  }
+}
+
 pub fn print_trail(trail: &Vec<NonNull<App>>) -> Vec<String> {
   let mut res: Vec<String> = vec![];
   for link in trail {
@@ -310,230 +461,593 @@ pub fn print_trail(trail: &Vec<NonNull<App>>) -> Vec<String> {
   res
 }
 
-impl DAG {
-  /// Reduces a DAG to its weak head normal form.
-  pub fn whnf(&mut self, defs: &Defs, should_count: bool) {
-    let mut node = self.head;
-    let mut trail: Vec<NonNull<App>> = vec![];
-    loop {
-      match node {
-        DAGPtr::App(link) => {
-          let App { fun, .. } = unsafe { link.as_ref() };
-          trail.push(link);
-          node = *fun;
-        }
-        DAGPtr::Lam(link) => {
-          if let Some(app_link) = trail.pop() {
-            node = reduce_lam(app_link, link, should_count);
-          }
-          else {
-            break;
+/// The children of a node in weak head normal form that `norm` goes on to
+/// reduce
+pub(crate) fn norm_children(node: DAGPtr) -> Vec<DAGPtr> {
+  match node {
+    DAGPtr::App(link) => unsafe {
+      let app = link.as_ptr();
+      vec![(*app).fun, (*app).arg]
+    },
+    DAGPtr::All(link) => unsafe {
+      let all = link.as_ptr();
+      vec![(*all).dom, DAGPtr::Lam((*all).img)]
+    },
+    DAGPtr::Lam(link) => unsafe { vec![(*link.as_ptr()).bod] },
+    DAGPtr::Slf(link) => unsafe { vec![(*link.as_ptr()).bod] },
+    DAGPtr::Cse(link) => unsafe { vec![(*link.as_ptr()).bod] },
+    DAGPtr::Dat(link) => unsafe { vec![(*link.as_ptr()).bod] },
+    _ => vec![],
+  }
+}
+
+/// A reduction to weak head normal form in progress: the node at the head
+/// of the spine, and the applications above it
+struct Frame {
+  node: DAGPtr,
+  trail: Vec<NonNull<App>>,
+  /// The definition most recently unfolded at the head
+  current: Option<Name>,
+  /// The subterm the frame was suspended on, now reduced, with what to do
+  /// next
+  resumed: Option<(Wait, DAGPtr)>,
+}
+
+impl Frame {
+  fn new(node: DAGPtr) -> Self {
+    Frame { node, trail: vec![], current: None, resumed: None }
+  }
+
+  /// The head of the frame's DAG as it stands
+  fn head(&self) -> DAGPtr {
+    match self.trail.first() {
+      Some(link) => DAGPtr::App(*link),
+      None => self.node,
+    }
+  }
+
+  /// The argument of the `i`th application above the head, from 1
+  fn arg(&self, i: usize) -> DAGPtr {
+    unsafe { (*self.trail[self.trail.len() - i].as_ptr()).arg }
+  }
+}
+
+/// What a suspended frame does once the subterm it needs is reduced
+enum Wait {
+  /// Contracts the redex of a lambda whose argument is reduced first
+  Arg(NonNull<Lam>),
+  /// Matches on the body of the `case` at the head
+  Cse,
+  /// Contracts a `let` whose value is reduced first
+  Let(NonNull<Let>),
+  /// Goes on to reduce the payload of an exception after its tag
+  Tag(Op),
+  Raise(Op, DAGPtr),
+  Catch(Op),
+  /// Applies a saturated operation once all its arguments are reduced,
+  /// holding those reduced so far
+  Args(Op, Vec<DAGPtr>),
+}
+
+enum Next {
+  Step,
+  /// Suspends the frame to reduce a subterm first
+  Eval(DAGPtr, Wait),
+  /// The frame is in weak head normal form or stuck
+  Done,
+}
+
+/// What every frame of a reduction shares
+struct Whnf<'a> {
+  defs: &'a Defs,
+  should_count: bool,
+  steps: &'a mut u64,
+  costs: &'a CostModel,
+  strategy: EvalStrategy,
+  sink: &'a mut dyn TraceSink,
+}
+
+impl<'a> Whnf<'a> {
+  fn step(&mut self, frame: &mut Frame) -> Next {
+    if let Some((wait, sub)) = frame.resumed.take() {
+      return self.resume(frame, wait, sub);
+    }
+    let node = frame.node;
+    match node {
+      DAGPtr::App(link) => {
+        let App { fun, .. } = unsafe { link.as_ref() };
+        frame.trail.push(link);
+        frame.node = *fun;
+        Next::Step
+      }
+      DAGPtr::Lam(link) => match frame.trail.last().copied() {
+        Some(app_link) => {
+          let arg = unsafe { (*app_link.as_ptr()).arg };
+          match self.strategy {
+            EvalStrategy::Lazy => self.beta(frame, link),
+            EvalStrategy::Strict => Next::Eval(arg, Wait::Arg(link)),
+            EvalStrategy::CallByName => {
+              let fun_ref = unsafe { &mut (*app_link.as_ptr()).fun_ref };
+              let lam = unshare_lam(link, NonNull::from(fun_ref));
+              unshare_arg(lam, arg);
+              self.beta(frame, lam)
+            }
           }
         }
-        DAGPtr::Ann(link) => {
-          let Ann { exp, .. } = unsafe { link.as_ref() };
-          replace_child(node, *exp);
-          free_dead_node(node);
-          node = *exp;
-        }
-        DAGPtr::Cse(link) => {
-          let mut body = unsafe { DAG::new((*link.as_ptr()).bod) };
-          body.whnf(defs, should_count);
-          match body.head {
-            DAGPtr::Dat(body_link) => {
-              let bod = unsafe { body_link.as_ref().bod };
-              replace_child(node, bod);
-              free_dead_node(node);
-              node = bod;
-            }
-            DAGPtr::Lit(link) => {
-              let Lit { lit, parents, .. } = unsafe { link.as_ref() };
-              match &lit.clone().expand() {
-                None => break,
-                Some(expand) => {
-                  let expand = DAG::from_term_inner(
-                    expand,
-                    0,
-                    BTreeMap::new(),
-                    *parents,
-                    None,
-                  );
-                  replace_child(node, expand);
-                  free_dead_node(node);
-                  node = expand;
-                }
-              }
-            }
-            _ => break,
+        None => Next::Done,
+      },
+      DAGPtr::Ann(link) => {
+        let exp = unsafe { link.as_ref().exp };
+        *self.steps -= 1;
+        replace_child(node, exp);
+        free_dead_node(node);
+        frame.node = exp;
+        Next::Step
+      }
+      DAGPtr::Cse(link) => Next::Eval(unsafe { link.as_ref().bod }, Wait::Cse),
+      DAGPtr::Let(link) => {
+        let Let { exp, bod, bod_ref, .. } = unsafe { &mut *link.as_ptr() };
+        match self.strategy {
+          EvalStrategy::Lazy => self.beta_let(frame, link),
+          EvalStrategy::Strict => Next::Eval(*exp, Wait::Let(link)),
+          EvalStrategy::CallByName => {
+            let lam = unshare_lam(*bod, NonNull::from(bod_ref));
+            unshare_arg(lam, *exp);
+            self.beta_let(frame, link)
           }
         }
-        DAGPtr::Let(link) => {
-          node = reduce_let(link, should_count);
+      }
+      DAGPtr::Fix(link) => unsafe {
+        *self.steps -= 1;
+        let Fix { var, bod, .. } = &mut *link.as_ptr();
+        let bod = *bod;
+        self
+          .sink
+          .step(&StepKind::Unfold(var.nam.clone()), frame.current.as_ref());
+        frame.current = Some(var.nam.clone());
+        replace_child(node, bod);
+        if var.parents.is_some() {
+          let new_fix =
+            alloc_fix(var.nam.clone(), 0, mem::zeroed(), None).as_mut();
+          let result = subst(
+            bod,
+            var,
+            DAGPtr::Var(NonNull::new_unchecked(&mut new_fix.var)),
+            true,
+            self.should_count,
+          );
+          new_fix.bod = result;
+          add_to_parents(result, NonNull::new_unchecked(&mut new_fix.bod_ref));
+          replace_child(
+            DAGPtr::Var(NonNull::new(var).unwrap()),
+            DAGPtr::Fix(NonNull::new_unchecked(new_fix)),
+          );
         }
-        DAGPtr::Fix(link) => unsafe {
-          let Fix { var, bod, .. } = &mut *link.as_ptr();
-          replace_child(node, *bod);
-          if var.parents.is_some() {
-            let new_fix =
-              alloc_fix(var.nam.clone(), 0, mem::zeroed(), None).as_mut();
-            let result = subst(
-              *bod,
-              var,
-              DAGPtr::Var(NonNull::new_unchecked(&mut new_fix.var)),
-              true,
-              should_count,
-            );
-            new_fix.bod = result;
-            add_to_parents(
-              result,
-              NonNull::new_unchecked(&mut new_fix.bod_ref),
-            );
-            replace_child(
-              DAGPtr::Var(NonNull::new(var).unwrap()),
-              DAGPtr::Fix(NonNull::new_unchecked(new_fix)),
-            );
+        free_dead_node(node);
+        frame.node = bod;
+        Next::Step
+      },
+      DAGPtr::Ref(link) => {
+        let Ref { nam, exp, ast, parents: ref_parents, .. } =
+          unsafe { &mut *link.as_ptr() };
+        if let Some(def) = self.defs.defs.get(exp) {
+          if !self.sink.unfolds(def) {
+            return Next::Done;
           }
+          *self.steps -= 1;
+          self
+            .sink
+            .step(&StepKind::Unfold(nam.clone()), frame.current.as_ref());
+          frame.current = Some(nam.clone());
+          let parents = *ref_parents;
+          *ref_parents = None;
+          frame.node = DAG::from_ref(def, nam.clone(), *exp, *ast, parents);
           free_dead_node(node);
-          node = *bod;
-        },
-        DAGPtr::Ref(link) => {
-          let Ref { nam, exp, ast, parents: ref_parents, .. } =
-            unsafe { &mut *link.as_ptr() };
-          if let Some(def) = defs.defs.get(exp) {
-            let parents = *ref_parents;
-            *ref_parents = None;
-            let ref_node = node;
-            node = DAG::from_ref(def, nam.clone(), *exp, *ast, parents);
-            free_dead_node(ref_node);
-            for parent in DLL::iter_option(parents) {
-              install_child(parent, node);
-            }
-          }
-          else {
-            panic!("undefined runtime reference: {}, {}", nam, exp);
+          for parent in DLL::iter_option(parents) {
+            install_child(parent, frame.node);
           }
+          Next::Step
         }
-        DAGPtr::Opr(link) => {
-          let opr = unsafe { (*link.as_ptr()).opr.clone() };
-          let len = trail.len();
-          if len == 0 && opr.arity() == 0 {
-            let res = opr.apply0();
-            if let Some(res) = res {
-              node = DAGPtr::Lit(alloc_val(Lit { lit: res, parents: None }));
-            }
-            else {
-              break;
-            }
-          }
-          else if len >= 1 && opr.arity() == 1 {
-            let mut arg = unsafe { DAG::new((*trail[len - 1].as_ptr()).arg) };
-            arg.whnf(defs, should_count);
-            match arg.head {
-              DAGPtr::Lit(link) => {
-                let x = unsafe { &(*link.as_ptr()).lit };
-                let res = opr.apply1(x);
-                if let Some(res) = res {
-                  let top = DAGPtr::App(trail.pop().unwrap());
-                  let new_node =
-                    DAGPtr::Lit(alloc_val(Lit { lit: res, parents: None }));
-                  replace_child(top, new_node);
-                  free_dead_node(top);
-                  node = new_node;
-                }
-                else {
-                  break;
-                }
-              }
-              _ => break,
-            }
-          }
-          else if len >= 2 && opr.arity() == 2 {
-            let mut arg1 = unsafe { DAG::new((*trail[len - 1].as_ptr()).arg) };
-            let mut arg2 = unsafe { DAG::new((*trail[len - 2].as_ptr()).arg) };
-            arg1.whnf(defs, should_count);
-            arg2.whnf(defs, should_count);
-            match (arg1.head, arg2.head) {
-              (DAGPtr::Lit(x_link), DAGPtr::Lit(y_link)) => {
-                let x = unsafe { &(*x_link.as_ptr()).lit };
-                let y = unsafe { &(*y_link.as_ptr()).lit };
-                let res = opr.apply2(x, y);
-                if let Some(res) = res {
-                  trail.pop();
-                  let top = DAGPtr::App(trail.pop().unwrap());
-                  let new_node =
-                    DAGPtr::Lit(alloc_val(Lit { lit: res, parents: None }));
-                  replace_child(top, new_node);
-                  free_dead_node(top);
-                  node = new_node;
-                }
-                else {
-                  break;
-                }
-              }
-              _ => break,
-            }
+        else {
+          panic!("undefined runtime reference: {}, {}", nam, exp);
+        }
+      }
+      DAGPtr::Lit(link) if !frame.trail.is_empty() && is_exception(link) => {
+        // Applying an exception to arguments raises it
+        *self.steps -= 1;
+        let top = DAGPtr::App(frame.trail[0]);
+        frame.trail.clear();
+        replace_child(top, node);
+        free_dead_node(top);
+        Next::Step
+      }
+      DAGPtr::Opr(link) => {
+        let opr =
+          self.defs.host.resolve(unsafe { (*link.as_ptr()).opr.clone() });
+        let len = frame.trail.len();
+        if len >= 3 && opr == Op::Exception(ExceptionOp::Raise) {
+          Next::Eval(frame.arg(2), Wait::Tag(opr))
+        }
+        else if len >= 3 && opr == Op::Exception(ExceptionOp::Catch) {
+          Next::Eval(frame.arg(2), Wait::Catch(opr))
+        }
+        else if len == 0 && opr.arity() == 0 {
+          let cost = self.costs.cost(&opr, &[]);
+          if cost > *self.steps {
+            *self.steps = 0;
+            return Next::Done;
           }
-          else if len >= 3 && opr.arity() == 3 {
-            let mut arg1 = unsafe { DAG::new((*trail[len - 1].as_ptr()).arg) };
-            let mut arg2 = unsafe { DAG::new((*trail[len - 2].as_ptr()).arg) };
-            let mut arg3 = unsafe { DAG::new((*trail[len - 3].as_ptr()).arg) };
-            arg1.whnf(defs, should_count);
-            arg2.whnf(defs, should_count);
-            arg3.whnf(defs, should_count);
-            match (arg1.head, arg2.head, arg3.head) {
-              (
-                DAGPtr::Lit(x_link),
-                DAGPtr::Lit(y_link),
-                DAGPtr::Lit(z_link),
-              ) => {
-                let x = unsafe { &(*x_link.as_ptr()).lit };
-                let y = unsafe { &(*y_link.as_ptr()).lit };
-                let z = unsafe { &(*z_link.as_ptr()).lit };
-                let res = opr.apply3(x, y, z);
-                if let Some(res) = res {
-                  trail.pop();
-                  trail.pop();
-                  let top = DAGPtr::App(trail.pop().unwrap());
-                  let new_node =
-                    DAGPtr::Lit(alloc_val(Lit { lit: res, parents: None }));
-                  replace_child(top, new_node);
-                  free_dead_node(top);
-                  node = new_node;
-                }
-                else {
-                  break;
-                }
-              }
-              _ => break,
+          match opr.apply(&[]) {
+            Some(res) => {
+              *self.steps -= cost;
+              self.sink.step(&StepKind::Opr(opr), frame.current.as_ref());
+              let lit = Lit { lit: res, parents: None };
+              frame.node = DAGPtr::Lit(alloc_val(lit));
+              Next::Step
             }
+            None => Next::Done,
           }
-          else {
-            break;
+        }
+        else if opr.arity() > 0 && len as u64 >= opr.arity() {
+          // Saturated operation, whose arguments are reduced to literals
+          Next::Eval(frame.arg(1), Wait::Args(opr, Vec::new()))
+        }
+        else {
+          Next::Done
+        }
+      }
+      _ => Next::Done,
+    }
+  }
+
+  /// Goes on with a frame, given the subterm it was waiting on reduced
+  fn resume(&mut self, frame: &mut Frame, wait: Wait, sub: DAGPtr) -> Next {
+    match wait {
+      Wait::Arg(lam) => self.beta(frame, lam),
+      Wait::Cse => self.case(frame, sub),
+      Wait::Let(link) => self.beta_let(frame, link),
+      Wait::Tag(opr) => Next::Eval(frame.arg(3), Wait::Raise(opr, sub)),
+      Wait::Raise(opr, tag) => self.raise(frame, opr, tag, sub),
+      Wait::Catch(opr) => self.catch(frame, opr, sub),
+      Wait::Args(opr, mut args) => {
+        args.push(sub);
+        if (args.len() as u64) < opr.arity() {
+          let arg = frame.arg(args.len() + 1);
+          Next::Eval(arg, Wait::Args(opr, args))
+        }
+        else {
+          self.apply(frame, opr, args)
+        }
+      }
+    }
+  }
+
+  /// Contracts the redex of the innermost application on the trail
+  fn beta(&mut self, frame: &mut Frame, lam: NonNull<Lam>) -> Next {
+    let app_link = frame.trail.pop().unwrap();
+    *self.steps -= 1;
+    self.sink.step(&StepKind::Beta, frame.current.as_ref());
+    frame.node = reduce_lam(app_link, lam, self.should_count);
+    Next::Step
+  }
+
+  fn beta_let(&mut self, frame: &mut Frame, link: NonNull<Let>) -> Next {
+    *self.steps -= 1;
+    self.sink.step(&StepKind::Beta, frame.current.as_ref());
+    frame.node = reduce_let(link, self.should_count);
+    Next::Step
+  }
+
+  /// Matches on the reduced body of the `case` at the head
+  fn case(&mut self, frame: &mut Frame, body: DAGPtr) -> Next {
+    let node = frame.node;
+    match body {
+      DAGPtr::Dat(body_link) => {
+        *self.steps -= 1;
+        let bod = unsafe { body_link.as_ref().bod };
+        replace_child(node, bod);
+        free_dead_node(node);
+        frame.node = bod;
+        Next::Step
+      }
+      DAGPtr::Lit(link) => {
+        let Lit { lit, parents, .. } = unsafe { link.as_ref() };
+        if let Literal::Exception(..) = lit {
+          // Matching on an exception raises it
+          *self.steps -= 1;
+          replace_child(node, body);
+          free_dead_node(node);
+          frame.node = body;
+          return Next::Step;
+        }
+        match &lit.clone().expand() {
+          None => Next::Done,
+          Some(expand) => {
+            *self.steps -= 1;
+            let expand =
+              DAG::from_term_inner(expand, 0, BTreeMap::new(), *parents, None);
+            replace_child(node, expand);
+            free_dead_node(node);
+            frame.node = expand;
+            Next::Step
           }
         }
-        _ => break,
       }
+      _ => Next::Done,
     }
-    if trail.is_empty() {
-      self.head = node;
+  }
+
+  fn raise(
+    &mut self,
+    frame: &mut Frame,
+    opr: Op,
+    tag: DAGPtr,
+    payload: DAGPtr,
+  ) -> Next {
+    let (x_link, y_link) = match (tag, payload) {
+      (DAGPtr::Lit(x_link), DAGPtr::Lit(y_link)) => (x_link, y_link),
+      _ => return Next::Done,
+    };
+    let x = unsafe { &(*x_link.as_ptr()).lit };
+    let y = unsafe { &(*y_link.as_ptr()).lit };
+    match ExceptionOp::raise(x, y) {
+      Some(res) => {
+        *self.steps -= 1;
+        self.sink.step(&StepKind::Opr(opr), frame.current.as_ref());
+        frame.trail.pop();
+        frame.trail.pop();
+        let top = DAGPtr::App(frame.trail.pop().unwrap());
+        let new_node = DAGPtr::Lit(alloc_val(Lit { lit: res, parents: None }));
+        replace_child(top, new_node);
+        free_dead_node(top);
+        frame.node = new_node;
+        Next::Step
+      }
+      None => Next::Done,
     }
-    else {
-      self.head = DAGPtr::App(trail[0]);
+  }
+
+  fn catch(&mut self, frame: &mut Frame, opr: Op, body: DAGPtr) -> Next {
+    *self.steps -= 1;
+    self.sink.step(&StepKind::Opr(opr), frame.current.as_ref());
+    frame.trail.pop();
+    frame.trail.pop();
+    let top = frame.trail.pop().unwrap();
+    let new_node = match caught(body) {
+      Some((tag, payload)) => handle(unsafe { top.as_ref().arg }, tag, payload),
+      None => body,
+    };
+    replace_child(DAGPtr::App(top), new_node);
+    free_dead_node(DAGPtr::App(top));
+    frame.node = new_node;
+    Next::Step
+  }
+
+  /// Applies a saturated operation to its reduced arguments, if they're all
+  /// literals and the gas covers it
+  fn apply(&mut self, frame: &mut Frame, opr: Op, args: Vec<DAGPtr>) -> Next {
+    let lits: Option<Vec<&Literal>> = args
+      .iter()
+      .map(|arg| match arg {
+        DAGPtr::Lit(link) => Some(unsafe { &(*link.as_ptr()).lit }),
+        _ => None,
+      })
+      .collect();
+    let lits = match lits {
+      Some(lits) => lits,
+      None => return Next::Done,
+    };
+    let cost = self.costs.cost(&opr, &lits);
+    if cost > *self.steps {
+      *self.steps = 0;
+      return Next::Done;
+    }
+    let lit = opr.raised(&lits).or_else(|| opr.apply(&lits));
+    let res = match lit {
+      Some(res) => {
+        Some(DAGPtr::Lit(alloc_val(Lit { lit: res, parents: None })))
+      }
+      None => opr
+        .apply_term(&lits)
+        .map(|res| DAG::from_term_inner(&res, 0, BTreeMap::new(), None, None)),
+    };
+    match res {
+      Some(new_node) => {
+        *self.steps -= cost;
+        self.sink.step(&StepKind::Opr(opr), frame.current.as_ref());
+        let len = frame.trail.len();
+        frame.trail.truncate(len - args.len() + 1);
+        let top = DAGPtr::App(frame.trail.pop().unwrap());
+        replace_child(top, new_node);
+        free_dead_node(top);
+        frame.node = new_node;
+        Next::Step
+      }
+      None => Next::Done,
     }
   }
+}
+
+impl DAG {
+  /// Reduces a DAG to its weak head normal form.
+  pub fn whnf(&mut self, defs: &Defs, should_count: bool) {
+    let mut steps = u64::MAX;
+    self.whnf_steps(defs, should_count, &mut steps)
+  }
+
+  /// Reduces a DAG towards its weak head normal form, performing at most
+  /// `steps` reductions and decrementing `steps` for each one. Stops early,
+  /// leaving a partially reduced DAG, when `steps` reaches zero.
+  pub fn whnf_steps(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    steps: &mut u64,
+  ) {
+    self.whnf_metered(
+      defs,
+      should_count,
+      steps,
+      &CostModel::unit(),
+      EvalStrategy::Lazy,
+    )
+  }
+
+  /// Reduces a DAG to its weak head normal form for checking conversion.
+  /// References to defs whose attributes keep them from unfolding, such as
+  /// `opaque` ones, are left folded, to be compared by their CIDs.
+  pub fn whnf_conv(&mut self, defs: &Defs, should_count: bool) {
+    let costs = CostModel::unit();
+    let strategy = EvalStrategy::Lazy;
+    let mut steps = u64::MAX;
+    let conv = &mut Conv;
+    self.whnf_traced(defs, should_count, &mut steps, &costs, strategy, conv)
+  }
+
+  /// Reduces a DAG to its weak head normal form, reducing arguments as
+  /// `strategy` says
+  pub fn whnf_with(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    strategy: EvalStrategy,
+  ) {
+    let costs = CostModel::unit();
+    let mut steps = u64::MAX;
+    self.whnf_metered(defs, should_count, &mut steps, &costs, strategy)
+  }
+
+  /// Reduces a DAG towards its weak head normal form as in `whnf_steps`, but
+  /// using `steps` as gas, charged by `costs` for applying primitive
+  /// operations. If an operation costs more than the remaining gas, it isn't
+  /// applied and `steps` is set to zero
+  pub fn whnf_metered(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    steps: &mut u64,
+    costs: &CostModel,
+    strategy: EvalStrategy,
+  ) {
+    self.whnf_traced(defs, should_count, steps, costs, strategy, &mut NoTrace)
+  }
+
+  /// Reduces a DAG as in `whnf_metered`, reporting each beta reduction,
+  /// operation and unfolding to `sink`. The subterms reduced along the way,
+  /// such as the arguments of operations, are kept on a stack of frames in
+  /// the heap, so deep nesting doesn't overflow the Rust stack.
+  pub fn whnf_traced(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    steps: &mut u64,
+    costs: &CostModel,
+    strategy: EvalStrategy,
+    sink: &mut dyn TraceSink,
+  ) {
+    let mut whnf = Whnf { defs, should_count, steps, costs, strategy, sink };
+    let mut stack: Vec<(Frame, Wait)> = vec![];
+    let mut frame = Frame::new(self.head);
+    loop {
+      if *whnf.steps == 0 {
+        // Every frame stops where it is, leaving the outermost one's DAG
+        if let Some((outer, _)) = stack.into_iter().next() {
+          frame = outer;
+        }
+        break;
+      }
+      match whnf.step(&mut frame) {
+        Next::Step => (),
+        Next::Eval(sub, wait) => {
+          let outer = mem::replace(&mut frame, Frame::new(sub));
+          stack.push((outer, wait));
+        }
+        Next::Done => match stack.pop() {
+          Some((mut outer, wait)) => {
+            outer.resumed = Some((wait, frame.head()));
+            frame = outer;
+          }
+          None => break,
+        },
+      }
+    }
+    self.head = frame.head();
+  }
 
   /// Reduces a DAG to its normal form.
   pub fn norm(&mut self, defs: &Defs, should_count: bool) {
-    self.whnf(defs, should_count);
+    let mut steps = u64::MAX;
+    self.norm_steps(defs, should_count, &mut steps)
+  }
+
+  /// Reduces a DAG towards its normal form, performing at most `steps`
+  /// reductions as in `whnf_steps`.
+  pub fn norm_steps(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    steps: &mut u64,
+  ) {
+    self.norm_metered(
+      defs,
+      should_count,
+      steps,
+      &CostModel::unit(),
+      EvalStrategy::Lazy,
+    )
+  }
+
+  /// Reduces a DAG to its normal form, reducing arguments as `strategy` says.
+  /// The strategies agree on the normal form when they terminate, but
+  /// `Strict` may diverge on an argument that the others never reduce
+  pub fn norm_with(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    strategy: EvalStrategy,
+  ) {
+    let costs = CostModel::unit();
+    let mut steps = u64::MAX;
+    self.norm_metered(defs, should_count, &mut steps, &costs, strategy)
+  }
+
+  /// Reduces a DAG towards its normal form, charging gas as in
+  /// `whnf_metered`
+  pub fn norm_metered(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    steps: &mut u64,
+    costs: &CostModel,
+    strategy: EvalStrategy,
+  ) {
+    self.norm_traced(defs, should_count, steps, costs, strategy, &mut NoTrace)
+  }
+
+  /// Reduces a DAG towards its normal form as in `norm_metered`, reporting
+  /// each step to `sink` as in `whnf_traced`
+  pub fn norm_traced(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    steps: &mut u64,
+    costs: &CostModel,
+    strategy: EvalStrategy,
+    sink: &mut dyn TraceSink,
+  ) {
+    self.whnf_traced(defs, should_count, steps, costs, strategy, sink);
     let mut trail = vec![self.head];
     while let Some(node) = trail.pop() {
+      if *steps == 0 {
+        break;
+      }
       match node {
         DAGPtr::App(link) => unsafe {
           let app = link.as_ptr();
           let mut fun = DAG::new((*app).fun);
           let mut arg = DAG::new((*app).arg);
-          fun.whnf(defs, should_count);
-          arg.whnf(defs, should_count);
+          fun.whnf_traced(defs, should_count, steps, costs, strategy, sink);
+          arg.whnf_traced(defs, should_count, steps, costs, strategy, sink);
           trail.push(fun.head);
           trail.push(arg.head);
         },
@@ -541,33 +1055,33 @@ impl DAG {
           let all = link.as_ptr();
           let mut dom = DAG::new((*all).dom);
           let mut img = DAG::new(DAGPtr::Lam((*all).img));
-          dom.whnf(defs, should_count);
-          img.whnf(defs, should_count);
+          dom.whnf_traced(defs, should_count, steps, costs, strategy, sink);
+          img.whnf_traced(defs, should_count, steps, costs, strategy, sink);
           trail.push(dom.head);
           trail.push(img.head);
         },
         DAGPtr::Lam(link) => unsafe {
           let lam = link.as_ptr();
           let mut body = DAG::new((*lam).bod);
-          body.whnf(defs, should_count);
+          body.whnf_traced(defs, should_count, steps, costs, strategy, sink);
           trail.push(body.head);
         },
         DAGPtr::Slf(link) => unsafe {
           let slf = link.as_ptr();
           let mut body = DAG::new((*slf).bod);
-          body.whnf(defs, should_count);
+          body.whnf_traced(defs, should_count, steps, costs, strategy, sink);
           trail.push(body.head);
         },
         DAGPtr::Cse(link) => unsafe {
           let cse = link.as_ptr();
           let mut body = DAG::new((*cse).bod);
-          body.whnf(defs, should_count);
+          body.whnf_traced(defs, should_count, steps, costs, strategy, sink);
           trail.push(body.head);
         },
         DAGPtr::Dat(link) => unsafe {
           let dat = link.as_ptr();
           let mut body = DAG::new((*dat).bod);
-          body.whnf(defs, should_count);
+          body.whnf_traced(defs, should_count, steps, costs, strategy, sink);
           trail.push(body.head);
         },
         _ => (),
@@ -578,6 +1092,8 @@ impl DAG {
 
 //#[cfg(test)]
 pub mod test {
+  #[cfg(test)]
+  use super::EvalStrategy;
   use super::DAG;
   use crate::{
     defs::Defs,
@@ -630,8 +1146,9 @@ pub mod test {
   }
 
   pub fn norm_assert_defs(input: &str, result: &str, defs: Defs) {
-    match parse(&input) {
-      Ok((_, mut dag)) => {
+    match crate::parse::term::parse(input, defs.clone()) {
+      Ok((_, tree)) => {
+        let mut dag = DAG::from_term(&tree);
         dag.norm(&defs, false);
         assert_eq!(format!("{}", dag), result)
       }
@@ -690,6 +1207,358 @@ pub mod test {
     );
   }
 
+  #[test]
+  pub fn reduce_test_checked() {
+    norm_assert("#U8.checked_add 1u8 2u8", "λ P none some => some 3u8");
+    norm_assert("#U8.checked_add 255u8 1u8", "λ P none some => none");
+    norm_assert("#I8.checked_sub -128i8 +1i8", "λ P none some => none");
+    norm_assert("#I32.checked_mul +3i32 -2i32", "λ P none some => some -6i32");
+    norm_assert("#I32.checked_div +7i32 +0i32", "λ P none some => none");
+  }
+
+  #[test]
+  pub fn reduce_test_text() {
+    norm_assert("#Text.find \"l\" \"hello\"", "λ P none some => some 2");
+    norm_assert(
+      "#Text.split_on \",\" \"a,b\"",
+      "λ P nil cons => cons \"a\" (cons \"b\" nil)",
+    );
+  }
+
+  #[test]
+  pub fn reduce_test_map() {
+    let map = "#Map.insert \"b\" x'20' (#Map.insert \"a\" x'10' #Map.empty)";
+    norm_assert(&format!("#Map.size ({})", map), "2");
+    norm_assert(
+      &format!("#Map.lookup \"a\" ({})", map),
+      "λ P none some => some x'10'",
+    );
+    norm_assert(
+      &format!("#Map.lookup \"a\" (#Map.delete \"a\" ({}))", map),
+      "λ P none some => none",
+    );
+    norm_assert(
+      &format!("#Map.keys ({})", map),
+      "λ P nil cons => cons \"a\" (cons \"b\" nil)",
+    );
+    norm_assert(
+      &format!("#Map.fold ({})", map),
+      "λ P nil cons => cons \"a\" x'10' (cons \"b\" x'20' nil)",
+    );
+  }
+
+  #[test]
+  pub fn reduce_test_set() {
+    let xs = "#Set.insert x'02' (#Set.insert x'01' #Set.empty)";
+    let ys = "#Set.insert x'03' (#Set.insert x'02' #Set.empty)";
+    norm_assert(
+      &format!("#Set.to_list (#Set.union ({}) ({}))", xs, ys),
+      "λ P nil cons => cons x'01' (cons x'02' (cons x'03' nil))",
+    );
+    norm_assert(
+      &format!("#Set.size (#Set.intersection ({}) ({}))", xs, ys),
+      "1",
+    );
+    norm_assert(
+      &format!("#Set.member x'01' (#Set.difference ({}) ({}))", xs, ys),
+      "#Bool.true",
+    );
+  }
+
+  #[test]
+  pub fn reduce_test_vector() {
+    let xs = "#Vector.push x'02' (#Vector.push x'01' #Vector.empty)";
+    norm_assert(&format!("#Vector.get 1 ({})", xs), "x'02'");
+    norm_assert(&format!("#Vector.len (#Vector.slice 1 5 ({}))", xs), "1");
+    norm_assert(
+      &format!("#Vector.to_list (#Vector.set 0 x'03' ({}))", xs),
+      "λ P nil cons => cons x'03' (cons x'02' nil)",
+    );
+  }
+
+  #[test]
+  pub fn reduce_test_cid() {
+    let cid = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+    let cid = format!("(#Cid.from_text \"{}\")", cid);
+    norm_assert(&cid, &cid);
+    norm_assert(&format!("#Cid.codec {}", cid), "112u64");
+    norm_assert(&format!("#Cid.hash_code {}", cid), "18u64");
+    norm_assert("#Cid.codec (#Cid.of x'00')", "113u64");
+    norm_assert("#Cid.parse \"nope\"", "λ P none some => none");
+  }
+
+  #[test]
+  pub fn reduce_test_conv() {
+    norm_assert("#U16.truncating_to_U8 300u16", "44u8");
+    norm_assert("#Nat.truncating_to_I8 255", "-1i8");
+    norm_assert("#Int.truncating_to_Nat -3", "0");
+    norm_assert("#Nat.checked_to_U8 255", "λ P none some => some 255u8");
+    norm_assert("#I8.checked_to_U64 -1i8", "λ P none some => none");
+    norm_assert("#I8.checked_to_Int", "#I8.checked_to_Int");
+  }
+
+  #[test]
+  pub fn reduce_test_show() {
+    norm_assert("#U8.show 3u8", "\"3u8\"");
+    norm_assert("#Text.show \"a\"", "\"\\\"a\\\"\"");
+    norm_assert("#Bool.show #Bool.true", "\"#Bool.true\"");
+    norm_assert("#U8.show 3u16", "#U8.show 3u16");
+    norm_assert(
+      "#Text.format \"{0}: {1}\" (#Vector.push (#Text.to_bytes \"b\") \
+       (#Vector.push (#Text.to_bytes \"a\") #Vector.empty))",
+      "\"a: b\"",
+    );
+  }
+
+  #[test]
+  pub fn reduce_test_exception() {
+    norm_assert(
+      "#Exception.raise #U8 \"e\" x'00'",
+      "(#Exception.raise #Exception \"e\" x'00')",
+    );
+    norm_assert(
+      "#Exception.tag (#Exception.raise #Exception \"e\" x'00')",
+      "\"e\"",
+    );
+    // Primitives and applications propagate exceptions to the `catch`
+    norm_assert(
+      "#Exception.catch #Text \
+       (#U8.show (#U8.add 1u8 (#Exception.raise #U8 \"oops\" x'00'))) \
+       (λ tag payload => tag)",
+      "\"oops\"",
+    );
+    norm_assert(
+      "#Exception.catch #U8 \
+       (#Exception.raise (∀ #U8 -> #U8) \"e\" x'00' 1u8) \
+       (λ tag payload => 0u8)",
+      "0u8",
+    );
+    // A stuck primitive is caught with the operation's name as its tag
+    norm_assert(
+      "#Exception.catch #Text (#U8.div 1u8 0u8) (λ tag payload => tag)",
+      "\"#U8.div\"",
+    );
+    norm_assert(
+      "#Exception.catch #U8 (#U8.add 1u8 2u8) (λ tag payload => 0u8)",
+      "3u8",
+    );
+  }
+
+  #[test]
+  pub fn reduce_test_gas() {
+    use crate::{
+      prim::{
+        cost::CostModel,
+        u8::U8Op,
+      },
+      term::Op,
+    };
+    let run = |gas: u64, costs: &CostModel| {
+      let (_, mut dag) = parse("#U8.add 1u8 (#U8.add 1u8 1u8)").unwrap();
+      let mut gas = gas;
+      let lazy = EvalStrategy::Lazy;
+      dag.norm_metered(&Defs::new(), false, &mut gas, costs, lazy);
+      (format!("{}", dag), gas)
+    };
+    let mut costs = CostModel::new();
+    assert_eq!(run(10, &costs), ("3u8".to_owned(), 8));
+    costs.set(&Op::U8(U8Op::Add), 4);
+    assert_eq!(run(10, &costs), ("3u8".to_owned(), 2));
+    // The outer addition costs more than the gas left after the inner one
+    assert_eq!(run(7, &costs), ("#U8.add 1u8 2u8".to_owned(), 0));
+  }
+
+  #[test]
+  pub fn reduce_test_host() {
+    use crate::prim::host::tests::Double;
+    use sp_std::rc::Rc;
+    let mut defs = Defs::new();
+    let cid = defs.host.register(Rc::new(Double));
+    let input = format!("#Host.{} 21u64", cid);
+    norm_assert_defs(&input, "42u64", defs);
+    // Without the host operation registered, the reference is stuck
+    norm_assert(&input, &input);
+  }
+
+  #[test]
+  pub fn reduce_test_rng() {
+    norm_assert("#Rng.new 0u64", "(#Rng.new 0u64)");
+    norm_assert(
+      "#Rng.next_u64 (#Rng.new 0u64) #U64 (λ x g => x)",
+      "16294208416658607535u64",
+    );
+    norm_assert(
+      "#Rng.split (#Rng.new 0u64) #U64 \
+       (λ g h => #Rng.next_u64 h #U64 (λ x g => x))",
+      "5095610196844313600u64",
+    );
+  }
+
+  #[test]
+  pub fn reduce_test_io() {
+    // Actions only reduce their literal arguments, the runtime does the rest
+    norm_assert(
+      "#IO.print (#U64.show 3u64) #IO.done",
+      "#IO.print \"3u64\" #IO.done",
+    );
+    norm_assert(
+      "#IO.read_line #IO.done (λ x => #IO.print x #IO.done)",
+      "#IO.read_line #IO.done (λ x => #IO.print x #IO.done)",
+    );
+  }
+
+  #[test]
+  pub fn reduce_test_u256() {
+    use crate::prim::u256::U256;
+    norm_assert("#U256.add #U256.max 1u256", "0u256");
+    norm_assert("#U256.sub 0u256 1u256", &format!("{}u256", U256::MAX));
+    norm_assert(
+      "#U256.from_limbs 1u64 0u64 0u64 0u64",
+      "6277101735386680763835789423207666416102355444464034512896u256",
+    );
+    norm_assert("#U256.limb 3u32 (#U256.shl 192u32 5u256)", "5u64");
+    norm_assert("#U256.mul_mod 7u256 8u256 10u256", "6u256");
+    norm_assert(
+      "#U256.checked_sub 1u256 2u256 #Bool #Bool.false (λ x => #Bool.true)",
+      "#Bool.false",
+    );
+    norm_assert("#U256.truncating_to_U8 511u256", "255u8");
+  }
+
+  #[test]
+  pub fn reduce_test_bits() {
+    norm_assert("#Bits.and #b01100 #b01010", "#b01000");
+    norm_assert("#Bits.xor #b01100 #b01010", "#b00110");
+    norm_assert("#Bits.not #b01100", "#b10011");
+    norm_assert("#Bits.shl 1 #b01100", "#b11000");
+    norm_assert("#Bits.slice 1 4 #b01100", "#b110");
+    norm_assert("#Bits.popcount #b01101", "3");
+    norm_assert("#Bits.to_Nat #b01101", "13");
+    norm_assert("#Bits.from_Nat 5 45", "#b01101");
+    norm_assert("#Bits.and #b01100 #b0101", "#Bits.and #b01100 #x5");
+  }
+
+  #[test]
+  pub fn reduce_test_from_text() {
+    norm_assert("#U8.from_Text \"0xff\"", "λ P none some => some 255u8");
+    norm_assert("#I8.from_Text \"-0b11\"", "λ P none some => some -3i8");
+    norm_assert("#Nat.from_Text \"12\"", "λ P none some => some 12");
+    norm_assert("#U8.from_Text \"256\"", "λ P none some => none");
+    norm_assert("#Int.from_Text \"1.5\"", "λ P none some => none");
+  }
+
+  #[test]
+  pub fn reduce_test_strategy() {
+    use crate::prim::cost::CostModel;
+    use EvalStrategy::*;
+    let run = |input: &str, strategy: EvalStrategy| {
+      let (_, mut dag) = parse(input).unwrap();
+      let mut steps = u64::MAX;
+      let costs = CostModel::unit();
+      dag.norm_metered(&Defs::new(), false, &mut steps, &costs, strategy);
+      (format!("{}", dag), u64::MAX - steps)
+    };
+    // A used argument is reduced once unless it is passed by name
+    let used = "(λ x => #U8.add x x) (#U8.add 1u8 1u8)";
+    assert_eq!(run(used, Lazy), ("4u8".to_owned(), 3));
+    assert_eq!(run(used, Strict), ("4u8".to_owned(), 3));
+    assert_eq!(run(used, CallByName), ("4u8".to_owned(), 4));
+    // An unused argument is only reduced by value
+    let unused = "(λ x y => y) (#U8.add 1u8 1u8) 2u8";
+    assert_eq!(run(unused, Lazy), ("2u8".to_owned(), 2));
+    assert_eq!(run(unused, Strict), ("2u8".to_owned(), 3));
+    assert_eq!(run(unused, CallByName), ("2u8".to_owned(), 2));
+    let shared = "let x: #U8 = #U8.add 1u8 1u8; #U8.mul x x";
+    assert_eq!(run(shared, Lazy), ("4u8".to_owned(), 3));
+    assert_eq!(run(shared, CallByName), ("4u8".to_owned(), 4));
+  }
+
+  #[test]
+  pub fn reduce_test_strategies_agree() {
+    let three = "λ s z => s (s (s z))";
+    let add = "λ m n s z => m s (n s z)";
+    let id = "λ x => x";
+    let inputs = vec![
+      "λ y => (λ z => z z) ((λ x => x) y)".to_owned(),
+      "λ y => (λ x z => x (x z)) ((λ f a => f (f a)) y)".to_owned(),
+      format!("(({}) ({}) {})", add, three, three),
+      format!("({three}) (({three}) ({id})) ({id})", id = id, three = three),
+      "let f: ∀ #U8 -> #U8 = λ x => #U8.add x x; f (f 3u8)".to_owned(),
+      "#Exception.catch #U8 ((λ x y => #U8.div x y) 1u8 0u8) \
+       (λ tag payload => 7u8)"
+        .to_owned(),
+    ];
+    for input in inputs {
+      let norm = |strategy| {
+        let (_, mut dag) = parse(&input).unwrap();
+        dag.norm_with(&Defs::new(), false, strategy);
+        format!("{}", dag)
+      };
+      let lazy = norm(EvalStrategy::Lazy);
+      assert_eq!(norm(EvalStrategy::Strict), lazy, "{}", input);
+      assert_eq!(norm(EvalStrategy::CallByName), lazy, "{}", input);
+    }
+  }
+
+  #[test]
+  pub fn reduce_test_deep() {
+    // The accumulator is a chain of additions as long as the count, which
+    // is only reduced at the end
+    let (_, defs) = parse_defs(
+      "def count (n: #U64) (acc: #U64): #U64 = \
+       (case (#U64.eql n 0u64)) Type acc \
+       (count (#U64.sub n 1u64) (#U64.add acc 1u64))",
+    )
+    .unwrap();
+    norm_assert_defs("count 100000u64 0u64", "100000u64", defs);
+  }
+
+  #[test]
+  pub fn reduce_test_trace() {
+    use crate::{
+      name::Name,
+      prim::cost::CostModel,
+      trace::{
+        Only,
+        TraceCounts,
+        TraceLog,
+      },
+    };
+    let src = "def double: ∀ #U8 -> #U8 = λ x => #U8.add x x\n\
+               def quad: ∀ #U8 -> #U8 = λ x => double (double x)";
+    let (_, defs) = parse_defs(src).unwrap();
+    let trace = |sink: &mut dyn crate::trace::TraceSink| {
+      let (_, term) =
+        crate::parse::term::parse("quad 1u8", defs.clone()).unwrap();
+      let mut dag = DAG::from_term(&term);
+      let costs = CostModel::unit();
+      let lazy = EvalStrategy::Lazy;
+      let mut steps = u64::MAX;
+      dag.norm_traced(&defs, false, &mut steps, &costs, lazy, sink);
+      format!("{}", dag)
+    };
+    let mut counts = TraceCounts::new();
+    assert_eq!(trace(&mut counts), "4u8");
+    assert_eq!(counts.beta, 3);
+    assert_eq!(counts.opr.get("#U8.add"), Some(&2));
+    assert_eq!(counts.unfold.get(&Name::from("quad")), Some(&1));
+    assert_eq!(counts.unfold.get(&Name::from("double")), Some(&2));
+    assert_eq!(counts.total(), 8);
+    let mut only = Only::new(
+      vec![Name::from("double")].into_iter().collect(),
+      TraceLog::default(),
+    );
+    trace(&mut only);
+    assert_eq!(only.sink.lines, vec![
+      "unfold double in quad",
+      "beta in double",
+      "unfold double",
+      "beta in double",
+      "primop #U8.add in double",
+      "primop #U8.add in double",
+    ]);
+  }
+
   #[test]
   pub fn reduce_test() {
     // Already normalized
@@ -0,0 +1,327 @@
+use crate::{
+  arena::with_arena,
+  dag::{
+    live_bytes,
+    DAG,
+  },
+  defs::Defs,
+  term::Term,
+};
+
+use sp_std::fmt;
+
+/// Bounds on an evaluation, where `None` leaves a bound off
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct Limits {
+  /// The most reduction steps taken
+  pub steps: Option<u64>,
+  /// The most seconds spent. The clock is only read with the `std` feature
+  /// and off wasm, so elsewhere only steps are bounded.
+  pub seconds: Option<u64>,
+  /// The most bytes of DAG nodes allocated on top of those live when the
+  /// evaluation started. Nodes are counted across threads, so evaluations
+  /// running side by side share the budget.
+  pub bytes: Option<usize>,
+}
+
+impl Limits {
+  pub fn none() -> Self { Self::default() }
+
+  pub fn steps(steps: u64) -> Self {
+    Limits { steps: Some(steps), ..Self::default() }
+  }
+
+  pub fn is_none(&self) -> bool {
+    self.steps.is_none() && self.seconds.is_none() && self.bytes.is_none()
+  }
+
+  /// Starts an evaluation against the limits
+  pub fn fuel(&self) -> Fuel {
+    Fuel {
+      left: self.steps.unwrap_or(u64::MAX),
+      taken: 0,
+      deadline: self.seconds.and_then(Deadline::after),
+      memory: self.bytes.map(|cap| (live_bytes(), cap)),
+      out: None,
+    }
+  }
+}
+
+/// Which limit stopped an evaluation
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Exhausted {
+  Steps,
+  Time,
+  /// The bytes allocated during the evaluation, and the budget they went
+  /// over
+  Memory(usize, usize),
+}
+
+/// The steps left to an evaluation, the time it must stop by, and the
+/// memory it may allocate
+#[derive(Clone, Copy, Debug)]
+pub struct Fuel {
+  left: u64,
+  taken: u64,
+  deadline: Option<Deadline>,
+  /// The live bytes at the start, and the budget on top of them
+  memory: Option<(usize, usize)>,
+  out: Option<Exhausted>,
+}
+
+/// How many steps are taken between readings of the clock
+const CLOCK_EVERY: u64 = 1024;
+
+/// How many steps `DAG::norm_limited` takes between readings of the clock,
+/// or of the memory in use if it's bounded
+const CHUNK: u64 = 1 << 16;
+const MEMORY_CHUNK: u64 = CLOCK_EVERY;
+
+impl Fuel {
+  pub fn unlimited() -> Self { Limits::none().fuel() }
+
+  pub fn left(&self) -> u64 { self.left }
+
+  pub fn taken(&self) -> u64 { self.taken }
+
+  /// Whether the evaluation used every step it had, ran past its deadline
+  /// or allocated past its budget
+  pub fn is_out(&self) -> bool { self.out.is_some() }
+
+  pub fn exhausted(&self) -> Option<Exhausted> { self.out }
+
+  /// The error to stop the evaluation with, once it's out of fuel
+  pub fn out_of_fuel(&self) -> Option<OutOfFuel> {
+    self.out.map(|cause| OutOfFuel { steps: self.taken, cause })
+  }
+
+  /// Accounts for `n` steps already taken, returning whether the evaluation
+  /// may go on
+  pub fn spend(&mut self, n: u64) -> bool {
+    let before = self.taken;
+    self.taken += n;
+    self.left = self.left.saturating_sub(n);
+    let ticked = before / CLOCK_EVERY != self.taken / CLOCK_EVERY;
+    if self.out.is_none() {
+      self.out = self.check(ticked);
+    }
+    self.out.is_none()
+  }
+
+  /// Finds the first limit passed, reading the clock only if `ticked`
+  fn check(&self, ticked: bool) -> Option<Exhausted> {
+    if self.left == 0 {
+      return Some(Exhausted::Steps);
+    }
+    if ticked && self.deadline.map_or(false, |d| d.passed()) {
+      return Some(Exhausted::Time);
+    }
+    let (base, cap) = self.memory?;
+    let used = live_bytes().saturating_sub(base);
+    if used > cap {
+      Some(Exhausted::Memory(used, cap))
+    }
+    else {
+      None
+    }
+  }
+
+  /// Takes a step, if there's fuel left for one
+  pub fn burn(&mut self) -> bool {
+    if self.out.is_some() {
+      return false;
+    }
+    if self.left == 0 {
+      self.out = Some(Exhausted::Steps);
+      return false;
+    }
+    self.spend(1);
+    true
+  }
+
+  /// How many steps to take between checks of the limits
+  fn chunk(&self) -> u64 {
+    let chunk = if self.memory.is_some() { MEMORY_CHUNK } else { CHUNK };
+    self.left.min(chunk)
+  }
+}
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+#[derive(Clone, Copy, Debug)]
+struct Deadline(std::time::Instant);
+
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+impl Deadline {
+  fn after(seconds: u64) -> Option<Self> {
+    let now = std::time::Instant::now();
+    Some(Deadline(now + std::time::Duration::from_secs(seconds)))
+  }
+
+  fn passed(&self) -> bool { std::time::Instant::now() >= self.0 }
+}
+
+#[cfg(not(all(feature = "std", not(target_arch = "wasm32"))))]
+#[derive(Clone, Copy, Debug)]
+struct Deadline;
+
+#[cfg(not(all(feature = "std", not(target_arch = "wasm32"))))]
+impl Deadline {
+  fn after(_seconds: u64) -> Option<Self> { None }
+
+  fn passed(&self) -> bool { false }
+}
+
+/// An evaluation stopped by its limits after taking `steps` steps
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct OutOfFuel {
+  pub steps: u64,
+  pub cause: Exhausted,
+}
+
+impl fmt::Display for OutOfFuel {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self.cause {
+      Exhausted::Steps => write!(f, "Out of fuel after {} steps", self.steps),
+      Exhausted::Time => write!(f, "Out of time after {} steps", self.steps),
+      Exhausted::Memory(used, cap) => write!(
+        f,
+        "Out of memory after {} steps, with {} bytes allocated over a budget \
+         of {}",
+        self.steps, used, cap
+      ),
+    }
+  }
+}
+
+/// The result of `eval_with_limit`
+#[derive(PartialEq, Clone, Debug)]
+pub enum EvalResult {
+  /// The normal form of the term
+  Normal(Term),
+  /// The term as far as it was reduced within the limits, unless it ran out
+  /// of memory, when reading the DAG back as a tree could run out again
+  OutOfFuel(Option<Term>, OutOfFuel),
+}
+
+impl DAG {
+  /// Reduces a DAG towards its normal form, spending `fuel` on every step.
+  /// Returns whether the normal form was reached before the fuel ran out.
+  pub fn norm_limited(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    fuel: &mut Fuel,
+  ) -> bool {
+    loop {
+      let chunk = fuel.chunk();
+      let mut left = chunk;
+      self.norm_steps(defs, should_count, &mut left);
+      if left > 0 {
+        fuel.spend(chunk - left);
+        return true;
+      }
+      if !fuel.spend(chunk) {
+        return false;
+      }
+    }
+  }
+}
+
+/// Normalizes a closed term within `limits`, rather than looping forever on
+/// a term with no normal form. The DAG lives in an arena, freed in one go.
+pub fn eval_with_limit(defs: &Defs, term: &Term, limits: Limits) -> EvalResult {
+  // Only the terms read back from the DAG leave the arena
+  unsafe {
+    with_arena(|| {
+      let mut dag = DAG::from_term(term);
+      let mut fuel = limits.fuel();
+      dag.norm_limited(defs, false, &mut fuel);
+      match fuel.out_of_fuel() {
+        None => EvalResult::Normal(dag.to_term(false)),
+        Some(err @ OutOfFuel { cause: Exhausted::Memory(..), .. }) => {
+          EvalResult::OutOfFuel(None, err)
+        }
+        Some(err) => EvalResult::OutOfFuel(Some(dag.to_term(false)), err),
+      }
+    })
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::parse::{
+    package,
+    span::Span,
+    term::{
+      input_cid,
+      parse,
+    },
+  };
+
+  fn eval(src: &str, limits: Limits) -> EvalResult {
+    let defs = "def loop (x: #U64): #U64 = loop (#U64.add x 1u64)\n\
+                def spin (x: #U64): #U64 = spin x";
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(defs), Defs::new())(Span::new(defs))
+        .unwrap();
+    let (_, term) = parse(src, defs.clone()).unwrap();
+    eval_with_limit(&defs, &term, limits)
+  }
+
+  #[test]
+  fn test_eval_with_limit() {
+    assert!(matches!(
+      eval("#U64.add 1u64 2u64", Limits::steps(10)),
+      EvalResult::Normal(x) if format!("{}", x) == "3u64"
+    ));
+    assert!(matches!(
+      eval("loop 0u64", Limits::steps(100)),
+      EvalResult::OutOfFuel(Some(_), OutOfFuel {
+        steps: 100,
+        cause: Exhausted::Steps
+      })
+    ));
+    // Without the clock nothing would stop this one. Unlike `loop`, `spin`
+    // stays small enough to read back by the time the clock is read.
+    if cfg!(all(feature = "std", not(target_arch = "wasm32"))) {
+      let limits = Limits { seconds: Some(0), ..Limits::none() };
+      assert!(matches!(
+        eval("spin 0u64", limits),
+        EvalResult::OutOfFuel(_, OutOfFuel { cause: Exhausted::Time, .. })
+      ));
+    }
+  }
+
+  #[test]
+  fn test_memory_budget() {
+    // The unevaluated argument of `loop` grows with every call
+    let limits =
+      Limits { steps: Some(1 << 24), bytes: Some(1 << 12), ..Limits::none() };
+    assert!(matches!(
+      eval("loop 0u64", limits),
+      EvalResult::OutOfFuel(None, OutOfFuel {
+        cause: Exhausted::Memory(_, 4096),
+        ..
+      })
+    ));
+    let limits = Limits { bytes: Some(1 << 24), ..Limits::none() };
+    assert!(matches!(
+      eval("#U64.add 1u64 2u64", limits),
+      EvalResult::Normal(_)
+    ));
+  }
+
+  #[test]
+  fn test_fuel() {
+    let mut fuel = Limits::steps(2).fuel();
+    assert!(fuel.burn());
+    assert!(fuel.burn());
+    assert!(!fuel.burn());
+    assert!(fuel.is_out());
+    assert_eq!(fuel.taken(), 2);
+    let mut fuel = Fuel::unlimited();
+    assert!(fuel.spend(1 << 20));
+    assert!(!fuel.is_out());
+  }
+}
@@ -0,0 +1,198 @@
+use crate::{
+  dag::{
+    free_dead_node,
+    get_parents,
+    replace_child,
+    DAGPtr,
+    ParentPtr,
+    Ref,
+    DAG,
+  },
+  dll::DLL,
+  ipld::ord::IpldKey,
+  level::Level,
+};
+
+use sp_cid::Cid;
+
+use sp_std::collections::btree_map::BTreeMap;
+
+/// What makes two nodes interchangeable: their constructor and the addresses
+/// of their children, which were interned first, or the value of a leaf.
+/// Binders are never merged, since each owns the variable it binds. Leaf
+/// values are keyed by their IPLD, as literals and operations aren't `Ord`.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+enum Key {
+  App(DAGPtr, DAGPtr),
+  Ann(DAGPtr, DAGPtr),
+  Dat(DAGPtr),
+  Cse(DAGPtr),
+  /// A reference, by the CIDs of its def and whether it's recursive
+  Ref(Cid, Cid, bool),
+  Typ(Level),
+  Lit(IpldKey),
+  LTy(IpldKey),
+  Opr(IpldKey),
+}
+
+#[derive(Default)]
+struct HashCons {
+  table: BTreeMap<Key, DAGPtr>,
+  /// The node each visited node was interned as
+  seen: BTreeMap<DAGPtr, DAGPtr>,
+  merged: usize,
+}
+
+/// Merges the structurally identical nodes of some DAGs, so duplicated
+/// subterms become one node with many parents and compare equal by address.
+/// Reduction rewrites nodes in place, so nothing is remembered between
+/// calls. The heads of the DAGs are left as they are, since each DAG frees
+/// its own. Returns the number of nodes freed.
+pub fn hash_cons(dags: &[&DAG]) -> usize {
+  let mut cons = HashCons::default();
+  for dag in dags {
+    cons.node(dag.head);
+  }
+  cons.merged
+}
+
+impl DAG {
+  /// Merges the structurally identical nodes of the DAG
+  pub fn hash_cons(&self) -> usize { hash_cons(&[self]) }
+}
+
+impl HashCons {
+  /// Interns a node after its children, returning the node that replaced it
+  fn node(&mut self, node: DAGPtr) -> DAGPtr {
+    if let Some(res) = self.seen.get(&node) {
+      return *res;
+    }
+    let key = unsafe {
+      match node {
+        DAGPtr::Lam(link) => {
+          self.node(link.as_ref().bod);
+          None
+        }
+        DAGPtr::Slf(link) => {
+          self.node(link.as_ref().bod);
+          None
+        }
+        DAGPtr::Fix(link) => {
+          self.node(link.as_ref().bod);
+          None
+        }
+        DAGPtr::All(link) => {
+          self.node(link.as_ref().dom);
+          self.node(DAGPtr::Lam(link.as_ref().img));
+          None
+        }
+        DAGPtr::Let(link) => {
+          self.node(link.as_ref().typ);
+          self.node(link.as_ref().exp);
+          self.node(DAGPtr::Lam(link.as_ref().bod));
+          None
+        }
+        DAGPtr::App(link) => {
+          self.node(link.as_ref().fun);
+          self.node(link.as_ref().arg);
+          Some(Key::App(link.as_ref().fun, link.as_ref().arg))
+        }
+        DAGPtr::Ann(link) => {
+          self.node(link.as_ref().typ);
+          self.node(link.as_ref().exp);
+          Some(Key::Ann(link.as_ref().typ, link.as_ref().exp))
+        }
+        DAGPtr::Dat(link) => {
+          self.node(link.as_ref().bod);
+          Some(Key::Dat(link.as_ref().bod))
+        }
+        DAGPtr::Cse(link) => {
+          self.node(link.as_ref().bod);
+          Some(Key::Cse(link.as_ref().bod))
+        }
+        DAGPtr::Ref(link) => {
+          let Ref { exp, ast, rec, .. } = link.as_ref();
+          Some(Key::Ref(*exp, *ast, *rec))
+        }
+        DAGPtr::Var(_) => None,
+        DAGPtr::Typ(link) => Some(Key::Typ(link.as_ref().lvl.clone())),
+        DAGPtr::Lit(link) => {
+          Some(Key::Lit(IpldKey(link.as_ref().lit.to_ipld())))
+        }
+        DAGPtr::LTy(link) => {
+          Some(Key::LTy(IpldKey(link.as_ref().lty.to_ipld())))
+        }
+        DAGPtr::Opr(link) => {
+          Some(Key::Opr(IpldKey(link.as_ref().opr.to_ipld())))
+        }
+      }
+    };
+    let res = match key {
+      Some(key) if !is_head(node) => match self.table.get(&key) {
+        Some(&canon) => {
+          replace_child(node, canon);
+          free_dead_node(node);
+          self.merged += 1;
+          canon
+        }
+        None => {
+          self.table.insert(key, node);
+          node
+        }
+      },
+      _ => node,
+    };
+    self.seen.insert(node, res);
+    res
+  }
+}
+
+/// Whether a node is the head of a DAG
+fn is_head(node: DAGPtr) -> bool {
+  DLL::iter_option(get_parents(node)).any(|p| matches!(p, ParentPtr::Root))
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    defs::Defs,
+    parse::term::parse,
+  };
+
+  fn from_src(src: &str) -> DAG {
+    let (_, term) = parse(src, Defs::new()).unwrap();
+    DAG::from_term(&term)
+  }
+
+  #[test]
+  fn test_hash_cons() {
+    let src = "λ x => #U64.add (#U64.mul x 2u64) (#U64.mul x 2u64)";
+    let dag = from_src(src);
+    let term = dag.to_term(false);
+    // The second `#U64.mul`, `2u64` and both applications to them
+    assert_eq!(dag.hash_cons(), 4);
+    assert_eq!(dag.to_term(false), term);
+    assert_eq!(dag.hash_cons(), 0);
+    dag.free();
+    // The shared product is reduced once, for both of its parents
+    let mut dag =
+      from_src("#U64.add (#U64.mul 3u64 2u64) (#U64.mul 3u64 2u64)");
+    assert_eq!(dag.hash_cons(), 5);
+    dag.norm(&Defs::new(), false);
+    assert_eq!(format!("{}", dag), "12u64");
+    dag.free();
+  }
+
+  #[test]
+  fn test_hash_cons_across() {
+    let a = from_src("#U64.add 1u64 2u64");
+    let b = from_src("#U64.mul (#U64.add 1u64 2u64) 3u64");
+    let (a_term, b_term) = (a.to_term(false), b.to_term(false));
+    // `b` shares all of `a` but its head, which stays its own
+    assert_eq!(hash_cons(&[&a, &b]), 4);
+    assert_eq!((a.to_term(false), b.to_term(false)), (a_term, b_term));
+    a.free();
+    b.free();
+  }
+}
@@ -0,0 +1,78 @@
+pub mod access;
+pub mod block;
+pub mod borrowed;
+pub mod convert;
+pub mod dag;
+#[cfg(feature = "crypto")]
+pub mod encrypted;
+pub mod error;
+pub mod hamt;
+pub mod hash;
+pub mod link;
+pub mod ord;
+pub mod patch;
+pub mod path;
+pub mod proof;
+pub mod schema;
+#[cfg(feature = "crypto")]
+pub mod signed;
+pub mod store;
+pub mod sync;
+pub mod writer;
+
+pub use access::IpldExt;
+pub use block::Block;
+pub use borrowed::{
+  FloatPolicy,
+  IpldRef,
+};
+pub use convert::{
+  ConvertError,
+  FromIpld,
+  IntoIpld,
+};
+pub use dag::{
+  Dag,
+  DagStats,
+  Order,
+  Walk,
+};
+#[cfg(feature = "crypto")]
+pub use encrypted::EncryptedBlock;
+pub use error::{
+  DecodeError,
+  EncodeError,
+};
+pub use hamt::Hamt;
+pub use ord::IpldKey;
+pub use patch::{
+  apply,
+  diff,
+  Patch,
+  Segment,
+};
+pub use path::{
+  IpldPath,
+  PathError,
+  Resolved,
+};
+pub use proof::{
+  prove,
+  Proof,
+  ProofError,
+};
+pub use schema::{
+  Schema,
+  SchemaError,
+};
+#[cfg(feature = "crypto")]
+pub use signed::SignedBlock;
+pub use store::{
+  references_closure,
+  BlockStore,
+  MemStore,
+};
+pub use writer::{
+  FloatWidth,
+  Writer,
+};
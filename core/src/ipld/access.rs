@@ -0,0 +1,196 @@
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+
+use sp_std::{
+  collections::btree_map::{
+    self,
+    BTreeMap,
+  },
+  mem,
+  slice,
+  vec,
+};
+
+use alloc::string::String;
+
+/// Accessors for the contents of an IPLD object, in place of matching on its
+/// variants by hand
+pub trait IpldExt {
+  fn as_bool(&self) -> Option<bool>;
+  fn as_int(&self) -> Option<i128>;
+  fn as_str(&self) -> Option<&str>;
+  fn as_bytes(&self) -> Option<&[u8]>;
+  fn as_link(&self) -> Option<Cid>;
+  fn as_list(&self) -> Option<&[Ipld]>;
+  fn as_map(&self) -> Option<&BTreeMap<String, Ipld>>;
+
+  /// The element at `index` of a list
+  fn get(&self, index: usize) -> Option<&Ipld>;
+
+  /// The value at `key` of a map
+  fn get_key(&self, key: &str) -> Option<&Ipld>;
+
+  /// Moves the object out, leaving `Ipld::Null` in its place
+  fn take(&mut self) -> Ipld;
+
+  /// Iterates over the elements of a list or the values of a map. Other
+  /// objects have no children. Named apart from `Ipld::iter`, which walks
+  /// the whole tree.
+  fn children(&self) -> Iter<'_>;
+
+  /// Consumes a list or map into its elements or values
+  fn into_children(self) -> IntoIter;
+}
+
+impl IpldExt for Ipld {
+  fn as_bool(&self) -> Option<bool> {
+    match self {
+      Ipld::Bool(b) => Some(*b),
+      _ => None,
+    }
+  }
+
+  fn as_int(&self) -> Option<i128> {
+    match self {
+      Ipld::Integer(i) => Some(*i),
+      _ => None,
+    }
+  }
+
+  fn as_str(&self) -> Option<&str> {
+    match self {
+      Ipld::String(s) => Some(s),
+      _ => None,
+    }
+  }
+
+  fn as_bytes(&self) -> Option<&[u8]> {
+    match self {
+      Ipld::Bytes(b) => Some(b),
+      _ => None,
+    }
+  }
+
+  fn as_link(&self) -> Option<Cid> {
+    match self {
+      Ipld::Link(cid) => Some(*cid),
+      _ => None,
+    }
+  }
+
+  fn as_list(&self) -> Option<&[Ipld]> {
+    match self {
+      Ipld::List(xs) => Some(xs),
+      _ => None,
+    }
+  }
+
+  fn as_map(&self) -> Option<&BTreeMap<String, Ipld>> {
+    match self {
+      Ipld::StringMap(m) => Some(m),
+      _ => None,
+    }
+  }
+
+  fn get(&self, index: usize) -> Option<&Ipld> {
+    self.as_list().and_then(|xs| xs.get(index))
+  }
+
+  fn get_key(&self, key: &str) -> Option<&Ipld> {
+    self.as_map().and_then(|m| m.get(key))
+  }
+
+  fn take(&mut self) -> Ipld { mem::replace(self, Ipld::Null) }
+
+  fn children(&self) -> Iter<'_> {
+    match self {
+      Ipld::List(xs) => Iter::List(xs.iter()),
+      Ipld::StringMap(m) => Iter::Map(m.values()),
+      _ => Iter::Empty,
+    }
+  }
+
+  fn into_children(self) -> IntoIter {
+    match self {
+      Ipld::List(xs) => IntoIter::List(xs.into_iter()),
+      Ipld::StringMap(m) => IntoIter::Map(m.into_iter()),
+      _ => IntoIter::Empty,
+    }
+  }
+}
+
+/// Borrowing iterator over the children of an IPLD object
+pub enum Iter<'a> {
+  Empty,
+  List(slice::Iter<'a, Ipld>),
+  Map(btree_map::Values<'a, String, Ipld>),
+}
+
+impl<'a> Iterator for Iter<'a> {
+  type Item = &'a Ipld;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self {
+      Self::Empty => None,
+      Self::List(xs) => xs.next(),
+      Self::Map(m) => m.next(),
+    }
+  }
+}
+
+/// Owning iterator over the children of an IPLD object
+pub enum IntoIter {
+  Empty,
+  List(vec::IntoIter<Ipld>),
+  Map(btree_map::IntoIter<String, Ipld>),
+}
+
+impl Iterator for IntoIter {
+  type Item = Ipld;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    match self {
+      Self::Empty => None,
+      Self::List(xs) => xs.next(),
+      Self::Map(m) => m.next().map(|(_, v)| v),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::tests::arbitrary_cid;
+  use alloc::string::ToString;
+  use quickcheck::Gen;
+
+  #[test]
+  fn test_accessors() {
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    let mut map = BTreeMap::new();
+    map.insert("link".to_string(), Ipld::Link(cid));
+    map.insert("name".to_string(), Ipld::String("id".to_string()));
+    let mut ipld = Ipld::List(vec![
+      Ipld::Bool(true),
+      Ipld::Integer(-3),
+      Ipld::Bytes(vec![1, 2]),
+      Ipld::StringMap(map),
+    ]);
+    assert_eq!(ipld.get(0).and_then(IpldExt::as_bool), Some(true));
+    assert_eq!(ipld.get(1).and_then(IpldExt::as_int), Some(-3));
+    assert_eq!(ipld.get(2).and_then(IpldExt::as_bytes), Some(&[1u8, 2][..]));
+    assert_eq!(ipld.get(1).and_then(IpldExt::as_str), None);
+    let map = ipld.get(3).unwrap();
+    assert_eq!(map.get_key("link").and_then(IpldExt::as_link), Some(cid));
+    assert_eq!(map.get_key("name").and_then(IpldExt::as_str), Some("id"));
+    assert_eq!(map.get_key("other"), None);
+    assert_eq!(map.children().count(), 2);
+    assert_eq!(ipld.get(4), None);
+    assert_eq!(ipld.children().count(), 4);
+    let taken = ipld.take();
+    assert_eq!(ipld, Ipld::Null);
+    assert_eq!(ipld.children().count(), 0);
+    let last = taken.into_children().last().unwrap();
+    assert_eq!(last.into_children().count(), 2);
+  }
+}
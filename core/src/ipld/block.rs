@@ -0,0 +1,273 @@
+use core::fmt;
+
+use sp_cid::Cid;
+use sp_ipld::{
+  dag_cbor::DagCborCodec,
+  Codec,
+  Ipld,
+};
+
+use sp_std::vec::Vec;
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::ipld::{
+  borrowed::{
+    FloatPolicy,
+    IpldRef,
+  },
+  error::DecodeError,
+  hash::{
+    digest,
+    Hasher,
+    DAG_CBOR,
+  },
+};
+
+/// Reasons a block cannot be built or decoded
+#[derive(PartialEq, Clone, Debug)]
+pub enum BlockError {
+  /// The codec isn't DAG-CBOR
+  UnsupportedCodec(u64),
+  /// The multihash code isn't one of the supported hash functions
+  UnsupportedHash(u64),
+  /// The data doesn't hash to the CID it came with
+  Mismatch(Cid, Cid),
+  Encode(String),
+  Decode(String),
+}
+
+impl fmt::Display for BlockError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnsupportedCodec(c) => write!(f, "Unsupported codec {:#x}", c),
+      Self::UnsupportedHash(c) => write!(f, "Unsupported hash {:#x}", c),
+      Self::Mismatch(expected, actual) => {
+        write!(f, "Block data hashes to {}, not {}", actual, expected)
+      }
+      Self::Encode(e) => write!(f, "Encoding error: {}", e),
+      Self::Decode(e) => write!(f, "Decoding error: {}", e),
+    }
+  }
+}
+
+impl From<BlockError> for String {
+  fn from(e: BlockError) -> String { e.to_string() }
+}
+
+/// Encoded data together with the CID that addresses it. A block can only
+/// be built from data that hashes to its CID.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Block {
+  cid: Cid,
+  data: Vec<u8>,
+}
+
+impl Block {
+  /// Encodes an IPLD object and computes its CID. Under `FloatPolicy::Strict`
+  /// NaN and infinite floats are rejected, while `FloatPolicy::Permissive`
+  /// writes them, for blocks only read back by a permissive reader.
+  pub fn encode(
+    codec: u64,
+    hasher: Hasher,
+    floats: FloatPolicy,
+    ipld: &Ipld,
+  ) -> Result<Self, BlockError> {
+    if codec != DAG_CBOR {
+      return Err(BlockError::UnsupportedCodec(codec));
+    }
+    if floats == FloatPolicy::Strict && has_non_finite(ipld) {
+      return Err(BlockError::Encode("non-finite float".to_string()));
+    }
+    let data = DagCborCodec
+      .encode(ipld)
+      .map_err(|e| BlockError::Encode(e.to_string()))?
+      .into_inner();
+    Ok(Block { cid: digest(codec, hasher, &data), data })
+  }
+
+  /// Wraps data received under `cid`, checking that it hashes to it
+  pub fn new(cid: Cid, data: Vec<u8>) -> Result<Self, BlockError> {
+    if cid.codec() != DAG_CBOR {
+      return Err(BlockError::UnsupportedCodec(cid.codec()));
+    }
+    let code = cid.hash().code();
+    let hasher =
+      Hasher::from_code(code).ok_or(BlockError::UnsupportedHash(code))?;
+    let actual = digest(cid.codec(), hasher, &data);
+    if actual != cid {
+      return Err(BlockError::Mismatch(cid, actual));
+    }
+    Ok(Block { cid, data })
+  }
+
+  pub fn cid(&self) -> Cid { self.cid }
+
+  pub fn codec(&self) -> u64 { self.cid.codec() }
+
+  pub fn data(&self) -> &[u8] { &self.data }
+
+  pub fn into_data(self) -> Vec<u8> { self.data }
+
+  /// Decodes the block's data. It's read by a default `Reader`, so that a
+  /// block nested too deeply, or with more elements than the reader allows,
+  /// fails to decode rather than overflow the stack or exhaust memory.
+  pub fn decode(&self) -> Result<Ipld, BlockError> {
+    self
+      .decode_ref()
+      .map(|ipld| ipld.to_ipld())
+      .map_err(|e| BlockError::Decode(e.to_string()))
+  }
+
+  /// Decodes the block's data without copying its strings and bytes
+  pub fn decode_ref(&self) -> Result<IpldRef<'_>, DecodeError> {
+    IpldRef::decode(&self.data)
+  }
+}
+
+/// Whether an IPLD object contains a NaN or infinite float, which DAG-CBOR
+/// can't represent. `DagCborCodec` encodes them anyway, so blocks are checked
+/// up front.
+pub fn has_non_finite(ipld: &Ipld) -> bool {
+  match ipld {
+    Ipld::Float(f) => !f.is_finite(),
+    Ipld::List(xs) => xs.iter().any(has_non_finite),
+    Ipld::StringMap(m) => m.values().any(has_non_finite),
+    _ => false,
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    ipld::borrowed::DEFAULT_MAX_ELEMENTS,
+    term::Term,
+  };
+  use sp_ipld::dag_cbor;
+
+  #[quickcheck]
+  fn block_roundtrip(x: Term) -> bool {
+    let ipld = x.embed().0.to_ipld();
+    match Block::encode(DAG_CBOR, Hasher::default(), FloatPolicy::Strict, &ipld)
+    {
+      Ok(block) => {
+        block.cid() == dag_cbor::cid(&ipld)
+          && block.decode() == Ok(ipld.clone())
+          && Block::new(block.cid(), block.data().to_vec()) == Ok(block)
+      }
+      Err(_) => false,
+    }
+  }
+
+  #[test]
+  fn block_decode_depth() {
+    // Lists nested far deeper than the reader allows
+    let mut data = vec![0x81; 1 << 20];
+    data.push(0xf6);
+    let cid = digest(DAG_CBOR, Hasher::Sha2_256, &data);
+    let block = Block::new(cid, data).unwrap();
+    let err = block.decode_ref().unwrap_err();
+    assert!(matches!(err, DecodeError::DepthLimit { .. }));
+    assert_eq!(block.decode(), Err(BlockError::Decode(err.to_string())));
+  }
+
+  #[test]
+  fn block_decode_elements() {
+    // A list of nulls, one more than the reader allows by default
+    let len = DEFAULT_MAX_ELEMENTS + 1;
+    let mut data = vec![0x9a];
+    data.extend_from_slice(&(len as u32).to_be_bytes());
+    data.resize(data.len() + len, 0xf6);
+    let cid = digest(DAG_CBOR, Hasher::Sha2_256, &data);
+    let block = Block::new(cid, data).unwrap();
+    let err = block.decode_ref().unwrap_err();
+    assert!(matches!(err, DecodeError::ElementLimit { .. }));
+    assert_eq!(block.decode(), Err(BlockError::Decode(err.to_string())));
+  }
+
+  #[test]
+  fn block_verifies() {
+    let block = Block::encode(
+      DAG_CBOR,
+      Hasher::Sha2_256,
+      FloatPolicy::Strict,
+      &Ipld::Integer(1),
+    )
+    .unwrap();
+    let other = Block::encode(
+      DAG_CBOR,
+      Hasher::Sha2_256,
+      FloatPolicy::Strict,
+      &Ipld::Integer(2),
+    )
+    .unwrap();
+    assert_eq!(
+      Block::new(block.cid(), other.data().to_vec()),
+      Err(BlockError::Mismatch(block.cid(), other.cid()))
+    );
+    assert_eq!(
+      Block::encode(0x55, Hasher::Sha2_256, FloatPolicy::Strict, &Ipld::Null),
+      Err(BlockError::UnsupportedCodec(0x55))
+    );
+    assert_eq!(block.decode_ref(), Ok(IpldRef::Integer(1)));
+    let inf = Ipld::List(vec![Ipld::Float(f64::INFINITY)]);
+    assert!(Block::encode(
+      DAG_CBOR,
+      Hasher::Sha2_256,
+      FloatPolicy::Strict,
+      &inf
+    )
+    .is_err());
+    let block =
+      Block::encode(DAG_CBOR, Hasher::Sha2_256, FloatPolicy::Permissive, &inf)
+        .unwrap();
+    assert_eq!(
+      block.decode_ref(),
+      Err(DecodeError::NonFiniteFloat { offset: 1 })
+    );
+    let reader = crate::ipld::borrowed::Reader::new(block.data())
+      .with_float_policy(FloatPolicy::Permissive);
+    assert_eq!(
+      IpldRef::decode_with(reader),
+      Ok(IpldRef::List(vec![IpldRef::Float(f64::INFINITY)]))
+    );
+  }
+
+  #[test]
+  fn block_v0_links() {
+    use crate::ipld::{
+      link::to_v1,
+      store::references,
+      writer,
+    };
+    use sp_cid::Version;
+    let v0 =
+      crate::parse::parse_cid("QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG")
+        .unwrap();
+    let ipld = Ipld::List(vec![Ipld::Link(v0), Ipld::Link(to_v1(v0))]);
+    let block =
+      Block::encode(DAG_CBOR, Hasher::Sha2_256, FloatPolicy::Strict, &ipld)
+        .unwrap();
+    assert_eq!(writer::encode(&ipld), Ok(block.data().to_vec()));
+    assert_eq!(block.decode(), Ok(ipld));
+    let links = references(block.data()).unwrap();
+    assert_eq!(links, vec![v0, to_v1(v0)]);
+    assert_eq!(links[0].version(), Version::V0);
+    assert_eq!(
+      block.decode_ref(),
+      Ok(IpldRef::List(vec![IpldRef::Link(v0), IpldRef::Link(to_v1(v0))]))
+    );
+    // A v0 link with a byte after its multihash
+    let mut data = block.data().to_vec();
+    data[4] += 1;
+    data.insert(5 + 35, 0);
+    assert_eq!(
+      IpldRef::decode(&data),
+      Err(DecodeError::InvalidCid { offset: 5 })
+    );
+  }
+}
@@ -0,0 +1,767 @@
+use crate::ipld::{
+  error::DecodeError,
+  writer::FloatWidth,
+};
+
+use num_bigint::{
+  BigInt,
+  BigUint,
+};
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+use sp_multihash::{
+  Code,
+  Multihash,
+};
+
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  convert::TryFrom,
+  str,
+  vec::Vec,
+};
+
+use alloc::string::ToString;
+
+/// The CBOR tag DAG-CBOR uses for links
+pub const CID_TAG: u64 = 42;
+
+/// The CBOR tags of positive and negative bignums
+pub const BIGNUM_TAGS: (u64, u64) = (2, 3);
+
+/// Default maximum nesting of lists and maps, well within the stack
+pub const DEFAULT_MAX_DEPTH: usize = 256;
+
+/// Default maximum number of list elements and map entries in one block
+pub const DEFAULT_MAX_ELEMENTS: usize = 1 << 20;
+
+/// How floats outside of the DAG-CBOR data model are treated
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum FloatPolicy {
+  /// Reject NaN and infinities, as the DAG-CBOR spec requires
+  Strict,
+  /// Accept NaN and infinities, for blocks written by lenient encoders
+  Permissive,
+}
+
+impl Default for FloatPolicy {
+  fn default() -> Self { Self::Strict }
+}
+
+/// An IPLD object whose strings and bytes borrow from the block they were
+/// decoded from, so decoding copies nothing but the structure itself
+#[derive(PartialEq, Clone, Debug)]
+pub enum IpldRef<'a> {
+  Null,
+  Bool(bool),
+  Integer(i128),
+  Float(f64),
+  String(&'a str),
+  Bytes(&'a [u8]),
+  List(Vec<IpldRef<'a>>),
+  StringMap(BTreeMap<&'a str, IpldRef<'a>>),
+  Link(Cid),
+}
+
+impl<'a> IpldRef<'a> {
+  /// Decodes a DAG-CBOR block, borrowing strings and bytes from it
+  pub fn decode(bytes: &'a [u8]) -> Result<Self, DecodeError> {
+    Self::decode_with(Reader::new(bytes))
+  }
+
+  /// Decodes a DAG-CBOR block with a configured reader
+  pub fn decode_with(mut reader: Reader<'a>) -> Result<Self, DecodeError> {
+    let ipld = reader.read_ipld()?;
+    if reader.remaining() != 0 {
+      return Err(DecodeError::TrailingBytes { offset: reader.offset() });
+    }
+    Ok(ipld)
+  }
+
+  /// Copies the borrowed data into an owned IPLD object
+  pub fn to_ipld(&self) -> Ipld {
+    match self {
+      Self::Null => Ipld::Null,
+      Self::Bool(b) => Ipld::Bool(*b),
+      Self::Integer(i) => Ipld::Integer(*i),
+      Self::Float(f) => Ipld::Float(*f),
+      Self::String(s) => Ipld::String(s.to_string()),
+      Self::Bytes(b) => Ipld::Bytes(b.to_vec()),
+      Self::List(xs) => Ipld::List(xs.iter().map(Self::to_ipld).collect()),
+      Self::StringMap(m) => Ipld::StringMap(
+        m.iter().map(|(k, v)| (k.to_string(), v.to_ipld())).collect(),
+      ),
+      Self::Link(cid) => Ipld::Link(*cid),
+    }
+  }
+}
+
+/// Turns the value following a tag into the object it stands for. It's
+/// given the value and the offset of the tag, for errors.
+pub type TagHandler =
+  for<'b> fn(IpldRef<'b>, usize) -> Result<IpldRef<'b>, DecodeError>;
+
+/// A cursor over a DAG-CBOR encoded block
+pub struct Reader<'a> {
+  bytes: &'a [u8],
+  offset: usize,
+  depth: usize,
+  max_depth: usize,
+  elements: usize,
+  max_elements: usize,
+  floats: FloatPolicy,
+  widths: Option<FloatWidth>,
+  tags: BTreeMap<u64, TagHandler>,
+  fallible: bool,
+}
+
+impl<'a> Reader<'a> {
+  pub fn new(bytes: &'a [u8]) -> Self {
+    Reader {
+      bytes,
+      offset: 0,
+      depth: 0,
+      max_depth: DEFAULT_MAX_DEPTH,
+      elements: 0,
+      max_elements: DEFAULT_MAX_ELEMENTS,
+      floats: FloatPolicy::default(),
+      widths: None,
+      tags: BTreeMap::new(),
+      fallible: false,
+    }
+  }
+
+  /// Limits how deeply lists and maps may nest, so that adversarial blocks
+  /// fail to decode rather than overflow the stack
+  pub fn with_max_depth(mut self, max_depth: usize) -> Self {
+    self.max_depth = max_depth;
+    self
+  }
+
+  /// Limits the total number of list elements and map entries, which bounds
+  /// the memory a block can make the reader allocate
+  pub fn with_max_elements(mut self, max_elements: usize) -> Self {
+    self.max_elements = max_elements;
+    self
+  }
+
+  /// Sets whether NaN and infinite floats are accepted
+  pub fn with_float_policy(mut self, floats: FloatPolicy) -> Self {
+    self.floats = floats;
+    self
+  }
+
+  /// Requires floats to be written at the given width, so that blocks whose
+  /// CIDs depend on how an encoder chose to write floats are rejected
+  pub fn with_canonical_floats(mut self, width: FloatWidth) -> Self {
+    self.widths = Some(width);
+    self
+  }
+
+  /// Registers a handler for values tagged with `tag`, which would otherwise
+  /// fail to decode. The CID tag can't be overridden.
+  pub fn with_tag(mut self, tag: u64, handler: TagHandler) -> Self {
+    if tag != CID_TAG {
+      self.tags.insert(tag, handler);
+    }
+    self
+  }
+
+  /// Reserves the storage of each list up front with `try_reserve`, so that
+  /// a block too large for the allocator fails to decode rather than aborting.
+  /// Strings and bytes are borrowed and need no allocation, but map nodes are
+  /// still allocated infallibly, one entry at a time.
+  pub fn with_fallible_alloc(mut self) -> Self {
+    self.fallible = true;
+    self
+  }
+
+  /// The offset of the next byte to be read
+  pub fn offset(&self) -> usize { self.offset }
+
+  /// The number of bytes left to read
+  pub fn remaining(&self) -> usize { self.bytes.len() - self.offset }
+
+  /// Reads `len` bytes, borrowing them from the block
+  pub fn read_slice(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+    if len > self.remaining() {
+      return Err(DecodeError::UnexpectedEof {
+        offset: self.bytes.len(),
+        needed: len - self.remaining(),
+      });
+    }
+    let slice = &self.bytes[self.offset..self.offset + len];
+    self.offset += len;
+    Ok(slice)
+  }
+
+  pub fn read_u8(&mut self) -> Result<u8, DecodeError> {
+    Ok(self.read_slice(1)?[0])
+  }
+
+  /// Reads an unsigned big-endian integer of `len` bytes
+  pub fn read_uint(&mut self, len: usize) -> Result<u64, DecodeError> {
+    let bytes = self.read_slice(len)?;
+    Ok(bytes.iter().fold(0u64, |acc, b| (acc << 8) | u64::from(*b)))
+  }
+
+  /// Reads a major type and its argument. Indefinite lengths are rejected, as
+  /// DAG-CBOR forbids them.
+  pub fn read_header(&mut self) -> Result<(u8, u64), DecodeError> {
+    let offset = self.offset;
+    let byte = self.read_u8()?;
+    let major = byte >> 5;
+    let arg = match byte & 0x1f {
+      n @ 0..=23 => u64::from(n),
+      24 => self.read_uint(1)?,
+      25 => self.read_uint(2)?,
+      26 => self.read_uint(4)?,
+      27 => self.read_uint(8)?,
+      _ => {
+        return Err(DecodeError::UnexpectedCode {
+          code: byte,
+          offset,
+          expected: "a definite length",
+        });
+      }
+    };
+    Ok((major, arg))
+  }
+
+  /// Reads a length argument, which must fit in the remaining input
+  fn read_len(&mut self, arg: u64) -> Result<usize, DecodeError> {
+    usize::try_from(arg)
+      .ok()
+      .filter(|len| *len <= self.remaining())
+      .ok_or(DecodeError::LengthOutOfRange { len: arg, offset: self.offset })
+  }
+
+  pub fn read_str(&mut self, len: usize) -> Result<&'a str, DecodeError> {
+    let offset = self.offset;
+    let bytes = self.read_slice(len)?;
+    str::from_utf8(bytes).map_err(|_| DecodeError::InvalidUtf8 { offset })
+  }
+
+  /// Reads the bytes of a link, which follow the identity multibase prefix.
+  /// A CIDv0 is written as its bare sha2-256 multihash, and is told apart
+  /// from a CIDv1 by the multihash's fixed `0x12 0x20` header. Bytes left
+  /// over after the CID make the link invalid.
+  pub fn read_link(&mut self) -> Result<Cid, DecodeError> {
+    let offset = self.offset;
+    let (major, len) = self.read_header()?;
+    if major != 2 {
+      return Err(DecodeError::UnexpectedCode {
+        code: self.bytes[offset],
+        offset,
+        expected: "link bytes",
+      });
+    }
+    let len = self.read_len(len)?;
+    let offset = self.offset;
+    let cid = match self.read_slice(len)?.split_first() {
+      Some((0, [0x12, 0x20, digest @ ..])) if digest.len() == 32 => {
+        Multihash::wrap(Code::Sha2_256.into(), digest)
+          .ok()
+          .and_then(|hash| Cid::new_v0(hash).ok())
+      }
+      Some((0, bytes)) => Cid::try_from(bytes)
+        .ok()
+        .filter(|cid| cid.to_bytes().len() == bytes.len()),
+      _ => None,
+    };
+    cid.ok_or(DecodeError::InvalidCid { offset })
+  }
+
+  /// Reads any DAG-CBOR value
+  pub fn read_ipld(&mut self) -> Result<IpldRef<'a>, DecodeError> {
+    let offset = self.offset;
+    let (major, arg) = self.read_header()?;
+    match major {
+      0 => Ok(IpldRef::Integer(i128::from(arg))),
+      1 => Ok(IpldRef::Integer(-1 - i128::from(arg))),
+      2 => {
+        let len = self.read_len(arg)?;
+        Ok(IpldRef::Bytes(self.read_slice(len)?))
+      }
+      3 => {
+        let len = self.read_len(arg)?;
+        Ok(IpldRef::String(self.read_str(len)?))
+      }
+      4 => {
+        let len = self.read_len(arg)?;
+        self.enter(offset)?;
+        self.count(len, offset)?;
+        // Lengths come from untrusted input, so grow as elements are read
+        // unless the allocation can fail cleanly
+        let mut xs = Vec::new();
+        if self.fallible {
+          xs.try_reserve_exact(len)
+            .map_err(|_| DecodeError::AllocationFailed { len, offset })?;
+        }
+        for _ in 0..len {
+          xs.push(self.read_ipld()?);
+        }
+        self.depth -= 1;
+        Ok(IpldRef::List(xs))
+      }
+      5 => {
+        let len = self.read_len(arg)?;
+        self.enter(offset)?;
+        self.count(len, offset)?;
+        let mut map = BTreeMap::new();
+        for _ in 0..len {
+          let key_offset = self.offset;
+          let key = match self.read_header()? {
+            (3, key_len) => {
+              let key_len = self.read_len(key_len)?;
+              self.read_str(key_len)?
+            }
+            _ => {
+              return Err(DecodeError::UnexpectedCode {
+                code: self.bytes[key_offset],
+                offset: key_offset,
+                expected: "a string key",
+              });
+            }
+          };
+          let val = self.read_ipld()?;
+          if map.insert(key, val).is_some() {
+            return Err(DecodeError::DuplicateKey {
+              key: key.to_string(),
+              offset: key_offset,
+            });
+          }
+        }
+        self.depth -= 1;
+        Ok(IpldRef::StringMap(map))
+      }
+      6 if arg == CID_TAG => Ok(IpldRef::Link(self.read_link()?)),
+      6 => match self.tags.get(&arg).copied() {
+        Some(handler) => {
+          self.enter(offset)?;
+          let val = self.read_ipld()?;
+          self.depth -= 1;
+          handler(val, offset)
+        }
+        None => Err(DecodeError::UnknownTag { tag: arg, offset }),
+      },
+      _ => self.read_simple(offset),
+    }
+  }
+
+  /// Reads an integer of any size, either plain or as a bignum tagged 2 or 3
+  /// with big-endian magnitude bytes. Bignums are only accepted outside of
+  /// the range plain integers cover, without leading zeros.
+  pub fn read_bigint(&mut self) -> Result<BigInt, DecodeError> {
+    let offset = self.offset;
+    match self.read_header()? {
+      (0, n) => Ok(BigInt::from(n)),
+      (1, n) => Ok(-BigInt::from(n) - 1u32),
+      (6, tag) if tag == BIGNUM_TAGS.0 || tag == BIGNUM_TAGS.1 => {
+        let bytes_offset = self.offset;
+        let bytes = match self.read_header()? {
+          (2, len) => {
+            let len = self.read_len(len)?;
+            self.read_slice(len)?
+          }
+          _ => {
+            return Err(DecodeError::UnexpectedCode {
+              code: self.bytes[bytes_offset],
+              offset: bytes_offset,
+              expected: "bignum bytes",
+            });
+          }
+        };
+        if bytes.len() <= 8 || bytes[0] == 0 {
+          return Err(DecodeError::NonCanonicalInteger { offset });
+        }
+        let n = BigInt::from(BigUint::from_bytes_be(bytes));
+        Ok(if tag == BIGNUM_TAGS.0 { n } else { -n - 1u32 })
+      }
+      _ => Err(DecodeError::UnexpectedCode {
+        code: self.bytes[offset],
+        offset,
+        expected: "an integer or bignum",
+      }),
+    }
+  }
+
+  /// Reads a `u128`, written plain or as a bignum like `read_bigint` reads
+  pub fn read_u128(&mut self) -> Result<u128, DecodeError> {
+    let offset = self.offset;
+    let int = self.read_bigint()?;
+    u128::try_from(&int).map_err(|_| DecodeError::IntegerOutOfRange { offset })
+  }
+
+  /// Reads an `i128`, written plain or as a bignum like `read_bigint` reads
+  pub fn read_i128(&mut self) -> Result<i128, DecodeError> {
+    let offset = self.offset;
+    let int = self.read_bigint()?;
+    i128::try_from(&int).map_err(|_| DecodeError::IntegerOutOfRange { offset })
+  }
+
+  /// Skips over one value without building it. Nesting is still bounded by
+  /// the depth limit, but skipped elements don't count towards the budget.
+  /// Tagged values are skipped whether or not their tag is registered.
+  pub fn skip(&mut self) -> Result<(), DecodeError> { self.scan(&mut |_| ()) }
+
+  /// Reads one value without building it, collecting the links in it in the
+  /// order they appear, including links inside values with unknown tags
+  pub fn read_links(
+    &mut self,
+    links: &mut Vec<Cid>,
+  ) -> Result<(), DecodeError> {
+    self.scan(&mut |cid| links.push(cid))
+  }
+
+  fn scan(&mut self, on_link: &mut dyn FnMut(Cid)) -> Result<(), DecodeError> {
+    let offset = self.offset;
+    let (major, arg) = self.read_header()?;
+    match major {
+      0 | 1 => Ok(()),
+      2 | 3 => {
+        let len = self.read_len(arg)?;
+        self.read_slice(len).map(|_| ())
+      }
+      4 | 5 => {
+        let len = self.read_len(arg)?;
+        self.enter(offset)?;
+        for _ in 0..if major == 4 { len } else { len * 2 } {
+          self.scan(on_link)?;
+        }
+        self.depth -= 1;
+        Ok(())
+      }
+      6 if arg == CID_TAG => self.read_link().map(on_link),
+      6 => {
+        self.enter(offset)?;
+        self.scan(on_link)?;
+        self.depth -= 1;
+        Ok(())
+      }
+      _ => self.read_simple(offset).map(|_| ()),
+    }
+  }
+
+  /// Skips over `n` consecutive values
+  pub fn skip_n(&mut self, n: usize) -> Result<(), DecodeError> {
+    for _ in 0..n {
+      self.skip()?;
+    }
+    Ok(())
+  }
+
+  fn count(&mut self, len: usize, offset: usize) -> Result<(), DecodeError> {
+    match self.elements.checked_add(len) {
+      Some(elements) if elements <= self.max_elements => {
+        self.elements = elements;
+        Ok(())
+      }
+      _ => Err(DecodeError::ElementLimit { max: self.max_elements, offset }),
+    }
+  }
+
+  fn enter(&mut self, offset: usize) -> Result<(), DecodeError> {
+    if self.depth >= self.max_depth {
+      return Err(DecodeError::DepthLimit { max: self.max_depth, offset });
+    }
+    self.depth += 1;
+    Ok(())
+  }
+
+  /// Re-reads the header at `offset` as a simple value or float
+  fn read_simple(&mut self, offset: usize) -> Result<IpldRef<'a>, DecodeError> {
+    let info = self.bytes[offset] & 0x1f;
+    let bits = self.bytes[offset + 1..self.offset]
+      .iter()
+      .fold(0u64, |acc, b| (acc << 8) | u64::from(*b));
+    let float = match info {
+      20 => return Ok(IpldRef::Bool(false)),
+      21 => return Ok(IpldRef::Bool(true)),
+      22 => return Ok(IpldRef::Null),
+      25 => f16_to_f64(bits as u16),
+      26 => f64::from(f32::from_bits(bits as u32)),
+      27 => f64::from_bits(bits),
+      _ => {
+        return Err(DecodeError::UnexpectedCode {
+          code: self.bytes[offset],
+          offset,
+          expected: "a simple value or float",
+        });
+      }
+    };
+    if self.floats == FloatPolicy::Strict && !float.is_finite() {
+      return Err(DecodeError::NonFiniteFloat { offset });
+    }
+    if self.widths.map_or(false, |w| w.info(float) != info) {
+      return Err(DecodeError::NonCanonicalFloat { offset });
+    }
+    Ok(IpldRef::Float(float))
+  }
+}
+
+/// Decodes the value under `key` in an encoded map, skipping over the other
+/// entries instead of decoding them
+pub fn extract_field<'a>(
+  bytes: &'a [u8],
+  key: &str,
+) -> Result<Option<IpldRef<'a>>, DecodeError> {
+  let mut reader = Reader::new(bytes);
+  let len = match reader.read_header()? {
+    (5, len) => reader.read_len(len)?,
+    _ => {
+      return Err(DecodeError::UnexpectedCode {
+        code: bytes[0],
+        offset: 0,
+        expected: "a map",
+      });
+    }
+  };
+  for _ in 0..len {
+    let key_offset = reader.offset();
+    let k = match reader.read_header()? {
+      (3, key_len) => {
+        let key_len = reader.read_len(key_len)?;
+        reader.read_str(key_len)?
+      }
+      _ => {
+        return Err(DecodeError::UnexpectedCode {
+          code: bytes[key_offset],
+          offset: key_offset,
+          expected: "a string key",
+        });
+      }
+    };
+    if k == key {
+      return reader.read_ipld().map(Some);
+    }
+    reader.skip()?;
+  }
+  Ok(None)
+}
+
+/// Widens an IEEE 754 half precision float
+pub fn f16_to_f64(half: u16) -> f64 {
+  let sign = if half & 0x8000 == 0 { 1.0 } else { -1.0 };
+  let exp = i32::from((half >> 10) & 0x1f);
+  let frac = f64::from(half & 0x3ff);
+  match exp {
+    0 => sign * frac * 2f64.powi(-24),
+    31 if frac == 0.0 => sign * f64::INFINITY,
+    31 => f64::NAN,
+    _ => sign * (1.0 + frac / 1024.0) * 2f64.powi(exp - 15),
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    ipld::writer::Writer,
+    term::Term,
+    tests::arbitrary_cid,
+  };
+  use quickcheck::Gen;
+  use sp_ipld::{
+    dag_cbor::DagCborCodec,
+    Codec,
+  };
+
+  fn encode(ipld: &Ipld) -> Vec<u8> {
+    DagCborCodec.encode(ipld).unwrap().into_inner()
+  }
+
+  #[quickcheck]
+  fn borrowed_decode_agrees(x: Term) -> bool {
+    let (anon, meta) = x.embed();
+    [anon.to_ipld(), meta.to_ipld()].iter().all(|ipld| {
+      let bytes = encode(ipld);
+      IpldRef::decode(&bytes).map(|i| i.to_ipld()) == Ok(ipld.clone())
+    })
+  }
+
+  #[quickcheck]
+  fn borrowed_fallible_agrees(x: Term) -> bool {
+    let bytes = encode(&x.embed().0.to_ipld());
+    IpldRef::decode_with(Reader::new(&bytes).with_fallible_alloc())
+      == IpldRef::decode(&bytes)
+  }
+
+  #[test]
+  fn borrowed_decode_borrows() {
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    let mut map = BTreeMap::new();
+    map.insert("name".to_string(), Ipld::String("id".to_string()));
+    map.insert("link".to_string(), Ipld::Link(cid));
+    map.insert("bytes".to_string(), Ipld::Bytes(vec![1, 2, 3]));
+    let ipld = Ipld::StringMap(map);
+    let bytes = encode(&ipld);
+    let decoded = IpldRef::decode(&bytes).unwrap();
+    match &decoded {
+      IpldRef::StringMap(m) => {
+        assert_eq!(m.get("name"), Some(&IpldRef::String("id")));
+        assert_eq!(m.get("link"), Some(&IpldRef::Link(cid)));
+        assert_eq!(m.get("bytes"), Some(&IpldRef::Bytes(&[1, 2, 3])));
+      }
+      _ => panic!("expected a map"),
+    }
+    assert_eq!(decoded.to_ipld(), ipld);
+  }
+
+  #[test]
+  fn borrowed_decode_errors() {
+    assert_eq!(
+      IpldRef::decode(&[]),
+      Err(DecodeError::UnexpectedEof { offset: 0, needed: 1 })
+    );
+    assert_eq!(
+      IpldRef::decode(&[0x9f]),
+      Err(DecodeError::UnexpectedCode {
+        code: 0x9f,
+        offset: 0,
+        expected: "a definite length"
+      })
+    );
+    assert_eq!(
+      IpldRef::decode(&[0x62, 0x61]),
+      Err(DecodeError::LengthOutOfRange { len: 2, offset: 1 })
+    );
+    assert_eq!(
+      IpldRef::decode(&[0x61, 0xff]),
+      Err(DecodeError::InvalidUtf8 { offset: 1 })
+    );
+    assert_eq!(
+      IpldRef::decode(&[0x01, 0x02]),
+      Err(DecodeError::TrailingBytes { offset: 1 })
+    );
+    assert!(IpldRef::decode(&[0x62, 0x61]).unwrap_err().is_truncation());
+    assert!(!IpldRef::decode(&[0xc1, 0x00]).unwrap_err().is_truncation());
+    assert_eq!(f16_to_f64(0x3c00), 1.0);
+    assert_eq!(f16_to_f64(0xc000), -2.0);
+  }
+
+  #[test]
+  fn borrowed_decode_depth() {
+    let nested = |depth: usize| {
+      let mut bytes = vec![0x81; depth];
+      bytes.push(0xf6);
+      bytes
+    };
+    let ok = nested(DEFAULT_MAX_DEPTH);
+    assert!(IpldRef::decode(&ok).is_ok());
+    let deep = nested(DEFAULT_MAX_DEPTH + 1);
+    assert_eq!(
+      IpldRef::decode(&deep),
+      Err(DecodeError::DepthLimit {
+        max: DEFAULT_MAX_DEPTH,
+        offset: DEFAULT_MAX_DEPTH
+      })
+    );
+    let hostile = nested(1 << 20);
+    assert!(IpldRef::decode(&hostile).is_err());
+    let shallow = Reader::new(&ok).with_max_depth(2);
+    assert!(IpldRef::decode_with(shallow).is_err());
+    assert!(IpldRef::decode_with(
+      Reader::new(&[0x82, 0x80, 0xa0]).with_max_depth(2)
+    )
+    .is_ok());
+  }
+
+  #[test]
+  fn borrowed_decode_elements() {
+    let list = [0x83, 0x01, 0x02, 0x81, 0x03];
+    assert!(
+      IpldRef::decode_with(Reader::new(&list).with_max_elements(4)).is_ok()
+    );
+    assert_eq!(
+      IpldRef::decode_with(Reader::new(&list).with_max_elements(3)),
+      Err(DecodeError::ElementLimit { max: 3, offset: 3 })
+    );
+    // A header claiming 2^32 elements is rejected before allocating
+    let hostile = [0x9a, 0xff, 0xff, 0xff, 0xff, 0x00];
+    assert!(IpldRef::decode(&hostile).unwrap_err().is_truncation());
+  }
+
+  #[test]
+  fn borrowed_skip_and_extract() {
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    let mut map = BTreeMap::new();
+    map.insert(
+      "body".to_string(),
+      Ipld::List(vec![
+        Ipld::Link(cid),
+        Ipld::Float(1.5),
+        Ipld::StringMap(BTreeMap::new()),
+      ]),
+    );
+    map.insert("name".to_string(), Ipld::String("id".to_string()));
+    let bytes = encode(&Ipld::StringMap(map));
+    assert_eq!(extract_field(&bytes, "name"), Ok(Some(IpldRef::String("id"))));
+    assert_eq!(extract_field(&bytes, "type"), Ok(None));
+    assert_eq!(
+      extract_field(&[0x80], "name"),
+      Err(DecodeError::UnexpectedCode {
+        code: 0x80,
+        offset: 0,
+        expected: "a map"
+      })
+    );
+    let mut reader = Reader::new(&bytes);
+    reader.skip().unwrap();
+    assert_eq!(reader.remaining(), 0);
+    let list = [0x01, 0x62, 0x61, 0x62, 0x81, 0xf5, 0x02];
+    let mut reader = Reader::new(&list);
+    reader.skip_n(3).unwrap();
+    assert_eq!(reader.read_ipld(), Ok(IpldRef::Integer(2)));
+  }
+
+  #[test]
+  fn borrowed_tags() {
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    let mut writer = Writer::new();
+    writer.write_header(4, 2);
+    writer.write_header(6, 1);
+    writer.write_ipld(&Ipld::Integer(1_600_000_000)).unwrap();
+    writer.write_header(6, 1234);
+    writer.write_ipld(&Ipld::Link(cid)).unwrap();
+    let bytes = writer.into_inner();
+    assert_eq!(
+      IpldRef::decode(&bytes),
+      Err(DecodeError::UnknownTag { tag: 1, offset: 1 })
+    );
+    let mut reader = Reader::new(&bytes);
+    reader.skip().unwrap();
+    assert_eq!(reader.remaining(), 0);
+    let mut links = Vec::new();
+    Reader::new(&bytes).read_links(&mut links).unwrap();
+    assert_eq!(links, vec![cid]);
+    fn untag(val: IpldRef, _: usize) -> Result<IpldRef, DecodeError> { Ok(val) }
+    let reader = Reader::new(&bytes).with_tag(1, untag).with_tag(1234, untag);
+    assert_eq!(
+      IpldRef::decode_with(reader),
+      Ok(IpldRef::List(vec![
+        IpldRef::Integer(1_600_000_000),
+        IpldRef::Link(cid)
+      ]))
+    );
+  }
+
+  #[test]
+  fn borrowed_float_policy() {
+    let inf = [0xf9, 0x7c, 0x00];
+    let nan = [0xfb, 0x7f, 0xf8, 0, 0, 0, 0, 0, 0];
+    assert_eq!(
+      IpldRef::decode(&inf),
+      Err(DecodeError::NonFiniteFloat { offset: 0 })
+    );
+    assert!(Reader::new(&nan).skip().is_err());
+    fn permissive(bytes: &[u8]) -> Result<IpldRef, DecodeError> {
+      let reader =
+        Reader::new(bytes).with_float_policy(FloatPolicy::Permissive);
+      IpldRef::decode_with(reader)
+    }
+    assert_eq!(permissive(&inf), Ok(IpldRef::Float(f64::INFINITY)));
+    assert!(matches!(permissive(&nan), Ok(IpldRef::Float(f)) if f.is_nan()));
+    assert_eq!(IpldRef::decode(&[0xf9, 0x3e, 0x00]), Ok(IpldRef::Float(1.5)));
+  }
+}
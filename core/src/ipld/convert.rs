@@ -0,0 +1,184 @@
+use core::fmt;
+
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  convert::TryFrom,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// The name of the kind of an IPLD object, as used in conversion errors
+pub fn kind(ipld: &Ipld) -> &'static str {
+  match ipld {
+    Ipld::Null => "null",
+    Ipld::Bool(_) => "bool",
+    Ipld::Integer(_) => "integer",
+    Ipld::Float(_) => "float",
+    Ipld::String(_) => "string",
+    Ipld::Bytes(_) => "bytes",
+    Ipld::List(_) => "list",
+    Ipld::StringMap(_) => "map",
+    Ipld::Link(_) => "link",
+  }
+}
+
+/// Reasons an IPLD object can't be converted into a Rust value
+#[derive(PartialEq, Clone, Debug)]
+pub enum ConvertError {
+  /// The object is of another kind than the one the target type holds
+  Kind { expected: &'static str, found: &'static str },
+  /// An integer that doesn't fit in the target type
+  OutOfRange(i128),
+}
+
+impl fmt::Display for ConvertError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Kind { expected, found } => {
+        write!(f, "Expected {}, found {}", expected, found)
+      }
+      Self::OutOfRange(i) => write!(f, "Integer {} out of range", i),
+    }
+  }
+}
+
+impl From<ConvertError> for String {
+  fn from(e: ConvertError) -> String { e.to_string() }
+}
+
+/// Conversion of Rust values into IPLD. The orphan rule keeps `From` from
+/// being implemented for `Ipld` outside of sp_ipld, so this stands in for it.
+pub trait IntoIpld {
+  fn into_ipld(self) -> Ipld;
+}
+
+/// Fallible conversion of IPLD into Rust values, in place of `TryFrom`
+pub trait FromIpld: Sized {
+  fn try_from_ipld(ipld: Ipld) -> Result<Self, ConvertError>;
+}
+
+fn mismatch<T>(expected: &'static str, ipld: &Ipld) -> Result<T, ConvertError> {
+  Err(ConvertError::Kind { expected, found: kind(ipld) })
+}
+
+macro_rules! convert_int {
+  ($($t:ty),*) => {
+    $(
+      impl IntoIpld for $t {
+        fn into_ipld(self) -> Ipld { Ipld::Integer(i128::from(self)) }
+      }
+
+      impl FromIpld for $t {
+        fn try_from_ipld(ipld: Ipld) -> Result<Self, ConvertError> {
+          match ipld {
+            Ipld::Integer(i) => {
+              <$t>::try_from(i).map_err(|_| ConvertError::OutOfRange(i))
+            }
+            _ => mismatch("integer", &ipld),
+          }
+        }
+      }
+    )*
+  };
+}
+
+convert_int!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+macro_rules! convert_variant {
+  ($t:ty, $variant:ident, $kind:expr) => {
+    impl IntoIpld for $t {
+      fn into_ipld(self) -> Ipld { Ipld::$variant(self) }
+    }
+
+    impl FromIpld for $t {
+      fn try_from_ipld(ipld: Ipld) -> Result<Self, ConvertError> {
+        match ipld {
+          Ipld::$variant(x) => Ok(x),
+          _ => mismatch($kind, &ipld),
+        }
+      }
+    }
+  };
+}
+
+convert_variant!(bool, Bool, "bool");
+convert_variant!(f64, Float, "float");
+convert_variant!(String, String, "string");
+convert_variant!(Vec<u8>, Bytes, "bytes");
+convert_variant!(Vec<Ipld>, List, "list");
+convert_variant!(BTreeMap<String, Ipld>, StringMap, "map");
+convert_variant!(Cid, Link, "link");
+
+impl IntoIpld for &str {
+  fn into_ipld(self) -> Ipld { Ipld::String(self.to_string()) }
+}
+
+impl IntoIpld for Ipld {
+  fn into_ipld(self) -> Ipld { self }
+}
+
+impl FromIpld for Ipld {
+  fn try_from_ipld(ipld: Ipld) -> Result<Self, ConvertError> { Ok(ipld) }
+}
+
+/// `None` is `Ipld::Null`
+impl<T: IntoIpld> IntoIpld for Option<T> {
+  fn into_ipld(self) -> Ipld { self.map_or(Ipld::Null, IntoIpld::into_ipld) }
+}
+
+impl<T: FromIpld> FromIpld for Option<T> {
+  fn try_from_ipld(ipld: Ipld) -> Result<Self, ConvertError> {
+    match ipld {
+      Ipld::Null => Ok(None),
+      _ => T::try_from_ipld(ipld).map(Some),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::tests::arbitrary_cid;
+  use quickcheck::Gen;
+
+  fn roundtrip<T: IntoIpld + FromIpld + Clone + PartialEq>(x: T) -> bool {
+    T::try_from_ipld(x.clone().into_ipld()) == Ok(x)
+  }
+
+  #[quickcheck]
+  fn convert_roundtrip(a: bool, b: i64, c: u8, d: String, e: Vec<u8>) -> bool {
+    roundtrip(a)
+      && roundtrip(b)
+      && roundtrip(c)
+      && roundtrip(d)
+      && roundtrip(e)
+      && roundtrip(Some(b))
+      && roundtrip(None::<u8>)
+  }
+
+  #[test]
+  fn test_convert_errors() {
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    assert_eq!(Cid::try_from_ipld(cid.into_ipld()), Ok(cid));
+    assert_eq!(
+      u8::try_from_ipld(Ipld::Integer(256)),
+      Err(ConvertError::OutOfRange(256))
+    );
+    assert_eq!(
+      u64::try_from_ipld(Ipld::Integer(-1)),
+      Err(ConvertError::OutOfRange(-1))
+    );
+    assert_eq!(String::try_from_ipld("id".into_ipld()), Ok("id".to_string()));
+    assert_eq!(
+      bool::try_from_ipld(Ipld::Link(cid)),
+      Err(ConvertError::Kind { expected: "bool", found: "link" })
+    );
+  }
+}
@@ -0,0 +1,312 @@
+use core::fmt;
+
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+
+use sp_std::{
+  collections::{
+    btree_map::BTreeMap,
+    btree_set::BTreeSet,
+    vec_deque::VecDeque,
+  },
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::ipld::{
+  block::BlockError,
+  store::{
+    references,
+    BlockStore,
+  },
+};
+
+/// The order in which `Dag::walk` visits blocks
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Order {
+  /// Pre-order depth first, following links in the order they appear
+  DepthFirst,
+  /// Level by level from the root
+  BreadthFirst,
+}
+
+/// Reasons a walk can stop short
+#[derive(PartialEq, Clone, Debug)]
+pub enum WalkError {
+  /// A linked block isn't in the store
+  Missing(Cid),
+  Block(BlockError),
+}
+
+impl fmt::Display for WalkError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Missing(cid) => write!(f, "Missing block {}", cid),
+      Self::Block(e) => write!(f, "{}", e),
+    }
+  }
+}
+
+impl From<WalkError> for String {
+  fn from(e: WalkError) -> String { e.to_string() }
+}
+
+/// Traversals of the DAG of blocks reachable from a root
+pub struct Dag;
+
+impl Dag {
+  /// Walks the blocks reachable from `root` depth first, yielding each block
+  /// once even if it's linked from several places
+  pub fn walk<S: BlockStore>(root: Cid, store: &S) -> Walk<'_, S> {
+    Self::walk_in(root, store, Order::DepthFirst)
+  }
+
+  pub fn walk_in<S: BlockStore>(
+    root: Cid,
+    store: &S,
+    order: Order,
+  ) -> Walk<'_, S> {
+    let mut frontier = VecDeque::new();
+    frontier.push_back(root);
+    Walk { store, order, frontier, visited: BTreeSet::new() }
+  }
+}
+
+/// Iterator returned by `Dag::walk`. A block that's missing or doesn't
+/// decode is reported as an error and its links aren't followed, but the
+/// walk carries on with the rest of the DAG.
+pub struct Walk<'a, S: BlockStore> {
+  store: &'a S,
+  order: Order,
+  frontier: VecDeque<Cid>,
+  visited: BTreeSet<Cid>,
+}
+
+impl<'a, S: BlockStore> Walk<'a, S> {
+  /// The blocks yielded so far
+  pub fn visited(&self) -> &BTreeSet<Cid> { &self.visited }
+
+  fn next_cid(&mut self) -> Option<Cid> {
+    loop {
+      let cid = match self.order {
+        Order::DepthFirst => self.frontier.pop_back()?,
+        Order::BreadthFirst => self.frontier.pop_front()?,
+      };
+      if self.visited.insert(cid) {
+        return Some(cid);
+      }
+    }
+  }
+}
+
+/// Collects the links of an IPLD object, in the order they appear
+pub fn links(ipld: &Ipld) -> Vec<Cid> {
+  fn go(ipld: &Ipld, acc: &mut Vec<Cid>) {
+    match ipld {
+      Ipld::Link(cid) => acc.push(*cid),
+      Ipld::List(xs) => xs.iter().for_each(|x| go(x, acc)),
+      Ipld::StringMap(m) => m.values().for_each(|x| go(x, acc)),
+      _ => (),
+    }
+  }
+  let mut acc = Vec::new();
+  go(ipld, &mut acc);
+  acc
+}
+
+impl<'a, S: BlockStore> Iterator for Walk<'a, S> {
+  type Item = Result<(Cid, Ipld), WalkError>;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let cid = self.next_cid()?;
+    let block = match self.store.get(&cid) {
+      Some(block) => block,
+      None => return Some(Err(WalkError::Missing(cid))),
+    };
+    let ipld = match block.decode() {
+      Ok(ipld) => ipld,
+      Err(e) => return Some(Err(WalkError::Block(e))),
+    };
+    let mut children: Vec<Cid> =
+      links(&ipld).into_iter().filter(|c| !self.visited.contains(c)).collect();
+    // Pushed in reverse so the first link is popped first
+    if self.order == Order::DepthFirst {
+      children.reverse();
+    }
+    self.frontier.extend(children);
+    Some(Ok((cid, ipld)))
+  }
+}
+
+/// How many of the largest blocks `DagStats` keeps
+pub const LARGEST: usize = 10;
+
+/// Size and shape of the DAG under a root
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct DagStats {
+  /// Blocks found in the store, each counted once
+  pub blocks: usize,
+  pub bytes: usize,
+  /// Links on the longest path from the root, including links to missing
+  /// blocks
+  pub depth: usize,
+  /// Number of blocks and of bytes for each codec
+  pub codecs: BTreeMap<u64, (usize, usize)>,
+  /// The `LARGEST` largest blocks and their sizes, largest first
+  pub largest: Vec<(Cid, usize)>,
+  /// Linked blocks the store doesn't have
+  pub missing: BTreeSet<Cid>,
+}
+
+/// Gathers statistics on the blocks reachable from `root`. Links are read
+/// without decoding the blocks. Missing blocks are recorded rather than
+/// failing, but a block that doesn't decode is an error.
+pub fn stats<S: BlockStore>(
+  root: Cid,
+  store: &S,
+) -> Result<DagStats, WalkError> {
+  let mut stats = DagStats::default();
+  let mut children: BTreeMap<Cid, Vec<Cid>> = BTreeMap::new();
+  let mut frontier = vec![root];
+  while let Some(cid) = frontier.pop() {
+    if children.contains_key(&cid) || stats.missing.contains(&cid) {
+      continue;
+    }
+    let block = match store.get(&cid) {
+      Some(block) => block,
+      None => {
+        stats.missing.insert(cid);
+        continue;
+      }
+    };
+    let links = references(block.data())
+      .map_err(|e| WalkError::Block(BlockError::Decode(e.to_string())))?;
+    let size = block.data().len();
+    stats.blocks += 1;
+    stats.bytes += size;
+    let codec = stats.codecs.entry(block.codec()).or_insert((0, 0));
+    codec.0 += 1;
+    codec.1 += size;
+    stats.largest.push((cid, size));
+    frontier.extend(links.iter().copied());
+    children.insert(cid, links);
+  }
+  stats.largest.sort_by(|a, b| b.1.cmp(&a.1));
+  stats.largest.truncate(LARGEST);
+  stats.depth = longest_path(root, &children);
+  Ok(stats)
+}
+
+/// The number of links on the longest path from `root`, computed bottom up
+/// without recursion so deep DAGs can't overflow the stack
+fn longest_path(root: Cid, children: &BTreeMap<Cid, Vec<Cid>>) -> usize {
+  let mut depths: BTreeMap<Cid, usize> = BTreeMap::new();
+  let mut stack = vec![(root, false)];
+  while let Some((cid, expanded)) = stack.pop() {
+    if depths.contains_key(&cid) {
+      continue;
+    }
+    let kids = children.get(&cid).map_or(&[][..], |k| k.as_slice());
+    if expanded {
+      let depth =
+        kids.iter().filter_map(|k| depths.get(k)).map(|d| d + 1).max();
+      depths.insert(cid, depth.unwrap_or(0));
+    }
+    else {
+      stack.push((cid, true));
+      stack.extend(
+        kids.iter().filter(|k| !depths.contains_key(k)).map(|k| (*k, false)),
+      );
+    }
+  }
+  depths.get(&root).copied().unwrap_or(0)
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::ipld::{
+    block::Block,
+    borrowed::FloatPolicy,
+    hash::{
+      Hasher,
+      DAG_CBOR,
+    },
+    store::MemStore,
+  };
+
+  fn put(store: &MemStore, ipld: Ipld) -> Cid {
+    store.put(
+      Block::encode(DAG_CBOR, Hasher::default(), FloatPolicy::Strict, &ipld)
+        .unwrap(),
+    )
+  }
+
+  fn cids<S: BlockStore>(walk: Walk<S>) -> Vec<Cid> {
+    walk.map(|r| r.unwrap().0).collect()
+  }
+
+  #[test]
+  fn test_walk_orders() {
+    let store = MemStore::new();
+    let leaf = put(&store, Ipld::String("leaf".into()));
+    let a = put(&store, Ipld::List(vec![Ipld::Link(leaf), Ipld::Integer(1)]));
+    let b = put(&store, Ipld::List(vec![Ipld::Link(leaf), Ipld::Integer(2)]));
+    let root = put(&store, Ipld::List(vec![Ipld::Link(a), Ipld::Link(b)]));
+    assert_eq!(cids(Dag::walk(root, &store)), vec![root, a, leaf, b]);
+    assert_eq!(cids(Dag::walk_in(root, &store, Order::BreadthFirst)), vec![
+      root, a, b, leaf
+    ]);
+  }
+
+  #[test]
+  fn test_walk_missing() {
+    let store = MemStore::new();
+    let gone = Block::encode(
+      DAG_CBOR,
+      Hasher::default(),
+      FloatPolicy::Strict,
+      &Ipld::Null,
+    )
+    .unwrap()
+    .cid();
+    let root = put(&store, Ipld::List(vec![Ipld::Link(gone)]));
+    let mut walk = Dag::walk(root, &store);
+    assert_eq!(walk.next().map(|r| r.map(|x| x.0)), Some(Ok(root)));
+    assert_eq!(walk.next(), Some(Err(WalkError::Missing(gone))));
+    assert_eq!(walk.next(), None);
+  }
+
+  #[test]
+  fn test_stats() {
+    let store = MemStore::new();
+    let leaf = put(&store, Ipld::Bytes(vec![0; 100]));
+    let a = put(&store, Ipld::List(vec![Ipld::Link(leaf)]));
+    let b = put(&store, Ipld::List(vec![Ipld::Link(a), Ipld::Link(leaf)]));
+    let gone = Block::encode(
+      DAG_CBOR,
+      Hasher::default(),
+      FloatPolicy::Strict,
+      &Ipld::Null,
+    )
+    .unwrap()
+    .cid();
+    let root = put(&store, Ipld::List(vec![Ipld::Link(b), Ipld::Link(gone)]));
+    let stats = stats(root, &store).unwrap();
+    let bytes: usize = [root, a, b, leaf]
+      .iter()
+      .map(|c| store.get(c).unwrap().data().len())
+      .sum();
+    assert_eq!(stats.blocks, 4);
+    assert_eq!(stats.bytes, bytes);
+    assert_eq!(stats.depth, 3);
+    assert_eq!(stats.codecs.get(&DAG_CBOR), Some(&(4, bytes)));
+    assert_eq!(stats.largest[0], (leaf, 102));
+    assert_eq!(stats.largest.len(), 4);
+    assert_eq!(stats.missing.into_iter().collect::<Vec<_>>(), vec![gone]);
+  }
+}
@@ -0,0 +1,351 @@
+use core::fmt;
+
+use chacha20poly1305::{
+  aead::{
+    Aead,
+    NewAead,
+    Payload,
+  },
+  Key,
+  XChaCha20Poly1305,
+  XNonce,
+};
+use rand_core::{
+  CryptoRng,
+  RngCore,
+};
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  collections::btree_map::BTreeMap,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::ipld::{
+  block::{
+    Block,
+    BlockError,
+  },
+  borrowed::{
+    FloatPolicy,
+    IpldRef,
+  },
+  convert::kind,
+  error::EncodeError,
+  hash::{
+    Hasher,
+    DAG_CBOR,
+  },
+  writer,
+};
+
+/// The AEAD the envelope is sealed with, recorded in the envelope so other
+/// ciphers can be added later
+pub const AEAD: &str = "xchacha20poly1305";
+
+/// Associated data of the content ciphertext
+const CONTENT_AAD: &[u8] = b"yatima/encrypted-block";
+
+/// A 256 bit symmetric key
+pub type SecretKey = [u8; 32];
+
+/// Reasons an encrypted or signed envelope can't be made, opened or verified
+#[derive(PartialEq, Clone, Debug)]
+pub enum CryptoError {
+  /// The envelope has no wrapped key for this key id
+  UnknownRecipient(String),
+  /// Wrong key, or the envelope was tampered with
+  Decrypt,
+  Encrypt,
+  /// The signature doesn't match the payload and public key
+  BadSignature,
+  /// An algorithm other than the one envelopes are made with
+  Unsupported(String),
+  Malformed(String),
+  Encode(EncodeError),
+  Block(BlockError),
+}
+
+impl fmt::Display for CryptoError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnknownRecipient(kid) => write!(f, "No key for recipient {}", kid),
+      Self::Decrypt => write!(f, "Decryption failed"),
+      Self::Encrypt => write!(f, "Encryption failed"),
+      Self::BadSignature => write!(f, "Invalid signature"),
+      Self::Unsupported(a) => write!(f, "Unsupported algorithm {}", a),
+      Self::Malformed(e) => write!(f, "Malformed envelope: {}", e),
+      Self::Encode(e) => write!(f, "{}", e),
+      Self::Block(e) => write!(f, "{}", e),
+    }
+  }
+}
+
+impl From<CryptoError> for String {
+  fn from(e: CryptoError) -> String { e.to_string() }
+}
+
+/// The content key, encrypted under one recipient's key
+#[derive(PartialEq, Clone, Debug)]
+pub struct Recipient {
+  /// Identifies which of the recipient's keys to unwrap with
+  pub kid: String,
+  pub nonce: Vec<u8>,
+  pub wrapped: Vec<u8>,
+}
+
+/// A block encrypted under a fresh content key, which is in turn wrapped for
+/// each recipient. The CID and data of the inner block are both encrypted, so
+/// the envelope reveals nothing about its content but its size. The envelope
+/// is itself an IPLD object and is stored and addressed like any other block.
+#[derive(PartialEq, Clone, Debug)]
+pub struct EncryptedBlock {
+  pub nonce: Vec<u8>,
+  pub ciphertext: Vec<u8>,
+  pub recipients: Vec<Recipient>,
+}
+
+fn encrypt(
+  key: &SecretKey,
+  nonce: &[u8],
+  msg: &[u8],
+  aad: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+  XChaCha20Poly1305::new(Key::from_slice(key))
+    .encrypt(XNonce::from_slice(nonce), Payload { msg, aad })
+    .map_err(|_| CryptoError::Encrypt)
+}
+
+fn decrypt(
+  key: &[u8],
+  nonce: &[u8],
+  msg: &[u8],
+  aad: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+  if key.len() != 32 || nonce.len() != 24 {
+    return Err(CryptoError::Decrypt);
+  }
+  XChaCha20Poly1305::new(Key::from_slice(key))
+    .decrypt(XNonce::from_slice(nonce), Payload { msg, aad })
+    .map_err(|_| CryptoError::Decrypt)
+}
+
+fn nonce<R: RngCore + CryptoRng>(rng: &mut R) -> Vec<u8> {
+  let mut nonce = vec![0; 24];
+  rng.fill_bytes(&mut nonce);
+  nonce
+}
+
+impl EncryptedBlock {
+  /// Encrypts `block` so that each of `recipients`, given as a key id and
+  /// key, can open it
+  pub fn seal<R: RngCore + CryptoRng>(
+    block: &Block,
+    recipients: &[(&str, &SecretKey)],
+    rng: &mut R,
+  ) -> Result<Self, CryptoError> {
+    let mut content_key: SecretKey = [0; 32];
+    rng.fill_bytes(&mut content_key);
+    let plaintext = writer::encode(&Ipld::List(vec![
+      Ipld::Link(block.cid()),
+      Ipld::Bytes(block.data().to_vec()),
+    ]))
+    .map_err(CryptoError::Encode)?;
+    let content_nonce = nonce(rng);
+    let ciphertext =
+      encrypt(&content_key, &content_nonce, &plaintext, CONTENT_AAD)?;
+    let recipients = recipients
+      .iter()
+      .map(|(kid, key)| -> Result<Recipient, CryptoError> {
+        let nonce = nonce(rng);
+        // Binding the key id keeps a wrapped key from being moved to another
+        // recipient entry
+        let wrapped = encrypt(key, &nonce, &content_key, kid.as_bytes())?;
+        Ok(Recipient { kid: (*kid).to_owned(), nonce, wrapped })
+      })
+      .collect::<Result<_, _>>()?;
+    Ok(EncryptedBlock { nonce: content_nonce, ciphertext, recipients })
+  }
+
+  /// Decrypts the inner block with the key of recipient `kid`, checking that
+  /// it hashes to its CID
+  pub fn open(&self, kid: &str, key: &SecretKey) -> Result<Block, CryptoError> {
+    let recipient = self
+      .recipients
+      .iter()
+      .find(|r| r.kid == kid)
+      .ok_or_else(|| CryptoError::UnknownRecipient(kid.to_owned()))?;
+    let content_key =
+      decrypt(key, &recipient.nonce, &recipient.wrapped, kid.as_bytes())?;
+    let plaintext =
+      decrypt(&content_key, &self.nonce, &self.ciphertext, CONTENT_AAD)?;
+    match IpldRef::decode(&plaintext) {
+      Ok(IpldRef::List(xs)) => match xs.as_slice() {
+        [IpldRef::Link(cid), IpldRef::Bytes(data)] => {
+          Block::new(*cid, data.to_vec()).map_err(CryptoError::Block)
+        }
+        _ => Err(CryptoError::Malformed("expected [cid, data]".into())),
+      },
+      Ok(_) => Err(CryptoError::Malformed("expected [cid, data]".into())),
+      Err(e) => Err(CryptoError::Malformed(e.to_string())),
+    }
+  }
+
+  pub fn to_ipld(&self) -> Ipld {
+    let recipients = self
+      .recipients
+      .iter()
+      .map(|r| {
+        let mut map = BTreeMap::new();
+        map.insert("kid".to_owned(), Ipld::String(r.kid.clone()));
+        map.insert("nonce".to_owned(), Ipld::Bytes(r.nonce.clone()));
+        map.insert("wrapped".to_owned(), Ipld::Bytes(r.wrapped.clone()));
+        Ipld::StringMap(map)
+      })
+      .collect();
+    let mut map = BTreeMap::new();
+    map.insert("aead".to_owned(), Ipld::String(AEAD.to_owned()));
+    map.insert("nonce".to_owned(), Ipld::Bytes(self.nonce.clone()));
+    map.insert("ciphertext".to_owned(), Ipld::Bytes(self.ciphertext.clone()));
+    map.insert("recipients".to_owned(), Ipld::List(recipients));
+    Ipld::StringMap(map)
+  }
+
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, CryptoError> {
+    let map = match ipld {
+      Ipld::StringMap(map) => map,
+      x => {
+        return Err(CryptoError::Malformed(format!(
+          "expected map, found {}",
+          kind(x)
+        )));
+      }
+    };
+    let field = |map: &BTreeMap<String, Ipld>, key: &str| {
+      map
+        .get(key)
+        .cloned()
+        .ok_or_else(|| CryptoError::Malformed(format!("missing {}", key)))
+    };
+    let bytes = |map: &BTreeMap<String, Ipld>, key: &str| match field(map, key)?
+    {
+      Ipld::Bytes(b) => Ok(b),
+      x => Err(CryptoError::Malformed(format!(
+        "expected bytes at {}, found {}",
+        key,
+        kind(&x)
+      ))),
+    };
+    match field(map, "aead")? {
+      Ipld::String(a) if a == AEAD => (),
+      Ipld::String(a) => return Err(CryptoError::Unsupported(a)),
+      x => {
+        return Err(CryptoError::Malformed(format!(
+          "expected string at aead, found {}",
+          kind(&x)
+        )));
+      }
+    }
+    let recipients = match field(map, "recipients")? {
+      Ipld::List(rs) => rs
+        .iter()
+        .map(|r| match r {
+          Ipld::StringMap(r) => {
+            let kid = match field(r, "kid")? {
+              Ipld::String(kid) => kid,
+              x => {
+                return Err(CryptoError::Malformed(format!(
+                  "expected string at kid, found {}",
+                  kind(&x)
+                )));
+              }
+            };
+            Ok(Recipient {
+              kid,
+              nonce: bytes(r, "nonce")?,
+              wrapped: bytes(r, "wrapped")?,
+            })
+          }
+          x => Err(CryptoError::Malformed(format!(
+            "expected recipient map, found {}",
+            kind(x)
+          ))),
+        })
+        .collect::<Result<_, _>>()?,
+      x => {
+        return Err(CryptoError::Malformed(format!(
+          "expected list at recipients, found {}",
+          kind(&x)
+        )));
+      }
+    };
+    Ok(EncryptedBlock {
+      nonce: bytes(map, "nonce")?,
+      ciphertext: bytes(map, "ciphertext")?,
+      recipients,
+    })
+  }
+
+  /// The envelope as a DAG-CBOR block, ready to be stored
+  pub fn to_block(&self) -> Result<Block, CryptoError> {
+    Block::encode(
+      DAG_CBOR,
+      Hasher::default(),
+      FloatPolicy::Strict,
+      &self.to_ipld(),
+    )
+    .map_err(CryptoError::Block)
+  }
+
+  pub fn from_block(block: &Block) -> Result<Self, CryptoError> {
+    Self::from_ipld(&block.decode().map_err(CryptoError::Block)?)
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+
+  #[test]
+  fn test_seal_open() {
+    let mut rng = rand::thread_rng();
+    let block = Block::encode(
+      DAG_CBOR,
+      Hasher::default(),
+      FloatPolicy::Strict,
+      &Ipld::String("private".into()),
+    )
+    .unwrap();
+    let alice: SecretKey = [1; 32];
+    let bob: SecretKey = [2; 32];
+    let sealed = EncryptedBlock::seal(
+      &block,
+      &[("alice", &alice), ("bob", &bob)],
+      &mut rng,
+    )
+    .unwrap();
+    let sealed =
+      EncryptedBlock::from_block(&sealed.to_block().unwrap()).unwrap();
+    assert_eq!(sealed.open("alice", &alice), Ok(block.clone()));
+    assert_eq!(sealed.open("bob", &bob), Ok(block));
+    assert_eq!(sealed.open("alice", &bob), Err(CryptoError::Decrypt));
+    assert_eq!(
+      sealed.open("carol", &alice),
+      Err(CryptoError::UnknownRecipient("carol".to_owned()))
+    );
+
+    let mut swapped = sealed.clone();
+    swapped.recipients[0].kid = "bob".to_owned();
+    swapped.recipients.remove(1);
+    assert_eq!(swapped.open("bob", &alice), Err(CryptoError::Decrypt));
+    let mut tampered = sealed;
+    tampered.ciphertext[0] ^= 1;
+    assert_eq!(tampered.open("alice", &alice), Err(CryptoError::Decrypt));
+  }
+}
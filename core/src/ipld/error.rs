@@ -0,0 +1,145 @@
+use core::fmt;
+
+use alloc::string::String;
+
+/// Ways a DAG-CBOR block can fail to decode, each with the byte offset at
+/// which the problem was found
+#[derive(PartialEq, Clone, Debug)]
+pub enum DecodeError {
+  /// A header whose major type or additional info isn't allowed here
+  UnexpectedCode { code: u8, offset: usize, expected: &'static str },
+  /// A length that doesn't fit in the rest of the block
+  LengthOutOfRange { len: u64, offset: usize },
+  /// A string that isn't valid UTF-8
+  InvalidUtf8 { offset: usize },
+  /// Link bytes that aren't a valid CID
+  InvalidCid { offset: usize },
+  /// The block ended while `needed` more bytes were expected
+  UnexpectedEof { offset: usize, needed: usize },
+  /// A tag other than the CID tag
+  UnknownTag { tag: u64, offset: usize },
+  /// A map key that appears more than once
+  DuplicateKey { key: String, offset: usize },
+  /// Bytes left over after the top-level object
+  TrailingBytes { offset: usize },
+  /// Lists or maps nested deeper than the reader allows
+  DepthLimit { max: usize, offset: usize },
+  /// More list elements and map entries than the reader allows
+  ElementLimit { max: usize, offset: usize },
+  /// A NaN or infinite float, which DAG-CBOR forbids
+  NonFiniteFloat { offset: usize },
+  /// A float that isn't written at the width the reader requires
+  NonCanonicalFloat { offset: usize },
+  /// A bignum that fits in a plain integer or has leading zero bytes
+  NonCanonicalInteger { offset: usize },
+  /// An integer outside of the range of the type being read
+  IntegerOutOfRange { offset: usize },
+  /// The allocator couldn't provide room for a list of `len` elements
+  AllocationFailed { len: usize, offset: usize },
+}
+
+impl DecodeError {
+  /// The byte offset at which decoding failed
+  pub fn offset(&self) -> usize {
+    match self {
+      Self::UnexpectedCode { offset, .. }
+      | Self::LengthOutOfRange { offset, .. }
+      | Self::InvalidUtf8 { offset }
+      | Self::InvalidCid { offset }
+      | Self::UnexpectedEof { offset, .. }
+      | Self::UnknownTag { offset, .. }
+      | Self::DuplicateKey { offset, .. }
+      | Self::TrailingBytes { offset }
+      | Self::DepthLimit { offset, .. }
+      | Self::ElementLimit { offset, .. }
+      | Self::NonFiniteFloat { offset }
+      | Self::NonCanonicalFloat { offset }
+      | Self::NonCanonicalInteger { offset }
+      | Self::IntegerOutOfRange { offset }
+      | Self::AllocationFailed { offset, .. } => *offset,
+    }
+  }
+
+  /// Whether the block was cut short, as opposed to being malformed
+  pub fn is_truncation(&self) -> bool {
+    matches!(self, Self::UnexpectedEof { .. } | Self::LengthOutOfRange { .. })
+  }
+}
+
+impl fmt::Display for DecodeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UnexpectedCode { code, offset, expected } => write!(
+        f,
+        "Unexpected code {:#04x} at {}, expected {}",
+        code, offset, expected
+      ),
+      Self::LengthOutOfRange { len, offset } => {
+        write!(f, "Length {} out of range at {}", len, offset)
+      }
+      Self::InvalidUtf8 { offset } => write!(f, "Invalid UTF-8 at {}", offset),
+      Self::InvalidCid { offset } => write!(f, "Invalid CID at {}", offset),
+      Self::UnexpectedEof { offset, needed } => write!(
+        f,
+        "Unexpected end of input at {}, {} more bytes needed",
+        offset, needed
+      ),
+      Self::UnknownTag { tag, offset } => {
+        write!(f, "Unknown tag {} at {}", tag, offset)
+      }
+      Self::DuplicateKey { key, offset } => {
+        write!(f, "Duplicate key {} at {}", key, offset)
+      }
+      Self::TrailingBytes { offset } => {
+        write!(f, "Trailing bytes after object at {}", offset)
+      }
+      Self::DepthLimit { max, offset } => {
+        write!(f, "Nesting deeper than {} at {}", max, offset)
+      }
+      Self::ElementLimit { max, offset } => {
+        write!(f, "More than {} elements at {}", max, offset)
+      }
+      Self::NonFiniteFloat { offset } => {
+        write!(f, "Non-finite float at {}", offset)
+      }
+      Self::NonCanonicalFloat { offset } => {
+        write!(f, "Float at {} isn't in canonical form", offset)
+      }
+      Self::NonCanonicalInteger { offset } => {
+        write!(f, "Integer at {} isn't in canonical form", offset)
+      }
+      Self::IntegerOutOfRange { offset } => {
+        write!(f, "Integer out of range at {}", offset)
+      }
+      Self::AllocationFailed { len, offset } => {
+        write!(f, "Out of memory for a list of {} at {}", len, offset)
+      }
+    }
+  }
+}
+
+impl From<DecodeError> for String {
+  fn from(e: DecodeError) -> String { format!("{}", e) }
+}
+
+/// Ways an IPLD object can fail to encode as DAG-CBOR
+#[derive(PartialEq, Clone, Debug)]
+pub enum EncodeError {
+  /// An integer outside of the range CBOR major types 0 and 1 can hold
+  IntegerOutOfRange(i128),
+  /// A NaN or infinite float, which DAG-CBOR forbids
+  NonFiniteFloat(f64),
+}
+
+impl fmt::Display for EncodeError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::IntegerOutOfRange(i) => write!(f, "Integer {} out of range", i),
+      Self::NonFiniteFloat(x) => write!(f, "Non-finite float {}", x),
+    }
+  }
+}
+
+impl From<EncodeError> for String {
+  fn from(e: EncodeError) -> String { format!("{}", e) }
+}
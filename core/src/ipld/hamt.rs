@@ -0,0 +1,387 @@
+use core::fmt;
+
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+use sp_multihash::MultihashDigest;
+
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::ipld::{
+  block::{
+    Block,
+    BlockError,
+  },
+  borrowed::FloatPolicy,
+  hash::{
+    Hasher,
+    DAG_CBOR,
+  },
+  store::BlockStore,
+};
+
+/// Number of hash bits consumed per level, so every node has 256 slots
+pub const BIT_WIDTH: usize = 8;
+/// Entries a bucket holds before it is split into a child node
+pub const DEFAULT_BUCKET_SIZE: usize = 3;
+
+const MAP_BYTES: usize = (1 << BIT_WIDTH) / 8;
+
+/// Reasons a HAMT operation can fail
+#[derive(PartialEq, Clone, Debug)]
+pub enum HamtError {
+  /// A node referenced by the trie isn't in the store
+  MissingNode(Cid),
+  /// A block doesn't have the shape of a HAMT root or node
+  Malformed(Cid, String),
+  /// The key hashes collide on every bit with more than a bucket's worth of
+  /// other keys
+  MaxDepth,
+  Block(BlockError),
+}
+
+impl fmt::Display for HamtError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::MissingNode(cid) => write!(f, "Missing HAMT node {}", cid),
+      Self::Malformed(cid, msg) => {
+        write!(f, "Malformed HAMT node {}: {}", cid, msg)
+      }
+      Self::MaxDepth => write!(f, "HAMT key hashes exhausted"),
+      Self::Block(e) => write!(f, "{}", e),
+    }
+  }
+}
+
+impl From<HamtError> for String {
+  fn from(e: HamtError) -> String { e.to_string() }
+}
+
+impl From<BlockError> for HamtError {
+  fn from(e: BlockError) -> Self { Self::Block(e) }
+}
+
+/// A slot of a node: either a link to a child node or a bucket of entries
+/// sorted by key
+#[derive(PartialEq, Clone, Debug)]
+enum Element {
+  Link(Cid),
+  Bucket(Vec<(Vec<u8>, Ipld)>),
+}
+
+/// A HAMT node, laid out as in the IPLD HashMap spec: a bitmap of occupied
+/// slots followed by the occupied slots in order
+#[derive(PartialEq, Clone, Debug)]
+struct Node {
+  map: [u8; MAP_BYTES],
+  data: Vec<Element>,
+}
+
+impl Node {
+  fn empty() -> Self { Node { map: [0; MAP_BYTES], data: Vec::new() } }
+
+  // The bitmap is big-endian: slot 0 is the lowest bit of the last byte
+  fn has(&self, slot: usize) -> bool {
+    self.map[MAP_BYTES - 1 - slot / 8] & (1 << (slot % 8)) != 0
+  }
+
+  fn set(&mut self, slot: usize) {
+    self.map[MAP_BYTES - 1 - slot / 8] |= 1 << (slot % 8);
+  }
+
+  /// The position in `data` of a slot, i.e. the number of occupied slots
+  /// before it
+  fn position(&self, slot: usize) -> usize {
+    (0..slot).filter(|s| self.has(*s)).count()
+  }
+
+  fn to_ipld(&self) -> Ipld {
+    let data = self
+      .data
+      .iter()
+      .map(|e| match e {
+        Element::Link(cid) => Ipld::Link(*cid),
+        Element::Bucket(entries) => Ipld::List(
+          entries
+            .iter()
+            .map(|(k, v)| Ipld::List(vec![Ipld::Bytes(k.clone()), v.clone()]))
+            .collect(),
+        ),
+      })
+      .collect();
+    Ipld::List(vec![Ipld::Bytes(self.map.to_vec()), Ipld::List(data)])
+  }
+
+  fn from_ipld(cid: Cid, ipld: &Ipld) -> Result<Self, HamtError> {
+    let bad = |msg: &str| HamtError::Malformed(cid, msg.to_string());
+    let (map, data) = match ipld {
+      Ipld::List(xs) => match xs.as_slice() {
+        [Ipld::Bytes(map), Ipld::List(data)] if map.len() == MAP_BYTES => {
+          (map, data)
+        }
+        _ => return Err(bad("expected a bitmap and a list of elements")),
+      },
+      _ => return Err(bad("expected a list")),
+    };
+    let mut node = Node::empty();
+    node.map.copy_from_slice(map);
+    let occupied = (0..1 << BIT_WIDTH).filter(|s| node.has(*s)).count();
+    if occupied != data.len() {
+      return Err(bad("bitmap doesn't match the number of elements"));
+    }
+    for elem in data {
+      node.data.push(match elem {
+        Ipld::Link(cid) => Element::Link(*cid),
+        Ipld::List(entries) => {
+          let mut bucket = Vec::new();
+          for entry in entries {
+            match entry {
+              Ipld::List(kv) => match kv.as_slice() {
+                [Ipld::Bytes(k), v] => bucket.push((k.clone(), v.clone())),
+                _ => return Err(bad("expected a key and a value")),
+              },
+              _ => return Err(bad("expected a bucket entry")),
+            }
+          }
+          Element::Bucket(bucket)
+        }
+        _ => return Err(bad("expected a link or a bucket")),
+      });
+    }
+    Ok(node)
+  }
+}
+
+/// A map from byte keys to IPLD values, sharded over blocks so that lookups
+/// and inserts only touch the nodes along one key's path. The root block
+/// follows the IPLD HashMap spec with a bit width of 8, keys hashed with
+/// `hasher`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Hamt {
+  pub hasher: Hasher,
+  pub bucket_size: usize,
+  root: Node,
+}
+
+impl Default for Hamt {
+  fn default() -> Self {
+    Hamt {
+      hasher: Hasher::Sha2_256,
+      bucket_size: DEFAULT_BUCKET_SIZE,
+      root: Node::empty(),
+    }
+  }
+}
+
+impl Hamt {
+  pub fn new() -> Self { Self::default() }
+
+  /// Loads the root block of a HAMT
+  pub fn load(store: &impl BlockStore, cid: &Cid) -> Result<Self, HamtError> {
+    let bad = |msg: &str| HamtError::Malformed(*cid, msg.to_string());
+    let ipld = store.get(cid).ok_or(HamtError::MissingNode(*cid))?.decode()?;
+    let map = match ipld {
+      Ipld::StringMap(map) => map,
+      _ => return Err(bad("expected a map")),
+    };
+    let hasher = match map.get("hashAlg") {
+      Some(Ipld::Integer(code)) => Hasher::from_code(*code as u64)
+        .ok_or_else(|| bad("unsupported hash algorithm"))?,
+      _ => return Err(bad("expected a hashAlg")),
+    };
+    let bucket_size = match map.get("bucketSize") {
+      Some(Ipld::Integer(n)) if *n > 0 => *n as usize,
+      _ => return Err(bad("expected a positive bucketSize")),
+    };
+    let root = Node::from_ipld(*cid, map.get("hamt").unwrap_or(&Ipld::Null))?;
+    Ok(Hamt { hasher, bucket_size, root })
+  }
+
+  /// Stores the root block, returning its CID. Child nodes are stored as
+  /// they are changed by `insert`.
+  pub fn store(&self, store: &impl BlockStore) -> Result<Cid, HamtError> {
+    let mut map = BTreeMap::new();
+    map.insert(
+      "hashAlg".to_string(),
+      Ipld::Integer(u64::from(self.hasher.code()).into()),
+    );
+    map.insert(
+      "bucketSize".to_string(),
+      Ipld::Integer(self.bucket_size as i128),
+    );
+    map.insert("hamt".to_string(), self.root.to_ipld());
+    put(store, &Ipld::StringMap(map))
+  }
+
+  fn hash(&self, key: &[u8]) -> Vec<u8> {
+    self.hasher.code().digest(key).digest().to_vec()
+  }
+
+  /// Looks up the value stored under `key`
+  pub fn get(
+    &self,
+    store: &impl BlockStore,
+    key: &[u8],
+  ) -> Result<Option<Ipld>, HamtError> {
+    let hash = self.hash(key);
+    let mut node = self.root.clone();
+    for slot in hash.iter().map(|b| *b as usize) {
+      if !node.has(slot) {
+        return Ok(None);
+      }
+      match &node.data[node.position(slot)] {
+        Element::Bucket(entries) => {
+          return Ok(
+            entries.iter().find(|(k, _)| k == key).map(|(_, v)| v.clone()),
+          );
+        }
+        Element::Link(cid) => node = load_node(store, cid)?,
+      }
+    }
+    Ok(None)
+  }
+
+  /// Inserts or replaces the value stored under `key`
+  pub fn insert(
+    &mut self,
+    store: &impl BlockStore,
+    key: Vec<u8>,
+    value: Ipld,
+  ) -> Result<(), HamtError> {
+    let hash = self.hash(&key);
+    let mut root = core::mem::replace(&mut self.root, Node::empty());
+    let res = self.insert_at(store, &mut root, &hash, 0, key, value);
+    self.root = root;
+    res
+  }
+
+  fn insert_at(
+    &self,
+    store: &impl BlockStore,
+    node: &mut Node,
+    hash: &[u8],
+    depth: usize,
+    key: Vec<u8>,
+    value: Ipld,
+  ) -> Result<(), HamtError> {
+    let slot = *hash.get(depth).ok_or(HamtError::MaxDepth)? as usize;
+    let pos = node.position(slot);
+    if !node.has(slot) {
+      node.set(slot);
+      node.data.insert(pos, Element::Bucket(vec![(key, value)]));
+      return Ok(());
+    }
+    match &mut node.data[pos] {
+      Element::Link(cid) => {
+        let mut child = load_node(store, cid)?;
+        self.insert_at(store, &mut child, hash, depth + 1, key, value)?;
+        *cid = put(store, &child.to_ipld())?;
+      }
+      Element::Bucket(entries) => {
+        match entries.binary_search_by(|(k, _)| k.as_slice().cmp(&key)) {
+          Ok(i) => entries[i].1 = value,
+          Err(i) if entries.len() < self.bucket_size => {
+            entries.insert(i, (key, value))
+          }
+          Err(_) => {
+            let mut child = Node::empty();
+            let entries = core::mem::take(entries);
+            for (k, v) in
+              entries.into_iter().chain(core::iter::once((key, value)))
+            {
+              let h = self.hash(&k);
+              self.insert_at(store, &mut child, &h, depth + 1, k, v)?;
+            }
+            node.data[pos] = Element::Link(put(store, &child.to_ipld())?);
+          }
+        }
+      }
+    }
+    Ok(())
+  }
+
+  /// All entries of the map, in hash order
+  pub fn entries(
+    &self,
+    store: &impl BlockStore,
+  ) -> Result<Vec<(Vec<u8>, Ipld)>, HamtError> {
+    fn go(
+      store: &impl BlockStore,
+      node: &Node,
+      acc: &mut Vec<(Vec<u8>, Ipld)>,
+    ) -> Result<(), HamtError> {
+      for elem in &node.data {
+        match elem {
+          Element::Bucket(entries) => acc.extend(entries.iter().cloned()),
+          Element::Link(cid) => go(store, &load_node(store, cid)?, acc)?,
+        }
+      }
+      Ok(())
+    }
+    let mut acc = Vec::new();
+    go(store, &self.root, &mut acc)?;
+    Ok(acc)
+  }
+}
+
+fn put(store: &impl BlockStore, ipld: &Ipld) -> Result<Cid, HamtError> {
+  Ok(store.put(Block::encode(
+    DAG_CBOR,
+    Hasher::default(),
+    FloatPolicy::Strict,
+    ipld,
+  )?))
+}
+
+fn load_node(store: &impl BlockStore, cid: &Cid) -> Result<Node, HamtError> {
+  let block = store.get(cid).ok_or(HamtError::MissingNode(*cid))?;
+  Node::from_ipld(*cid, &block.decode()?)
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::ipld::store::MemStore;
+
+  fn key(i: u32) -> Vec<u8> { format!("def{}", i).into_bytes() }
+
+  #[test]
+  fn test_hamt_insert_get() {
+    let store = MemStore::new();
+    let mut hamt = Hamt::new();
+    for i in 0..2000 {
+      hamt.insert(&store, key(i), Ipld::Integer(i.into())).unwrap();
+    }
+    hamt.insert(&store, key(7), Ipld::Null).unwrap();
+    let root = hamt.store(&store).unwrap();
+    let hamt = Hamt::load(&store, &root).unwrap();
+    assert_eq!(hamt.get(&store, &key(7)), Ok(Some(Ipld::Null)));
+    for i in (0..2000).filter(|i| *i != 7) {
+      assert_eq!(hamt.get(&store, &key(i)), Ok(Some(Ipld::Integer(i.into()))));
+    }
+    assert_eq!(hamt.get(&store, b"missing"), Ok(None));
+    assert_eq!(hamt.entries(&store).unwrap().len(), 2000);
+    // 2000 entries don't fit in the root's 256 buckets of 3
+    assert!(store.len() > 1);
+  }
+
+  #[test]
+  fn test_hamt_deterministic() {
+    let (a, b) = (MemStore::new(), MemStore::new());
+    let (mut x, mut y) = (Hamt::new(), Hamt::new());
+    for i in 0..500 {
+      x.insert(&a, key(i), Ipld::Bool(true)).unwrap();
+      y.insert(&b, key(499 - i), Ipld::Bool(true)).unwrap();
+    }
+    assert_eq!(x.store(&a), y.store(&b));
+    let null = put(&a, &Ipld::Null).unwrap();
+    assert!(matches!(Hamt::load(&a, &null), Err(HamtError::Malformed(..))));
+  }
+}
@@ -0,0 +1,122 @@
+use core::fmt;
+
+use sp_cid::Cid;
+use sp_ipld::{
+  dag_cbor::DagCborCodec,
+  Codec,
+  Ipld,
+};
+use sp_multihash::{
+  Code,
+  MultihashDigest,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// Multicodec code of DAG-CBOR, the codec used for all Yatima blocks
+pub const DAG_CBOR: u64 = 0x71;
+
+/// Hash functions available for content addressing
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Hasher {
+  Sha2_256,
+  Blake2b256,
+  Blake3,
+}
+
+impl Hasher {
+  /// Returns the multihash code of the hash function
+  pub fn code(self) -> Code {
+    match self {
+      Self::Sha2_256 => Code::Sha2_256,
+      Self::Blake2b256 => Code::Blake2b256,
+      Self::Blake3 => Code::Blake3_256,
+    }
+  }
+
+  /// Returns the multihash table name, as used by the IPFS HTTP API
+  pub fn name(self) -> &'static str {
+    match self {
+      Self::Sha2_256 => "sha2-256",
+      Self::Blake2b256 => "blake2b-256",
+      Self::Blake3 => "blake3",
+    }
+  }
+
+  /// Looks up the hash function of a multihash code
+  pub fn from_code(code: u64) -> Option<Self> {
+    [Self::Sha2_256, Self::Blake2b256, Self::Blake3]
+      .iter()
+      .copied()
+      .find(|h| u64::from(h.code()) == code)
+  }
+
+  /// Parses a multihash table name
+  pub fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "sha2-256" => Some(Self::Sha2_256),
+      "blake2b-256" => Some(Self::Blake2b256),
+      "blake3" => Some(Self::Blake3),
+      _ => None,
+    }
+  }
+}
+
+impl Default for Hasher {
+  fn default() -> Self { Self::Blake2b256 }
+}
+
+impl fmt::Display for Hasher {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.name())
+  }
+}
+
+/// Encodes an IPLD object as DAG-CBOR and computes its CIDv1 under `codec`
+/// with the given hash function. `cid(DAG_CBOR, Hasher::Blake2b256, ipld)`
+/// agrees with `sp_ipld::dag_cbor::cid`.
+pub fn cid(codec: u64, hasher: Hasher, ipld: &Ipld) -> Result<Cid, String> {
+  let bytes = DagCborCodec.encode(ipld).map_err(|e| e.to_string())?;
+  Ok(digest(codec, hasher, &bytes.into_inner()))
+}
+
+/// Computes the CIDv1 of already encoded bytes
+pub fn digest(codec: u64, hasher: Hasher, bytes: &[u8]) -> Cid {
+  Cid::new_v1(codec, hasher.code().digest(bytes))
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::term::Term;
+  use sp_ipld::dag_cbor;
+
+  #[quickcheck]
+  fn hash_default_agrees(x: Term) -> bool {
+    let ipld = x.embed().0.to_ipld();
+    cid(DAG_CBOR, Hasher::default(), &ipld) == Ok(dag_cbor::cid(&ipld))
+  }
+
+  #[quickcheck]
+  fn hash_codes(x: Term) -> bool {
+    let ipld = x.embed().0.to_ipld();
+    [Hasher::Sha2_256, Hasher::Blake2b256, Hasher::Blake3].iter().all(|h| {
+      let code: u64 = h.code().into();
+      cid(DAG_CBOR, *h, &ipld).map_or(false, |c| c.hash().code() == code)
+    })
+  }
+
+  #[test]
+  fn hash_names() {
+    for h in [Hasher::Sha2_256, Hasher::Blake2b256, Hasher::Blake3].iter() {
+      assert_eq!(Hasher::from_name(h.name()), Some(*h));
+    }
+    assert_eq!(Hasher::from_name("md5"), None);
+    for h in [Hasher::Sha2_256, Hasher::Blake2b256, Hasher::Blake3].iter() {
+      assert_eq!(Hasher::from_code(h.code().into()), Some(*h));
+    }
+  }
+}
@@ -0,0 +1,131 @@
+use multibase::Base;
+use sp_cid::{
+  Cid,
+  Version,
+};
+use sp_multihash::Code;
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// Multicodec code of DAG-PB, the codec implied by every CIDv0
+pub const DAG_PB: u64 = 0x70;
+
+/// Converts a CIDv0 into the equivalent CIDv1, leaving CIDv1s unchanged
+pub fn to_v1(cid: Cid) -> Cid {
+  match cid.version() {
+    Version::V0 => Cid::new_v1(DAG_PB, *cid.hash()),
+    Version::V1 => cid,
+  }
+}
+
+/// Renders a CID as a base32 CIDv1, so that the same content prints the same
+/// way whichever version it was given in
+pub fn cid_string(cid: &Cid) -> String { to_v1(*cid).to_string() }
+
+/// Renders a CID in a multibase encoding as a CIDv1, except that a CIDv0
+/// asked for in base58btc keeps its legacy unprefixed form
+pub fn cid_to_base(cid: &Cid, base: Base) -> String {
+  match (cid.version(), base) {
+    (Version::V0, Base::Base58Btc) => cid.to_string(),
+    _ => multibase::encode(base, to_v1(*cid).to_bytes()),
+  }
+}
+
+/// Renders the encoded data of a block in a multibase encoding
+pub fn block_to_base(data: &[u8], base: Base) -> String {
+  multibase::encode(base, data)
+}
+
+/// Parses the name of a multibase encoding, such as given to `--base`
+pub fn parse_base(name: &str) -> Result<Base, String> {
+  match name {
+    "base32" | "base32lower" => Ok(Base::Base32Lower),
+    "base58" | "base58btc" => Ok(Base::Base58Btc),
+    "base16" | "hex" => Ok(Base::Base16Lower),
+    "base36" => Ok(Base::Base36Lower),
+    "base64" => Ok(Base::Base64),
+    _ => Err(format!(
+      "Unknown base `{}`: expected base32, base58btc, base16, base36 or base64",
+      name
+    )),
+  }
+}
+
+/// Converts a CID into a CIDv0 if it can be represented as one, that is if it
+/// addresses DAG-PB with a sha2-256 multihash
+pub fn to_v0(cid: Cid) -> Option<Cid> {
+  let sha2_256: u64 = Code::Sha2_256.into();
+  match cid.version() {
+    Version::V0 => Some(cid),
+    Version::V1 if cid.codec() == DAG_PB && cid.hash().code() == sha2_256 => {
+      Cid::new_v0(*cid.hash()).ok()
+    }
+    Version::V1 => None,
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    parse::{
+      parse_cid,
+      parse_cid_str,
+    },
+    tests::arbitrary_cid,
+  };
+  use quickcheck::Gen;
+
+  const V0: &str = "QmYwAPJzv5CZsnA625s3Xf2nemtYgPpHdWEz79ojWnPbdG";
+
+  #[test]
+  fn test_parse_v0() {
+    let cid = parse_cid(V0).unwrap();
+    assert_eq!(cid.version(), Version::V0);
+    assert_eq!(cid.codec(), DAG_PB);
+    assert_eq!(cid.to_string(), V0);
+    assert_eq!(parse_cid(&cid.to_string()), Ok(cid));
+  }
+
+  #[test]
+  fn test_v0_v1_roundtrip() {
+    let v0 = parse_cid(V0).unwrap();
+    let v1 = to_v1(v0);
+    assert_eq!(v1.version(), Version::V1);
+    assert_eq!(parse_cid(&v1.to_string()), Ok(v1));
+    assert_eq!(to_v0(v1), Some(v0));
+    let other = arbitrary_cid(&mut Gen::new(10));
+    assert_eq!(to_v1(other), other);
+    assert_eq!(to_v0(other), None);
+  }
+
+  #[test]
+  fn test_parse_cid_str() {
+    let v0 = parse_cid_str(V0).unwrap();
+    let v1 = parse_cid_str(&cid_string(&v0)).unwrap();
+    assert_eq!(v1, to_v1(v0));
+    assert!(cid_string(&v0).starts_with('b'));
+    assert_eq!(parse_cid_str(&format!(" {} ", cid_string(&v1))), Ok(v1));
+    assert!(parse_cid_str(&format!("{}x", cid_string(&v1))).is_err());
+    assert!(parse_cid_str("foo").is_err());
+    assert!(parse_cid_str("").is_err());
+  }
+
+  #[test]
+  fn test_cid_to_base() {
+    let v0 = parse_cid_str(V0).unwrap();
+    assert_eq!(cid_to_base(&v0, Base::Base58Btc), V0);
+    let other = arbitrary_cid(&mut Gen::new(10));
+    for name in ["base32", "base58btc", "base16", "base36", "base64"].iter() {
+      let base = parse_base(name).unwrap();
+      assert_eq!(parse_cid_str(&cid_to_base(&other, base)), Ok(other));
+      assert_eq!(parse_cid_str(&cid_to_base(&v0, base)).map(to_v1), Ok(to_v1(v0)));
+    }
+    assert_eq!(cid_to_base(&other, Base::Base32Lower), cid_string(&other));
+    assert!(parse_base("base2048").is_err());
+    assert_eq!(block_to_base(&[0xf6], Base::Base16Lower), "ff6");
+  }
+}
@@ -0,0 +1,184 @@
+use core::{
+  cmp::Ordering,
+  hash::{
+    Hash,
+    Hasher,
+  },
+};
+
+use sp_ipld::Ipld;
+
+/// The rank of each kind in the order of `compare`
+fn rank(ipld: &Ipld) -> u8 {
+  match ipld {
+    Ipld::Null => 0,
+    Ipld::Bool(_) => 1,
+    Ipld::Integer(_) => 2,
+    Ipld::Float(_) => 3,
+    Ipld::String(_) => 4,
+    Ipld::Bytes(_) => 5,
+    Ipld::List(_) => 6,
+    Ipld::StringMap(_) => 7,
+    Ipld::Link(_) => 8,
+  }
+}
+
+/// Maps the bits of a float to an integer that orders the same way: negative
+/// floats below positive ones, `-0.0` below `0.0` and NaNs at the ends
+fn float_key(f: f64) -> i64 {
+  let bits = f.to_bits() as i64;
+  bits ^ (((bits >> 63) as u64) >> 1) as i64
+}
+
+/// A total order on IPLD objects. Objects of different kinds are ordered by
+/// kind, null, bool, integer, float, string, bytes, list, map and link in
+/// that order. Within a kind, floats are ordered by IEEE 754 `totalOrder`,
+/// strings and bytes bytewise, lists lexicographically, maps as the list of
+/// their entries in key order, and links as CIDs.
+///
+/// This differs from `==` on `Ipld` only for floats: NaNs with the same bits
+/// are equal and `-0.0` is not equal to `0.0`.
+pub fn compare(a: &Ipld, b: &Ipld) -> Ordering {
+  match (a, b) {
+    (Ipld::Null, Ipld::Null) => Ordering::Equal,
+    (Ipld::Bool(a), Ipld::Bool(b)) => a.cmp(b),
+    (Ipld::Integer(a), Ipld::Integer(b)) => a.cmp(b),
+    (Ipld::Float(a), Ipld::Float(b)) => float_key(*a).cmp(&float_key(*b)),
+    (Ipld::String(a), Ipld::String(b)) => a.cmp(b),
+    (Ipld::Bytes(a), Ipld::Bytes(b)) => a.cmp(b),
+    (Ipld::List(a), Ipld::List(b)) => a
+      .iter()
+      .zip(b)
+      .map(|(a, b)| compare(a, b))
+      .find(|o| *o != Ordering::Equal)
+      .unwrap_or_else(|| a.len().cmp(&b.len())),
+    (Ipld::StringMap(a), Ipld::StringMap(b)) => a
+      .iter()
+      .zip(b)
+      .map(|((ka, va), (kb, vb))| ka.cmp(kb).then_with(|| compare(va, vb)))
+      .find(|o| *o != Ordering::Equal)
+      .unwrap_or_else(|| a.len().cmp(&b.len())),
+    (Ipld::Link(a), Ipld::Link(b)) => a.cmp(b),
+    _ => rank(a).cmp(&rank(b)),
+  }
+}
+
+/// Feeds an IPLD object to a hasher, consistently with `compare`
+pub fn hash<H: Hasher>(ipld: &Ipld, state: &mut H) {
+  rank(ipld).hash(state);
+  match ipld {
+    Ipld::Null => (),
+    Ipld::Bool(b) => b.hash(state),
+    Ipld::Integer(i) => i.hash(state),
+    Ipld::Float(f) => f.to_bits().hash(state),
+    Ipld::String(s) => s.hash(state),
+    Ipld::Bytes(b) => b.hash(state),
+    Ipld::List(xs) => {
+      xs.len().hash(state);
+      xs.iter().for_each(|x| hash(x, state));
+    }
+    Ipld::StringMap(m) => {
+      m.len().hash(state);
+      for (k, v) in m {
+        k.hash(state);
+        hash(v, state);
+      }
+    }
+    Ipld::Link(cid) => Hash::hash(cid, state),
+  }
+}
+
+/// An IPLD object with `Eq`, `Ord` and `Hash` given by `compare` and `hash`,
+/// for keying sets and maps by IPLD values. The orphan rule keeps these from
+/// being implemented for `Ipld` itself.
+#[derive(Clone, Debug)]
+pub struct IpldKey(pub Ipld);
+
+impl PartialEq for IpldKey {
+  fn eq(&self, other: &Self) -> bool {
+    compare(&self.0, &other.0) == Ordering::Equal
+  }
+}
+
+impl Eq for IpldKey {}
+
+impl PartialOrd for IpldKey {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+impl Ord for IpldKey {
+  fn cmp(&self, other: &Self) -> Ordering { compare(&self.0, &other.0) }
+}
+
+impl Hash for IpldKey {
+  fn hash<H: Hasher>(&self, state: &mut H) { hash(&self.0, state) }
+}
+
+impl From<Ipld> for IpldKey {
+  fn from(ipld: Ipld) -> Self { IpldKey(ipld) }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::term::Term;
+  use std::collections::{
+    hash_map::DefaultHasher,
+    BTreeSet,
+    HashSet,
+  };
+
+  fn hash_of(ipld: &Ipld) -> u64 {
+    let mut state = DefaultHasher::new();
+    hash(ipld, &mut state);
+    state.finish()
+  }
+
+  #[quickcheck]
+  fn ord_agrees_with_eq(x: Term, y: Term) -> bool {
+    let (x, y) = (x.embed().0.to_ipld(), y.embed().0.to_ipld());
+    (compare(&x, &y) == Ordering::Equal) == (x == y)
+      && compare(&x, &y) == compare(&y, &x).reverse()
+      && compare(&x, &x) == Ordering::Equal
+      && hash_of(&x) == hash_of(&x.clone())
+  }
+
+  #[test]
+  fn test_ord_floats() {
+    let floats = [f64::NEG_INFINITY, -1.0, -0.0, 0.0, 1.5, f64::INFINITY];
+    for w in floats.windows(2) {
+      assert_eq!(
+        compare(&Ipld::Float(w[0]), &Ipld::Float(w[1])),
+        Ordering::Less
+      );
+    }
+    assert_eq!(IpldKey(Ipld::Float(f64::NAN)), IpldKey(Ipld::Float(f64::NAN)));
+    assert_eq!(
+      compare(&Ipld::Integer(i128::MAX), &Ipld::Float(f64::NEG_INFINITY)),
+      Ordering::Less
+    );
+  }
+
+  #[test]
+  fn test_ipld_key_sets() {
+    let values = vec![
+      Ipld::List(vec![Ipld::Integer(1), Ipld::Null]),
+      Ipld::String("a".into()),
+      Ipld::List(vec![Ipld::Integer(1)]),
+      Ipld::String("a".into()),
+      Ipld::Null,
+    ];
+    let set: BTreeSet<IpldKey> = values.iter().cloned().map(IpldKey).collect();
+    let sorted: Vec<Ipld> = set.into_iter().map(|k| k.0).collect();
+    assert_eq!(sorted, vec![
+      Ipld::Null,
+      Ipld::String("a".into()),
+      Ipld::List(vec![Ipld::Integer(1)]),
+      Ipld::List(vec![Ipld::Integer(1), Ipld::Null]),
+    ]);
+    let set: HashSet<IpldKey> = values.into_iter().map(IpldKey).collect();
+    assert_eq!(set.len(), 4);
+  }
+}
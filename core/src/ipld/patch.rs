@@ -0,0 +1,343 @@
+use core::{
+  fmt,
+  slice,
+};
+
+use sp_ipld::Ipld;
+
+use sp_std::vec::Vec;
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::ipld::path::PathError;
+
+/// A step into a list or a map. Keys are kept whole rather than written into
+/// a slash-separated path, since they may themselves contain slashes or be
+/// empty.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Segment {
+  Index(usize),
+  Key(String),
+}
+
+impl fmt::Display for Segment {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Index(idx) => write!(f, "{}", idx),
+      Self::Key(key) if key.is_empty() || key.contains('/') => {
+        write!(f, "{:?}", key)
+      }
+      Self::Key(key) => write!(f, "{}", key),
+    }
+  }
+}
+
+/// Writes a path for display and error messages, slash-separated
+pub fn show_path(path: &[Segment]) -> String {
+  path.iter().map(|seg| seg.to_string()).collect::<Vec<_>>().join("/")
+}
+
+/// A single edit to an IPLD object at a path. Paths into lists refer to the
+/// list as it is when the edit is applied, after every earlier edit of the
+/// script.
+#[derive(PartialEq, Clone, Debug)]
+pub enum Patch {
+  /// Inserts a list element or map entry
+  Add(Vec<Segment>, Ipld),
+  /// Removes a list element or map entry
+  Remove(Vec<Segment>),
+  /// Replaces the value at a path
+  Replace(Vec<Segment>, Ipld),
+}
+
+impl Patch {
+  pub fn path(&self) -> &[Segment] {
+    match self {
+      Self::Add(p, _) | Self::Remove(p) | Self::Replace(p, _) => p,
+    }
+  }
+}
+
+impl fmt::Display for Patch {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Add(p, v) => write!(f, "+ /{}: {:?}", show_path(p), v),
+      Self::Remove(p) => write!(f, "- /{}", show_path(p)),
+      Self::Replace(p, v) => write!(f, "~ /{}: {:?}", show_path(p), v),
+    }
+  }
+}
+
+fn join(path: &[Segment], seg: Segment) -> Vec<Segment> {
+  let mut path = path.to_vec();
+  path.push(seg);
+  path
+}
+
+/// Computes an edit script turning `a` into `b`. Maps are compared key by key
+/// and lists are aligned on their longest common subsequence, so an element
+/// inserted in the middle of a list is a single `Add`.
+pub fn diff(a: &Ipld, b: &Ipld) -> Vec<Patch> {
+  let mut patches = Vec::new();
+  diff_at(Vec::new(), a, b, &mut patches);
+  patches
+}
+
+fn diff_at(path: Vec<Segment>, a: &Ipld, b: &Ipld, patches: &mut Vec<Patch>) {
+  if a == b {
+    return;
+  }
+  match (a, b) {
+    (Ipld::StringMap(xs), Ipld::StringMap(ys)) => {
+      let key = |k: &String| join(&path, Segment::Key(k.clone()));
+      for (k, x) in xs {
+        match ys.get(k) {
+          Some(y) => diff_at(key(k), x, y, patches),
+          None => patches.push(Patch::Remove(key(k))),
+        }
+      }
+      for (k, y) in ys {
+        if !xs.contains_key(k) {
+          patches.push(Patch::Add(key(k), y.clone()));
+        }
+      }
+    }
+    (Ipld::List(xs), Ipld::List(ys)) => diff_lists(&path, xs, ys, patches),
+    _ => patches.push(Patch::Replace(path, b.clone())),
+  }
+}
+
+/// Pairs of indices of equal elements in a longest common subsequence
+fn lcs(xs: &[Ipld], ys: &[Ipld]) -> Vec<(usize, usize)> {
+  let (n, m) = (xs.len(), ys.len());
+  let mut table = vec![vec![0usize; m + 1]; n + 1];
+  for i in (0..n).rev() {
+    for j in (0..m).rev() {
+      table[i][j] = if xs[i] == ys[j] {
+        table[i + 1][j + 1] + 1
+      }
+      else {
+        table[i + 1][j].max(table[i][j + 1])
+      };
+    }
+  }
+  let mut pairs = Vec::new();
+  let (mut i, mut j) = (0, 0);
+  while i < n && j < m {
+    if xs[i] == ys[j] {
+      pairs.push((i, j));
+      i += 1;
+      j += 1;
+    }
+    else if table[i + 1][j] >= table[i][j + 1] {
+      i += 1;
+    }
+    else {
+      j += 1;
+    }
+  }
+  pairs
+}
+
+fn diff_lists(
+  path: &[Segment],
+  xs: &[Ipld],
+  ys: &[Ipld],
+  patches: &mut Vec<Patch>,
+) {
+  let index = |k| join(path, Segment::Index(k));
+  let mut pairs = lcs(xs, ys);
+  pairs.push((xs.len(), ys.len()));
+  // `k` is the position in the list as edited so far
+  let (mut i, mut j, mut k) = (0, 0, 0);
+  for (mi, mj) in pairs {
+    let (removed, added) = (&xs[i..mi], &ys[j..mj]);
+    let changed = removed.len().min(added.len());
+    for (x, y) in removed.iter().zip(added) {
+      diff_at(index(k), x, y, patches);
+      k += 1;
+    }
+    for _ in changed..removed.len() {
+      patches.push(Patch::Remove(index(k)));
+    }
+    for y in &added[changed..] {
+      patches.push(Patch::Add(index(k), y.clone()));
+      k += 1;
+    }
+    // Skip the matched element
+    i = mi + 1;
+    j = mj + 1;
+    k += 1;
+  }
+}
+
+fn index(at: &str, seg: &Segment, len: usize) -> Result<usize, PathError> {
+  match seg {
+    Segment::Index(idx) if *idx > len => {
+      Err(PathError::IndexOutOfRange(at.to_string(), *idx, len))
+    }
+    Segment::Index(idx) => Ok(*idx),
+    Segment::Key(key) => {
+      Err(PathError::InvalidIndex(at.to_string(), key.to_string()))
+    }
+  }
+}
+
+fn key<'a>(at: &str, seg: &'a Segment) -> Result<&'a str, PathError> {
+  match seg {
+    Segment::Key(key) => Ok(key),
+    Segment::Index(idx) => {
+      Err(PathError::MissingKey(at.to_string(), idx.to_string()))
+    }
+  }
+}
+
+fn get_mut<'a>(
+  ipld: &'a mut Ipld,
+  segs: &[Segment],
+) -> Result<&'a mut Ipld, PathError> {
+  let mut node = ipld;
+  for (i, seg) in segs.iter().enumerate() {
+    let at = show_path(&segs[..i]);
+    node = match node {
+      Ipld::List(xs) => {
+        let len = xs.len();
+        let idx = index(&at, seg, len)?;
+        xs.get_mut(idx).ok_or(PathError::IndexOutOfRange(at, idx, len))?
+      }
+      Ipld::StringMap(map) => {
+        let key = key(&at, seg)?;
+        map
+          .get_mut(key)
+          .ok_or_else(|| PathError::MissingKey(at, key.to_string()))?
+      }
+      _ => return Err(PathError::NotIndexable(at, seg.to_string())),
+    };
+  }
+  Ok(node)
+}
+
+/// Applies a single edit in place
+pub fn apply_one(ipld: &mut Ipld, patch: &Patch) -> Result<(), PathError> {
+  let (last, parent) = match patch.path().split_last() {
+    Some((last, parent)) => (last, parent),
+    None => {
+      return match patch {
+        Patch::Replace(_, v) => {
+          *ipld = v.clone();
+          Ok(())
+        }
+        _ => Err(PathError::NotIndexable(String::new(), String::new())),
+      };
+    }
+  };
+  let at = show_path(parent);
+  match (get_mut(ipld, parent)?, patch) {
+    (Ipld::List(xs), Patch::Add(_, v)) => {
+      let idx = index(&at, last, xs.len())?;
+      xs.insert(idx, v.clone());
+    }
+    (Ipld::List(xs), Patch::Remove(_)) => {
+      let idx = index(&at, last, xs.len())?;
+      if idx == xs.len() {
+        return Err(PathError::IndexOutOfRange(at, idx, xs.len()));
+      }
+      xs.remove(idx);
+    }
+    (Ipld::StringMap(map), Patch::Add(_, v)) => {
+      map.insert(key(&at, last)?.to_string(), v.clone());
+    }
+    (Ipld::StringMap(map), Patch::Remove(_)) => {
+      let key = key(&at, last)?;
+      map
+        .remove(key)
+        .ok_or_else(|| PathError::MissingKey(at, key.to_string()))?;
+    }
+    (node, Patch::Replace(_, v)) => {
+      *get_mut(node, slice::from_ref(last))? = v.clone();
+    }
+    _ => return Err(PathError::NotIndexable(at, last.to_string())),
+  }
+  Ok(())
+}
+
+/// Applies an edit script such as one produced by `diff`
+pub fn apply(mut ipld: Ipld, patches: &[Patch]) -> Result<Ipld, PathError> {
+  for patch in patches {
+    apply_one(&mut ipld, patch)?;
+  }
+  Ok(ipld)
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::term::Term;
+  use sp_std::collections::btree_map::BTreeMap;
+
+  #[quickcheck]
+  fn patch_roundtrip(x: Term, y: Term) -> bool {
+    let (a, b) = (x.embed().0.to_ipld(), y.embed().0.to_ipld());
+    apply(a.clone(), &diff(&a, &b)) == Ok(b.clone()) && diff(&a, &a).is_empty()
+  }
+
+  #[test]
+  fn test_list_insert() {
+    let xs =
+      |v: Vec<i128>| Ipld::List(v.into_iter().map(Ipld::Integer).collect());
+    let a = xs(vec![1, 2, 3, 4]);
+    let b = xs(vec![1, 5, 2, 3]);
+    let patches = diff(&a, &b);
+    assert_eq!(patches, vec![
+      Patch::Add(vec![Segment::Index(1)], Ipld::Integer(5)),
+      Patch::Remove(vec![Segment::Index(4)]),
+    ]);
+    assert_eq!(apply(a, &patches), Ok(b));
+  }
+
+  #[test]
+  fn test_map_edit() {
+    let mut x = BTreeMap::new();
+    x.insert("name".to_string(), Ipld::String("id".to_string()));
+    x.insert("old".to_string(), Ipld::Null);
+    let mut y = BTreeMap::new();
+    y.insert("name".to_string(), Ipld::String("const".to_string()));
+    y.insert("new".to_string(), Ipld::Bool(true));
+    let (a, b) = (Ipld::StringMap(x), Ipld::StringMap(y));
+    let patches = diff(&a, &b);
+    let key = |k: &str| vec![Segment::Key(k.to_string())];
+    assert_eq!(patches, vec![
+      Patch::Replace(key("name"), Ipld::String("const".to_string())),
+      Patch::Remove(key("old")),
+      Patch::Add(key("new"), Ipld::Bool(true)),
+    ]);
+    assert_eq!(apply(a.clone(), &patches), Ok(b));
+    assert!(apply(a, &[Patch::Remove(key("missing"))]).is_err());
+  }
+
+  #[test]
+  fn test_awkward_keys() {
+    let map = |kvs: Vec<(&str, i128)>| {
+      Ipld::StringMap(
+        kvs
+          .into_iter()
+          .map(|(k, v)| (k.to_string(), Ipld::Integer(v)))
+          .collect(),
+      )
+    };
+    let a = map(vec![("a/b", 1), ("", 2), ("a", 3)]);
+    let b = map(vec![("a/b", 4), ("/", 5), ("a", 3)]);
+    let key = |k: &str| vec![Segment::Key(k.to_string())];
+    let patches = diff(&a, &b);
+    assert_eq!(patches, vec![
+      Patch::Remove(key("")),
+      Patch::Replace(key("a/b"), Ipld::Integer(4)),
+      Patch::Add(key("/"), Ipld::Integer(5)),
+    ]);
+    assert_eq!(patches[1].to_string(), "~ /\"a/b\": 4");
+    assert_eq!(apply(a, &patches), Ok(b));
+  }
+}
@@ -0,0 +1,172 @@
+use core::fmt;
+
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+
+use sp_std::vec::Vec;
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// The result of resolving a path inside a single IPLD block
+#[derive(PartialEq, Clone, Debug)]
+pub enum Resolved<'a> {
+  /// The path was fully resolved to a value inside the block
+  Value(&'a Ipld),
+  /// The path crossed a link; the remaining segments must be resolved in the
+  /// block behind the CID
+  Link(Cid, String),
+}
+
+/// Path segments that could not be resolved
+#[derive(PartialEq, Clone, Debug)]
+pub enum PathError {
+  /// A list index that was not a number
+  InvalidIndex(String, String),
+  /// A list index past the end of the list
+  IndexOutOfRange(String, usize, usize),
+  /// A map key that was not present
+  MissingKey(String, String),
+  /// A segment applied to a value that is neither a list nor a map
+  NotIndexable(String, String),
+}
+
+impl fmt::Display for PathError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::InvalidIndex(at, seg) => {
+        write!(f, "Invalid list index \"{}\" at \"{}\"", seg, at)
+      }
+      Self::IndexOutOfRange(at, idx, len) => write!(
+        f,
+        "List index {} out of range at \"{}\" (length {})",
+        idx, at, len
+      ),
+      Self::MissingKey(at, key) => {
+        write!(f, "Missing key \"{}\" at \"{}\"", key, at)
+      }
+      Self::NotIndexable(at, seg) => {
+        write!(f, "Cannot index into a scalar with \"{}\" at \"{}\"", seg, at)
+      }
+    }
+  }
+}
+
+impl From<PathError> for String {
+  fn from(err: PathError) -> String { err.to_string() }
+}
+
+/// Splits a path into its non-empty segments, so that leading, trailing and
+/// doubled slashes are ignored
+pub fn segments(path: &str) -> Vec<&str> {
+  path.split('/').filter(|s| !s.is_empty()).collect()
+}
+
+/// Resolves `path` inside `ipld`, indexing into lists by position and into
+/// string maps by key, and stopping at the first link that still has segments
+/// left to resolve
+pub fn get_path<'a>(
+  ipld: &'a Ipld,
+  path: &str,
+) -> Result<Resolved<'a>, PathError> {
+  let segs = segments(path);
+  let mut node = ipld;
+  for (i, seg) in segs.iter().enumerate() {
+    let at = segs[..i].join("/");
+    node = match node {
+      Ipld::List(xs) => {
+        let idx = seg
+          .parse::<usize>()
+          .map_err(|_| PathError::InvalidIndex(at.clone(), seg.to_string()))?;
+        xs.get(idx)
+          .ok_or_else(|| PathError::IndexOutOfRange(at, idx, xs.len()))?
+      }
+      Ipld::StringMap(map) => map
+        .get(*seg)
+        .ok_or_else(|| PathError::MissingKey(at, seg.to_string()))?,
+      Ipld::Link(cid) => {
+        return Ok(Resolved::Link(*cid, segs[i..].join("/")));
+      }
+      _ => return Err(PathError::NotIndexable(at, seg.to_string())),
+    };
+  }
+  Ok(Resolved::Value(node))
+}
+
+/// Path resolution on IPLD objects
+pub trait IpldPath {
+  /// Resolves a slash-separated path such as `"defs/3/name"`
+  fn get_path(&self, path: &str) -> Result<Resolved, PathError>;
+}
+
+impl IpldPath for Ipld {
+  fn get_path(&self, path: &str) -> Result<Resolved, PathError> {
+    get_path(self, path)
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::tests::arbitrary_cid;
+  use quickcheck::Gen;
+  use sp_std::collections::btree_map::BTreeMap;
+
+  fn example(cid: Cid) -> Ipld {
+    let mut def = BTreeMap::new();
+    def.insert("name".to_string(), Ipld::String("id".to_string()));
+    let mut root = BTreeMap::new();
+    root.insert(
+      "defs".to_string(),
+      Ipld::List(vec![Ipld::Null, Ipld::Link(cid), Ipld::StringMap(def)]),
+    );
+    Ipld::StringMap(root)
+  }
+
+  #[test]
+  fn test_get_path_value() {
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    let ipld = example(cid);
+    assert_eq!(
+      ipld.get_path("defs/2/name"),
+      Ok(Resolved::Value(&Ipld::String("id".to_string())))
+    );
+    assert_eq!(ipld.get_path(""), Ok(Resolved::Value(&ipld)));
+    assert_eq!(ipld.get_path("/defs/0/"), Ok(Resolved::Value(&Ipld::Null)));
+  }
+
+  #[test]
+  fn test_get_path_link() {
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    let ipld = example(cid);
+    assert_eq!(
+      ipld.get_path("defs/1/type/0"),
+      Ok(Resolved::Link(cid, "type/0".to_string()))
+    );
+    assert_eq!(ipld.get_path("defs/1"), Ok(Resolved::Value(&Ipld::Link(cid))));
+  }
+
+  #[test]
+  fn test_get_path_errors() {
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    let ipld = example(cid);
+    assert_eq!(
+      ipld.get_path("defs/x"),
+      Err(PathError::InvalidIndex("defs".to_string(), "x".to_string()))
+    );
+    assert_eq!(
+      ipld.get_path("defs/3"),
+      Err(PathError::IndexOutOfRange("defs".to_string(), 3, 3))
+    );
+    assert_eq!(
+      ipld.get_path("imports"),
+      Err(PathError::MissingKey("".to_string(), "imports".to_string()))
+    );
+    assert_eq!(
+      ipld.get_path("defs/0/name"),
+      Err(PathError::NotIndexable("defs/0".to_string(), "name".to_string()))
+    );
+  }
+}
@@ -0,0 +1,230 @@
+use core::fmt;
+
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::ipld::{
+  block::{
+    Block,
+    BlockError,
+  },
+  convert::kind,
+  path::{
+    get_path,
+    PathError,
+    Resolved,
+  },
+  store::BlockStore,
+};
+
+/// Reasons a proof can't be made or doesn't check out
+#[derive(PartialEq, Clone, Debug)]
+pub enum ProofError {
+  /// A block along the path isn't in the store
+  Missing(Cid),
+  /// The proof ends at a link with part of the path still to resolve
+  Incomplete(Cid, String),
+  /// The path was resolved before the last block of the proof
+  Unused(usize),
+  Block(BlockError),
+  Path(PathError),
+  /// The proof isn't of the form `[root, path, blocks]`
+  Malformed(String),
+}
+
+impl fmt::Display for ProofError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Missing(cid) => write!(f, "Missing block {}", cid),
+      Self::Incomplete(cid, rest) => {
+        write!(f, "Proof ends at {} with \"{}\" unresolved", cid, rest)
+      }
+      Self::Unused(n) => write!(f, "Proof has {} unused blocks", n),
+      Self::Block(e) => write!(f, "{}", e),
+      Self::Path(e) => write!(f, "{}", e),
+      Self::Malformed(e) => write!(f, "Malformed proof: {}", e),
+    }
+  }
+}
+
+impl From<ProofError> for String {
+  fn from(e: ProofError) -> String { e.to_string() }
+}
+
+/// Evidence that a value is reachable by `path` from `root`: the data of
+/// every block the path passes through, in order. Each block's CID is the
+/// link reached in the block before it, so only the data is kept.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Proof {
+  pub root: Cid,
+  pub path: String,
+  pub blocks: Vec<Vec<u8>>,
+}
+
+/// Resolves `path` from `root`, collecting the blocks it passes through
+pub fn prove<S: BlockStore>(
+  root: Cid,
+  path: &str,
+  store: &S,
+) -> Result<Proof, ProofError> {
+  let mut blocks = Vec::new();
+  let mut cid = root;
+  let mut rest = path.to_owned();
+  loop {
+    let block = store.get(&cid).ok_or(ProofError::Missing(cid))?;
+    let ipld = block.decode().map_err(ProofError::Block)?;
+    blocks.push(block.into_data());
+    match get_path(&ipld, &rest).map_err(ProofError::Path)? {
+      Resolved::Value(_) => break,
+      Resolved::Link(next, next_rest) => {
+        cid = next;
+        rest = next_rest;
+      }
+    }
+  }
+  Ok(Proof { root, path: path.to_owned(), blocks })
+}
+
+impl Proof {
+  /// Checks the proof, returning the value it proves is at `path`. Every
+  /// block is hashed and must match the link that leads to it.
+  pub fn verify(&self) -> Result<Ipld, ProofError> {
+    let mut cid = self.root;
+    let mut rest = self.path.clone();
+    for (i, data) in self.blocks.iter().enumerate() {
+      let block = Block::new(cid, data.clone()).map_err(ProofError::Block)?;
+      let ipld = block.decode().map_err(ProofError::Block)?;
+      match get_path(&ipld, &rest).map_err(ProofError::Path)? {
+        Resolved::Value(value) => {
+          let unused = self.blocks.len() - i - 1;
+          if unused != 0 {
+            return Err(ProofError::Unused(unused));
+          }
+          return Ok(value.clone());
+        }
+        Resolved::Link(next, next_rest) => {
+          cid = next;
+          rest = next_rest;
+        }
+      }
+    }
+    Err(ProofError::Incomplete(cid, rest))
+  }
+
+  /// Total size of the proof's block data
+  pub fn size(&self) -> usize { self.blocks.iter().map(|b| b.len()).sum() }
+
+  pub fn to_ipld(&self) -> Ipld {
+    Ipld::List(vec![
+      Ipld::Link(self.root),
+      Ipld::String(self.path.clone()),
+      Ipld::List(self.blocks.iter().cloned().map(Ipld::Bytes).collect()),
+    ])
+  }
+
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, ProofError> {
+    match ipld {
+      Ipld::List(xs) => match xs.as_slice() {
+        [Ipld::Link(root), Ipld::String(path), Ipld::List(bs)] => {
+          let blocks = bs
+            .iter()
+            .map(|b| match b {
+              Ipld::Bytes(b) => Ok(b.clone()),
+              x => Err(ProofError::Malformed(format!(
+                "expected bytes, found {}",
+                kind(x)
+              ))),
+            })
+            .collect::<Result<_, _>>()?;
+          Ok(Proof { root: *root, path: path.clone(), blocks })
+        }
+        _ => Err(ProofError::Malformed("expected [root, path, blocks]".into())),
+      },
+      x => {
+        Err(ProofError::Malformed(format!("expected list, found {}", kind(x))))
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::ipld::{
+    borrowed::FloatPolicy,
+    hash::{
+      Hasher,
+      DAG_CBOR,
+    },
+    store::MemStore,
+  };
+  use sp_std::collections::btree_map::BTreeMap;
+
+  fn put(store: &MemStore, ipld: Ipld) -> Cid {
+    store.put(
+      Block::encode(DAG_CBOR, Hasher::default(), FloatPolicy::Strict, &ipld)
+        .unwrap(),
+    )
+  }
+
+  fn example(store: &MemStore) -> Cid {
+    let def = put(store, Ipld::List(vec![Ipld::String("id".into())]));
+    let mut index = BTreeMap::new();
+    index.insert("id".to_string(), Ipld::Link(def));
+    let index = put(store, Ipld::StringMap(index));
+    put(store, Ipld::List(vec![Ipld::Null, Ipld::Link(index)]))
+  }
+
+  #[test]
+  fn test_prove_verify() {
+    let store = MemStore::new();
+    let root = example(&store);
+    let proof = prove(root, "1/id/0", &store).unwrap();
+    assert_eq!(proof.blocks.len(), 3);
+    assert_eq!(proof.verify(), Ok(Ipld::String("id".into())));
+    assert_eq!(Proof::from_ipld(&proof.to_ipld()), Ok(proof.clone()));
+
+    let mut short = proof.clone();
+    short.blocks.pop();
+    assert!(matches!(short.verify(), Err(ProofError::Incomplete(_, _))));
+    let mut long = proof.clone();
+    long.blocks.push(vec![]);
+    assert_eq!(long.verify(), Err(ProofError::Unused(1)));
+    let mut forged = proof;
+    forged.blocks[2] = Block::encode(
+      DAG_CBOR,
+      Hasher::default(),
+      FloatPolicy::Strict,
+      &Ipld::List(vec![Ipld::String("const".into())]),
+    )
+    .unwrap()
+    .into_data();
+    assert!(matches!(
+      forged.verify(),
+      Err(ProofError::Block(BlockError::Mismatch(_, _)))
+    ));
+  }
+
+  #[test]
+  fn test_prove_missing() {
+    let store = MemStore::new();
+    let root = example(&store);
+    let other = MemStore::new();
+    other.put(store.get(&root).unwrap());
+    assert!(matches!(prove(root, "1/id", &other), Err(ProofError::Missing(_))));
+    assert!(matches!(
+      prove(root, "2", &store),
+      Err(ProofError::Path(PathError::IndexOutOfRange(_, 2, 2)))
+    ));
+  }
+}
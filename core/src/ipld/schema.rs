@@ -0,0 +1,713 @@
+use core::fmt;
+
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  boxed::Box,
+  collections::btree_map::BTreeMap,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// The wire format of Yatima packages and entries
+pub const YATIMA: &str = "
+type Position struct {
+  input Link
+  fromOffset Int
+  fromLine Int
+  fromColumn Int
+  uptoOffset Int
+  uptoLine Int
+  uptoColumn Int
+} representation tuple
+
+type Pos union {
+  | Null null
+  | Position list
+} representation kinded
+
+type Entry struct {
+  pos Pos
+  typeAnon Link
+  termAnon Link
+  typeMeta Any
+  termMeta Any
+} representation tuple
+
+type Import struct {
+  cid Link
+  name String
+  alias String
+  with [String]
+} representation tuple
+
+type IndexEntry struct {
+  name String
+  cid Link
+} representation tuple
+
+type Index [IndexEntry]
+
+type Package struct {
+  pos Pos
+  name String
+  imports [Import]
+  index Index
+} representation tuple
+";
+
+/// The data model kinds, as named in union representations
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Kind {
+  Null,
+  Bool,
+  Int,
+  Float,
+  String,
+  Bytes,
+  List,
+  Map,
+  Link,
+}
+
+impl Kind {
+  pub fn of(ipld: &Ipld) -> Self {
+    match ipld {
+      Ipld::Null => Self::Null,
+      Ipld::Bool(_) => Self::Bool,
+      Ipld::Integer(_) => Self::Int,
+      Ipld::Float(_) => Self::Float,
+      Ipld::String(_) => Self::String,
+      Ipld::Bytes(_) => Self::Bytes,
+      Ipld::List(_) => Self::List,
+      Ipld::StringMap(_) => Self::Map,
+      Ipld::Link(_) => Self::Link,
+    }
+  }
+
+  fn from_name(name: &str) -> Option<Self> {
+    match name {
+      "null" => Some(Self::Null),
+      "bool" => Some(Self::Bool),
+      "int" => Some(Self::Int),
+      "float" => Some(Self::Float),
+      "string" => Some(Self::String),
+      "bytes" => Some(Self::Bytes),
+      "list" => Some(Self::List),
+      "map" => Some(Self::Map),
+      "link" => Some(Self::Link),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for Kind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let name = match self {
+      Self::Null => "null",
+      Self::Bool => "bool",
+      Self::Int => "int",
+      Self::Float => "float",
+      Self::String => "string",
+      Self::Bytes => "bytes",
+      Self::List => "list",
+      Self::Map => "map",
+      Self::Link => "link",
+    };
+    write!(f, "{}", name)
+  }
+}
+
+/// A struct field. Optional fields may be left out of map representations,
+/// nullable ones may be `null`.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Field {
+  pub name: String,
+  pub optional: bool,
+  pub nullable: bool,
+  pub typ: Type,
+}
+
+#[derive(PartialEq, Clone, Debug)]
+pub enum Type {
+  Any,
+  /// A kind with no further structure, like `Int` or `Link`
+  Kind(Kind),
+  /// A reference to a type declared in the schema
+  Named(String),
+  List {
+    nullable: bool,
+    of: Box<Type>,
+  },
+  Map {
+    nullable: bool,
+    of: Box<Type>,
+  },
+  /// Represented as a map by default, or as a list of the fields in order
+  /// with `representation tuple`
+  Struct {
+    fields: Vec<Field>,
+    tuple: bool,
+  },
+  /// A kinded union, picking the member by the kind of the data
+  Union(Vec<(Kind, Type)>),
+  /// A string that must be one of the members
+  Enum(Vec<String>),
+}
+
+/// Reasons a schema can fail to parse or data can fail to validate
+#[derive(PartialEq, Clone, Debug)]
+pub enum SchemaError {
+  /// A syntax error on the given line of the schema text
+  Parse(usize, String),
+  UnknownType(String),
+  /// The data at `path` doesn't match the schema
+  Invalid {
+    path: String,
+    reason: String,
+  },
+}
+
+impl fmt::Display for SchemaError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Parse(line, msg) => write!(f, "Schema line {}: {}", line, msg),
+      Self::UnknownType(name) => write!(f, "Unknown schema type {}", name),
+      Self::Invalid { path, reason } => {
+        write!(f, "Invalid data at /{}: {}", path, reason)
+      }
+    }
+  }
+}
+
+impl From<SchemaError> for String {
+  fn from(e: SchemaError) -> String { e.to_string() }
+}
+
+/// A set of named type declarations
+#[derive(PartialEq, Clone, Debug, Default)]
+pub struct Schema {
+  pub types: BTreeMap<String, Type>,
+}
+
+/// Parses schema text in the IPLD Schema DSL. Supported are struct (map and
+/// tuple representations), kinded union and enum declarations, list and map
+/// types, typed links `&T` and `#` comments.
+pub fn parse(text: &str) -> Result<Schema, SchemaError> {
+  let mut parser = Parser { tokens: tokenize(text)?, pos: 0 };
+  let mut schema = Schema::default();
+  while !parser.done() {
+    parser.expect("type")?;
+    let name = parser.ident()?;
+    let typ = parser.typedef()?;
+    if schema.types.insert(name.clone(), typ).is_some() {
+      return Err(parser.error(format!("Duplicate type {}", name)));
+    }
+  }
+  for typ in schema.types.values() {
+    schema.check_names(typ)?;
+  }
+  Ok(schema)
+}
+
+impl Schema {
+  /// Checks `ipld` against the type named `root`
+  pub fn validate(&self, root: &str, ipld: &Ipld) -> Result<(), SchemaError> {
+    self.check(&Type::Named(root.to_owned()), ipld, &mut Vec::new())
+  }
+
+  fn check_names(&self, typ: &Type) -> Result<(), SchemaError> {
+    match typ {
+      Type::Named(name) if !self.types.contains_key(name) => {
+        Err(SchemaError::UnknownType(name.clone()))
+      }
+      Type::List { of, .. } | Type::Map { of, .. } => self.check_names(of),
+      Type::Struct { fields, .. } => {
+        fields.iter().try_for_each(|f| self.check_names(&f.typ))
+      }
+      Type::Union(members) => {
+        members.iter().try_for_each(|(_, t)| self.check_names(t))
+      }
+      _ => Ok(()),
+    }
+  }
+
+  /// Follows aliases to a type with structure. An alias can't refer to
+  /// itself without going through a structured type, since that would never
+  /// terminate.
+  fn resolve<'a>(&'a self, mut typ: &'a Type) -> Result<&'a Type, SchemaError> {
+    let mut steps = 0;
+    while let Type::Named(name) = typ {
+      if steps > self.types.len() {
+        return Err(SchemaError::UnknownType(name.clone()));
+      }
+      typ = self
+        .types
+        .get(name)
+        .ok_or_else(|| SchemaError::UnknownType(name.clone()))?;
+      steps += 1;
+    }
+    Ok(typ)
+  }
+
+  fn check(
+    &self,
+    typ: &Type,
+    ipld: &Ipld,
+    path: &mut Vec<String>,
+  ) -> Result<(), SchemaError> {
+    let typ = self.resolve(typ)?;
+    let invalid = |path: &Vec<String>, reason: String| SchemaError::Invalid {
+      path: path.join("/"),
+      reason,
+    };
+    let expect = |path: &Vec<String>, kind: Kind| {
+      if Kind::of(ipld) == kind {
+        Ok(())
+      }
+      else {
+        Err(invalid(
+          path,
+          format!("Expected {}, found {}", kind, Kind::of(ipld)),
+        ))
+      }
+    };
+    match typ {
+      Type::Any => Ok(()),
+      Type::Kind(kind) => expect(path, *kind),
+      Type::Named(_) => unreachable!(),
+      Type::List { nullable, of } => {
+        expect(path, Kind::List)?;
+        if let Ipld::List(xs) = ipld {
+          for (i, x) in xs.iter().enumerate() {
+            path.push(i.to_string());
+            self.check_nullable(of, *nullable, x, path)?;
+            path.pop();
+          }
+        }
+        Ok(())
+      }
+      Type::Map { nullable, of } => {
+        expect(path, Kind::Map)?;
+        if let Ipld::StringMap(m) = ipld {
+          for (k, x) in m {
+            path.push(k.clone());
+            self.check_nullable(of, *nullable, x, path)?;
+            path.pop();
+          }
+        }
+        Ok(())
+      }
+      Type::Struct { fields, tuple: true } => {
+        expect(path, Kind::List)?;
+        if let Ipld::List(xs) = ipld {
+          if xs.len() != fields.len() {
+            return Err(invalid(
+              path,
+              format!("Expected {} fields, found {}", fields.len(), xs.len()),
+            ));
+          }
+          for (field, x) in fields.iter().zip(xs) {
+            path.push(field.name.clone());
+            self.check_nullable(&field.typ, field.nullable, x, path)?;
+            path.pop();
+          }
+        }
+        Ok(())
+      }
+      Type::Struct { fields, tuple: false } => {
+        expect(path, Kind::Map)?;
+        if let Ipld::StringMap(m) = ipld {
+          for field in fields {
+            match m.get(&field.name) {
+              Some(x) => {
+                path.push(field.name.clone());
+                self.check_nullable(&field.typ, field.nullable, x, path)?;
+                path.pop();
+              }
+              None if field.optional => (),
+              None => {
+                return Err(invalid(
+                  path,
+                  format!("Missing field {}", field.name),
+                ));
+              }
+            }
+          }
+          if let Some(k) =
+            m.keys().find(|k| !fields.iter().any(|f| &f.name == *k))
+          {
+            return Err(invalid(path, format!("Unknown field {}", k)));
+          }
+        }
+        Ok(())
+      }
+      Type::Union(members) => {
+        let kind = Kind::of(ipld);
+        match members.iter().find(|(k, _)| *k == kind) {
+          Some((_, member)) => self.check(member, ipld, path),
+          None => Err(invalid(path, format!("No union member for {}", kind))),
+        }
+      }
+      Type::Enum(members) => {
+        expect(path, Kind::String)?;
+        match ipld {
+          Ipld::String(s) if members.contains(s) => Ok(()),
+          _ => Err(invalid(path, "Not an enum member".to_owned())),
+        }
+      }
+    }
+  }
+
+  fn check_nullable(
+    &self,
+    typ: &Type,
+    nullable: bool,
+    ipld: &Ipld,
+    path: &mut Vec<String>,
+  ) -> Result<(), SchemaError> {
+    if nullable && *ipld == Ipld::Null {
+      Ok(())
+    }
+    else {
+      self.check(typ, ipld, path)
+    }
+  }
+}
+
+/// Parses `text` and checks `ipld` against its type named `root`
+pub fn validate(
+  text: &str,
+  root: &str,
+  ipld: &Ipld,
+) -> Result<(), SchemaError> {
+  parse(text)?.validate(root, ipld)
+}
+
+#[derive(PartialEq, Clone, Debug)]
+enum Token {
+  Ident(String),
+  Str(String),
+  Punct(char),
+}
+
+fn tokenize(text: &str) -> Result<Vec<(usize, Token)>, SchemaError> {
+  let mut tokens = Vec::new();
+  let mut line = 1;
+  let mut chars = text.chars().peekable();
+  while let Some(c) = chars.next() {
+    match c {
+      '\n' => line += 1,
+      c if c.is_whitespace() => (),
+      '#' => {
+        while chars.peek().map_or(false, |c| *c != '\n') {
+          chars.next();
+        }
+      }
+      '[' | ']' | '{' | '}' | ':' | '|' | '&' => {
+        tokens.push((line, Token::Punct(c)))
+      }
+      '"' => {
+        let mut s = String::new();
+        loop {
+          match chars.next() {
+            Some('"') => break,
+            Some('\n') | None => {
+              return Err(SchemaError::Parse(
+                line,
+                "Unterminated string".to_owned(),
+              ));
+            }
+            Some(c) => s.push(c),
+          }
+        }
+        tokens.push((line, Token::Str(s)))
+      }
+      c if c.is_alphanumeric() || c == '_' => {
+        let mut s = String::new();
+        s.push(c);
+        while let Some(c) =
+          chars.peek().filter(|c| c.is_alphanumeric() || **c == '_')
+        {
+          s.push(*c);
+          chars.next();
+        }
+        tokens.push((line, Token::Ident(s)))
+      }
+      c => {
+        return Err(SchemaError::Parse(line, format!("Unexpected `{}`", c)));
+      }
+    }
+  }
+  Ok(tokens)
+}
+
+struct Parser {
+  tokens: Vec<(usize, Token)>,
+  pos: usize,
+}
+
+impl Parser {
+  fn done(&self) -> bool { self.pos >= self.tokens.len() }
+
+  fn peek(&self) -> Option<&Token> { self.tokens.get(self.pos).map(|t| &t.1) }
+
+  fn error(&self, msg: String) -> SchemaError {
+    let line = self
+      .tokens
+      .get(self.pos)
+      .or_else(|| self.tokens.last())
+      .map_or(1, |t| t.0);
+    SchemaError::Parse(line, msg)
+  }
+
+  fn next(&mut self) -> Result<Token, SchemaError> {
+    let token = self
+      .peek()
+      .cloned()
+      .ok_or_else(|| self.error("Unexpected end of schema".to_owned()))?;
+    self.pos += 1;
+    Ok(token)
+  }
+
+  fn ident(&mut self) -> Result<String, SchemaError> {
+    match self.next()? {
+      Token::Ident(s) => Ok(s),
+      t => {
+        self.pos -= 1;
+        Err(self.error(format!("Expected a name, found {:?}", t)))
+      }
+    }
+  }
+
+  fn punct(&mut self, c: char) -> Result<(), SchemaError> {
+    match self.next()? {
+      Token::Punct(d) if c == d => Ok(()),
+      t => {
+        self.pos -= 1;
+        Err(self.error(format!("Expected `{}`, found {:?}", c, t)))
+      }
+    }
+  }
+
+  fn expect(&mut self, word: &str) -> Result<(), SchemaError> {
+    let found = self.ident()?;
+    if found == word {
+      Ok(())
+    }
+    else {
+      self.pos -= 1;
+      Err(self.error(format!("Expected `{}`, found `{}`", word, found)))
+    }
+  }
+
+  /// Consumes `word` if it's next
+  fn keyword(&mut self, word: &str) -> bool {
+    if self.peek() == Some(&Token::Ident(word.to_owned())) {
+      self.pos += 1;
+      true
+    }
+    else {
+      false
+    }
+  }
+
+  fn is_punct(&self, c: char) -> bool { self.peek() == Some(&Token::Punct(c)) }
+
+  fn typedef(&mut self) -> Result<Type, SchemaError> {
+    if self.keyword("struct") {
+      self.punct('{')?;
+      let mut fields = Vec::new();
+      while !self.is_punct('}') {
+        let name = self.ident()?;
+        let optional = self.keyword("optional");
+        let nullable = self.keyword("nullable");
+        let typ = self.type_expr()?;
+        fields.push(Field { name, optional, nullable, typ });
+      }
+      self.punct('}')?;
+      let tuple = if self.keyword("representation") {
+        match self.ident()?.as_str() {
+          "map" => false,
+          "tuple" => true,
+          r => {
+            self.pos -= 1;
+            return Err(
+              self.error(format!("Unsupported representation {}", r)),
+            );
+          }
+        }
+      }
+      else {
+        false
+      };
+      if tuple && fields.iter().any(|f| f.optional) {
+        return Err(
+          self.error("Tuple structs can't have optional fields".into()),
+        );
+      }
+      Ok(Type::Struct { fields, tuple })
+    }
+    else if self.keyword("union") {
+      self.punct('{')?;
+      let mut members = Vec::new();
+      while self.is_punct('|') {
+        self.punct('|')?;
+        let typ = self.type_expr()?;
+        let name = self.ident()?;
+        match Kind::from_name(&name) {
+          Some(kind) => members.push((kind, typ)),
+          None => {
+            self.pos -= 1;
+            return Err(self.error(format!("Unknown kind {}", name)));
+          }
+        }
+      }
+      self.punct('}')?;
+      self.expect("representation")?;
+      self.expect("kinded")?;
+      Ok(Type::Union(members))
+    }
+    else if self.keyword("enum") {
+      self.punct('{')?;
+      let mut members = Vec::new();
+      while self.is_punct('|') {
+        self.punct('|')?;
+        members.push(self.ident()?);
+      }
+      self.punct('}')?;
+      Ok(Type::Enum(members))
+    }
+    else {
+      self.type_expr()
+    }
+  }
+
+  fn type_expr(&mut self) -> Result<Type, SchemaError> {
+    if self.is_punct('[') {
+      self.punct('[')?;
+      let nullable = self.keyword("nullable");
+      let of = Box::new(self.type_expr()?);
+      self.punct(']')?;
+      Ok(Type::List { nullable, of })
+    }
+    else if self.is_punct('{') {
+      self.punct('{')?;
+      self.expect("String")?;
+      self.punct(':')?;
+      let nullable = self.keyword("nullable");
+      let of = Box::new(self.type_expr()?);
+      self.punct('}')?;
+      Ok(Type::Map { nullable, of })
+    }
+    else if self.is_punct('&') {
+      // The target type of a link can't be checked without loading it
+      self.punct('&')?;
+      self.ident()?;
+      Ok(Type::Kind(Kind::Link))
+    }
+    else {
+      let name = self.ident()?;
+      Ok(match name.as_str() {
+        "Any" => Type::Any,
+        "Null" => Type::Kind(Kind::Null),
+        "Bool" => Type::Kind(Kind::Bool),
+        "Int" => Type::Kind(Kind::Int),
+        "Float" => Type::Kind(Kind::Float),
+        "String" => Type::Kind(Kind::String),
+        "Bytes" => Type::Kind(Kind::Bytes),
+        "Link" => Type::Kind(Kind::Link),
+        _ => Type::Named(name),
+      })
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    name::Name,
+    package::{
+      Import,
+      Index,
+      Package,
+    },
+    position::Pos,
+    tests::arbitrary_cid,
+  };
+  use quickcheck::Gen;
+
+  #[test]
+  fn test_yatima_schema() {
+    let schema = parse(YATIMA).unwrap();
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    let package = Package {
+      pos: Pos::None,
+      name: Name::from("test"),
+      imports: vec![Import {
+        cid,
+        name: Name::from("base"),
+        alias: Name::from(""),
+        with: vec![Name::from("id")],
+      }],
+      index: Index(vec![(Name::from("id"), cid)]),
+    };
+    assert_eq!(schema.validate("Package", &package.to_ipld()), Ok(()));
+    let mut bad = package.to_ipld();
+    if let Ipld::List(xs) = &mut bad {
+      xs[1] = Ipld::Integer(1);
+    }
+    assert_eq!(
+      schema.validate("Package", &bad),
+      Err(SchemaError::Invalid {
+        path: "name".to_owned(),
+        reason: "Expected string, found int".to_owned(),
+      })
+    );
+  }
+
+  #[test]
+  fn test_parse_errors() {
+    assert_eq!(
+      parse("type A struct { x B }"),
+      Err(SchemaError::UnknownType("B".to_owned()))
+    );
+    assert!(matches!(parse("type A\n[Int"), Err(SchemaError::Parse(2, _))));
+    assert!(parse("type A A").is_ok());
+    assert!(matches!(
+      parse("type A A").unwrap().validate("A", &Ipld::Null),
+      Err(SchemaError::UnknownType(_))
+    ));
+  }
+
+  #[test]
+  fn test_map_struct() {
+    let schema = parse(
+      "
+      # Metadata attached to a package
+      type Meta struct {
+        author String
+        license optional License
+        tags {String:nullable Int}
+      }
+      type License enum { | MIT | Apache }
+      ",
+    )
+    .unwrap();
+    let mut m = BTreeMap::new();
+    m.insert("author".to_owned(), Ipld::String("me".into()));
+    m.insert("tags".to_owned(), Ipld::StringMap(BTreeMap::new()));
+    assert_eq!(schema.validate("Meta", &Ipld::StringMap(m.clone())), Ok(()));
+    m.insert("license".to_owned(), Ipld::String("GPL".into()));
+    assert!(schema.validate("Meta", &Ipld::StringMap(m.clone())).is_err());
+    m.insert("license".to_owned(), Ipld::String("MIT".into()));
+    m.insert("extra".to_owned(), Ipld::Null);
+    assert!(schema.validate("Meta", &Ipld::StringMap(m)).is_err());
+  }
+}
@@ -0,0 +1,192 @@
+use core::convert::TryFrom;
+
+use ed25519_dalek::{
+  Keypair,
+  PublicKey,
+  Signature,
+  Signer,
+  Verifier,
+};
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  collections::btree_map::BTreeMap,
+  vec::Vec,
+};
+
+use alloc::string::String;
+
+use crate::ipld::{
+  block::Block,
+  borrowed::FloatPolicy,
+  convert::kind,
+  encrypted::CryptoError,
+  hash::{
+    Hasher,
+    DAG_CBOR,
+  },
+  writer,
+};
+
+/// The signature algorithm, recorded in the envelope
+pub const ALG: &str = "ed25519";
+
+/// Separates signatures over blocks from anything else made with the same key
+const CONTEXT: &str = "yatima/signed-block";
+
+/// A signature by `public_key` over the CID of a payload block. Since the CID
+/// commits to the payload's content, signing it signs the whole DAG under it.
+#[derive(PartialEq, Clone, Debug)]
+pub struct SignedBlock {
+  pub payload: Cid,
+  pub public_key: Vec<u8>,
+  pub signature: Vec<u8>,
+}
+
+/// The bytes that get signed: the encoding of `[context, payload]`
+fn message(payload: Cid) -> Vec<u8> {
+  writer::encode(&Ipld::List(vec![
+    Ipld::String(CONTEXT.to_owned()),
+    Ipld::Link(payload),
+  ]))
+  .expect("a string and a link always encode")
+}
+
+impl SignedBlock {
+  pub fn sign(payload: Cid, keypair: &Keypair) -> Self {
+    let signature = keypair.sign(&message(payload));
+    SignedBlock {
+      payload,
+      public_key: keypair.public.to_bytes().to_vec(),
+      signature: signature.to_bytes().to_vec(),
+    }
+  }
+
+  /// Checks that the signature was made over the payload with the secret key
+  /// of `public_key`. Whether that key is trusted is up to the caller.
+  pub fn verify(&self) -> Result<(), CryptoError> {
+    let public = PublicKey::from_bytes(&self.public_key)
+      .map_err(|_| CryptoError::Malformed("invalid public key".into()))?;
+    let signature = Signature::try_from(self.signature.as_slice())
+      .map_err(|_| CryptoError::Malformed("invalid signature".into()))?;
+    public
+      .verify(&message(self.payload), &signature)
+      .map_err(|_| CryptoError::BadSignature)
+  }
+
+  pub fn to_ipld(&self) -> Ipld {
+    let mut map = BTreeMap::new();
+    map.insert("alg".to_owned(), Ipld::String(ALG.to_owned()));
+    map.insert("payload".to_owned(), Ipld::Link(self.payload));
+    map.insert("publicKey".to_owned(), Ipld::Bytes(self.public_key.clone()));
+    map.insert("signature".to_owned(), Ipld::Bytes(self.signature.clone()));
+    Ipld::StringMap(map)
+  }
+
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, CryptoError> {
+    let map = match ipld {
+      Ipld::StringMap(map) => map,
+      x => {
+        return Err(CryptoError::Malformed(format!(
+          "expected map, found {}",
+          kind(x)
+        )));
+      }
+    };
+    let field = |key: &str| {
+      map
+        .get(key)
+        .ok_or_else(|| CryptoError::Malformed(format!("missing {}", key)))
+    };
+    let bytes = |key: &str| match field(key)? {
+      Ipld::Bytes(b) => Ok(b.clone()),
+      x => Err(CryptoError::Malformed(format!(
+        "expected bytes at {}, found {}",
+        key,
+        kind(x)
+      ))),
+    };
+    match field("alg")? {
+      Ipld::String(a) if a == ALG => (),
+      Ipld::String(a) => return Err(CryptoError::Unsupported(a.clone())),
+      x => {
+        return Err(CryptoError::Malformed(format!(
+          "expected string at alg, found {}",
+          kind(x)
+        )));
+      }
+    }
+    let payload = match field("payload")? {
+      Ipld::Link(cid) => *cid,
+      x => {
+        return Err(CryptoError::Malformed(format!(
+          "expected link at payload, found {}",
+          kind(x)
+        )));
+      }
+    };
+    Ok(SignedBlock {
+      payload,
+      public_key: bytes("publicKey")?,
+      signature: bytes("signature")?,
+    })
+  }
+
+  /// The envelope as a DAG-CBOR block, ready to be stored
+  pub fn to_block(&self) -> Result<Block, CryptoError> {
+    Block::encode(
+      DAG_CBOR,
+      Hasher::default(),
+      FloatPolicy::Strict,
+      &self.to_ipld(),
+    )
+    .map_err(CryptoError::Block)
+  }
+
+  pub fn from_block(block: &Block) -> Result<Self, CryptoError> {
+    Self::from_ipld(&block.decode().map_err(CryptoError::Block)?)
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use ed25519_dalek::SecretKey;
+
+  fn keypair(seed: u8) -> Keypair {
+    let secret = SecretKey::from_bytes(&[seed; 32]).unwrap();
+    let public = PublicKey::from(&secret);
+    Keypair { secret, public }
+  }
+
+  #[test]
+  fn test_sign_verify() {
+    let payload = Block::encode(
+      DAG_CBOR,
+      Hasher::default(),
+      FloatPolicy::Strict,
+      &Ipld::String("pkg".into()),
+    )
+    .unwrap()
+    .cid();
+    let signed = SignedBlock::sign(payload, &keypair(1));
+    let signed = SignedBlock::from_block(&signed.to_block().unwrap()).unwrap();
+    assert_eq!(signed.verify(), Ok(()));
+
+    let mut other_key = signed.clone();
+    other_key.public_key = keypair(2).public.to_bytes().to_vec();
+    assert_eq!(other_key.verify(), Err(CryptoError::BadSignature));
+    let mut other_payload = signed;
+    other_payload.payload = Block::encode(
+      DAG_CBOR,
+      Hasher::default(),
+      FloatPolicy::Strict,
+      &Ipld::Null,
+    )
+    .unwrap()
+    .cid();
+    assert_eq!(other_payload.verify(), Err(CryptoError::BadSignature));
+  }
+}
@@ -0,0 +1,167 @@
+use core::cell::RefCell;
+
+use sp_cid::Cid;
+
+use sp_std::{
+  collections::{
+    btree_map::BTreeMap,
+    btree_set::BTreeSet,
+  },
+  vec::Vec,
+};
+
+use crate::ipld::{
+  block::Block,
+  borrowed::Reader,
+  error::DecodeError,
+};
+
+/// Storage of verified blocks addressed by their CIDs
+pub trait BlockStore {
+  /// Gets the block addressed by `cid`
+  fn get(&self, cid: &Cid) -> Option<Block>;
+
+  /// Stores a block, returning its CID
+  fn put(&self, block: Block) -> Cid;
+
+  /// Whether the store holds the block addressed by `cid`
+  fn has(&self, cid: &Cid) -> bool { self.get(cid).is_some() }
+
+  /// The CIDs linked from the block addressed by `cid`, or `None` if the
+  /// store doesn't hold it
+  fn references(&self, cid: &Cid) -> Option<Result<Vec<Cid>, DecodeError>> {
+    self.get(cid).map(|block| references(block.data()))
+  }
+}
+
+/// Collects the links of an encoded block, in the order they appear. Values
+/// with tags other than the CID tag are searched for links too.
+pub fn references(data: &[u8]) -> Result<Vec<Cid>, DecodeError> {
+  let mut reader = Reader::new(data);
+  let mut links = Vec::new();
+  reader.read_links(&mut links)?;
+  if reader.remaining() != 0 {
+    return Err(DecodeError::TrailingBytes { offset: reader.offset() });
+  }
+  Ok(links)
+}
+
+/// The CIDs of every block reachable from `root`, `root` included, which is
+/// what pinning, garbage collection and export all need. Links to blocks the
+/// store doesn't hold are included but can't be followed. A reachable block
+/// that doesn't decode is an error, since its links can't be known. Each block
+/// is read once, however many times it's linked.
+pub fn references_closure<S: BlockStore + ?Sized>(
+  root: Cid,
+  store: &S,
+) -> Result<BTreeSet<Cid>, DecodeError> {
+  let mut visited = BTreeSet::new();
+  let mut stack = vec![root];
+  while let Some(cid) = stack.pop() {
+    if !visited.insert(cid) {
+      continue;
+    }
+    if let Some(links) = store.references(&cid) {
+      stack.extend(links?.into_iter().filter(|l| !visited.contains(l)));
+    }
+  }
+  Ok(visited)
+}
+
+/// A block store held in memory
+#[derive(Clone, Debug, Default)]
+pub struct MemStore {
+  blocks: RefCell<BTreeMap<Cid, Block>>,
+}
+
+impl MemStore {
+  pub fn new() -> Self { Self::default() }
+
+  pub fn len(&self) -> usize { self.blocks.borrow().len() }
+
+  pub fn is_empty(&self) -> bool { self.blocks.borrow().is_empty() }
+}
+
+impl BlockStore for MemStore {
+  fn get(&self, cid: &Cid) -> Option<Block> {
+    self.blocks.borrow().get(cid).cloned()
+  }
+
+  fn put(&self, block: Block) -> Cid {
+    let cid = block.cid();
+    self.blocks.borrow_mut().insert(cid, block);
+    cid
+  }
+
+  fn has(&self, cid: &Cid) -> bool { self.blocks.borrow().contains_key(cid) }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::ipld::{
+    borrowed::FloatPolicy,
+    hash::{
+      digest,
+      Hasher,
+      DAG_CBOR,
+    },
+  };
+  use sp_ipld::Ipld;
+
+  fn block(ipld: &Ipld) -> Block {
+    Block::encode(DAG_CBOR, Hasher::default(), FloatPolicy::Strict, ipld)
+      .unwrap()
+  }
+
+  #[test]
+  fn test_mem_store() {
+    let store = MemStore::new();
+    let leaf = block(&Ipld::String("leaf".into()));
+    let leaf_cid = store.put(leaf.clone());
+    let root = block(&Ipld::List(vec![
+      Ipld::Link(leaf_cid),
+      Ipld::List(vec![Ipld::Link(leaf_cid)]),
+    ]));
+    let root_cid = store.put(root);
+    assert_eq!(store.len(), 2);
+    assert!(store.has(&leaf_cid));
+    assert_eq!(store.get(&leaf_cid), Some(leaf));
+    assert_eq!(store.references(&root_cid), Some(Ok(vec![leaf_cid, leaf_cid])));
+    assert_eq!(store.references(&leaf_cid), Some(Ok(vec![])));
+    let missing = block(&Ipld::Null).cid();
+    assert!(!store.has(&missing));
+    assert_eq!(store.references(&missing), None);
+  }
+
+  #[test]
+  fn test_references_closure() {
+    let store = MemStore::new();
+    let missing = block(&Ipld::Null).cid();
+    let leaf = store.put(block(&Ipld::List(vec![Ipld::Link(missing)])));
+    let a = store.put(block(&Ipld::List(vec![Ipld::Link(leaf)])));
+    let root = store.put(block(&Ipld::List(vec![
+      Ipld::Link(a),
+      Ipld::Link(leaf),
+      Ipld::Link(a),
+    ])));
+    let unrelated = store.put(block(&Ipld::Integer(1)));
+    let closure = references_closure(root, &store).unwrap();
+    assert_eq!(closure, [root, a, leaf, missing].iter().copied().collect());
+    assert!(!closure.contains(&unrelated));
+    assert_eq!(references_closure(missing, &store).unwrap().len(), 1);
+  }
+
+  #[test]
+  fn test_references_closure_undecodable() {
+    let store = MemStore::new();
+    let data = vec![0x82, 0x01];
+    let cid = digest(DAG_CBOR, Hasher::default(), &data);
+    let broken = store.put(Block::new(cid, data).unwrap());
+    let root = store.put(block(&Ipld::List(vec![Ipld::Link(broken)])));
+    assert_eq!(
+      references_closure(root, &store),
+      Err(DecodeError::LengthOutOfRange { len: 2, offset: 1 })
+    );
+  }
+}
@@ -0,0 +1,222 @@
+use core::fmt;
+
+use sp_cid::Cid;
+
+use sp_std::{
+  collections::{
+    btree_map::BTreeMap,
+    btree_set::BTreeSet,
+  },
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::ipld::{
+  block::{
+    Block,
+    BlockError,
+  },
+  error::DecodeError,
+  store::{
+    BlockStore,
+    MemStore,
+  },
+};
+
+/// A remote peer or gateway that blocks can be requested from in batches
+pub trait BlockSource {
+  /// Fetches whichever of `cids` the source has, as raw block data. The data
+  /// is untrusted and gets verified against the CIDs by the caller.
+  fn fetch(&self, cids: &[Cid]) -> Vec<(Cid, Vec<u8>)>;
+}
+
+impl BlockSource for MemStore {
+  fn fetch(&self, cids: &[Cid]) -> Vec<(Cid, Vec<u8>)> {
+    cids
+      .iter()
+      .filter_map(|cid| self.get(cid).map(|b| (*cid, b.into_data())))
+      .collect()
+  }
+}
+
+/// The part of the DAG under a root that should be synchronized
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Selector {
+  /// Every block reachable from the root
+  All,
+  /// The blocks at most this many links away from the root
+  Depth(usize),
+}
+
+/// Reasons a sync can fail. Blocks fetched before the failure are kept.
+#[derive(PartialEq, Clone, Debug)]
+pub enum SyncError {
+  /// Blocks neither the local store nor the source has
+  Unavailable(Vec<Cid>),
+  /// A fetched block that doesn't match its CID
+  Block(BlockError),
+  /// A block whose links can't be read
+  Decode(Cid, DecodeError),
+}
+
+impl fmt::Display for SyncError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Unavailable(cids) => {
+        write!(f, "{} blocks are unavailable, including", cids.len())?;
+        cids.iter().take(3).try_for_each(|cid| write!(f, " {}", cid))
+      }
+      Self::Block(e) => write!(f, "{}", e),
+      Self::Decode(cid, e) => write!(f, "Cannot read links of {}: {}", cid, e),
+    }
+  }
+}
+
+impl From<SyncError> for String {
+  fn from(e: SyncError) -> String { e.to_string() }
+}
+
+/// What a sync did
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct SyncReport {
+  /// Blocks that were already in the local store
+  pub present: usize,
+  /// Blocks fetched from the source
+  pub fetched: usize,
+  /// Bytes fetched from the source
+  pub bytes: usize,
+}
+
+/// Copies the blocks `selector` picks under `root` into `local`, fetching
+/// only the ones it doesn't have yet. The DAG is explored a level at a time,
+/// each level's missing blocks being requested from `remote` in one batch.
+pub fn sync(
+  root: Cid,
+  selector: Selector,
+  local: &impl BlockStore,
+  remote: &impl BlockSource,
+) -> Result<SyncReport, SyncError> {
+  let mut report = SyncReport::default();
+  let mut visited = BTreeSet::new();
+  let mut level = vec![root];
+  let mut depth = 0;
+  while !level.is_empty() {
+    level.retain(|cid| visited.insert(*cid));
+    let missing: BTreeSet<Cid> =
+      level.iter().filter(|cid| !local.has(cid)).copied().collect();
+    report.present += level.len() - missing.len();
+    if !missing.is_empty() {
+      let wanted: Vec<Cid> = missing.iter().copied().collect();
+      let mut arrived = BTreeMap::new();
+      for (cid, data) in remote.fetch(&wanted) {
+        if missing.contains(&cid) {
+          let block = Block::new(cid, data).map_err(SyncError::Block)?;
+          arrived.insert(cid, block);
+        }
+      }
+      let unavailable: Vec<Cid> =
+        wanted.into_iter().filter(|cid| !arrived.contains_key(cid)).collect();
+      if !unavailable.is_empty() {
+        return Err(SyncError::Unavailable(unavailable));
+      }
+      for (_, block) in arrived {
+        report.fetched += 1;
+        report.bytes += block.data().len();
+        local.put(block);
+      }
+    }
+    if selector == Selector::Depth(depth) {
+      break;
+    }
+    let mut next = Vec::new();
+    for cid in &level {
+      match local.references(cid) {
+        Some(Ok(links)) => next.extend(links),
+        Some(Err(e)) => return Err(SyncError::Decode(*cid, e)),
+        None => return Err(SyncError::Unavailable(vec![*cid])),
+      }
+    }
+    level = next;
+    depth += 1;
+  }
+  Ok(report)
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::ipld::{
+    borrowed::FloatPolicy,
+    hash::{
+      Hasher,
+      DAG_CBOR,
+    },
+  };
+  use sp_ipld::Ipld;
+
+  fn block(ipld: Ipld) -> Block {
+    Block::encode(DAG_CBOR, Hasher::default(), FloatPolicy::Strict, &ipld)
+      .unwrap()
+  }
+
+  /// A chain root -> mid -> leaf, plus a second leaf under the root
+  fn dag() -> (MemStore, Vec<Block>) {
+    let leaf = block(Ipld::String("leaf".into()));
+    let other = block(Ipld::String("other".into()));
+    let mid = block(Ipld::List(vec![Ipld::Link(leaf.cid())]));
+    let root =
+      block(Ipld::List(vec![Ipld::Link(mid.cid()), Ipld::Link(other.cid())]));
+    let store = MemStore::new();
+    let blocks = vec![root, mid, leaf, other];
+    blocks.iter().for_each(|b| {
+      store.put(b.clone());
+    });
+    (store, blocks)
+  }
+
+  #[test]
+  fn test_sync_fetches_missing() {
+    let (remote, blocks) = dag();
+    let root = blocks[0].cid();
+    let local = MemStore::new();
+    local.put(blocks[1].clone());
+    let report = sync(root, Selector::All, &local, &remote).unwrap();
+    assert_eq!(report.present, 1);
+    assert_eq!(report.fetched, 3);
+    assert_eq!(local.len(), 4);
+    let again = sync(root, Selector::All, &local, &MemStore::new()).unwrap();
+    assert_eq!(again, SyncReport { present: 4, fetched: 0, bytes: 0 });
+    let shallow = MemStore::new();
+    let report = sync(root, Selector::Depth(1), &shallow, &remote).unwrap();
+    assert_eq!(report.fetched, 3);
+    assert!(!shallow.has(&blocks[2].cid()));
+  }
+
+  struct Liar;
+
+  impl BlockSource for Liar {
+    fn fetch(&self, cids: &[Cid]) -> Vec<(Cid, Vec<u8>)> {
+      cids.iter().map(|cid| (*cid, b"\xf6".to_vec())).collect()
+    }
+  }
+
+  #[test]
+  fn test_sync_verifies() {
+    let (_, blocks) = dag();
+    let root = blocks[0].cid();
+    let local = MemStore::new();
+    assert!(matches!(
+      sync(root, Selector::All, &local, &Liar),
+      Err(SyncError::Block(BlockError::Mismatch(..)))
+    ));
+    assert!(local.is_empty());
+    assert_eq!(
+      sync(root, Selector::All, &local, &MemStore::new()),
+      Err(SyncError::Unavailable(vec![root]))
+    );
+  }
+}
@@ -0,0 +1,550 @@
+use num_bigint::{
+  BigInt,
+  Sign,
+};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+use sp_ipld::Ipld;
+
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  convert::TryFrom,
+  vec::Vec,
+};
+
+use alloc::string::String;
+
+use crate::ipld::{
+  borrowed::{
+    BIGNUM_TAGS,
+    CID_TAG,
+  },
+  error::EncodeError,
+};
+
+/// The width at which floats are written
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum FloatWidth {
+  /// 32 bits when that is exact and 64 bits otherwise, which is what
+  /// `DagCborCodec` emits
+  Shortest,
+  /// Always 64 bits, as the DAG-CBOR spec requires
+  Double,
+}
+
+impl Default for FloatWidth {
+  fn default() -> Self { Self::Shortest }
+}
+
+impl FloatWidth {
+  /// The additional info of the header a float is written with
+  pub fn info(self, float: f64) -> u8 {
+    match self {
+      Self::Shortest if f64::from(float as f32) == float => 26,
+      _ => 27,
+    }
+  }
+}
+
+/// A DAG-CBOR encoder writing into a growing buffer
+#[derive(Clone, Debug, Default)]
+pub struct Writer {
+  buf: Vec<u8>,
+  floats: FloatWidth,
+}
+
+impl Writer {
+  pub fn new() -> Self { Self::default() }
+
+  /// A writer whose buffer starts out with room for `capacity` bytes, such
+  /// as the `encoded_len` of what will be written
+  pub fn with_capacity(capacity: usize) -> Self {
+    Writer { buf: Vec::with_capacity(capacity), floats: FloatWidth::default() }
+  }
+
+  pub fn with_float_width(mut self, floats: FloatWidth) -> Self {
+    self.floats = floats;
+    self
+  }
+
+  pub fn into_inner(self) -> Vec<u8> { self.buf }
+
+  /// Writes a major type and its argument in the fewest bytes
+  pub fn write_header(&mut self, major: u8, arg: u64) {
+    let major = major << 5;
+    if arg < 24 {
+      self.buf.push(major | arg as u8);
+    }
+    else if arg <= u64::from(u8::MAX) {
+      self.buf.push(major | 24);
+      self.buf.push(arg as u8);
+    }
+    else if arg <= u64::from(u16::MAX) {
+      self.buf.push(major | 25);
+      self.buf.extend_from_slice(&(arg as u16).to_be_bytes());
+    }
+    else if arg <= u64::from(u32::MAX) {
+      self.buf.push(major | 26);
+      self.buf.extend_from_slice(&(arg as u32).to_be_bytes());
+    }
+    else {
+      self.buf.push(major | 27);
+      self.buf.extend_from_slice(&arg.to_be_bytes());
+    }
+  }
+
+  pub fn write_float(&mut self, float: f64) -> Result<(), EncodeError> {
+    if !float.is_finite() {
+      return Err(EncodeError::NonFiniteFloat(float));
+    }
+    match self.floats.info(float) {
+      26 => {
+        self.buf.push(0xfa);
+        self.buf.extend_from_slice(&(float as f32).to_be_bytes());
+      }
+      _ => {
+        self.buf.push(0xfb);
+        self.buf.extend_from_slice(&float.to_be_bytes());
+      }
+    }
+    Ok(())
+  }
+
+  /// Writes an integer of any size, as a plain integer when it fits and as a
+  /// bignum otherwise
+  pub fn write_bigint(&mut self, int: &BigInt) {
+    // Negative integers are written as -1 - n, like in major type 1
+    let (major, tag, n) = if int.sign() == Sign::Minus {
+      (1, BIGNUM_TAGS.1, -int - 1u32)
+    }
+    else {
+      (0, BIGNUM_TAGS.0, int.clone())
+    };
+    match u64::try_from(n.magnitude()) {
+      Ok(arg) => self.write_header(major, arg),
+      Err(_) => {
+        let bytes = n.magnitude().to_bytes_be();
+        self.write_header(6, tag);
+        self.write_header(2, bytes.len() as u64);
+        self.buf.extend_from_slice(&bytes);
+      }
+    }
+  }
+
+  /// Writes a `u128`, as a bignum past `u64::MAX`
+  pub fn write_u128(&mut self, int: u128) { self.write_bigint(&int.into()) }
+
+  /// Writes an `i128`, as a bignum outside of the range of major types 0
+  /// and 1
+  pub fn write_i128(&mut self, int: i128) { self.write_bigint(&int.into()) }
+
+  /// Writes any IPLD object. Map entries are written in canonical order,
+  /// shorter keys first and keys of equal length bytewise.
+  pub fn write_ipld(&mut self, ipld: &Ipld) -> Result<(), EncodeError> {
+    match ipld {
+      Ipld::Null => self.buf.push(0xf6),
+      Ipld::Bool(b) => self.buf.push(if *b { 0xf5 } else { 0xf4 }),
+      Ipld::Integer(i) => {
+        if *i >= 0 && *i <= i128::from(u64::MAX) {
+          self.write_header(0, *i as u64);
+        }
+        else if *i < 0 && -1 - *i <= i128::from(u64::MAX) {
+          self.write_header(1, (-1 - *i) as u64);
+        }
+        else {
+          return Err(EncodeError::IntegerOutOfRange(*i));
+        }
+      }
+      Ipld::Float(f) => self.write_float(*f)?,
+      Ipld::String(s) => {
+        self.write_header(3, s.len() as u64);
+        self.buf.extend_from_slice(s.as_bytes());
+      }
+      Ipld::Bytes(b) => {
+        self.write_header(2, b.len() as u64);
+        self.buf.extend_from_slice(b);
+      }
+      Ipld::List(xs) => {
+        self.write_header(4, xs.len() as u64);
+        for x in xs {
+          self.write_ipld(x)?;
+        }
+      }
+      Ipld::StringMap(map) => {
+        self.write_header(5, map.len() as u64);
+        for (k, v) in canonical_entries(map) {
+          self.write_header(3, k.len() as u64);
+          self.buf.extend_from_slice(k.as_bytes());
+          self.write_ipld(v)?;
+        }
+      }
+      Ipld::Link(cid) => {
+        let bytes = cid.to_bytes();
+        self.write_header(6, CID_TAG);
+        // Links are prefixed with the identity multibase
+        self.write_header(2, bytes.len() as u64 + 1);
+        self.buf.push(0);
+        self.buf.extend_from_slice(&bytes);
+      }
+    }
+    Ok(())
+  }
+}
+
+/// The entries of a map in canonical order. The map already orders keys
+/// bytewise, so a stable sort by length alone is enough.
+fn canonical_entries(map: &BTreeMap<String, Ipld>) -> Vec<(&String, &Ipld)> {
+  let mut entries: Vec<_> = map.iter().collect();
+  entries.sort_by_key(|(k, _)| k.len());
+  entries
+}
+
+/// Lists and maps with at least this many entries are encoded in parallel
+#[cfg(feature = "rayon")]
+pub const PAR_THRESHOLD: usize = 256;
+
+#[cfg(feature = "rayon")]
+impl Writer {
+  /// Like `write_ipld`, but the entries of large lists and maps are encoded
+  /// into separate buffers on the rayon thread pool and then concatenated.
+  /// The output is the same.
+  pub fn par_write_ipld(&mut self, ipld: &Ipld) -> Result<(), EncodeError> {
+    let floats = self.floats;
+    match ipld {
+      Ipld::List(xs) if xs.len() >= PAR_THRESHOLD => {
+        let parts = xs
+          .par_iter()
+          .map(|x| {
+            let mut writer = Writer::with_capacity(encoded_len(x, floats)?)
+              .with_float_width(floats);
+            writer.par_write_ipld(x)?;
+            Ok(writer.into_inner())
+          })
+          .collect::<Result<Vec<_>, EncodeError>>()?;
+        self.write_header(4, xs.len() as u64);
+        parts.iter().for_each(|p| self.buf.extend_from_slice(p));
+      }
+      Ipld::StringMap(map) if map.len() >= PAR_THRESHOLD => {
+        let parts = canonical_entries(map)
+          .par_iter()
+          .map(|(k, v)| {
+            let len = header_len(k.len() as u64) + k.len();
+            let mut writer =
+              Writer::with_capacity(len + encoded_len(v, floats)?)
+                .with_float_width(floats);
+            writer.write_header(3, k.len() as u64);
+            writer.buf.extend_from_slice(k.as_bytes());
+            writer.par_write_ipld(v)?;
+            Ok(writer.into_inner())
+          })
+          .collect::<Result<Vec<_>, EncodeError>>()?;
+        self.write_header(5, map.len() as u64);
+        parts.iter().for_each(|p| self.buf.extend_from_slice(p));
+      }
+      // Small containers can still hold large ones
+      Ipld::List(xs) => {
+        self.write_header(4, xs.len() as u64);
+        for x in xs {
+          self.par_write_ipld(x)?;
+        }
+      }
+      Ipld::StringMap(map) => {
+        self.write_header(5, map.len() as u64);
+        for (k, v) in canonical_entries(map) {
+          self.write_header(3, k.len() as u64);
+          self.buf.extend_from_slice(k.as_bytes());
+          self.par_write_ipld(v)?;
+        }
+      }
+      _ => self.write_ipld(ipld)?,
+    }
+    Ok(())
+  }
+}
+
+/// Encodes like `encode`, using the rayon thread pool for large lists and
+/// maps
+#[cfg(feature = "rayon")]
+pub fn par_encode(ipld: &Ipld) -> Result<Vec<u8>, EncodeError> {
+  let mut writer =
+    Writer::with_capacity(encoded_len(ipld, FloatWidth::default())?);
+  writer.par_write_ipld(ipld)?;
+  Ok(writer.into_inner())
+}
+
+/// Encodes an IPLD object as DAG-CBOR with the default float width, into a
+/// buffer of exactly the right size
+pub fn encode(ipld: &Ipld) -> Result<Vec<u8>, EncodeError> {
+  let mut writer =
+    Writer::with_capacity(encoded_len(ipld, FloatWidth::default())?);
+  writer.write_ipld(ipld)?;
+  Ok(writer.into_inner())
+}
+
+/// The number of bytes of a header with argument `arg`
+pub fn header_len(arg: u64) -> usize {
+  match arg {
+    0..=23 => 1,
+    24..=0xff => 2,
+    0x100..=0xffff => 3,
+    0x1_0000..=0xffff_ffff => 5,
+    _ => 9,
+  }
+}
+
+/// The number of bytes `ipld` encodes to, without encoding it. Fails for
+/// the objects that fail to encode.
+pub fn encoded_len(
+  ipld: &Ipld,
+  floats: FloatWidth,
+) -> Result<usize, EncodeError> {
+  Ok(match ipld {
+    Ipld::Null | Ipld::Bool(_) => 1,
+    Ipld::Integer(i) => {
+      if *i >= 0 && *i <= i128::from(u64::MAX) {
+        header_len(*i as u64)
+      }
+      else if *i < 0 && -1 - *i <= i128::from(u64::MAX) {
+        header_len((-1 - *i) as u64)
+      }
+      else {
+        return Err(EncodeError::IntegerOutOfRange(*i));
+      }
+    }
+    Ipld::Float(f) if !f.is_finite() => {
+      return Err(EncodeError::NonFiniteFloat(*f));
+    }
+    Ipld::Float(f) => match floats.info(*f) {
+      26 => 5,
+      _ => 9,
+    },
+    Ipld::String(s) => header_len(s.len() as u64) + s.len(),
+    Ipld::Bytes(b) => header_len(b.len() as u64) + b.len(),
+    Ipld::List(xs) => {
+      let mut len = header_len(xs.len() as u64);
+      for x in xs {
+        len += encoded_len(x, floats)?;
+      }
+      len
+    }
+    Ipld::StringMap(map) => {
+      let mut len = header_len(map.len() as u64);
+      for (k, v) in map {
+        len += header_len(k.len() as u64) + k.len() + encoded_len(v, floats)?;
+      }
+      len
+    }
+    Ipld::Link(cid) => {
+      let cid_len = cid.to_bytes().len() + 1;
+      header_len(CID_TAG) + header_len(cid_len as u64) + cid_len
+    }
+  })
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    ipld::{
+      borrowed::{
+        IpldRef,
+        Reader,
+      },
+      error::DecodeError,
+    },
+    term::Term,
+  };
+  use alloc::string::{
+    String,
+    ToString,
+  };
+  use sp_ipld::{
+    dag_cbor::DagCborCodec,
+    Codec,
+  };
+  use sp_std::collections::btree_map::BTreeMap;
+
+  #[quickcheck]
+  fn writer_agrees(x: Term) -> bool {
+    let (anon, meta) = x.embed();
+    [anon.to_ipld(), meta.to_ipld()].iter().all(|ipld| {
+      encode(ipld).ok()
+        == DagCborCodec.encode(ipld).ok().map(|b| b.into_inner())
+    })
+  }
+
+  fn float_roundtrip(x: f64, width: FloatWidth) -> Result<f64, DecodeError> {
+    let mut writer = Writer::new().with_float_width(width);
+    writer.write_float(x).unwrap();
+    let bytes = writer.into_inner();
+    match IpldRef::decode_with(
+      Reader::new(&bytes).with_canonical_floats(width),
+    )? {
+      IpldRef::Float(f) => Ok(f),
+      _ => panic!("expected a float"),
+    }
+  }
+
+  #[cfg(feature = "rayon")]
+  #[quickcheck]
+  fn par_encode_agrees(x: Term) -> bool {
+    let (anon, meta) = x.embed();
+    let wide = Ipld::List(vec![anon.to_ipld(); PAR_THRESHOLD]);
+    let map = Ipld::StringMap(
+      (0..PAR_THRESHOLD)
+        .map(|i| (i.to_string(), meta.to_ipld()))
+        .collect::<BTreeMap<_, _>>(),
+    );
+    [anon.to_ipld(), wide, map]
+      .iter()
+      .all(|ipld| par_encode(ipld) == encode(ipld))
+  }
+
+  #[quickcheck]
+  fn writer_encoded_len(x: Term, f: f64) -> bool {
+    let (anon, meta) = x.embed();
+    let float =
+      Ipld::List(vec![Ipld::Float(f), Ipld::Float(f64::from(f as f32))]);
+    [anon.to_ipld(), meta.to_ipld(), float].iter().all(|ipld| {
+      [FloatWidth::Shortest, FloatWidth::Double].iter().all(|w| {
+        let mut writer = Writer::new().with_float_width(*w);
+        match (writer.write_ipld(ipld), encoded_len(ipld, *w)) {
+          (Ok(()), Ok(len)) => writer.into_inner().len() == len,
+          (Err(a), Err(b)) => a.to_string() == b.to_string(),
+          _ => false,
+        }
+      })
+    })
+  }
+
+  #[quickcheck]
+  fn writer_float_roundtrip(x: f64) -> bool {
+    if !x.is_finite() {
+      return matches!(
+        encode(&Ipld::Float(x)),
+        Err(EncodeError::NonFiniteFloat(_))
+      );
+    }
+    [FloatWidth::Shortest, FloatWidth::Double]
+      .iter()
+      .all(|w| float_roundtrip(x, *w).map(f64::to_bits) == Ok(x.to_bits()))
+  }
+
+  #[quickcheck]
+  fn writer_u128_roundtrip(x: u128, y: i128) -> bool {
+    let mut writer = Writer::new();
+    writer.write_u128(x);
+    writer.write_i128(y);
+    let bytes = writer.into_inner();
+    let mut reader = Reader::new(&bytes);
+    reader.read_u128() == Ok(x)
+      && reader.read_i128() == Ok(y)
+      && reader.remaining() == 0
+  }
+
+  #[test]
+  fn writer_u128_range() {
+    let mut writer = Writer::new();
+    writer.write_u128(u128::MAX);
+    writer.write_i128(i128::MIN);
+    writer.write_bigint(&(BigInt::from(u128::MAX) + 1u32));
+    let bytes = writer.into_inner();
+    let mut reader = Reader::new(&bytes);
+    assert_eq!(
+      reader.read_i128(),
+      Err(DecodeError::IntegerOutOfRange { offset: 0 })
+    );
+    assert_eq!(
+      reader.read_u128(),
+      Err(DecodeError::IntegerOutOfRange { offset: 18 })
+    );
+    assert_eq!(
+      reader.read_u128(),
+      Err(DecodeError::IntegerOutOfRange { offset: 36 })
+    );
+    assert_eq!(Reader::new(&bytes).read_u128(), Ok(u128::MAX));
+  }
+
+  #[quickcheck]
+  fn writer_bigint_roundtrip(bytes: Vec<u8>, negative: bool) -> bool {
+    let sign = if negative { Sign::Minus } else { Sign::Plus };
+    let int = BigInt::from_bytes_be(sign, &bytes);
+    let mut writer = Writer::new();
+    writer.write_bigint(&int);
+    let bytes = writer.into_inner();
+    let mut reader = Reader::new(&bytes);
+    reader.read_bigint() == Ok(int) && reader.remaining() == 0
+  }
+
+  #[test]
+  fn writer_bigint() {
+    let mut writer = Writer::new();
+    writer.write_bigint(&BigInt::from(u64::MAX));
+    writer.write_bigint(&(BigInt::from(u64::MAX) + 1u32));
+    writer.write_bigint(&(-BigInt::from(u64::MAX) - 2u32));
+    let mut expected = vec![0x1b];
+    expected.extend_from_slice(&[0xff; 8]);
+    expected.extend_from_slice(&[0xc2, 0x49, 0x01]);
+    expected.extend_from_slice(&[0x00; 8]);
+    expected.extend_from_slice(&[0xc3, 0x49, 0x01]);
+    expected.extend_from_slice(&[0x00; 8]);
+    assert_eq!(writer.into_inner(), expected);
+    // 2^64 - 1 written as a bignum
+    let mut padded = vec![0xc2, 0x48];
+    padded.extend_from_slice(&[0xff; 8]);
+    assert_eq!(
+      Reader::new(&padded).read_bigint(),
+      Err(DecodeError::NonCanonicalInteger { offset: 0 })
+    );
+  }
+
+  #[test]
+  fn writer_map_order() {
+    let map: BTreeMap<String, Ipld> = ["b", "aa", "a", "ab", "c"]
+      .iter()
+      .map(|k| (k.to_string(), Ipld::Null))
+      .collect();
+    let bytes = encode(&Ipld::StringMap(map)).unwrap();
+    let mut reader = Reader::new(&bytes);
+    assert_eq!(reader.read_header(), Ok((5, 5)));
+    let keys: Vec<&str> = (0..5)
+      .map(|_| {
+        let (_, len) = reader.read_header().unwrap();
+        let key = reader.read_str(len as usize).unwrap();
+        reader.skip().unwrap();
+        key
+      })
+      .collect();
+    assert_eq!(keys, vec!["a", "b", "c", "aa", "ab"]);
+  }
+
+  #[test]
+  fn writer_float_widths() {
+    assert_eq!(encode(&Ipld::Float(1.5)), Ok(vec![0xfa, 0x3f, 0xc0, 0, 0]));
+    let double = Writer::new().with_float_width(FloatWidth::Double);
+    let mut writer = double.clone();
+    writer.write_float(1.5).unwrap();
+    let bytes = writer.into_inner();
+    assert_eq!(bytes, vec![0xfb, 0x3f, 0xf8, 0, 0, 0, 0, 0, 0]);
+    assert_eq!(
+      IpldRef::decode_with(
+        Reader::new(&bytes).with_canonical_floats(FloatWidth::Shortest)
+      ),
+      Err(DecodeError::NonCanonicalFloat { offset: 0 })
+    );
+    // Half precision floats are never canonical
+    assert!(IpldRef::decode_with(
+      Reader::new(&[0xf9, 0x3e, 0x00])
+        .with_canonical_floats(FloatWidth::Shortest)
+    )
+    .is_err());
+    assert_eq!(
+      encode(&Ipld::Integer(i128::from(u64::MAX) + 1)),
+      Err(EncodeError::IntegerOutOfRange(i128::from(u64::MAX) + 1))
+    );
+    assert_eq!(
+      encode(&Ipld::Integer(-1 - i128::from(u64::MAX))),
+      Ok(vec![0x3b, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff])
+    );
+  }
+}
@@ -35,7 +35,24 @@ pub enum IpldError {
   I32Op(Ipld),
   I64Op(Ipld),
   I128Op(Ipld),
+  F32Op(Ipld),
+  F64Op(Ipld),
+  MapOp(Ipld),
+  SetOp(Ipld),
+  VecOp(Ipld),
+  Ed25519Op(Ipld),
+  Secp256k1Op(Ipld),
+  CidOp(Ipld),
+  ConvOp(Ipld),
+  ShowOp(Ipld),
+  FromTextOp(Ipld),
+  ExceptionOp(Ipld),
+  RngOp(Ipld),
+  ActionOp(Ipld),
+  U256Op(Ipld),
   IoOp(Ipld),
+  Level(Ipld),
+  Attrs(Ipld),
   Anon(Ipld),
   Meta(Ipld),
   Entry(Ipld),
@@ -44,6 +61,7 @@ pub enum IpldError {
   Import(Ipld),
   ImportEntry(Ipld),
   Package(Ipld),
+  Certificate(Ipld),
 }
 
 impl From<IpldError> for String {
@@ -0,0 +1,591 @@
+use crate::{
+  check::infer_lit,
+  defs::Defs,
+  level::Level,
+  literal::Literal,
+  name::Name,
+  optimize::subst,
+  position::Pos,
+  prim::host::HostRef,
+  term::{
+    Op,
+    Term,
+  },
+  uses::Uses,
+};
+
+use sp_cid::Cid;
+
+use sp_std::{
+  boxed::Box,
+  collections::btree_map::BTreeMap,
+  fmt,
+  rc::Rc,
+  vec::Vec,
+};
+
+use alloc::string::String;
+
+/// Why the kernel rejects a def
+#[derive(PartialEq, Clone, Debug)]
+pub enum KernelError {
+  UndefinedDef(Name),
+  UndefinedReference(Name, Cid),
+  UnboundVariable(Name, u64),
+  UnknownOperation(String),
+  /// A λ or data with no type to check it against
+  Untyped(Term),
+  /// A term whose type should have been of some form, with the type found
+  Expected(&'static str, Term, Term),
+  /// A term, the type expected of it and the type inferred for it
+  Mismatch(Term, Term, Term),
+}
+
+impl fmt::Display for KernelError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UndefinedDef(nam) => write!(f, "No def named {}", nam),
+      Self::UndefinedReference(nam, cid) => {
+        write!(f, "Undefined reference {} to {}", nam, cid)
+      }
+      Self::UnboundVariable(nam, idx) => {
+        write!(f, "Unbound variable {} at index {}", nam, idx)
+      }
+      Self::UnknownOperation(opr) => write!(f, "Unknown operation {}", opr),
+      Self::Untyped(term) => write!(f, "No type to check {} against", term),
+      Self::Expected(what, term, typ) => {
+        write!(f, "Expected {} as the type of {}, found {}", what, term, typ)
+      }
+      Self::Mismatch(term, expected, detected) => write!(
+        f,
+        "Type mismatch for {}: expected {}, found {}",
+        term, expected, detected
+      ),
+    }
+  }
+}
+
+/// Checks a def again, independently of `check::check_def`. The kernel
+/// works on terms as elaborated, with every implicit argument given, and
+/// has one rule per term former: a λ or data is only checked against a
+/// type, and a let or a λ applied on the spot binds its variable at the
+/// type of its expression. It shares no code with the checker but the
+/// types of literals and operations, and reduces terms by substitution
+/// rather than on DAGs, so that a def both accept rests on two separate
+/// implementations. Quantities aren't checked.
+pub fn check_def(defs: &Defs, name: &str) -> Result<(), KernelError> {
+  let name = Name::from(name);
+  let def =
+    defs.get(&name).ok_or_else(|| KernelError::UndefinedDef(name.clone()))?;
+  let kernel =
+    Kernel { defs, rec: (name, def.def_cid, def.ast_cid), typ: &def.typ_ };
+  let mut ctx = Vec::new();
+  kernel.infer_level(&mut ctx, &def.typ_)?;
+  kernel.check(&mut ctx, &def.term, &def.typ_)
+}
+
+/// The defs a def refers to, the def itself as the target of `Rec`, and its
+/// type
+struct Kernel<'a> {
+  defs: &'a Defs,
+  rec: (Name, Cid, Cid),
+  typ: &'a Term,
+}
+
+/// Runs `f` with a variable of type `typ` bound
+fn under<T>(
+  ctx: &mut Vec<Term>,
+  typ: Term,
+  f: impl FnOnce(&mut Vec<Term>) -> Result<T, KernelError>,
+) -> Result<T, KernelError> {
+  ctx.push(typ);
+  let res = f(ctx);
+  ctx.pop();
+  res
+}
+
+impl<'a> Kernel<'a> {
+  /// Checks a term against a type, where the context holds the types of the
+  /// variables in scope
+  fn check(
+    &self,
+    ctx: &mut Vec<Term>,
+    term: &Term,
+    typ: &Term,
+  ) -> Result<(), KernelError> {
+    match term {
+      Term::Lam(_, _, bod) => match self.whnf(typ) {
+        Term::All(_, _, _, _, dom_img) => {
+          let (dom, img) = *dom_img;
+          under(ctx, dom, |ctx| self.check(ctx, bod, &img))
+        }
+        typ => Err(KernelError::Expected("a ∀", term.clone(), typ)),
+      },
+      Term::Dat(_, bod) => match self.whnf(typ) {
+        Term::Slf(_, _, slf_bod) => {
+          self.check(ctx, bod, &subst(*slf_bod, 0, term))
+        }
+        typ => Err(KernelError::Expected("a self type", term.clone(), typ)),
+      },
+      Term::Let(_, rec, _, _, typ_exp_bod) => {
+        let (exp_typ, exp, bod) = typ_exp_bod.as_ref();
+        self.check_exp(ctx, *rec, exp_typ, exp)?;
+        let typ = typ.clone().shift(1, Some(0));
+        under(ctx, exp_typ.clone(), |ctx| self.check(ctx, bod, &typ))
+      }
+      Term::App(_, fun_arg) => match fun_arg.as_ref() {
+        (Term::Lam(_, _, bod), arg) => {
+          let arg_typ = self.infer(ctx, arg)?;
+          let typ = typ.clone().shift(1, Some(0));
+          under(ctx, arg_typ, |ctx| self.check(ctx, bod, &typ))
+        }
+        _ => self.check_inferred(ctx, term, typ),
+      },
+      _ => self.check_inferred(ctx, term, typ),
+    }
+  }
+
+  /// Checks a term by comparing the type inferred for it with the expected
+  /// one
+  fn check_inferred(
+    &self,
+    ctx: &mut Vec<Term>,
+    term: &Term,
+    typ: &Term,
+  ) -> Result<(), KernelError> {
+    let detected = self.infer(ctx, term)?;
+    if self.conv(typ, &detected) || self.cumulative(typ, &detected) {
+      Ok(())
+    }
+    else {
+      Err(KernelError::Mismatch(term.clone(), typ.clone(), detected))
+    }
+  }
+
+  fn infer(
+    &self,
+    ctx: &mut Vec<Term>,
+    term: &Term,
+  ) -> Result<Term, KernelError> {
+    match term {
+      Term::Var(_, nam, idx) => ctx
+        .iter()
+        .rev()
+        .nth(*idx as usize)
+        .map(|typ| typ.clone().shift(*idx as i64 + 1, Some(0)))
+        .ok_or_else(|| KernelError::UnboundVariable(nam.clone(), *idx)),
+      Term::Ref(_, nam, def, _) => {
+        let def =
+          self.defs.defs.get(def).ok_or_else(|| {
+            KernelError::UndefinedReference(nam.clone(), *def)
+          })?;
+        // Each reference is at levels of its own
+        let mut fresh = BTreeMap::new();
+        Ok(map_levels(def.typ_.clone(), &mut |lvl| {
+          for v in lvl.vars.keys() {
+            fresh
+              .entry(v.clone())
+              .or_insert_with(|| Level::var(Level::fresh(v)));
+          }
+          lvl.subst(&fresh)
+        }))
+      }
+      Term::Rec(_) => Ok(self.typ.clone()),
+      Term::Typ(_, lvl) => Ok(Term::Typ(Pos::None, lvl.succ())),
+      Term::Lit(_, lit) => Ok(infer_lit(lit.clone())),
+      Term::LTy(..) => Ok(Term::Typ(Pos::None, Level::zero())),
+      Term::Opr(_, opr) => match self.defs.host.resolve(opr.clone()) {
+        Op::Host(HostRef { op: None, .. }) => {
+          Err(KernelError::UnknownOperation(opr.symbol()))
+        }
+        opr => Ok(opr.type_of()),
+      },
+      Term::Lam(..) | Term::Dat(..) => Err(KernelError::Untyped(term.clone())),
+      Term::App(_, fun_arg) => match fun_arg.as_ref() {
+        (Term::Lam(_, _, bod), arg) => {
+          let arg_typ = self.infer(ctx, arg)?;
+          let bod_typ = under(ctx, arg_typ, |ctx| self.infer(ctx, bod))?;
+          Ok(subst(bod_typ, 0, arg))
+        }
+        (fun, arg) => {
+          let fun_typ = self.infer(ctx, fun)?;
+          match self.whnf(&fun_typ) {
+            Term::All(_, _, _, _, dom_img) => {
+              let (dom, img) = self.instantiate(ctx, *dom_img, arg)?;
+              self.check(ctx, arg, &dom)?;
+              Ok(subst(img, 0, arg))
+            }
+            typ => Err(KernelError::Expected("a ∀", fun.clone(), typ)),
+          }
+        }
+      },
+      Term::Cse(_, exp) => {
+        let exp_typ = self.infer(ctx, exp)?;
+        match self.whnf(&exp_typ) {
+          Term::Slf(_, _, bod) => Ok(subst(*bod, 0, exp)),
+          Term::LTy(pos, lty) => {
+            lty.induction(exp.as_ref().clone()).ok_or_else(|| {
+              let typ = Term::LTy(pos, lty);
+              KernelError::Expected("an inductive type", *exp.clone(), typ)
+            })
+          }
+          typ => Err(KernelError::Expected("a self type", *exp.clone(), typ)),
+        }
+      }
+      Term::All(_, _, _, _, dom_img) => {
+        let (dom, img) = dom_img.as_ref();
+        let dom_lvl = self.infer_level(ctx, dom)?;
+        let img_lvl =
+          under(ctx, dom.clone(), |ctx| self.infer_level(ctx, img))?;
+        Ok(Term::Typ(Pos::None, Level::imax(&dom_lvl, &img_lvl)))
+      }
+      Term::Slf(_, _, bod) => {
+        // The variable of a self type stands for a term of that type
+        let lvl = under(ctx, term.clone(), |ctx| self.infer_level(ctx, bod))?;
+        Ok(Term::Typ(Pos::None, lvl))
+      }
+      Term::Ann(_, typ_exp) => {
+        let (typ, exp) = typ_exp.as_ref();
+        self.infer_level(ctx, typ)?;
+        self.check(ctx, exp, typ)?;
+        Ok(typ.clone())
+      }
+      Term::Let(pos, rec, uses, nam, typ_exp_bod) => {
+        let (typ, exp, bod) = typ_exp_bod.as_ref();
+        self.check_exp(ctx, *rec, typ, exp)?;
+        let bod_typ = under(ctx, typ.clone(), |ctx| self.infer(ctx, bod))?;
+        let val =
+          if *rec { fix(*pos, *uses, nam, typ, exp) } else { exp.clone() };
+        Ok(subst(bod_typ, 0, &val))
+      }
+    }
+  }
+
+  /// Infers the level of a term that must be a type
+  fn infer_level(
+    &self,
+    ctx: &mut Vec<Term>,
+    term: &Term,
+  ) -> Result<Level, KernelError> {
+    let typ = self.infer(ctx, term)?;
+    match self.whnf(&typ) {
+      Term::Typ(_, lvl) => Ok(lvl),
+      typ => Err(KernelError::Expected("a universe", term.clone(), typ)),
+    }
+  }
+
+  /// Checks the expression of a let against its type, which is checked to
+  /// be one, with the let's own variable bound if it's recursive
+  fn check_exp(
+    &self,
+    ctx: &mut Vec<Term>,
+    rec: bool,
+    typ: &Term,
+    exp: &Term,
+  ) -> Result<(), KernelError> {
+    self.infer_level(ctx, typ)?;
+    if rec {
+      let exp_typ = typ.clone().shift(1, Some(0));
+      under(ctx, typ.clone(), |ctx| self.check(ctx, exp, &exp_typ))
+    }
+    else {
+      self.check(ctx, exp, typ)
+    }
+  }
+
+  /// The domain and image of a `∀` applied to `arg`. A universe domain at a
+  /// fresh level, as at a reference to a universe-polymorphic def, takes
+  /// the level of `arg` throughout the `∀`.
+  fn instantiate(
+    &self,
+    ctx: &mut Vec<Term>,
+    dom_img: (Term, Term),
+    arg: &Term,
+  ) -> Result<(Term, Term), KernelError> {
+    let sub = match &dom_img.0 {
+      Term::Typ(_, lvl) if lvl.vars.keys().any(Level::is_fresh) => {
+        lvl.solve(&self.infer_level(ctx, arg)?)
+      }
+      _ => None,
+    };
+    match sub {
+      Some((var, val)) => {
+        let mut sub = BTreeMap::new();
+        sub.insert(var, val);
+        let (dom, img) = dom_img;
+        let mut f = |lvl: &Level| lvl.subst(&sub);
+        Ok((map_levels(dom, &mut f), map_levels(img, &mut f)))
+      }
+      None => Ok(dom_img),
+    }
+  }
+
+  fn whnf(&self, term: &Term) -> Term { self.reduce(term.clone(), true) }
+
+  /// Reduces a term to weak head normal form, unfolding references at the
+  /// head only if `unfold`. Opaque and partial defs are never unfolded.
+  fn reduce(&self, term: Term, unfold: bool) -> Term {
+    // The arguments of the head, the first one last
+    let mut args = Vec::new();
+    let mut term = term;
+    let head = loop {
+      term = match term {
+        Term::App(_, fun_arg) => {
+          let (fun, arg) = *fun_arg;
+          args.push(arg);
+          fun
+        }
+        Term::Lam(_, _, bod) if !args.is_empty() => {
+          subst(*bod, 0, &args.pop().unwrap())
+        }
+        Term::Ann(_, typ_exp) => typ_exp.1,
+        Term::Let(_, false, _, _, typ_exp_bod) => {
+          let (_, exp, bod) = *typ_exp_bod;
+          subst(bod, 0, &exp)
+        }
+        Term::Let(pos, true, uses, nam, typ_exp_bod) => {
+          let (typ, exp, bod) = *typ_exp_bod;
+          let fix = fix(pos, uses, &nam, &typ, &exp);
+          match bod {
+            // The fixed point itself unrolls once
+            Term::Var(_, _, 0) => subst(exp, 0, &fix),
+            bod => subst(bod, 0, &fix),
+          }
+        }
+        Term::Rec(pos) => {
+          let (nam, def, ast) = self.rec.clone();
+          Term::Ref(pos, nam, def, ast)
+        }
+        Term::Ref(pos, nam, def_cid, ast_cid) => {
+          match self.defs.defs.get(&def_cid) {
+            Some(def) if unfold && def.attrs.unfolds() => {
+              let this = Term::Ref(pos, nam, def_cid, ast_cid);
+              def.term.clone().un_rec(Rc::new(this))
+            }
+            _ => break Term::Ref(pos, nam, def_cid, ast_cid),
+          }
+        }
+        Term::Cse(pos, bod) => match self.reduce(*bod, true) {
+          Term::Dat(_, bod) => *bod,
+          // Matching on an exception raises it
+          Term::Lit(lit_pos, lit @ Literal::Exception(..)) => {
+            Term::Lit(lit_pos, lit)
+          }
+          Term::Lit(lit_pos, lit) => match lit.clone().expand() {
+            Some(expand) => expand,
+            None => break Term::Cse(pos, Box::new(Term::Lit(lit_pos, lit))),
+          },
+          bod => break Term::Cse(pos, Box::new(bod)),
+        },
+        Term::Lit(pos, lit @ Literal::Exception(..)) if !args.is_empty() => {
+          args.clear();
+          Term::Lit(pos, lit)
+        }
+        Term::Opr(pos, opr) => {
+          let opr = self.defs.host.resolve(opr);
+          let arity = opr.arity() as usize;
+          let len = args.len();
+          if len < arity || (arity == 0 && len > 0) {
+            break Term::Opr(pos, opr);
+          }
+          for arg in args[len - arity..].iter_mut() {
+            *arg = self.reduce(arg.clone(), true);
+          }
+          let lits: Option<Vec<&Literal>> = args[len - arity..]
+            .iter()
+            .rev()
+            .map(|arg| match arg {
+              Term::Lit(_, lit) => Some(lit),
+              _ => None,
+            })
+            .collect();
+          let res = lits.and_then(|lits| {
+            match opr.raised(&lits).or_else(|| opr.apply(&lits)) {
+              Some(lit) => Some(Term::Lit(Pos::None, lit)),
+              None => opr.apply_term(&lits),
+            }
+          });
+          match res {
+            Some(res) => {
+              args.truncate(len - arity);
+              res
+            }
+            None => break Term::Opr(pos, opr),
+          }
+        }
+        term => break term,
+      }
+    };
+    args
+      .into_iter()
+      .rev()
+      .fold(head, |fun, arg| Term::App(Pos::None, Box::new((fun, arg))))
+  }
+
+  /// Whether two terms are definitionally equal. References are only
+  /// unfolded when comparing their arguments isn't enough.
+  fn conv(&self, a: &Term, b: &Term) -> bool {
+    if a == b {
+      return true;
+    }
+    let a = self.reduce(a.clone(), false);
+    let b = self.reduce(b.clone(), false);
+    if a == b {
+      return true;
+    }
+    if let (Some((x, xs)), Some((y, ys))) = (ref_spine(&a), ref_spine(&b)) {
+      if x == y
+        && xs.len() == ys.len()
+        && xs.iter().zip(ys.iter()).all(|(x, y)| self.conv(x, y))
+      {
+        return true;
+      }
+    }
+    let a = self.reduce(a, true);
+    let b = self.reduce(b, true);
+    match (&a, &b) {
+      (Term::Var(_, _, x), Term::Var(_, _, y)) => x == y,
+      (Term::Ref(_, _, x, _), Term::Ref(_, _, y, _)) => x == y,
+      (Term::Typ(_, x), Term::Typ(_, y)) => x == y,
+      (Term::Lit(_, x), Term::Lit(_, y)) => x == y,
+      (Term::LTy(_, x), Term::LTy(_, y)) => x == y,
+      (Term::Opr(_, x), Term::Opr(_, y)) => x == y,
+      (Term::Lam(_, _, x), Term::Lam(_, _, y))
+      | (Term::Slf(_, _, x), Term::Slf(_, _, y))
+      | (Term::Dat(_, x), Term::Dat(_, y))
+      | (Term::Cse(_, x), Term::Cse(_, y)) => self.conv(x, y),
+      (Term::All(_, _, x_uses, _, x), Term::All(_, _, y_uses, _, y)) => {
+        x_uses == y_uses && self.conv(&x.0, &y.0) && self.conv(&x.1, &y.1)
+      }
+      (Term::App(_, x), Term::App(_, y)) => {
+        self.conv(&x.0, &y.0) && self.conv(&x.1, &y.1)
+      }
+      _ => false,
+    }
+  }
+
+  /// Whether both types are universes, the detected one within the expected
+  /// one
+  fn cumulative(&self, expected: &Term, detected: &Term) -> bool {
+    match (self.whnf(expected), self.whnf(detected)) {
+      (Term::Typ(_, a), Term::Typ(_, b)) => b.leq(&a),
+      _ => false,
+    }
+  }
+}
+
+/// The def at the head of an application and its arguments, if the head is
+/// a reference
+fn ref_spine(term: &Term) -> Option<(Cid, Vec<&Term>)> {
+  let mut args = Vec::new();
+  let mut head = term;
+  while let Term::App(_, fun_arg) = head {
+    args.push(&fun_arg.1);
+    head = &fun_arg.0;
+  }
+  match head {
+    Term::Ref(_, _, def, _) => Some((*def, args)),
+    _ => None,
+  }
+}
+
+/// The value a recursive let binds: the let itself, returning its variable
+fn fix(pos: Pos, uses: Uses, nam: &Name, typ: &Term, exp: &Term) -> Term {
+  let var = Term::Var(pos, nam.clone(), 0);
+  let typ_exp_bod = (typ.clone(), exp.clone(), var);
+  Term::Let(pos, true, uses, nam.clone(), Box::new(typ_exp_bod))
+}
+
+/// Replaces the level of every universe in a term by its image under `f`
+fn map_levels<F: FnMut(&Level) -> Level>(term: Term, f: &mut F) -> Term {
+  match term {
+    Term::Typ(pos, lvl) => Term::Typ(pos, f(&lvl)),
+    Term::Lam(pos, nam, bod) => {
+      Term::Lam(pos, nam, Box::new(map_levels(*bod, f)))
+    }
+    Term::Slf(pos, nam, bod) => {
+      Term::Slf(pos, nam, Box::new(map_levels(*bod, f)))
+    }
+    Term::Dat(pos, bod) => Term::Dat(pos, Box::new(map_levels(*bod, f))),
+    Term::Cse(pos, bod) => Term::Cse(pos, Box::new(map_levels(*bod, f))),
+    Term::App(pos, fun_arg) => {
+      let (fun, arg) = *fun_arg;
+      Term::App(pos, Box::new((map_levels(fun, f), map_levels(arg, f))))
+    }
+    Term::Ann(pos, typ_exp) => {
+      let (typ, exp) = *typ_exp;
+      Term::Ann(pos, Box::new((map_levels(typ, f), map_levels(exp, f))))
+    }
+    Term::All(pos, imp, uses, nam, dom_img) => {
+      let (dom, img) = *dom_img;
+      let dom_img = (map_levels(dom, f), map_levels(img, f));
+      Term::All(pos, imp, uses, nam, Box::new(dom_img))
+    }
+    Term::Let(pos, rec, uses, nam, typ_exp_bod) => {
+      let (typ, exp, bod) = *typ_exp_bod;
+      let typ_exp_bod =
+        (map_levels(typ, f), map_levels(exp, f), map_levels(bod, f));
+      Term::Let(pos, rec, uses, nam, Box::new(typ_exp_bod))
+    }
+    x => x,
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::parse::{
+    package,
+    span::Span,
+    term::input_cid,
+  };
+
+  fn defs(src: &str) -> Defs {
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    defs
+  }
+
+  #[test]
+  fn test_kernel_accepts() {
+    let src = "type Nat { Z, S (pred: Nat) }\n\
+               def add (m n: Nat): Nat = (case m) (λ _ => Nat) n (λ p => \
+               Nat.S (add p n))\n\
+               def id {A: Type{u}} (x: A): A = x\n\
+               def ty: Type{1} = id Type\n\
+               def num: #U64 = let x: #U64 = id 1u64; #U64.add x x\n\
+               def two: #U64 = (λ x => #U64.add x 1u64) 1u64\n\
+               def big: Type{2} = #U64\n\
+               def nat: Type = Nat\n\
+               def three: nat = add (Nat.S Nat.Z) (Nat.S (Nat.S Nat.Z))";
+    let defs = defs(src);
+    for nam in defs.names().iter() {
+      assert_eq!(check_def(&defs, nam), Ok(()), "{}", nam);
+    }
+  }
+
+  #[test]
+  fn test_kernel_rejects() {
+    let src = "def bad: #Text = #U64.add 1u64 1u64";
+    assert!(matches!(
+      check_def(&defs(src), "bad"),
+      Err(KernelError::Mismatch(..))
+    ));
+    let src = "def bad: Type = Type";
+    assert!(matches!(
+      check_def(&defs(src), "bad"),
+      Err(KernelError::Mismatch(..))
+    ));
+    let src = "def bad: 1u64 = 1u64";
+    assert!(matches!(
+      check_def(&defs(src), "bad"),
+      Err(KernelError::Expected("a universe", ..))
+    ));
+    let src = "def id {A: Type} (x: A): A = x\ndef ty: Type{1} = id Type";
+    assert!(check_def(&defs(src), "ty").is_err());
+    assert!(matches!(
+      check_def(&defs(src), "none"),
+      Err(KernelError::UndefinedDef(_))
+    ));
+  }
+}
@@ -0,0 +1,269 @@
+use crate::{
+  ipld_error::IpldError,
+  name::Name,
+};
+
+use sp_ipld::Ipld;
+
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  fmt,
+  vec::Vec,
+};
+
+use core::sync::atomic::{
+  AtomicU64,
+  Ordering,
+};
+
+use alloc::string::ToString;
+
+/// A universe level: the maximum of a constant and of level variables, each
+/// raised by an offset. Levels are kept normalized, so levels equal for
+/// every value of their variables are equal as values.
+///
+/// `Type` is `Type{0}`, the lowest universe, and `Type{n}` has type
+/// `Type{n+1}`. The lowest universe is impredicative, so that the self types
+/// encoding datatypes, which quantify over motives into it, live in it.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Debug, Default)]
+pub struct Level {
+  pub base: u64,
+  pub vars: BTreeMap<Name, u64>,
+}
+
+/// Counts the level variables made fresh by `Level::fresh`
+static FRESH: AtomicU64 = AtomicU64::new(0);
+
+impl Level {
+  pub fn zero() -> Self { Self::default() }
+
+  pub fn lit(base: u64) -> Self { Level { base, vars: BTreeMap::new() } }
+
+  pub fn var(nam: Name) -> Self {
+    let mut vars = BTreeMap::new();
+    vars.insert(nam, 0);
+    Level { base: 0, vars }
+  }
+
+  /// A variable no other level mentions, standing for the level `nam` of a
+  /// definition at one of its references
+  pub fn fresh(nam: &Name) -> Name {
+    let n = FRESH.fetch_add(1, Ordering::Relaxed);
+    Name::from(format!("?{}.{}", nam, n))
+  }
+
+  /// Whether a variable was made by `Level::fresh`, and so may be solved
+  /// while checking the term it occurs in
+  pub fn is_fresh(nam: &Name) -> bool { nam.starts_with('?') }
+
+  pub fn is_zero(&self) -> bool { self.base == 0 && self.vars.is_empty() }
+
+  fn normalize(mut self) -> Self {
+    if self.vars.values().any(|k| *k >= self.base) {
+      self.base = 0;
+    }
+    self
+  }
+
+  /// The level raised by `k`
+  pub fn raise(&self, k: u64) -> Self {
+    Level {
+      base: self.base + k,
+      vars: self.vars.iter().map(|(v, j)| (v.clone(), j + k)).collect(),
+    }
+    .normalize()
+  }
+
+  pub fn succ(&self) -> Self { self.raise(1) }
+
+  /// The least level at least both levels
+  pub fn join(&self, other: &Level) -> Self {
+    let mut vars = self.vars.clone();
+    for (v, k) in other.vars.iter() {
+      let j = vars.entry(v.clone()).or_insert(*k);
+      *j = (*j).max(*k);
+    }
+    Level { base: self.base.max(other.base), vars }.normalize()
+  }
+
+  /// The level of a `∀` whose domain has level `dom` and image level `img`.
+  /// A `∀` into the lowest universe stays in it, however large its domain.
+  /// For an image level with variables this is an upper bound, which is
+  /// enough by cumulativity.
+  pub fn imax(dom: &Level, img: &Level) -> Self {
+    if img.is_zero() {
+      Level::zero()
+    }
+    else {
+      dom.join(img)
+    }
+  }
+
+  /// Whether the level is at most `other` for every value of the variables
+  pub fn leq(&self, other: &Level) -> bool {
+    let bound = other.vars.values().copied().fold(other.base, u64::max);
+    self.base <= bound
+      && self
+        .vars
+        .iter()
+        .all(|(v, k)| other.vars.get(v).map_or(false, |j| k <= j))
+  }
+
+  /// The level less `k`, if it can be lowered that far
+  pub fn lower(&self, k: u64) -> Option<Self> {
+    let vars = self.vars.values().all(|j| *j >= k);
+    // A base below `k` can only be one dropped by normalization
+    let base = self.base >= k || (self.base == 0 && !self.vars.is_empty());
+    if !(vars && base) {
+      return None;
+    }
+    Some(
+      Level {
+        base: self.base.saturating_sub(k),
+        vars: self.vars.iter().map(|(v, j)| (v.clone(), j - k)).collect(),
+      }
+      .normalize(),
+    )
+  }
+
+  /// Replaces the variables of the level by the given levels
+  pub fn subst(&self, sub: &BTreeMap<Name, Level>) -> Self {
+    let mut res = Level::lit(self.base);
+    for (v, k) in self.vars.iter() {
+      let lvl = match sub.get(v) {
+        Some(lvl) => lvl.raise(*k),
+        None => Level::var(v.clone()).raise(*k),
+      };
+      res = res.join(&lvl);
+    }
+    res
+  }
+
+  /// If `self` is a single fresh variable raised by some offset, the value
+  /// of that variable making `self` equal to `other`
+  pub fn solve(&self, other: &Level) -> Option<(Name, Level)> {
+    match self.vars.iter().collect::<Vec<_>>().as_slice() {
+      [(v, k)] if self.base == 0 && Level::is_fresh(v) => {
+        Some(((*v).clone(), other.lower(**k)?))
+      }
+      _ => None,
+    }
+  }
+
+  pub fn to_ipld(&self) -> Ipld {
+    let vars = self
+      .vars
+      .iter()
+      .map(|(v, k)| {
+        Ipld::List(vec![Ipld::String(v.to_string()), Ipld::Integer(*k as i128)])
+      })
+      .collect();
+    Ipld::List(vec![Ipld::Integer(self.base as i128), Ipld::List(vars)])
+  }
+
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    let err = || IpldError::Level(ipld.clone());
+    match ipld {
+      Ipld::List(xs) => match xs.as_slice() {
+        [Ipld::Integer(base), Ipld::List(ys)] => {
+          let mut vars = BTreeMap::new();
+          for y in ys {
+            match y {
+              Ipld::List(v) => match v.as_slice() {
+                [Ipld::String(v), Ipld::Integer(k)] => {
+                  vars.insert(Name::from(v.clone()), *k as u64);
+                }
+                _ => return Err(err()),
+              },
+              _ => return Err(err()),
+            }
+          }
+          Ok(Level { base: *base as u64, vars })
+        }
+        _ => Err(err()),
+      },
+      _ => Err(err()),
+    }
+  }
+}
+
+/// Formats a level as it appears between the braces of `Type{..}`, e.g.
+/// `u+1, 2` for the maximum of `u+1` and `2`
+impl fmt::Display for Level {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mut parts: Vec<_> = self
+      .vars
+      .iter()
+      .map(|(v, k)| {
+        if *k == 0 {
+          v.to_string()
+        }
+        else {
+          format!("{}+{}", v, k)
+        }
+      })
+      .collect();
+    if self.base > 0 || parts.is_empty() {
+      parts.push(self.base.to_string());
+    }
+    write!(f, "{}", parts.join(", "))
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+
+  impl Arbitrary for Level {
+    fn arbitrary(g: &mut Gen) -> Self {
+      let vars: Vec<(bool, u8)> = Arbitrary::arbitrary(g);
+      let base: u8 = Arbitrary::arbitrary(g);
+      let mut lvl = Level::lit(u64::from(base % 4));
+      for (v, k) in vars.into_iter().take(2) {
+        let v = Name::from(if v { "u" } else { "v" });
+        lvl = lvl.join(&Level::var(v).raise(u64::from(k % 4)));
+      }
+      lvl
+    }
+  }
+
+  #[quickcheck]
+  fn level_ipld(x: Level) -> bool {
+    matches!(Level::from_ipld(&x.to_ipld()), Ok(y) if x == y)
+  }
+
+  #[quickcheck]
+  fn level_max_leq(x: Level, y: Level) -> bool {
+    let m = x.join(&y);
+    x.leq(&m) && y.leq(&m) && m == y.join(&x)
+  }
+
+  #[test]
+  fn test_levels() {
+    let u = Level::var(Name::from("u"));
+    let v = Level::var(Name::from("v"));
+    assert_eq!(Level::lit(1).join(&u.succ()), u.succ());
+    assert_eq!(format!("{}", u.succ().join(&Level::lit(2))), "u+1, 2");
+    assert_eq!(format!("{}", Level::zero()), "0");
+    assert!(Level::lit(1).leq(&u.succ()));
+    assert!(!Level::lit(1).leq(&u));
+    assert!(!u.leq(&v));
+    assert!(u.leq(&u.join(&v)));
+    assert_eq!(Level::imax(&Level::lit(1), &Level::zero()), Level::zero());
+    assert_eq!(Level::imax(&Level::lit(1), &u), u.join(&Level::lit(1)));
+    let mut sub = BTreeMap::new();
+    sub.insert(Name::from("u"), Level::lit(2));
+    assert_eq!(u.succ().join(&v).subst(&sub), v.join(&Level::lit(3)));
+    let w = Level::var(Level::fresh(&Name::from("u")));
+    assert_eq!(
+      w.succ().solve(&Level::lit(3)).map(|x| x.1),
+      Some(Level::lit(2))
+    );
+    assert_eq!(w.succ().solve(&Level::zero()), None);
+    assert_eq!(u.solve(&Level::lit(3)), None);
+  }
+}
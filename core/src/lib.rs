@@ -12,23 +12,39 @@ extern crate quickcheck_macros;
 extern crate rand;
 
 pub mod anon;
+pub mod arena;
+pub mod certificate;
 pub mod check;
 pub mod dag;
+pub mod debugger;
 pub mod defs;
 pub mod dll;
+pub mod elab;
 pub mod embed_error;
+pub mod erase;
 pub mod eval;
+pub mod fuel;
+pub mod hashcons;
+pub mod ipld;
 pub mod ipld_error;
+pub mod kernel;
+pub mod level;
 pub mod literal;
+pub mod memo;
 pub mod meta;
 pub mod name;
+pub mod optimize;
 pub mod package;
+#[cfg(feature = "rayon")]
+pub mod par;
 pub mod typedef;
 #[macro_use]
 pub mod parse;
 pub mod position;
 pub mod prim;
 pub mod term;
+pub mod termination;
+pub mod trace;
 pub mod upcopy;
 pub mod uses;
 
@@ -7,11 +7,16 @@ use crate::{
   prim::{
     bits,
     text,
+    u256::U256,
   },
   term::Term,
   yatima,
 };
 
+use sp_cid::Cid;
+
+use sp_im::Vector;
+
 use sp_ipld::Ipld;
 
 use sp_ropey::Rope;
@@ -24,6 +29,10 @@ use num_bigint::{
 
 use sp_std::{
   borrow::ToOwned,
+  collections::{
+    btree_map::BTreeMap,
+    btree_set::BTreeSet,
+  },
   convert::TryInto,
   fmt,
   vec::Vec,
@@ -49,11 +58,20 @@ pub enum Literal {
   U32(u32),
   U64(u64),
   U128(u128),
+  U256(U256),
   I8(i8),
   I16(i16),
   I32(i32),
   I64(i64),
   I128(i128),
+  F32(f32),
+  F64(f64),
+  Map(BTreeMap<String, Vec<u8>>),
+  Set(BTreeSet<Vec<u8>>),
+  Vector(Vector<Vec<u8>>),
+  Cid(Cid),
+  Exception(Rope, Vec<u8>),
+  Rng(u64),
 }
 
 /// The type of each literal
@@ -71,11 +89,22 @@ pub enum LitType {
   U32,
   U64,
   U128,
+  U256,
   I8,
   I16,
   I32,
   I64,
   I128,
+  F32,
+  F64,
+  Map,
+  Set,
+  Vector,
+  Cid,
+  Exception,
+  Rng,
+  /// Programs of primitive actions, which have no literals
+  IO,
 }
 
 impl fmt::Display for Literal {
@@ -124,6 +153,7 @@ impl fmt::Display for Literal {
       U32(x) => write!(f, "{}u32", x),
       U64(x) => write!(f, "{}u64", x),
       U128(x) => write!(f, "{}u128", x),
+      U256(x) => write!(f, "{}u256", x),
       I8(x) => {
         if x.is_negative() {
           write!(f, "{}i8", x)
@@ -164,11 +194,109 @@ impl fmt::Display for Literal {
           write!(f, "+{}i128", x)
         }
       }
+      // Floats without a literal syntax print as the operations that make
+      // them, which loses the payload of a NaN
+      F32(x) => {
+        if x.is_finite() {
+          write!(f, "{:?}f32", x)
+        }
+        else if x.is_nan() {
+          write!(f, "#F32.nan")
+        }
+        else if x.is_sign_positive() {
+          write!(f, "#F32.inf")
+        }
+        else {
+          write!(f, "(#F32.neg #F32.inf)")
+        }
+      }
+      F64(x) => {
+        if x.is_finite() {
+          write!(f, "{:?}f64", x)
+        }
+        else if x.is_nan() {
+          write!(f, "#F64.nan")
+        }
+        else if x.is_sign_positive() {
+          write!(f, "#F64.inf")
+        }
+        else {
+          write!(f, "(#F64.neg #F64.inf)")
+        }
+      }
+      Map(xs) => {
+        let mut res = String::from("#Map.empty");
+        for (k, v) in xs.iter().rev() {
+          res = format!(
+            "(#Map.insert {} {} {})",
+            Text(Rope::from(k.as_str())),
+            Bytes(v.clone()),
+            res
+          );
+        }
+        write!(f, "{}", res)
+      }
+      Set(xs) => {
+        let mut res = String::from("#Set.empty");
+        for x in xs.iter().rev() {
+          res = format!("(#Set.insert {} {})", Bytes(x.clone()), res);
+        }
+        write!(f, "{}", res)
+      }
+      Vector(xs) => {
+        let mut res = String::from("#Vector.empty");
+        for x in xs.iter() {
+          res = format!("(#Vector.push {} {})", Bytes(x.clone()), res);
+        }
+        write!(f, "{}", res)
+      }
+      Cid(x) => {
+        write!(f, "(#Cid.from_text {})", Text(Rope::from(x.to_string())))
+      }
+      Exception(tag, payload) => write!(
+        f,
+        "(#Exception.raise #Exception {} {})",
+        Text(tag.clone()),
+        Bytes(payload.clone())
+      ),
+      Rng(x) => write!(f, "(#Rng.new {}u64)", x),
     }
   }
 }
 
 impl Literal {
+  /// Gets the type of a literal
+  pub fn lit_type(&self) -> LitType {
+    match self {
+      Self::Nat(_) => LitType::Nat,
+      Self::Int(_) => LitType::Int,
+      Self::Bits(_) => LitType::Bits,
+      Self::Bytes(_) => LitType::Bytes,
+      Self::Text(_) => LitType::Text,
+      Self::Char(_) => LitType::Char,
+      Self::Bool(_) => LitType::Bool,
+      Self::U8(_) => LitType::U8,
+      Self::U16(_) => LitType::U16,
+      Self::U32(_) => LitType::U32,
+      Self::U64(_) => LitType::U64,
+      Self::U128(_) => LitType::U128,
+      Self::U256(_) => LitType::U256,
+      Self::I8(_) => LitType::I8,
+      Self::I16(_) => LitType::I16,
+      Self::I32(_) => LitType::I32,
+      Self::I64(_) => LitType::I64,
+      Self::I128(_) => LitType::I128,
+      Self::F32(_) => LitType::F32,
+      Self::F64(_) => LitType::F64,
+      Self::Map(_) => LitType::Map,
+      Self::Set(_) => LitType::Set,
+      Self::Vector(_) => LitType::Vector,
+      Self::Cid(_) => LitType::Cid,
+      Self::Exception(..) => LitType::Exception,
+      Self::Rng(_) => LitType::Rng,
+    }
+  }
+
   /// Converts a literal into a lambda expression
   pub fn expand(self) -> Option<Term> {
     match self {
@@ -292,6 +420,45 @@ impl Literal {
         Ipld::Integer(16),
         Ipld::Bytes(x.to_be_bytes().to_vec()),
       ]),
+      Self::F32(x) => Ipld::List(vec![
+        Ipld::Integer(17),
+        Ipld::Bytes(x.to_be_bytes().to_vec()),
+      ]),
+      Self::F64(x) => Ipld::List(vec![
+        Ipld::Integer(18),
+        Ipld::Bytes(x.to_be_bytes().to_vec()),
+      ]),
+      Self::Map(xs) => Ipld::List(vec![
+        Ipld::Integer(19),
+        Ipld::List(
+          xs.iter()
+            .map(|(k, v)| {
+              Ipld::List(vec![Ipld::String(k.clone()), Ipld::Bytes(v.clone())])
+            })
+            .collect(),
+        ),
+      ]),
+      Self::Set(xs) => Ipld::List(vec![
+        Ipld::Integer(20),
+        Ipld::List(xs.iter().map(|x| Ipld::Bytes(x.clone())).collect()),
+      ]),
+      Self::Vector(xs) => Ipld::List(vec![
+        Ipld::Integer(21),
+        Ipld::List(xs.iter().map(|x| Ipld::Bytes(x.clone())).collect()),
+      ]),
+      Self::Cid(x) => Ipld::List(vec![Ipld::Integer(22), Ipld::Link(*x)]),
+      Self::Exception(tag, payload) => Ipld::List(vec![
+        Ipld::Integer(23),
+        Ipld::Bytes(tag.to_string().into_bytes()),
+        Ipld::Bytes(payload.to_owned()),
+      ]),
+      Self::Rng(x) => {
+        Ipld::List(vec![Ipld::Integer(24), Ipld::Integer(*x as i128)])
+      }
+      Self::U256(x) => Ipld::List(vec![
+        Ipld::Integer(25),
+        Ipld::Bytes(x.to_be_bytes().to_vec()),
+      ]),
     }
   }
 
@@ -401,6 +568,81 @@ impl Literal {
             .map_or_else(|e| Err(IpldError::ByteCount(e, 16)), Ok)?;
           Ok(Self::I128(i128::from_be_bytes(x)))
         }
+        [Ipld::Integer(17), Ipld::Bytes(x)] => {
+          let x: [u8; 4] = x
+            .to_owned()
+            .try_into()
+            .map_or_else(|e| Err(IpldError::ByteCount(e, 4)), Ok)?;
+          Ok(Self::F32(f32::from_be_bytes(x)))
+        }
+        [Ipld::Integer(18), Ipld::Bytes(x)] => {
+          let x: [u8; 8] = x
+            .to_owned()
+            .try_into()
+            .map_or_else(|e| Err(IpldError::ByteCount(e, 8)), Ok)?;
+          Ok(Self::F64(f64::from_be_bytes(x)))
+        }
+        // Entries must be sorted by strictly increasing key, so that every map
+        // or set has exactly one encoding
+        [Ipld::Integer(19), Ipld::List(ys)] => {
+          let mut map = BTreeMap::new();
+          for y in ys {
+            match y {
+              Ipld::List(kv) => match kv.as_slice() {
+                [Ipld::String(k), Ipld::Bytes(v)]
+                  if map.keys().next_back().map_or(true, |last| last < k) =>
+                {
+                  map.insert(k.clone(), v.clone());
+                }
+                _ => return Err(IpldError::Literal(ipld.clone())),
+              },
+              _ => return Err(IpldError::Literal(ipld.clone())),
+            }
+          }
+          Ok(Self::Map(map))
+        }
+        [Ipld::Integer(20), Ipld::List(ys)] => {
+          let mut set = BTreeSet::new();
+          for y in ys {
+            match y {
+              Ipld::Bytes(x)
+                if set.iter().next_back().map_or(true, |last| last < x) =>
+              {
+                set.insert(x.clone());
+              }
+              _ => return Err(IpldError::Literal(ipld.clone())),
+            }
+          }
+          Ok(Self::Set(set))
+        }
+        [Ipld::Integer(21), Ipld::List(ys)] => {
+          let mut xs = Vector::new();
+          for y in ys {
+            match y {
+              Ipld::Bytes(x) => xs.push_back(x.clone()),
+              _ => return Err(IpldError::Literal(ipld.clone())),
+            }
+          }
+          Ok(Self::Vector(xs))
+        }
+        [Ipld::Integer(22), Ipld::Link(x)] => Ok(Self::Cid(*x)),
+        [Ipld::Integer(23), Ipld::Bytes(tag), Ipld::Bytes(payload)] => {
+          String::from_utf8(tag.to_owned()).map_or_else(
+            |e| Err(IpldError::Utf8(tag.clone(), e)),
+            |tag| Ok(Self::Exception(tag.into(), payload.to_owned())),
+          )
+        }
+        [Ipld::Integer(24), Ipld::Integer(x)] => (*x)
+          .try_into()
+          .map(Self::Rng)
+          .map_err(|_| IpldError::Literal(ipld.clone())),
+        [Ipld::Integer(25), Ipld::Bytes(x)] => {
+          let x: [u8; 32] = x
+            .to_owned()
+            .try_into()
+            .map_or_else(|e| Err(IpldError::ByteCount(e, 32)), Ok)?;
+          Ok(Self::U256(U256::from_be_bytes(x)))
+        }
         xs => Err(IpldError::Literal(Ipld::List(xs.to_owned()))),
       },
       _ => Err(IpldError::Literal(ipld.clone())),
@@ -483,6 +725,16 @@ impl LitType {
       Self::I32 => Ipld::List(vec![Ipld::Integer(14)]),
       Self::I64 => Ipld::List(vec![Ipld::Integer(15)]),
       Self::I128 => Ipld::List(vec![Ipld::Integer(16)]),
+      Self::F32 => Ipld::List(vec![Ipld::Integer(17)]),
+      Self::F64 => Ipld::List(vec![Ipld::Integer(18)]),
+      Self::Map => Ipld::List(vec![Ipld::Integer(19)]),
+      Self::Set => Ipld::List(vec![Ipld::Integer(20)]),
+      Self::Vector => Ipld::List(vec![Ipld::Integer(21)]),
+      Self::Cid => Ipld::List(vec![Ipld::Integer(22)]),
+      Self::Exception => Ipld::List(vec![Ipld::Integer(23)]),
+      Self::Rng => Ipld::List(vec![Ipld::Integer(24)]),
+      Self::U256 => Ipld::List(vec![Ipld::Integer(25)]),
+      Self::IO => Ipld::List(vec![Ipld::Integer(26)]),
     }
   }
 
@@ -507,6 +759,16 @@ impl LitType {
         [Ipld::Integer(14)] => Ok(Self::I32),
         [Ipld::Integer(15)] => Ok(Self::I64),
         [Ipld::Integer(16)] => Ok(Self::I128),
+        [Ipld::Integer(17)] => Ok(Self::F32),
+        [Ipld::Integer(18)] => Ok(Self::F64),
+        [Ipld::Integer(19)] => Ok(Self::Map),
+        [Ipld::Integer(20)] => Ok(Self::Set),
+        [Ipld::Integer(21)] => Ok(Self::Vector),
+        [Ipld::Integer(22)] => Ok(Self::Cid),
+        [Ipld::Integer(23)] => Ok(Self::Exception),
+        [Ipld::Integer(24)] => Ok(Self::Rng),
+        [Ipld::Integer(25)] => Ok(Self::U256),
+        [Ipld::Integer(26)] => Ok(Self::IO),
         xs => Err(IpldError::LitType(Ipld::List(xs.to_owned()))),
       },
       _ => Err(IpldError::LitType(ipld.clone())),
@@ -519,6 +781,7 @@ impl fmt::Display for LitType {
     match self {
       Self::Nat => write!(f, "#Nat"),
       Self::Int => write!(f, "#Int"),
+      Self::Bits => write!(f, "#Bits"),
       Self::Bytes => write!(f, "#Bytes"),
       Self::Text => write!(f, "#Text"),
       Self::Char => write!(f, "#Char"),
@@ -527,11 +790,22 @@ impl fmt::Display for LitType {
       Self::U16 => write!(f, "#U16"),
       Self::U32 => write!(f, "#U32"),
       Self::U64 => write!(f, "#U64"),
+      Self::U128 => write!(f, "#U128"),
+      Self::U256 => write!(f, "#U256"),
       Self::I8 => write!(f, "#I8"),
       Self::I16 => write!(f, "#I16"),
       Self::I32 => write!(f, "#I32"),
       Self::I64 => write!(f, "#I64"),
-      _ => todo!(),
+      Self::I128 => write!(f, "#I128"),
+      Self::F32 => write!(f, "#F32"),
+      Self::F64 => write!(f, "#F64"),
+      Self::Map => write!(f, "#Map"),
+      Self::Set => write!(f, "#Set"),
+      Self::Vector => write!(f, "#Vector"),
+      Self::Cid => write!(f, "#Cid"),
+      Self::Exception => write!(f, "#Exception"),
+      Self::Rng => write!(f, "#Rng"),
+      Self::IO => write!(f, "#IO"),
     }
   }
 }
@@ -544,7 +818,13 @@ pub mod tests {
     Gen,
   };
 
-  use crate::tests::frequency;
+  use crate::{
+    ipld::hash::{
+      digest,
+      Hasher,
+    },
+    tests::frequency,
+  };
   use sp_std::{
     boxed::Box,
     vec::Vec,
@@ -631,6 +911,13 @@ pub mod tests {
     })
   }
 
+  pub fn arbitrary_u256() -> Box<dyn Fn(&mut Gen) -> Literal> {
+    Box::new(move |g: &mut Gen| {
+      let x: U256 = Arbitrary::arbitrary(g);
+      Literal::U256(x)
+    })
+  }
+
   pub fn arbitrary_i8() -> Box<dyn Fn(&mut Gen) -> Literal> {
     Box::new(move |g: &mut Gen| {
       let x: i8 = Arbitrary::arbitrary(g);
@@ -661,6 +948,30 @@ pub mod tests {
       Literal::I128(x)
     })
   }
+  // Non-finite floats have no literal syntax, so they are left out to keep
+  // printed terms parseable
+  pub fn arbitrary_f32() -> Box<dyn Fn(&mut Gen) -> Literal> {
+    Box::new(move |g: &mut Gen| {
+      let x: f32 = Arbitrary::arbitrary(g);
+      Literal::F32(if x.is_finite() { x } else { 0.0 })
+    })
+  }
+  pub fn arbitrary_f64() -> Box<dyn Fn(&mut Gen) -> Literal> {
+    Box::new(move |g: &mut Gen| {
+      let x: f64 = Arbitrary::arbitrary(g);
+      Literal::F64(if x.is_finite() { x } else { 0.0 })
+    })
+  }
+
+  // Maps, sets and vectors print as the operations that build them rather
+  // than as a literal, so they are tested on their own instead of being
+  // generated with the rest
+  pub fn arbitrary_map() -> Box<dyn Fn(&mut Gen) -> Literal> {
+    Box::new(move |g: &mut Gen| {
+      let x: BTreeMap<String, Vec<u8>> = Arbitrary::arbitrary(g);
+      Literal::Map(x)
+    })
+  }
 
   impl Arbitrary for Literal {
     fn arbitrary(g: &mut Gen) -> Self {
@@ -677,11 +988,14 @@ pub mod tests {
         (1, arbitrary_u32()),
         (1, arbitrary_u64()),
         (1, arbitrary_u128()),
+        (1, arbitrary_u256()),
         (1, arbitrary_i8()),
         (1, arbitrary_i16()),
         (1, arbitrary_i32()),
         (1, arbitrary_i64()),
         (1, arbitrary_i128()),
+        (1, arbitrary_f32()),
+        (1, arbitrary_f64()),
         (1, Box::new(|g| Self::Char(Arbitrary::arbitrary(g)))),
       ])
     }
@@ -695,6 +1009,85 @@ pub mod tests {
     }
   }
 
+  #[quickcheck]
+  fn map_ipld(x: BTreeMap<String, Vec<u8>>) -> bool {
+    let x = Literal::Map(x);
+    match Literal::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn set_ipld(x: BTreeSet<Vec<u8>>) -> bool {
+    let x = Literal::Set(x);
+    match Literal::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn vector_ipld(x: Vec<Vec<u8>>) -> bool {
+    let x = Literal::Vector(x.into_iter().collect());
+    match Literal::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  // CIDs also print as an operation, `#Cid.from_text`
+  #[quickcheck]
+  fn cid_ipld(x: Vec<u8>) -> bool {
+    let x = Literal::Cid(digest(0x55, Hasher::default(), &x));
+    match Literal::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  // Exceptions print as `#Exception.raise` at the type `#Exception`
+  #[quickcheck]
+  fn exception_ipld(tag: String, payload: Vec<u8>) -> bool {
+    let x = Literal::Exception(Rope::from(tag), payload);
+    match Literal::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn rng_ipld(x: u64) -> bool {
+    let x = Literal::Rng(x);
+    match Literal::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[test]
+  fn test_set_ipld_canonical() {
+    let set = |xs: Vec<u8>| {
+      let xs = xs.into_iter().map(|x| Ipld::Bytes(vec![x])).collect();
+      Ipld::List(vec![Ipld::Integer(20), Ipld::List(xs)])
+    };
+    assert!(Literal::from_ipld(&set(vec![0, 1])).is_ok());
+    assert!(Literal::from_ipld(&set(vec![1, 0])).is_err());
+    assert!(Literal::from_ipld(&set(vec![0, 0])).is_err());
+  }
+
+  #[test]
+  fn test_map_ipld_canonical() {
+    let entry = |k: &str| {
+      Ipld::List(vec![Ipld::String(k.to_owned()), Ipld::Bytes(vec![])])
+    };
+    let map =
+      |xs: Vec<Ipld>| Ipld::List(vec![Ipld::Integer(19), Ipld::List(xs)]);
+    assert!(Literal::from_ipld(&map(vec![entry("a"), entry("b")])).is_ok());
+    assert!(Literal::from_ipld(&map(vec![entry("b"), entry("a")])).is_err());
+    assert!(Literal::from_ipld(&map(vec![entry("a"), entry("a")])).is_err());
+  }
+
   impl Arbitrary for LitType {
     fn arbitrary(g: &mut Gen) -> Self {
       let input: Vec<(i64, Box<dyn Fn(&mut Gen) -> LitType>)> = vec![
@@ -0,0 +1,254 @@
+use crate::{
+  dag::*,
+  defs::Defs,
+  dll::*,
+  eval::norm_children,
+  term::Term,
+};
+
+use sp_cid::Cid;
+
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  fmt,
+  rc::Rc,
+};
+
+/// Persistent storage of normal forms, keyed by the anonymous CID of the term
+/// they were computed from
+pub trait NormStore {
+  /// The normal form of the term with CID `cid`, if one was stored
+  fn get(&self, cid: &Cid) -> Option<Term>;
+
+  /// Stores the normal form of the term with CID `cid`
+  fn put(&self, cid: Cid, norm: &Term);
+}
+
+/// Memoizes the normal forms of definitions. A closed term's normal form is
+/// determined by its anonymous CID, so a cached normal form stays valid
+/// across packages and sessions.
+#[derive(Clone, Default)]
+pub struct NormCache {
+  norms: BTreeMap<Cid, Cid>,
+  terms: BTreeMap<Cid, Term>,
+  store: Option<Rc<dyn NormStore>>,
+  hits: u64,
+  misses: u64,
+}
+
+impl NormCache {
+  pub fn new() -> Self { Self::default() }
+
+  /// A cache that falls back to `store` on a miss and writes every new normal
+  /// form through to it
+  pub fn with_store(store: Rc<dyn NormStore>) -> Self {
+    NormCache { store: Some(store), ..Self::default() }
+  }
+
+  pub fn set_store(&mut self, store: Option<Rc<dyn NormStore>>) {
+    self.store = store;
+  }
+
+  /// The CID of the normal form of the term with CID `cid`
+  pub fn norm_cid(&self, cid: &Cid) -> Option<Cid> {
+    self.norms.get(cid).copied()
+  }
+
+  /// Looks up the normal form of the term with CID `cid`, loading it from the
+  /// persistent store if it isn't in memory
+  pub fn get(&mut self, cid: &Cid) -> Option<Term> {
+    let terms = &self.terms;
+    let norm = self.norms.get(cid).and_then(|n| terms.get(n)).cloned();
+    if norm.is_some() {
+      self.hits += 1;
+      return norm;
+    }
+    match self.store.as_ref().and_then(|store| store.get(cid)) {
+      Some(norm) => {
+        self.hits += 1;
+        self.remember(*cid, norm.clone());
+        Some(norm)
+      }
+      None => {
+        self.misses += 1;
+        None
+      }
+    }
+  }
+
+  /// Records `norm` as the normal form of the term with CID `cid`
+  pub fn insert(&mut self, cid: Cid, norm: Term) {
+    if let Some(store) = self.store.as_ref() {
+      store.put(cid, &norm);
+    }
+    self.remember(cid, norm);
+  }
+
+  fn remember(&mut self, cid: Cid, norm: Term) {
+    let norm_cid = norm.embed().0.cid();
+    self.norms.insert(cid, norm_cid);
+    self.terms.entry(norm_cid).or_insert(norm);
+  }
+
+  /// Forgets the normal forms held in memory, keeping the persistent store
+  pub fn clear(&mut self) {
+    self.norms.clear();
+    self.terms.clear();
+  }
+
+  /// The number of terms with a known normal form
+  pub fn len(&self) -> usize { self.norms.len() }
+
+  pub fn is_empty(&self) -> bool { self.norms.is_empty() }
+
+  pub fn hits(&self) -> u64 { self.hits }
+
+  pub fn misses(&self) -> u64 { self.misses }
+}
+
+impl fmt::Display for NormCache {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} terms, {} normal forms, {} hits, {} misses",
+      self.norms.len(),
+      self.terms.len(),
+      self.hits,
+      self.misses
+    )
+  }
+}
+
+impl DAG {
+  /// Reduces a DAG to its normal form as in `norm`, except that every
+  /// reference normalized along the way is replaced by its normal form from
+  /// `cache`. References missing from the cache are normalized on their own
+  /// and added to it.
+  pub fn norm_cached(
+    &mut self,
+    defs: &Defs,
+    should_count: bool,
+    cache: &mut NormCache,
+  ) {
+    self.memo(defs, should_count, cache);
+    self.whnf(defs, should_count);
+    let mut trail = vec![self.head];
+    while let Some(node) = trail.pop() {
+      for child in norm_children(node) {
+        let mut child = DAG::new(child);
+        child.memo(defs, should_count, cache);
+        child.whnf(defs, should_count);
+        trail.push(child.head);
+      }
+    }
+  }
+
+  /// Replaces the head of the DAG by its normal form if it is a reference
+  fn memo(&mut self, defs: &Defs, should_count: bool, cache: &mut NormCache) {
+    let link = match self.head {
+      DAGPtr::Ref(link) => link,
+      _ => return,
+    };
+    let Ref { nam, exp, ast, parents: ref_parents, .. } =
+      unsafe { &mut *link.as_ptr() };
+    let def = match defs.defs.get(exp) {
+      Some(def) => def,
+      None => return,
+    };
+    let norm = match cache.get(ast) {
+      Some(norm) => norm,
+      None => {
+        let mut dag = DAG::from_def(def, nam.clone());
+        dag.norm_cached(defs, should_count, cache);
+        let norm = dag.to_term(false);
+        dag.free();
+        cache.insert(*ast, norm.clone());
+        norm
+      }
+    };
+    let parents = *ref_parents;
+    *ref_parents = None;
+    let node = DAG::from_term_inner(&norm, 0, BTreeMap::new(), parents, None);
+    free_dead_node(self.head);
+    for parent in DLL::iter_option(parents) {
+      install_child(parent, node);
+    }
+    self.head = node;
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    name::Name,
+    parse::{
+      package,
+      span::Span,
+      term::{
+        input_cid,
+        parse,
+      },
+    },
+  };
+  use core::cell::RefCell;
+
+  #[derive(Default)]
+  struct MemNorms(RefCell<BTreeMap<Cid, Term>>);
+
+  impl NormStore for MemNorms {
+    fn get(&self, cid: &Cid) -> Option<Term> {
+      self.0.borrow().get(cid).cloned()
+    }
+
+    fn put(&self, cid: Cid, norm: &Term) {
+      self.0.borrow_mut().insert(cid, norm.clone());
+    }
+  }
+
+  fn norm(input: &str, defs: &Defs, cache: &mut NormCache) -> String {
+    let (_, term) = parse(input, defs.clone()).unwrap();
+    let mut dag = DAG::from_term(&term);
+    dag.norm_cached(defs, false, cache);
+    format!("{}", dag)
+  }
+
+  #[test]
+  fn test_norm_cached() {
+    let src = "def two: #U64 = #U64.add 1u64 1u64\n\
+               def four: #U64 = #U64.mul two two\n\
+               def pair: Type = λ f => f two four";
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    let mut cache = NormCache::new();
+    assert_eq!(norm("pair", &defs, &mut cache), "λ f => f 2u64 4u64");
+    // `pair`, `two` and `four` were each normalized once
+    assert_eq!(cache.len(), 3);
+    assert_eq!(cache.misses(), 3);
+    let hits = cache.hits();
+    assert_eq!(
+      norm("λ x => x four two", &defs, &mut cache),
+      "λ x => x 4u64 2u64"
+    );
+    assert_eq!(cache.hits(), hits + 2);
+    assert_eq!(cache.len(), 3);
+    let two = defs.get(&Name::from("two")).unwrap();
+    let four = defs.get(&Name::from("four")).unwrap();
+    assert_ne!(cache.norm_cid(&two.ast_cid), cache.norm_cid(&four.ast_cid));
+  }
+
+  #[test]
+  fn test_norm_store() {
+    let src = "def two: #U64 = #U64.add 1u64 1u64";
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    let store = Rc::new(MemNorms::default());
+    let mut cache = NormCache::with_store(store.clone());
+    assert_eq!(norm("two", &defs, &mut cache), "2u64");
+    assert_eq!(store.0.borrow().len(), 1);
+    // A fresh cache backed by the same store finds the normal form there
+    let mut cache = NormCache::with_store(store);
+    assert_eq!(norm("two", &defs, &mut cache), "2u64");
+    assert_eq!((cache.hits(), cache.misses()), (1, 0));
+  }
+}
@@ -21,7 +21,7 @@ pub enum Meta {
   Var(Pos, Name),
   Lam(Pos, Name, Box<Meta>),
   App(Pos, Box<(Meta, Meta)>),
-  All(Pos, Name, Box<(Meta, Meta)>),
+  All(Pos, bool, Name, Box<(Meta, Meta)>),
   Slf(Pos, Name, Box<Meta>),
   Dat(Pos, Box<Meta>),
   Cse(Pos, Box<Meta>),
@@ -59,10 +59,12 @@ impl Meta {
           arg.to_ipld(),
         ])
       }
-      Self::All(pos, nam, dom_img) => {
+      Self::All(pos, imp, nam, dom_img) => {
         let (dom, img) = (*dom_img).as_ref();
+        // Implicit foralls get their own tag, so that explicit ones keep the
+        // encoding they had before implicits existed
         Ipld::List(vec![
-          Ipld::Integer(3),
+          Ipld::Integer(if *imp { 15 } else { 3 }),
           pos.to_ipld(),
           Ipld::String(nam.to_string()),
           dom.to_ipld(),
@@ -134,11 +136,12 @@ impl Meta {
           let arg = Meta::from_ipld(arg)?;
           Ok(Meta::App(pos, Box::new((fun, arg))))
         }
-        [Ipld::Integer(3), pos, Ipld::String(nam), dom, img] => {
+        [Ipld::Integer(tag @ (3 | 15)), pos, Ipld::String(nam), dom, img] => {
           let pos = Pos::from_ipld(pos)?;
           let dom = Meta::from_ipld(dom)?;
           let img = Meta::from_ipld(img)?;
-          Ok(Meta::All(pos, Name::from(nam.clone()), Box::new((dom, img))))
+          let nam = Name::from(nam.clone());
+          Ok(Meta::All(pos, *tag == 15, nam, Box::new((dom, img))))
         }
         [Ipld::Integer(4), pos, Ipld::String(nam), bod] => {
           let pos = Pos::from_ipld(pos)?;
@@ -206,7 +209,12 @@ impl fmt::Display for Meta {
       Var(_, name) => write!(f, "Var({})", name),
       Lam(_, name, b) => write!(f, "Lam({}, {}", name, *b),
       App(_, b) => write!(f, "App({}, {})", (*b).0, (*b).1),
-      All(_, name, b) => write!(f, "All({}, {}, {})", name, (*b).0, (*b).1),
+      All(_, false, name, b) => {
+        write!(f, "All({}, {}, {})", name, (*b).0, (*b).1)
+      }
+      All(_, true, name, b) => {
+        write!(f, "Imp({}, {}, {})", name, (*b).0, (*b).1)
+      }
       Slf(_, name, b) => writeln!(f, "Slf({}, {})", name, *b),
       Dat(_, b) => write!(f, "Dat({})", *b),
       Cse(_, b) => write!(f, "Cse({})", *b),
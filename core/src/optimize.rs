@@ -0,0 +1,361 @@
+use crate::{
+  defs::Defs,
+  prim::Op,
+  term::Term,
+};
+
+use sp_cid::Cid;
+
+use sp_std::{
+  boxed::Box,
+  vec::Vec,
+};
+
+/// How hard the optimizer works, with one preset per `-O` level
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Opts {
+  /// The size, in nodes, of the largest def inlined at its references
+  pub inline_size: usize,
+  /// The most simplification passes made over a term
+  pub rounds: usize,
+}
+
+impl Opts {
+  pub fn level(level: u64) -> Self {
+    match level {
+      0 => Opts { inline_size: 0, rounds: 0 },
+      1 => Opts { inline_size: 16, rounds: 4 },
+      _ => Opts { inline_size: 64, rounds: 16 },
+    }
+  }
+
+  pub fn is_off(&self) -> bool { self.rounds == 0 }
+}
+
+/// Optimizes the term of every def. The defs keep their CIDs and types, so
+/// the result is for running or compiling, not for storing.
+pub fn optimize_defs(defs: &Defs, opts: Opts) -> Defs {
+  let mut res = defs.clone();
+  if opts.is_off() {
+    return res;
+  }
+  for def in res.defs.values_mut() {
+    def.term = optimize_term(defs, &def.term, opts);
+  }
+  res
+}
+
+/// Inlines the small defs a term refers to, then simplifies it until
+/// nothing changes or the passes run out
+pub fn optimize_term(defs: &Defs, term: &Term, opts: Opts) -> Term {
+  if opts.is_off() {
+    return term.clone();
+  }
+  let mut term = inline(defs, opts, &mut Vec::new(), term.clone());
+  for _ in 0..opts.rounds {
+    let next = simplify(term.clone());
+    if next == term {
+      break;
+    }
+    term = next;
+  }
+  term
+}
+
+/// Replaces references to small, non-recursive defs by their terms. The
+/// stack holds the defs being inlined, so mutually referring defs stop
+/// unfolding once they meet again.
+fn inline(defs: &Defs, opts: Opts, stack: &mut Vec<Cid>, term: Term) -> Term {
+  let go = |stack: &mut Vec<Cid>, x: Term| inline(defs, opts, stack, x);
+  match term {
+    Term::Ref(pos, nam, def_cid, ast_cid) => match defs.defs.get(&def_cid) {
+      Some(def)
+        if !stack.contains(&def_cid)
+          && size(&def.term) <= opts.inline_size
+          && !has_rec(&def.term) =>
+      {
+        stack.push(def_cid);
+        let res = go(stack, def.term.clone());
+        stack.pop();
+        res
+      }
+      _ => Term::Ref(pos, nam, def_cid, ast_cid),
+    },
+    Term::Lam(pos, nam, bod) => Term::Lam(pos, nam, Box::new(go(stack, *bod))),
+    Term::Dat(pos, bod) => Term::Dat(pos, Box::new(go(stack, *bod))),
+    Term::Cse(pos, bod) => Term::Cse(pos, Box::new(go(stack, *bod))),
+    Term::App(pos, fun_arg) => {
+      let (fun, arg) = *fun_arg;
+      Term::App(pos, Box::new((go(stack, fun), go(stack, arg))))
+    }
+    Term::Ann(pos, typ_exp) => {
+      let (typ, exp) = *typ_exp;
+      Term::Ann(pos, Box::new((typ, go(stack, exp))))
+    }
+    Term::Let(pos, rec, uses, nam, typ_exp_bod) => {
+      let (typ, exp, bod) = *typ_exp_bod;
+      let exp = go(stack, exp);
+      let bod = go(stack, bod);
+      Term::Let(pos, rec, uses, nam, Box::new((typ, exp, bod)))
+    }
+    x => x,
+  }
+}
+
+/// One bottom-up pass of beta and let reduction, dead binding removal,
+/// `case`-of-`data` cancellation and constant folding. Types are left alone.
+fn simplify(term: Term) -> Term {
+  match term {
+    Term::Lam(pos, nam, bod) => Term::Lam(pos, nam, Box::new(simplify(*bod))),
+    Term::Dat(pos, bod) => Term::Dat(pos, Box::new(simplify(*bod))),
+    Term::Cse(pos, bod) => match simplify(*bod) {
+      Term::Dat(_, bod) => *bod,
+      bod => Term::Cse(pos, Box::new(bod)),
+    },
+    Term::Ann(pos, typ_exp) => {
+      let (typ, exp) = *typ_exp;
+      Term::Ann(pos, Box::new((typ, simplify(exp))))
+    }
+    Term::App(pos, fun_arg) => {
+      let (fun, arg) = *fun_arg;
+      let arg = simplify(arg);
+      match simplify(fun) {
+        Term::Lam(_, _, bod) if substitutable(&bod, &arg) => {
+          subst(*bod, 0, &arg)
+        }
+        fun => fold(Term::App(pos, Box::new((fun, arg)))),
+      }
+    }
+    Term::Let(pos, rec, uses, nam, typ_exp_bod) => {
+      let (typ, exp, bod) = *typ_exp_bod;
+      let exp = simplify(exp);
+      let bod = simplify(bod);
+      if occurrences(&bod, 0).0 == 0 {
+        bod.shift(-1, Some(0))
+      }
+      else if !rec && substitutable(&bod, &exp) {
+        subst(bod, 0, &exp)
+      }
+      else {
+        Term::Let(pos, rec, uses, nam, Box::new((typ, exp, bod)))
+      }
+    }
+    x => x,
+  }
+}
+
+/// Whether a value can replace the variable bound around `bod` without
+/// repeating work: it's atomic or a lambda, or it's needed at most once and
+/// not from under a lambda, which may run many times
+fn substitutable(bod: &Term, val: &Term) -> bool {
+  match val {
+    Term::Var(..)
+    | Term::Ref(..)
+    | Term::Rec(_)
+    | Term::Lit(..)
+    | Term::Opr(..)
+    | Term::Typ(..)
+    | Term::LTy(..) => true,
+    _ => {
+      let (n, under_lam) = occurrences(bod, 0);
+      n == 0 || (n == 1 && (!under_lam || matches!(val, Term::Lam(..))))
+    }
+  }
+}
+
+/// Folds a saturated primitive operation on literals. Operations with
+/// effects, host operations and operations stuck on their arguments are left
+/// to the runtime.
+fn fold(term: Term) -> Term {
+  let mut args = Vec::new();
+  let mut head = &term;
+  while let Term::App(_, fun_arg) = head {
+    match &fun_arg.1 {
+      Term::Lit(_, lit) => args.push(lit),
+      _ => return term,
+    }
+    head = &fun_arg.0;
+  }
+  args.reverse();
+  match head {
+    Term::Opr(_, Op::Io(_) | Op::Host(_)) => term,
+    Term::Opr(_, opr) if args.len() as u64 == opr.arity() => {
+      match opr.apply(&args) {
+        Some(lit) => Term::Lit(term.pos(), lit),
+        None => opr.apply_term(&args).unwrap_or(term),
+      }
+    }
+    _ => term,
+  }
+}
+
+/// Replaces the variable with index `dep` by `val`, which is closed under the
+/// binder of that variable, and lowers the variables bound outside it
+pub(crate) fn subst(term: Term, dep: u64, val: &Term) -> Term {
+  match term {
+    Term::Var(pos, nam, idx) => {
+      if idx == dep {
+        val.clone().shift(dep as i64, Some(0))
+      }
+      else if idx > dep {
+        Term::Var(pos, nam, idx - 1)
+      }
+      else {
+        Term::Var(pos, nam, idx)
+      }
+    }
+    Term::Lam(pos, nam, bod) => {
+      Term::Lam(pos, nam, Box::new(subst(*bod, dep + 1, val)))
+    }
+    Term::Slf(pos, nam, bod) => {
+      Term::Slf(pos, nam, Box::new(subst(*bod, dep + 1, val)))
+    }
+    Term::Dat(pos, bod) => Term::Dat(pos, Box::new(subst(*bod, dep, val))),
+    Term::Cse(pos, bod) => Term::Cse(pos, Box::new(subst(*bod, dep, val))),
+    Term::App(pos, fun_arg) => {
+      let (fun, arg) = *fun_arg;
+      Term::App(pos, Box::new((subst(fun, dep, val), subst(arg, dep, val))))
+    }
+    Term::Ann(pos, typ_exp) => {
+      let (typ, exp) = *typ_exp;
+      Term::Ann(pos, Box::new((subst(typ, dep, val), subst(exp, dep, val))))
+    }
+    Term::All(pos, imp, uses, nam, dom_img) => {
+      let (dom, img) = *dom_img;
+      let dom_img = (subst(dom, dep, val), subst(img, dep + 1, val));
+      Term::All(pos, imp, uses, nam, Box::new(dom_img))
+    }
+    Term::Let(pos, rec, uses, nam, typ_exp_bod) => {
+      let (typ, exp, bod) = *typ_exp_bod;
+      let exp = subst(exp, if rec { dep + 1 } else { dep }, val);
+      let typ_exp_bod = (subst(typ, dep, val), exp, subst(bod, dep + 1, val));
+      Term::Let(pos, rec, uses, nam, Box::new(typ_exp_bod))
+    }
+    x => x,
+  }
+}
+
+/// Calls `f` on every subterm, with the number of binders above it and
+/// whether one of them is a lambda
+fn walk<F: FnMut(&Term, u64, bool)>(
+  term: &Term,
+  dep: u64,
+  lam: bool,
+  f: &mut F,
+) {
+  f(term, dep, lam);
+  match term {
+    Term::Lam(_, _, bod) => walk(bod, dep + 1, true, f),
+    Term::Slf(_, _, bod) => walk(bod, dep + 1, lam, f),
+    Term::Dat(_, bod) | Term::Cse(_, bod) => walk(bod, dep, lam, f),
+    Term::App(_, xy) | Term::Ann(_, xy) => {
+      walk(&xy.0, dep, lam, f);
+      walk(&xy.1, dep, lam, f);
+    }
+    Term::All(_, _, _, _, dom_img) => {
+      walk(&dom_img.0, dep, lam, f);
+      walk(&dom_img.1, dep + 1, lam, f);
+    }
+    Term::Let(_, rec, _, _, typ_exp_bod) => {
+      let (typ, exp, bod) = typ_exp_bod.as_ref();
+      walk(typ, dep, lam, f);
+      walk(exp, if *rec { dep + 1 } else { dep }, lam, f);
+      walk(bod, dep + 1, lam, f);
+    }
+    _ => (),
+  }
+}
+
+/// How many times the variable with index `idx` occurs, and whether any
+/// occurrence is under a lambda
+fn occurrences(term: &Term, idx: u64) -> (u64, bool) {
+  let mut res = (0, false);
+  walk(term, 0, false, &mut |x, dep, lam| {
+    if let Term::Var(_, _, i) = x {
+      if *i == idx + dep {
+        res = (res.0 + 1, res.1 || lam);
+      }
+    }
+  });
+  res
+}
+
+fn size(term: &Term) -> usize {
+  let mut res = 0;
+  walk(term, 0, false, &mut |_, _, _| res += 1);
+  res
+}
+
+fn has_rec(term: &Term) -> bool {
+  let mut res = false;
+  walk(term, 0, false, &mut |x, _, _| res = res || matches!(x, Term::Rec(_)));
+  res
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    name::Name,
+    parse::{
+      package,
+      span::Span,
+      term::{
+        input_cid,
+        parse,
+      },
+    },
+  };
+
+  fn defs(src: &str) -> Defs {
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    defs
+  }
+
+  fn optimized(defs: &Defs, nam: &str, level: u64) -> String {
+    let optimized = optimize_defs(defs, Opts::level(level));
+    format!("{}", optimized.get(&Name::from(nam)).unwrap().term)
+  }
+
+  #[test]
+  fn test_optimize_defs() {
+    let defs = defs(
+      "def double (x: #U64): #U64 = #U64.mul x 2u64\n\
+       def six: #U64 = double 3u64\n\
+       def sum (n: #U64): #U64 = (case (#U64.eql n 0u64)) Type 0u64 \
+       (#U64.add n (sum (#U64.sub n 1u64)))\n\
+       def ten: #U64 = sum 4u64\n\
+       def dead: #U64 = let x: #U64 = #U64.div 1u64 0u64; 7u64\n\
+       def shared (n: #U64): #U64 = let y: #U64 = double n; #U64.add y y",
+    );
+    assert_eq!(optimized(&defs, "six", 0), "double 3u64");
+    assert_eq!(optimized(&defs, "six", 1), "6u64");
+    // Recursive defs aren't inlined
+    assert_eq!(optimized(&defs, "ten", 2), "sum 4u64");
+    assert_eq!(optimized(&defs, "dead", 1), "7u64");
+    // A binding used twice stays, so its value is computed once
+    assert_eq!(
+      optimized(&defs, "shared", 1),
+      "λ n => let y: #U64 = #U64.mul n 2u64; #U64.add y y"
+    );
+  }
+
+  #[test]
+  fn test_optimize_term() {
+    let defs = defs("def id (0 A: Type) (x: A): A = x");
+    let opt = |src: &str| {
+      let (_, term) = parse(src, defs.clone()).unwrap();
+      format!("{}", optimize_term(&defs, &term, Opts::level(1)))
+    };
+    assert_eq!(opt("id #U64 (#U64.add 1u64 2u64)"), "3u64");
+    assert_eq!(opt("(case (data λ x => x)) 1u64"), "1u64");
+    assert_eq!(opt("λ x => (λ y => #U64.add y y) x"), "λ x => #U64.add x x");
+    // An argument used twice isn't duplicated
+    assert_eq!(
+      opt("λ a => (λ y => λ z => #U64.add y y) (#U64.add a 1u64)"),
+      "λ a => (λ y z => #U64.add y y) (#U64.add a 1u64)"
+    );
+    assert_eq!(opt("#U64.div 1u64 0u64"), "#U64.div 1u64 0u64");
+  }
+}
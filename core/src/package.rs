@@ -45,6 +45,114 @@ pub struct Import {
 #[derive(PartialEq, Clone, Debug)]
 pub struct Index(pub Vec<(Name, Cid)>);
 
+/// Whether a def must be shown to terminate
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Totality {
+  /// Checked to terminate, which is the default
+  Total,
+  /// Exempt from the termination check
+  Partial,
+}
+
+/// Whether conversion checking may unfold a def
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Unfolding {
+  /// Unfolded if the def is total. A partial def may not terminate, so it
+  /// is compared by reference instead.
+  Auto,
+  /// Never unfolded, so that the def is only convertible with itself
+  Opaque,
+  /// Always unfolded, even if the def is partial
+  Unfold,
+}
+
+/// The attributes a def is declared with, as in `partial opaque def`
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct Attrs {
+  pub totality: Totality,
+  pub unfolding: Unfolding,
+}
+
+impl Default for Attrs {
+  fn default() -> Self {
+    Attrs { totality: Totality::Total, unfolding: Unfolding::Auto }
+  }
+}
+
+impl Attrs {
+  pub fn is_default(&self) -> bool { *self == Attrs::default() }
+
+  pub fn is_partial(&self) -> bool { self.totality == Totality::Partial }
+
+  /// Whether conversion checking may unfold a def with these attributes
+  pub fn unfolds(&self) -> bool {
+    match self.unfolding {
+      Unfolding::Auto => self.totality == Totality::Total,
+      Unfolding::Opaque => false,
+      Unfolding::Unfold => true,
+    }
+  }
+
+  /// The keywords of the attributes that aren't the default
+  pub fn keywords(&self) -> Vec<&'static str> {
+    let mut res = vec![];
+    if self.totality == Totality::Partial {
+      res.push("partial");
+    }
+    match self.unfolding {
+      Unfolding::Auto => (),
+      Unfolding::Opaque => res.push("opaque"),
+      Unfolding::Unfold => res.push("unfold"),
+    }
+    res
+  }
+
+  /// Adds the attribute named by a keyword, returning `None` if the keyword
+  /// isn't an attribute or conflicts with one already set
+  pub fn with_keyword(self, key: &str) -> Option<Self> {
+    let total = self.totality == Totality::Total;
+    let auto = self.unfolding == Unfolding::Auto;
+    match key {
+      "total" if total => Some(self),
+      "partial" if total => Some(Attrs { totality: Totality::Partial, ..self }),
+      "opaque" if auto => Some(Attrs { unfolding: Unfolding::Opaque, ..self }),
+      "unfold" if auto => Some(Attrs { unfolding: Unfolding::Unfold, ..self }),
+      _ => None,
+    }
+  }
+
+  pub fn to_ipld(&self) -> Ipld {
+    Ipld::List(
+      self.keywords().into_iter().map(|k| Ipld::String(k.to_owned())).collect(),
+    )
+  }
+
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    let err = || IpldError::Attrs(ipld.clone());
+    match ipld {
+      Ipld::List(xs) => xs.iter().try_fold(Attrs::default(), |attrs, x| {
+        match x {
+          Ipld::String(key) if key != "total" => attrs.with_keyword(key),
+          _ => None,
+        }
+        .ok_or_else(err)
+      }),
+      _ => Err(err()),
+    }
+  }
+}
+
+/// Formats the attributes as they're written before `def`, with a trailing
+/// space unless there are none
+impl fmt::Display for Attrs {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for key in self.keywords() {
+      write!(f, "{} ", key)?;
+    }
+    Ok(())
+  }
+}
+
 /// IPLD encoding of a def
 #[derive(PartialEq, Clone, Debug)]
 pub struct Entry {
@@ -53,18 +161,25 @@ pub struct Entry {
   pub term_anon: Cid,
   pub type_meta: Meta,
   pub term_meta: Meta,
+  pub attrs: Attrs,
 }
 
 impl Entry {
-  /// Converts an Entry into an IPLD object
+  /// Converts an Entry into an IPLD object. Entries with attributes other
+  /// than the default get a trailing list of them, so the encoding of other
+  /// entries is unchanged.
   pub fn to_ipld(&self) -> Ipld {
-    Ipld::List(vec![
+    let mut xs = vec![
       self.pos.to_ipld(),
       Ipld::Link(self.type_anon),
       Ipld::Link(self.term_anon),
       self.type_meta.to_ipld(),
       self.term_meta.to_ipld(),
-    ])
+    ];
+    if !self.attrs.is_default() {
+      xs.push(self.attrs.to_ipld());
+    }
+    Ipld::List(xs)
   }
 
   /// Converts an IPLD object into an Entry
@@ -77,7 +192,13 @@ impl Entry {
           Ipld::Link(term_anon),
           type_meta,
           term_meta,
+          attrs @ ..,
         ] => {
+          let attrs = match attrs {
+            [] => Attrs::default(),
+            [attrs] => Attrs::from_ipld(attrs)?,
+            _ => return Err(IpldError::Entry(Ipld::List(xs.to_owned()))),
+          };
           let pos = Pos::from_ipld(pos)?;
           let type_meta = Meta::from_ipld(type_meta)?;
           let term_meta = Meta::from_ipld(term_meta)?;
@@ -86,7 +207,8 @@ impl Entry {
             type_anon: *type_anon,
             term_anon: *term_anon,
             type_meta,
-            term_meta
+            term_meta,
+            attrs,
             })
         }
         xs => Err(IpldError::Entry(Ipld::List(xs.to_owned()))),
@@ -106,6 +228,9 @@ impl fmt::Display for Entry {
     writeln!(f, "  {}", self.type_meta)?;
     writeln!(f, "  Term ({}):", self.term_anon)?;
     writeln!(f, "  {}", self.term_meta)?;
+    if !self.attrs.is_default() {
+      writeln!(f, "  Attributes: {}", self.attrs.keywords().join(", "))?;
+    }
     Ok(())
   }
 }
@@ -310,6 +435,16 @@ pub mod tests {
     fn arbitrary(g: &mut Gen) -> Self { arbitrary_def(g).1 }
   }
 
+  impl Arbitrary for Attrs {
+    fn arbitrary(g: &mut Gen) -> Self {
+      let totality = *g.choose(&[Totality::Total, Totality::Partial]).unwrap();
+      let unfolding = *g
+        .choose(&[Unfolding::Auto, Unfolding::Opaque, Unfolding::Unfold])
+        .unwrap();
+      Attrs { totality, unfolding }
+    }
+  }
+
   impl Arbitrary for Index {
     fn arbitrary(g: &mut Gen) -> Self {
       let vec: Vec<()> = Arbitrary::arbitrary(g);
@@ -353,6 +488,29 @@ pub mod tests {
       _ => false,
     }
   }
+  #[quickcheck]
+  fn attrs_ipld(x: Attrs) -> bool {
+    match Attrs::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[test]
+  fn test_attrs() {
+    let attrs = Attrs::default().with_keyword("partial").unwrap();
+    assert!(attrs.is_partial() && !attrs.unfolds());
+    let attrs = attrs.with_keyword("unfold").unwrap();
+    assert!(attrs.unfolds());
+    assert_eq!(format!("{}def", attrs), "partial unfold def");
+    assert_eq!(attrs.with_keyword("opaque"), None);
+    assert_eq!(attrs.with_keyword("total"), None);
+    let attrs = Attrs::default().with_keyword("total").unwrap();
+    assert!(attrs.is_default() && attrs.unfolds());
+    assert!(!attrs.with_keyword("opaque").unwrap().unfolds());
+    assert_eq!(attrs.with_keyword("inline"), None);
+  }
+
   #[quickcheck]
   fn index_ipld(x: Index) -> bool {
     match Index::from_ipld(&x.to_ipld()) {
@@ -0,0 +1,336 @@
+use crate::{
+  anon::Anon,
+  check::conv::ConvCache,
+  dag::*,
+  defs::{
+    Def,
+    Defs,
+  },
+  dll::*,
+  eval::norm_children,
+  meta::Meta,
+  package::Attrs,
+  position::Pos,
+  prim::host::HostOps,
+  term::Term,
+};
+
+use rayon::prelude::*;
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+
+use sp_std::{
+  collections::{
+    btree_map::BTreeMap,
+    btree_set::BTreeSet,
+  },
+  vec::Vec,
+};
+
+/// Independent subterms with fewer nodes than this, and no references to
+/// unfold, are reduced in place rather than on the thread pool
+pub const PAR_THRESHOLD: usize = 64;
+
+/// A term encoded as IPLD. Terms share their names through reference counts
+/// and can't cross threads, but their encodings can.
+#[derive(Clone, Debug)]
+struct Portable {
+  anon: Ipld,
+  meta: Ipld,
+}
+
+impl Portable {
+  fn new(term: &Term) -> Self {
+    let (anon, meta) = term.embed();
+    Portable { anon: anon.to_ipld(), meta: meta.to_ipld() }
+  }
+
+  fn term(&self) -> Term {
+    let anon = Anon::from_ipld(&self.anon).expect("encoded anon");
+    let meta = Meta::from_ipld(&self.meta).expect("encoded meta");
+    Term::unembed(&anon, &meta).expect("embedded term")
+  }
+}
+
+/// The defs a subterm may unfold, encoded once and shared by every job
+struct PortableDefs {
+  defs: BTreeMap<Cid, (Cid, Portable, Portable, Attrs)>,
+}
+
+impl PortableDefs {
+  fn new() -> Self { PortableDefs { defs: BTreeMap::new() } }
+
+  /// Encodes the defs reachable from `term` that aren't encoded yet,
+  /// returning the CIDs of all of those reachable
+  fn reachable(&mut self, defs: &Defs, term: &Term) -> BTreeSet<Cid> {
+    let mut seen = BTreeSet::new();
+    let mut stack = vec![];
+    refs(term, &mut stack);
+    while let Some(cid) = stack.pop() {
+      if !seen.insert(cid) {
+        continue;
+      }
+      if let Some(def) = defs.defs.get(&cid) {
+        refs(&def.term, &mut stack);
+        self.defs.entry(cid).or_insert_with(|| {
+          let typ_ = Portable::new(&def.typ_);
+          (def.ast_cid, typ_, Portable::new(&def.term), def.attrs)
+        });
+      }
+    }
+    seen
+  }
+
+  /// Decodes the given defs on the current thread
+  fn decode(&self, cids: &BTreeSet<Cid>) -> Defs {
+    let mut defs = BTreeMap::new();
+    for cid in cids {
+      if let Some((ast_cid, typ_, term, attrs)) = self.defs.get(cid) {
+        defs.insert(*cid, Def {
+          pos: Pos::None,
+          def_cid: *cid,
+          ast_cid: *ast_cid,
+          typ_: typ_.term(),
+          term: term.term(),
+          attrs: *attrs,
+        });
+      }
+    }
+    Defs {
+      defs,
+      names: BTreeMap::new(),
+      host: HostOps::new(),
+      conv: ConvCache::new(),
+    }
+  }
+}
+
+/// Collects the CIDs of the defs a term refers to
+fn refs(term: &Term, cids: &mut Vec<Cid>) {
+  match term {
+    Term::Ref(_, _, def_cid, _) => cids.push(*def_cid),
+    Term::Lam(_, _, bod)
+    | Term::Slf(_, _, bod)
+    | Term::Dat(_, bod)
+    | Term::Cse(_, bod) => refs(bod, cids),
+    Term::App(_, xs) | Term::All(_, _, _, _, xs) | Term::Ann(_, xs) => {
+      refs(&xs.0, cids);
+      refs(&xs.1, cids);
+    }
+    Term::Let(_, _, _, _, xs) => {
+      refs(&xs.0, cids);
+      refs(&xs.1, cids);
+      refs(&xs.2, cids);
+    }
+    _ => (),
+  }
+}
+
+fn binder_addr(binder: BinderPtr) -> Option<usize> {
+  match binder {
+    BinderPtr::Free => None,
+    BinderPtr::Lam(link) => Some(link.as_ptr() as usize),
+    BinderPtr::Slf(link) => Some(link.as_ptr() as usize),
+    BinderPtr::Fix(link) => Some(link.as_ptr() as usize),
+  }
+}
+
+/// Whether the sub-DAG at `root` can be reduced apart from the rest of the
+/// DAG. This holds when none of its nodes is shared with the outside and all
+/// its variables are bound within it. Returns the number of nodes and whether
+/// it has any references if so.
+fn independent(root: DAGPtr) -> Option<(usize, bool)> {
+  let mut binders = BTreeSet::new();
+  let mut stack = vec![root];
+  let mut size = 0;
+  let mut has_refs = false;
+  while let Some(node) = stack.pop() {
+    size += 1;
+    if let DAGPtr::Var(link) = node {
+      // A bound variable's parents are its occurrences, so it may have many
+      let binder = unsafe { (*link.as_ptr()).binder };
+      if !binder_addr(binder).map_or(false, |addr| binders.contains(&addr)) {
+        return None;
+      }
+      continue;
+    }
+    if DLL::iter_option(get_parents(node)).count() != 1 {
+      return None;
+    }
+    unsafe {
+      match node {
+        DAGPtr::Lam(link) => {
+          binders.insert(link.as_ptr() as usize);
+          stack.push((*link.as_ptr()).bod);
+        }
+        DAGPtr::Slf(link) => {
+          binders.insert(link.as_ptr() as usize);
+          stack.push((*link.as_ptr()).bod);
+        }
+        DAGPtr::Fix(link) => {
+          binders.insert(link.as_ptr() as usize);
+          stack.push((*link.as_ptr()).bod);
+        }
+        DAGPtr::Dat(link) => stack.push((*link.as_ptr()).bod),
+        DAGPtr::Cse(link) => stack.push((*link.as_ptr()).bod),
+        DAGPtr::App(link) => {
+          stack.push((*link.as_ptr()).fun);
+          stack.push((*link.as_ptr()).arg);
+        }
+        DAGPtr::All(link) => {
+          stack.push((*link.as_ptr()).dom);
+          stack.push(DAGPtr::Lam((*link.as_ptr()).img));
+        }
+        DAGPtr::Ann(link) => {
+          stack.push((*link.as_ptr()).typ);
+          stack.push((*link.as_ptr()).exp);
+        }
+        DAGPtr::Let(link) => {
+          stack.push((*link.as_ptr()).typ);
+          stack.push((*link.as_ptr()).exp);
+          stack.push(DAGPtr::Lam((*link.as_ptr()).bod));
+        }
+        DAGPtr::Ref(_) => has_refs = true,
+        _ => (),
+      }
+    }
+  }
+  Some((size, has_refs))
+}
+
+/// Normalizes an encoded term against encoded defs on the current thread
+fn norm_job(
+  table: &PortableDefs,
+  cids: &BTreeSet<Cid>,
+  term: &Portable,
+) -> Portable {
+  let defs = table.decode(cids);
+  let mut dag = DAG::from_term(&term.term());
+  dag.norm_par(&defs);
+  let norm = Portable::new(&dag.to_term(false));
+  dag.free();
+  norm
+}
+
+impl DAG {
+  /// Reduces a DAG to its normal form as in `norm`, reducing independent
+  /// sibling subterms in parallel on the rayon thread pool. Subterms are
+  /// independent when the DAG shares none of their nodes and binds none of
+  /// their variables outside them, so reducing them apart gives the same
+  /// normal form. Host operations aren't thread safe, so with any registered
+  /// this is the same as `norm`.
+  pub fn norm_par(&mut self, defs: &Defs) {
+    if !defs.host.ops.is_empty() {
+      return self.norm(defs, false);
+    }
+    self.whnf(defs, false);
+    let mut slots = vec![];
+    let mut trail = vec![self.head];
+    while let Some(node) = trail.pop() {
+      for child in norm_children(node) {
+        match independent(child) {
+          Some((size, has_refs)) if has_refs || size >= PAR_THRESHOLD => {
+            slots.push(child)
+          }
+          _ => {
+            let mut child = DAG::new(child);
+            child.whnf(defs, false);
+            trail.push(child.head);
+          }
+        }
+      }
+    }
+    if slots.is_empty() {
+      return;
+    }
+    let mut table = PortableDefs::new();
+    let jobs: Vec<(BTreeSet<Cid>, Portable)> = slots
+      .iter()
+      .map(|slot| {
+        let term = DAG::new(*slot).to_term(false);
+        (table.reachable(defs, &term), Portable::new(&term))
+      })
+      .collect();
+    let norms: Vec<Portable> = jobs
+      .par_iter()
+      .map(|(cids, term)| norm_job(&table, cids, term))
+      .collect();
+    for (slot, norm) in slots.into_iter().zip(norms) {
+      let parents = get_parents(slot);
+      set_parents(slot, None);
+      let node =
+        DAG::from_term_inner(&norm.term(), 0, BTreeMap::new(), parents, None);
+      free_dead_node(slot);
+      for parent in DLL::iter_option(parents) {
+        install_child(parent, node);
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::parse::{
+    package,
+    span::Span,
+    term::{
+      input_cid,
+      parse,
+    },
+  };
+
+  fn norms(input: &str, defs: &Defs) -> (String, String) {
+    let (_, term) = parse(input, defs.clone()).unwrap();
+    let mut seq = DAG::from_term(&term);
+    seq.norm(defs, false);
+    let mut par = DAG::from_term(&term);
+    par.norm_par(defs);
+    (format!("{}", seq), format!("{}", par))
+  }
+
+  #[test]
+  fn test_norm_par() {
+    let src = "def double: ∀ #U64 -> #U64 = λ x => #U64.add x x\ndef four: \
+               #U64 = double (double 1u64)";
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    let inputs = [
+      "λ f => f four (double four) ((λ x => x) Type)",
+      "λ f g => f (λ y => g y (double 3u64)) (λ z => z four)",
+      "λ f => (λ x => f x x) (double four)",
+      "#U64.add four (double four)",
+    ];
+    for input in inputs.iter() {
+      let (seq, par) = norms(input, &defs);
+      assert_eq!(seq, par, "{}", input);
+    }
+    assert_eq!(norms(inputs[0], &defs).1, "λ f => f 4u64 8u64 Type");
+  }
+
+  #[test]
+  fn test_independent() {
+    let (_, term) = parse("λ f => f (λ x => x) f", Defs::new()).unwrap();
+    let dag = DAG::from_term(&term);
+    let (fun, arg) = match dag.head {
+      DAGPtr::Lam(lam) => match unsafe { (*lam.as_ptr()).bod } {
+        DAGPtr::App(app) => unsafe {
+          ((*app.as_ptr()).fun, (*app.as_ptr()).arg)
+        },
+        _ => panic!(),
+      },
+      _ => panic!(),
+    };
+    // `f` is bound outside both `f (λ x => x)` and `f`
+    assert_eq!(independent(fun), None);
+    assert_eq!(independent(arg), None);
+    match fun {
+      DAGPtr::App(app) => {
+        let id = unsafe { (*app.as_ptr()).arg };
+        assert_eq!(independent(id), Some((2, false)));
+      }
+      _ => panic!(),
+    }
+    dag.free();
+  }
+}
@@ -8,9 +8,17 @@ pub mod string;
 pub mod term;
 pub mod typedef;
 
+use multibase::Base;
 use nom::Finish;
 use sp_cid::Cid;
 
+use sp_std::convert::TryFrom;
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
 /// Try to parse a str to a Cid
 pub fn parse_cid(
   s: &str,
@@ -18,3 +26,20 @@ pub fn parse_cid(
   let result = package::parse_link(span::Span::new(s)).finish().map(|(_, x)| x);
   result
 }
+
+/// Parses a CID in any multibase encoding, or a bare base58btc CIDv0,
+/// failing with a message suitable for users
+pub fn parse_cid_str(s: &str) -> Result<Cid, String> {
+  let s = s.trim();
+  let invalid = |why: &str| format!("Invalid CID `{}`: {}", s, why);
+  let bytes = if s.starts_with("Qm") && s.len() == 46 {
+    Base::Base58Btc.decode(s).map_err(|e| invalid(&e.to_string()))?
+  }
+  else if s.is_empty() {
+    return Err(invalid("expected a multibase encoded CID"));
+  }
+  else {
+    multibase::decode(s).map_err(|e| invalid(&e.to_string()))?.1
+  };
+  Cid::try_from(bytes).map_err(|_| invalid("the decoded bytes aren't a CID"))
+}
@@ -1,4 +1,5 @@
 use crate::{
+  elab::ElabError,
   name::Name,
   parse::{
     base,
@@ -8,6 +9,7 @@ use crate::{
     LitType,
     Literal,
   },
+  typedef::PositivityError,
 };
 
 use nom::{
@@ -21,17 +23,25 @@ use nom::{
 #[cfg(not(feature = "std"))]
 use sp_std::{
   cmp::Ordering,
+  collections::btree_set::BTreeSet,
   fmt,
   fmt::Write,
-  num::ParseIntError,
+  num::{
+    ParseFloatError,
+    ParseIntError,
+  },
   vec::Vec,
 };
 #[cfg(feature = "std")]
 use std::{
   cmp::Ordering,
+  collections::BTreeSet,
   fmt,
   fmt::Write,
-  num::ParseIntError,
+  num::{
+    ParseFloatError,
+    ParseIntError,
+  },
   vec::Vec,
 };
 
@@ -51,6 +61,8 @@ pub enum ParseErrorKind {
   MultibaseError(multibase::Error),
   CidError,
   ParseIntErr(ParseIntError),
+  U256OutOfRange(String),
+  ParseFloatErr(ParseFloatError),
   ReservedKeyword(String),
   NumericSyntax(String),
   ReservedSyntax(String),
@@ -68,13 +80,28 @@ pub enum ParseErrorKind {
   UnknownU32Op(Name),
   UnknownU64Op(Name),
   UnknownU128Op(Name),
+  UnknownU256Op(Name),
   UnknownI8Op(Name),
   UnknownI16Op(Name),
   UnknownI32Op(Name),
   UnknownI64Op(Name),
   UnknownI128Op(Name),
+  UnknownF32Op(Name),
+  UnknownF64Op(Name),
+  UnknownMapOp(Name),
+  UnknownSetOp(Name),
+  UnknownVecOp(Name),
+  UnknownEd25519Op(Name),
+  UnknownSecp256k1Op(Name),
+  UnknownCidOp(Name),
+  UnknownExceptionOp(Name),
+  UnknownRngOp(Name),
+  UnknownActionOp(Name),
   TypeDefConstructorMustReturnItsType,
   InvalidSymbol(String),
+  Elab(ElabError),
+  Positivity(PositivityError),
+  ConflictingAttribute(String),
   Nom(ErrorKind),
 }
 
@@ -105,6 +132,12 @@ impl<'a> fmt::Display for ParseErrorKind {
       Self::ParseIntErr(e) => {
         write!(f, "Error parsing number: {}", e)
       }
+      Self::U256OutOfRange(digits) => {
+        write!(f, "Error parsing number: {} is not a valid #U256", digits)
+      }
+      Self::ParseFloatErr(e) => {
+        write!(f, "Error parsing float: {}", e)
+      }
       Self::ReservedKeyword(name) => {
         write!(f, "{}` is a reserved language keyword", name)
       }
@@ -122,6 +155,11 @@ impl<'a> fmt::Display for ParseErrorKind {
           name
         )
       }
+      Self::Elab(e) => write!(f, "{}", e),
+      Self::Positivity(e) => write!(f, "{}", e),
+      Self::ConflictingAttribute(key) => {
+        write!(f, "The attribute {} repeats or contradicts an earlier one", key)
+      }
       Self::LiteralLacksWhitespaceTermination(x) => {
         write!(f, "Literal {} must be terminated by whitespace or eof", x)
       }
@@ -152,13 +190,33 @@ impl ParseErrorKind {
 #[derive(PartialEq, Debug, Clone)]
 pub struct ParseError<I: AsBytes> {
   pub input: I,
-  pub expected: Option<&'static str>,
+  /// What any of the alternatives tried at `input` would have parsed
+  pub expected: BTreeSet<&'static str>,
   pub errors: Vec<ParseErrorKind>,
 }
 
 impl<I: AsBytes> ParseError<I> {
   pub fn new(input: I, error: ParseErrorKind) -> Self {
-    ParseError { input, expected: None, errors: vec![error] }
+    ParseError { input, expected: BTreeSet::new(), errors: vec![error] }
+  }
+}
+
+/// Writes the `expected` set of a parse error, if it isn't empty
+pub fn write_expected(
+  res: &mut String,
+  expected: &BTreeSet<&'static str>,
+) -> fmt::Result {
+  let mut iter = expected.iter();
+  match (iter.next(), expected.len()) {
+    (None, _) => Ok(()),
+    (Some(exp), 1) => writeln!(res, "Expected {}", exp),
+    (Some(exp), _) => {
+      write!(res, "Expected one of {}", exp)?;
+      for exp in iter {
+        write!(res, ", {}", exp)?;
+      }
+      writeln!(res)
+    }
   }
 }
 
@@ -183,21 +241,19 @@ impl<'a> fmt::Display for ParseError<Span<'a>> {
     }
     writeln!(&mut res, "^")?;
 
-    if let Some(exp) = self.expected {
-      writeln!(&mut res, "Expected {}", exp)?;
-    }
+    write_expected(&mut res, &self.expected)?;
 
     let mut errs = self.errors.iter().filter(|x| !x.is_nom_err()).peekable();
-    if errs.peek() == None {
-      // TODO: Nom verbose mode
-      writeln!(&mut res, "Internal parser error")?;
-    }
-    else {
+    if errs.peek().is_some() {
       writeln!(&mut res, "Reported errors:")?;
       for kind in errs {
         writeln!(&mut res, "- {}", kind)?;
       }
     }
+    else if self.expected.is_empty() {
+      // TODO: Nom verbose mode
+      writeln!(&mut res, "Internal parser error")?;
+    }
 
     write!(f, "{}", res)
   }
@@ -230,6 +286,7 @@ where
         for x in self.errors {
           other.errors.push(x);
         }
+        other.expected.extend(self.expected);
         other
       }
       Ordering::Greater => other,
@@ -242,15 +299,17 @@ where
   I: InputLength,
   I: Clone,
 {
-  fn add_context(input: I, ctx: &'static str, other: Self) -> Self {
+  fn add_context(input: I, ctx: &'static str, mut other: Self) -> Self {
     match input.input_len().cmp(&other.input.input_len()) {
       Ordering::Less => {
-        ParseError { input, expected: Some(ctx), errors: vec![] }
+        let mut expected = BTreeSet::new();
+        expected.insert(ctx);
+        ParseError { input, expected, errors: vec![] }
+      }
+      Ordering::Equal => {
+        other.expected.insert(ctx);
+        other
       }
-      Ordering::Equal => match other.expected {
-        None => ParseError { input, expected: Some(ctx), errors: other.errors },
-        _ => other,
-      },
       Ordering::Greater => other,
     }
   }
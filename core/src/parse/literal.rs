@@ -6,7 +6,10 @@ use num_bigint::{
 
 use crate::{
   literal::Literal,
-  prim::bits,
+  prim::{
+    bits,
+    u256::U256,
+  },
 };
 
 use crate::parse::{
@@ -27,6 +30,7 @@ use nom::{
   combinator::{
     opt,
     peek,
+    recognize,
     value,
   },
   error::context,
@@ -34,6 +38,7 @@ use nom::{
     delimited,
     preceded,
     terminated,
+    tuple,
   },
   Err,
   IResult,
@@ -43,8 +48,14 @@ pub fn parse_nat(from: Span) -> IResult<Span, Literal, ParseError<Span>> {
   let (i, base) = opt(preceded(tag("0"), base::parse_litbase_code()))(from)?;
   let base = base.unwrap_or(base::LitBase::Dec);
   let (i, digits) = base::parse_litbase_digits(base)(i)?;
-  let (upto, suffix) =
-    opt(alt((tag("u8"), tag("u16"), tag("u32"), tag("u64"), tag("u128"))))(i)?;
+  let (upto, suffix) = opt(alt((
+    tag("u8"),
+    tag("u16"),
+    tag("u32"),
+    tag("u64"),
+    tag("u128"),
+    tag("u256"),
+  )))(i)?;
   match suffix {
     None => match base_x::decode(base.base_digits(), &digits) {
       Ok(bytes) => Ok((upto, Literal::Nat(BigUint::from_bytes_be(&bytes)))),
@@ -94,6 +105,16 @@ pub fn parse_nat(from: Span) -> IResult<Span, Literal, ParseError<Span>> {
         )?;
         Ok((upto, Literal::U128(x)))
       }
+      "u256" => {
+        use ParseErrorKind::U256OutOfRange;
+        let x = BigUint::parse_bytes(digits.as_bytes(), base.radix())
+          .and_then(|x| U256::from_biguint(&x))
+          .map_or_else(
+            || Err(Err::Error(ParseError::new(from, U256OutOfRange(digits)))),
+            Ok,
+          )?;
+        Ok((upto, Literal::U256(x)))
+      }
       _ => panic!("implementation error in parse_nat"),
     },
   }
@@ -174,6 +195,37 @@ pub fn parse_int(from: Span) -> IResult<Span, Literal, ParseError<Span>> {
   }
 }
 
+/// Parses a decimal float with an `f32` or `f64` suffix, such as `-1.5e3f64`.
+/// The sign, fraction and exponent are optional.
+pub fn parse_float(from: Span) -> IResult<Span, Literal, ParseError<Span>> {
+  let sign = || opt(alt((tag("-"), tag("+"))));
+  let (i, digits) = recognize(tuple((
+    sign(),
+    digit1,
+    opt(preceded(tag("."), digit1)),
+    opt(tuple((alt((tag("e"), tag("E"))), sign(), digit1))),
+  )))(from)?;
+  let (upto, suffix) = alt((tag("f32"), tag("f64")))(i)?;
+  use ParseErrorKind::ParseFloatErr;
+  match *suffix.fragment() {
+    "f32" => {
+      let x = digits.fragment().parse::<f32>().map_or_else(
+        |e| Err(Err::Error(ParseError::new(from, ParseFloatErr(e)))),
+        Ok,
+      )?;
+      Ok((upto, Literal::F32(x)))
+    }
+    "f64" => {
+      let x = digits.fragment().parse::<f64>().map_or_else(
+        |e| Err(Err::Error(ParseError::new(from, ParseFloatErr(e)))),
+        Ok,
+      )?;
+      Ok((upto, Literal::F64(x)))
+    }
+    _ => panic!("implementation error in parse_float"),
+  }
+}
+
 pub fn parse_text(from: Span) -> IResult<Span, Literal, ParseError<Span>> {
   let (i, _) = context("open quotes", tag("\""))(from)?;
   let (i, s) = parse_string("\"")(i)?;
@@ -265,4 +317,23 @@ pub mod tests {
     let res = parse_nat(Span::new("15"));
     assert_eq!(res.unwrap().1, Literal::Nat(15u64.into()));
   }
+
+  #[test]
+  fn test_parse_float() {
+    let res = parse_float(Span::new("1.5f64"));
+    assert_eq!(res.unwrap().1, Literal::F64(1.5));
+    let res = parse_float(Span::new("-2f32"));
+    assert_eq!(res.unwrap().1, Literal::F32(-2.0));
+    let res = parse_float(Span::new("+1.25e-3f64"));
+    assert_eq!(res.unwrap().1, Literal::F64(1.25e-3));
+    let res = parse_float(Span::new("1e20f32"));
+    assert_eq!(res.unwrap().1, Literal::F32(1e20));
+    assert!(parse_float(Span::new("15")).is_err());
+    assert!(parse_float(Span::new("+15i64")).is_err());
+    for x in &[0.1f64, -0.0, 1e-300, f64::MAX] {
+      let txt = Literal::F64(*x).to_string();
+      let res = parse_float(Span::new(&txt));
+      assert_eq!(res.unwrap().1, Literal::F64(*x));
+    }
+  }
 }
@@ -4,25 +4,44 @@ use crate::{
       ParseError,
       ParseErrorKind,
     },
+    package::parse_link,
     term::parse_name,
   },
   position::Pos,
   prim::{
+    action::ActionOp,
     bits::BitsOp,
     bool::BoolOp,
     bytes::BytesOp,
     char::CharOp,
+    cid::CidOp,
+    conv::ConvOp,
+    ed25519::Ed25519Op,
+    exception::ExceptionOp,
+    f32::F32Op,
+    f64::F64Op,
+    from_text::FromTextOp,
+    host::HostRef,
+    i128::I128Op,
     i16::I16Op,
     i32::I32Op,
     i64::I64Op,
     i8::I8Op,
     int::IntOp,
+    map::MapOp,
     nat::NatOp,
+    rng::RngOp,
+    secp256k1::Secp256k1Op,
+    set::SetOp,
+    show::ShowOp,
     text::TextOp,
+    u128::U128Op,
     u16::U16Op,
+    u256::U256Op,
     u32::U32Op,
     u64::U64Op,
     u8::U8Op,
+    vector::VecOp,
   },
   term::{
     Op,
@@ -37,6 +56,7 @@ use crate::parse::span::Span;
 use nom::{
   branch::alt,
   bytes::complete::tag,
+  error::ErrorKind,
   sequence::preceded,
   Err,
   IResult,
@@ -174,17 +194,29 @@ pub fn parse_u64_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
   }
 }
 
-// pub fn parse_u128_op() -> impl Fn(Span) -> IResult<Span, Op,
-// ParseError<Span>> {  move |from: Span| {
-//    let (i, name) = parse_name(from)?;
-//    match U128Op::from_symbol(&name) {
-//      Some(op) => Ok((i, Op::U128(op))),
-//      None => {
-//        Err(Err::Error(ParseError::new(i,
-// ParseErrorKind::UnknownU128Op(name))))      }
-//    }
-//  }
-//}
+pub fn parse_u128_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match U128Op::from_symbol(&name) {
+      Some(op) => Ok((i, Op::U128(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownU128Op(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_u256_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match U256Op::from_symbol(&name) {
+      Some(op) => Ok((i, Op::U256(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownU256Op(name))))
+      }
+    }
+  }
+}
 
 pub fn parse_i8_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
   move |from: Span| {
@@ -234,23 +266,197 @@ pub fn parse_i64_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
   }
 }
 
-// pub fn parse_i128_op() -> impl Fn(Span) -> IResult<Span, Op,
-// ParseError<Span>> {  move |from: Span| {
-//    let (i, name) = parse_name(from)?;
-//    match I128Op::from_symbol(&name) {
-//      Some(op) => Ok((i, Op::I128(op))),
-//      None => {
-//        Err(Err::Error(ParseError::new(i,
-// ParseErrorKind::UnknownI128Op(name))))      }
-//    }
-//  }
-//}
+pub fn parse_i128_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match I128Op::from_symbol(&name) {
+      Some(op) => Ok((i, Op::I128(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownI128Op(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_f32_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match F32Op::from_symbol(&name) {
+      Some(op) => Ok((i, Op::F32(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownF32Op(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_f64_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match F64Op::from_symbol(&name) {
+      Some(op) => Ok((i, Op::F64(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownF64Op(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_map_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match MapOp::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Map(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownMapOp(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_set_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match SetOp::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Set(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownSetOp(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_vec_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match VecOp::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Vector(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownVecOp(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_ed25519_op()
+-> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match Ed25519Op::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Ed25519(op))),
+      None => Err(Err::Error(ParseError::new(
+        i,
+        ParseErrorKind::UnknownEd25519Op(name),
+      ))),
+    }
+  }
+}
+
+pub fn parse_secp256k1_op()
+-> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match Secp256k1Op::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Secp256k1(op))),
+      None => Err(Err::Error(ParseError::new(
+        i,
+        ParseErrorKind::UnknownSecp256k1Op(name),
+      ))),
+    }
+  }
+}
+
+pub fn parse_cid_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match CidOp::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Cid(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownCidOp(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_exception_op()
+-> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match ExceptionOp::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Exception(op))),
+      None => Err(Err::Error(ParseError::new(
+        i,
+        ParseErrorKind::UnknownExceptionOp(name),
+      ))),
+    }
+  }
+}
+
+pub fn parse_rng_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match RngOp::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Rng(op))),
+      None => {
+        Err(Err::Error(ParseError::new(i, ParseErrorKind::UnknownRngOp(name))))
+      }
+    }
+  }
+}
+
+pub fn parse_action_op()
+-> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    let (i, name) = parse_name(from)?;
+    match ActionOp::from_symbol(&name) {
+      Some(op) => Ok((i, Op::Action(op))),
+      None => Err(Err::Error(ParseError::new(
+        i,
+        ParseErrorKind::UnknownActionOp(name),
+      ))),
+    }
+  }
+}
+
+/// Parses a reference to a host operation by its content id, which is resolved
+/// against the host operations of the defs when it is checked or evaluated
+pub fn parse_host_op() -> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>>
+{
+  move |from: Span| {
+    let (i, cid) = parse_link(from)?;
+    Ok((i, Op::Host(HostRef::unresolved(cid))))
+  }
+}
+
+/// Parses an operation generated per literal type, such as the conversion
+/// `#U16.truncating_to_U8`, `#U8.show` or `#U64.from_Text`. Since these share
+/// their prefixes with the other typed operations, a name that isn't one of
+/// them fails without consuming input
+pub fn parse_generated_op()
+-> impl Fn(Span) -> IResult<Span, Op, ParseError<Span>> {
+  move |from: Span| {
+    if let Ok((i, name)) = preceded(tag("#"), parse_name)(from) {
+      if let Some(op) = ConvOp::from_name(&name) {
+        return Ok((i, Op::Conv(op)));
+      }
+      if let Some(op) = ShowOp::from_name(&name) {
+        return Ok((i, Op::Show(op)));
+      }
+      if let Some(op) = FromTextOp::from_name(&name) {
+        return Ok((i, Op::FromText(op)));
+      }
+    }
+    Err(Err::Error(ParseError::new(from, ParseErrorKind::Nom(ErrorKind::Tag))))
+  }
+}
 
 pub fn parse_opr(
   input: Cid,
 ) -> impl Fn(Span) -> IResult<Span, Term, ParseError<Span>> {
   move |from: Span| {
+    // `alt` takes at most 21 parsers, so the fixed-width numbers are grouped
     let (upto, op) = alt((
+      parse_generated_op(),
       preceded(tag("#Nat."), parse_nat_op()),
       preceded(tag("#Int."), parse_int_op()),
       preceded(tag("#Bool."), parse_bool_op()),
@@ -258,16 +464,31 @@ pub fn parse_opr(
       preceded(tag("#Bytes."), parse_bytes_op()),
       preceded(tag("#Text."), parse_text_op()),
       preceded(tag("#Char."), parse_char_op()),
-      preceded(tag("#U8."), parse_u8_op()),
-      preceded(tag("#U16."), parse_u16_op()),
-      preceded(tag("#U32."), parse_u32_op()),
-      preceded(tag("#U64."), parse_u64_op()),
-      // preceded(tag("#U128."), parse_u128_op()),
-      preceded(tag("#I8."), parse_i8_op()),
-      preceded(tag("#I16."), parse_i16_op()),
-      preceded(tag("#I32."), parse_i32_op()),
-      preceded(tag("#I64."), parse_i64_op()),
-      // preceded(tag("#I128."), parse_i128_op()),
+      alt((
+        preceded(tag("#U8."), parse_u8_op()),
+        preceded(tag("#U16."), parse_u16_op()),
+        preceded(tag("#U32."), parse_u32_op()),
+        preceded(tag("#U64."), parse_u64_op()),
+        preceded(tag("#U128."), parse_u128_op()),
+        preceded(tag("#U256."), parse_u256_op()),
+        preceded(tag("#I8."), parse_i8_op()),
+        preceded(tag("#I16."), parse_i16_op()),
+        preceded(tag("#I32."), parse_i32_op()),
+        preceded(tag("#I64."), parse_i64_op()),
+        preceded(tag("#I128."), parse_i128_op()),
+        preceded(tag("#F32."), parse_f32_op()),
+        preceded(tag("#F64."), parse_f64_op()),
+      )),
+      preceded(tag("#Map."), parse_map_op()),
+      preceded(tag("#Set."), parse_set_op()),
+      preceded(tag("#Vector."), parse_vec_op()),
+      preceded(tag("#Ed25519."), parse_ed25519_op()),
+      preceded(tag("#Secp256k1."), parse_secp256k1_op()),
+      preceded(tag("#Cid."), parse_cid_op()),
+      preceded(tag("#Exception."), parse_exception_op()),
+      preceded(tag("#Host."), parse_host_op()),
+      preceded(tag("#Rng."), parse_rng_op()),
+      preceded(tag("#IO."), parse_action_op()),
     ))(from)?;
     let pos = Pos::from_upto(input, from, upto);
     Ok((upto, Term::Opr(pos, op)))
@@ -3,14 +3,19 @@ use crate::{
     Def,
     Defs,
   },
+  elab::elaborate,
   name::Name,
   package::{
+    Attrs,
     Entry,
     Import,
     Index,
   },
   parse::{
-    base::parse_multibase,
+    base::{
+      parse_multibase,
+      parse_multibase_digits,
+    },
     error::{
       ParseError,
       ParseErrorKind,
@@ -24,7 +29,10 @@ use crate::{
 
 use sp_std::{
   cell::RefCell,
-  collections::vec_deque::VecDeque,
+  collections::{
+    btree_set::BTreeSet,
+    vec_deque::VecDeque,
+  },
   convert::TryFrom,
   rc::Rc,
   vec::Vec,
@@ -37,16 +45,37 @@ use nom::{
     eof,
     opt,
   },
+  error::context,
   multi::separated_list0,
   sequence::terminated,
   Err,
   IResult,
+  Slice,
 };
 
+use alloc::string::ToString;
+
+use multibase::Base;
 use sp_cid::Cid;
 use sp_im::conslist::ConsList;
 
+/// Parses a CIDv0, which is a bare base58btc string starting with `Qm` rather
+/// than a multibase string
+pub fn parse_link_v0(from: Span) -> IResult<Span, Cid, ParseError<Span>> {
+  let (upto, o) = parse_multibase_digits()(from)?;
+  if !(o.fragment().starts_with("Qm") && o.fragment().len() == 46) {
+    return Err(Err::Error(ParseError::new(from, ParseErrorKind::CidError)));
+  }
+  match Base::Base58Btc.decode(o.fragment()).map(Cid::try_from) {
+    Ok(Ok(cid)) => Ok((upto, cid)),
+    _ => Err(Err::Error(ParseError::new(upto, ParseErrorKind::CidError))),
+  }
+}
+
 pub fn parse_link(from: Span) -> IResult<Span, Cid, ParseError<Span>> {
+  if let Ok(res) = parse_link_v0(from) {
+    return Ok(res);
+  }
   let (upto, (_, bytes)) = parse_multibase()(from)?;
   match Cid::try_from(bytes) {
     Ok(cid) => Ok((upto, cid)),
@@ -83,12 +112,38 @@ pub fn parse_import(i: Span) -> IResult<Span, Import, ParseError<Span>> {
   Ok((i, Import { cid: from, name, alias, with }))
 }
 
+/// Parses the attributes written before `def`, such as the `partial opaque`
+/// of `partial opaque def`
+pub fn parse_attrs(from: Span) -> IResult<Span, Attrs, ParseError<Span>> {
+  let mut attrs = Attrs::default();
+  let mut i = from;
+  loop {
+    let key = alt((tag("total"), tag("partial"), tag("opaque"), tag("unfold")));
+    let res: IResult<Span, Span, ParseError<Span>> =
+      terminated(key, parse_space1)(i);
+    match res {
+      Ok((upto, key)) => {
+        attrs = attrs.with_keyword(key.fragment()).ok_or_else(|| {
+          let key = key.fragment().to_string();
+          Err::Failure(ParseError::new(
+            i,
+            ParseErrorKind::ConflictingAttribute(key),
+          ))
+        })?;
+        i = upto;
+      }
+      Err(_) => return Ok((i, attrs)),
+    }
+  }
+}
+
 pub fn parse_entry(
   input: Cid,
   defs: Rc<RefCell<Defs>>,
 ) -> impl Fn(Span) -> IResult<Span, Vec<(Name, Def, Entry)>, ParseError<Span>> {
   move |from: Span| {
-    let (i, _) = tag("def")(from)?;
+    let (i, attrs) = parse_attrs(from)?;
+    let (i, _) = tag("def")(i)?;
     let (i, _) = parse_space(i)?;
     let (i, nam) = parse_name(i)?;
     if defs.borrow().names.get(&nam.clone()).is_some() {
@@ -109,8 +164,11 @@ pub fn parse_entry(
         nam.clone(),
         false,
       )(i)?;
+      let term = elaborate(&defs.borrow(), &typ_, term).map_err(|e| {
+        Err::Failure(ParseError::new(from, ParseErrorKind::Elab(e)))
+      })?;
       let pos = Pos::from_upto(input, from, upto);
-      let (def, entry) = Def::make(pos, typ_, term);
+      let (def, entry) = Def::make_with(pos, typ_, term, attrs);
       Ok((upto, vec![(nam, def, entry)]))
     }
   }
@@ -132,10 +190,7 @@ pub fn parse_defs(
         return Ok((i2, (defs.as_ref().clone().into_inner(), Index(ind))));
       }
       else {
-        let (i2, entries) = alt((
-          parse_entry(input, defs.clone()),
-          parse_typedef_elaborated(input, defs.clone()),
-        ))(i)?;
+        let (i2, entries) = parse_declaration(input, defs.clone())(i)?;
         for (name, def, _) in entries {
           ind.push((name.clone(), def.def_cid));
           defs.borrow_mut().insert(name, def);
@@ -146,6 +201,104 @@ pub fn parse_defs(
   }
 }
 
+/// Parses a def or a type declaration, with the defs it makes
+pub fn parse_declaration(
+  input: Cid,
+  defs: Rc<RefCell<Defs>>,
+) -> impl Fn(Span) -> IResult<Span, Vec<(Name, Def, Entry)>, ParseError<Span>> {
+  move |i: Span| {
+    context(
+      "def or type declaration",
+      alt((
+        parse_entry(input, defs.clone()),
+        parse_typedef_elaborated(input, defs.clone()),
+      )),
+    )(i)
+  }
+}
+
+/// Parses defs like `parse_defs`, but rather than stopping at the first def
+/// that fails to parse, skips to the next line starting a declaration and
+/// goes on from there. Returns the defs that parsed along with the error of
+/// every one that didn't, in order. Errors that only follow from an earlier
+/// one, as references to a def that failed, aren't reported.
+pub fn parse_defs_recovering(
+  input: Cid,
+  import_defs: Defs,
+) -> impl Fn(Span) -> (Span, (Defs, Index), Vec<ParseError<Span>>) {
+  move |i: Span| {
+    let defs = Rc::new(RefCell::new(import_defs.clone()));
+    let mut ind: Vec<(Name, Cid)> = Vec::new();
+    let mut errs = Vec::new();
+    let mut failed = BTreeSet::new();
+    let mut i = i;
+    loop {
+      if let Ok((i2, _)) = parse_space(i) {
+        i = i2;
+      }
+      let end: IResult<Span, Span, ParseError<Span>> = eof(i);
+      if end.is_ok() {
+        break;
+      }
+      match parse_declaration(input, defs.clone())(i) {
+        Ok((i2, entries)) => {
+          for (name, def, _) in entries {
+            ind.push((name.clone(), def.def_cid));
+            defs.borrow_mut().insert(name, def);
+          }
+          i = i2;
+        }
+        Err(Err::Error(e)) | Err(Err::Failure(e)) => {
+          if !follows_from(&e, &failed) {
+            errs.push(e);
+          }
+          if let Some(nam) = declared_name(i) {
+            failed.insert(nam);
+          }
+          i = skip_declaration(i);
+        }
+        Err(Err::Incomplete(_)) => break,
+      }
+    }
+    (i, (defs.as_ref().clone().into_inner(), Index(ind)), errs)
+  }
+}
+
+/// The name of the def or type declared at `i`, if that much parses
+fn declared_name(i: Span) -> Option<Name> {
+  let (i, _) = parse_attrs(i).ok()?;
+  let res: IResult<Span, Span, ParseError<Span>> =
+    terminated(alt((tag("def"), tag("type"))), parse_space1)(i);
+  let (i, _) = res.ok()?;
+  parse_name(i).ok().map(|(_, nam)| nam)
+}
+
+/// Whether a parse error is a reference to a def that failed to parse, or
+/// to a constructor of a type that did
+fn follows_from(err: &ParseError<Span>, failed: &BTreeSet<Name>) -> bool {
+  err.errors.iter().any(|kind| match kind {
+    ParseErrorKind::UndefinedReference(nam, _) => {
+      failed.iter().any(|f| nam == f || nam.starts_with(&format!("{}.", f)))
+    }
+    _ => false,
+  })
+}
+
+/// Skips to the start of the next line beginning a def or a type
+/// declaration, or to the end of the input
+fn skip_declaration(i: Span) -> Span {
+  let keywords = ["def", "type", "total", "partial", "opaque", "unfold"];
+  let txt: &str = i.fragment();
+  let next = txt.match_indices('\n').map(|(n, _)| n + 1).find(|n| {
+    keywords.iter().any(|kw| {
+      txt[*n..]
+        .strip_prefix(kw)
+        .map_or(false, |rest| rest.starts_with(char::is_whitespace))
+    })
+  });
+  i.slice(next.unwrap_or_else(|| txt.len())..)
+}
+
 // #[cfg(test)]
 // pub mod tests {
 //  use super::*;
@@ -166,3 +319,31 @@ pub fn parse_defs(
 //    assert!(res.is_ok());
 //  }
 // }
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+
+  #[test]
+  fn test_parse_defs_recovering() {
+    let src = "def one: #U64 = 1u64
+def bad: #U64 = (1u64
+def two: #U64 = #U64.add one one
+def worse #U64 = 2u64
+def uses_bad: #U64 = bad
+def three: #U64 = 3u64";
+    let (_, (defs, index), errs) =
+      parse_defs_recovering(input_cid(src), Defs::new())(Span::new(src));
+    // `uses_bad` only fails because `bad` did
+    assert_eq!(errs.len(), 2);
+    assert_eq!(errs[0].input.location_line(), 3);
+    assert!(errs[0]
+      .expected
+      .contains("close parenthesis ')' of an expression"));
+    assert_eq!(errs[1].input.location_line(), 4);
+    let names: Vec<String> =
+      index.0.iter().map(|(n, _)| n.to_string()).collect();
+    assert_eq!(names, vec!["one", "two", "three"]);
+    assert!(defs.get(&Name::from("bad")).is_none());
+  }
+}
@@ -12,6 +12,7 @@ use crate::{
   },
   position::Pos,
   term::{
+    Level,
     LitType,
     Term,
     Uses,
@@ -51,6 +52,7 @@ use nom::{
     tag,
     take_till,
     take_till1,
+    take_while,
   },
   character::complete::{
     digit1,
@@ -63,6 +65,7 @@ use nom::{
     map,
     opt,
     peek,
+    recognize,
     success,
     value,
   },
@@ -105,6 +108,10 @@ pub fn reserved_symbols() -> VecDeque<String> {
     String::from("type"),
     String::from("data"),
     String::from("def"),
+    String::from("total"),
+    String::from("partial"),
+    String::from("opaque"),
+    String::from("unfold"),
     String::from("open"),
     String::from("case"),
     String::from("Type"),
@@ -295,7 +302,7 @@ pub fn parse_lam(
     let (i, _) = parse_space(i)?;
     let (i, ns) = separated_list1(multispace1, parse_name)(i)?;
     let (i, _) = parse_space(i)?;
-    let (i, _) = tag("=>")(i)?;
+    let (i, _) = context("'=>' after the variables of a λ", tag("=>"))(i)?;
     let (i, _) = parse_space(i)?;
     let mut ctx2 = ctx.clone();
     for n in ns.clone().into_iter() {
@@ -340,13 +347,37 @@ pub fn parse_binder_full(
   ctx: Ctx,
   quasi: Rc<VecDeque<Term>>,
   uses: Uses,
+) -> impl Fn(Span) -> IResult<Span, Vec<(Uses, Name, Term)>, ParseError<Span>> {
+  parse_binder_group(input, defs, rec, ctx, quasi, uses, ("(", ")"))
+}
+
+/// Parse an implicit binder, which is a full binder in braces
+pub fn parse_binder_implicit(
+  input: Cid,
+  defs: Rc<RefCell<Defs>>,
+  rec: Option<Name>,
+  ctx: Ctx,
+  quasi: Rc<VecDeque<Term>>,
+  uses: Uses,
+) -> impl Fn(Span) -> IResult<Span, Vec<(Uses, Name, Term)>, ParseError<Span>> {
+  parse_binder_group(input, defs, rec, ctx, quasi, uses, ("{", "}"))
+}
+
+fn parse_binder_group(
+  input: Cid,
+  defs: Rc<RefCell<Defs>>,
+  rec: Option<Name>,
+  ctx: Ctx,
+  quasi: Rc<VecDeque<Term>>,
+  uses: Uses,
+  (open, close): (&'static str, &'static str),
 ) -> impl Fn(Span) -> IResult<Span, Vec<(Uses, Name, Term)>, ParseError<Span>> {
   move |i: Span| {
-    let (i, _) = tag("(")(i)?;
+    let (i, _) = context("binder", tag(open))(i)?;
     let (i, _) = parse_space(i)?;
     let (i, u) = parse_uses(uses)(i)?;
     let (i, ns) = many1(terminated(parse_name, parse_space))(i)?;
-    let (i, _) = tag(":")(i)?;
+    let (i, _) = context("':' after the names of a binder", tag(":"))(i)?;
     let (i, _) = parse_space(i)?;
     let (i, typ) = parse_expression(
       input,
@@ -355,7 +386,7 @@ pub fn parse_binder_full(
       ctx.clone(),
       quasi.to_owned(),
     )(i)?;
-    let (i, _) = tag(")")(i)?;
+    let (i, _) = context("closing bracket of a binder", tag(close))(i)?;
     let mut res = Vec::new();
     for (i, n) in ns.iter().enumerate() {
       res.push((u, n.to_owned(), typ.clone().shift(i as i64, Some(0))))
@@ -479,6 +510,45 @@ pub fn parse_binders(
   }
 }
 
+/// Parse zero or more implicit binders, which come before any explicit ones
+pub fn parse_implicits(
+  input: Cid,
+  defs: Rc<RefCell<Defs>>,
+  rec: Option<Name>,
+  ctx: Ctx,
+  quasi: Rc<VecDeque<Term>>,
+  uses: Uses,
+) -> impl Fn(Span) -> IResult<Span, Vec<(Uses, Name, Term)>, ParseError<Span>> {
+  move |mut i: Span| {
+    let mut ctx = ctx.clone();
+    let mut res = Vec::new();
+    loop {
+      match preceded(
+        parse_space,
+        parse_binder_implicit(
+          input,
+          defs.to_owned(),
+          rec.clone(),
+          ctx.clone(),
+          quasi.to_owned(),
+          uses,
+        ),
+      )(i)
+      {
+        Err(Err::Error(_)) => return Ok((i, res)),
+        Err(e) => return Err(e),
+        Ok((i2, bs)) => {
+          for (u, n, t) in bs {
+            ctx = ctx.cons(n.to_owned());
+            res.push((u, n, t));
+          }
+          i = i2;
+        }
+      }
+    }
+  }
+}
+
 /// Parse one or more binders
 pub fn parse_binders1(
   input: Cid,
@@ -540,19 +610,46 @@ pub fn parse_all(
   move |from: Span| {
     let (i, _) = alt((tag("∀"), tag("forall")))(from)?;
     let (i, _) = parse_space(i)?;
-    let (i, bs) = parse_binders1(
+    let (i, imps) = parse_implicits(
       input,
       defs.clone(),
       rec.clone(),
       ctx.clone(),
       quasi.clone(),
-      true,
-      vec!['-'],
       Uses::Many,
     )(i)?;
-    let (i, _) = tag("->")(i)?;
+    let mut ctx1 = ctx.clone();
+    for (_, n, _) in imps.iter() {
+      ctx1 = ctx1.cons(n.clone());
+    }
+    // With implicit binders the explicit ones may be left out
+    let (i, bs) = if imps.is_empty() {
+      parse_binders1(
+        input,
+        defs.clone(),
+        rec.clone(),
+        ctx1.clone(),
+        quasi.clone(),
+        true,
+        vec!['-'],
+        Uses::Many,
+      )(i)?
+    }
+    else {
+      parse_binders(
+        input,
+        defs.clone(),
+        rec.clone(),
+        ctx1.clone(),
+        quasi.clone(),
+        true,
+        vec!['-'],
+        Uses::Many,
+      )(i)?
+    };
+    let (i, _) = context("'->' after the binders of a ∀", tag("->"))(i)?;
     let (i, _) = parse_space(i)?;
-    let mut ctx2 = ctx.clone();
+    let mut ctx2 = ctx1;
     for (_, n, _) in bs.iter() {
       ctx2 = ctx2.cons(n.clone());
     }
@@ -564,22 +661,73 @@ pub fn parse_all(
       quasi.to_owned(),
     )(i)?;
     let pos = Pos::from_upto(input, from, upto);
-    let trm = bs
-      .into_iter()
-      .rev()
-      .fold(bod, |acc, (u, n, t)| Term::All(pos, u, n, Box::new((t, acc))));
+    let trm = alls(pos, imps, bs, bod);
     Ok((upto, trm))
   }
 }
 
-/// Parses a Typ (type of types) term
+/// Folds implicit and then explicit binders into a telescope of foralls
+fn alls(
+  pos: Pos,
+  imps: Vec<(Uses, Name, Term)>,
+  bs: Vec<(Uses, Name, Term)>,
+  img: Term,
+) -> Term {
+  let imps = imps.into_iter().map(|b| (true, b));
+  let bs = bs.into_iter().map(|b| (false, b));
+  imps.chain(bs).rev().fold(img, |acc, (i, (u, n, t))| {
+    Term::All(pos, i, u, n, Box::new((t, acc)))
+  })
+}
+
+fn parse_level_nat(from: Span) -> IResult<Span, u64, ParseError<Span>> {
+  let (upto, ds) = digit1(from)?;
+  match ds.fragment().parse::<u64>() {
+    Ok(n) => Ok((upto, n)),
+    Err(e) => {
+      Err(Err::Error(ParseError::new(from, ParseErrorKind::ParseIntErr(e))))
+    }
+  }
+}
+
+/// Parses one of the levels a universe level is the maximum of, such as `2`,
+/// `u` or `u+1`
+pub fn parse_level_part(from: Span) -> IResult<Span, Level, ParseError<Span>> {
+  if let Ok((upto, n)) = parse_level_nat(from) {
+    return Ok((upto, Level::lit(n)));
+  }
+  let (i, nam) = recognize(preceded(
+    satisfy(char::is_alphabetic),
+    take_while(|c: char| c.is_alphanumeric() || c == '_'),
+  ))(from)?;
+  let (upto, k) = opt(preceded(tag("+"), parse_level_nat))(i)?;
+  let nam = Name::from(nam.fragment().to_string());
+  Ok((upto, Level::var(nam).raise(k.unwrap_or(0))))
+}
+
+/// Parses a universe level between braces, such as the `{u+1, 2}` of
+/// `Type{u+1, 2}`, which is the maximum of `u+1` and `2`
+pub fn parse_level(from: Span) -> IResult<Span, Level, ParseError<Span>> {
+  let (i, _) = tag("{")(from)?;
+  let (i, _) = parse_space(i)?;
+  let (i, parts) = separated_list1(
+    terminated(tag(","), parse_space),
+    terminated(parse_level_part, parse_space),
+  )(i)?;
+  let (upto, _) = tag("}")(i)?;
+  Ok((upto, parts.iter().fold(Level::zero(), |acc, l| acc.join(l))))
+}
+
+/// Parses a Typ (type of types) term, with an optional universe level
+/// written right after it, as in `Type{1}`
 pub fn parse_type(
   input: Cid,
 ) -> impl Fn(Span) -> IResult<Span, Term, ParseError<Span>> {
   move |from: Span| {
-    let (upto, _) = tag("Type")(from)?;
+    let (i, _) = tag("Type")(from)?;
+    let (upto, lvl) = opt(parse_level)(i)?;
     let pos = Pos::from_upto(input, from, upto);
-    Ok((upto, Term::Typ(pos)))
+    Ok((upto, Term::Typ(pos, lvl.unwrap_or_else(Level::zero))))
   }
 }
 
@@ -658,7 +806,8 @@ pub fn parse_data(
 /// The input `(A: Type) (x: A) : A = x` returns:
 ///   - type: `∀ (A: Type) (x: A) -> A`
 ///   - term: `λ A x => x`
-/// This is useful for parsing lets and defs
+/// This is useful for parsing lets and defs. Implicit binders, as in
+/// `{A: Type} (x: A) : A = x`, are bound by lambdas like explicit ones.
 pub fn parse_bound_expression(
   input: Cid,
   defs: Rc<RefCell<Defs>>,
@@ -670,20 +819,32 @@ pub fn parse_bound_expression(
   letrec: bool,
 ) -> impl Fn(Span) -> IResult<Span, (Term, Term), ParseError<Span>> {
   move |from: Span| {
-    let (i, bs) = parse_binders(
+    let (i, imps) = parse_implicits(
       input,
       defs.clone(),
       type_rec.clone(),
       ctx.clone(),
       quasi.clone(),
+      Uses::Many,
+    )(from)?;
+    let mut bind_ctx = ctx.clone();
+    for (_, n, _) in imps.iter() {
+      bind_ctx = bind_ctx.cons(n.clone());
+    }
+    let (i, bs) = parse_binders(
+      input,
+      defs.clone(),
+      type_rec.clone(),
+      bind_ctx,
+      quasi.clone(),
       false,
       vec![':'],
       Uses::Many,
-    )(from)?;
-    let (i, _) = tag(":")(i)?;
+    )(i)?;
+    let (i, _) = context("':' before the type", tag(":"))(i)?;
     let (i, _) = parse_space(i)?;
     let mut type_ctx = ctx.clone();
-    for (_, n, _) in bs.iter() {
+    for (_, n, _) in imps.iter().chain(bs.iter()) {
       type_ctx = type_ctx.cons(n.clone());
     }
     let (i, typ) = parse_expression(
@@ -697,11 +858,11 @@ pub fn parse_bound_expression(
     if letrec {
       term_ctx = term_ctx.cons(nam.clone());
     };
-    for (_, n, _) in bs.iter() {
+    for (_, n, _) in imps.iter().chain(bs.iter()) {
       term_ctx = term_ctx.cons(n.clone());
     }
     let (i, _) = parse_space(i)?;
-    let (i, _) = tag("=")(i)?;
+    let (i, _) = context("'=' before the value", tag("="))(i)?;
     let (i, _) = parse_space(i)?;
     let (upto, trm) = parse_expression(
       input,
@@ -711,14 +872,12 @@ pub fn parse_bound_expression(
       quasi.clone(),
     )(i)?;
     let pos = Pos::from_upto(input, from, upto);
-    let trm = bs
+    let trm = imps
       .iter()
+      .chain(bs.iter())
       .rev()
       .fold(trm, |acc, (_, n, _)| Term::Lam(pos, n.clone(), Box::new(acc)));
-    let typ = bs
-      .into_iter()
-      .rev()
-      .fold(typ, |acc, (u, n, t)| Term::All(pos, u, n, Box::new((t, acc))));
+    let typ = alls(pos, imps, bs, typ);
     Ok((upto, (typ, trm)))
   }
 }
@@ -739,17 +898,37 @@ pub fn parse_let(
     let (i, _) = parse_space(i)?;
     let (i, nam) = parse_name(i)?;
     let (i, _) = parse_space(i)?;
-    let (i, (typ, exp)) = parse_bound_expression(
-      input,
-      defs.clone(),
-      rec.clone(),
-      rec.clone(),
-      ctx.clone(),
-      quasi.clone(),
-      nam.clone(),
-      letrec,
-    )(i)?;
-    let (i, _) = alt((tag(";"), tag("in")))(i)?;
+    let untyped: IResult<Span, Span, ParseError<Span>> = tag("=")(i);
+    let (i, (typ, exp)) = match untyped {
+      // The type of a plain let can be left out, making it a redex whose
+      // variable takes the inferred type of the expression
+      Ok((i, _)) if !letrec && uses == Uses::Many => {
+        let (i, _) = parse_space(i)?;
+        let (i, exp) = parse_expression(
+          input,
+          defs.clone(),
+          rec.clone(),
+          ctx.clone(),
+          quasi.clone(),
+        )(i)?;
+        (i, (None, exp))
+      }
+      _ => {
+        let (i, (typ, exp)) = parse_bound_expression(
+          input,
+          defs.clone(),
+          rec.clone(),
+          rec.clone(),
+          ctx.clone(),
+          quasi.clone(),
+          nam.clone(),
+          letrec,
+        )(i)?;
+        (i, (Some(typ), exp))
+      }
+    };
+    let (i, _) =
+      context("';' or 'in' after a let", alt((tag(";"), tag("in"))))(i)?;
     let (i, _) = parse_space(i)?;
     let mut ctx2 = ctx.clone();
     ctx2 = ctx2.cons(nam.clone());
@@ -761,7 +940,15 @@ pub fn parse_let(
       quasi.to_owned(),
     )(i)?;
     let pos = Pos::from_upto(input, from, upto);
-    Ok((upto, Term::Let(pos, letrec, uses, nam, Box::new((typ, exp, bod)))))
+    match typ {
+      Some(typ) => {
+        Ok((upto, Term::Let(pos, letrec, uses, nam, Box::new((typ, exp, bod)))))
+      }
+      None => {
+        let lam = Term::Lam(pos, nam, Box::new(bod));
+        Ok((upto, Term::App(pos, Box::new((lam, exp)))))
+      }
+    }
   }
 }
 
@@ -796,16 +983,28 @@ pub fn parse_lty(
       value(LitType::Bool, tag("#Bool")),
       value(LitType::Text, tag("#Text")),
       value(LitType::Char, tag("#Char")),
-      value(LitType::U8, tag("#U8")),
-      value(LitType::U16, tag("#U16")),
-      value(LitType::U32, tag("#U32")),
-      value(LitType::U64, tag("#U64")),
-      value(LitType::U128, tag("#U128")),
-      value(LitType::I8, tag("#I8")),
-      value(LitType::I16, tag("#I16")),
-      value(LitType::I32, tag("#I32")),
-      value(LitType::I64, tag("#I64")),
-      value(LitType::I128, tag("#I128")),
+      alt((
+        value(LitType::U8, tag("#U8")),
+        value(LitType::U16, tag("#U16")),
+        value(LitType::U32, tag("#U32")),
+        value(LitType::U64, tag("#U64")),
+        value(LitType::U128, tag("#U128")),
+        value(LitType::U256, tag("#U256")),
+        value(LitType::I8, tag("#I8")),
+        value(LitType::I16, tag("#I16")),
+        value(LitType::I32, tag("#I32")),
+        value(LitType::I64, tag("#I64")),
+        value(LitType::I128, tag("#I128")),
+        value(LitType::F32, tag("#F32")),
+        value(LitType::F64, tag("#F64")),
+      )),
+      value(LitType::Map, tag("#Map")),
+      value(LitType::Set, tag("#Set")),
+      value(LitType::Vector, tag("#Vector")),
+      value(LitType::Cid, tag("#Cid")),
+      value(LitType::Exception, tag("#Exception")),
+      value(LitType::Rng, tag("#Rng")),
+      value(LitType::IO, tag("#IO")),
     ))(from)?;
     let (upto, _) = throw_err(parse_builtin_symbol_end()(i), |_| {
       ParseError::new(
@@ -840,6 +1039,7 @@ pub fn parse_lit(
       parse_bool,
       parse_text,
       parse_char,
+      parse_float,
       parse_int,
       parse_nat,
     ))(from)?;
@@ -1159,6 +1359,7 @@ pub mod tests {
       Name::from("a"),
       Term::All(
         Pos::None,
+        false,
         Uses::Many,
         Name::from("x"),
         Box::new((
@@ -1232,6 +1433,21 @@ pub mod tests {
     assert!(res.is_ok());
   }
 
+  #[test]
+  fn test_parse_type() {
+    fn test(i: &str) -> String {
+      let (_, term) = parse_type(input_cid(i))(Span::new(i)).unwrap();
+      format!("{}", term)
+    }
+    assert_eq!(test("Type"), "Type");
+    assert_eq!(test("Type{0}"), "Type");
+    assert_eq!(test("Type{2}"), "Type{2}");
+    assert_eq!(test("Type{ 2, u+1 }"), "Type{u+1, 2}");
+    // Constants at most some variable's offset are absorbed
+    assert_eq!(test("Type{u+1, 1, v}"), "Type{u+1, v}");
+    assert!(parse_level(Span::new("{}")).is_err());
+  }
+
   #[test]
   fn test_parse_let() {
     fn test(i: &str) -> IResult<Span, Term, ParseError<Span>> {
@@ -1332,7 +1548,7 @@ pub mod tests {
     assert!(
       res.unwrap().1
         == vec![
-          (Uses::Many, Name::from("_"), Typ(Pos::None)),
+          (Uses::Many, Name::from("_"), Typ(Pos::None, Level::zero())),
           (Uses::Many, Name::from("_"), LTy(Pos::None, LitType::Text)),
         ]
     );
@@ -1363,8 +1579,8 @@ pub mod tests {
     assert!(
       res.unwrap().1
         == vec![
-          (Uses::Many, Name::from("_"), Typ(Pos::None)),
-          (Uses::Many, Name::from("_"), Typ(Pos::None)),
+          (Uses::Many, Name::from("_"), Typ(Pos::None, Level::zero())),
+          (Uses::Many, Name::from("_"), Typ(Pos::None, Level::zero())),
         ]
     );
     let res = test(true, "(A: Type) (a b c: A):");
@@ -1372,7 +1588,7 @@ pub mod tests {
     assert!(
       res.unwrap().1
         == vec![
-          (Uses::Many, Name::from("A"), Typ(Pos::None)),
+          (Uses::Many, Name::from("A"), Typ(Pos::None, Level::zero())),
           (Uses::Many, Name::from("a"), Var(Pos::None, Name::from("A"), 0)),
           (Uses::Many, Name::from("b"), Var(Pos::None, Name::from("A"), 1)),
           (Uses::Many, Name::from("c"), Var(Pos::None, Name::from("A"), 2)),
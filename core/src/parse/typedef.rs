@@ -329,6 +329,9 @@ pub fn parse_typedef_elaborated(
 ) -> impl Fn(Span) -> IResult<Span, Vec<(Name, Def, Entry)>, ParseError<Span>> {
   move |from: Span| {
     let (i, typedef) = parse_typedef(input, defs.clone())(from)?;
+    typedef.check_positivity().map_err(|e| {
+      Err::Failure(ParseError::new(from, ParseErrorKind::Positivity(e)))
+    })?;
     let mut res: Vec<(Name, Def, Entry)> = vec![];
     res.push(typedef.type_def());
     res.append(&mut typedef.constructors());
@@ -1118,4 +1121,40 @@ pub mod tests {
     let res_no_sigs = res_no_sigs.unwrap().1;
     assert_eq!(res, res_no_sigs);
   }
+  #[test]
+  fn typedef_positivity() {
+    let positive = [
+      "type Nat { Z, S (x: Nat) }",
+      "type List (A: Type) { Nil, Cons (x: A) (xs: List A) }",
+      "type Tree (A: Type) { Leaf (x: A), Node (f: ∀ #Nat -> Tree A) }",
+      "type Vector (A: Type): ∀ (k: #Nat) -> Type { Nil: Vector A 0, Cons \
+       (0 k: #Nat) (x: A) (xs: Vector A k): Vector A (#Nat.suc k) }",
+    ];
+    for i in positive.iter() {
+      let res = test_parse(i).unwrap().1;
+      assert_eq!(res.check_positivity(), Ok(()), "{}", i);
+    }
+    let res = test_parse("type Bad { Mk (f: ∀ (b: Bad) -> #Bool) }").unwrap().1;
+    assert_eq!(
+      res.check_positivity(),
+      Err(PositivityError::Negative(
+        Name::from("Bad"),
+        Name::from("Mk"),
+        Name::from("f"),
+        String::from("∀ (b: Bad) -> #Bool")
+      ))
+    );
+    let res =
+      test_parse("type Bad { Mk (f: ∀ (∀ Bad -> #Bool) -> Bad) }").unwrap().1;
+    assert!(matches!(
+      res.check_positivity(),
+      Err(PositivityError::Negative(..))
+    ));
+    // Whether `F` uses its argument positively isn't known
+    let res =
+      test_parse("type Wrap (F: ∀ Type -> Type) { Mk (x: F (Wrap F)) }")
+        .unwrap()
+        .1;
+    assert!(matches!(res.check_positivity(), Err(PositivityError::Nested(..))));
+  }
 }
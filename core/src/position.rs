@@ -1,4 +1,5 @@
 use crate::{
+  ipld::IntoIpld,
   ipld_error::IpldError,
   parse::span::Span,
 };
@@ -68,13 +69,13 @@ impl Position {
   /// Converts a position into an IPLD object
   pub fn to_ipld(self) -> Ipld {
     Ipld::List(vec![
-      Ipld::Link(self.input),
-      Ipld::Integer(self.from_offset as i128),
-      Ipld::Integer(self.from_line as i128),
-      Ipld::Integer(self.from_column as i128),
-      Ipld::Integer(self.upto_offset as i128),
-      Ipld::Integer(self.upto_line as i128),
-      Ipld::Integer(self.upto_column as i128),
+      self.input.into_ipld(),
+      self.from_offset.into_ipld(),
+      self.from_line.into_ipld(),
+      self.from_column.into_ipld(),
+      self.upto_offset.into_ipld(),
+      self.upto_line.into_ipld(),
+      self.upto_column.into_ipld(),
     ])
   }
 
@@ -1,26 +1,45 @@
+pub mod action;
 pub mod bits;
 pub mod bool;
 pub mod bytes;
 pub mod char;
-// pub mod i128;
+pub mod cid;
+pub mod conv;
+pub mod cost;
+pub mod ed25519;
+pub mod exception;
+pub mod f32;
+pub mod f64;
+pub mod from_text;
+pub mod host;
+pub mod i128;
 pub mod i16;
 pub mod i32;
 pub mod i64;
 pub mod i8;
 pub mod int;
+pub mod map;
 pub mod nat;
+pub mod rng;
+pub mod secp256k1;
+pub mod set;
+pub mod show;
 pub mod text;
-// pub mod u128;
+pub mod u128;
 pub mod u16;
+pub mod u256;
 pub mod u32;
 pub mod u64;
 pub mod u8;
+pub mod vector;
 
 pub mod io;
 
 use sp_std::{
   borrow::ToOwned,
+  boxed::Box,
   fmt,
+  vec::Vec,
 };
 
 use alloc::string::String;
@@ -28,28 +47,51 @@ use alloc::string::String;
 use sp_ipld::Ipld;
 
 use crate::{
+  defs,
   ipld_error::IpldError,
   literal::Literal,
+  name::Name,
+  parse,
+  position::Pos,
   term::Term,
+  yatima,
 };
 
 use crate::prim::{
+  action::ActionOp,
   bits::BitsOp,
   bool::BoolOp,
   bytes::BytesOp,
   char::CharOp,
+  cid::CidOp,
+  conv::ConvOp,
+  ed25519::Ed25519Op,
+  exception::ExceptionOp,
+  f32::F32Op,
+  f64::F64Op,
+  from_text::FromTextOp,
+  host::HostRef,
+  i128::I128Op,
   i16::I16Op,
   i32::I32Op,
   i64::I64Op,
   i8::I8Op,
   int::IntOp,
   io::IoOp,
+  map::MapOp,
   nat::NatOp,
+  rng::RngOp,
+  secp256k1::Secp256k1Op,
+  set::SetOp,
+  show::ShowOp,
   text::TextOp,
+  u128::U128Op,
   u16::U16Op,
+  u256::U256Op,
   u32::U32Op,
   u64::U64Op,
   u8::U8Op,
+  vector::VecOp,
 };
 
 /// Primitive types and their operations
@@ -67,12 +109,28 @@ pub enum Op {
   U16(U16Op),
   U32(U32Op),
   U64(U64Op),
-  // U128(U128Op),
+  U128(U128Op),
+  U256(U256Op),
   I8(I8Op),
   I16(I16Op),
   I32(I32Op),
   I64(I64Op),
-  // I128(I128Op),
+  I128(I128Op),
+  F32(F32Op),
+  F64(F64Op),
+  Map(MapOp),
+  Set(SetOp),
+  Vector(VecOp),
+  Ed25519(Ed25519Op),
+  Secp256k1(Secp256k1Op),
+  Cid(CidOp),
+  Conv(ConvOp),
+  Show(ShowOp),
+  FromText(FromTextOp),
+  Exception(ExceptionOp),
+  Host(HostRef),
+  Rng(RngOp),
+  Action(ActionOp),
 }
 
 impl Op {
@@ -91,12 +149,28 @@ impl Op {
       Self::U16(op) => format!("#U16.{}", op.symbol()),
       Self::U32(op) => format!("#U32.{}", op.symbol()),
       Self::U64(op) => format!("#U64.{}", op.symbol()),
-      // Self::U128(op) => format!("#U128.{}", op.symbol()),
+      Self::U128(op) => format!("#U128.{}", op.symbol()),
+      Self::U256(op) => format!("#U256.{}", op.symbol()),
       Self::I8(op) => format!("#I8.{}", op.symbol()),
       Self::I16(op) => format!("#I16.{}", op.symbol()),
       Self::I32(op) => format!("#I32.{}", op.symbol()),
       Self::I64(op) => format!("#I64.{}", op.symbol()),
-      // Self::I128(op) => format!("#I128.{}", op.symbol()),
+      Self::I128(op) => format!("#I128.{}", op.symbol()),
+      Self::F32(op) => format!("#F32.{}", op.symbol()),
+      Self::F64(op) => format!("#F64.{}", op.symbol()),
+      Self::Map(op) => format!("#Map.{}", op.symbol()),
+      Self::Set(op) => format!("#Set.{}", op.symbol()),
+      Self::Vector(op) => format!("#Vector.{}", op.symbol()),
+      Self::Ed25519(op) => format!("#Ed25519.{}", op.symbol()),
+      Self::Secp256k1(op) => format!("#Secp256k1.{}", op.symbol()),
+      Self::Cid(op) => format!("#Cid.{}", op.symbol()),
+      Self::Conv(op) => format!("{}.{}", op.from, op.symbol()),
+      Self::Show(op) => format!("{}.{}", op.typ, op.symbol()),
+      Self::FromText(op) => format!("{}.{}", op.typ, op.symbol()),
+      Self::Exception(op) => format!("#Exception.{}", op.symbol()),
+      Self::Host(op) => format!("#Host.{}", op.symbol()),
+      Self::Rng(op) => format!("#Rng.{}", op.symbol()),
+      Self::Action(op) => format!("#IO.{}", op.symbol()),
     }
   }
 
@@ -115,12 +189,32 @@ impl Op {
       Self::U16(op) => Ipld::List(vec![Ipld::Integer(8), op.to_ipld()]),
       Self::U32(op) => Ipld::List(vec![Ipld::Integer(9), op.to_ipld()]),
       Self::U64(op) => Ipld::List(vec![Ipld::Integer(10), op.to_ipld()]),
-      // Self::U128(op) => Ipld::List(vec![Ipld::Integer(11), op.to_ipld()]),
+      Self::U128(op) => Ipld::List(vec![Ipld::Integer(11), op.to_ipld()]),
       Self::I8(op) => Ipld::List(vec![Ipld::Integer(12), op.to_ipld()]),
       Self::I16(op) => Ipld::List(vec![Ipld::Integer(13), op.to_ipld()]),
       Self::I32(op) => Ipld::List(vec![Ipld::Integer(14), op.to_ipld()]),
       Self::I64(op) => Ipld::List(vec![Ipld::Integer(15), op.to_ipld()]),
-      // Self::I128(op) => Ipld::List(vec![Ipld::Integer(16), op.to_ipld()]),
+      Self::I128(op) => Ipld::List(vec![Ipld::Integer(16), op.to_ipld()]),
+      Self::F32(op) => Ipld::List(vec![Ipld::Integer(17), op.to_ipld()]),
+      Self::F64(op) => Ipld::List(vec![Ipld::Integer(18), op.to_ipld()]),
+      Self::Map(op) => Ipld::List(vec![Ipld::Integer(19), op.to_ipld()]),
+      Self::Set(op) => Ipld::List(vec![Ipld::Integer(20), op.to_ipld()]),
+      Self::Vector(op) => Ipld::List(vec![Ipld::Integer(21), op.to_ipld()]),
+      Self::Ed25519(op) => Ipld::List(vec![Ipld::Integer(22), op.to_ipld()]),
+      Self::Secp256k1(op) => {
+        Ipld::List(vec![Ipld::Integer(23), op.to_ipld()])
+      }
+      Self::Cid(op) => Ipld::List(vec![Ipld::Integer(24), op.to_ipld()]),
+      Self::Conv(op) => Ipld::List(vec![Ipld::Integer(25), op.to_ipld()]),
+      Self::Show(op) => Ipld::List(vec![Ipld::Integer(26), op.to_ipld()]),
+      Self::FromText(op) => Ipld::List(vec![Ipld::Integer(27), op.to_ipld()]),
+      Self::Exception(op) => {
+        Ipld::List(vec![Ipld::Integer(28), op.to_ipld()])
+      }
+      Self::Host(op) => Ipld::List(vec![Ipld::Integer(29), op.to_ipld()]),
+      Self::Rng(op) => Ipld::List(vec![Ipld::Integer(30), op.to_ipld()]),
+      Self::U256(op) => Ipld::List(vec![Ipld::Integer(31), op.to_ipld()]),
+      Self::Action(op) => Ipld::List(vec![Ipld::Integer(32), op.to_ipld()]),
     }
   }
 
@@ -141,12 +235,38 @@ impl Op {
         [Ipld::Integer(8), ys] => U16Op::from_ipld(ys).map(Self::U16),
         [Ipld::Integer(9), ys] => U32Op::from_ipld(ys).map(Self::U32),
         [Ipld::Integer(10), ys] => U64Op::from_ipld(ys).map(Self::U64),
-        //[Ipld::Integer(11), ys] => U128Op::from_ipld(ys).map(Self::U128),
+        [Ipld::Integer(11), ys] => U128Op::from_ipld(ys).map(Self::U128),
         [Ipld::Integer(12), ys] => I8Op::from_ipld(ys).map(Self::I8),
         [Ipld::Integer(13), ys] => I16Op::from_ipld(ys).map(Self::I16),
         [Ipld::Integer(14), ys] => I32Op::from_ipld(ys).map(Self::I32),
         [Ipld::Integer(15), ys] => I64Op::from_ipld(ys).map(Self::I64),
-        //[Ipld::Integer(16), ys] => I128Op::from_ipld(ys).map(Self::I128),
+        [Ipld::Integer(16), ys] => I128Op::from_ipld(ys).map(Self::I128),
+        [Ipld::Integer(17), ys] => F32Op::from_ipld(ys).map(Self::F32),
+        [Ipld::Integer(18), ys] => F64Op::from_ipld(ys).map(Self::F64),
+        [Ipld::Integer(19), ys] => MapOp::from_ipld(ys).map(Self::Map),
+        [Ipld::Integer(20), ys] => SetOp::from_ipld(ys).map(Self::Set),
+        [Ipld::Integer(21), ys] => VecOp::from_ipld(ys).map(Self::Vector),
+        [Ipld::Integer(22), ys] => {
+          Ed25519Op::from_ipld(ys).map(Self::Ed25519)
+        }
+        [Ipld::Integer(23), ys] => {
+          Secp256k1Op::from_ipld(ys).map(Self::Secp256k1)
+        }
+        [Ipld::Integer(24), ys] => CidOp::from_ipld(ys).map(Self::Cid),
+        [Ipld::Integer(25), ys] => ConvOp::from_ipld(ys).map(Self::Conv),
+        [Ipld::Integer(26), ys] => ShowOp::from_ipld(ys).map(Self::Show),
+        [Ipld::Integer(27), ys] => {
+          FromTextOp::from_ipld(ys).map(Self::FromText)
+        }
+        [Ipld::Integer(28), ys] => {
+          ExceptionOp::from_ipld(ys).map(Self::Exception)
+        }
+        [Ipld::Integer(29), Ipld::Link(cid)] => {
+          Ok(Self::Host(HostRef::unresolved(*cid)))
+        }
+        [Ipld::Integer(30), ys] => RngOp::from_ipld(ys).map(Self::Rng),
+        [Ipld::Integer(31), ys] => U256Op::from_ipld(ys).map(Self::U256),
+        [Ipld::Integer(32), ys] => ActionOp::from_ipld(ys).map(Self::Action),
         xs => Err(IpldError::PrimOp(Ipld::List(xs.to_owned()))),
       },
       xs => Err(IpldError::PrimOp(xs.to_owned())),
@@ -169,98 +289,126 @@ impl Op {
       Self::U16(op) => op.arity(),
       Self::U32(op) => op.arity(),
       Self::U64(op) => op.arity(),
-      // Self::U128(op) => op.arity(),
+      Self::U128(op) => op.arity(),
+      Self::U256(op) => op.arity(),
       Self::I8(op) => op.arity(),
       Self::I16(op) => op.arity(),
       Self::I32(op) => op.arity(),
       Self::I64(op) => op.arity(),
-      // Self::I128(op) => op.arity(),
+      Self::I128(op) => op.arity(),
+      Self::F32(op) => op.arity(),
+      Self::F64(op) => op.arity(),
+      Self::Map(op) => op.arity(),
+      Self::Set(op) => op.arity(),
+      Self::Vector(op) => op.arity(),
+      Self::Ed25519(op) => op.arity(),
+      Self::Secp256k1(op) => op.arity(),
+      Self::Cid(op) => op.arity(),
+      Self::Conv(op) => op.arity(),
+      Self::Show(op) => op.arity(),
+      Self::FromText(op) => op.arity(),
+      Self::Exception(op) => op.arity(),
+      Self::Host(op) => op.arity(),
+      Self::Rng(op) => op.arity(),
+      Self::Action(op) => op.arity(),
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(&self) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(&self, args: &[&Literal]) -> Option<Literal> {
+    if args.len() as u64 != self.arity() {
+      return None;
+    }
     match self {
       //#[cfg(feature = "std")]
-      Self::Io(op) => op.apply0(),
-      Self::U8(op) => op.apply0(),
-      Self::U16(op) => op.apply0(),
-      Self::U32(op) => op.apply0(),
-      Self::U64(op) => op.apply0(),
-      // Self::U128(op) => op.apply0(),
-      Self::I8(op) => op.apply0(),
-      Self::I16(op) => op.apply0(),
-      Self::I32(op) => op.apply0(),
-      Self::I64(op) => op.apply0(),
-      // Self::I128(op) => op.apply0(),
-      _ => None,
+      Self::Io(op) => op.apply(args),
+      Self::Nat(op) => op.apply(args),
+      Self::Int(op) => op.apply(args),
+      Self::Bits(op) => op.apply(args),
+      Self::Bytes(op) => op.apply(args),
+      Self::Text(op) => op.apply(args),
+      Self::Char(op) => op.apply(args),
+      Self::Bool(op) => op.apply(args),
+      Self::U8(op) => op.apply(args),
+      Self::U16(op) => op.apply(args),
+      Self::U32(op) => op.apply(args),
+      Self::U64(op) => op.apply(args),
+      // TODO These break wasm and wasm-pack
+      Self::U128(op) => op.apply(args),
+      Self::U256(op) => op.apply(args),
+      Self::I8(op) => op.apply(args),
+      Self::I16(op) => op.apply(args),
+      Self::I32(op) => op.apply(args),
+      Self::I64(op) => op.apply(args),
+      Self::I128(op) => op.apply(args),
+      Self::F32(op) => op.apply(args),
+      Self::F64(op) => op.apply(args),
+      Self::Map(op) => op.apply(args),
+      Self::Set(op) => op.apply(args),
+      Self::Vector(op) => op.apply(args),
+      Self::Ed25519(op) => op.apply(args),
+      Self::Secp256k1(op) => op.apply(args),
+      Self::Cid(op) => op.apply(args),
+      Self::Conv(op) => op.apply(args),
+      Self::Show(op) => op.apply(args),
+      Self::FromText(_) => None,
+      Self::Exception(op) => op.apply(args),
+      Self::Host(op) => op.apply(args),
+      Self::Rng(op) => op.apply(args),
+      Self::Action(_) => None,
     }
   }
 
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(&self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation whose result is a term rather than a literal, such
+  /// as listing the keys of a map or the checked arithmetic ops, to as many
+  /// literals as its arity and returns it if successful
+  pub fn apply_term(&self, args: &[&Literal]) -> Option<Term> {
+    if args.len() as u64 != self.arity() {
+      return None;
+    }
     match self {
-      //#[cfg(feature = "std")]
-      Self::Io(op) => op.apply1(x),
-      Self::Nat(op) => op.apply1(x),
-      Self::Int(op) => op.apply1(x),
-      Self::Bits(op) => op.apply1(x),
-      Self::Bytes(op) => op.apply1(x),
-      Self::Text(op) => op.apply1(x),
-      Self::Char(op) => op.apply1(x),
-      Self::Bool(op) => op.apply1(x),
-      Self::U8(op) => op.apply1(x),
-      Self::U16(op) => op.apply1(x),
-      Self::U32(op) => op.apply1(x),
-      Self::U64(op) => op.apply1(x),
-      // Self::U128(op) => op.apply1(x),
-      Self::I8(op) => op.apply1(x),
-      Self::I16(op) => op.apply1(x),
-      Self::I32(op) => op.apply1(x),
-      Self::I64(op) => op.apply1(x),
-      // Self::I128(op) => op.apply1(x),
+      Self::Nat(op) => op.apply_term(args),
+      Self::Text(op) => op.apply_term(args),
+      Self::Map(op) => op.apply_term(args),
+      Self::Set(op) => op.apply_term(args),
+      Self::Vector(op) => op.apply_term(args),
+      Self::Secp256k1(op) => op.apply_term(args),
+      Self::Cid(op) => op.apply_term(args),
+      Self::Rng(op) => op.apply_term(args),
+      Self::U8(op) => op.apply_checked(args).map(option_term),
+      Self::U16(op) => op.apply_checked(args).map(option_term),
+      Self::U32(op) => op.apply_checked(args).map(option_term),
+      Self::U64(op) => op.apply_checked(args).map(option_term),
+      Self::U128(op) => op.apply_checked(args).map(option_term),
+      Self::U256(op) => op.apply_checked(args).map(option_term),
+      Self::I8(op) => op.apply_checked(args).map(option_term),
+      Self::I16(op) => op.apply_checked(args).map(option_term),
+      Self::I32(op) => op.apply_checked(args).map(option_term),
+      Self::I64(op) => op.apply_checked(args).map(option_term),
+      Self::I128(op) => op.apply_checked(args).map(option_term),
+      Self::Conv(op) => op.apply_checked(args).map(option_term),
+      Self::FromText(op) => op.apply_parsed(args).map(option_term),
+      _ => None,
     }
   }
 
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(&self, x: &Literal, y: &Literal) -> Option<Literal> {
-    match self {
-      //#[cfg(feature = "std")]
-      Self::Io(op) => op.apply2(x, y),
-      Self::Nat(op) => op.apply2(x, y),
-      Self::Int(op) => op.apply2(x, y),
-      Self::Bits(op) => op.apply2(x, y),
-      Self::Bytes(op) => op.apply2(x, y),
-      Self::Text(op) => op.apply2(x, y),
-      Self::Char(op) => op.apply2(x, y),
-      Self::Bool(op) => op.apply2(x, y),
-      Self::U8(op) => op.apply2(x, y),
-      Self::U16(op) => op.apply2(x, y),
-      Self::U32(op) => op.apply2(x, y),
-      Self::U64(op) => op.apply2(x, y),
-      // TODO These break wasm and wasm-pack
-      // Self::U128(op) => op.apply2(x, y),
-      Self::I8(op) => op.apply2(x, y),
-      Self::I16(op) => op.apply2(x, y),
-      Self::I32(op) => op.apply2(x, y),
-      Self::I64(op) => op.apply2(x, y),
-      // Self::I128(op) => op.apply2(x, y),
-      //_ => None,
-    }
+  /// Returns the default gas cost of applying an operation to its arguments
+  pub fn cost(&self, args: &[&Literal]) -> u64 {
+    let words = args.iter().map(|x| cost::words(x)).sum::<u64>();
+    cost::base_cost(self) + words + cost::result_words(self, args)
   }
 
-  /// Applies a ternary operation to a literal and returns it if successful
-  pub fn apply3(
-    &self,
-    x: &Literal,
-    y: &Literal,
-    z: &Literal,
-  ) -> Option<Literal> {
+  /// Gets the first exception among the arguments of an operation, which is
+  /// its result in place of applying it. Exception operations aren't
+  /// short-circuited, since they take exceptions apart
+  pub fn raised(&self, args: &[&Literal]) -> Option<Literal> {
     match self {
-      Self::Bytes(op) => op.apply3(x, y, z),
-      Self::Bits(op) => op.apply3(x, y, z),
-      Self::Text(op) => op.apply3(x, y, z),
-      _ => None,
+      Self::Exception(_) => None,
+      _ => args
+        .iter()
+        .find(|x| matches!(x, Literal::Exception(..)))
+        .map(|x| (*x).clone()),
     }
   }
 
@@ -280,16 +428,69 @@ impl Op {
       Self::U16(op) => op.type_of(),
       Self::U32(op) => op.type_of(),
       Self::U64(op) => op.type_of(),
-      // Self::U128(op) => op.type_of(),
+      Self::U128(op) => op.type_of(),
+      Self::U256(op) => op.type_of(),
       Self::I8(op) => op.type_of(),
       Self::I16(op) => op.type_of(),
       Self::I32(op) => op.type_of(),
       Self::I64(op) => op.type_of(),
-      // Self::I128(op) => op.type_of(),
+      Self::I128(op) => op.type_of(),
+      Self::F32(op) => op.type_of(),
+      Self::F64(op) => op.type_of(),
+      Self::Map(op) => op.type_of(),
+      Self::Set(op) => op.type_of(),
+      Self::Vector(op) => op.type_of(),
+      Self::Ed25519(op) => op.type_of(),
+      Self::Secp256k1(op) => op.type_of(),
+      Self::Cid(op) => op.type_of(),
+      Self::Conv(op) => op.type_of(),
+      Self::Show(op) => op.type_of(),
+      Self::FromText(op) => op.type_of(),
+      Self::Exception(op) => op.type_of(),
+      Self::Host(op) => op.type_of().unwrap_or_else(|| {
+        panic!("unresolved host operation #Host.{}", op.symbol())
+      }),
+      Self::Rng(op) => op.type_of(),
+      Self::Action(op) => op.type_of(),
     }
   }
 }
 
+/// Encodes an optional literal as a lambda of type
+/// `∀ (0 P: Type) (none: P) (some: ∀ A -> P) -> P`
+pub fn option_term(x: Option<Literal>) -> Term {
+  match x {
+    Some(x) => yatima!("λ P none some => some #$0", Term::Lit(Pos::None, x)),
+    None => yatima!("λ P none some => none"),
+  }
+}
+
+/// Encodes a pair of literals as a lambda of type
+/// `∀ (0 P: Type) (pair: ∀ A B -> P) -> P`
+pub fn pair_term(x: Literal, y: Literal) -> Term {
+  yatima!(
+    "λ P pair => pair #$0 #$1",
+    Term::Lit(Pos::None, x),
+    Term::Lit(Pos::None, y)
+  )
+}
+
+/// Church-encodes a list of literals as a lambda of type
+/// `∀ (0 P: Type) (nil: P) (cons: ∀ A P -> P) -> P`
+/// The term is built directly rather than quasiquoted, as parsing around
+/// the tail once per element runs out of stack on long lists.
+pub fn list_term(xs: Vec<Literal>) -> Term {
+  let var = |nam: &str, idx| Term::Var(Pos::None, Name::from(nam), idx);
+  let app = |fun, arg| Term::App(Pos::None, Box::new((fun, arg)));
+  let lam = |nam: &str, bod| Term::Lam(Pos::None, Name::from(nam), Box::new(bod));
+  let list = |bod| lam("P", lam("nil", lam("cons", bod)));
+  xs.into_iter().rev().fold(list(var("nil", 1)), |acc, x| {
+    // λ P nil cons => cons x (acc P nil cons)
+    let tail = app(app(app(acc, var("P", 2)), var("nil", 1)), var("cons", 0));
+    list(app(app(var("cons", 0), Term::Lit(Pos::None, x)), tail))
+  })
+}
+
 impl fmt::Display for Op {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     write!(f, "{}", self.symbol())
@@ -307,7 +508,7 @@ pub mod tests {
   impl Arbitrary for Op {
     fn arbitrary(g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=13);
+      let gen: u32 = rng.gen_range(0..=30);
       match gen {
         0 => Self::Nat(NatOp::arbitrary(g)),
         1 => Self::Int(IntOp::arbitrary(g)),
@@ -319,10 +520,27 @@ pub mod tests {
         7 => Self::U16(U16Op::arbitrary(g)),
         8 => Self::U32(U32Op::arbitrary(g)),
         9 => Self::U64(U64Op::arbitrary(g)),
-        10 => Self::I8(I8Op::arbitrary(g)),
-        11 => Self::I16(I16Op::arbitrary(g)),
-        12 => Self::I32(I32Op::arbitrary(g)),
-        _ => Self::I64(I64Op::arbitrary(g)),
+        10 => Self::U128(U128Op::arbitrary(g)),
+        11 => Self::I8(I8Op::arbitrary(g)),
+        12 => Self::I16(I16Op::arbitrary(g)),
+        13 => Self::I32(I32Op::arbitrary(g)),
+        14 => Self::I64(I64Op::arbitrary(g)),
+        15 => Self::I128(I128Op::arbitrary(g)),
+        16 => Self::F32(F32Op::arbitrary(g)),
+        17 => Self::F64(F64Op::arbitrary(g)),
+        18 => Self::Map(MapOp::arbitrary(g)),
+        19 => Self::Set(SetOp::arbitrary(g)),
+        20 => Self::Vector(VecOp::arbitrary(g)),
+        21 => Self::Ed25519(Ed25519Op::arbitrary(g)),
+        22 => Self::Secp256k1(Secp256k1Op::arbitrary(g)),
+        23 => Self::Cid(CidOp::arbitrary(g)),
+        24 => Self::Conv(ConvOp::arbitrary(g)),
+        25 => Self::Show(ShowOp::arbitrary(g)),
+        26 => Self::FromText(FromTextOp::arbitrary(g)),
+        27 => Self::Exception(ExceptionOp::arbitrary(g)),
+        28 => Self::U256(U256Op::arbitrary(g)),
+        29 => Self::Rng(RngOp::arbitrary(g)),
+        _ => Self::Action(ActionOp::arbitrary(g)),
       }
     }
   }
@@ -335,6 +553,30 @@ pub mod tests {
     }
   }
 
+  #[quickcheck]
+  fn apply_needs_arity(
+    op: Op,
+    v: Literal,
+    w: Literal,
+    x: Literal,
+    y: Literal,
+    z: Literal,
+  ) -> bool {
+    let args = [&v, &w, &x, &y, &z];
+    let arity = op.arity() as usize;
+    (0..=args.len()).filter(|n| *n != arity).all(|n| {
+      op.apply(&args[0..n]).is_none() && op.apply_term(&args[0..n]).is_none()
+    })
+  }
+
+  #[test]
+  fn apply_quaternary() {
+    use Literal::U64;
+    let op = Op::U256(U256Op::FromLimbs);
+    let res = op.apply(&[&U64(0), &U64(0), &U64(1), &U64(2)]);
+    assert_eq!(res, Some(Literal::U256(u256::U256([2, 1, 0, 0]))));
+  }
+
   #[derive(Clone, Debug)]
   pub enum TestArg3 {
     A,
@@ -0,0 +1,185 @@
+use sp_ipld::Ipld;
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  parse,
+  term::Term,
+  yatima,
+};
+
+/// Primitive actions of an `#IO` program. An action takes its literal
+/// arguments, then the continuations to go on with once it's done, so a
+/// program is a chain of actions ending in `#IO.done`. Actions never reduce:
+/// evaluating a program only builds this chain, which the runtime carries out
+/// under `yatima run`
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ActionOp {
+  Done,
+  Print,
+  ReadLine,
+  ReadFile,
+  WriteFile,
+  GetArgs,
+  GetEnv,
+}
+
+impl ActionOp {
+  /// Gets the syntax string of an action
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Done => "done".to_owned(),
+      Self::Print => "print".to_owned(),
+      Self::ReadLine => "read_line".to_owned(),
+      Self::ReadFile => "read_file".to_owned(),
+      Self::WriteFile => "write_file".to_owned(),
+      Self::GetArgs => "get_args".to_owned(),
+      Self::GetEnv => "get_env".to_owned(),
+    }
+  }
+
+  /// Gets an action from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "done" => Some(Self::Done),
+      "print" => Some(Self::Print),
+      "read_line" => Some(Self::ReadLine),
+      "read_file" => Some(Self::ReadFile),
+      "write_file" => Some(Self::WriteFile),
+      "get_args" => Some(Self::GetArgs),
+      "get_env" => Some(Self::GetEnv),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of an action. Failures are passed to an `err`
+  /// continuation with a message
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Done => yatima!("#IO"),
+      Self::Print => yatima!("∀ #Text (next: #IO) -> #IO"),
+      Self::ReadLine => {
+        yatima!("∀ (eof: #IO) (line: ∀ #Text -> #IO) -> #IO")
+      }
+      Self::ReadFile => yatima!(
+        "∀ (path: #Text) (err: ∀ #Text -> #IO) (ok: ∀ #Bytes -> #IO) -> #IO"
+      ),
+      Self::WriteFile => {
+        yatima!("∀ (path: #Text) #Bytes (err: ∀ #Text -> #IO) (ok: #IO) -> #IO")
+      }
+      Self::GetArgs => yatima!(
+        "∀ (args: ∀
+             (list: ∀ (0 P: Type) (nil: P) (cons: ∀ #Text P -> P) -> P)
+           -> #IO)
+         -> #IO"
+      ),
+      Self::GetEnv => {
+        yatima!("∀ #Text (none: #IO) (some: ∀ #Text -> #IO) -> #IO")
+      }
+    }
+  }
+
+  /// Converts an action into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Done => Ipld::Integer(0),
+      Self::Print => Ipld::Integer(1),
+      Self::ReadLine => Ipld::Integer(2),
+      Self::ReadFile => Ipld::Integer(3),
+      Self::WriteFile => Ipld::Integer(4),
+      Self::GetArgs => Ipld::Integer(5),
+      Self::GetEnv => Ipld::Integer(6),
+    }
+  }
+
+  /// Converts an IPLD object into an action
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Done),
+      Ipld::Integer(1) => Ok(Self::Print),
+      Ipld::Integer(2) => Ok(Self::ReadLine),
+      Ipld::Integer(3) => Ok(Self::ReadFile),
+      Ipld::Integer(4) => Ok(Self::WriteFile),
+      Ipld::Integer(5) => Ok(Self::GetArgs),
+      Ipld::Integer(6) => Ok(Self::GetEnv),
+      xs => Err(IpldError::ActionOp(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of literal arguments of an action, which are reduced
+  /// before it's carried out. The continuations come after them
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Done => 0,
+      Self::Print => 1,
+      Self::ReadLine => 0,
+      Self::ReadFile => 1,
+      Self::WriteFile => 2,
+      Self::GetArgs => 0,
+      Self::GetEnv => 1,
+    }
+  }
+
+  /// Returns the number of continuations of an action
+  pub fn continuations(self) -> usize {
+    match self {
+      Self::Done => 0,
+      Self::Print => 1,
+      Self::ReadLine => 2,
+      Self::ReadFile => 2,
+      Self::WriteFile => 2,
+      Self::GetArgs => 1,
+      Self::GetEnv => 2,
+    }
+  }
+}
+
+impl fmt::Display for ActionOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  impl Arbitrary for ActionOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=6);
+      match gen {
+        0 => Self::Done,
+        1 => Self::Print,
+        2 => Self::ReadLine,
+        3 => Self::ReadFile,
+        4 => Self::WriteFile,
+        5 => Self::GetArgs,
+        _ => Self::GetEnv,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn action_op_ipld(x: ActionOp) -> bool {
+    match ActionOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn action_op_symbol(x: ActionOp) -> bool {
+    ActionOp::from_symbol(&x.symbol()) == Some(x)
+  }
+}
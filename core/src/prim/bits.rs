@@ -19,7 +19,9 @@ use crate::{
 
 use core::convert::TryFrom;
 
-/// Primitive bit operations
+/// Primitive bit operations. Bits are indexed from the least significant
+/// end, so `cons` and `head` work on the most significant bit, while the
+/// bitwise and numeric operations treat the bits as a fixed width word
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum BitsOp {
   Cons,
@@ -33,6 +35,17 @@ pub enum BitsOp {
   Remove,
   Index,
   ToBytes,
+  And,
+  Or,
+  Xor,
+  Not,
+  Shl,
+  Shr,
+  Slice,
+  PopCount,
+  ToNat,
+  FromNat,
+  FromBytes,
 }
 
 impl BitsOp {
@@ -50,6 +63,17 @@ impl BitsOp {
       Self::Remove => "remove".to_owned(),
       Self::Index => "index".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::And => "and".to_owned(),
+      Self::Or => "or".to_owned(),
+      Self::Xor => "xor".to_owned(),
+      Self::Not => "not".to_owned(),
+      Self::Shl => "shl".to_owned(),
+      Self::Shr => "shr".to_owned(),
+      Self::Slice => "slice".to_owned(),
+      Self::PopCount => "popcount".to_owned(),
+      Self::ToNat => "to_Nat".to_owned(),
+      Self::FromNat => "from_Nat".to_owned(),
+      Self::FromBytes => "from_Bytes".to_owned(),
     }
   }
 
@@ -67,6 +91,17 @@ impl BitsOp {
       "remove" => Some(Self::Remove),
       "index" => Some(Self::Index),
       "to_Bytes" => Some(Self::ToBytes),
+      "and" => Some(Self::And),
+      "or" => Some(Self::Or),
+      "xor" => Some(Self::Xor),
+      "not" => Some(Self::Not),
+      "shl" => Some(Self::Shl),
+      "shr" => Some(Self::Shr),
+      "slice" => Some(Self::Slice),
+      "popcount" => Some(Self::PopCount),
+      "to_Nat" => Some(Self::ToNat),
+      "from_Nat" => Some(Self::FromNat),
+      "from_Bytes" => Some(Self::FromBytes),
       _ => None,
     }
   }
@@ -85,6 +120,17 @@ impl BitsOp {
       Self::Remove => yatima!("∀ #Nat #Bits -> #Bits"),
       Self::Index => yatima!("∀ #Nat #Bits -> #U8"),
       Self::ToBytes => yatima!("∀ #Bits -> #Bytes"),
+      Self::And => yatima!("∀ #Bits #Bits -> #Bits"),
+      Self::Or => yatima!("∀ #Bits #Bits -> #Bits"),
+      Self::Xor => yatima!("∀ #Bits #Bits -> #Bits"),
+      Self::Not => yatima!("∀ #Bits -> #Bits"),
+      Self::Shl => yatima!("∀ #Nat #Bits -> #Bits"),
+      Self::Shr => yatima!("∀ #Nat #Bits -> #Bits"),
+      Self::Slice => yatima!("∀ #Nat #Nat #Bits -> #Bits"),
+      Self::PopCount => yatima!("∀ #Bits -> #Nat"),
+      Self::ToNat => yatima!("∀ #Bits -> #Nat"),
+      Self::FromNat => yatima!("∀ #Nat #Nat -> #Bits"),
+      Self::FromBytes => yatima!("∀ #Bytes -> #Bits"),
     }
   }
 
@@ -102,6 +148,17 @@ impl BitsOp {
       Self::Remove => Ipld::Integer(8),
       Self::Index => Ipld::Integer(9),
       Self::ToBytes => Ipld::Integer(10),
+      Self::And => Ipld::Integer(11),
+      Self::Or => Ipld::Integer(12),
+      Self::Xor => Ipld::Integer(13),
+      Self::Not => Ipld::Integer(14),
+      Self::Shl => Ipld::Integer(15),
+      Self::Shr => Ipld::Integer(16),
+      Self::Slice => Ipld::Integer(17),
+      Self::PopCount => Ipld::Integer(18),
+      Self::ToNat => Ipld::Integer(19),
+      Self::FromNat => Ipld::Integer(20),
+      Self::FromBytes => Ipld::Integer(21),
     }
   }
 
@@ -119,6 +176,17 @@ impl BitsOp {
       Ipld::Integer(8) => Ok(Self::Remove),
       Ipld::Integer(9) => Ok(Self::Index),
       Ipld::Integer(10) => Ok(Self::ToBytes),
+      Ipld::Integer(11) => Ok(Self::And),
+      Ipld::Integer(12) => Ok(Self::Or),
+      Ipld::Integer(13) => Ok(Self::Xor),
+      Ipld::Integer(14) => Ok(Self::Not),
+      Ipld::Integer(15) => Ok(Self::Shl),
+      Ipld::Integer(16) => Ok(Self::Shr),
+      Ipld::Integer(17) => Ok(Self::Slice),
+      Ipld::Integer(18) => Ok(Self::PopCount),
+      Ipld::Integer(19) => Ok(Self::ToNat),
+      Ipld::Integer(20) => Ok(Self::FromNat),
+      Ipld::Integer(21) => Ok(Self::FromBytes),
       xs => Err(IpldError::BitsOp(xs.to_owned())),
     }
   }
@@ -137,52 +205,64 @@ impl BitsOp {
       Self::Remove => 2,
       Self::Index => 2,
       Self::ToBytes => 1,
+      Self::And => 2,
+      Self::Or => 2,
+      Self::Xor => 2,
+      Self::Not => 1,
+      Self::Shl => 2,
+      Self::Shr => 2,
+      Self::Slice => 3,
+      Self::PopCount => 1,
+      Self::ToNat => 1,
+      Self::FromNat => 2,
+      Self::FromBytes => 1,
     }
   }
 
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::Len, Bits(xs)) => Some(Nat(xs.len().into())),
-      (Self::Head, Bits(xs)) => {
+    match (self, args) {
+      (Self::Len, [Bits(xs)]) => Some(Nat(xs.len().into())),
+      (Self::Head, [Bits(xs)]) => {
         let x = xs.last();
         x.map(|x| Bool(*x))
       }
-      (Self::Tail, Bits(xs)) => Some(Bits(if xs.is_empty() {
+      (Self::Tail, [Bits(xs)]) => Some(Bits(if xs.is_empty() {
         vec![]
       }
       else {
         xs[0..xs.len() - 1].to_vec()
       })),
-      (Self::ToBytes, Bits(xs)) => Some(Literal::Bytes(bits_to_bytes(xs).1)),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Cons, Bool(x), Bits(xs)) => {
+      (Self::ToBytes, [Bits(xs)]) => Some(Literal::Bytes(bits_to_bytes(xs).1)),
+      (Self::Not, [Bits(xs)]) => Some(Bits(xs.iter().map(|x| !x).collect())),
+      (Self::PopCount, [Bits(xs)]) => {
+        Some(Nat(xs.iter().filter(|x| **x).count().into()))
+      }
+      (Self::ToNat, [Bits(xs)]) => Some(Nat(bits_to_nat(xs))),
+      (Self::FromBytes, [Bytes(xs)]) => {
+        Some(Bits(bytes_to_bits(xs.len() * 8, xs)))
+      }
+      (Self::Cons, [Bool(x), Bits(xs)]) => {
         let mut xs = xs.clone();
         xs.push(*x);
         Some(Bits(xs))
       }
-      (Self::Drop, Nat(x), Bits(xs)) => {
+      (Self::Drop, [Nat(x), Bits(xs)]) => {
         let (_, ys) = safe_split(x, xs);
         Some(Bits(ys))
       }
-      (Self::Take, Nat(x), Bits(xs)) => {
+      (Self::Take, [Nat(x), Bits(xs)]) => {
         let (xs, _) = safe_split(x, xs);
         Some(Bits(xs))
       }
-      (Self::Append, Bits(xs), Bits(ys)) => {
+      (Self::Append, [Bits(xs), Bits(ys)]) => {
         let mut xs = xs.clone();
         xs.extend_from_slice(&ys);
         Some(Bits(xs))
       }
-      (Self::Remove, Nat(idx), Bits(xs)) => {
+      (Self::Remove, [Nat(idx), Bits(xs)]) => {
         let idx = usize::try_from(idx);
         match idx {
           Ok(idx) if idx < xs.len() => {
@@ -193,7 +273,7 @@ impl BitsOp {
           _ => Some(Bits(xs.clone())),
         }
       }
-      (Self::Index, Nat(idx), Bits(xs)) => {
+      (Self::Index, [Nat(idx), Bits(xs)]) => {
         let idx = usize::try_from(idx);
         match idx {
           Ok(idx) if idx < xs.len() => {
@@ -203,20 +283,22 @@ impl BitsOp {
           _ => None,
         }
       }
-      _ => None,
-    }
-  }
-
-  /// Applies a ternary operation to a literal and returns it if successful
-  pub fn apply3(
-    self,
-    x: &Literal,
-    y: &Literal,
-    z: &Literal,
-  ) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y, z) {
-      (Self::Insert, Nat(idx), Bool(y), Bits(xs)) => {
+      (Self::And, [Bits(xs), Bits(ys)]) => {
+        zip_bits(xs, ys, |x, y| x & y).map(Bits)
+      }
+      (Self::Or, [Bits(xs), Bits(ys)]) => {
+        zip_bits(xs, ys, |x, y| x | y).map(Bits)
+      }
+      (Self::Xor, [Bits(xs), Bits(ys)]) => {
+        zip_bits(xs, ys, |x, y| x ^ y).map(Bits)
+      }
+      (Self::Shl, [Nat(x), Bits(xs)]) => Some(Bits(shift_left(x, xs))),
+      (Self::Shr, [Nat(x), Bits(xs)]) => Some(Bits(shift_right(x, xs))),
+      (Self::FromNat, [Nat(len), Nat(x)]) => {
+        let len = usize::try_from(len).ok()?;
+        Some(Bits(nat_to_bits(len, x)))
+      }
+      (Self::Insert, [Nat(idx), Bool(y), Bits(xs)]) => {
         let idx = usize::try_from(idx);
         match idx {
           Ok(idx) if idx < xs.len() => {
@@ -227,6 +309,9 @@ impl BitsOp {
           _ => Some(Bits(xs.clone())),
         }
       }
+      (Self::Slice, [Nat(from), Nat(upto), Bits(xs)]) => {
+        Some(Bits(safe_slice(from, upto, xs)))
+      }
       _ => None,
     }
   }
@@ -245,6 +330,61 @@ pub fn safe_split(idx: &BigUint, xs: &Vec<bool>) -> (Vec<bool>, Vec<bool>) {
   }
 }
 
+/// Gets the bits from index `from` up to index `upto`, clamped to the length
+/// of the bits
+pub fn safe_slice(from: &BigUint, upto: &BigUint, xs: &Vec<bool>) -> Vec<bool> {
+  if from >= upto {
+    return vec![];
+  }
+  let (xs, _) = safe_split(upto, xs);
+  safe_split(from, &xs).1
+}
+
+/// Combines two lists of bits of the same length bit by bit
+pub fn zip_bits(
+  xs: &Vec<bool>,
+  ys: &Vec<bool>,
+  f: impl Fn(bool, bool) -> bool,
+) -> Option<Vec<bool>> {
+  if xs.len() == ys.len() {
+    Some(xs.iter().zip(ys).map(|(x, y)| f(*x, *y)).collect())
+  }
+  else {
+    None
+  }
+}
+
+/// Shifts bits towards the most significant end, filling in zeros and
+/// keeping the length
+pub fn shift_left(n: &BigUint, xs: &Vec<bool>) -> Vec<bool> {
+  let n = usize::try_from(n).unwrap_or(usize::MAX).min(xs.len());
+  let mut res = vec![false; n];
+  res.extend_from_slice(&xs[0..xs.len() - n]);
+  res
+}
+
+/// Shifts bits towards the least significant end, filling in zeros and
+/// keeping the length
+pub fn shift_right(n: &BigUint, xs: &Vec<bool>) -> Vec<bool> {
+  let n = usize::try_from(n).unwrap_or(usize::MAX).min(xs.len());
+  let mut res = xs[n..].to_vec();
+  res.resize(xs.len(), false);
+  res
+}
+
+/// Reads bits as an unsigned number
+pub fn bits_to_nat(xs: &Vec<bool>) -> BigUint {
+  BigUint::from_bytes_le(&bits_to_bytes(xs).1)
+}
+
+/// Writes the number into `len` bits, dropping any higher bits
+pub fn nat_to_bits(len: usize, x: &BigUint) -> Vec<bool> {
+  let bytes = x.to_bytes_le();
+  let mut res = bytes_to_bits(len, &bytes);
+  res.resize(len, false);
+  res
+}
+
 /// Converts a list of 8 bits into a byte
 pub fn bits_to_byte(bits: &[bool]) -> u8 {
   let mut i = 0;
@@ -330,7 +470,7 @@ pub mod tests {
   impl Arbitrary for BitsOp {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=10);
+      let gen: u32 = rng.gen_range(0..=21);
       match gen {
         0 => Self::Cons,
         1 => Self::Len,
@@ -342,7 +482,18 @@ pub mod tests {
         7 => Self::Insert,
         8 => Self::Remove,
         9 => Self::Index,
-        _ => Self::ToBytes,
+        10 => Self::ToBytes,
+        11 => Self::And,
+        12 => Self::Or,
+        13 => Self::Xor,
+        14 => Self::Not,
+        15 => Self::Shl,
+        16 => Self::Shr,
+        17 => Self::Slice,
+        18 => Self::PopCount,
+        19 => Self::ToNat,
+        20 => Self::FromNat,
+        _ => Self::FromBytes,
       }
     }
   }
@@ -399,35 +550,45 @@ pub mod tests {
     let big = BigUint::from;
     let apply1_bits = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BitsOp::apply1(op, &Literal::Bits(a.clone())) == expected,
+        BitsOp::apply(op, &[&Literal::Bits(a.clone())]) == expected,
       )
     };
 
     let apply2_bool_bits = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BitsOp::apply2(op, &Bool(b), &Literal::Bits(a.clone())) == expected,
+        BitsOp::apply(op, &[&Bool(b), &Literal::Bits(a.clone())]) == expected,
       )
     };
 
     let apply2_nat_bits = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BitsOp::apply2(op, &Nat(big(c)), &Literal::Bits(a.clone())) == expected,
+        BitsOp::apply(op, &[&Nat(big(c)), &Literal::Bits(a.clone())])
+          == expected,
       )
     };
 
     let apply2_bits_bits = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BitsOp::apply2(
-          op,
+        BitsOp::apply(op, &[
           &Literal::Bits(a.clone()),
           &Literal::Bits(d.clone()),
-        ) == expected,
+        ]) == expected,
+      )
+    };
+
+    let apply3_nat_nat_bits = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        BitsOp::apply(op, &[
+          &Nat(big(c / 2)),
+          &Nat(big(c)),
+          &Literal::Bits(a.clone()),
+        ]) == expected,
       )
     };
 
     let apply3_nat_bool_bits = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BitsOp::apply3(op, &Nat(big(c)), &Bool(b), &Literal::Bits(a.clone()))
+        BitsOp::apply(op, &[&Nat(big(c)), &Bool(b), &Literal::Bits(a.clone())])
           == expected,
       )
     };
@@ -489,16 +650,15 @@ pub mod tests {
         }
       }
       BitsOp::ToBytes => {
-        let bytes = BitsOp::apply1(op, &Literal::Bits(a.clone()));
+        let bytes = BitsOp::apply(op, &[&Literal::Bits(a.clone())]);
         match bytes {
           None => apply1_bits(None),
           Some(bytes_) => match bytes_.clone() {
             Literal::Bytes(_) => {
-              let bits = BytesOp::apply2(
-                BytesOp::ToBits,
+              let bits = BytesOp::apply(BytesOp::ToBits, &[
                 &Literal::Nat(big(a.len().try_into().unwrap())),
                 &bytes_,
-              );
+              ]);
               match bits {
                 None => apply1_bits(None),
                 Some(bits_) => from_bool(bits_ == Literal::Bits(a.clone())),
@@ -508,9 +668,90 @@ pub mod tests {
           },
         }
       }
+      BitsOp::And => {
+        apply2_bits_bits(zip_bits(&a, &d, |x, y| x && y).map(Literal::Bits))
+      }
+      BitsOp::Or => {
+        apply2_bits_bits(zip_bits(&a, &d, |x, y| x || y).map(Literal::Bits))
+      }
+      BitsOp::Xor => {
+        apply2_bits_bits(zip_bits(&a, &d, |x, y| x != y).map(Literal::Bits))
+      }
+      BitsOp::Not => {
+        apply1_bits(Some(Literal::Bits(a.iter().map(|x| !x).collect())))
+      }
+      BitsOp::Shl => {
+        let n = usize::try_from(c).unwrap_or(usize::MAX).min(a.len());
+        let mut res = vec![false; n];
+        res.extend_from_slice(&a[0..a.len() - n]);
+        apply2_nat_bits(Some(Literal::Bits(res)))
+      }
+      BitsOp::Shr => {
+        let n = usize::try_from(c).unwrap_or(usize::MAX).min(a.len());
+        let mut res = a[n..].to_vec();
+        res.resize(a.len(), false);
+        apply2_nat_bits(Some(Literal::Bits(res)))
+      }
+      BitsOp::Slice => {
+        let (xs, _) = safe_split(&big(c), &a);
+        apply3_nat_nat_bits(Some(Literal::Bits(safe_split(&big(c / 2), &xs).1)))
+      }
+      BitsOp::PopCount => {
+        apply1_bits(Some(Nat(a.iter().filter(|x| **x).count().into())))
+      }
+      BitsOp::ToNat => {
+        let bits = BitsOp::apply(BitsOp::FromNat, &[
+          &Nat(a.len().into()),
+          &BitsOp::apply(op, &[&Literal::Bits(a.clone())]).unwrap(),
+        ]);
+        from_bool(bits == Some(Literal::Bits(a.clone())))
+      }
+      BitsOp::FromNat => {
+        let bits = BitsOp::apply(op, &[&Nat(big(64)), &Nat(big(c))]);
+        let nat = bits.and_then(|x| BitsOp::apply(BitsOp::ToNat, &[&x]));
+        from_bool(nat == Some(Nat(big(c))))
+      }
+      BitsOp::FromBytes => {
+        let (_, bytes) = bits_to_bytes(&a);
+        let bits = BitsOp::apply(op, &[&Literal::Bytes(bytes.clone())]);
+        from_bool(
+          bits == Some(Literal::Bits(bytes_to_bits(bytes.len() * 8, &bytes))),
+        )
+      }
     }
   }
 
+  #[test]
+  fn test_word_ops() {
+    use Literal::Bits;
+    let big = BigUint::from;
+    let bits = |x: u64, len: u64| {
+      BitsOp::apply(BitsOp::FromNat, &[&Nat(big(len)), &Nat(big(x))]).unwrap()
+    };
+    let apply1 = |op, x| BitsOp::apply(op, &[&x]);
+    let apply2 = |op, x, y| BitsOp::apply(op, &[&x, &y]);
+    assert_eq!(bits(6, 4), Bits(vec![false, true, true, false]));
+    assert_eq!(bits(0x1f, 4), bits(0xf, 4));
+    assert_eq!(apply2(BitsOp::And, bits(12, 4), bits(10, 4)), Some(bits(8, 4)));
+    assert_eq!(apply2(BitsOp::Or, bits(12, 4), bits(10, 4)), Some(bits(14, 4)));
+    assert_eq!(apply2(BitsOp::Xor, bits(12, 4), bits(10, 4)), Some(bits(6, 4)));
+    assert_eq!(apply2(BitsOp::And, bits(12, 4), bits(10, 5)), None);
+    assert_eq!(apply1(BitsOp::Not, bits(12, 4)), Some(bits(3, 4)));
+    assert_eq!(apply2(BitsOp::Shl, Nat(big(1)), bits(12, 4)), Some(bits(8, 4)));
+    assert_eq!(apply2(BitsOp::Shr, Nat(big(2)), bits(12, 4)), Some(bits(3, 4)));
+    assert_eq!(apply2(BitsOp::Shr, Nat(big(9)), bits(12, 4)), Some(bits(0, 4)));
+    assert_eq!(
+      BitsOp::apply(BitsOp::Slice, &[&Nat(big(1)), &Nat(big(3)), &bits(12, 4)]),
+      Some(bits(2, 2))
+    );
+    assert_eq!(apply1(BitsOp::PopCount, bits(13, 4)), Some(Nat(big(3))));
+    assert_eq!(apply1(BitsOp::ToNat, bits(13, 8)), Some(Nat(big(13))));
+    assert_eq!(
+      apply1(BitsOp::FromBytes, Literal::Bytes(vec![1, 2])),
+      Some(bits(0x0201, 16))
+    );
+  }
+
   #[quickcheck]
   fn test_apply_none_on_invalid(
     op: BitsOp,
@@ -527,13 +768,14 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(BitsOp::apply1(op, &a) == None)
+        TestResult::from_bool(BitsOp::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(BitsOp::apply2(op, &a_, &b_) == None);
+        let go =
+          || TestResult::from_bool(BitsOp::apply(op, &[&a_, &b_]) == None);
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -558,7 +800,7 @@ pub mod tests {
                                        c_: Literal|
      -> TestResult {
       let go =
-        || TestResult::from_bool(BitsOp::apply3(op, &a_, &b_, &c_) == None);
+        || TestResult::from_bool(BitsOp::apply(op, &[&a_, &b_, &c_]) == None);
       match test_arg_3 {
         TestArg3::A => {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
@@ -589,8 +831,16 @@ pub mod tests {
 
     match op {
       // Arity 1, valid is Bits.
-      BitsOp::Len | BitsOp::Head | BitsOp::Tail | BitsOp::ToBytes => {
-        test_apply1_none_on_invalid(Literal::Bits(b))
+      BitsOp::Len
+      | BitsOp::Head
+      | BitsOp::Tail
+      | BitsOp::ToBytes
+      | BitsOp::Not
+      | BitsOp::PopCount
+      | BitsOp::ToNat => test_apply1_none_on_invalid(Literal::Bits(b)),
+      // Arity 1, valid is Bytes.
+      BitsOp::FromBytes => {
+        test_apply1_none_on_invalid(Literal::Bytes(bits_to_bytes(&b).1))
       }
       // Arity 2, valid are Bool on a and Bits on b.
       BitsOp::Cons => {
@@ -602,7 +852,12 @@ pub mod tests {
         }
       }
       // Arity 2, valid are Nat on a and Bits on b.
-      BitsOp::Take | BitsOp::Drop | BitsOp::Remove | BitsOp::Index => {
+      BitsOp::Take
+      | BitsOp::Drop
+      | BitsOp::Remove
+      | BitsOp::Index
+      | BitsOp::Shl
+      | BitsOp::Shr => {
         if test_arg_2 {
           test_apply2_none_on_invalid(Nat(big(d)), a, Literal::Bits(b))
         }
@@ -610,8 +865,17 @@ pub mod tests {
           test_apply2_none_on_invalid(Literal::Bits(b), Nat(big(d)), a)
         }
       }
+      // Arity 2, valid are Nat on a and b.
+      BitsOp::FromNat => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(Nat(big(d)), a, Nat(big(d)))
+        }
+        else {
+          test_apply2_none_on_invalid(Nat(big(d)), Nat(big(d)), a)
+        }
+      }
       // Arity 2, valid are Bits on a and b.
-      BitsOp::Append => {
+      BitsOp::Append | BitsOp::And | BitsOp::Or | BitsOp::Xor => {
         if test_arg_2 {
           test_apply2_none_on_invalid(
             Literal::Bits(b.clone()),
@@ -639,6 +903,27 @@ pub mod tests {
           test_apply3_none_on_invalid(Literal::Bits(b), Nat(big(d)), Bool(c), a)
         }
       },
+      // Arity 3, valid are Nat on a and b and Bits on c.
+      BitsOp::Slice => match test_arg_3 {
+        TestArg3::A => test_apply3_none_on_invalid(
+          Nat(big(d)),
+          a,
+          Nat(big(d)),
+          Literal::Bits(b),
+        ),
+        TestArg3::B => test_apply3_none_on_invalid(
+          Nat(big(d)),
+          Nat(big(d)),
+          a,
+          Literal::Bits(b),
+        ),
+        TestArg3::C => test_apply3_none_on_invalid(
+          Literal::Bits(b),
+          Nat(big(d)),
+          Nat(big(d)),
+          a,
+        ),
+      },
     }
   }
 }
@@ -123,27 +123,20 @@ impl BoolOp {
     }
   }
 
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::Not, Bool(x)) => Some(Bool(!x)),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, Bool(x), Bool(y)) => Some(Bool(x == y)),
-      (Self::Lte, Bool(x), Bool(y)) => Some(Bool(x <= y)),
-      (Self::Lth, Bool(x), Bool(y)) => Some(Bool(x < y)),
-      (Self::Gte, Bool(x), Bool(y)) => Some(Bool(x >= y)),
-      (Self::Gth, Bool(x), Bool(y)) => Some(Bool(x > y)),
-      (Self::And, Bool(x), Bool(y)) => Some(Bool(x & y)),
-      (Self::Or, Bool(x), Bool(y)) => Some(Bool(x | y)),
-      (Self::Xor, Bool(x), Bool(y)) => Some(Bool(x ^ y)),
+    match (self, args) {
+      (Self::Not, [Bool(x)]) => Some(Bool(!x)),
+      (Self::Eql, [Bool(x), Bool(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [Bool(x), Bool(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [Bool(x), Bool(y)]) => Some(Bool(x < y)),
+      (Self::Gte, [Bool(x), Bool(y)]) => Some(Bool(x >= y)),
+      (Self::Gth, [Bool(x), Bool(y)]) => Some(Bool(x > y)),
+      (Self::And, [Bool(x), Bool(y)]) => Some(Bool(x & y)),
+      (Self::Or, [Bool(x), Bool(y)]) => Some(Bool(x | y)),
+      (Self::Xor, [Bool(x), Bool(y)]) => Some(Bool(x ^ y)),
       _ => None,
     }
   }
@@ -195,11 +188,13 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: BoolOp, a: bool, b: bool) -> TestResult {
     let apply1_bool = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(BoolOp::apply1(op, &Bool(a)) == expected)
+      TestResult::from_bool(BoolOp::apply(op, &[&Bool(a)]) == expected)
     };
 
     let apply2_bool_bool = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(BoolOp::apply2(op, &Bool(a), &Bool(b)) == expected)
+      TestResult::from_bool(
+        BoolOp::apply(op, &[&Bool(a), &Bool(b)]) == expected,
+      )
     };
 
     match op {
@@ -227,13 +222,14 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(BoolOp::apply1(op, &a) == None)
+        TestResult::from_bool(BoolOp::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(BoolOp::apply2(op, &a_, &b_) == None);
+        let go =
+          || TestResult::from_bool(BoolOp::apply(op, &[&a_, &b_]) == None);
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -278,15 +274,13 @@ pub mod tests {
   fn test_associativity(op: BoolOp, a: bool, b: bool, c: bool) -> TestResult {
     match op {
       BoolOp::Or | BoolOp::And => TestResult::from_bool(
-        BoolOp::apply2(
-          op,
+        BoolOp::apply(op, &[
           &Bool(a),
-          &BoolOp::apply2(op, &Bool(b), &Bool(c)).unwrap(),
-        ) == BoolOp::apply2(
-          op,
-          &BoolOp::apply2(op, &Bool(a), &Bool(b)).unwrap(),
+          &BoolOp::apply(op, &[&Bool(b), &Bool(c)]).unwrap(),
+        ]) == BoolOp::apply(op, &[
+          &BoolOp::apply(op, &[&Bool(a), &Bool(b)]).unwrap(),
           &Bool(c),
-        ),
+        ]),
       ),
       _ => TestResult::discard(),
     }
@@ -296,8 +290,8 @@ pub mod tests {
   fn test_commutativity(op: BoolOp, left: bool, right: bool) -> TestResult {
     match op {
       BoolOp::Or | BoolOp::And => TestResult::from_bool(
-        BoolOp::apply2(op, &Bool(left), &Bool(right))
-          == BoolOp::apply2(op, &Bool(left), &Bool(right)),
+        BoolOp::apply(op, &[&Bool(left), &Bool(right)])
+          == BoolOp::apply(op, &[&Bool(left), &Bool(right)]),
       ),
       _ => TestResult::discard(),
     }
@@ -305,22 +299,20 @@ pub mod tests {
 
   #[quickcheck]
   fn test_distributivity_of_and_over_or(a: bool, b: bool, c: bool) -> bool {
-    BoolOp::apply2(
-      BoolOp::And,
+    BoolOp::apply(BoolOp::And, &[
       &Bool(a),
-      &BoolOp::apply2(BoolOp::Or, &Bool(b), &Bool(c)).unwrap(),
-    ) == BoolOp::apply2(
-      BoolOp::Or,
-      &BoolOp::apply2(BoolOp::And, &Bool(a), &Bool(b)).unwrap(),
-      &BoolOp::apply2(BoolOp::And, &Bool(a), &Bool(c)).unwrap(),
-    )
+      &BoolOp::apply(BoolOp::Or, &[&Bool(b), &Bool(c)]).unwrap(),
+    ]) == BoolOp::apply(BoolOp::Or, &[
+      &BoolOp::apply(BoolOp::And, &[&Bool(a), &Bool(b)]).unwrap(),
+      &BoolOp::apply(BoolOp::And, &[&Bool(a), &Bool(c)]).unwrap(),
+    ])
   }
 
   #[quickcheck]
   fn test_idempotence(op: BoolOp, input: bool) -> TestResult {
     match op {
       BoolOp::Or | BoolOp::And => TestResult::from_bool(
-        BoolOp::apply2(op, &Bool(input), &Bool(input)) == Some(Bool(input)),
+        BoolOp::apply(op, &[&Bool(input), &Bool(input)]) == Some(Bool(input)),
       ),
       _ => TestResult::discard(),
     }
@@ -328,65 +320,60 @@ pub mod tests {
 
   #[quickcheck]
   fn test_absorption_1(a: bool, b: bool) -> bool {
-    BoolOp::apply2(
-      BoolOp::And,
+    BoolOp::apply(BoolOp::And, &[
       &Bool(a),
-      &BoolOp::apply2(BoolOp::Or, &Bool(a), &Bool(b)).unwrap(),
-    ) == Some(Bool(a))
+      &BoolOp::apply(BoolOp::Or, &[&Bool(a), &Bool(b)]).unwrap(),
+    ]) == Some(Bool(a))
   }
 
   #[quickcheck]
   fn test_absorption_2(a: bool, b: bool) -> bool {
-    BoolOp::apply2(
-      BoolOp::Or,
+    BoolOp::apply(BoolOp::Or, &[
       &Bool(a),
-      &BoolOp::apply2(BoolOp::And, &Bool(a), &Bool(b)).unwrap(),
-    ) == Some(Bool(a))
+      &BoolOp::apply(BoolOp::And, &[&Bool(a), &Bool(b)]).unwrap(),
+    ]) == Some(Bool(a))
   }
 
   #[quickcheck]
   fn test_distributivity_of_or_over_and(a: bool, b: bool, c: bool) -> bool {
-    BoolOp::apply2(
-      BoolOp::Or,
+    BoolOp::apply(BoolOp::Or, &[
       &Bool(a),
-      &BoolOp::apply2(BoolOp::And, &Bool(b), &Bool(c)).unwrap(),
-    ) == BoolOp::apply2(
-      BoolOp::And,
-      &BoolOp::apply2(BoolOp::Or, &Bool(a), &Bool(b)).unwrap(),
-      &BoolOp::apply2(BoolOp::Or, &Bool(a), &Bool(c)).unwrap(),
-    )
+      &BoolOp::apply(BoolOp::And, &[&Bool(b), &Bool(c)]).unwrap(),
+    ]) == BoolOp::apply(BoolOp::And, &[
+      &BoolOp::apply(BoolOp::Or, &[&Bool(a), &Bool(b)]).unwrap(),
+      &BoolOp::apply(BoolOp::Or, &[&Bool(a), &Bool(c)]).unwrap(),
+    ])
   }
 
   #[quickcheck]
   fn test_double_negation(input: bool) -> bool {
-    BoolOp::apply1(
-      BoolOp::Not,
-      &BoolOp::apply1(BoolOp::Not, &Bool(input)).unwrap(),
-    ) == Some(Bool(input))
+    BoolOp::apply(BoolOp::Not, &[
+      &BoolOp::apply(BoolOp::Not, &[&Bool(input)]).unwrap()
+    ]) == Some(Bool(input))
   }
 
   #[quickcheck]
   fn test_de_morgan_1(a: bool, b: bool) -> bool {
-    BoolOp::apply2(
-      BoolOp::And,
-      &BoolOp::apply1(BoolOp::Not, &Bool(a)).unwrap(),
-      &BoolOp::apply1(BoolOp::Not, &Bool(b)).unwrap(),
-    ) == BoolOp::apply1(
-      BoolOp::Not,
-      &BoolOp::apply2(BoolOp::Or, &Bool(a), &Bool(b)).unwrap(),
-    )
+    BoolOp::apply(BoolOp::And, &[
+      &BoolOp::apply(BoolOp::Not, &[&Bool(a)]).unwrap(),
+      &BoolOp::apply(BoolOp::Not, &[&Bool(b)]).unwrap(),
+    ]) == BoolOp::apply(BoolOp::Not, &[&BoolOp::apply(BoolOp::Or, &[
+      &Bool(a),
+      &Bool(b),
+    ])
+    .unwrap()])
   }
 
   #[quickcheck]
   fn test_de_morgan_2(a: bool, b: bool) -> bool {
-    BoolOp::apply2(
-      BoolOp::Or,
-      &BoolOp::apply1(BoolOp::Not, &Bool(a)).unwrap(),
-      &BoolOp::apply1(BoolOp::Not, &Bool(b)).unwrap(),
-    ) == BoolOp::apply1(
-      BoolOp::Not,
-      &BoolOp::apply2(BoolOp::And, &Bool(a), &Bool(b)).unwrap(),
-    )
+    BoolOp::apply(BoolOp::Or, &[
+      &BoolOp::apply(BoolOp::Not, &[&Bool(a)]).unwrap(),
+      &BoolOp::apply(BoolOp::Not, &[&Bool(b)]).unwrap(),
+    ]) == BoolOp::apply(BoolOp::Not, &[&BoolOp::apply(BoolOp::And, &[
+      &Bool(a),
+      &Bool(b),
+    ])
+    .unwrap()])
   }
 
   //#[test]
@@ -13,12 +13,18 @@ use crate::{
   ipld_error::IpldError,
   literal::Literal,
   parse,
-  prim::bits,
+  prim::{
+    bits,
+    nat::MAX_RESULT_BITS,
+  },
   term::Term,
   yatima,
 };
 
-use core::convert::TryFrom;
+use core::convert::{
+  TryFrom,
+  TryInto,
+};
 
 /// Primitive byte operations
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
@@ -34,6 +40,24 @@ pub enum BytesOp {
   Remove,
   Index,
   ToBits,
+  ReadU16Be,
+  ReadU16Le,
+  ReadU32Be,
+  ReadU32Le,
+  ReadU64Be,
+  ReadU64Le,
+  ReadU128Be,
+  ReadU128Le,
+  WriteU16Be,
+  WriteU16Le,
+  WriteU32Be,
+  WriteU32Le,
+  WriteU64Be,
+  WriteU64Le,
+  WriteU128Be,
+  WriteU128Le,
+  Slice,
+  Fill,
 }
 
 impl BytesOp {
@@ -51,6 +75,24 @@ impl BytesOp {
       Self::Remove => "remove".to_owned(),
       Self::Index => "index".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
+      Self::ReadU16Be => "read_u16_be".to_owned(),
+      Self::ReadU16Le => "read_u16_le".to_owned(),
+      Self::ReadU32Be => "read_u32_be".to_owned(),
+      Self::ReadU32Le => "read_u32_le".to_owned(),
+      Self::ReadU64Be => "read_u64_be".to_owned(),
+      Self::ReadU64Le => "read_u64_le".to_owned(),
+      Self::ReadU128Be => "read_u128_be".to_owned(),
+      Self::ReadU128Le => "read_u128_le".to_owned(),
+      Self::WriteU16Be => "write_u16_be".to_owned(),
+      Self::WriteU16Le => "write_u16_le".to_owned(),
+      Self::WriteU32Be => "write_u32_be".to_owned(),
+      Self::WriteU32Le => "write_u32_le".to_owned(),
+      Self::WriteU64Be => "write_u64_be".to_owned(),
+      Self::WriteU64Le => "write_u64_le".to_owned(),
+      Self::WriteU128Be => "write_u128_be".to_owned(),
+      Self::WriteU128Le => "write_u128_le".to_owned(),
+      Self::Slice => "slice".to_owned(),
+      Self::Fill => "fill".to_owned(),
     }
   }
 
@@ -68,6 +110,24 @@ impl BytesOp {
       "remove" => Some(Self::Remove),
       "index" => Some(Self::Index),
       "to_Bits" => Some(Self::ToBits),
+      "read_u16_be" => Some(Self::ReadU16Be),
+      "read_u16_le" => Some(Self::ReadU16Le),
+      "read_u32_be" => Some(Self::ReadU32Be),
+      "read_u32_le" => Some(Self::ReadU32Le),
+      "read_u64_be" => Some(Self::ReadU64Be),
+      "read_u64_le" => Some(Self::ReadU64Le),
+      "read_u128_be" => Some(Self::ReadU128Be),
+      "read_u128_le" => Some(Self::ReadU128Le),
+      "write_u16_be" => Some(Self::WriteU16Be),
+      "write_u16_le" => Some(Self::WriteU16Le),
+      "write_u32_be" => Some(Self::WriteU32Be),
+      "write_u32_le" => Some(Self::WriteU32Le),
+      "write_u64_be" => Some(Self::WriteU64Be),
+      "write_u64_le" => Some(Self::WriteU64Le),
+      "write_u128_be" => Some(Self::WriteU128Be),
+      "write_u128_le" => Some(Self::WriteU128Le),
+      "slice" => Some(Self::Slice),
+      "fill" => Some(Self::Fill),
       _ => None,
     }
   }
@@ -86,6 +146,24 @@ impl BytesOp {
       Self::Remove => yatima!("∀ #Nat #Bytes -> #Bytes"),
       Self::Index => yatima!("∀ #Nat #Bytes -> #U8"),
       Self::ToBits => yatima!("∀ #Nat #Bytes -> #Bits"),
+      Self::ReadU16Be => yatima!("∀ #Nat #Bytes -> #U16"),
+      Self::ReadU16Le => yatima!("∀ #Nat #Bytes -> #U16"),
+      Self::ReadU32Be => yatima!("∀ #Nat #Bytes -> #U32"),
+      Self::ReadU32Le => yatima!("∀ #Nat #Bytes -> #U32"),
+      Self::ReadU64Be => yatima!("∀ #Nat #Bytes -> #U64"),
+      Self::ReadU64Le => yatima!("∀ #Nat #Bytes -> #U64"),
+      Self::ReadU128Be => yatima!("∀ #Nat #Bytes -> #U128"),
+      Self::ReadU128Le => yatima!("∀ #Nat #Bytes -> #U128"),
+      Self::WriteU16Be => yatima!("∀ #Nat #U16 #Bytes -> #Bytes"),
+      Self::WriteU16Le => yatima!("∀ #Nat #U16 #Bytes -> #Bytes"),
+      Self::WriteU32Be => yatima!("∀ #Nat #U32 #Bytes -> #Bytes"),
+      Self::WriteU32Le => yatima!("∀ #Nat #U32 #Bytes -> #Bytes"),
+      Self::WriteU64Be => yatima!("∀ #Nat #U64 #Bytes -> #Bytes"),
+      Self::WriteU64Le => yatima!("∀ #Nat #U64 #Bytes -> #Bytes"),
+      Self::WriteU128Be => yatima!("∀ #Nat #U128 #Bytes -> #Bytes"),
+      Self::WriteU128Le => yatima!("∀ #Nat #U128 #Bytes -> #Bytes"),
+      Self::Slice => yatima!("∀ #Nat #Nat #Bytes -> #Bytes"),
+      Self::Fill => yatima!("∀ #Nat #U8 -> #Bytes"),
     }
   }
 
@@ -103,6 +181,24 @@ impl BytesOp {
       Self::Remove => Ipld::Integer(8),
       Self::Index => Ipld::Integer(9),
       Self::ToBits => Ipld::Integer(10),
+      Self::ReadU16Be => Ipld::Integer(11),
+      Self::ReadU16Le => Ipld::Integer(12),
+      Self::ReadU32Be => Ipld::Integer(13),
+      Self::ReadU32Le => Ipld::Integer(14),
+      Self::ReadU64Be => Ipld::Integer(15),
+      Self::ReadU64Le => Ipld::Integer(16),
+      Self::ReadU128Be => Ipld::Integer(17),
+      Self::ReadU128Le => Ipld::Integer(18),
+      Self::WriteU16Be => Ipld::Integer(19),
+      Self::WriteU16Le => Ipld::Integer(20),
+      Self::WriteU32Be => Ipld::Integer(21),
+      Self::WriteU32Le => Ipld::Integer(22),
+      Self::WriteU64Be => Ipld::Integer(23),
+      Self::WriteU64Le => Ipld::Integer(24),
+      Self::WriteU128Be => Ipld::Integer(25),
+      Self::WriteU128Le => Ipld::Integer(26),
+      Self::Slice => Ipld::Integer(27),
+      Self::Fill => Ipld::Integer(28),
     }
   }
 
@@ -120,6 +216,24 @@ impl BytesOp {
       Ipld::Integer(8) => Ok(Self::Remove),
       Ipld::Integer(9) => Ok(Self::Index),
       Ipld::Integer(10) => Ok(Self::ToBits),
+      Ipld::Integer(11) => Ok(Self::ReadU16Be),
+      Ipld::Integer(12) => Ok(Self::ReadU16Le),
+      Ipld::Integer(13) => Ok(Self::ReadU32Be),
+      Ipld::Integer(14) => Ok(Self::ReadU32Le),
+      Ipld::Integer(15) => Ok(Self::ReadU64Be),
+      Ipld::Integer(16) => Ok(Self::ReadU64Le),
+      Ipld::Integer(17) => Ok(Self::ReadU128Be),
+      Ipld::Integer(18) => Ok(Self::ReadU128Le),
+      Ipld::Integer(19) => Ok(Self::WriteU16Be),
+      Ipld::Integer(20) => Ok(Self::WriteU16Le),
+      Ipld::Integer(21) => Ok(Self::WriteU32Be),
+      Ipld::Integer(22) => Ok(Self::WriteU32Le),
+      Ipld::Integer(23) => Ok(Self::WriteU64Be),
+      Ipld::Integer(24) => Ok(Self::WriteU64Le),
+      Ipld::Integer(25) => Ok(Self::WriteU128Be),
+      Ipld::Integer(26) => Ok(Self::WriteU128Le),
+      Ipld::Integer(27) => Ok(Self::Slice),
+      Ipld::Integer(28) => Ok(Self::Fill),
       xs => Err(IpldError::BytesOp(xs.to_owned())),
     }
   }
@@ -138,51 +252,62 @@ impl BytesOp {
       Self::Remove => 2,
       Self::Index => 2,
       Self::ToBits => 2,
+      Self::ReadU16Be => 2,
+      Self::ReadU16Le => 2,
+      Self::ReadU32Be => 2,
+      Self::ReadU32Le => 2,
+      Self::ReadU64Be => 2,
+      Self::ReadU64Le => 2,
+      Self::ReadU128Be => 2,
+      Self::ReadU128Le => 2,
+      Self::WriteU16Be => 3,
+      Self::WriteU16Le => 3,
+      Self::WriteU32Be => 3,
+      Self::WriteU32Le => 3,
+      Self::WriteU64Be => 3,
+      Self::WriteU64Le => 3,
+      Self::WriteU128Be => 3,
+      Self::WriteU128Le => 3,
+      Self::Slice => 3,
+      Self::Fill => 2,
     }
   }
 
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::Len, Bytes(xs)) => Some(Nat(xs.len().into())),
-      (Self::Head, Bytes(xs)) => {
+    match (self, args) {
+      (Self::Len, [Bytes(xs)]) => Some(Nat(xs.len().into())),
+      (Self::Head, [Bytes(xs)]) => {
         let x = xs.last();
         x.map(|x| U8(*x))
       }
-      (Self::Tail, Bytes(xs)) => Some(Bytes(if xs.is_empty() {
+      (Self::Tail, [Bytes(xs)]) => Some(Bytes(if xs.is_empty() {
         vec![]
       }
       else {
         xs[0..xs.len() - 1].to_vec()
       })),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Cons, U8(x), Bytes(xs)) => {
+      (Self::Cons, [U8(x), Bytes(xs)]) => {
         let mut xs = xs.clone();
         xs.push(*x);
         Some(Bytes(xs))
       }
-      (Self::Drop, Nat(x), Bytes(xs)) => {
+      (Self::Drop, [Nat(x), Bytes(xs)]) => {
         let (_, ys) = safe_split(x, xs);
         Some(Bytes(ys))
       }
-      (Self::Take, Nat(x), Bytes(xs)) => {
+      (Self::Take, [Nat(x), Bytes(xs)]) => {
         let (xs, _) = safe_split(x, xs);
         Some(Bytes(xs))
       }
-      (Self::Append, Bytes(xs), Bytes(ys)) => {
+      (Self::Append, [Bytes(xs), Bytes(ys)]) => {
         let mut xs = xs.clone();
         xs.extend_from_slice(&ys);
         Some(Bytes(xs))
       }
-      (Self::Remove, Nat(idx), Bytes(xs)) => {
+      (Self::Remove, [Nat(idx), Bytes(xs)]) => {
         let idx = usize::try_from(idx);
         match idx {
           Ok(idx) if idx < xs.len() => {
@@ -193,7 +318,7 @@ impl BytesOp {
           _ => Some(Bytes(xs.clone())),
         }
       }
-      (Self::Index, Nat(idx), Bytes(xs)) => {
+      (Self::Index, [Nat(idx), Bytes(xs)]) => {
         let idx = usize::try_from(idx);
         match idx {
           Ok(idx) if idx < xs.len() => {
@@ -203,24 +328,42 @@ impl BytesOp {
           _ => None,
         }
       }
-      (Self::ToBits, Nat(idx), Bytes(xs)) => match usize::try_from(idx) {
+      (Self::ToBits, [Nat(idx), Bytes(xs)]) => match usize::try_from(idx) {
         Ok(x) => Some(Literal::Bits(bits::bytes_to_bits(x, xs))),
         _ => None,
       },
-      _ => None,
-    }
-  }
-
-  /// Applies a ternary operation to a literal and returns it if successful
-  pub fn apply3(
-    self,
-    x: &Literal,
-    y: &Literal,
-    z: &Literal,
-  ) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y, z) {
-      (Self::Insert, Nat(idx), U8(y), Bytes(xs)) => {
+      (Self::ReadU16Be, [Nat(idx), Bytes(xs)]) => {
+        read_bytes(idx, xs).map(|x| U16(u16::from_be_bytes(x)))
+      }
+      (Self::ReadU16Le, [Nat(idx), Bytes(xs)]) => {
+        read_bytes(idx, xs).map(|x| U16(u16::from_le_bytes(x)))
+      }
+      (Self::ReadU32Be, [Nat(idx), Bytes(xs)]) => {
+        read_bytes(idx, xs).map(|x| U32(u32::from_be_bytes(x)))
+      }
+      (Self::ReadU32Le, [Nat(idx), Bytes(xs)]) => {
+        read_bytes(idx, xs).map(|x| U32(u32::from_le_bytes(x)))
+      }
+      (Self::ReadU64Be, [Nat(idx), Bytes(xs)]) => {
+        read_bytes(idx, xs).map(|x| U64(u64::from_be_bytes(x)))
+      }
+      (Self::ReadU64Le, [Nat(idx), Bytes(xs)]) => {
+        read_bytes(idx, xs).map(|x| U64(u64::from_le_bytes(x)))
+      }
+      (Self::ReadU128Be, [Nat(idx), Bytes(xs)]) => {
+        read_bytes(idx, xs).map(|x| U128(u128::from_be_bytes(x)))
+      }
+      (Self::ReadU128Le, [Nat(idx), Bytes(xs)]) => {
+        read_bytes(idx, xs).map(|x| U128(u128::from_le_bytes(x)))
+      }
+      // Lengths past `MAX_RESULT_BITS` would exhaust memory, so they are stuck
+      (Self::Fill, [Nat(len), U8(x)]) => match u64::try_from(len) {
+        Ok(len) if len <= MAX_RESULT_BITS / 8 => {
+          Some(Bytes(vec![*x; len as usize]))
+        }
+        _ => None,
+      },
+      (Self::Insert, [Nat(idx), U8(y), Bytes(xs)]) => {
         let idx = usize::try_from(idx);
         match idx {
           Ok(idx) if idx < xs.len() => {
@@ -231,6 +374,33 @@ impl BytesOp {
           _ => Some(Bytes(xs.clone())),
         }
       }
+      (Self::WriteU16Be, [Nat(idx), U16(x), Bytes(xs)]) => {
+        write_bytes(idx, &x.to_be_bytes(), xs).map(Bytes)
+      }
+      (Self::WriteU16Le, [Nat(idx), U16(x), Bytes(xs)]) => {
+        write_bytes(idx, &x.to_le_bytes(), xs).map(Bytes)
+      }
+      (Self::WriteU32Be, [Nat(idx), U32(x), Bytes(xs)]) => {
+        write_bytes(idx, &x.to_be_bytes(), xs).map(Bytes)
+      }
+      (Self::WriteU32Le, [Nat(idx), U32(x), Bytes(xs)]) => {
+        write_bytes(idx, &x.to_le_bytes(), xs).map(Bytes)
+      }
+      (Self::WriteU64Be, [Nat(idx), U64(x), Bytes(xs)]) => {
+        write_bytes(idx, &x.to_be_bytes(), xs).map(Bytes)
+      }
+      (Self::WriteU64Le, [Nat(idx), U64(x), Bytes(xs)]) => {
+        write_bytes(idx, &x.to_le_bytes(), xs).map(Bytes)
+      }
+      (Self::WriteU128Be, [Nat(idx), U128(x), Bytes(xs)]) => {
+        write_bytes(idx, &x.to_be_bytes(), xs).map(Bytes)
+      }
+      (Self::WriteU128Le, [Nat(idx), U128(x), Bytes(xs)]) => {
+        write_bytes(idx, &x.to_le_bytes(), xs).map(Bytes)
+      }
+      (Self::Slice, [Nat(from), Nat(upto), Bytes(xs)]) => {
+        Some(Bytes(safe_slice(from, upto, xs)))
+      }
       _ => None,
     }
   }
@@ -249,6 +419,28 @@ pub fn safe_split(idx: &BigUint, xs: &Vec<u8>) -> (Vec<u8>, Vec<u8>) {
   }
 }
 
+/// Gets the bytes between two indices, clamping both to the length
+pub fn safe_slice(from: &BigUint, upto: &BigUint, xs: &Vec<u8>) -> Vec<u8> {
+  let (xs, _) = safe_split(upto, xs);
+  if from > upto { vec![] } else { safe_split(from, &xs).1 }
+}
+
+/// Gets the `N` bytes starting at an index if they are all in bounds
+pub fn read_bytes<const N: usize>(idx: &BigUint, xs: &[u8]) -> Option<[u8; N]> {
+  let idx = usize::try_from(idx).ok()?;
+  let end = idx.checked_add(N)?;
+  xs.get(idx..end)?.try_into().ok()
+}
+
+/// Overwrites the bytes starting at an index if they are all in bounds
+pub fn write_bytes(idx: &BigUint, ys: &[u8], xs: &[u8]) -> Option<Vec<u8>> {
+  let idx = usize::try_from(idx).ok()?;
+  let end = idx.checked_add(ys.len())?;
+  let mut xs = xs.to_vec();
+  xs.get_mut(idx..end)?.copy_from_slice(ys);
+  Some(xs)
+}
+
 impl fmt::Display for BytesOp {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     write!(f, "{}", self.symbol())
@@ -277,12 +469,16 @@ pub mod tests {
     Bits,
     Bytes,
     Nat,
+    U128,
+    U16,
+    U32,
+    U64,
     U8,
   };
   impl Arbitrary for BytesOp {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=10);
+      let gen: u32 = rng.gen_range(0..=28);
       match gen {
         0 => Self::Cons,
         1 => Self::Len,
@@ -294,7 +490,25 @@ pub mod tests {
         7 => Self::Insert,
         8 => Self::Remove,
         9 => Self::Index,
-        _ => Self::ToBits,
+        10 => Self::ToBits,
+        11 => Self::ReadU16Be,
+        12 => Self::ReadU16Le,
+        13 => Self::ReadU32Be,
+        14 => Self::ReadU32Le,
+        15 => Self::ReadU64Be,
+        16 => Self::ReadU64Le,
+        17 => Self::ReadU128Be,
+        18 => Self::ReadU128Le,
+        19 => Self::WriteU16Be,
+        20 => Self::WriteU16Le,
+        21 => Self::WriteU32Be,
+        22 => Self::WriteU32Le,
+        23 => Self::WriteU64Be,
+        24 => Self::WriteU64Le,
+        25 => Self::WriteU128Be,
+        26 => Self::WriteU128Le,
+        27 => Self::Slice,
+        _ => Self::Fill,
       }
     }
   }
@@ -307,6 +521,42 @@ pub mod tests {
     }
   }
 
+  #[test]
+  fn test_read_write() {
+    let xs = vec![1, 2, 3, 4];
+    assert_eq!(read_bytes::<2>(&1u64.into(), &xs), Some([2, 3]));
+    assert_eq!(read_bytes::<2>(&3u64.into(), &xs), None);
+    assert_eq!(write_bytes(&2u64.into(), &[9, 9], &xs), Some(vec![1, 2, 9, 9]));
+    assert_eq!(write_bytes(&3u64.into(), &[9, 9], &xs), None);
+    assert_eq!(
+      BytesOp::apply(BytesOp::ReadU16Le, &[
+        &Nat(0u64.into()),
+        &Bytes(xs.clone())
+      ]),
+      Some(U16(0x0201))
+    );
+    assert_eq!(
+      BytesOp::apply(BytesOp::WriteU32Be, &[
+        &Nat(0u64.into()),
+        &U32(0x0a0b0c0d),
+        &Bytes(xs.clone())
+      ]),
+      Some(Bytes(vec![0x0a, 0x0b, 0x0c, 0x0d]))
+    );
+    assert_eq!(safe_slice(&1u64.into(), &3u64.into(), &xs), vec![2, 3]);
+    assert_eq!(safe_slice(&2u64.into(), &9u64.into(), &xs), vec![3, 4]);
+    assert_eq!(safe_slice(&3u64.into(), &1u64.into(), &xs), Vec::<u8>::new());
+  }
+
+  #[test]
+  fn test_fill_len() {
+    let fill =
+      |len: u64| BytesOp::apply(BytesOp::Fill, &[&Nat(len.into()), &U8(0)]);
+    assert_eq!(fill(u64::MAX), None);
+    assert_eq!(fill(MAX_RESULT_BITS / 8 + 1), None);
+    assert_eq!(fill(3), Some(Bytes(vec![0; 3])));
+  }
+
   #[quickcheck]
   fn test_apply(
     op: BytesOp,
@@ -314,37 +564,58 @@ pub mod tests {
     b: u8,
     c: u64,
     d: Vec<u8>,
+    e: u128,
   ) -> TestResult {
     let big = BigUint::from;
     let apply1_bytes = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(BytesOp::apply1(op, &Bytes(a.clone())) == expected)
+      TestResult::from_bool(
+        BytesOp::apply(op, &[&Bytes(a.clone())]) == expected,
+      )
     };
 
     let apply2_u8_bytes = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BytesOp::apply2(op, &U8(b), &Bytes(a.clone())) == expected,
+        BytesOp::apply(op, &[&U8(b), &Bytes(a.clone())]) == expected,
       )
     };
 
     let apply2_nat_bytes = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BytesOp::apply2(op, &Nat(big(c)), &Bytes(a.clone())) == expected,
+        BytesOp::apply(op, &[&Nat(big(c)), &Bytes(a.clone())]) == expected,
       )
     };
 
     let apply2_bytes_bytes = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BytesOp::apply2(op, &Bytes(a.clone()), &Bytes(d.clone())) == expected,
+        BytesOp::apply(op, &[&Bytes(a.clone()), &Bytes(d.clone())]) == expected,
       )
     };
 
     let apply3_nat_u8_bytes = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        BytesOp::apply3(op, &Nat(big(c)), &U8(b), &Bytes(a.clone()))
+        BytesOp::apply(op, &[&Nat(big(c)), &U8(b), &Bytes(a.clone())])
           == expected,
       )
     };
 
+    let apply3_nat_word_bytes =
+      |word: Literal, expected: Option<Literal>| -> TestResult {
+        TestResult::from_bool(
+          BytesOp::apply(op, &[&Nat(big(c)), &word, &Bytes(a.clone())])
+            == expected,
+        )
+      };
+
+    let apply3_nat_nat_bytes = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        BytesOp::apply(op, &[
+          &Nat(big(c)),
+          &Nat(big(e as u64)),
+          &Bytes(a.clone()),
+        ]) == expected,
+      )
+    };
+
     let from_bool = TestResult::from_bool;
 
     match op {
@@ -398,16 +669,15 @@ pub mod tests {
         }
       }
       BytesOp::ToBits => {
-        let bits = BytesOp::apply1(op, &Bytes(a.clone()));
+        let bits = BytesOp::apply(op, &[&Bytes(a.clone())]);
         match bits {
           None => apply1_bytes(None),
           Some(bits_) => match bits_.clone() {
             Bits(_) => {
-              let bytes = BitsOp::apply2(
-                BitsOp::ToBytes,
+              let bytes = BitsOp::apply(BitsOp::ToBytes, &[
                 &Nat(big(a.len().try_into().unwrap())),
                 &bits_,
-              );
+              ]);
               match bytes {
                 None => apply1_bytes(None),
                 Some(bytes_) => from_bool(bytes_ == Bytes(a.clone())),
@@ -417,6 +687,83 @@ pub mod tests {
           },
         }
       }
+      BytesOp::ReadU16Be => apply2_nat_bytes(
+        read_bytes(&big(c), &a).map(|x| U16(u16::from_be_bytes(x))),
+      ),
+      BytesOp::ReadU16Le => apply2_nat_bytes(
+        read_bytes(&big(c), &a).map(|x| U16(u16::from_le_bytes(x))),
+      ),
+      BytesOp::ReadU32Be => apply2_nat_bytes(
+        read_bytes(&big(c), &a).map(|x| U32(u32::from_be_bytes(x))),
+      ),
+      BytesOp::ReadU32Le => apply2_nat_bytes(
+        read_bytes(&big(c), &a).map(|x| U32(u32::from_le_bytes(x))),
+      ),
+      BytesOp::ReadU64Be => apply2_nat_bytes(
+        read_bytes(&big(c), &a).map(|x| U64(u64::from_be_bytes(x))),
+      ),
+      BytesOp::ReadU64Le => apply2_nat_bytes(
+        read_bytes(&big(c), &a).map(|x| U64(u64::from_le_bytes(x))),
+      ),
+      BytesOp::ReadU128Be => apply2_nat_bytes(
+        read_bytes(&big(c), &a).map(|x| U128(u128::from_be_bytes(x))),
+      ),
+      BytesOp::ReadU128Le => apply2_nat_bytes(
+        read_bytes(&big(c), &a).map(|x| U128(u128::from_le_bytes(x))),
+      ),
+      BytesOp::WriteU16Be => {
+        let x = e as u16;
+        let res = write_bytes(&big(c), &x.to_be_bytes(), &a).map(Bytes);
+        apply3_nat_word_bytes(U16(x), res)
+      }
+      BytesOp::WriteU16Le => {
+        let x = e as u16;
+        let res = write_bytes(&big(c), &x.to_le_bytes(), &a).map(Bytes);
+        apply3_nat_word_bytes(U16(x), res)
+      }
+      BytesOp::WriteU32Be => {
+        let x = e as u32;
+        let res = write_bytes(&big(c), &x.to_be_bytes(), &a).map(Bytes);
+        apply3_nat_word_bytes(U32(x), res)
+      }
+      BytesOp::WriteU32Le => {
+        let x = e as u32;
+        let res = write_bytes(&big(c), &x.to_le_bytes(), &a).map(Bytes);
+        apply3_nat_word_bytes(U32(x), res)
+      }
+      BytesOp::WriteU64Be => {
+        let x = e as u64;
+        let res = write_bytes(&big(c), &x.to_be_bytes(), &a).map(Bytes);
+        apply3_nat_word_bytes(U64(x), res)
+      }
+      BytesOp::WriteU64Le => {
+        let x = e as u64;
+        let res = write_bytes(&big(c), &x.to_le_bytes(), &a).map(Bytes);
+        apply3_nat_word_bytes(U64(x), res)
+      }
+      BytesOp::WriteU128Be => {
+        let x = e;
+        let res = write_bytes(&big(c), &x.to_be_bytes(), &a).map(Bytes);
+        apply3_nat_word_bytes(U128(x), res)
+      }
+      BytesOp::WriteU128Le => {
+        let x = e;
+        let res = write_bytes(&big(c), &x.to_le_bytes(), &a).map(Bytes);
+        apply3_nat_word_bytes(U128(x), res)
+      }
+      BytesOp::Slice => apply3_nat_nat_bytes(Some(Bytes(safe_slice(
+        &big(c),
+        &big(e as u64),
+        &a,
+      )))),
+      BytesOp::Fill => {
+        // Only lengths small enough to allocate
+        let len = c % 4096;
+        TestResult::from_bool(
+          BytesOp::apply(op, &[&Nat(big(len)), &U8(b)])
+            == Some(Bytes(vec![b; len as usize])),
+        )
+      }
     }
   }
 
@@ -436,14 +783,14 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(BytesOp::apply1(op, &a) == None)
+        TestResult::from_bool(BytesOp::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
         let go =
-          || TestResult::from_bool(BytesOp::apply2(op, &a_, &b_) == None);
+          || TestResult::from_bool(BytesOp::apply(op, &[&a_, &b_]) == None);
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -468,7 +815,7 @@ pub mod tests {
                                        c_: Literal|
      -> TestResult {
       let go =
-        || TestResult::from_bool(BytesOp::apply3(op, &a_, &b_, &c_) == None);
+        || TestResult::from_bool(BytesOp::apply(op, &[&a_, &b_, &c_]) == None);
       match test_arg_3 {
         TestArg3::A => {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
@@ -516,7 +863,15 @@ pub mod tests {
       | BytesOp::Drop
       | BytesOp::Remove
       | BytesOp::Index
-      | BytesOp::ToBits => {
+      | BytesOp::ToBits
+      | BytesOp::ReadU16Be
+      | BytesOp::ReadU16Le
+      | BytesOp::ReadU32Be
+      | BytesOp::ReadU32Le
+      | BytesOp::ReadU64Be
+      | BytesOp::ReadU64Le
+      | BytesOp::ReadU128Be
+      | BytesOp::ReadU128Le => {
         if test_arg_2 {
           test_apply2_none_on_invalid(Nat(big(d)), a, Bytes(b))
         }
@@ -545,6 +900,54 @@ pub mod tests {
           test_apply3_none_on_invalid(Bytes(b), Nat(big(d)), U8(c), a)
         }
       },
+      // Arity 3, valid are Nat on a, an unsigned word on b and Bytes on c.
+      BytesOp::WriteU16Be
+      | BytesOp::WriteU16Le
+      | BytesOp::WriteU32Be
+      | BytesOp::WriteU32Le
+      | BytesOp::WriteU64Be
+      | BytesOp::WriteU64Le
+      | BytesOp::WriteU128Be
+      | BytesOp::WriteU128Le => {
+        let word = match op {
+          BytesOp::WriteU16Be | BytesOp::WriteU16Le => U16(d as u16),
+          BytesOp::WriteU32Be | BytesOp::WriteU32Le => U32(d as u32),
+          BytesOp::WriteU64Be | BytesOp::WriteU64Le => U64(d),
+          _ => U128(d.into()),
+        };
+        match test_arg_3 {
+          TestArg3::A => {
+            test_apply3_none_on_invalid(Nat(big(d)), a, word, Bytes(b))
+          }
+          TestArg3::B => {
+            test_apply3_none_on_invalid(word, Nat(big(d)), a, Bytes(b))
+          }
+          TestArg3::C => {
+            test_apply3_none_on_invalid(Bytes(b), Nat(big(d)), word, a)
+          }
+        }
+      }
+      // Arity 3, valid are Nat on a and b and Bytes on c.
+      BytesOp::Slice => match test_arg_3 {
+        TestArg3::A => {
+          test_apply3_none_on_invalid(Nat(big(d)), a, Nat(big(d)), Bytes(b))
+        }
+        TestArg3::B => {
+          test_apply3_none_on_invalid(Nat(big(d)), Nat(big(d)), a, Bytes(b))
+        }
+        TestArg3::C => {
+          test_apply3_none_on_invalid(Bytes(b), Nat(big(d)), Nat(big(d)), a)
+        }
+      },
+      // Arity 2, valid are Nat on a and U8 on b.
+      BytesOp::Fill => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(Nat(big(d)), a, U8(c))
+        }
+        else {
+          test_apply2_none_on_invalid(U8(c), Nat(big(d)), a)
+        }
+      }
     }
   }
 }
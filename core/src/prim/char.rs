@@ -287,67 +287,64 @@ impl CharOp {
     }
   }
 
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::FromU32, U32(x)) => char::from_u32(*x).map(Char),
-      (Self::ToU32, Char(x)) => Some(U32((*x).into())),
-      (Self::IsAlphabetic, Char(x)) => Some(Bool(x.is_alphabetic())),
-      (Self::IsAlphanumeric, Char(x)) => Some(Bool(x.is_alphanumeric())),
-      (Self::IsAscii, Char(x)) => Some(Bool(x.is_ascii())),
-      (Self::IsAsciiAlphabetic, Char(x)) => Some(Bool(x.is_ascii_alphabetic())),
-      (Self::IsAsciiAlphanumeric, Char(x)) => {
+    match (self, args) {
+      (Self::FromU32, [U32(x)]) => char::from_u32(*x).map(Char),
+      (Self::ToU32, [Char(x)]) => Some(U32((*x).into())),
+      (Self::IsAlphabetic, [Char(x)]) => Some(Bool(x.is_alphabetic())),
+      (Self::IsAlphanumeric, [Char(x)]) => Some(Bool(x.is_alphanumeric())),
+      (Self::IsAscii, [Char(x)]) => Some(Bool(x.is_ascii())),
+      (Self::IsAsciiAlphabetic, [Char(x)]) => {
+        Some(Bool(x.is_ascii_alphabetic()))
+      }
+      (Self::IsAsciiAlphanumeric, [Char(x)]) => {
         Some(Bool(x.is_ascii_alphanumeric()))
       }
-      (Self::IsAsciiControl, Char(x)) => Some(Bool(x.is_ascii_control())),
-      (Self::IsAsciiDigit, Char(x)) => Some(Bool(x.is_ascii_digit())),
-      (Self::IsAsciiGraphic, Char(x)) => Some(Bool(x.is_ascii_graphic())),
-      (Self::IsAsciiHexDigit, Char(x)) => Some(Bool(x.is_ascii_hexdigit())),
-      (Self::IsAsciiLowerCase, Char(x)) => Some(Bool(x.is_ascii_lowercase())),
-      (Self::IsAsciiPunctuation, Char(x)) => {
+      (Self::IsAsciiControl, [Char(x)]) => Some(Bool(x.is_ascii_control())),
+      (Self::IsAsciiDigit, [Char(x)]) => Some(Bool(x.is_ascii_digit())),
+      (Self::IsAsciiGraphic, [Char(x)]) => Some(Bool(x.is_ascii_graphic())),
+      (Self::IsAsciiHexDigit, [Char(x)]) => Some(Bool(x.is_ascii_hexdigit())),
+      (Self::IsAsciiLowerCase, [Char(x)]) => Some(Bool(x.is_ascii_lowercase())),
+      (Self::IsAsciiPunctuation, [Char(x)]) => {
         Some(Bool(x.is_ascii_punctuation()))
       }
-      (Self::IsAsciiUpperCase, Char(x)) => Some(Bool(x.is_ascii_uppercase())),
-      (Self::IsAsciiWhitespace, Char(x)) => Some(Bool(x.is_ascii_whitespace())),
-      (Self::IsControl, Char(x)) => Some(Bool(x.is_control())),
-      (Self::IsLowercase, Char(x)) => Some(Bool(x.is_lowercase())),
-      (Self::IsNumeric, Char(x)) => Some(Bool(x.is_numeric())),
-      (Self::IsUppercase, Char(x)) => Some(Bool(x.is_uppercase())),
-      (Self::IsWhitespace, Char(x)) => Some(Bool(x.is_whitespace())),
-      (Self::LenUTF8, Char(x)) => Some(Nat(x.len_utf8().into())),
-      (Self::LenUTF16, Char(x)) => Some(Nat(x.len_utf16().into())),
-      (Self::ToAsciiLowercase, Char(x)) => Some(Char(x.to_ascii_lowercase())),
-      (Self::ToAsciiUppercase, Char(x)) => Some(Char(x.to_ascii_uppercase())),
-      (Self::ToLowercase, Char(x)) => {
+      (Self::IsAsciiUpperCase, [Char(x)]) => Some(Bool(x.is_ascii_uppercase())),
+      (Self::IsAsciiWhitespace, [Char(x)]) => {
+        Some(Bool(x.is_ascii_whitespace()))
+      }
+      (Self::IsControl, [Char(x)]) => Some(Bool(x.is_control())),
+      (Self::IsLowercase, [Char(x)]) => Some(Bool(x.is_lowercase())),
+      (Self::IsNumeric, [Char(x)]) => Some(Bool(x.is_numeric())),
+      (Self::IsUppercase, [Char(x)]) => Some(Bool(x.is_uppercase())),
+      (Self::IsWhitespace, [Char(x)]) => Some(Bool(x.is_whitespace())),
+      (Self::LenUTF8, [Char(x)]) => Some(Nat(x.len_utf8().into())),
+      (Self::LenUTF16, [Char(x)]) => Some(Nat(x.len_utf16().into())),
+      (Self::ToAsciiLowercase, [Char(x)]) => Some(Char(x.to_ascii_lowercase())),
+      (Self::ToAsciiUppercase, [Char(x)]) => Some(Char(x.to_ascii_uppercase())),
+      (Self::ToLowercase, [Char(x)]) => {
         Some(Text(x.to_lowercase().to_string().into()))
       }
-      (Self::ToUppercase, Char(x)) => {
+      (Self::ToUppercase, [Char(x)]) => {
         Some(Text(x.to_uppercase().to_string().into()))
       }
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      // TODO: Hardcoding the maximum radix here is probably bad, not sure how
+      // TODO: Hardcoding the radix bounds here is probably bad, not sure how
       // to do it differently though.
-      (Self::IsDigit, Char(x), U32(y)) => {
-        if *y > 36 {
+      (Self::IsDigit, [Char(x), U32(y)]) => {
+        if !(2..=36).contains(y) {
           None
         }
         else {
           Some(Bool(x.is_digit(*y)))
         }
       }
-      (Self::Eql, Char(x), Char(y)) => Some(Bool(x == y)),
-      (Self::Lte, Char(x), Char(y)) => Some(Bool(x <= y)),
-      (Self::Lth, Char(x), Char(y)) => Some(Bool(x < y)),
-      (Self::Gth, Char(x), Char(y)) => Some(Bool(x > y)),
-      (Self::Gte, Char(x), Char(y)) => Some(Bool(x >= y)),
+      (Self::Eql, [Char(x), Char(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [Char(x), Char(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [Char(x), Char(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [Char(x), Char(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [Char(x), Char(y)]) => Some(Bool(x >= y)),
       _ => None,
     }
   }
@@ -428,18 +425,20 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: CharOp, a: u32, b: char, c: char) -> TestResult {
     let apply1_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(CharOp::apply1(op, &U32(a)) == expected)
+      TestResult::from_bool(CharOp::apply(op, &[&U32(a)]) == expected)
     };
 
     let apply1_char = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(CharOp::apply1(op, &Char(b)) == expected)
+      TestResult::from_bool(CharOp::apply(op, &[&Char(b)]) == expected)
     };
 
     let apply2_char_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(CharOp::apply2(op, &Char(b), &U32(a)) == expected)
+      TestResult::from_bool(CharOp::apply(op, &[&Char(b), &U32(a)]) == expected)
     };
     let apply2_char_char = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(CharOp::apply2(op, &Char(b), &Char(c)) == expected)
+      TestResult::from_bool(
+        CharOp::apply(op, &[&Char(b), &Char(c)]) == expected,
+      )
     };
 
     match op {
@@ -472,9 +471,9 @@ pub mod tests {
       }
       CharOp::IsControl => apply1_char(Some(Bool(b.is_control()))),
       CharOp::IsDigit => apply2_char_u32(
-        // TODO: Hardcoding the maximum radix here is probably bad, not sure
+        // TODO: Hardcoding the radix bounds here is probably bad, not sure
         // how to do it differently though.
-        if a > 36 { None } else { Some(Bool(b.is_digit(a))) },
+        if !(2..=36).contains(&a) { None } else { Some(Bool(b.is_digit(a))) },
       ),
       CharOp::IsLowercase => apply1_char(Some(Bool(b.is_lowercase()))),
       CharOp::IsNumeric => apply1_char(Some(Bool(b.is_numeric()))),
@@ -515,13 +514,14 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(CharOp::apply1(op, &a) == None)
+        TestResult::from_bool(CharOp::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(CharOp::apply2(op, &a_, &b_) == None);
+        let go =
+          || TestResult::from_bool(CharOp::apply(op, &[&a_, &b_]) == None);
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -0,0 +1,300 @@
+use sp_ipld::Ipld;
+use sp_ropey::Rope;
+use sp_std::{
+  borrow::ToOwned,
+  convert::TryFrom,
+  fmt,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::{
+  defs,
+  ipld::hash::{
+    digest,
+    Hasher,
+    DAG_CBOR,
+  },
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  prim::option_term,
+  term::Term,
+  yatima,
+};
+
+/// Primitive operations on content identifiers, so that programs can address
+/// blocks of the package graph without treating CIDs as opaque text
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum CidOp {
+  Of,
+  Parse,
+  FromText,
+  ToText,
+  FromBytes,
+  ToBytes,
+  Codec,
+  HashCode,
+  Hash,
+  Eql,
+}
+
+impl CidOp {
+  /// Gets the syntax string of a CID operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Of => "of".to_owned(),
+      Self::Parse => "parse".to_owned(),
+      Self::FromText => "from_text".to_owned(),
+      Self::ToText => "to_text".to_owned(),
+      Self::FromBytes => "from_bytes".to_owned(),
+      Self::ToBytes => "to_bytes".to_owned(),
+      Self::Codec => "codec".to_owned(),
+      Self::HashCode => "hash_code".to_owned(),
+      Self::Hash => "hash".to_owned(),
+      Self::Eql => "eql".to_owned(),
+    }
+  }
+
+  /// Gets a CID operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "of" => Some(Self::Of),
+      "parse" => Some(Self::Parse),
+      "from_text" => Some(Self::FromText),
+      "to_text" => Some(Self::ToText),
+      "from_bytes" => Some(Self::FromBytes),
+      "to_bytes" => Some(Self::ToBytes),
+      "codec" => Some(Self::Codec),
+      "hash_code" => Some(Self::HashCode),
+      "hash" => Some(Self::Hash),
+      "eql" => Some(Self::Eql),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of a CID operation
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Of => yatima!("∀ #Bytes -> #Cid"),
+      Self::Parse => {
+        yatima!("∀ #Text (0 P: Type) (none: P) (some: ∀ #Cid -> P) -> P")
+      }
+      Self::FromText => yatima!("∀ #Text -> #Cid"),
+      Self::ToText => yatima!("∀ #Cid -> #Text"),
+      Self::FromBytes => {
+        yatima!("∀ #Bytes (0 P: Type) (none: P) (some: ∀ #Cid -> P) -> P")
+      }
+      Self::ToBytes => yatima!("∀ #Cid -> #Bytes"),
+      Self::Codec => yatima!("∀ #Cid -> #U64"),
+      Self::HashCode => yatima!("∀ #Cid -> #U64"),
+      Self::Hash => yatima!("∀ #Cid -> #Bytes"),
+      Self::Eql => yatima!("∀ #Cid #Cid -> #Bool"),
+    }
+  }
+
+  /// Converts a CID operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Of => Ipld::Integer(0),
+      Self::Parse => Ipld::Integer(1),
+      Self::FromText => Ipld::Integer(2),
+      Self::ToText => Ipld::Integer(3),
+      Self::FromBytes => Ipld::Integer(4),
+      Self::ToBytes => Ipld::Integer(5),
+      Self::Codec => Ipld::Integer(6),
+      Self::HashCode => Ipld::Integer(7),
+      Self::Hash => Ipld::Integer(8),
+      Self::Eql => Ipld::Integer(9),
+    }
+  }
+
+  /// Converts an IPLD object into a CID operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Of),
+      Ipld::Integer(1) => Ok(Self::Parse),
+      Ipld::Integer(2) => Ok(Self::FromText),
+      Ipld::Integer(3) => Ok(Self::ToText),
+      Ipld::Integer(4) => Ok(Self::FromBytes),
+      Ipld::Integer(5) => Ok(Self::ToBytes),
+      Ipld::Integer(6) => Ok(Self::Codec),
+      Ipld::Integer(7) => Ok(Self::HashCode),
+      Ipld::Integer(8) => Ok(Self::Hash),
+      Ipld::Integer(9) => Ok(Self::Eql),
+      xs => Err(IpldError::CidOp(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Of => 1,
+      Self::Parse => 1,
+      Self::FromText => 1,
+      Self::ToText => 1,
+      Self::FromBytes => 1,
+      Self::ToBytes => 1,
+      Self::Codec => 1,
+      Self::HashCode => 1,
+      Self::Hash => 1,
+      Self::Eql => 2,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Of, [Bytes(xs)]) => {
+        Some(Cid(digest(DAG_CBOR, Hasher::default(), xs)))
+      }
+      (Self::FromText, [Text(xs)]) => {
+        parse::parse_cid_str(&xs.to_string()).ok().map(Cid)
+      }
+      (Self::ToText, [Cid(x)]) => Some(Text(Rope::from(x.to_string()))),
+      (Self::ToBytes, [Cid(x)]) => Some(Bytes(x.to_bytes())),
+      (Self::Codec, [Cid(x)]) => Some(U64(x.codec())),
+      (Self::HashCode, [Cid(x)]) => Some(U64(x.hash().code())),
+      (Self::Hash, [Cid(x)]) => Some(Bytes(x.hash().digest().to_vec())),
+      (Self::Eql, [Cid(x), Cid(y)]) => Some(Bool(x == y)),
+      _ => None,
+    }
+  }
+
+  /// Applies an operation whose result is a term rather than a literal to
+  /// as many literals as its arity and returns it if successful
+  pub fn apply_term(self, args: &[&Literal]) -> Option<Term> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Parse, [Text(xs)]) => {
+        let cid = parse::parse_cid_str(&xs.to_string()).ok();
+        Some(option_term(cid.map(Cid)))
+      }
+      (Self::FromBytes, [Bytes(xs)]) => {
+        let cid = sp_cid::Cid::try_from(xs.as_slice()).ok();
+        Some(option_term(cid.map(Cid)))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for CidOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::tests::arbitrary_cid;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  use sp_ipld::{
+    dag_cbor::DagCborCodec,
+    Codec,
+  };
+  use sp_std::vec::Vec;
+  impl Arbitrary for CidOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=9);
+      match gen {
+        0 => Self::Of,
+        1 => Self::Parse,
+        2 => Self::FromText,
+        3 => Self::ToText,
+        4 => Self::FromBytes,
+        5 => Self::ToBytes,
+        6 => Self::Codec,
+        7 => Self::HashCode,
+        8 => Self::Hash,
+        _ => Self::Eql,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn cid_op_ipld(x: CidOp) -> bool {
+    match CidOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn test_text_bytes_roundtrip(xs: Vec<u8>) -> bool {
+    let cid = Literal::Cid(digest(0x55, Hasher::default(), &xs));
+    let text = CidOp::apply(CidOp::ToText, &[&cid]).unwrap();
+    let bytes = CidOp::apply(CidOp::ToBytes, &[&cid]).unwrap();
+    CidOp::apply(CidOp::FromText, &[&text]) == Some(cid.clone())
+      && CidOp::apply_term(CidOp::Parse, &[&text])
+        == Some(option_term(Some(cid.clone())))
+      && CidOp::apply_term(CidOp::FromBytes, &[&bytes])
+        == Some(option_term(Some(cid)))
+  }
+
+  #[test]
+  fn test_parts() {
+    use Literal::{
+      Bytes,
+      Cid,
+      Text,
+      U64,
+    };
+    let cid = arbitrary_cid(&mut Gen::new(10));
+    assert_eq!(CidOp::apply(CidOp::Codec, &[&Cid(cid)]), Some(U64(0x55)));
+    assert_eq!(
+      CidOp::apply(CidOp::HashCode, &[&Cid(cid)]),
+      Some(U64(cid.hash().code()))
+    );
+    assert_eq!(
+      CidOp::apply(CidOp::Hash, &[&Cid(cid)]),
+      Some(Bytes(cid.hash().digest().to_vec()))
+    );
+    let bad = Text(Rope::from("not a cid"));
+    assert_eq!(CidOp::apply(CidOp::FromText, &[&bad]), None);
+    assert_eq!(
+      CidOp::apply_term(CidOp::Parse, &[&bad]),
+      Some(option_term(None))
+    );
+  }
+
+  // `of` gives a serialized literal the same CID as its block
+  #[quickcheck]
+  fn test_of_agrees(x: Literal) -> bool {
+    let ipld = x.to_ipld();
+    let bytes = DagCborCodec.encode(&ipld).unwrap().into_inner();
+    CidOp::apply(CidOp::Of, &[&Literal::Bytes(bytes)])
+      == Some(Literal::Cid(sp_ipld::dag_cbor::cid(&ipld)))
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: CidOp, a: Literal) -> bool {
+    match a {
+      Literal::Bytes(_) | Literal::Text(_) => true,
+      _ => {
+        CidOp::apply(op, &[&a]) == None
+          && CidOp::apply_term(op, &[&a]) == None
+          && CidOp::apply(op, &[&a, &a]) == None
+      }
+    }
+  }
+
+  #[test]
+  fn test_eql() {
+    let x = Literal::Cid(arbitrary_cid(&mut Gen::new(10)));
+    let y = CidOp::apply(CidOp::Of, &[&Literal::Bytes(vec![])]).unwrap();
+    assert_eq!(CidOp::apply(CidOp::Eql, &[&x, &x]), Some(Literal::Bool(true)));
+    assert_eq!(CidOp::apply(CidOp::Eql, &[&x, &y]), Some(Literal::Bool(false)));
+  }
+}
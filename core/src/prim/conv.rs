@@ -0,0 +1,355 @@
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  convert::TryFrom,
+  fmt,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::{
+    LitType,
+    Literal,
+  },
+  parse,
+  position::Pos,
+  prim::u256::U256,
+  term::Term,
+  yatima,
+};
+
+use num_bigint::BigInt;
+
+/// The numeric literal types. There is a truncating and a checked conversion
+/// from each of these to each of the others
+pub const NUM_TYPES: [LitType; 13] = [
+  LitType::Nat,
+  LitType::Int,
+  LitType::U8,
+  LitType::U16,
+  LitType::U32,
+  LitType::U64,
+  LitType::U128,
+  LitType::U256,
+  LitType::I8,
+  LitType::I16,
+  LitType::I32,
+  LitType::I64,
+  LitType::I128,
+];
+
+/// How a conversion treats a value that the target type can't represent
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ConvMode {
+  /// Keeps the low bits of the two's complement value, like Rust's `as`, and
+  /// clamps negative values to zero in `#Nat`
+  Truncating,
+  /// Returns an option that is `none` when the value is out of range
+  Checked,
+}
+
+/// A conversion between two numeric types, such as `#U16.truncating_to_U8`
+/// or `#Int.checked_to_U64`. These are generated from `NUM_TYPES` rather than
+/// written out per type, so every pair is covered with the same naming and
+/// typing
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ConvOp {
+  pub mode: ConvMode,
+  pub from: LitType,
+  pub to: LitType,
+}
+
+impl ConvOp {
+  /// Makes a conversion between two distinct numeric types
+  pub fn new(mode: ConvMode, from: LitType, to: LitType) -> Option<Self> {
+    if from != to && NUM_TYPES.contains(&from) && NUM_TYPES.contains(&to) {
+      Some(ConvOp { mode, from, to })
+    }
+    else {
+      None
+    }
+  }
+
+  /// Gets the syntax string of a conversion, without the prefix of its source
+  /// type
+  pub fn symbol(self) -> String {
+    match self.mode {
+      ConvMode::Truncating => format!("truncating_to_{}", type_name(self.to)),
+      ConvMode::Checked => format!("checked_to_{}", type_name(self.to)),
+    }
+  }
+
+  /// Gets a conversion from its source type and syntax string
+  pub fn from_symbol(from: LitType, x: &str) -> Option<Self> {
+    let (mode, to) = if let Some(to) = x.strip_prefix("truncating_to_") {
+      (ConvMode::Truncating, to)
+    }
+    else if let Some(to) = x.strip_prefix("checked_to_") {
+      (ConvMode::Checked, to)
+    }
+    else {
+      return None;
+    };
+    let to = NUM_TYPES.iter().find(|t| type_name(**t) == to)?;
+    Self::new(mode, from, *to)
+  }
+
+  /// Gets a conversion from a full name such as `U16.truncating_to_U8`
+  pub fn from_name(x: &str) -> Option<Self> {
+    let mut parts = x.splitn(2, '.');
+    let from = parts.next()?;
+    let from = NUM_TYPES.iter().find(|t| type_name(**t) == from)?;
+    Self::from_symbol(*from, parts.next()?)
+  }
+
+  /// Returns the type of a conversion
+  pub fn type_of(self) -> Term {
+    let from = Term::LTy(Pos::None, self.from);
+    let to = Term::LTy(Pos::None, self.to);
+    match self.mode {
+      ConvMode::Truncating => yatima!("∀ #$0 -> #$1", from, to),
+      ConvMode::Checked => {
+        yatima!("∀ #$0 (0 P: Type) (none: P) (some: ∀ #$1 -> P) -> P", from, to)
+      }
+    }
+  }
+
+  /// Converts a conversion into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    let mode = match self.mode {
+      ConvMode::Truncating => Ipld::Integer(0),
+      ConvMode::Checked => Ipld::Integer(1),
+    };
+    Ipld::List(vec![mode, self.from.to_ipld(), self.to.to_ipld()])
+  }
+
+  /// Converts an IPLD object into a conversion
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    let err = || IpldError::ConvOp(ipld.to_owned());
+    match ipld {
+      Ipld::List(xs) => match xs.as_slice() {
+        [mode, from, to] => {
+          let mode = match mode {
+            Ipld::Integer(0) => ConvMode::Truncating,
+            Ipld::Integer(1) => ConvMode::Checked,
+            _ => return Err(err()),
+          };
+          let from = LitType::from_ipld(from).map_err(|_| err())?;
+          let to = LitType::from_ipld(to).map_err(|_| err())?;
+          Self::new(mode, from, to).ok_or_else(err)
+        }
+        _ => Err(err()),
+      },
+      _ => Err(err()),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 { 1 }
+
+  /// Applies a truncating conversion to a literal and returns it if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    match (self.mode, args) {
+      (ConvMode::Truncating, [x]) => truncate(self.to, to_int(self.from, x)?),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked conversion, returning `Some(None)` when the value is
+  /// out of range and `None` if the argument is invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    match (self.mode, args) {
+      (ConvMode::Checked, [x]) => {
+        Some(from_int(self.to, &to_int(self.from, x)?))
+      }
+      _ => None,
+    }
+  }
+}
+
+/// Gets the name of a literal type without its `#`
+pub fn type_name(typ: LitType) -> String {
+  let name = typ.to_string();
+  name.strip_prefix('#').unwrap_or(&name).to_owned()
+}
+
+/// Gets the value of a numeric literal of type `typ`
+pub fn to_int(typ: LitType, x: &Literal) -> Option<BigInt> {
+  match (typ, x) {
+    (LitType::Nat, Literal::Nat(x)) => Some(x.clone().into()),
+    (LitType::Int, Literal::Int(x)) => Some(x.clone()),
+    (LitType::U8, Literal::U8(x)) => Some((*x).into()),
+    (LitType::U16, Literal::U16(x)) => Some((*x).into()),
+    (LitType::U32, Literal::U32(x)) => Some((*x).into()),
+    (LitType::U64, Literal::U64(x)) => Some((*x).into()),
+    (LitType::U128, Literal::U128(x)) => Some((*x).into()),
+    (LitType::U256, Literal::U256(x)) => Some(x.to_biguint().into()),
+    (LitType::I8, Literal::I8(x)) => Some((*x).into()),
+    (LitType::I16, Literal::I16(x)) => Some((*x).into()),
+    (LitType::I32, Literal::I32(x)) => Some((*x).into()),
+    (LitType::I64, Literal::I64(x)) => Some((*x).into()),
+    (LitType::I128, Literal::I128(x)) => Some((*x).into()),
+    _ => None,
+  }
+}
+
+/// Makes a literal of type `typ` with the value `x`, if it's in range
+pub fn from_int(typ: LitType, x: &BigInt) -> Option<Literal> {
+  match typ {
+    LitType::Nat => x.to_biguint().map(Literal::Nat),
+    LitType::Int => Some(Literal::Int(x.clone())),
+    LitType::U8 => u8::try_from(x).ok().map(Literal::U8),
+    LitType::U16 => u16::try_from(x).ok().map(Literal::U16),
+    LitType::U32 => u32::try_from(x).ok().map(Literal::U32),
+    LitType::U64 => u64::try_from(x).ok().map(Literal::U64),
+    LitType::U128 => u128::try_from(x).ok().map(Literal::U128),
+    LitType::U256 => {
+      x.to_biguint().and_then(|x| U256::from_biguint(&x)).map(Literal::U256)
+    }
+    LitType::I8 => i8::try_from(x).ok().map(Literal::I8),
+    LitType::I16 => i16::try_from(x).ok().map(Literal::I16),
+    LitType::I32 => i32::try_from(x).ok().map(Literal::I32),
+    LitType::I64 => i64::try_from(x).ok().map(Literal::I64),
+    LitType::I128 => i128::try_from(x).ok().map(Literal::I128),
+    _ => None,
+  }
+}
+
+/// Returns the bit width and signedness of a fixed-width numeric type
+pub fn width(typ: LitType) -> Option<(usize, bool)> {
+  match typ {
+    LitType::U8 => Some((8, false)),
+    LitType::U16 => Some((16, false)),
+    LitType::U32 => Some((32, false)),
+    LitType::U64 => Some((64, false)),
+    LitType::U128 => Some((128, false)),
+    LitType::U256 => Some((256, false)),
+    LitType::I8 => Some((8, true)),
+    LitType::I16 => Some((16, true)),
+    LitType::I32 => Some((32, true)),
+    LitType::I64 => Some((64, true)),
+    LitType::I128 => Some((128, true)),
+    _ => None,
+  }
+}
+
+/// Makes a literal of type `typ` from `x`, wrapping it into the range of a
+/// fixed-width type
+pub fn truncate(typ: LitType, x: BigInt) -> Option<Literal> {
+  match (typ, width(typ)) {
+    (_, Some((bits, signed))) => {
+      let m = BigInt::from(1u8) << bits;
+      let mut x = ((x % &m) + &m) % &m;
+      if signed && x >= (&m >> 1) {
+        x -= &m;
+      }
+      from_int(typ, &x)
+    }
+    (LitType::Nat, None) => {
+      Some(Literal::Nat(x.to_biguint().unwrap_or_default()))
+    }
+    _ => from_int(typ, &x),
+  }
+}
+
+impl fmt::Display for ConvOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  impl Arbitrary for ConvOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let mode =
+        if rng.gen() { ConvMode::Truncating } else { ConvMode::Checked };
+      let from = rng.gen_range(0..NUM_TYPES.len());
+      let to = (from + rng.gen_range(1..NUM_TYPES.len())) % NUM_TYPES.len();
+      ConvOp::new(mode, NUM_TYPES[from], NUM_TYPES[to]).unwrap()
+    }
+  }
+
+  #[quickcheck]
+  fn conv_op_ipld(x: ConvOp) -> bool {
+    match ConvOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn conv_op_symbol(x: ConvOp) -> bool {
+    ConvOp::from_symbol(x.from, &x.symbol()) == Some(x)
+      && ConvOp::from_name(&format!("{}.{}", type_name(x.from), x.symbol()))
+        == Some(x)
+  }
+
+  #[test]
+  fn test_from_symbol_invalid() {
+    assert_eq!(ConvOp::from_symbol(LitType::U8, "truncating_to_U8"), None);
+    assert_eq!(ConvOp::from_symbol(LitType::U8, "checked_to_Bytes"), None);
+    assert_eq!(ConvOp::from_symbol(LitType::Text, "checked_to_U8"), None);
+    assert_eq!(ConvOp::from_symbol(LitType::U8, "to_U16"), None);
+    assert_eq!(ConvOp::from_name("U8"), None);
+  }
+
+  #[test]
+  fn test_truncate() {
+    use Literal::*;
+    let conv = |from, to, x: Literal| {
+      ConvOp::new(ConvMode::Truncating, from, to).unwrap().apply(&[&x])
+    };
+    assert_eq!(conv(LitType::U16, LitType::U8, U16(300)), Some(U8(44)));
+    assert_eq!(conv(LitType::I8, LitType::U8, I8(-1)), Some(U8(255)));
+    assert_eq!(conv(LitType::U8, LitType::I8, U8(255)), Some(I8(-1)));
+    assert_eq!(
+      conv(LitType::U128, LitType::I128, U128(u128::MAX)),
+      Some(I128(-1))
+    );
+    assert_eq!(
+      conv(LitType::I8, LitType::U256, I8(-1)),
+      Some(U256(crate::prim::u256::U256::MAX))
+    );
+    assert_eq!(conv(LitType::I8, LitType::Nat, I8(-5)), Some(Nat(0u64.into())));
+    assert_eq!(
+      conv(LitType::Int, LitType::I16, Int((-65537).into())),
+      Some(I16(-1))
+    );
+  }
+
+  // A checked conversion succeeds exactly when the truncating one keeps the
+  // value, and then they agree
+  #[quickcheck]
+  fn test_checked_agrees(op: ConvOp, x: i128) -> bool {
+    let x = truncate(op.from, x.into()).unwrap();
+    let truncating = ConvOp { mode: ConvMode::Truncating, ..op };
+    let checked = ConvOp { mode: ConvMode::Checked, ..op };
+    let y = truncating.apply(&[&x]).unwrap();
+    match checked.apply_checked(&[&x]).unwrap() {
+      Some(z) => z == y,
+      None => to_int(op.to, &y) != to_int(op.from, &x),
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: ConvOp, a: Literal) -> bool {
+    to_int(op.from, &a).is_some()
+      || (op.apply(&[&a]) == None && op.apply_checked(&[&a]) == None)
+  }
+}
@@ -0,0 +1,149 @@
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  convert::TryFrom,
+};
+
+use alloc::string::String;
+
+use crate::{
+  literal::Literal,
+  prim::{
+    bytes::BytesOp,
+    int::IntOp,
+    nat::NatOp,
+    Op,
+  },
+};
+
+/// Default cost of an operation before charging for the size of its
+/// arguments. Signatures and hashing are much slower than arithmetic, and
+/// `#Io` and `#Host` operations reach outside the evaluator
+pub fn base_cost(op: &Op) -> u64 {
+  match op {
+    Op::Ed25519(_) | Op::Secp256k1(_) => 1000,
+    Op::Cid(_) => 100,
+    Op::Io(_) | Op::Host(_) => 100,
+    Op::Conv(_) | Op::Show(_) | Op::FromText(_) => 2,
+    _ => 1,
+  }
+}
+
+/// Size of a literal in 64-bit words, rounded down, so that fixed width
+/// numbers are free and large values are charged by their length
+pub fn words(x: &Literal) -> u64 {
+  let bytes = match x {
+    Literal::Nat(x) => x.bits() / 8,
+    Literal::Int(x) => x.bits() / 8,
+    Literal::Bits(x) => x.len() as u64 / 8,
+    Literal::Bytes(x) => x.len() as u64,
+    Literal::Text(x) => x.len_bytes() as u64,
+    Literal::Map(x) => x.iter().map(|(k, v)| (k.len() + v.len()) as u64).sum(),
+    Literal::Set(x) => x.iter().map(|k| k.len() as u64).sum(),
+    Literal::Vector(x) => x.iter().map(|k| k.len() as u64).sum(),
+    Literal::Exception(tag, payload) => {
+      (tag.len_bytes() + payload.len()) as u64
+    }
+    _ => 0,
+  };
+  bytes / 8
+}
+
+/// Size in 64-bit words of the result of an operation that can be much
+/// larger than its arguments, estimated from the arguments, so that filling
+/// bytes or raising to a power is charged by what it builds
+pub fn result_words(op: &Op, args: &[&Literal]) -> u64 {
+  let bits = match (op, args) {
+    (Op::Nat(NatOp::Pow), [Literal::Nat(x), Literal::Nat(y)]) => {
+      u64::try_from(y).unwrap_or(u64::MAX).saturating_mul(x.bits())
+    }
+    (Op::Int(IntOp::Pow), [Literal::Int(x), Literal::Nat(y)]) => {
+      u64::try_from(y).unwrap_or(u64::MAX).saturating_mul(x.bits())
+    }
+    (Op::Nat(NatOp::Shl), [Literal::Nat(x), Literal::Nat(y)]) => {
+      u64::try_from(y).unwrap_or(u64::MAX).saturating_add(x.bits())
+    }
+    (Op::Bytes(BytesOp::Fill), [Literal::Nat(len), _]) => {
+      u64::try_from(len).unwrap_or(u64::MAX).saturating_mul(8)
+    }
+    _ => 0,
+  };
+  bits / 64
+}
+
+/// The gas charged for applying operations in `DAG::whnf_metered`. Unless
+/// overridden, an operation costs its `base_cost` plus the `words` of its
+/// arguments and the `result_words` of what it builds
+#[derive(Clone, Debug, Default)]
+pub struct CostModel {
+  /// Costs by operation symbol, such as `#U8.add`, replacing the defaults
+  pub overrides: BTreeMap<String, u64>,
+  /// Whether every operation costs one, so that gas counts reductions
+  pub unit: bool,
+}
+
+impl CostModel {
+  /// Makes the cost model with the default costs
+  pub fn new() -> Self { CostModel::default() }
+
+  /// Makes the cost model in which every operation is one reduction step
+  pub fn unit() -> Self { CostModel { overrides: BTreeMap::new(), unit: true } }
+
+  /// Overrides the cost of an operation
+  pub fn set(&mut self, op: &Op, cost: u64) -> &mut Self {
+    self.overrides.insert(op.symbol(), cost);
+    self
+  }
+
+  /// Gets the gas charged for applying an operation to its arguments
+  pub fn cost(&self, op: &Op, args: &[&Literal]) -> u64 {
+    if self.unit {
+      return 1;
+    }
+    if !self.overrides.is_empty() {
+      if let Some(cost) = self.overrides.get(&op.symbol()) {
+        return *cost;
+      }
+    }
+    op.cost(args)
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::prim::u8::U8Op;
+
+  #[test]
+  fn test_cost() {
+    let add = Op::U8(U8Op::Add);
+    let len = Op::Bytes(BytesOp::Len);
+    let x = Literal::U8(1);
+    let xs = Literal::Bytes(vec![0; 80]);
+    let mut costs = CostModel::new();
+    assert_eq!(costs.cost(&add, &[&x, &x]), 1);
+    assert_eq!(costs.cost(&len, &[&xs]), 11);
+    costs.set(&add, 5);
+    assert_eq!(costs.cost(&add, &[&x, &x]), 5);
+    assert_eq!(costs.cost(&len, &[&xs]), 11);
+    assert_eq!(CostModel::unit().cost(&len, &[&xs]), 1);
+  }
+
+  #[test]
+  fn test_cost_result() {
+    let fill = Op::Bytes(BytesOp::Fill);
+    let pow = Op::Nat(NatOp::Pow);
+    let costs = CostModel::new();
+    let n = |x: u64| Literal::Nat(x.into());
+    assert_eq!(costs.cost(&fill, &[&n(80), &Literal::U8(0)]), 11);
+    assert_eq!(costs.cost(&pow, &[&n(1 << 63), &n(64)]), 66);
+    assert_eq!(
+      costs.cost(&fill, &[&n(u64::MAX), &Literal::U8(0)]),
+      2 + u64::MAX / 64
+    );
+  }
+
+  #[quickcheck]
+  fn test_cost_positive(op: Op, x: Literal) -> bool {
+    CostModel::new().cost(&op, &[&x]) >= 1
+  }
+}
@@ -0,0 +1,237 @@
+#[cfg(feature = "crypto")]
+use core::convert::TryFrom;
+
+#[cfg(feature = "crypto")]
+use ed25519_dalek::{
+  Keypair,
+  PublicKey,
+  SecretKey,
+  Signature,
+  Signer,
+  Verifier,
+};
+use sp_ipld::Ipld;
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  term::Term,
+  yatima,
+};
+
+/// Primitive Ed25519 signature operations. Keys, messages and signatures are
+/// all `#Bytes`, and secret keys are the 32-byte seeds they are derived from.
+/// Without the `crypto` feature these still parse and typecheck, but don't
+/// reduce.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Ed25519Op {
+  PublicKey,
+  Sign,
+  Verify,
+}
+
+// Without `crypto` the apply functions are left with only their fallback arm
+#[cfg_attr(not(feature = "crypto"), allow(clippy::match_single_binding))]
+impl Ed25519Op {
+  /// Gets the syntax string of an Ed25519 operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::PublicKey => "public_key".to_owned(),
+      Self::Sign => "sign".to_owned(),
+      Self::Verify => "verify".to_owned(),
+    }
+  }
+
+  /// Gets an Ed25519 operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "public_key" => Some(Self::PublicKey),
+      "sign" => Some(Self::Sign),
+      "verify" => Some(Self::Verify),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of an Ed25519 operation. `sign` takes the seed and then
+  /// the message, and `verify` takes the public key, message and signature
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::PublicKey => yatima!("∀ #Bytes -> #Bytes"),
+      Self::Sign => yatima!("∀ #Bytes #Bytes -> #Bytes"),
+      Self::Verify => yatima!("∀ #Bytes #Bytes #Bytes -> #Bool"),
+    }
+  }
+
+  /// Converts an Ed25519 operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::PublicKey => Ipld::Integer(0),
+      Self::Sign => Ipld::Integer(1),
+      Self::Verify => Ipld::Integer(2),
+    }
+  }
+
+  /// Converts an IPLD object into an Ed25519 operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::PublicKey),
+      Ipld::Integer(1) => Ok(Self::Sign),
+      Ipld::Integer(2) => Ok(Self::Verify),
+      xs => Err(IpldError::Ed25519Op(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::PublicKey => 1,
+      Self::Sign => 2,
+      Self::Verify => 3,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    match (self, args) {
+      #[cfg(feature = "crypto")]
+      (Self::PublicKey, [Literal::Bytes(seed)]) => {
+        keypair(seed).map(|k| Literal::Bytes(k.public.to_bytes().to_vec()))
+      }
+      #[cfg(feature = "crypto")]
+      (Self::Sign, [Literal::Bytes(seed), Literal::Bytes(msg)]) => {
+        keypair(seed).map(|k| Literal::Bytes(k.sign(msg).to_bytes().to_vec()))
+      }
+      #[cfg(feature = "crypto")]
+      (
+        Self::Verify,
+        [Literal::Bytes(key), Literal::Bytes(msg), Literal::Bytes(sig)],
+      ) => Some(Literal::Bool(verify(key, msg, sig))),
+      _ => None,
+    }
+  }
+}
+
+/// Derives the keypair whose secret key is `seed`
+#[cfg(feature = "crypto")]
+pub fn keypair(seed: &[u8]) -> Option<Keypair> {
+  let secret = SecretKey::from_bytes(seed).ok()?;
+  let public = PublicKey::from(&secret);
+  Some(Keypair { secret, public })
+}
+
+/// Checks a signature, treating malformed keys and signatures as invalid
+#[cfg(feature = "crypto")]
+pub fn verify(key: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+  match (PublicKey::from_bytes(key), Signature::try_from(sig)) {
+    (Ok(key), Ok(sig)) => key.verify(msg, &sig).is_ok(),
+    _ => false,
+  }
+}
+
+impl fmt::Display for Ed25519Op {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  #[cfg(feature = "crypto")]
+  use sp_std::vec::Vec;
+  impl Arbitrary for Ed25519Op {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=2);
+      match gen {
+        0 => Self::PublicKey,
+        1 => Self::Sign,
+        _ => Self::Verify,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn ed25519_op_ipld(x: Ed25519Op) -> bool {
+    match Ed25519Op::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[cfg(feature = "crypto")]
+  fn hex(x: &str) -> Vec<u8> {
+    (0..x.len())
+      .step_by(2)
+      .map(|i| u8::from_str_radix(&x[i..i + 2], 16).unwrap())
+      .collect()
+  }
+
+  // The first test vector of RFC 8032, section 7.1
+  #[cfg(feature = "crypto")]
+  #[test]
+  fn test_rfc8032() {
+    use Literal::Bytes;
+    let seed =
+      hex("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60");
+    let public =
+      hex("d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a");
+    let sig = hex(
+      "e5564300c360ac729086e2cc806e828a84877f1eb8e5d974d873e065224901555fb882\
+       1590a33bacc61e39701cf9b46bd25bf5f0595bbe24655141438e7a100b",
+    );
+    assert_eq!(
+      Ed25519Op::apply(Ed25519Op::PublicKey, &[&Bytes(seed.clone())]),
+      Some(Bytes(public.clone()))
+    );
+    assert_eq!(
+      Ed25519Op::apply(Ed25519Op::Sign, &[&Bytes(seed), &Bytes(vec![])]),
+      Some(Bytes(sig.clone()))
+    );
+    assert!(verify(&public, &[], &sig));
+    assert!(!verify(&public, &[0], &sig));
+    assert!(!verify(&public[1..], &[], &sig));
+  }
+
+  #[cfg(feature = "crypto")]
+  #[quickcheck]
+  fn test_sign_verify(seed: u64, msg: Vec<u8>) -> bool {
+    use Literal::{
+      Bool,
+      Bytes,
+    };
+    let mut bytes = vec![0; 32];
+    bytes[..8].copy_from_slice(&seed.to_be_bytes());
+    let seed = Bytes(bytes);
+    let msg = Bytes(msg);
+    let key = Ed25519Op::apply(Ed25519Op::PublicKey, &[&seed]).unwrap();
+    let sig = Ed25519Op::apply(Ed25519Op::Sign, &[&seed, &msg]).unwrap();
+    Ed25519Op::apply(Ed25519Op::Verify, &[&key, &msg, &sig]) == Some(Bool(true))
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: Ed25519Op, a: Literal) -> bool {
+    match a {
+      Literal::Bytes(_) => true,
+      _ => {
+        Ed25519Op::apply(op, &[&a]) == None
+          && Ed25519Op::apply(op, &[&a, &a]) == None
+          && Ed25519Op::apply(op, &[&a, &a, &a]) == None
+      }
+    }
+  }
+}
@@ -0,0 +1,170 @@
+use sp_ipld::Ipld;
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  term::Term,
+  yatima,
+};
+
+/// Primitive operations on exceptions. `raise` and `catch` take a type
+/// argument and are reduced by the evaluator rather than by `apply`: a raised
+/// exception stands in for a value of any type, and is propagated by every
+/// other primitive it is passed to until a `catch` hands its tag and payload
+/// to a handler. `catch` also handles a primitive that is stuck on literal
+/// arguments, such as a division by zero, as an exception tagged with the
+/// name of the operation
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ExceptionOp {
+  Raise,
+  Catch,
+  Tag,
+  Payload,
+}
+
+impl ExceptionOp {
+  /// Gets the syntax string of an exception operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Raise => "raise".to_owned(),
+      Self::Catch => "catch".to_owned(),
+      Self::Tag => "tag".to_owned(),
+      Self::Payload => "payload".to_owned(),
+    }
+  }
+
+  /// Gets an exception operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "raise" => Some(Self::Raise),
+      "catch" => Some(Self::Catch),
+      "tag" => Some(Self::Tag),
+      "payload" => Some(Self::Payload),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of an exception operation
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Raise => yatima!("∀ (0 A: Type) #Text #Bytes -> A"),
+      Self::Catch => {
+        yatima!("∀ (0 A: Type) (x: A) (handler: ∀ #Text #Bytes -> A) -> A")
+      }
+      Self::Tag => yatima!("∀ #Exception -> #Text"),
+      Self::Payload => yatima!("∀ #Exception -> #Bytes"),
+    }
+  }
+
+  /// Converts an exception operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Raise => Ipld::Integer(0),
+      Self::Catch => Ipld::Integer(1),
+      Self::Tag => Ipld::Integer(2),
+      Self::Payload => Ipld::Integer(3),
+    }
+  }
+
+  /// Converts an IPLD object into an exception operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Raise),
+      Ipld::Integer(1) => Ok(Self::Catch),
+      Ipld::Integer(2) => Ok(Self::Tag),
+      Ipld::Integer(3) => Ok(Self::Payload),
+      xs => Err(IpldError::ExceptionOp(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation, including the
+  /// type arguments of `raise` and `catch`
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Raise => 3,
+      Self::Catch => 3,
+      Self::Tag => 1,
+      Self::Payload => 1,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Tag, [Exception(tag, _)]) => Some(Text(tag.clone())),
+      (Self::Payload, [Exception(_, payload)]) => Some(Bytes(payload.clone())),
+      _ => None,
+    }
+  }
+
+  /// Makes the exception raised with a tag and payload, if they are valid
+  pub fn raise(tag: &Literal, payload: &Literal) -> Option<Literal> {
+    match (tag, payload) {
+      (Literal::Text(tag), Literal::Bytes(payload)) => {
+        Some(Literal::Exception(tag.clone(), payload.clone()))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for ExceptionOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  use sp_ropey::Rope;
+  impl Arbitrary for ExceptionOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=3);
+      match gen {
+        0 => Self::Raise,
+        1 => Self::Catch,
+        2 => Self::Tag,
+        _ => Self::Payload,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn exception_op_ipld(x: ExceptionOp) -> bool {
+    match ExceptionOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn test_raise_parts(tag: String, payload: Vec<u8>) -> bool {
+    let tag = Literal::Text(Rope::from(tag));
+    let payload = Literal::Bytes(payload);
+    let exn = ExceptionOp::raise(&tag, &payload).unwrap();
+    ExceptionOp::apply(ExceptionOp::Tag, &[&exn]) == Some(tag)
+      && ExceptionOp::apply(ExceptionOp::Payload, &[&exn]) == Some(payload)
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: ExceptionOp, a: Literal) -> bool {
+    ExceptionOp::apply(op, &[&a]) == None && ExceptionOp::raise(&a, &a) == None
+  }
+}
@@ -0,0 +1,635 @@
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  term::Term,
+  yatima,
+};
+
+/// Primitive 32-bit floating point operations
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum F32Op {
+  Nan,
+  Inf,
+  Eql,
+  Lte,
+  Lth,
+  Gth,
+  Gte,
+  IsNan,
+  IsInf,
+  Neg,
+  Abs,
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Rem,
+  Pow,
+  Sqrt,
+  Exp,
+  Ln,
+  Floor,
+  Ceil,
+  Round,
+  Trunc,
+  Sin,
+  Cos,
+  Tan,
+  Asin,
+  Acos,
+  Atan,
+  Atan2,
+  ToBits,
+  FromBits,
+  ToI32,
+  FromI32,
+  ToF64,
+}
+
+impl F32Op {
+  /// Gets the syntax string of an f32 operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Nan => "nan".to_owned(),
+      Self::Inf => "inf".to_owned(),
+      Self::Eql => "eql".to_owned(),
+      Self::Lte => "lte".to_owned(),
+      Self::Lth => "lth".to_owned(),
+      Self::Gth => "gth".to_owned(),
+      Self::Gte => "gte".to_owned(),
+      Self::IsNan => "is_nan".to_owned(),
+      Self::IsInf => "is_inf".to_owned(),
+      Self::Neg => "neg".to_owned(),
+      Self::Abs => "abs".to_owned(),
+      Self::Add => "add".to_owned(),
+      Self::Sub => "sub".to_owned(),
+      Self::Mul => "mul".to_owned(),
+      Self::Div => "div".to_owned(),
+      Self::Rem => "rem".to_owned(),
+      Self::Pow => "pow".to_owned(),
+      Self::Sqrt => "sqrt".to_owned(),
+      Self::Exp => "exp".to_owned(),
+      Self::Ln => "ln".to_owned(),
+      Self::Floor => "floor".to_owned(),
+      Self::Ceil => "ceil".to_owned(),
+      Self::Round => "round".to_owned(),
+      Self::Trunc => "trunc".to_owned(),
+      Self::Sin => "sin".to_owned(),
+      Self::Cos => "cos".to_owned(),
+      Self::Tan => "tan".to_owned(),
+      Self::Asin => "asin".to_owned(),
+      Self::Acos => "acos".to_owned(),
+      Self::Atan => "atan".to_owned(),
+      Self::Atan2 => "atan2".to_owned(),
+      Self::ToBits => "to_bits".to_owned(),
+      Self::FromBits => "from_bits".to_owned(),
+      Self::ToI32 => "to_I32".to_owned(),
+      Self::FromI32 => "from_I32".to_owned(),
+      Self::ToF64 => "to_F64".to_owned(),
+    }
+  }
+
+  /// Gets an f32 operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "nan" => Some(Self::Nan),
+      "inf" => Some(Self::Inf),
+      "eql" => Some(Self::Eql),
+      "lte" => Some(Self::Lte),
+      "lth" => Some(Self::Lth),
+      "gth" => Some(Self::Gth),
+      "gte" => Some(Self::Gte),
+      "is_nan" => Some(Self::IsNan),
+      "is_inf" => Some(Self::IsInf),
+      "neg" => Some(Self::Neg),
+      "abs" => Some(Self::Abs),
+      "add" => Some(Self::Add),
+      "sub" => Some(Self::Sub),
+      "mul" => Some(Self::Mul),
+      "div" => Some(Self::Div),
+      "rem" => Some(Self::Rem),
+      "pow" => Some(Self::Pow),
+      "sqrt" => Some(Self::Sqrt),
+      "exp" => Some(Self::Exp),
+      "ln" => Some(Self::Ln),
+      "floor" => Some(Self::Floor),
+      "ceil" => Some(Self::Ceil),
+      "round" => Some(Self::Round),
+      "trunc" => Some(Self::Trunc),
+      "sin" => Some(Self::Sin),
+      "cos" => Some(Self::Cos),
+      "tan" => Some(Self::Tan),
+      "asin" => Some(Self::Asin),
+      "acos" => Some(Self::Acos),
+      "atan" => Some(Self::Atan),
+      "atan2" => Some(Self::Atan2),
+      "to_bits" => Some(Self::ToBits),
+      "from_bits" => Some(Self::FromBits),
+      "to_I32" => Some(Self::ToI32),
+      "from_I32" => Some(Self::FromI32),
+      "to_F64" => Some(Self::ToF64),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of an f32 operation
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Nan => yatima!("#F32"),
+      Self::Inf => yatima!("#F32"),
+      Self::Eql => yatima!("∀ #F32 #F32 -> #Bool"),
+      Self::Lte => yatima!("∀ #F32 #F32 -> #Bool"),
+      Self::Lth => yatima!("∀ #F32 #F32 -> #Bool"),
+      Self::Gth => yatima!("∀ #F32 #F32 -> #Bool"),
+      Self::Gte => yatima!("∀ #F32 #F32 -> #Bool"),
+      Self::IsNan => yatima!("∀ #F32 -> #Bool"),
+      Self::IsInf => yatima!("∀ #F32 -> #Bool"),
+      Self::Neg => yatima!("∀ #F32 -> #F32"),
+      Self::Abs => yatima!("∀ #F32 -> #F32"),
+      Self::Add => yatima!("∀ #F32 #F32 -> #F32"),
+      Self::Sub => yatima!("∀ #F32 #F32 -> #F32"),
+      Self::Mul => yatima!("∀ #F32 #F32 -> #F32"),
+      Self::Div => yatima!("∀ #F32 #F32 -> #F32"),
+      Self::Rem => yatima!("∀ #F32 #F32 -> #F32"),
+      Self::Pow => yatima!("∀ #F32 #F32 -> #F32"),
+      Self::Sqrt => yatima!("∀ #F32 -> #F32"),
+      Self::Exp => yatima!("∀ #F32 -> #F32"),
+      Self::Ln => yatima!("∀ #F32 -> #F32"),
+      Self::Floor => yatima!("∀ #F32 -> #F32"),
+      Self::Ceil => yatima!("∀ #F32 -> #F32"),
+      Self::Round => yatima!("∀ #F32 -> #F32"),
+      Self::Trunc => yatima!("∀ #F32 -> #F32"),
+      Self::Sin => yatima!("∀ #F32 -> #F32"),
+      Self::Cos => yatima!("∀ #F32 -> #F32"),
+      Self::Tan => yatima!("∀ #F32 -> #F32"),
+      Self::Asin => yatima!("∀ #F32 -> #F32"),
+      Self::Acos => yatima!("∀ #F32 -> #F32"),
+      Self::Atan => yatima!("∀ #F32 -> #F32"),
+      Self::Atan2 => yatima!("∀ #F32 #F32 -> #F32"),
+      Self::ToBits => yatima!("∀ #F32 -> #U32"),
+      Self::FromBits => yatima!("∀ #U32 -> #F32"),
+      Self::ToI32 => yatima!("∀ #F32 -> #I32"),
+      Self::FromI32 => yatima!("∀ #I32 -> #F32"),
+      Self::ToF64 => yatima!("∀ #F32 -> #F64"),
+    }
+  }
+
+  /// Converts an f32 operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Nan => Ipld::Integer(0),
+      Self::Inf => Ipld::Integer(1),
+      Self::Eql => Ipld::Integer(2),
+      Self::Lte => Ipld::Integer(3),
+      Self::Lth => Ipld::Integer(4),
+      Self::Gth => Ipld::Integer(5),
+      Self::Gte => Ipld::Integer(6),
+      Self::IsNan => Ipld::Integer(7),
+      Self::IsInf => Ipld::Integer(8),
+      Self::Neg => Ipld::Integer(9),
+      Self::Abs => Ipld::Integer(10),
+      Self::Add => Ipld::Integer(11),
+      Self::Sub => Ipld::Integer(12),
+      Self::Mul => Ipld::Integer(13),
+      Self::Div => Ipld::Integer(14),
+      Self::Rem => Ipld::Integer(15),
+      Self::Pow => Ipld::Integer(16),
+      Self::Sqrt => Ipld::Integer(17),
+      Self::Exp => Ipld::Integer(18),
+      Self::Ln => Ipld::Integer(19),
+      Self::Floor => Ipld::Integer(20),
+      Self::Ceil => Ipld::Integer(21),
+      Self::Round => Ipld::Integer(22),
+      Self::Trunc => Ipld::Integer(23),
+      Self::Sin => Ipld::Integer(24),
+      Self::Cos => Ipld::Integer(25),
+      Self::Tan => Ipld::Integer(26),
+      Self::Asin => Ipld::Integer(27),
+      Self::Acos => Ipld::Integer(28),
+      Self::Atan => Ipld::Integer(29),
+      Self::Atan2 => Ipld::Integer(30),
+      Self::ToBits => Ipld::Integer(31),
+      Self::FromBits => Ipld::Integer(32),
+      Self::ToI32 => Ipld::Integer(33),
+      Self::FromI32 => Ipld::Integer(34),
+      Self::ToF64 => Ipld::Integer(35),
+    }
+  }
+
+  /// Converts an IPLD object into an f32 operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Nan),
+      Ipld::Integer(1) => Ok(Self::Inf),
+      Ipld::Integer(2) => Ok(Self::Eql),
+      Ipld::Integer(3) => Ok(Self::Lte),
+      Ipld::Integer(4) => Ok(Self::Lth),
+      Ipld::Integer(5) => Ok(Self::Gth),
+      Ipld::Integer(6) => Ok(Self::Gte),
+      Ipld::Integer(7) => Ok(Self::IsNan),
+      Ipld::Integer(8) => Ok(Self::IsInf),
+      Ipld::Integer(9) => Ok(Self::Neg),
+      Ipld::Integer(10) => Ok(Self::Abs),
+      Ipld::Integer(11) => Ok(Self::Add),
+      Ipld::Integer(12) => Ok(Self::Sub),
+      Ipld::Integer(13) => Ok(Self::Mul),
+      Ipld::Integer(14) => Ok(Self::Div),
+      Ipld::Integer(15) => Ok(Self::Rem),
+      Ipld::Integer(16) => Ok(Self::Pow),
+      Ipld::Integer(17) => Ok(Self::Sqrt),
+      Ipld::Integer(18) => Ok(Self::Exp),
+      Ipld::Integer(19) => Ok(Self::Ln),
+      Ipld::Integer(20) => Ok(Self::Floor),
+      Ipld::Integer(21) => Ok(Self::Ceil),
+      Ipld::Integer(22) => Ok(Self::Round),
+      Ipld::Integer(23) => Ok(Self::Trunc),
+      Ipld::Integer(24) => Ok(Self::Sin),
+      Ipld::Integer(25) => Ok(Self::Cos),
+      Ipld::Integer(26) => Ok(Self::Tan),
+      Ipld::Integer(27) => Ok(Self::Asin),
+      Ipld::Integer(28) => Ok(Self::Acos),
+      Ipld::Integer(29) => Ok(Self::Atan),
+      Ipld::Integer(30) => Ok(Self::Atan2),
+      Ipld::Integer(31) => Ok(Self::ToBits),
+      Ipld::Integer(32) => Ok(Self::FromBits),
+      Ipld::Integer(33) => Ok(Self::ToI32),
+      Ipld::Integer(34) => Ok(Self::FromI32),
+      Ipld::Integer(35) => Ok(Self::ToF64),
+      xs => Err(IpldError::F32Op(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Nan => 0,
+      Self::Inf => 0,
+      Self::Eql => 2,
+      Self::Lte => 2,
+      Self::Lth => 2,
+      Self::Gth => 2,
+      Self::Gte => 2,
+      Self::IsNan => 1,
+      Self::IsInf => 1,
+      Self::Neg => 1,
+      Self::Abs => 1,
+      Self::Add => 2,
+      Self::Sub => 2,
+      Self::Mul => 2,
+      Self::Div => 2,
+      Self::Rem => 2,
+      Self::Pow => 2,
+      Self::Sqrt => 1,
+      Self::Exp => 1,
+      Self::Ln => 1,
+      Self::Floor => 1,
+      Self::Ceil => 1,
+      Self::Round => 1,
+      Self::Trunc => 1,
+      Self::Sin => 1,
+      Self::Cos => 1,
+      Self::Tan => 1,
+      Self::Asin => 1,
+      Self::Acos => 1,
+      Self::Atan => 1,
+      Self::Atan2 => 2,
+      Self::ToBits => 1,
+      Self::FromBits => 1,
+      Self::ToI32 => 1,
+      Self::FromI32 => 1,
+      Self::ToF64 => 1,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Nan, []) => Some(F32(f32::NAN)),
+      (Self::Inf, []) => Some(F32(f32::INFINITY)),
+      (Self::IsNan, [F32(x)]) => Some(Bool(x.is_nan())),
+      (Self::IsInf, [F32(x)]) => Some(Bool(x.is_infinite())),
+      (Self::Neg, [F32(x)]) => Some(F32(-x)),
+      (Self::Abs, [F32(x)]) => Some(F32(libm::fabsf(*x))),
+      (Self::Sqrt, [F32(x)]) => Some(F32(libm::sqrtf(*x))),
+      (Self::Exp, [F32(x)]) => Some(F32(libm::expf(*x))),
+      (Self::Ln, [F32(x)]) => Some(F32(libm::logf(*x))),
+      (Self::Floor, [F32(x)]) => Some(F32(libm::floorf(*x))),
+      (Self::Ceil, [F32(x)]) => Some(F32(libm::ceilf(*x))),
+      (Self::Round, [F32(x)]) => Some(F32(libm::roundf(*x))),
+      (Self::Trunc, [F32(x)]) => Some(F32(libm::truncf(*x))),
+      (Self::Sin, [F32(x)]) => Some(F32(libm::sinf(*x))),
+      (Self::Cos, [F32(x)]) => Some(F32(libm::cosf(*x))),
+      (Self::Tan, [F32(x)]) => Some(F32(libm::tanf(*x))),
+      (Self::Asin, [F32(x)]) => Some(F32(libm::asinf(*x))),
+      (Self::Acos, [F32(x)]) => Some(F32(libm::acosf(*x))),
+      (Self::Atan, [F32(x)]) => Some(F32(libm::atanf(*x))),
+      (Self::ToBits, [F32(x)]) => Some(U32(x.to_bits())),
+      (Self::FromBits, [U32(x)]) => Some(F32(f32::from_bits(*x))),
+      // Truncates toward zero, and is stuck on NaN or out of range values
+      (Self::ToI32, [F32(x)]) => {
+        if *x >= i32::MIN as f32 && *x < -(i32::MIN as f32) {
+          Some(I32(*x as i32))
+        }
+        else {
+          None
+        }
+      }
+      (Self::FromI32, [I32(x)]) => Some(F32(*x as f32)),
+      (Self::ToF64, [F32(x)]) => Some(F64(*x as f64)),
+      (Self::Eql, [F32(x), F32(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [F32(x), F32(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [F32(x), F32(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [F32(x), F32(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [F32(x), F32(y)]) => Some(Bool(x >= y)),
+      (Self::Add, [F32(x), F32(y)]) => Some(F32(x + y)),
+      (Self::Sub, [F32(x), F32(y)]) => Some(F32(x - y)),
+      (Self::Mul, [F32(x), F32(y)]) => Some(F32(x * y)),
+      (Self::Div, [F32(x), F32(y)]) => Some(F32(x / y)),
+      (Self::Rem, [F32(x), F32(y)]) => Some(F32(libm::fmodf(*x, *y))),
+      (Self::Pow, [F32(x), F32(y)]) => Some(F32(libm::powf(*x, *y))),
+      (Self::Atan2, [F32(x), F32(y)]) => Some(F32(libm::atan2f(*x, *y))),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for F32Op {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::prim::f64::tests::same;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+    TestResult,
+  };
+  use rand::Rng;
+  use sp_std::mem;
+  use Literal::{
+    Bool,
+    F32,
+    F64,
+    I32,
+    U32,
+  };
+  impl Arbitrary for F32Op {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=35);
+      match gen {
+        0 => Self::Nan,
+        1 => Self::Inf,
+        2 => Self::Eql,
+        3 => Self::Lte,
+        4 => Self::Lth,
+        5 => Self::Gth,
+        6 => Self::Gte,
+        7 => Self::IsNan,
+        8 => Self::IsInf,
+        9 => Self::Neg,
+        10 => Self::Abs,
+        11 => Self::Add,
+        12 => Self::Sub,
+        13 => Self::Mul,
+        14 => Self::Div,
+        15 => Self::Rem,
+        16 => Self::Pow,
+        17 => Self::Sqrt,
+        18 => Self::Exp,
+        19 => Self::Ln,
+        20 => Self::Floor,
+        21 => Self::Ceil,
+        22 => Self::Round,
+        23 => Self::Trunc,
+        24 => Self::Sin,
+        25 => Self::Cos,
+        26 => Self::Tan,
+        27 => Self::Asin,
+        28 => Self::Acos,
+        29 => Self::Atan,
+        30 => Self::Atan2,
+        31 => Self::ToBits,
+        32 => Self::FromBits,
+        33 => Self::ToI32,
+        34 => Self::FromI32,
+        _ => Self::ToF64,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn f32_op_ipld(x: F32Op) -> bool {
+    match F32Op::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply(op: F32Op, a: f32, b: f32, c: u32, d: i32) -> TestResult {
+    let apply0_go = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(same(F32Op::apply(op, &[]), expected))
+    };
+
+    let apply1_f32 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(same(F32Op::apply(op, &[&F32(a)]), expected))
+    };
+
+    let apply2_f32_f32 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(same(
+        F32Op::apply(op, &[&F32(a), &F32(b)]),
+        expected,
+      ))
+    };
+
+    match op {
+      F32Op::Nan => apply0_go(Some(F32(f32::NAN))),
+      F32Op::Inf => apply0_go(Some(F32(f32::INFINITY))),
+      F32Op::Eql => apply2_f32_f32(Some(Bool(a == b))),
+      F32Op::Lte => apply2_f32_f32(Some(Bool(a <= b))),
+      F32Op::Lth => apply2_f32_f32(Some(Bool(a < b))),
+      F32Op::Gth => apply2_f32_f32(Some(Bool(a > b))),
+      F32Op::Gte => apply2_f32_f32(Some(Bool(a >= b))),
+      F32Op::IsNan => apply1_f32(Some(Bool(a.is_nan()))),
+      F32Op::IsInf => apply1_f32(Some(Bool(a.is_infinite()))),
+      F32Op::Neg => apply1_f32(Some(F32(-a))),
+      F32Op::Abs => apply1_f32(Some(F32(a.abs()))),
+      F32Op::Add => apply2_f32_f32(Some(F32(a + b))),
+      F32Op::Sub => apply2_f32_f32(Some(F32(a - b))),
+      F32Op::Mul => apply2_f32_f32(Some(F32(a * b))),
+      F32Op::Div => apply2_f32_f32(Some(F32(a / b))),
+      F32Op::Sqrt => apply1_f32(Some(F32(a.sqrt()))),
+      F32Op::Floor => apply1_f32(Some(F32(a.floor()))),
+      F32Op::Ceil => apply1_f32(Some(F32(a.ceil()))),
+      F32Op::Round => apply1_f32(Some(F32(a.round()))),
+      F32Op::Trunc => apply1_f32(Some(F32(a.trunc()))),
+      // Transcendental functions may differ from the platform's in the last
+      // place, so these are checked in `test_apply_transcendental`
+      F32Op::Rem
+      | F32Op::Pow
+      | F32Op::Exp
+      | F32Op::Ln
+      | F32Op::Sin
+      | F32Op::Cos
+      | F32Op::Tan
+      | F32Op::Asin
+      | F32Op::Acos
+      | F32Op::Atan
+      | F32Op::Atan2 => TestResult::discard(),
+      F32Op::ToBits => apply1_f32(Some(U32(a.to_bits()))),
+      F32Op::FromBits => TestResult::from_bool(same(
+        F32Op::apply(op, &[&U32(c)]),
+        Some(F32(f32::from_bits(c))),
+      )),
+      F32Op::ToI32 => {
+        if a >= -(2f32.powi(31)) && a < 2f32.powi(31) {
+          apply1_f32(Some(I32(a as i32)))
+        }
+        else {
+          apply1_f32(None)
+        }
+      }
+      F32Op::FromI32 => TestResult::from_bool(same(
+        F32Op::apply(op, &[&I32(d)]),
+        Some(F32(d as f32)),
+      )),
+      F32Op::ToF64 => apply1_f32(Some(F64(a as f64))),
+    }
+  }
+
+  #[test]
+  fn test_apply_transcendental() {
+    let apply1 = |op: F32Op, x: f32| match op.apply(&[&F32(x)]) {
+      Some(F32(y)) => y,
+      _ => panic!("{} of {} is stuck", op, x),
+    };
+    let apply2 = |op: F32Op, x: f32, y: f32| match op.apply(&[&F32(x), &F32(y)])
+    {
+      Some(F32(z)) => z,
+      _ => panic!("{} of {} and {} is stuck", op, x, y),
+    };
+    let close = |x: f32, y: f32| (x - y).abs() <= 1e-6;
+    assert!(close(apply1(F32Op::Sin, core::f32::consts::FRAC_PI_2), 1.0));
+    assert!(close(apply1(F32Op::Cos, core::f32::consts::PI), -1.0));
+    assert!(close(apply1(F32Op::Tan, core::f32::consts::FRAC_PI_4), 1.0));
+    assert!(close(apply1(F32Op::Asin, 1.0), core::f32::consts::FRAC_PI_2));
+    assert!(close(apply1(F32Op::Acos, -1.0), core::f32::consts::PI));
+    assert!(close(apply1(F32Op::Atan, 1.0), core::f32::consts::FRAC_PI_4));
+    assert!(close(apply1(F32Op::Exp, 1.0), core::f32::consts::E));
+    assert!(close(apply1(F32Op::Ln, core::f32::consts::E), 1.0));
+    assert!(close(
+      apply2(F32Op::Atan2, 1.0, -1.0),
+      3.0 * core::f32::consts::FRAC_PI_4
+    ));
+    assert_eq!(apply2(F32Op::Pow, 2.0, 10.0), 1024.0);
+    assert_eq!(apply2(F32Op::Rem, -7.5, 2.0), -1.5);
+    assert!(apply1(F32Op::Sqrt, -1.0).is_nan());
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(
+    op: F32Op,
+    a: Literal,
+    b: f32,
+    test_arg_2: bool,
+  ) -> TestResult {
+    let test_apply1_none_on_invalid = |valid_arg: Literal| -> TestResult {
+      if mem::discriminant(&valid_arg) == mem::discriminant(&a) {
+        TestResult::discard()
+      }
+      else {
+        TestResult::from_bool(F32Op::apply(op, &[&a]) == None)
+      }
+    };
+
+    let test_apply2_none_on_invalid =
+      |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
+        let go =
+          || TestResult::from_bool(F32Op::apply(op, &[&a_, &b_]) == None);
+        if test_arg_2 {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        else {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      };
+
+    match op {
+      // Arity 0.
+      F32Op::Nan | F32Op::Inf => TestResult::discard(),
+      // Arity 1, valid is U32.
+      F32Op::FromBits => test_apply1_none_on_invalid(U32(0)),
+      // Arity 1, valid is I32.
+      F32Op::FromI32 => test_apply1_none_on_invalid(I32(0)),
+      // Arity 1, valid is F32.
+      F32Op::IsNan
+      | F32Op::IsInf
+      | F32Op::Neg
+      | F32Op::Abs
+      | F32Op::Sqrt
+      | F32Op::Exp
+      | F32Op::Ln
+      | F32Op::Floor
+      | F32Op::Ceil
+      | F32Op::Round
+      | F32Op::Trunc
+      | F32Op::Sin
+      | F32Op::Cos
+      | F32Op::Tan
+      | F32Op::Asin
+      | F32Op::Acos
+      | F32Op::Atan
+      | F32Op::ToBits
+      | F32Op::ToI32
+      | F32Op::ToF64 => test_apply1_none_on_invalid(F32(b)),
+      // Arity 2, valid are F32 on a and b.
+      F32Op::Eql
+      | F32Op::Lte
+      | F32Op::Lth
+      | F32Op::Gth
+      | F32Op::Gte
+      | F32Op::Add
+      | F32Op::Sub
+      | F32Op::Mul
+      | F32Op::Div
+      | F32Op::Rem
+      | F32Op::Pow
+      | F32Op::Atan2 => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(F32(b), a, F32(b))
+        }
+        else {
+          test_apply2_none_on_invalid(F32(b), F32(b), a)
+        }
+      }
+    }
+  }
+}
@@ -0,0 +1,649 @@
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  term::Term,
+  yatima,
+};
+
+/// Primitive 64-bit floating point operations. Everything past the basic
+/// arithmetic goes through `libm`, so results don't depend on the platform.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum F64Op {
+  Nan,
+  Inf,
+  Eql,
+  Lte,
+  Lth,
+  Gth,
+  Gte,
+  IsNan,
+  IsInf,
+  Neg,
+  Abs,
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Rem,
+  Pow,
+  Sqrt,
+  Exp,
+  Ln,
+  Floor,
+  Ceil,
+  Round,
+  Trunc,
+  Sin,
+  Cos,
+  Tan,
+  Asin,
+  Acos,
+  Atan,
+  Atan2,
+  ToBits,
+  FromBits,
+  ToI64,
+  FromI64,
+  ToF32,
+}
+
+impl F64Op {
+  /// Gets the syntax string of an f64 operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Nan => "nan".to_owned(),
+      Self::Inf => "inf".to_owned(),
+      Self::Eql => "eql".to_owned(),
+      Self::Lte => "lte".to_owned(),
+      Self::Lth => "lth".to_owned(),
+      Self::Gth => "gth".to_owned(),
+      Self::Gte => "gte".to_owned(),
+      Self::IsNan => "is_nan".to_owned(),
+      Self::IsInf => "is_inf".to_owned(),
+      Self::Neg => "neg".to_owned(),
+      Self::Abs => "abs".to_owned(),
+      Self::Add => "add".to_owned(),
+      Self::Sub => "sub".to_owned(),
+      Self::Mul => "mul".to_owned(),
+      Self::Div => "div".to_owned(),
+      Self::Rem => "rem".to_owned(),
+      Self::Pow => "pow".to_owned(),
+      Self::Sqrt => "sqrt".to_owned(),
+      Self::Exp => "exp".to_owned(),
+      Self::Ln => "ln".to_owned(),
+      Self::Floor => "floor".to_owned(),
+      Self::Ceil => "ceil".to_owned(),
+      Self::Round => "round".to_owned(),
+      Self::Trunc => "trunc".to_owned(),
+      Self::Sin => "sin".to_owned(),
+      Self::Cos => "cos".to_owned(),
+      Self::Tan => "tan".to_owned(),
+      Self::Asin => "asin".to_owned(),
+      Self::Acos => "acos".to_owned(),
+      Self::Atan => "atan".to_owned(),
+      Self::Atan2 => "atan2".to_owned(),
+      Self::ToBits => "to_bits".to_owned(),
+      Self::FromBits => "from_bits".to_owned(),
+      Self::ToI64 => "to_I64".to_owned(),
+      Self::FromI64 => "from_I64".to_owned(),
+      Self::ToF32 => "to_F32".to_owned(),
+    }
+  }
+
+  /// Gets an f64 operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "nan" => Some(Self::Nan),
+      "inf" => Some(Self::Inf),
+      "eql" => Some(Self::Eql),
+      "lte" => Some(Self::Lte),
+      "lth" => Some(Self::Lth),
+      "gth" => Some(Self::Gth),
+      "gte" => Some(Self::Gte),
+      "is_nan" => Some(Self::IsNan),
+      "is_inf" => Some(Self::IsInf),
+      "neg" => Some(Self::Neg),
+      "abs" => Some(Self::Abs),
+      "add" => Some(Self::Add),
+      "sub" => Some(Self::Sub),
+      "mul" => Some(Self::Mul),
+      "div" => Some(Self::Div),
+      "rem" => Some(Self::Rem),
+      "pow" => Some(Self::Pow),
+      "sqrt" => Some(Self::Sqrt),
+      "exp" => Some(Self::Exp),
+      "ln" => Some(Self::Ln),
+      "floor" => Some(Self::Floor),
+      "ceil" => Some(Self::Ceil),
+      "round" => Some(Self::Round),
+      "trunc" => Some(Self::Trunc),
+      "sin" => Some(Self::Sin),
+      "cos" => Some(Self::Cos),
+      "tan" => Some(Self::Tan),
+      "asin" => Some(Self::Asin),
+      "acos" => Some(Self::Acos),
+      "atan" => Some(Self::Atan),
+      "atan2" => Some(Self::Atan2),
+      "to_bits" => Some(Self::ToBits),
+      "from_bits" => Some(Self::FromBits),
+      "to_I64" => Some(Self::ToI64),
+      "from_I64" => Some(Self::FromI64),
+      "to_F32" => Some(Self::ToF32),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of an f64 operation
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Nan => yatima!("#F64"),
+      Self::Inf => yatima!("#F64"),
+      Self::Eql => yatima!("∀ #F64 #F64 -> #Bool"),
+      Self::Lte => yatima!("∀ #F64 #F64 -> #Bool"),
+      Self::Lth => yatima!("∀ #F64 #F64 -> #Bool"),
+      Self::Gth => yatima!("∀ #F64 #F64 -> #Bool"),
+      Self::Gte => yatima!("∀ #F64 #F64 -> #Bool"),
+      Self::IsNan => yatima!("∀ #F64 -> #Bool"),
+      Self::IsInf => yatima!("∀ #F64 -> #Bool"),
+      Self::Neg => yatima!("∀ #F64 -> #F64"),
+      Self::Abs => yatima!("∀ #F64 -> #F64"),
+      Self::Add => yatima!("∀ #F64 #F64 -> #F64"),
+      Self::Sub => yatima!("∀ #F64 #F64 -> #F64"),
+      Self::Mul => yatima!("∀ #F64 #F64 -> #F64"),
+      Self::Div => yatima!("∀ #F64 #F64 -> #F64"),
+      Self::Rem => yatima!("∀ #F64 #F64 -> #F64"),
+      Self::Pow => yatima!("∀ #F64 #F64 -> #F64"),
+      Self::Sqrt => yatima!("∀ #F64 -> #F64"),
+      Self::Exp => yatima!("∀ #F64 -> #F64"),
+      Self::Ln => yatima!("∀ #F64 -> #F64"),
+      Self::Floor => yatima!("∀ #F64 -> #F64"),
+      Self::Ceil => yatima!("∀ #F64 -> #F64"),
+      Self::Round => yatima!("∀ #F64 -> #F64"),
+      Self::Trunc => yatima!("∀ #F64 -> #F64"),
+      Self::Sin => yatima!("∀ #F64 -> #F64"),
+      Self::Cos => yatima!("∀ #F64 -> #F64"),
+      Self::Tan => yatima!("∀ #F64 -> #F64"),
+      Self::Asin => yatima!("∀ #F64 -> #F64"),
+      Self::Acos => yatima!("∀ #F64 -> #F64"),
+      Self::Atan => yatima!("∀ #F64 -> #F64"),
+      Self::Atan2 => yatima!("∀ #F64 #F64 -> #F64"),
+      Self::ToBits => yatima!("∀ #F64 -> #U64"),
+      Self::FromBits => yatima!("∀ #U64 -> #F64"),
+      Self::ToI64 => yatima!("∀ #F64 -> #I64"),
+      Self::FromI64 => yatima!("∀ #I64 -> #F64"),
+      Self::ToF32 => yatima!("∀ #F64 -> #F32"),
+    }
+  }
+
+  /// Converts an f64 operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Nan => Ipld::Integer(0),
+      Self::Inf => Ipld::Integer(1),
+      Self::Eql => Ipld::Integer(2),
+      Self::Lte => Ipld::Integer(3),
+      Self::Lth => Ipld::Integer(4),
+      Self::Gth => Ipld::Integer(5),
+      Self::Gte => Ipld::Integer(6),
+      Self::IsNan => Ipld::Integer(7),
+      Self::IsInf => Ipld::Integer(8),
+      Self::Neg => Ipld::Integer(9),
+      Self::Abs => Ipld::Integer(10),
+      Self::Add => Ipld::Integer(11),
+      Self::Sub => Ipld::Integer(12),
+      Self::Mul => Ipld::Integer(13),
+      Self::Div => Ipld::Integer(14),
+      Self::Rem => Ipld::Integer(15),
+      Self::Pow => Ipld::Integer(16),
+      Self::Sqrt => Ipld::Integer(17),
+      Self::Exp => Ipld::Integer(18),
+      Self::Ln => Ipld::Integer(19),
+      Self::Floor => Ipld::Integer(20),
+      Self::Ceil => Ipld::Integer(21),
+      Self::Round => Ipld::Integer(22),
+      Self::Trunc => Ipld::Integer(23),
+      Self::Sin => Ipld::Integer(24),
+      Self::Cos => Ipld::Integer(25),
+      Self::Tan => Ipld::Integer(26),
+      Self::Asin => Ipld::Integer(27),
+      Self::Acos => Ipld::Integer(28),
+      Self::Atan => Ipld::Integer(29),
+      Self::Atan2 => Ipld::Integer(30),
+      Self::ToBits => Ipld::Integer(31),
+      Self::FromBits => Ipld::Integer(32),
+      Self::ToI64 => Ipld::Integer(33),
+      Self::FromI64 => Ipld::Integer(34),
+      Self::ToF32 => Ipld::Integer(35),
+    }
+  }
+
+  /// Converts an IPLD object into an f64 operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Nan),
+      Ipld::Integer(1) => Ok(Self::Inf),
+      Ipld::Integer(2) => Ok(Self::Eql),
+      Ipld::Integer(3) => Ok(Self::Lte),
+      Ipld::Integer(4) => Ok(Self::Lth),
+      Ipld::Integer(5) => Ok(Self::Gth),
+      Ipld::Integer(6) => Ok(Self::Gte),
+      Ipld::Integer(7) => Ok(Self::IsNan),
+      Ipld::Integer(8) => Ok(Self::IsInf),
+      Ipld::Integer(9) => Ok(Self::Neg),
+      Ipld::Integer(10) => Ok(Self::Abs),
+      Ipld::Integer(11) => Ok(Self::Add),
+      Ipld::Integer(12) => Ok(Self::Sub),
+      Ipld::Integer(13) => Ok(Self::Mul),
+      Ipld::Integer(14) => Ok(Self::Div),
+      Ipld::Integer(15) => Ok(Self::Rem),
+      Ipld::Integer(16) => Ok(Self::Pow),
+      Ipld::Integer(17) => Ok(Self::Sqrt),
+      Ipld::Integer(18) => Ok(Self::Exp),
+      Ipld::Integer(19) => Ok(Self::Ln),
+      Ipld::Integer(20) => Ok(Self::Floor),
+      Ipld::Integer(21) => Ok(Self::Ceil),
+      Ipld::Integer(22) => Ok(Self::Round),
+      Ipld::Integer(23) => Ok(Self::Trunc),
+      Ipld::Integer(24) => Ok(Self::Sin),
+      Ipld::Integer(25) => Ok(Self::Cos),
+      Ipld::Integer(26) => Ok(Self::Tan),
+      Ipld::Integer(27) => Ok(Self::Asin),
+      Ipld::Integer(28) => Ok(Self::Acos),
+      Ipld::Integer(29) => Ok(Self::Atan),
+      Ipld::Integer(30) => Ok(Self::Atan2),
+      Ipld::Integer(31) => Ok(Self::ToBits),
+      Ipld::Integer(32) => Ok(Self::FromBits),
+      Ipld::Integer(33) => Ok(Self::ToI64),
+      Ipld::Integer(34) => Ok(Self::FromI64),
+      Ipld::Integer(35) => Ok(Self::ToF32),
+      xs => Err(IpldError::F64Op(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Nan => 0,
+      Self::Inf => 0,
+      Self::Eql => 2,
+      Self::Lte => 2,
+      Self::Lth => 2,
+      Self::Gth => 2,
+      Self::Gte => 2,
+      Self::IsNan => 1,
+      Self::IsInf => 1,
+      Self::Neg => 1,
+      Self::Abs => 1,
+      Self::Add => 2,
+      Self::Sub => 2,
+      Self::Mul => 2,
+      Self::Div => 2,
+      Self::Rem => 2,
+      Self::Pow => 2,
+      Self::Sqrt => 1,
+      Self::Exp => 1,
+      Self::Ln => 1,
+      Self::Floor => 1,
+      Self::Ceil => 1,
+      Self::Round => 1,
+      Self::Trunc => 1,
+      Self::Sin => 1,
+      Self::Cos => 1,
+      Self::Tan => 1,
+      Self::Asin => 1,
+      Self::Acos => 1,
+      Self::Atan => 1,
+      Self::Atan2 => 2,
+      Self::ToBits => 1,
+      Self::FromBits => 1,
+      Self::ToI64 => 1,
+      Self::FromI64 => 1,
+      Self::ToF32 => 1,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Nan, []) => Some(F64(f64::NAN)),
+      (Self::Inf, []) => Some(F64(f64::INFINITY)),
+      (Self::IsNan, [F64(x)]) => Some(Bool(x.is_nan())),
+      (Self::IsInf, [F64(x)]) => Some(Bool(x.is_infinite())),
+      (Self::Neg, [F64(x)]) => Some(F64(-x)),
+      (Self::Abs, [F64(x)]) => Some(F64(libm::fabs(*x))),
+      (Self::Sqrt, [F64(x)]) => Some(F64(libm::sqrt(*x))),
+      (Self::Exp, [F64(x)]) => Some(F64(libm::exp(*x))),
+      (Self::Ln, [F64(x)]) => Some(F64(libm::log(*x))),
+      (Self::Floor, [F64(x)]) => Some(F64(libm::floor(*x))),
+      (Self::Ceil, [F64(x)]) => Some(F64(libm::ceil(*x))),
+      (Self::Round, [F64(x)]) => Some(F64(libm::round(*x))),
+      (Self::Trunc, [F64(x)]) => Some(F64(libm::trunc(*x))),
+      (Self::Sin, [F64(x)]) => Some(F64(libm::sin(*x))),
+      (Self::Cos, [F64(x)]) => Some(F64(libm::cos(*x))),
+      (Self::Tan, [F64(x)]) => Some(F64(libm::tan(*x))),
+      (Self::Asin, [F64(x)]) => Some(F64(libm::asin(*x))),
+      (Self::Acos, [F64(x)]) => Some(F64(libm::acos(*x))),
+      (Self::Atan, [F64(x)]) => Some(F64(libm::atan(*x))),
+      (Self::ToBits, [F64(x)]) => Some(U64(x.to_bits())),
+      (Self::FromBits, [U64(x)]) => Some(F64(f64::from_bits(*x))),
+      // Truncates toward zero, and is stuck on NaN or out of range values
+      (Self::ToI64, [F64(x)]) => {
+        if *x >= i64::MIN as f64 && *x < -(i64::MIN as f64) {
+          Some(I64(*x as i64))
+        }
+        else {
+          None
+        }
+      }
+      (Self::FromI64, [I64(x)]) => Some(F64(*x as f64)),
+      (Self::ToF32, [F64(x)]) => Some(F32(*x as f32)),
+      (Self::Eql, [F64(x), F64(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [F64(x), F64(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [F64(x), F64(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [F64(x), F64(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [F64(x), F64(y)]) => Some(Bool(x >= y)),
+      (Self::Add, [F64(x), F64(y)]) => Some(F64(x + y)),
+      (Self::Sub, [F64(x), F64(y)]) => Some(F64(x - y)),
+      (Self::Mul, [F64(x), F64(y)]) => Some(F64(x * y)),
+      (Self::Div, [F64(x), F64(y)]) => Some(F64(x / y)),
+      (Self::Rem, [F64(x), F64(y)]) => Some(F64(libm::fmod(*x, *y))),
+      (Self::Pow, [F64(x), F64(y)]) => Some(F64(libm::pow(*x, *y))),
+      (Self::Atan2, [F64(x), F64(y)]) => Some(F64(libm::atan2(*x, *y))),
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for F64Op {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+    TestResult,
+  };
+  use rand::Rng;
+  use sp_std::mem;
+  use Literal::{
+    Bool,
+    F32,
+    F64,
+    I64,
+    U64,
+  };
+  impl Arbitrary for F64Op {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=35);
+      match gen {
+        0 => Self::Nan,
+        1 => Self::Inf,
+        2 => Self::Eql,
+        3 => Self::Lte,
+        4 => Self::Lth,
+        5 => Self::Gth,
+        6 => Self::Gte,
+        7 => Self::IsNan,
+        8 => Self::IsInf,
+        9 => Self::Neg,
+        10 => Self::Abs,
+        11 => Self::Add,
+        12 => Self::Sub,
+        13 => Self::Mul,
+        14 => Self::Div,
+        15 => Self::Rem,
+        16 => Self::Pow,
+        17 => Self::Sqrt,
+        18 => Self::Exp,
+        19 => Self::Ln,
+        20 => Self::Floor,
+        21 => Self::Ceil,
+        22 => Self::Round,
+        23 => Self::Trunc,
+        24 => Self::Sin,
+        25 => Self::Cos,
+        26 => Self::Tan,
+        27 => Self::Asin,
+        28 => Self::Acos,
+        29 => Self::Atan,
+        30 => Self::Atan2,
+        31 => Self::ToBits,
+        32 => Self::FromBits,
+        33 => Self::ToI64,
+        34 => Self::FromI64,
+        _ => Self::ToF32,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn f64_op_ipld(x: F64Op) -> bool {
+    match F64Op::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  /// Equality under which any two NaNs match, since the payload of a NaN
+  /// result isn't specified
+  pub fn same(x: Option<Literal>, y: Option<Literal>) -> bool {
+    match (x, y) {
+      (Some(F64(x)), Some(F64(y))) => {
+        (x.is_nan() && y.is_nan()) || x.to_bits() == y.to_bits()
+      }
+      (Some(F32(x)), Some(F32(y))) => {
+        (x.is_nan() && y.is_nan()) || x.to_bits() == y.to_bits()
+      }
+      (x, y) => x == y,
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply(op: F64Op, a: f64, b: f64, c: u64, d: i64) -> TestResult {
+    let apply0_go = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(same(F64Op::apply(op, &[]), expected))
+    };
+
+    let apply1_f64 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(same(F64Op::apply(op, &[&F64(a)]), expected))
+    };
+
+    let apply2_f64_f64 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(same(
+        F64Op::apply(op, &[&F64(a), &F64(b)]),
+        expected,
+      ))
+    };
+
+    match op {
+      F64Op::Nan => apply0_go(Some(F64(f64::NAN))),
+      F64Op::Inf => apply0_go(Some(F64(f64::INFINITY))),
+      F64Op::Eql => apply2_f64_f64(Some(Bool(a == b))),
+      F64Op::Lte => apply2_f64_f64(Some(Bool(a <= b))),
+      F64Op::Lth => apply2_f64_f64(Some(Bool(a < b))),
+      F64Op::Gth => apply2_f64_f64(Some(Bool(a > b))),
+      F64Op::Gte => apply2_f64_f64(Some(Bool(a >= b))),
+      F64Op::IsNan => apply1_f64(Some(Bool(a.is_nan()))),
+      F64Op::IsInf => apply1_f64(Some(Bool(a.is_infinite()))),
+      F64Op::Neg => apply1_f64(Some(F64(-a))),
+      F64Op::Abs => apply1_f64(Some(F64(a.abs()))),
+      F64Op::Add => apply2_f64_f64(Some(F64(a + b))),
+      F64Op::Sub => apply2_f64_f64(Some(F64(a - b))),
+      F64Op::Mul => apply2_f64_f64(Some(F64(a * b))),
+      F64Op::Div => apply2_f64_f64(Some(F64(a / b))),
+      F64Op::Sqrt => apply1_f64(Some(F64(a.sqrt()))),
+      F64Op::Floor => apply1_f64(Some(F64(a.floor()))),
+      F64Op::Ceil => apply1_f64(Some(F64(a.ceil()))),
+      F64Op::Round => apply1_f64(Some(F64(a.round()))),
+      F64Op::Trunc => apply1_f64(Some(F64(a.trunc()))),
+      // Transcendental functions may differ from the platform's in the last
+      // place, so these are checked in `test_apply_transcendental`
+      F64Op::Rem
+      | F64Op::Pow
+      | F64Op::Exp
+      | F64Op::Ln
+      | F64Op::Sin
+      | F64Op::Cos
+      | F64Op::Tan
+      | F64Op::Asin
+      | F64Op::Acos
+      | F64Op::Atan
+      | F64Op::Atan2 => TestResult::discard(),
+      F64Op::ToBits => apply1_f64(Some(U64(a.to_bits()))),
+      F64Op::FromBits => TestResult::from_bool(same(
+        F64Op::apply(op, &[&U64(c)]),
+        Some(F64(f64::from_bits(c))),
+      )),
+      F64Op::ToI64 => {
+        if a >= -(2f64.powi(63)) && a < 2f64.powi(63) {
+          apply1_f64(Some(I64(a as i64)))
+        }
+        else {
+          apply1_f64(None)
+        }
+      }
+      F64Op::FromI64 => TestResult::from_bool(same(
+        F64Op::apply(op, &[&I64(d)]),
+        Some(F64(d as f64)),
+      )),
+      F64Op::ToF32 => apply1_f64(Some(F32(a as f32))),
+    }
+  }
+
+  #[test]
+  fn test_apply_transcendental() {
+    let apply1 = |op: F64Op, x: f64| match op.apply(&[&F64(x)]) {
+      Some(F64(y)) => y,
+      _ => panic!("{} of {} is stuck", op, x),
+    };
+    let apply2 = |op: F64Op, x: f64, y: f64| match op.apply(&[&F64(x), &F64(y)])
+    {
+      Some(F64(z)) => z,
+      _ => panic!("{} of {} and {} is stuck", op, x, y),
+    };
+    let close = |x: f64, y: f64| (x - y).abs() <= 1e-12;
+    assert!(close(apply1(F64Op::Sin, core::f64::consts::FRAC_PI_2), 1.0));
+    assert!(close(apply1(F64Op::Cos, core::f64::consts::PI), -1.0));
+    assert!(close(apply1(F64Op::Tan, core::f64::consts::FRAC_PI_4), 1.0));
+    assert!(close(apply1(F64Op::Asin, 1.0), core::f64::consts::FRAC_PI_2));
+    assert!(close(apply1(F64Op::Acos, -1.0), core::f64::consts::PI));
+    assert!(close(apply1(F64Op::Atan, 1.0), core::f64::consts::FRAC_PI_4));
+    assert!(close(apply1(F64Op::Exp, 1.0), core::f64::consts::E));
+    assert!(close(apply1(F64Op::Ln, core::f64::consts::E), 1.0));
+    assert!(close(
+      apply2(F64Op::Atan2, 1.0, -1.0),
+      3.0 * core::f64::consts::FRAC_PI_4
+    ));
+    assert_eq!(apply2(F64Op::Pow, 2.0, 10.0), 1024.0);
+    assert_eq!(apply2(F64Op::Rem, -7.5, 2.0), -1.5);
+    assert!(apply1(F64Op::Sqrt, -1.0).is_nan());
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(
+    op: F64Op,
+    a: Literal,
+    b: f64,
+    test_arg_2: bool,
+  ) -> TestResult {
+    let test_apply1_none_on_invalid = |valid_arg: Literal| -> TestResult {
+      if mem::discriminant(&valid_arg) == mem::discriminant(&a) {
+        TestResult::discard()
+      }
+      else {
+        TestResult::from_bool(F64Op::apply(op, &[&a]) == None)
+      }
+    };
+
+    let test_apply2_none_on_invalid =
+      |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
+        let go =
+          || TestResult::from_bool(F64Op::apply(op, &[&a_, &b_]) == None);
+        if test_arg_2 {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        else {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      };
+
+    match op {
+      // Arity 0.
+      F64Op::Nan | F64Op::Inf => TestResult::discard(),
+      // Arity 1, valid is U64.
+      F64Op::FromBits => test_apply1_none_on_invalid(U64(0)),
+      // Arity 1, valid is I64.
+      F64Op::FromI64 => test_apply1_none_on_invalid(I64(0)),
+      // Arity 1, valid is F64.
+      F64Op::IsNan
+      | F64Op::IsInf
+      | F64Op::Neg
+      | F64Op::Abs
+      | F64Op::Sqrt
+      | F64Op::Exp
+      | F64Op::Ln
+      | F64Op::Floor
+      | F64Op::Ceil
+      | F64Op::Round
+      | F64Op::Trunc
+      | F64Op::Sin
+      | F64Op::Cos
+      | F64Op::Tan
+      | F64Op::Asin
+      | F64Op::Acos
+      | F64Op::Atan
+      | F64Op::ToBits
+      | F64Op::ToI64
+      | F64Op::ToF32 => test_apply1_none_on_invalid(F64(b)),
+      // Arity 2, valid are F64 on a and b.
+      F64Op::Eql
+      | F64Op::Lte
+      | F64Op::Lth
+      | F64Op::Gth
+      | F64Op::Gte
+      | F64Op::Add
+      | F64Op::Sub
+      | F64Op::Mul
+      | F64Op::Div
+      | F64Op::Rem
+      | F64Op::Pow
+      | F64Op::Atan2 => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(F64(b), a, F64(b))
+        }
+        else {
+          test_apply2_none_on_invalid(F64(b), F64(b), a)
+        }
+      }
+    }
+  }
+}
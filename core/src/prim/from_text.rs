@@ -0,0 +1,225 @@
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::{
+    LitType,
+    Literal,
+  },
+  parse,
+  position::Pos,
+  prim::conv::{
+    from_int,
+    type_name,
+    NUM_TYPES,
+  },
+  term::Term,
+  yatima,
+};
+
+use num_bigint::{
+  BigInt,
+  BigUint,
+};
+
+/// Parses text into a numeric type, such as `#U64.from_Text`. The result is
+/// an option that is `none` if the text isn't a number or is out of range
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct FromTextOp {
+  pub typ: LitType,
+}
+
+impl FromTextOp {
+  /// Makes a parser for a numeric type
+  pub fn new(typ: LitType) -> Option<Self> {
+    if NUM_TYPES.contains(&typ) {
+      Some(FromTextOp { typ })
+    }
+    else {
+      None
+    }
+  }
+
+  /// Gets the syntax string of a parser, without the prefix of its type
+  pub fn symbol(self) -> String { "from_Text".to_owned() }
+
+  /// Gets a parser from its type and syntax string
+  pub fn from_symbol(typ: LitType, x: &str) -> Option<Self> {
+    match x {
+      "from_Text" => Self::new(typ),
+      _ => None,
+    }
+  }
+
+  /// Gets a parser from a full name such as `U64.from_Text`
+  pub fn from_name(x: &str) -> Option<Self> {
+    let mut parts = x.splitn(2, '.');
+    let typ = parts.next()?;
+    let typ = NUM_TYPES.iter().find(|t| type_name(**t) == typ)?;
+    Self::from_symbol(*typ, parts.next()?)
+  }
+
+  /// Returns the type of a parser
+  pub fn type_of(self) -> Term {
+    yatima!(
+      "∀ #Text (0 P: Type) (none: P) (some: ∀ #$0 -> P) -> P",
+      Term::LTy(Pos::None, self.typ)
+    )
+  }
+
+  /// Converts a parser into an IPLD object
+  pub fn to_ipld(self) -> Ipld { self.typ.to_ipld() }
+
+  /// Converts an IPLD object into a parser
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    let err = || IpldError::FromTextOp(ipld.to_owned());
+    let typ = LitType::from_ipld(ipld).map_err(|_| err())?;
+    Self::new(typ).ok_or_else(err)
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 { 1 }
+
+  /// Parses a text literal, returning `Some(None)` when the text isn't a
+  /// number of the parser's type and `None` if the argument isn't text
+  pub fn apply_parsed(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    match args {
+      [Literal::Text(xs)] => {
+        Some(parse_int(&xs.to_string()).and_then(|x| from_int(self.typ, &x)))
+      }
+      _ => None,
+    }
+  }
+}
+
+/// Parses an integer with an optional sign and an optional `0x`, `0o` or `0b`
+/// radix prefix. A type suffix, separators or surrounding whitespace aren't
+/// accepted
+pub fn parse_int(x: &str) -> Option<BigInt> {
+  let (negative, x) = match x.strip_prefix('-') {
+    Some(x) => (true, x),
+    None => (false, x.strip_prefix('+').unwrap_or(x)),
+  };
+  let (radix, digits) = if let Some(x) = x.strip_prefix("0x") {
+    (16, x)
+  }
+  else if let Some(x) = x.strip_prefix("0o") {
+    (8, x)
+  }
+  else if let Some(x) = x.strip_prefix("0b") {
+    (2, x)
+  }
+  else {
+    (10, x)
+  };
+  if digits.is_empty() || !digits.chars().all(|c| c.is_digit(radix)) {
+    return None;
+  }
+  let x = BigInt::from(BigUint::parse_bytes(digits.as_bytes(), radix)?);
+  if negative {
+    Some(-x)
+  }
+  else {
+    Some(x)
+  }
+}
+
+impl fmt::Display for FromTextOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::prim::conv::to_int;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  use sp_ropey::Rope;
+  impl Arbitrary for FromTextOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      FromTextOp { typ: NUM_TYPES[rng.gen_range(0..NUM_TYPES.len())] }
+    }
+  }
+
+  #[quickcheck]
+  fn from_text_op_ipld(x: FromTextOp) -> bool {
+    match FromTextOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn from_text_op_symbol(x: FromTextOp) -> bool {
+    FromTextOp::from_name(&format!("{}.from_Text", type_name(x.typ))) == Some(x)
+  }
+
+  #[test]
+  fn test_parse_int() {
+    let int = |x: i64| Some(BigInt::from(x));
+    assert_eq!(parse_int("42"), int(42));
+    assert_eq!(parse_int("+42"), int(42));
+    assert_eq!(parse_int("-42"), int(-42));
+    assert_eq!(parse_int("0x2a"), int(42));
+    assert_eq!(parse_int("0x2A"), int(42));
+    assert_eq!(parse_int("-0b101010"), int(-42));
+    assert_eq!(parse_int("0o52"), int(42));
+    assert_eq!(parse_int(""), None);
+    assert_eq!(parse_int("-"), None);
+    assert_eq!(parse_int("0x"), None);
+    assert_eq!(parse_int("0b12"), None);
+    assert_eq!(parse_int("--1"), None);
+    assert_eq!(parse_int("1_000"), None);
+    assert_eq!(parse_int(" 1"), None);
+    assert_eq!(parse_int("42u8"), None);
+  }
+
+  #[test]
+  fn test_range() {
+    use Literal::*;
+    let read = |typ, x: &str| {
+      FromTextOp { typ }.apply_parsed(&[&Text(Rope::from(x))]).unwrap()
+    };
+    assert_eq!(read(LitType::U8, "255"), Some(U8(255)));
+    assert_eq!(read(LitType::U8, "256"), None);
+    assert_eq!(read(LitType::I8, "-128"), Some(I8(-128)));
+    assert_eq!(read(LitType::I8, "0x80"), None);
+    assert_eq!(read(LitType::Nat, "-1"), None);
+    assert_eq!(read(LitType::Nat, "0x10"), Some(Nat(16u64.into())));
+  }
+
+  // Parsing the decimal digits of a number gives it back
+  #[quickcheck]
+  fn test_parse_agrees(op: FromTextOp, x: i128) -> bool {
+    let text = Literal::Text(Rope::from(x.to_string()));
+    let res = from_int(op.typ, &x.into());
+    op.apply_parsed(&[&text]) == Some(res.clone())
+      && res.map_or(true, |y| to_int(op.typ, &y) == Some(x.into()))
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: FromTextOp, a: Literal) -> bool {
+    match a {
+      Literal::Text(_) => true,
+      _ => op.apply_parsed(&[&a]) == None,
+    }
+  }
+}
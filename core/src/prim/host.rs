@@ -0,0 +1,206 @@
+use sp_cid::Cid;
+use sp_ipld::{
+  dag_cbor::cid,
+  Ipld,
+};
+use sp_std::{
+  collections::btree_map::BTreeMap,
+  fmt,
+  rc::Rc,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::{
+  literal::Literal,
+  prim::Op,
+  term::Term,
+};
+
+/// A primitive operation provided by an embedder of the evaluator, such as a
+/// storage read or an oracle call
+pub trait HostOp {
+  /// A readable name for the operation
+  fn name(&self) -> String;
+
+  /// Returns the type of the operation
+  fn type_of(&self) -> Term;
+
+  /// Returns the number of parameters used in the operation
+  fn arity(&self) -> u64;
+
+  /// Applies the operation to as many literals as its arity and returns the
+  /// result if successful
+  fn apply(&self, args: &[&Literal]) -> Option<Literal>;
+}
+
+/// Gets the content id of a host operation, from its name and anonymous type,
+/// which is how it is referred to in source and in IPLD
+pub fn host_cid(op: &dyn HostOp) -> Cid {
+  let (typ, _) = op.type_of().embed();
+  cid(&Ipld::List(vec![Ipld::String(op.name()), typ.to_ipld()]))
+}
+
+/// A reference to a host operation by content id. References parsed from
+/// source or IPLD are unresolved until they are looked up in the `HostOps`
+/// of the defs they are evaluated or checked with
+#[derive(Clone)]
+pub struct HostRef {
+  pub cid: Cid,
+  pub op: Option<Rc<dyn HostOp>>,
+}
+
+impl HostRef {
+  /// Makes a resolved reference to a host operation
+  pub fn new(op: Rc<dyn HostOp>) -> Self {
+    HostRef { cid: host_cid(&*op), op: Some(op) }
+  }
+
+  /// Makes an unresolved reference to the host operation with a content id
+  pub fn unresolved(cid: Cid) -> Self { HostRef { cid, op: None } }
+
+  /// Gets the syntax string of a host operation, which is its content id
+  pub fn symbol(&self) -> String { self.cid.to_string() }
+
+  /// Returns the type of a resolved host operation
+  pub fn type_of(&self) -> Option<Term> {
+    self.op.as_ref().map(|op| op.type_of())
+  }
+
+  /// Converts a host operation reference into an IPLD object
+  pub fn to_ipld(&self) -> Ipld { Ipld::Link(self.cid) }
+
+  /// Returns the number of parameters used in the operation, which is zero
+  /// for an unresolved reference
+  pub fn arity(&self) -> u64 { self.op.as_ref().map_or(0, |op| op.arity()) }
+
+  /// Applies a resolved host operation to its arguments
+  pub fn apply(&self, args: &[&Literal]) -> Option<Literal> {
+    let op = self.op.as_ref()?;
+    if args.len() as u64 == op.arity() {
+      op.apply(args)
+    }
+    else {
+      None
+    }
+  }
+}
+
+impl PartialEq for HostRef {
+  fn eq(&self, rhs: &Self) -> bool { self.cid == rhs.cid }
+}
+
+impl Eq for HostRef {}
+
+impl fmt::Debug for HostRef {
+  fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match &self.op {
+      Some(op) => write!(fmt, "HostRef {} {}", self.cid, op.name()),
+      None => write!(fmt, "HostRef {}", self.cid),
+    }
+  }
+}
+
+impl fmt::Display for HostRef {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+/// The host operations registered by an embedder, keyed by content id
+#[derive(Clone, Default)]
+pub struct HostOps {
+  pub ops: BTreeMap<Cid, Rc<dyn HostOp>>,
+}
+
+impl HostOps {
+  /// Makes an empty table of host operations
+  pub fn new() -> Self { HostOps::default() }
+
+  /// Registers a host operation, returning its content id
+  pub fn register(&mut self, op: Rc<dyn HostOp>) -> Cid {
+    let cid = host_cid(&*op);
+    self.ops.insert(cid, op);
+    cid
+  }
+
+  /// Gets a reference to a registered host operation
+  pub fn get(&self, cid: &Cid) -> Option<HostRef> {
+    self.ops.get(cid).map(|op| HostRef { cid: *cid, op: Some(op.clone()) })
+  }
+
+  /// Resolves an operation if it is an unresolved reference to a registered
+  /// host operation, and otherwise returns it unchanged
+  pub fn resolve(&self, opr: Op) -> Op {
+    match opr {
+      Op::Host(HostRef { cid, op: None }) => match self.get(&cid) {
+        Some(op) => Op::Host(op),
+        None => Op::Host(HostRef::unresolved(cid)),
+      },
+      opr => opr,
+    }
+  }
+
+  /// Adds the host operations of another table
+  pub fn extend(&mut self, other: &HostOps) {
+    for (k, v) in other.ops.iter() {
+      self.ops.insert(*k, v.clone());
+    }
+  }
+}
+
+impl PartialEq for HostOps {
+  fn eq(&self, rhs: &Self) -> bool { self.ops.keys().eq(rhs.ops.keys()) }
+}
+
+impl fmt::Debug for HostOps {
+  fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let names: Vec<String> = self.ops.values().map(|op| op.name()).collect();
+    write!(fmt, "HostOps {:?}", names)
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    defs,
+    parse,
+    yatima,
+  };
+
+  /// Doubles a `#U64`, standing in for an embedder's operation
+  pub struct Double;
+
+  impl HostOp for Double {
+    fn name(&self) -> String { "double".to_string() }
+
+    fn type_of(&self) -> Term { yatima!("∀ #U64 -> #U64") }
+
+    fn arity(&self) -> u64 { 1 }
+
+    fn apply(&self, args: &[&Literal]) -> Option<Literal> {
+      match args {
+        [Literal::U64(x)] => x.checked_mul(2).map(Literal::U64),
+        _ => None,
+      }
+    }
+  }
+
+  #[test]
+  fn test_host_ops() {
+    let mut ops = HostOps::new();
+    let cid = ops.register(Rc::new(Double));
+    assert_eq!(cid, host_cid(&Double));
+    let opr = ops.resolve(Op::Host(HostRef::unresolved(cid)));
+    assert_eq!(opr.arity(), 1);
+    assert_eq!(opr.apply(&[&Literal::U64(2)]), Some(Literal::U64(4)));
+    assert_eq!(Op::from_ipld(&opr.to_ipld()), Ok(opr.clone()));
+    let unknown = HostOps::new().resolve(Op::Host(HostRef::unresolved(cid)));
+    assert_eq!(unknown.apply(&[&Literal::U64(2)]), None);
+  }
+}
@@ -9,15 +9,17 @@ use sp_std::{
 use alloc::string::String;
 
 use crate::{
+  defs,
   ipld_error::IpldError,
   literal::Literal,
+  parse,
   prim::bits,
   term::Term,
   yatima,
-  parse,
-  defs,
 };
 
+use num_bigint::BigUint;
+
 /// Primitive 128-bit signed integer operations
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum I128Op {
@@ -59,6 +61,16 @@ pub enum I128Op {
   ToInt,
   ToBits,
   ToBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl I128Op {
@@ -103,6 +115,16 @@ impl I128Op {
       Self::ToInt => "to_Int".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -147,6 +169,16 @@ impl I128Op {
       "to_Int" => Some(Self::ToInt),
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -154,8 +186,8 @@ impl I128Op {
   /// Returns the type of an i128 operation
   pub fn type_of(self) -> Term {
     match self {
-      Self::Abs => yatima!("#I128 -> #U8"),
-      Self::Sgn => yatima!("#I128 -> #Bool"),
+      Self::Abs => yatima!("∀ #I128 -> #U128"),
+      Self::Sgn => yatima!("∀ #I128 -> #Bool"),
       Self::Max => yatima!("#I128"),
       Self::Min => yatima!("#I128"),
       Self::Eql => yatima!("∀ #I128 #I128 -> #Bool"),
@@ -163,10 +195,10 @@ impl I128Op {
       Self::Lth => yatima!("∀ #I128 #I128 -> #Bool"),
       Self::Gth => yatima!("∀ #I128 #I128 -> #Bool"),
       Self::Gte => yatima!("∀ #I128 #I128 -> #Bool"),
-      Self::Not => yatima!("∀ #I128 #I128 -> #Bool"),
-      Self::And => yatima!("∀ #I128 #I128 -> #Bool"),
-      Self::Or => yatima!("∀ #I128 #I128 -> #Bool"),
-      Self::Xor => yatima!("∀ #I128 #I128 -> #Bool"),
+      Self::Not => yatima!("∀ #I128 -> #I128"),
+      Self::And => yatima!("∀ #I128 #I128 -> #I128"),
+      Self::Or => yatima!("∀ #I128 #I128 -> #I128"),
+      Self::Xor => yatima!("∀ #I128 #I128 -> #I128"),
       Self::Add => yatima!("∀ #I128 #I128 -> #I128"),
       Self::Sub => yatima!("∀ #I128 #I128 -> #I128"),
       Self::Mul => yatima!("∀ #I128 #I128 -> #I128"),
@@ -190,8 +222,20 @@ impl I128Op {
       Self::ToI32 => yatima!("∀ #I128 -> #I32"),
       Self::ToI64 => yatima!("∀ #I128 -> #I64"),
       Self::ToInt => yatima!("∀ #I128 -> #Int"),
-      Self::ToBits => yatima!("∀ #I128 -> #Bits"),
       Self::ToBytes => yatima!("∀ #I128 -> #Bytes"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #I128 #I128 (0 P: Type) (none: P) (some: ∀ #I128 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #I128 #I128 -> #I128"),
+      Self::WrappingSub => yatima!("∀ #I128 #I128 -> #I128"),
+      Self::WrappingMul => yatima!("∀ #I128 #I128 -> #I128"),
+      Self::SaturatingAdd => yatima!("∀ #I128 #I128 -> #I128"),
+      Self::SaturatingSub => yatima!("∀ #I128 #I128 -> #I128"),
+      Self::SaturatingMul => yatima!("∀ #I128 #I128 -> #I128"),
+      Self::ToBits => yatima!("∀ #I128 -> #Bits"),
     }
   }
 
@@ -236,6 +280,16 @@ impl I128Op {
       Self::ToInt => Ipld::Integer(35),
       Self::ToBits => Ipld::Integer(36),
       Self::ToBytes => Ipld::Integer(37),
+      Self::CheckedAdd => Ipld::Integer(38),
+      Self::CheckedSub => Ipld::Integer(39),
+      Self::CheckedMul => Ipld::Integer(40),
+      Self::CheckedDiv => Ipld::Integer(41),
+      Self::WrappingAdd => Ipld::Integer(42),
+      Self::WrappingSub => Ipld::Integer(43),
+      Self::WrappingMul => Ipld::Integer(44),
+      Self::SaturatingAdd => Ipld::Integer(45),
+      Self::SaturatingSub => Ipld::Integer(46),
+      Self::SaturatingMul => Ipld::Integer(47),
     }
   }
 
@@ -280,7 +334,17 @@ impl I128Op {
       Ipld::Integer(35) => Ok(Self::ToInt),
       Ipld::Integer(36) => Ok(Self::ToBits),
       Ipld::Integer(37) => Ok(Self::ToBytes),
-      xs => Err(IpldError::NatOp(xs.to_owned())),
+      Ipld::Integer(38) => Ok(Self::CheckedAdd),
+      Ipld::Integer(39) => Ok(Self::CheckedSub),
+      Ipld::Integer(40) => Ok(Self::CheckedMul),
+      Ipld::Integer(41) => Ok(Self::CheckedDiv),
+      Ipld::Integer(42) => Ok(Self::WrappingAdd),
+      Ipld::Integer(43) => Ok(Self::WrappingSub),
+      Ipld::Integer(44) => Ok(Self::WrappingMul),
+      Ipld::Integer(45) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(46) => Ok(Self::SaturatingSub),
+      Ipld::Integer(47) => Ok(Self::SaturatingMul),
+      xs => Err(IpldError::I128Op(xs.to_owned())),
     }
   }
 
@@ -323,70 +387,120 @@ impl I128Op {
       Self::ToI32 => 1,
       Self::ToI64 => 1,
       Self::ToInt => 1,
-      Self::ToBytes => 1,
       Self::ToBits => 1,
+      Self::ToBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
-    use Literal::*;
-    match self {
-      Self::Max => Some(I16(i16::MAX)),
-      Self::Min => Some(I16(i16::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::Abs, I128(x)) => Some(U128(x.unsigned_abs())),
-      (Self::Sgn, I128(x)) => Some(Bool(x.is_positive())),
-      (Self::CountZeros, I128(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, I128(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, I128(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU16, I128(x)) => u16::try_from(*x).ok().map(U16),
-      (Self::ToU32, I128(x)) => u32::try_from(*x).ok().map(U32),
-      (Self::ToU64, I128(x)) => u64::try_from(*x).ok().map(U64),
-      (Self::ToU128, I128(x)) => u128::try_from(*x).ok().map(U128),
-      (Self::ToI8, I128(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI16, I128(x)) => i16::try_from(*x).ok().map(I16),
-      (Self::ToI32, I128(x)) => i32::try_from(*x).ok().map(I32),
-      (Self::ToI64, I128(x)) => i64::try_from(*x).ok().map(I64),
-      (Self::Not, I128(x)) => Some(I128(!x)),
-      (Self::ToInt, I128(x)) => Some(Int((*x).into())),
-      (Self::ToBytes, I128(x)) => Some(Bytes(x.to_be_bytes().into())),
-      (Self::ToBits, I128(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(I128(i128::MAX)),
+      (Self::Min, []) => Some(I128(i128::MIN)),
+      (Self::Abs, [I128(x)]) => Some(U128(x.unsigned_abs())),
+      (Self::Sgn, [I128(x)]) => Some(Bool(x.is_positive())),
+      (Self::CountZeros, [I128(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [I128(x)]) => Some(U32(x.count_ones())),
+      (Self::ToU8, [I128(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU16, [I128(x)]) => u16::try_from(*x).ok().map(U16),
+      (Self::ToU32, [I128(x)]) => u32::try_from(*x).ok().map(U32),
+      (Self::ToU64, [I128(x)]) => u64::try_from(*x).ok().map(U64),
+      (Self::ToU128, [I128(x)]) => u128::try_from(*x).ok().map(U128),
+      (Self::ToNat, [I128(x)]) => {
+        if x.is_negative() {
+          None
+        }
+        else {
+          Some(Nat(BigUint::from(u128::try_from(*x).unwrap())))
+        }
+      }
+      (Self::ToI8, [I128(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI16, [I128(x)]) => i16::try_from(*x).ok().map(I16),
+      (Self::ToI32, [I128(x)]) => i32::try_from(*x).ok().map(I32),
+      (Self::ToI64, [I128(x)]) => i64::try_from(*x).ok().map(I64),
+      (Self::Not, [I128(x)]) => Some(I128(!x)),
+      (Self::ToInt, [I128(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [I128(x)]) => {
         Some(Bits(bits::bytes_to_bits(128, &x.to_be_bytes().into())))
       }
+      (Self::ToBytes, [I128(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [I128(x), I128(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [I128(x), I128(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [I128(x), I128(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [I128(x), I128(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [I128(x), I128(y)]) => Some(Bool(x >= y)),
+      (Self::And, [I128(x), I128(y)]) => Some(I128(x & y)),
+      (Self::Or, [I128(x), I128(y)]) => Some(I128(x | y)),
+      (Self::Xor, [I128(x), I128(y)]) => Some(I128(x ^ y)),
+      (Self::Add, [I128(x), I128(y)]) => Some(I128(x.wrapping_add(*y))),
+      (Self::Sub, [I128(x), I128(y)]) => Some(I128(x.wrapping_sub(*y))),
+      (Self::Mul, [I128(x), I128(y)]) => Some(I128(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [I128(x), I128(y)]) => Some(I128(x.wrapping_add(*y))),
+      (Self::WrappingSub, [I128(x), I128(y)]) => Some(I128(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [I128(x), I128(y)]) => Some(I128(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [I128(x), I128(y)]) => {
+        Some(I128(x.saturating_add(*y)))
+      }
+      (Self::SaturatingSub, [I128(x), I128(y)]) => {
+        Some(I128(x.saturating_sub(*y)))
+      }
+      (Self::SaturatingMul, [I128(x), I128(y)]) => {
+        Some(I128(x.saturating_mul(*y)))
+      }
+      (Self::Div, [I128(x), I128(y)]) => {
+        if *y == 0 {
+          None
+        }
+        else {
+          Some(I128(x.wrapping_div(*y)))
+        }
+      }
+      (Self::Mod, [I128(x), I128(y)]) => {
+        if *y == 0 {
+          None
+        }
+        else {
+          Some(I128(x.wrapping_rem(*y)))
+        }
+      }
+      (Self::Pow, [I128(x), U32(y)]) => Some(I128(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), I128(y)]) => Some(I128(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), I128(y)]) => Some(I128(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), I128(y)]) => Some(I128(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), I128(y)]) => Some(I128(y.rotate_right(*x))),
       _ => None,
     }
   }
 
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
     use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, I128(x), I128(y)) => Some(Bool(x == y)),
-      (Self::Lte, I128(x), I128(y)) => Some(Bool(x <= y)),
-      (Self::Lth, I128(x), I128(y)) => Some(Bool(x < y)),
-      (Self::Gth, I128(x), I128(y)) => Some(Bool(x > y)),
-      (Self::Gte, I128(x), I128(y)) => Some(Bool(x >= y)),
-      (Self::And, I128(x), I128(y)) => Some(I128(x & y)),
-      (Self::Or, I128(x), I128(y)) => Some(I128(x | y)),
-      (Self::Xor, I128(x), I128(y)) => Some(I128(x ^ y)),
-      (Self::Add, I128(x), I128(y)) => Some(I128(x.wrapping_add(*y))),
-      (Self::Sub, I128(x), I128(y)) => Some(I128(x.wrapping_sub(*y))),
-      (Self::Mul, I128(x), I128(y)) => Some(I128(x.wrapping_mul(*y))),
-      (Self::Div, I128(x), I128(y)) => Some(I128(x.wrapping_div(*y))),
-      (Self::Mod, I128(x), I128(y)) => Some(I128(x.wrapping_rem(*y))),
-      (Self::Pow, I128(x), U32(y)) => Some(I128(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), I128(y)) => Some(I128(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), I128(y)) => Some(I128(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), I128(y)) => Some(I128(y.rotate_left(*x))),
-      (Self::Ror, U32(x), I128(y)) => Some(I128(y.rotate_right(*x))),
+    match (self, args) {
+      (Self::CheckedAdd, [I128(x), I128(y)]) => {
+        Some(x.checked_add(*y).map(I128))
+      }
+      (Self::CheckedSub, [I128(x), I128(y)]) => {
+        Some(x.checked_sub(*y).map(I128))
+      }
+      (Self::CheckedMul, [I128(x), I128(y)]) => {
+        Some(x.checked_mul(*y).map(I128))
+      }
+      (Self::CheckedDiv, [I128(x), I128(y)]) => {
+        Some(x.checked_div(*y).map(I128))
+      }
       _ => None,
     }
   }
@@ -401,15 +515,38 @@ impl fmt::Display for I128Op {
 #[cfg(test)]
 pub mod tests {
   use super::*;
+  use crate::prim::{
+    I16Op,
+    I32Op,
+    I64Op,
+    I8Op,
+    U16Op,
+    U32Op,
+    U64Op,
+    U8Op,
+  };
+  use num_bigint::BigUint;
   use quickcheck::{
     Arbitrary,
     Gen,
+    TestResult,
   };
   use rand::Rng;
+  use sp_std::mem;
+  use Literal::{
+    Bits,
+    Bool,
+    Bytes,
+    Int,
+    Nat,
+    I128,
+    U128,
+    U32,
+  };
   impl Arbitrary for I128Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..37);
+      let gen: u32 = rng.gen_range(0..=47);
       match gen {
         0 => Self::Abs,
         1 => Self::Sgn,
@@ -448,7 +585,17 @@ pub mod tests {
         34 => Self::ToI64,
         35 => Self::ToInt,
         36 => Self::ToBytes,
-        _ => Self::ToBits,
+        37 => Self::ToBits,
+        38 => Self::CheckedAdd,
+        39 => Self::CheckedSub,
+        40 => Self::CheckedMul,
+        41 => Self::CheckedDiv,
+        42 => Self::WrappingAdd,
+        43 => Self::WrappingSub,
+        44 => Self::WrappingMul,
+        45 => Self::SaturatingAdd,
+        46 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
       }
     }
   }
@@ -460,4 +607,304 @@ pub mod tests {
       _ => false,
     }
   }
+
+  #[quickcheck]
+  fn test_apply(op: I128Op, a: i128, b: i128, c: u32) -> TestResult {
+    let apply0_go = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(I128Op::apply(op, &[]) == expected)
+    };
+
+    let apply1_i128 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(I128Op::apply(op, &[&I128(a)]) == expected)
+    };
+
+    let apply2_i128_i128 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        I128Op::apply(op, &[&I128(a), &I128(b)]) == expected,
+      )
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        I128Op::apply_checked(op, &[&I128(a), &I128(b)]) == Some(expected),
+      )
+    };
+
+    let apply2_i128_u32 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(I128Op::apply(op, &[&I128(a), &U32(c)]) == expected)
+    };
+
+    let apply2_u32_i128 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(I128Op::apply(op, &[&U32(c), &I128(a)]) == expected)
+    };
+
+    let from_bool = TestResult::from_bool;
+
+    match op {
+      I128Op::Abs => apply1_i128(Some(U128(a.unsigned_abs()))),
+      I128Op::Sgn => apply1_i128(Some(Bool(a.is_positive()))),
+      I128Op::Max => apply0_go(Some(I128(i128::MAX))),
+      I128Op::Min => apply0_go(Some(I128(i128::MIN))),
+      I128Op::Eql => apply2_i128_i128(Some(Bool(a == b))),
+      I128Op::Lte => apply2_i128_i128(Some(Bool(a <= b))),
+      I128Op::Lth => apply2_i128_i128(Some(Bool(a < b))),
+      I128Op::Gth => apply2_i128_i128(Some(Bool(a > b))),
+      I128Op::Gte => apply2_i128_i128(Some(Bool(a >= b))),
+      I128Op::Not => apply1_i128(Some(I128(!a))),
+      I128Op::And => apply2_i128_i128(Some(I128(a & b))),
+      I128Op::Or => apply2_i128_i128(Some(I128(a | b))),
+      I128Op::Xor => apply2_i128_i128(Some(I128(a ^ b))),
+      I128Op::Add => apply2_i128_i128(Some(I128(a.wrapping_add(b)))),
+      I128Op::Sub => apply2_i128_i128(Some(I128(a.wrapping_sub(b)))),
+      I128Op::Mul => apply2_i128_i128(Some(I128(a.wrapping_mul(b)))),
+      I128Op::Div => apply2_i128_i128(if b == 0 {
+        None
+      }
+      else {
+        Some(I128(a.wrapping_div(b)))
+      }),
+      I128Op::Mod => apply2_i128_i128(if b == 0 {
+        None
+      }
+      else {
+        Some(I128(a.wrapping_rem(b)))
+      }),
+      I128Op::WrappingAdd => apply2_i128_i128(Some(I128(a.wrapping_add(b)))),
+      I128Op::WrappingSub => apply2_i128_i128(Some(I128(a.wrapping_sub(b)))),
+      I128Op::WrappingMul => apply2_i128_i128(Some(I128(a.wrapping_mul(b)))),
+      I128Op::SaturatingAdd => {
+        apply2_i128_i128(Some(I128(a.saturating_add(b))))
+      }
+      I128Op::SaturatingSub => {
+        apply2_i128_i128(Some(I128(a.saturating_sub(b))))
+      }
+      I128Op::SaturatingMul => {
+        apply2_i128_i128(Some(I128(a.saturating_mul(b))))
+      }
+      I128Op::CheckedAdd => apply2_checked(a.checked_add(b).map(I128)),
+      I128Op::CheckedSub => apply2_checked(a.checked_sub(b).map(I128)),
+      I128Op::CheckedMul => apply2_checked(a.checked_mul(b).map(I128)),
+      I128Op::CheckedDiv => apply2_checked(a.checked_div(b).map(I128)),
+      I128Op::Pow => apply2_i128_u32(Some(I128(a.wrapping_pow(c)))),
+      I128Op::Shl => apply2_u32_i128(Some(I128(a.wrapping_shl(c)))),
+      I128Op::Shr => apply2_u32_i128(Some(I128(a.wrapping_shr(c)))),
+      I128Op::Rol => apply2_u32_i128(Some(I128(a.rotate_left(c)))),
+      I128Op::Ror => apply2_u32_i128(Some(I128(a.rotate_right(c)))),
+      I128Op::CountZeros => apply1_i128(Some(U32(a.count_zeros()))),
+      I128Op::CountOnes => apply1_i128(Some(U32(a.count_ones()))),
+      I128Op::ToU8 => from_bool(if a < u8::MIN.into() || a > u8::MAX.into() {
+        I128Op::apply(op, &[&I128(a)]) == None
+      }
+      else {
+        U8Op::apply(U8Op::ToI128, &[&I128Op::apply(op, &[&I128(a)]).unwrap()])
+          == Some(I128(a))
+      }),
+      I128Op::ToU16 => {
+        from_bool(if a < u16::MIN.into() || a > u16::MAX.into() {
+          I128Op::apply(op, &[&I128(a)]) == None
+        }
+        else {
+          U16Op::apply(U16Op::ToI128, &[
+            &I128Op::apply(op, &[&I128(a)]).unwrap()
+          ]) == Some(I128(a))
+        })
+      }
+      I128Op::ToU32 => {
+        from_bool(if a < u32::MIN.into() || a > u32::MAX.into() {
+          I128Op::apply(op, &[&I128(a)]) == None
+        }
+        else {
+          U32Op::apply(U32Op::ToI128, &[
+            &I128Op::apply(op, &[&I128(a)]).unwrap()
+          ]) == Some(I128(a))
+        })
+      }
+      I128Op::ToU64 => {
+        from_bool(if a < u64::MIN.into() || a > u64::MAX.into() {
+          I128Op::apply(op, &[&I128(a)]) == None
+        }
+        else {
+          U64Op::apply(U64Op::ToI128, &[
+            &I128Op::apply(op, &[&I128(a)]).unwrap()
+          ]) == Some(I128(a))
+        })
+      }
+      I128Op::ToU128 => {
+        if a.is_negative() {
+          apply1_i128(None)
+        }
+        else {
+          apply1_i128(Some(U128(a as u128)))
+        }
+      }
+      I128Op::ToNat => {
+        if a.is_negative() {
+          apply1_i128(None)
+        }
+        else {
+          apply1_i128(Some(Nat(BigUint::from(u128::try_from(a).unwrap()))))
+        }
+      }
+      I128Op::ToI8 => from_bool(if a < i8::MIN.into() || a > i8::MAX.into() {
+        I128Op::apply(op, &[&I128(a)]) == None
+      }
+      else {
+        I8Op::apply(I8Op::ToI128, &[&I128Op::apply(op, &[&I128(a)]).unwrap()])
+          == Some(I128(a))
+      }),
+      I128Op::ToI16 => {
+        from_bool(if a < i16::MIN.into() || a > i16::MAX.into() {
+          I128Op::apply(op, &[&I128(a)]) == None
+        }
+        else {
+          I16Op::apply(I16Op::ToI128, &[
+            &I128Op::apply(op, &[&I128(a)]).unwrap()
+          ]) == Some(I128(a))
+        })
+      }
+      I128Op::ToI32 => {
+        from_bool(if a < i32::MIN.into() || a > i32::MAX.into() {
+          I128Op::apply(op, &[&I128(a)]) == None
+        }
+        else {
+          I32Op::apply(I32Op::ToI128, &[
+            &I128Op::apply(op, &[&I128(a)]).unwrap()
+          ]) == Some(I128(a))
+        })
+      }
+      I128Op::ToI64 => {
+        from_bool(if a < i64::MIN.into() || a > i64::MAX.into() {
+          I128Op::apply(op, &[&I128(a)]) == None
+        }
+        else {
+          I64Op::apply(I64Op::ToI128, &[
+            &I128Op::apply(op, &[&I128(a)]).unwrap()
+          ]) == Some(I128(a))
+        })
+      }
+      I128Op::ToInt => apply1_i128(Some(Int(a.into()))),
+      I128Op::ToBits => apply1_i128(Some(Bits(bits::bytes_to_bits(
+        128,
+        &a.to_be_bytes().into(),
+      )))),
+      I128Op::ToBytes => apply1_i128(Some(Bytes(a.to_be_bytes().into()))),
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(
+    op: I128Op,
+    a: Literal,
+    b: i128,
+    c: u32,
+    test_arg_2: bool,
+  ) -> TestResult {
+    let test_apply1_none_on_invalid = |valid_arg: Literal| -> TestResult {
+      if mem::discriminant(&valid_arg) == mem::discriminant(&a) {
+        TestResult::discard()
+      }
+      else {
+        TestResult::from_bool(I128Op::apply(op, &[&a]) == None)
+      }
+    };
+
+    let test_apply2_none_on_invalid =
+      |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
+        let go = || {
+          TestResult::from_bool(
+            I128Op::apply(op, &[&a_, &b_]) == None
+              && I128Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
+        if test_arg_2 {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        else {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      };
+
+    match op {
+      // Arity 0.
+      I128Op::Max | I128Op::Min => TestResult::discard(),
+      // Arity 1, valid is I128.
+      I128Op::Abs
+      | I128Op::Sgn
+      | I128Op::Not
+      | I128Op::CountZeros
+      | I128Op::CountOnes
+      | I128Op::ToU8
+      | I128Op::ToU16
+      | I128Op::ToU32
+      | I128Op::ToU64
+      | I128Op::ToU128
+      | I128Op::ToNat
+      | I128Op::ToI8
+      | I128Op::ToI16
+      | I128Op::ToI32
+      | I128Op::ToI64
+      | I128Op::ToInt
+      | I128Op::ToBytes
+      | I128Op::ToBits => test_apply1_none_on_invalid(I128(b)),
+      // Arity 2, valid are I128 on a and b.
+      I128Op::Eql
+      | I128Op::Lte
+      | I128Op::Lth
+      | I128Op::Gth
+      | I128Op::Gte
+      | I128Op::And
+      | I128Op::Or
+      | I128Op::Xor
+      | I128Op::Add
+      | I128Op::Sub
+      | I128Op::Mul
+      | I128Op::Div
+      | I128Op::Mod
+      | I128Op::CheckedAdd
+      | I128Op::CheckedSub
+      | I128Op::CheckedMul
+      | I128Op::CheckedDiv
+      | I128Op::WrappingAdd
+      | I128Op::WrappingSub
+      | I128Op::WrappingMul
+      | I128Op::SaturatingAdd
+      | I128Op::SaturatingSub
+      | I128Op::SaturatingMul => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(I128(b), a, I128(b))
+        }
+        else {
+          test_apply2_none_on_invalid(I128(b), I128(b), a)
+        }
+      }
+      // Arity 2, valid are I128 on a and U32 on b.
+      I128Op::Pow => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(I128(b), a, U32(c))
+        }
+        else {
+          test_apply2_none_on_invalid(U32(c), I128(b), a)
+        }
+      }
+      // Arity 2, valid are U32 on a and I128 on b.
+      I128Op::Shl | I128Op::Shr | I128Op::Rol | I128Op::Ror => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(U32(c), a, I128(b))
+        }
+        else {
+          test_apply2_none_on_invalid(I128(b), U32(c), a)
+        }
+      }
+    }
+  }
 }
@@ -61,6 +61,16 @@ pub enum I16Op {
   ToInt,
   ToBits,
   ToBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl I16Op {
@@ -105,6 +115,16 @@ impl I16Op {
       Self::ToInt => "to_Int".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -149,6 +169,16 @@ impl I16Op {
       "to_Int" => Some(Self::ToInt),
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -165,10 +195,10 @@ impl I16Op {
       Self::Lth => yatima!("∀ #I16 #I16 -> #Bool"),
       Self::Gth => yatima!("∀ #I16 #I16 -> #Bool"),
       Self::Gte => yatima!("∀ #I16 #I16 -> #Bool"),
-      Self::Not => yatima!("∀ #I16 #I16 -> #Bool"),
-      Self::And => yatima!("∀ #I16 #I16 -> #Bool"),
-      Self::Or => yatima!("∀ #I16 #I16 -> #Bool"),
-      Self::Xor => yatima!("∀ #I16 #I16 -> #Bool"),
+      Self::Not => yatima!("∀ #I16 -> #I16"),
+      Self::And => yatima!("∀ #I16 #I16 -> #I16"),
+      Self::Or => yatima!("∀ #I16 #I16 -> #I16"),
+      Self::Xor => yatima!("∀ #I16 #I16 -> #I16"),
       Self::Add => yatima!("∀ #I16 #I16 -> #I16"),
       Self::Sub => yatima!("∀ #I16 #I16 -> #I16"),
       Self::Mul => yatima!("∀ #I16 #I16 -> #I16"),
@@ -194,6 +224,18 @@ impl I16Op {
       Self::ToInt => yatima!("∀ #I16 -> #Int"),
       Self::ToBits => yatima!("∀ #I16 -> #Bits"),
       Self::ToBytes => yatima!("∀ #I16 -> #Bytes"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #I16 #I16 (0 P: Type) (none: P) (some: ∀ #I16 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #I16 #I16 -> #I16"),
+      Self::WrappingSub => yatima!("∀ #I16 #I16 -> #I16"),
+      Self::WrappingMul => yatima!("∀ #I16 #I16 -> #I16"),
+      Self::SaturatingAdd => yatima!("∀ #I16 #I16 -> #I16"),
+      Self::SaturatingSub => yatima!("∀ #I16 #I16 -> #I16"),
+      Self::SaturatingMul => yatima!("∀ #I16 #I16 -> #I16"),
     }
   }
 
@@ -238,6 +280,16 @@ impl I16Op {
       Self::ToInt => Ipld::Integer(35),
       Self::ToBits => Ipld::Integer(36),
       Self::ToBytes => Ipld::Integer(37),
+      Self::CheckedAdd => Ipld::Integer(38),
+      Self::CheckedSub => Ipld::Integer(39),
+      Self::CheckedMul => Ipld::Integer(40),
+      Self::CheckedDiv => Ipld::Integer(41),
+      Self::WrappingAdd => Ipld::Integer(42),
+      Self::WrappingSub => Ipld::Integer(43),
+      Self::WrappingMul => Ipld::Integer(44),
+      Self::SaturatingAdd => Ipld::Integer(45),
+      Self::SaturatingSub => Ipld::Integer(46),
+      Self::SaturatingMul => Ipld::Integer(47),
     }
   }
 
@@ -282,7 +334,17 @@ impl I16Op {
       Ipld::Integer(35) => Ok(Self::ToInt),
       Ipld::Integer(36) => Ok(Self::ToBits),
       Ipld::Integer(37) => Ok(Self::ToBytes),
-      xs => Err(IpldError::NatOp(xs.to_owned())),
+      Ipld::Integer(38) => Ok(Self::CheckedAdd),
+      Ipld::Integer(39) => Ok(Self::CheckedSub),
+      Ipld::Integer(40) => Ok(Self::CheckedMul),
+      Ipld::Integer(41) => Ok(Self::CheckedDiv),
+      Ipld::Integer(42) => Ok(Self::WrappingAdd),
+      Ipld::Integer(43) => Ok(Self::WrappingSub),
+      Ipld::Integer(44) => Ok(Self::WrappingMul),
+      Ipld::Integer(45) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(46) => Ok(Self::SaturatingSub),
+      Ipld::Integer(47) => Ok(Self::SaturatingMul),
+      xs => Err(IpldError::I16Op(xs.to_owned())),
     }
   }
 
@@ -327,33 +389,36 @@ impl I16Op {
       Self::ToInt => 1,
       Self::ToBits => 1,
       Self::ToBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match self {
-      Self::Max => Some(I16(i16::MAX)),
-      Self::Min => Some(I16(i16::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x) {
-      (Self::Abs, I16(x)) => Some(U16(x.unsigned_abs())),
-      (Self::Sgn, I16(x)) => Some(Bool(x.is_positive())),
-      (Self::CountZeros, I16(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, I16(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, I16(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU16, I16(x)) => u16::try_from(*x).ok().map(U16),
-      (Self::ToU32, I16(x)) => u32::try_from(*x).ok().map(U32),
-      (Self::ToU64, I16(x)) => u64::try_from(*x).ok().map(U64),
-      (Self::ToU128, I16(x)) => u128::try_from(*x).ok().map(U128),
-      (Self::ToNat, I16(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(I16(i16::MAX)),
+      (Self::Min, []) => Some(I16(i16::MIN)),
+      (Self::Abs, [I16(x)]) => Some(U16(x.unsigned_abs())),
+      (Self::Sgn, [I16(x)]) => Some(Bool(x.is_positive())),
+      (Self::CountZeros, [I16(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [I16(x)]) => Some(U32(x.count_ones())),
+      (Self::ToU8, [I16(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU16, [I16(x)]) => u16::try_from(*x).ok().map(U16),
+      (Self::ToU32, [I16(x)]) => u32::try_from(*x).ok().map(U32),
+      (Self::ToU64, [I16(x)]) => u64::try_from(*x).ok().map(U64),
+      (Self::ToU128, [I16(x)]) => u128::try_from(*x).ok().map(U128),
+      (Self::ToNat, [I16(x)]) => {
         if x.is_negative() {
           None
         }
@@ -361,36 +426,40 @@ impl I16Op {
           Some(Nat(BigUint::from(u64::try_from(*x).unwrap())))
         }
       }
-      (Self::ToI8, I16(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI32, I16(x)) => Some(I32((*x).into())),
-      (Self::ToI64, I16(x)) => Some(I64((*x).into())),
-      (Self::ToI128, I16(x)) => Some(I128((*x).into())),
-      (Self::Not, I16(x)) => Some(I16(!x)),
-      (Self::ToInt, I16(x)) => Some(Int((*x).into())),
-      (Self::ToBits, I16(x)) => {
+      (Self::ToI8, [I16(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI32, [I16(x)]) => Some(I32((*x).into())),
+      (Self::ToI64, [I16(x)]) => Some(I64((*x).into())),
+      (Self::ToI128, [I16(x)]) => Some(I128((*x).into())),
+      (Self::Not, [I16(x)]) => Some(I16(!x)),
+      (Self::ToInt, [I16(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [I16(x)]) => {
         Some(Bits(bits::bytes_to_bits(16, &x.to_be_bytes().into())))
       }
-      (Self::ToBytes, I16(x)) => Some(Bytes(x.to_be_bytes().into())),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, I16(x), I16(y)) => Some(Bool(x == y)),
-      (Self::Lte, I16(x), I16(y)) => Some(Bool(x <= y)),
-      (Self::Lth, I16(x), I16(y)) => Some(Bool(x < y)),
-      (Self::Gth, I16(x), I16(y)) => Some(Bool(x > y)),
-      (Self::Gte, I16(x), I16(y)) => Some(Bool(x >= y)),
-      (Self::And, I16(x), I16(y)) => Some(I16(x & y)),
-      (Self::Or, I16(x), I16(y)) => Some(I16(x | y)),
-      (Self::Xor, I16(x), I16(y)) => Some(I16(x ^ y)),
-      (Self::Add, I16(x), I16(y)) => Some(I16(x.wrapping_add(*y))),
-      (Self::Sub, I16(x), I16(y)) => Some(I16(x.wrapping_sub(*y))),
-      (Self::Mul, I16(x), I16(y)) => Some(I16(x.wrapping_mul(*y))),
-      (Self::Div, I16(x), I16(y)) => {
+      (Self::ToBytes, [I16(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [I16(x), I16(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [I16(x), I16(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [I16(x), I16(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [I16(x), I16(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [I16(x), I16(y)]) => Some(Bool(x >= y)),
+      (Self::And, [I16(x), I16(y)]) => Some(I16(x & y)),
+      (Self::Or, [I16(x), I16(y)]) => Some(I16(x | y)),
+      (Self::Xor, [I16(x), I16(y)]) => Some(I16(x ^ y)),
+      (Self::Add, [I16(x), I16(y)]) => Some(I16(x.wrapping_add(*y))),
+      (Self::Sub, [I16(x), I16(y)]) => Some(I16(x.wrapping_sub(*y))),
+      (Self::Mul, [I16(x), I16(y)]) => Some(I16(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [I16(x), I16(y)]) => Some(I16(x.wrapping_add(*y))),
+      (Self::WrappingSub, [I16(x), I16(y)]) => Some(I16(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [I16(x), I16(y)]) => Some(I16(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [I16(x), I16(y)]) => {
+        Some(I16(x.saturating_add(*y)))
+      }
+      (Self::SaturatingSub, [I16(x), I16(y)]) => {
+        Some(I16(x.saturating_sub(*y)))
+      }
+      (Self::SaturatingMul, [I16(x), I16(y)]) => {
+        Some(I16(x.saturating_mul(*y)))
+      }
+      (Self::Div, [I16(x), I16(y)]) => {
         if *y == 0 {
           None
         }
@@ -398,7 +467,7 @@ impl I16Op {
           Some(I16(x.wrapping_div(*y)))
         }
       }
-      (Self::Mod, I16(x), I16(y)) => {
+      (Self::Mod, [I16(x), I16(y)]) => {
         if *y == 0 {
           None
         }
@@ -406,11 +475,24 @@ impl I16Op {
           Some(I16(x.wrapping_rem(*y)))
         }
       }
-      (Self::Pow, I16(x), U32(y)) => Some(I16(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), I16(y)) => Some(I16(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), I16(y)) => Some(I16(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), I16(y)) => Some(I16(y.rotate_left(*x))),
-      (Self::Ror, U32(x), I16(y)) => Some(I16(y.rotate_right(*x))),
+      (Self::Pow, [I16(x), U32(y)]) => Some(I16(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), I16(y)]) => Some(I16(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), I16(y)]) => Some(I16(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), I16(y)]) => Some(I16(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), I16(y)]) => Some(I16(y.rotate_right(*x))),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    match (self, args) {
+      (Self::CheckedAdd, [I16(x), I16(y)]) => Some(x.checked_add(*y).map(I16)),
+      (Self::CheckedSub, [I16(x), I16(y)]) => Some(x.checked_sub(*y).map(I16)),
+      (Self::CheckedMul, [I16(x), I16(y)]) => Some(x.checked_mul(*y).map(I16)),
+      (Self::CheckedDiv, [I16(x), I16(y)]) => Some(x.checked_div(*y).map(I16)),
       _ => None,
     }
   }
@@ -458,7 +540,7 @@ pub mod tests {
   impl Arbitrary for I16Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=35);
+      let gen: u32 = rng.gen_range(0..=45);
       match gen {
         0 => Self::Abs,
         1 => Self::Sgn,
@@ -495,7 +577,17 @@ pub mod tests {
         32 => Self::ToI64,
         33 => Self::ToInt,
         34 => Self::ToBits,
-        _ => Self::ToBytes,
+        35 => Self::ToBytes,
+        36 => Self::CheckedAdd,
+        37 => Self::CheckedSub,
+        38 => Self::CheckedMul,
+        39 => Self::CheckedDiv,
+        40 => Self::WrappingAdd,
+        41 => Self::WrappingSub,
+        42 => Self::WrappingMul,
+        43 => Self::SaturatingAdd,
+        44 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
         /* 29 => Self::ToU128,
          * 34 => Self::ToI128, */
       }
@@ -513,23 +605,29 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: I16Op, a: i16, b: i16, c: u32) -> TestResult {
     let apply0_go = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I16Op::apply0(op) == expected)
+      TestResult::from_bool(I16Op::apply(op, &[]) == expected)
     };
 
     let apply1_i16 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I16Op::apply1(op, &I16(a)) == expected)
+      TestResult::from_bool(I16Op::apply(op, &[&I16(a)]) == expected)
     };
 
     let apply2_i16_i16 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I16Op::apply2(op, &I16(a), &I16(b)) == expected)
+      TestResult::from_bool(I16Op::apply(op, &[&I16(a), &I16(b)]) == expected)
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        I16Op::apply_checked(op, &[&I16(a), &I16(b)]) == Some(expected),
+      )
     };
 
     let apply2_i16_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I16Op::apply2(op, &I16(a), &U32(c)) == expected)
+      TestResult::from_bool(I16Op::apply(op, &[&I16(a), &U32(c)]) == expected)
     };
 
     let apply2_u32_i16 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I16Op::apply2(op, &U32(c), &I16(a)) == expected)
+      TestResult::from_bool(I16Op::apply(op, &[&U32(c), &I16(a)]) == expected)
     };
 
     let from_bool = TestResult::from_bool;
@@ -557,6 +655,16 @@ pub mod tests {
       I16Op::Mod => {
         apply2_i16_i16(if b == 0 { None } else { Some(I16(a.wrapping_rem(b))) })
       }
+      I16Op::WrappingAdd => apply2_i16_i16(Some(I16(a.wrapping_add(b)))),
+      I16Op::WrappingSub => apply2_i16_i16(Some(I16(a.wrapping_sub(b)))),
+      I16Op::WrappingMul => apply2_i16_i16(Some(I16(a.wrapping_mul(b)))),
+      I16Op::SaturatingAdd => apply2_i16_i16(Some(I16(a.saturating_add(b)))),
+      I16Op::SaturatingSub => apply2_i16_i16(Some(I16(a.saturating_sub(b)))),
+      I16Op::SaturatingMul => apply2_i16_i16(Some(I16(a.saturating_mul(b)))),
+      I16Op::CheckedAdd => apply2_checked(a.checked_add(b).map(I16)),
+      I16Op::CheckedSub => apply2_checked(a.checked_sub(b).map(I16)),
+      I16Op::CheckedMul => apply2_checked(a.checked_mul(b).map(I16)),
+      I16Op::CheckedDiv => apply2_checked(a.checked_div(b).map(I16)),
       I16Op::Pow => apply2_i16_u32(Some(I16(a.wrapping_pow(c)))),
       I16Op::Shl => apply2_u32_i16(Some(I16(a.wrapping_shl(c)))),
       I16Op::Shr => apply2_u32_i16(Some(I16(a.wrapping_shr(c)))),
@@ -565,31 +673,31 @@ pub mod tests {
       I16Op::CountZeros => apply1_i16(Some(U32(a.count_zeros()))),
       I16Op::CountOnes => apply1_i16(Some(U32(a.count_ones()))),
       I16Op::ToU8 => from_bool(if a < u8::MIN.into() || a > u8::MAX.into() {
-        I16Op::apply1(op, &I16(a)) == None
+        I16Op::apply(op, &[&I16(a)]) == None
       }
       else {
-        U8Op::apply1(U8Op::ToI16, &I16Op::apply1(op, &I16(a)).unwrap())
+        U8Op::apply(U8Op::ToI16, &[&I16Op::apply(op, &[&I16(a)]).unwrap()])
           == Some(I16(a))
       }),
       I16Op::ToU16 => from_bool(if a < u16::MIN.try_into().unwrap() {
-        I16Op::apply1(op, &I16(a)) == None
+        I16Op::apply(op, &[&I16(a)]) == None
       }
       else {
-        U16Op::apply1(U16Op::ToI16, &I16Op::apply1(op, &I16(a)).unwrap())
+        U16Op::apply(U16Op::ToI16, &[&I16Op::apply(op, &[&I16(a)]).unwrap()])
           == Some(I16(a))
       }),
       I16Op::ToU32 => from_bool(if a < u32::MIN.try_into().unwrap() {
-        I16Op::apply1(op, &I16(a)) == None
+        I16Op::apply(op, &[&I16(a)]) == None
       }
       else {
-        U32Op::apply1(U32Op::ToI16, &I16Op::apply1(op, &I16(a)).unwrap())
+        U32Op::apply(U32Op::ToI16, &[&I16Op::apply(op, &[&I16(a)]).unwrap()])
           == Some(I16(a))
       }),
       I16Op::ToU64 => from_bool(if a < u64::MIN.try_into().unwrap() {
-        I16Op::apply1(op, &I16(a)) == None
+        I16Op::apply(op, &[&I16(a)]) == None
       }
       else {
-        U64Op::apply1(U64Op::ToI16, &I16Op::apply1(op, &I16(a)).unwrap())
+        U64Op::apply(U64Op::ToI16, &[&I16Op::apply(op, &[&I16(a)]).unwrap()])
           == Some(I16(a))
       }),
       I16Op::ToU128 => TestResult::discard(),
@@ -602,18 +710,18 @@ pub mod tests {
         }
       }
       I16Op::ToI8 => from_bool(if a < i8::MIN.into() || a > i8::MAX.into() {
-        I16Op::apply1(op, &I16(a)) == None
+        I16Op::apply(op, &[&I16(a)]) == None
       }
       else {
-        I8Op::apply1(I8Op::ToI16, &I16Op::apply1(op, &I16(a)).unwrap())
+        I8Op::apply(I8Op::ToI16, &[&I16Op::apply(op, &[&I16(a)]).unwrap()])
           == Some(I16(a))
       }),
       I16Op::ToI32 => from_bool(
-        I32Op::apply1(I32Op::ToI16, &I16Op::apply1(op, &I16(a)).unwrap())
+        I32Op::apply(I32Op::ToI16, &[&I16Op::apply(op, &[&I16(a)]).unwrap()])
           == Some(I16(a)),
       ),
       I16Op::ToI64 => from_bool(
-        I64Op::apply1(I64Op::ToI16, &I16Op::apply1(op, &I16(a)).unwrap())
+        I64Op::apply(I64Op::ToI16, &[&I16Op::apply(op, &[&I16(a)]).unwrap()])
           == Some(I16(a)),
       ),
       I16Op::ToI128 => TestResult::discard(),
@@ -638,13 +746,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(I16Op::apply1(op, &a) == None)
+        TestResult::from_bool(I16Op::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(I16Op::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            I16Op::apply(op, &[&a_, &b_]) == None
+              && I16Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -698,7 +811,17 @@ pub mod tests {
       | I16Op::Sub
       | I16Op::Mul
       | I16Op::Div
-      | I16Op::Mod => {
+      | I16Op::Mod
+      | I16Op::CheckedAdd
+      | I16Op::CheckedSub
+      | I16Op::CheckedMul
+      | I16Op::CheckedDiv
+      | I16Op::WrappingAdd
+      | I16Op::WrappingSub
+      | I16Op::WrappingMul
+      | I16Op::SaturatingAdd
+      | I16Op::SaturatingSub
+      | I16Op::SaturatingMul => {
         if test_arg_2 {
           test_apply2_none_on_invalid(I16(b), a, I16(b))
         }
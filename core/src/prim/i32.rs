@@ -61,6 +61,16 @@ pub enum I32Op {
   ToInt,
   ToBits,
   ToBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl I32Op {
@@ -104,6 +114,16 @@ impl I32Op {
       Self::ToI128 => "to_I128".to_owned(),
       Self::ToInt => "to_Int".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
     }
   }
@@ -149,6 +169,16 @@ impl I32Op {
       "to_Int" => Some(Self::ToInt),
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -165,10 +195,10 @@ impl I32Op {
       Self::Lth => yatima!("∀ #I32 #I32 -> #Bool"),
       Self::Gth => yatima!("∀ #I32 #I32 -> #Bool"),
       Self::Gte => yatima!("∀ #I32 #I32 -> #Bool"),
-      Self::Not => yatima!("∀ #I32 #I32 -> #Bool"),
-      Self::And => yatima!("∀ #I32 #I32 -> #Bool"),
-      Self::Or => yatima!("∀ #I32 #I32 -> #Bool"),
-      Self::Xor => yatima!("∀ #I32 #I32 -> #Bool"),
+      Self::Not => yatima!("∀ #I32 -> #I32"),
+      Self::And => yatima!("∀ #I32 #I32 -> #I32"),
+      Self::Or => yatima!("∀ #I32 #I32 -> #I32"),
+      Self::Xor => yatima!("∀ #I32 #I32 -> #I32"),
       Self::Add => yatima!("∀ #I32 #I32 -> #I32"),
       Self::Sub => yatima!("∀ #I32 #I32 -> #I32"),
       Self::Mul => yatima!("∀ #I32 #I32 -> #I32"),
@@ -193,6 +223,18 @@ impl I32Op {
       Self::ToI128 => yatima!("∀ #I32 -> #I128"),
       Self::ToInt => yatima!("∀ #I32 -> #Int"),
       Self::ToBytes => yatima!("∀ #I32 -> #Bytes"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #I32 #I32 (0 P: Type) (none: P) (some: ∀ #I32 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #I32 #I32 -> #I32"),
+      Self::WrappingSub => yatima!("∀ #I32 #I32 -> #I32"),
+      Self::WrappingMul => yatima!("∀ #I32 #I32 -> #I32"),
+      Self::SaturatingAdd => yatima!("∀ #I32 #I32 -> #I32"),
+      Self::SaturatingSub => yatima!("∀ #I32 #I32 -> #I32"),
+      Self::SaturatingMul => yatima!("∀ #I32 #I32 -> #I32"),
       Self::ToBits => yatima!("∀ #I32 -> #Bits"),
     }
   }
@@ -238,6 +280,16 @@ impl I32Op {
       Self::ToInt => Ipld::Integer(35),
       Self::ToBits => Ipld::Integer(36),
       Self::ToBytes => Ipld::Integer(37),
+      Self::CheckedAdd => Ipld::Integer(38),
+      Self::CheckedSub => Ipld::Integer(39),
+      Self::CheckedMul => Ipld::Integer(40),
+      Self::CheckedDiv => Ipld::Integer(41),
+      Self::WrappingAdd => Ipld::Integer(42),
+      Self::WrappingSub => Ipld::Integer(43),
+      Self::WrappingMul => Ipld::Integer(44),
+      Self::SaturatingAdd => Ipld::Integer(45),
+      Self::SaturatingSub => Ipld::Integer(46),
+      Self::SaturatingMul => Ipld::Integer(47),
     }
   }
 
@@ -282,7 +334,17 @@ impl I32Op {
       Ipld::Integer(35) => Ok(Self::ToInt),
       Ipld::Integer(36) => Ok(Self::ToBits),
       Ipld::Integer(37) => Ok(Self::ToBytes),
-      xs => Err(IpldError::NatOp(xs.to_owned())),
+      Ipld::Integer(38) => Ok(Self::CheckedAdd),
+      Ipld::Integer(39) => Ok(Self::CheckedSub),
+      Ipld::Integer(40) => Ok(Self::CheckedMul),
+      Ipld::Integer(41) => Ok(Self::CheckedDiv),
+      Ipld::Integer(42) => Ok(Self::WrappingAdd),
+      Ipld::Integer(43) => Ok(Self::WrappingSub),
+      Ipld::Integer(44) => Ok(Self::WrappingMul),
+      Ipld::Integer(45) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(46) => Ok(Self::SaturatingSub),
+      Ipld::Integer(47) => Ok(Self::SaturatingMul),
+      xs => Err(IpldError::I32Op(xs.to_owned())),
     }
   }
 
@@ -327,33 +389,36 @@ impl I32Op {
       Self::ToInt => 1,
       Self::ToBits => 1,
       Self::ToBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match self {
-      Self::Max => Some(I32(i32::MAX)),
-      Self::Min => Some(I32(i32::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x) {
-      (Self::Abs, I32(x)) => Some(U32(x.unsigned_abs())),
-      (Self::Sgn, I32(x)) => Some(Bool(x.is_positive())),
-      (Self::CountZeros, I32(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, I32(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, I32(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU16, I32(x)) => u16::try_from(*x).ok().map(U16),
-      (Self::ToU32, I32(x)) => u32::try_from(*x).ok().map(U32),
-      (Self::ToU64, I32(x)) => u64::try_from(*x).ok().map(U64),
-      (Self::ToU128, I32(x)) => u128::try_from(*x).ok().map(U128),
-      (Self::ToNat, I32(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(I32(i32::MAX)),
+      (Self::Min, []) => Some(I32(i32::MIN)),
+      (Self::Abs, [I32(x)]) => Some(U32(x.unsigned_abs())),
+      (Self::Sgn, [I32(x)]) => Some(Bool(x.is_positive())),
+      (Self::CountZeros, [I32(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [I32(x)]) => Some(U32(x.count_ones())),
+      (Self::ToU8, [I32(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU16, [I32(x)]) => u16::try_from(*x).ok().map(U16),
+      (Self::ToU32, [I32(x)]) => u32::try_from(*x).ok().map(U32),
+      (Self::ToU64, [I32(x)]) => u64::try_from(*x).ok().map(U64),
+      (Self::ToU128, [I32(x)]) => u128::try_from(*x).ok().map(U128),
+      (Self::ToNat, [I32(x)]) => {
         if x.is_negative() {
           None
         }
@@ -361,36 +426,40 @@ impl I32Op {
           Some(Nat(BigUint::from(u64::try_from(*x).unwrap())))
         }
       }
-      (Self::ToI8, I32(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI16, I32(x)) => i16::try_from(*x).ok().map(I16),
-      (Self::ToI64, I32(x)) => Some(I64((*x).into())),
-      (Self::ToI128, I32(x)) => Some(I128((*x).into())),
-      (Self::Not, I32(x)) => Some(I32(!x)),
-      (Self::ToInt, I32(x)) => Some(Int((*x).into())),
-      (Self::ToBits, I32(x)) => {
+      (Self::ToI8, [I32(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI16, [I32(x)]) => i16::try_from(*x).ok().map(I16),
+      (Self::ToI64, [I32(x)]) => Some(I64((*x).into())),
+      (Self::ToI128, [I32(x)]) => Some(I128((*x).into())),
+      (Self::Not, [I32(x)]) => Some(I32(!x)),
+      (Self::ToInt, [I32(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [I32(x)]) => {
         Some(Bits(bits::bytes_to_bits(32, &x.to_be_bytes().into())))
       }
-      (Self::ToBytes, I32(x)) => Some(Bytes(x.to_be_bytes().into())),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, I32(x), I32(y)) => Some(Bool(x == y)),
-      (Self::Lte, I32(x), I32(y)) => Some(Bool(x <= y)),
-      (Self::Lth, I32(x), I32(y)) => Some(Bool(x < y)),
-      (Self::Gth, I32(x), I32(y)) => Some(Bool(x > y)),
-      (Self::Gte, I32(x), I32(y)) => Some(Bool(x >= y)),
-      (Self::And, I32(x), I32(y)) => Some(I32(x & y)),
-      (Self::Or, I32(x), I32(y)) => Some(I32(x | y)),
-      (Self::Xor, I32(x), I32(y)) => Some(I32(x ^ y)),
-      (Self::Add, I32(x), I32(y)) => Some(I32(x.wrapping_add(*y))),
-      (Self::Sub, I32(x), I32(y)) => Some(I32(x.wrapping_sub(*y))),
-      (Self::Mul, I32(x), I32(y)) => Some(I32(x.wrapping_mul(*y))),
-      (Self::Div, I32(x), I32(y)) => {
+      (Self::ToBytes, [I32(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [I32(x), I32(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [I32(x), I32(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [I32(x), I32(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [I32(x), I32(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [I32(x), I32(y)]) => Some(Bool(x >= y)),
+      (Self::And, [I32(x), I32(y)]) => Some(I32(x & y)),
+      (Self::Or, [I32(x), I32(y)]) => Some(I32(x | y)),
+      (Self::Xor, [I32(x), I32(y)]) => Some(I32(x ^ y)),
+      (Self::Add, [I32(x), I32(y)]) => Some(I32(x.wrapping_add(*y))),
+      (Self::Sub, [I32(x), I32(y)]) => Some(I32(x.wrapping_sub(*y))),
+      (Self::Mul, [I32(x), I32(y)]) => Some(I32(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [I32(x), I32(y)]) => Some(I32(x.wrapping_add(*y))),
+      (Self::WrappingSub, [I32(x), I32(y)]) => Some(I32(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [I32(x), I32(y)]) => Some(I32(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [I32(x), I32(y)]) => {
+        Some(I32(x.saturating_add(*y)))
+      }
+      (Self::SaturatingSub, [I32(x), I32(y)]) => {
+        Some(I32(x.saturating_sub(*y)))
+      }
+      (Self::SaturatingMul, [I32(x), I32(y)]) => {
+        Some(I32(x.saturating_mul(*y)))
+      }
+      (Self::Div, [I32(x), I32(y)]) => {
         if *y == 0 {
           None
         }
@@ -398,7 +467,7 @@ impl I32Op {
           Some(I32(x.wrapping_div(*y)))
         }
       }
-      (Self::Mod, I32(x), I32(y)) => {
+      (Self::Mod, [I32(x), I32(y)]) => {
         if *y == 0 {
           None
         }
@@ -406,11 +475,24 @@ impl I32Op {
           Some(I32(x.wrapping_rem(*y)))
         }
       }
-      (Self::Pow, I32(x), U32(y)) => Some(I32(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), I32(y)) => Some(I32(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), I32(y)) => Some(I32(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), I32(y)) => Some(I32(y.rotate_left(*x))),
-      (Self::Ror, U32(x), I32(y)) => Some(I32(y.rotate_right(*x))),
+      (Self::Pow, [I32(x), U32(y)]) => Some(I32(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), I32(y)]) => Some(I32(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), I32(y)]) => Some(I32(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), I32(y)]) => Some(I32(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), I32(y)]) => Some(I32(y.rotate_right(*x))),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    match (self, args) {
+      (Self::CheckedAdd, [I32(x), I32(y)]) => Some(x.checked_add(*y).map(I32)),
+      (Self::CheckedSub, [I32(x), I32(y)]) => Some(x.checked_sub(*y).map(I32)),
+      (Self::CheckedMul, [I32(x), I32(y)]) => Some(x.checked_mul(*y).map(I32)),
+      (Self::CheckedDiv, [I32(x), I32(y)]) => Some(x.checked_div(*y).map(I32)),
       _ => None,
     }
   }
@@ -456,7 +538,7 @@ pub mod tests {
   impl Arbitrary for I32Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=35);
+      let gen: u32 = rng.gen_range(0..=45);
       match gen {
         0 => Self::Abs,
         1 => Self::Sgn,
@@ -493,7 +575,17 @@ pub mod tests {
         32 => Self::ToI64,
         33 => Self::ToInt,
         34 => Self::ToBytes,
-        _ => Self::ToBits,
+        35 => Self::ToBits,
+        36 => Self::CheckedAdd,
+        37 => Self::CheckedSub,
+        38 => Self::CheckedMul,
+        39 => Self::CheckedDiv,
+        40 => Self::WrappingAdd,
+        41 => Self::WrappingSub,
+        42 => Self::WrappingMul,
+        43 => Self::SaturatingAdd,
+        44 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
         /* 29 => Self::ToU128,
          * 34 => Self::ToI128, */
       }
@@ -511,23 +603,29 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: I32Op, a: i32, b: i32, c: u32) -> TestResult {
     let apply0_go = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I32Op::apply0(op) == expected)
+      TestResult::from_bool(I32Op::apply(op, &[]) == expected)
     };
 
     let apply1_i32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I32Op::apply1(op, &I32(a)) == expected)
+      TestResult::from_bool(I32Op::apply(op, &[&I32(a)]) == expected)
     };
 
     let apply2_i32_i32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I32Op::apply2(op, &I32(a), &I32(b)) == expected)
+      TestResult::from_bool(I32Op::apply(op, &[&I32(a), &I32(b)]) == expected)
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        I32Op::apply_checked(op, &[&I32(a), &I32(b)]) == Some(expected),
+      )
     };
 
     let apply2_i32_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I32Op::apply2(op, &I32(a), &U32(c)) == expected)
+      TestResult::from_bool(I32Op::apply(op, &[&I32(a), &U32(c)]) == expected)
     };
 
     let apply2_u32_i32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I32Op::apply2(op, &U32(c), &I32(a)) == expected)
+      TestResult::from_bool(I32Op::apply(op, &[&U32(c), &I32(a)]) == expected)
     };
 
     let from_bool = TestResult::from_bool;
@@ -555,6 +653,16 @@ pub mod tests {
       I32Op::Mod => {
         apply2_i32_i32(if b == 0 { None } else { Some(I32(a.wrapping_rem(b))) })
       }
+      I32Op::WrappingAdd => apply2_i32_i32(Some(I32(a.wrapping_add(b)))),
+      I32Op::WrappingSub => apply2_i32_i32(Some(I32(a.wrapping_sub(b)))),
+      I32Op::WrappingMul => apply2_i32_i32(Some(I32(a.wrapping_mul(b)))),
+      I32Op::SaturatingAdd => apply2_i32_i32(Some(I32(a.saturating_add(b)))),
+      I32Op::SaturatingSub => apply2_i32_i32(Some(I32(a.saturating_sub(b)))),
+      I32Op::SaturatingMul => apply2_i32_i32(Some(I32(a.saturating_mul(b)))),
+      I32Op::CheckedAdd => apply2_checked(a.checked_add(b).map(I32)),
+      I32Op::CheckedSub => apply2_checked(a.checked_sub(b).map(I32)),
+      I32Op::CheckedMul => apply2_checked(a.checked_mul(b).map(I32)),
+      I32Op::CheckedDiv => apply2_checked(a.checked_div(b).map(I32)),
       I32Op::Pow => apply2_i32_u32(Some(I32(a.wrapping_pow(c)))),
       I32Op::Shl => apply2_u32_i32(Some(I32(a.wrapping_shl(c)))),
       I32Op::Shr => apply2_u32_i32(Some(I32(a.wrapping_shr(c)))),
@@ -563,33 +671,33 @@ pub mod tests {
       I32Op::CountZeros => apply1_i32(Some(U32(a.count_zeros()))),
       I32Op::CountOnes => apply1_i32(Some(U32(a.count_ones()))),
       I32Op::ToU8 => from_bool(if a < u8::MIN.into() || a > u8::MAX.into() {
-        I32Op::apply1(op, &I32(a)) == None
+        I32Op::apply(op, &[&I32(a)]) == None
       }
       else {
-        U8Op::apply1(U8Op::ToI32, &I32Op::apply1(op, &I32(a)).unwrap())
+        U8Op::apply(U8Op::ToI32, &[&I32Op::apply(op, &[&I32(a)]).unwrap()])
           == Some(I32(a))
       }),
       I32Op::ToU16 => {
         from_bool(if a < u16::MIN.into() || a > u16::MAX.into() {
-          I32Op::apply1(op, &I32(a)) == None
+          I32Op::apply(op, &[&I32(a)]) == None
         }
         else {
-          U16Op::apply1(U16Op::ToI32, &I32Op::apply1(op, &I32(a)).unwrap())
+          U16Op::apply(U16Op::ToI32, &[&I32Op::apply(op, &[&I32(a)]).unwrap()])
             == Some(I32(a))
         })
       }
       I32Op::ToU32 => from_bool(if a < u32::MIN.try_into().unwrap() {
-        I32Op::apply1(op, &I32(a)) == None
+        I32Op::apply(op, &[&I32(a)]) == None
       }
       else {
-        U32Op::apply1(U32Op::ToI32, &I32Op::apply1(op, &I32(a)).unwrap())
+        U32Op::apply(U32Op::ToI32, &[&I32Op::apply(op, &[&I32(a)]).unwrap()])
           == Some(I32(a))
       }),
       I32Op::ToU64 => from_bool(if a < u64::MIN.try_into().unwrap() {
-        I32Op::apply1(op, &I32(a)) == None
+        I32Op::apply(op, &[&I32(a)]) == None
       }
       else {
-        U64Op::apply1(U64Op::ToI32, &I32Op::apply1(op, &I32(a)).unwrap())
+        U64Op::apply(U64Op::ToI32, &[&I32Op::apply(op, &[&I32(a)]).unwrap()])
           == Some(I32(a))
       }),
       I32Op::ToU128 => TestResult::discard(),
@@ -602,23 +710,23 @@ pub mod tests {
         }
       }
       I32Op::ToI8 => from_bool(if a < i8::MIN.into() || a > i8::MAX.into() {
-        I32Op::apply1(op, &I32(a)) == None
+        I32Op::apply(op, &[&I32(a)]) == None
       }
       else {
-        I8Op::apply1(I8Op::ToI32, &I32Op::apply1(op, &I32(a)).unwrap())
+        I8Op::apply(I8Op::ToI32, &[&I32Op::apply(op, &[&I32(a)]).unwrap()])
           == Some(I32(a))
       }),
       I32Op::ToI16 => {
         from_bool(if a < i16::MIN.into() || a > i16::MAX.into() {
-          I32Op::apply1(op, &I32(a)) == None
+          I32Op::apply(op, &[&I32(a)]) == None
         }
         else {
-          I16Op::apply1(I16Op::ToI32, &I32Op::apply1(op, &I32(a)).unwrap())
+          I16Op::apply(I16Op::ToI32, &[&I32Op::apply(op, &[&I32(a)]).unwrap()])
             == Some(I32(a))
         })
       }
       I32Op::ToI64 => from_bool(
-        I64Op::apply1(I64Op::ToI32, &I32Op::apply1(op, &I32(a)).unwrap())
+        I64Op::apply(I64Op::ToI32, &[&I32Op::apply(op, &[&I32(a)]).unwrap()])
           == Some(I32(a)),
       ),
       I32Op::ToI128 => TestResult::discard(),
@@ -643,13 +751,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(I32Op::apply1(op, &a) == None)
+        TestResult::from_bool(I32Op::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(I32Op::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            I32Op::apply(op, &[&a_, &b_]) == None
+              && I32Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -703,7 +816,17 @@ pub mod tests {
       | I32Op::Sub
       | I32Op::Mul
       | I32Op::Div
-      | I32Op::Mod => {
+      | I32Op::Mod
+      | I32Op::CheckedAdd
+      | I32Op::CheckedSub
+      | I32Op::CheckedMul
+      | I32Op::CheckedDiv
+      | I32Op::WrappingAdd
+      | I32Op::WrappingSub
+      | I32Op::WrappingMul
+      | I32Op::SaturatingAdd
+      | I32Op::SaturatingSub
+      | I32Op::SaturatingMul => {
         if test_arg_2 {
           test_apply2_none_on_invalid(I32(b), a, I32(b))
         }
@@ -61,6 +61,16 @@ pub enum I64Op {
   ToInt,
   ToBits,
   ToBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl I64Op {
@@ -105,6 +115,16 @@ impl I64Op {
       Self::ToInt => "to_Int".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -149,6 +169,16 @@ impl I64Op {
       "to_Int" => Some(Self::ToInt),
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -165,10 +195,10 @@ impl I64Op {
       Self::Lth => yatima!("∀ #I64 #I64 -> #Bool"),
       Self::Gth => yatima!("∀ #I64 #I64 -> #Bool"),
       Self::Gte => yatima!("∀ #I64 #I64 -> #Bool"),
-      Self::Not => yatima!("∀ #I64 #I64 -> #Bool"),
-      Self::And => yatima!("∀ #I64 #I64 -> #Bool"),
-      Self::Or => yatima!("∀ #I64 #I64 -> #Bool"),
-      Self::Xor => yatima!("∀ #I64 #I64 -> #Bool"),
+      Self::Not => yatima!("∀ #I64 -> #I64"),
+      Self::And => yatima!("∀ #I64 #I64 -> #I64"),
+      Self::Or => yatima!("∀ #I64 #I64 -> #I64"),
+      Self::Xor => yatima!("∀ #I64 #I64 -> #I64"),
       Self::Add => yatima!("∀ #I64 #I64 -> #I64"),
       Self::Sub => yatima!("∀ #I64 #I64 -> #I64"),
       Self::Mul => yatima!("∀ #I64 #I64 -> #I64"),
@@ -193,6 +223,18 @@ impl I64Op {
       Self::ToI128 => yatima!("∀ #I64 -> #I128"),
       Self::ToInt => yatima!("∀ #I64 -> #Int"),
       Self::ToBytes => yatima!("∀ #I64 -> #Bytes"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #I64 #I64 (0 P: Type) (none: P) (some: ∀ #I64 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #I64 #I64 -> #I64"),
+      Self::WrappingSub => yatima!("∀ #I64 #I64 -> #I64"),
+      Self::WrappingMul => yatima!("∀ #I64 #I64 -> #I64"),
+      Self::SaturatingAdd => yatima!("∀ #I64 #I64 -> #I64"),
+      Self::SaturatingSub => yatima!("∀ #I64 #I64 -> #I64"),
+      Self::SaturatingMul => yatima!("∀ #I64 #I64 -> #I64"),
       Self::ToBits => yatima!("∀ #I64 -> #Bits"),
     }
   }
@@ -238,6 +280,16 @@ impl I64Op {
       Self::ToInt => Ipld::Integer(35),
       Self::ToBits => Ipld::Integer(36),
       Self::ToBytes => Ipld::Integer(37),
+      Self::CheckedAdd => Ipld::Integer(38),
+      Self::CheckedSub => Ipld::Integer(39),
+      Self::CheckedMul => Ipld::Integer(40),
+      Self::CheckedDiv => Ipld::Integer(41),
+      Self::WrappingAdd => Ipld::Integer(42),
+      Self::WrappingSub => Ipld::Integer(43),
+      Self::WrappingMul => Ipld::Integer(44),
+      Self::SaturatingAdd => Ipld::Integer(45),
+      Self::SaturatingSub => Ipld::Integer(46),
+      Self::SaturatingMul => Ipld::Integer(47),
     }
   }
 
@@ -282,7 +334,17 @@ impl I64Op {
       Ipld::Integer(35) => Ok(Self::ToInt),
       Ipld::Integer(36) => Ok(Self::ToBits),
       Ipld::Integer(37) => Ok(Self::ToBytes),
-      xs => Err(IpldError::NatOp(xs.to_owned())),
+      Ipld::Integer(38) => Ok(Self::CheckedAdd),
+      Ipld::Integer(39) => Ok(Self::CheckedSub),
+      Ipld::Integer(40) => Ok(Self::CheckedMul),
+      Ipld::Integer(41) => Ok(Self::CheckedDiv),
+      Ipld::Integer(42) => Ok(Self::WrappingAdd),
+      Ipld::Integer(43) => Ok(Self::WrappingSub),
+      Ipld::Integer(44) => Ok(Self::WrappingMul),
+      Ipld::Integer(45) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(46) => Ok(Self::SaturatingSub),
+      Ipld::Integer(47) => Ok(Self::SaturatingMul),
+      xs => Err(IpldError::I64Op(xs.to_owned())),
     }
   }
 
@@ -327,33 +389,36 @@ impl I64Op {
       Self::ToInt => 1,
       Self::ToBits => 1,
       Self::ToBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match self {
-      Self::Max => Some(I64(i64::MAX)),
-      Self::Min => Some(I64(i64::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x) {
-      (Self::Abs, I64(x)) => Some(U64(x.unsigned_abs())),
-      (Self::Sgn, I64(x)) => Some(Bool(x.is_positive())),
-      (Self::CountZeros, I64(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, I64(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, I64(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU16, I64(x)) => u16::try_from(*x).ok().map(U16),
-      (Self::ToU32, I64(x)) => u32::try_from(*x).ok().map(U32),
-      (Self::ToU64, I64(x)) => u64::try_from(*x).ok().map(U64),
-      (Self::ToU128, I64(x)) => u128::try_from(*x).ok().map(U128),
-      (Self::ToNat, I64(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(I64(i64::MAX)),
+      (Self::Min, []) => Some(I64(i64::MIN)),
+      (Self::Abs, [I64(x)]) => Some(U64(x.unsigned_abs())),
+      (Self::Sgn, [I64(x)]) => Some(Bool(x.is_positive())),
+      (Self::CountZeros, [I64(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [I64(x)]) => Some(U32(x.count_ones())),
+      (Self::ToU8, [I64(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU16, [I64(x)]) => u16::try_from(*x).ok().map(U16),
+      (Self::ToU32, [I64(x)]) => u32::try_from(*x).ok().map(U32),
+      (Self::ToU64, [I64(x)]) => u64::try_from(*x).ok().map(U64),
+      (Self::ToU128, [I64(x)]) => u128::try_from(*x).ok().map(U128),
+      (Self::ToNat, [I64(x)]) => {
         if x.is_negative() {
           None
         }
@@ -361,36 +426,40 @@ impl I64Op {
           Some(Nat(BigUint::from(u64::try_from(*x).unwrap())))
         }
       }
-      (Self::ToI8, I64(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI16, I64(x)) => i16::try_from(*x).ok().map(I16),
-      (Self::ToI32, I64(x)) => i32::try_from(*x).ok().map(I32),
-      (Self::ToI128, I64(x)) => Some(I128((*x).into())),
-      (Self::Not, I64(x)) => Some(I64(!x)),
-      (Self::ToInt, I64(x)) => Some(Int((*x).into())),
-      (Self::ToBits, I64(x)) => {
+      (Self::ToI8, [I64(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI16, [I64(x)]) => i16::try_from(*x).ok().map(I16),
+      (Self::ToI32, [I64(x)]) => i32::try_from(*x).ok().map(I32),
+      (Self::ToI128, [I64(x)]) => Some(I128((*x).into())),
+      (Self::Not, [I64(x)]) => Some(I64(!x)),
+      (Self::ToInt, [I64(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [I64(x)]) => {
         Some(Bits(bits::bytes_to_bits(64, &x.to_be_bytes().into())))
       }
-      (Self::ToBytes, I64(x)) => Some(Bytes(x.to_be_bytes().into())),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, I64(x), I64(y)) => Some(Bool(x == y)),
-      (Self::Lte, I64(x), I64(y)) => Some(Bool(x <= y)),
-      (Self::Lth, I64(x), I64(y)) => Some(Bool(x < y)),
-      (Self::Gth, I64(x), I64(y)) => Some(Bool(x > y)),
-      (Self::Gte, I64(x), I64(y)) => Some(Bool(x >= y)),
-      (Self::And, I64(x), I64(y)) => Some(I64(x & y)),
-      (Self::Or, I64(x), I64(y)) => Some(I64(x | y)),
-      (Self::Xor, I64(x), I64(y)) => Some(I64(x ^ y)),
-      (Self::Add, I64(x), I64(y)) => Some(I64(x.wrapping_add(*y))),
-      (Self::Sub, I64(x), I64(y)) => Some(I64(x.wrapping_sub(*y))),
-      (Self::Mul, I64(x), I64(y)) => Some(I64(x.wrapping_mul(*y))),
-      (Self::Div, I64(x), I64(y)) => {
+      (Self::ToBytes, [I64(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [I64(x), I64(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [I64(x), I64(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [I64(x), I64(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [I64(x), I64(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [I64(x), I64(y)]) => Some(Bool(x >= y)),
+      (Self::And, [I64(x), I64(y)]) => Some(I64(x & y)),
+      (Self::Or, [I64(x), I64(y)]) => Some(I64(x | y)),
+      (Self::Xor, [I64(x), I64(y)]) => Some(I64(x ^ y)),
+      (Self::Add, [I64(x), I64(y)]) => Some(I64(x.wrapping_add(*y))),
+      (Self::Sub, [I64(x), I64(y)]) => Some(I64(x.wrapping_sub(*y))),
+      (Self::Mul, [I64(x), I64(y)]) => Some(I64(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [I64(x), I64(y)]) => Some(I64(x.wrapping_add(*y))),
+      (Self::WrappingSub, [I64(x), I64(y)]) => Some(I64(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [I64(x), I64(y)]) => Some(I64(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [I64(x), I64(y)]) => {
+        Some(I64(x.saturating_add(*y)))
+      }
+      (Self::SaturatingSub, [I64(x), I64(y)]) => {
+        Some(I64(x.saturating_sub(*y)))
+      }
+      (Self::SaturatingMul, [I64(x), I64(y)]) => {
+        Some(I64(x.saturating_mul(*y)))
+      }
+      (Self::Div, [I64(x), I64(y)]) => {
         if *y == 0 {
           None
         }
@@ -398,7 +467,7 @@ impl I64Op {
           Some(I64(x.wrapping_div(*y)))
         }
       }
-      (Self::Mod, I64(x), I64(y)) => {
+      (Self::Mod, [I64(x), I64(y)]) => {
         if *y == 0 {
           None
         }
@@ -406,11 +475,24 @@ impl I64Op {
           Some(I64(x.wrapping_rem(*y)))
         }
       }
-      (Self::Pow, I64(x), U32(y)) => Some(I64(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), I64(y)) => Some(I64(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), I64(y)) => Some(I64(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), I64(y)) => Some(I64(y.rotate_left(*x))),
-      (Self::Ror, U32(x), I64(y)) => Some(I64(y.rotate_right(*x))),
+      (Self::Pow, [I64(x), U32(y)]) => Some(I64(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), I64(y)]) => Some(I64(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), I64(y)]) => Some(I64(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), I64(y)]) => Some(I64(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), I64(y)]) => Some(I64(y.rotate_right(*x))),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    match (self, args) {
+      (Self::CheckedAdd, [I64(x), I64(y)]) => Some(x.checked_add(*y).map(I64)),
+      (Self::CheckedSub, [I64(x), I64(y)]) => Some(x.checked_sub(*y).map(I64)),
+      (Self::CheckedMul, [I64(x), I64(y)]) => Some(x.checked_mul(*y).map(I64)),
+      (Self::CheckedDiv, [I64(x), I64(y)]) => Some(x.checked_div(*y).map(I64)),
       _ => None,
     }
   }
@@ -458,7 +540,7 @@ pub mod tests {
   impl Arbitrary for I64Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=35);
+      let gen: u32 = rng.gen_range(0..=45);
       match gen {
         0 => Self::Abs,
         1 => Self::Sgn,
@@ -495,7 +577,17 @@ pub mod tests {
         32 => Self::ToI32,
         33 => Self::ToInt,
         34 => Self::ToBytes,
-        _ => Self::ToBits,
+        35 => Self::ToBits,
+        36 => Self::CheckedAdd,
+        37 => Self::CheckedSub,
+        38 => Self::CheckedMul,
+        39 => Self::CheckedDiv,
+        40 => Self::WrappingAdd,
+        41 => Self::WrappingSub,
+        42 => Self::WrappingMul,
+        43 => Self::SaturatingAdd,
+        44 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
         /* 29 => Self::ToU128,
          * 34 => Self::ToI128, */
       }
@@ -513,23 +605,29 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: I64Op, a: i64, b: i64, c: u32) -> TestResult {
     let apply0_go = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I64Op::apply0(op) == expected)
+      TestResult::from_bool(I64Op::apply(op, &[]) == expected)
     };
 
     let apply1_i64 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I64Op::apply1(op, &I64(a)) == expected)
+      TestResult::from_bool(I64Op::apply(op, &[&I64(a)]) == expected)
     };
 
     let apply2_i64_i64 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I64Op::apply2(op, &I64(a), &I64(b)) == expected)
+      TestResult::from_bool(I64Op::apply(op, &[&I64(a), &I64(b)]) == expected)
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        I64Op::apply_checked(op, &[&I64(a), &I64(b)]) == Some(expected),
+      )
     };
 
     let apply2_i64_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I64Op::apply2(op, &I64(a), &U32(c)) == expected)
+      TestResult::from_bool(I64Op::apply(op, &[&I64(a), &U32(c)]) == expected)
     };
 
     let apply2_u32_i64 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I64Op::apply2(op, &U32(c), &I64(a)) == expected)
+      TestResult::from_bool(I64Op::apply(op, &[&U32(c), &I64(a)]) == expected)
     };
 
     let from_bool = TestResult::from_bool;
@@ -557,6 +655,16 @@ pub mod tests {
       I64Op::Mod => {
         apply2_i64_i64(if b == 0 { None } else { Some(I64(a.wrapping_rem(b))) })
       }
+      I64Op::WrappingAdd => apply2_i64_i64(Some(I64(a.wrapping_add(b)))),
+      I64Op::WrappingSub => apply2_i64_i64(Some(I64(a.wrapping_sub(b)))),
+      I64Op::WrappingMul => apply2_i64_i64(Some(I64(a.wrapping_mul(b)))),
+      I64Op::SaturatingAdd => apply2_i64_i64(Some(I64(a.saturating_add(b)))),
+      I64Op::SaturatingSub => apply2_i64_i64(Some(I64(a.saturating_sub(b)))),
+      I64Op::SaturatingMul => apply2_i64_i64(Some(I64(a.saturating_mul(b)))),
+      I64Op::CheckedAdd => apply2_checked(a.checked_add(b).map(I64)),
+      I64Op::CheckedSub => apply2_checked(a.checked_sub(b).map(I64)),
+      I64Op::CheckedMul => apply2_checked(a.checked_mul(b).map(I64)),
+      I64Op::CheckedDiv => apply2_checked(a.checked_div(b).map(I64)),
       I64Op::Pow => apply2_i64_u32(Some(I64(a.wrapping_pow(c)))),
       I64Op::Shl => apply2_u32_i64(Some(I64(a.wrapping_shl(c)))),
       I64Op::Shr => apply2_u32_i64(Some(I64(a.wrapping_shr(c)))),
@@ -565,35 +673,35 @@ pub mod tests {
       I64Op::CountZeros => apply1_i64(Some(U32(a.count_zeros()))),
       I64Op::CountOnes => apply1_i64(Some(U32(a.count_ones()))),
       I64Op::ToU8 => from_bool(if a < u8::MIN.into() || a > u8::MAX.into() {
-        I64Op::apply1(op, &I64(a)) == None
+        I64Op::apply(op, &[&I64(a)]) == None
       }
       else {
-        U8Op::apply1(U8Op::ToI64, &I64Op::apply1(op, &I64(a)).unwrap())
+        U8Op::apply(U8Op::ToI64, &[&I64Op::apply(op, &[&I64(a)]).unwrap()])
           == Some(I64(a))
       }),
       I64Op::ToU16 => {
         from_bool(if a < u16::MIN.into() || a > u16::MAX.into() {
-          I64Op::apply1(op, &I64(a)) == None
+          I64Op::apply(op, &[&I64(a)]) == None
         }
         else {
-          U16Op::apply1(U16Op::ToI64, &I64Op::apply1(op, &I64(a)).unwrap())
+          U16Op::apply(U16Op::ToI64, &[&I64Op::apply(op, &[&I64(a)]).unwrap()])
             == Some(I64(a))
         })
       }
       I64Op::ToU32 => {
         from_bool(if a < u32::MIN.into() || a > u32::MAX.into() {
-          I64Op::apply1(op, &I64(a)) == None
+          I64Op::apply(op, &[&I64(a)]) == None
         }
         else {
-          U32Op::apply1(U32Op::ToI64, &I64Op::apply1(op, &I64(a)).unwrap())
+          U32Op::apply(U32Op::ToI64, &[&I64Op::apply(op, &[&I64(a)]).unwrap()])
             == Some(I64(a))
         })
       }
       I64Op::ToU64 => from_bool(if a < u64::MIN.try_into().unwrap() {
-        I64Op::apply1(op, &I64(a)) == None
+        I64Op::apply(op, &[&I64(a)]) == None
       }
       else {
-        U64Op::apply1(U64Op::ToI64, &I64Op::apply1(op, &I64(a)).unwrap())
+        U64Op::apply(U64Op::ToI64, &[&I64Op::apply(op, &[&I64(a)]).unwrap()])
           == Some(I64(a))
       }),
       I64Op::ToU128 => TestResult::discard(),
@@ -606,27 +714,27 @@ pub mod tests {
         }
       }
       I64Op::ToI8 => from_bool(if a < i8::MIN.into() || a > i8::MAX.into() {
-        I64Op::apply1(op, &I64(a)) == None
+        I64Op::apply(op, &[&I64(a)]) == None
       }
       else {
-        I8Op::apply1(I8Op::ToI64, &I64Op::apply1(op, &I64(a)).unwrap())
+        I8Op::apply(I8Op::ToI64, &[&I64Op::apply(op, &[&I64(a)]).unwrap()])
           == Some(I64(a))
       }),
       I64Op::ToI16 => {
         from_bool(if a < i16::MIN.into() || a > i16::MAX.into() {
-          I64Op::apply1(op, &I64(a)) == None
+          I64Op::apply(op, &[&I64(a)]) == None
         }
         else {
-          I16Op::apply1(I16Op::ToI64, &I64Op::apply1(op, &I64(a)).unwrap())
+          I16Op::apply(I16Op::ToI64, &[&I64Op::apply(op, &[&I64(a)]).unwrap()])
             == Some(I64(a))
         })
       }
       I64Op::ToI32 => {
         from_bool(if a < i32::MIN.into() || a > i32::MAX.into() {
-          I64Op::apply1(op, &I64(a)) == None
+          I64Op::apply(op, &[&I64(a)]) == None
         }
         else {
-          I32Op::apply1(I32Op::ToI64, &I64Op::apply1(op, &I64(a)).unwrap())
+          I32Op::apply(I32Op::ToI64, &[&I64Op::apply(op, &[&I64(a)]).unwrap()])
             == Some(I64(a))
         })
       }
@@ -652,13 +760,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(I64Op::apply1(op, &a) == None)
+        TestResult::from_bool(I64Op::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(I64Op::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            I64Op::apply(op, &[&a_, &b_]) == None
+              && I64Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -712,7 +825,17 @@ pub mod tests {
       | I64Op::Sub
       | I64Op::Mul
       | I64Op::Div
-      | I64Op::Mod => {
+      | I64Op::Mod
+      | I64Op::CheckedAdd
+      | I64Op::CheckedSub
+      | I64Op::CheckedMul
+      | I64Op::CheckedDiv
+      | I64Op::WrappingAdd
+      | I64Op::WrappingSub
+      | I64Op::WrappingMul
+      | I64Op::SaturatingAdd
+      | I64Op::SaturatingSub
+      | I64Op::SaturatingMul => {
         if test_arg_2 {
           test_apply2_none_on_invalid(I64(b), a, I64(b))
         }
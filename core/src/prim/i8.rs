@@ -61,6 +61,16 @@ pub enum I8Op {
   ToInt,
   ToBits,
   ToBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl I8Op {
@@ -105,6 +115,16 @@ impl I8Op {
       Self::ToInt => "to_Int".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -149,6 +169,16 @@ impl I8Op {
       "to_Int" => Some(Self::ToInt),
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -165,10 +195,10 @@ impl I8Op {
       Self::Lth => yatima!("∀ #I8 #I8 -> #Bool"),
       Self::Gth => yatima!("∀ #I8 #I8 -> #Bool"),
       Self::Gte => yatima!("∀ #I8 #I8 -> #Bool"),
-      Self::Not => yatima!("∀ #I8 #I8 -> #Bool"),
-      Self::And => yatima!("∀ #I8 #I8 -> #Bool"),
-      Self::Or => yatima!("∀ #I8 #I8 -> #Bool"),
-      Self::Xor => yatima!("∀ #I8 #I8 -> #Bool"),
+      Self::Not => yatima!("∀ #I8 -> #I8"),
+      Self::And => yatima!("∀ #I8 #I8 -> #I8"),
+      Self::Or => yatima!("∀ #I8 #I8 -> #I8"),
+      Self::Xor => yatima!("∀ #I8 #I8 -> #I8"),
       Self::Add => yatima!("∀ #I8 #I8 -> #I8"),
       Self::Sub => yatima!("∀ #I8 #I8 -> #I8"),
       Self::Mul => yatima!("∀ #I8 #I8 -> #I8"),
@@ -194,6 +224,18 @@ impl I8Op {
       Self::ToInt => yatima!("∀ #I8 -> #Int"),
       Self::ToBits => yatima!("∀ #I8 -> #Bits"),
       Self::ToBytes => yatima!("∀ #I8 -> #Bytes"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #I8 #I8 (0 P: Type) (none: P) (some: ∀ #I8 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #I8 #I8 -> #I8"),
+      Self::WrappingSub => yatima!("∀ #I8 #I8 -> #I8"),
+      Self::WrappingMul => yatima!("∀ #I8 #I8 -> #I8"),
+      Self::SaturatingAdd => yatima!("∀ #I8 #I8 -> #I8"),
+      Self::SaturatingSub => yatima!("∀ #I8 #I8 -> #I8"),
+      Self::SaturatingMul => yatima!("∀ #I8 #I8 -> #I8"),
     }
   }
 
@@ -238,6 +280,16 @@ impl I8Op {
       Self::ToInt => Ipld::Integer(35),
       Self::ToBits => Ipld::Integer(36),
       Self::ToBytes => Ipld::Integer(37),
+      Self::CheckedAdd => Ipld::Integer(38),
+      Self::CheckedSub => Ipld::Integer(39),
+      Self::CheckedMul => Ipld::Integer(40),
+      Self::CheckedDiv => Ipld::Integer(41),
+      Self::WrappingAdd => Ipld::Integer(42),
+      Self::WrappingSub => Ipld::Integer(43),
+      Self::WrappingMul => Ipld::Integer(44),
+      Self::SaturatingAdd => Ipld::Integer(45),
+      Self::SaturatingSub => Ipld::Integer(46),
+      Self::SaturatingMul => Ipld::Integer(47),
     }
   }
 
@@ -282,7 +334,17 @@ impl I8Op {
       Ipld::Integer(35) => Ok(Self::ToInt),
       Ipld::Integer(36) => Ok(Self::ToBits),
       Ipld::Integer(37) => Ok(Self::ToBytes),
-      xs => Err(IpldError::NatOp(xs.to_owned())),
+      Ipld::Integer(38) => Ok(Self::CheckedAdd),
+      Ipld::Integer(39) => Ok(Self::CheckedSub),
+      Ipld::Integer(40) => Ok(Self::CheckedMul),
+      Ipld::Integer(41) => Ok(Self::CheckedDiv),
+      Ipld::Integer(42) => Ok(Self::WrappingAdd),
+      Ipld::Integer(43) => Ok(Self::WrappingSub),
+      Ipld::Integer(44) => Ok(Self::WrappingMul),
+      Ipld::Integer(45) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(46) => Ok(Self::SaturatingSub),
+      Ipld::Integer(47) => Ok(Self::SaturatingMul),
+      xs => Err(IpldError::I8Op(xs.to_owned())),
     }
   }
 
@@ -327,33 +389,36 @@ impl I8Op {
       Self::ToInt => 1,
       Self::ToBits => 1,
       Self::ToBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
-    use Literal::*;
-    match self {
-      Self::Max => Some(I8(i8::MAX)),
-      Self::Min => Some(I8(i8::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::Abs, I8(x)) => Some(U8(x.unsigned_abs())),
-      (Self::Sgn, I8(x)) => Some(Bool(x.is_positive())),
-      (Self::CountZeros, I8(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, I8(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, I8(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU16, I8(x)) => u16::try_from(*x).ok().map(U16),
-      (Self::ToU32, I8(x)) => u32::try_from(*x).ok().map(U32),
-      (Self::ToU64, I8(x)) => u64::try_from(*x).ok().map(U64),
-      (Self::ToU128, I8(x)) => u128::try_from(*x).ok().map(U128),
-      (Self::ToNat, I8(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(I8(i8::MAX)),
+      (Self::Min, []) => Some(I8(i8::MIN)),
+      (Self::Abs, [I8(x)]) => Some(U8(x.unsigned_abs())),
+      (Self::Sgn, [I8(x)]) => Some(Bool(x.is_positive())),
+      (Self::CountZeros, [I8(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [I8(x)]) => Some(U32(x.count_ones())),
+      (Self::ToU8, [I8(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU16, [I8(x)]) => u16::try_from(*x).ok().map(U16),
+      (Self::ToU32, [I8(x)]) => u32::try_from(*x).ok().map(U32),
+      (Self::ToU64, [I8(x)]) => u64::try_from(*x).ok().map(U64),
+      (Self::ToU128, [I8(x)]) => u128::try_from(*x).ok().map(U128),
+      (Self::ToNat, [I8(x)]) => {
         if x.is_negative() {
           None
         }
@@ -361,36 +426,34 @@ impl I8Op {
           Some(Nat(BigUint::from(u64::try_from(*x).unwrap())))
         }
       }
-      (Self::ToI16, I8(x)) => Some(I16((*x).into())),
-      (Self::ToI32, I8(x)) => Some(I32((*x).into())),
-      (Self::ToI64, I8(x)) => Some(I64((*x).into())),
-      (Self::ToI128, I8(x)) => Some(I128((*x).into())),
-      (Self::Not, I8(x)) => Some(I8(!x)),
-      (Self::ToInt, I8(x)) => Some(Int((*x).into())),
-      (Self::ToBits, I8(x)) => {
+      (Self::ToI16, [I8(x)]) => Some(I16((*x).into())),
+      (Self::ToI32, [I8(x)]) => Some(I32((*x).into())),
+      (Self::ToI64, [I8(x)]) => Some(I64((*x).into())),
+      (Self::ToI128, [I8(x)]) => Some(I128((*x).into())),
+      (Self::Not, [I8(x)]) => Some(I8(!x)),
+      (Self::ToInt, [I8(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [I8(x)]) => {
         Some(Bits(bits::bytes_to_bits(8, &x.to_be_bytes().into())))
       }
-      (Self::ToBytes, I8(x)) => Some(Bytes(x.to_be_bytes().into())),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, I8(x), I8(y)) => Some(Bool(x == y)),
-      (Self::Lte, I8(x), I8(y)) => Some(Bool(x <= y)),
-      (Self::Lth, I8(x), I8(y)) => Some(Bool(x < y)),
-      (Self::Gth, I8(x), I8(y)) => Some(Bool(x > y)),
-      (Self::Gte, I8(x), I8(y)) => Some(Bool(x >= y)),
-      (Self::And, I8(x), I8(y)) => Some(I8(x & y)),
-      (Self::Or, I8(x), I8(y)) => Some(I8(x | y)),
-      (Self::Xor, I8(x), I8(y)) => Some(I8(x ^ y)),
-      (Self::Add, I8(x), I8(y)) => Some(I8(x.wrapping_add(*y))),
-      (Self::Sub, I8(x), I8(y)) => Some(I8(x.wrapping_sub(*y))),
-      (Self::Mul, I8(x), I8(y)) => Some(I8(x.wrapping_mul(*y))),
-      (Self::Div, I8(x), I8(y)) => {
+      (Self::ToBytes, [I8(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [I8(x), I8(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [I8(x), I8(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [I8(x), I8(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [I8(x), I8(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [I8(x), I8(y)]) => Some(Bool(x >= y)),
+      (Self::And, [I8(x), I8(y)]) => Some(I8(x & y)),
+      (Self::Or, [I8(x), I8(y)]) => Some(I8(x | y)),
+      (Self::Xor, [I8(x), I8(y)]) => Some(I8(x ^ y)),
+      (Self::Add, [I8(x), I8(y)]) => Some(I8(x.wrapping_add(*y))),
+      (Self::Sub, [I8(x), I8(y)]) => Some(I8(x.wrapping_sub(*y))),
+      (Self::Mul, [I8(x), I8(y)]) => Some(I8(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [I8(x), I8(y)]) => Some(I8(x.wrapping_add(*y))),
+      (Self::WrappingSub, [I8(x), I8(y)]) => Some(I8(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [I8(x), I8(y)]) => Some(I8(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [I8(x), I8(y)]) => Some(I8(x.saturating_add(*y))),
+      (Self::SaturatingSub, [I8(x), I8(y)]) => Some(I8(x.saturating_sub(*y))),
+      (Self::SaturatingMul, [I8(x), I8(y)]) => Some(I8(x.saturating_mul(*y))),
+      (Self::Div, [I8(x), I8(y)]) => {
         if *y == 0 {
           None
         }
@@ -398,7 +461,7 @@ impl I8Op {
           Some(I8(x.wrapping_div(*y)))
         }
       }
-      (Self::Mod, I8(x), I8(y)) => {
+      (Self::Mod, [I8(x), I8(y)]) => {
         if *y == 0 {
           None
         }
@@ -406,11 +469,24 @@ impl I8Op {
           Some(I8(x.wrapping_rem(*y)))
         }
       }
-      (Self::Pow, I8(x), U32(y)) => Some(I8(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), I8(y)) => Some(I8(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), I8(y)) => Some(I8(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), I8(y)) => Some(I8(y.rotate_left(*x))),
-      (Self::Ror, U32(x), I8(y)) => Some(I8(y.rotate_right(*x))),
+      (Self::Pow, [I8(x), U32(y)]) => Some(I8(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), I8(y)]) => Some(I8(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), I8(y)]) => Some(I8(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), I8(y)]) => Some(I8(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), I8(y)]) => Some(I8(y.rotate_right(*x))),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    match (self, args) {
+      (Self::CheckedAdd, [I8(x), I8(y)]) => Some(x.checked_add(*y).map(I8)),
+      (Self::CheckedSub, [I8(x), I8(y)]) => Some(x.checked_sub(*y).map(I8)),
+      (Self::CheckedMul, [I8(x), I8(y)]) => Some(x.checked_mul(*y).map(I8)),
+      (Self::CheckedDiv, [I8(x), I8(y)]) => Some(x.checked_div(*y).map(I8)),
       _ => None,
     }
   }
@@ -458,7 +534,7 @@ pub mod tests {
   impl Arbitrary for I8Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=35);
+      let gen: u32 = rng.gen_range(0..=45);
       match gen {
         0 => Self::Abs,
         1 => Self::Sgn,
@@ -495,7 +571,17 @@ pub mod tests {
         32 => Self::ToI64,
         33 => Self::ToInt,
         34 => Self::ToBits,
-        _ => Self::ToBytes,
+        35 => Self::ToBytes,
+        36 => Self::CheckedAdd,
+        37 => Self::CheckedSub,
+        38 => Self::CheckedMul,
+        39 => Self::CheckedDiv,
+        40 => Self::WrappingAdd,
+        41 => Self::WrappingSub,
+        42 => Self::WrappingMul,
+        43 => Self::SaturatingAdd,
+        44 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
         /* 29 => Self::ToU128,
          * 34 => Self::ToI128, */
       }
@@ -513,23 +599,29 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: I8Op, a: i8, b: i8, c: u32) -> TestResult {
     let apply0_go = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I8Op::apply0(op) == expected)
+      TestResult::from_bool(I8Op::apply(op, &[]) == expected)
     };
 
     let apply1_i8 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I8Op::apply1(op, &I8(a)) == expected)
+      TestResult::from_bool(I8Op::apply(op, &[&I8(a)]) == expected)
     };
 
     let apply2_i8_i8 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I8Op::apply2(op, &I8(a), &I8(b)) == expected)
+      TestResult::from_bool(I8Op::apply(op, &[&I8(a), &I8(b)]) == expected)
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        I8Op::apply_checked(op, &[&I8(a), &I8(b)]) == Some(expected),
+      )
     };
 
     let apply2_i8_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I8Op::apply2(op, &I8(a), &U32(c)) == expected)
+      TestResult::from_bool(I8Op::apply(op, &[&I8(a), &U32(c)]) == expected)
     };
 
     let apply2_u32_i8 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(I8Op::apply2(op, &U32(c), &I8(a)) == expected)
+      TestResult::from_bool(I8Op::apply(op, &[&U32(c), &I8(a)]) == expected)
     };
 
     let from_bool = TestResult::from_bool;
@@ -557,6 +649,16 @@ pub mod tests {
       I8Op::Mod => {
         apply2_i8_i8(if b == 0 { None } else { Some(I8(a.wrapping_rem(b))) })
       }
+      I8Op::WrappingAdd => apply2_i8_i8(Some(I8(a.wrapping_add(b)))),
+      I8Op::WrappingSub => apply2_i8_i8(Some(I8(a.wrapping_sub(b)))),
+      I8Op::WrappingMul => apply2_i8_i8(Some(I8(a.wrapping_mul(b)))),
+      I8Op::SaturatingAdd => apply2_i8_i8(Some(I8(a.saturating_add(b)))),
+      I8Op::SaturatingSub => apply2_i8_i8(Some(I8(a.saturating_sub(b)))),
+      I8Op::SaturatingMul => apply2_i8_i8(Some(I8(a.saturating_mul(b)))),
+      I8Op::CheckedAdd => apply2_checked(a.checked_add(b).map(I8)),
+      I8Op::CheckedSub => apply2_checked(a.checked_sub(b).map(I8)),
+      I8Op::CheckedMul => apply2_checked(a.checked_mul(b).map(I8)),
+      I8Op::CheckedDiv => apply2_checked(a.checked_div(b).map(I8)),
       I8Op::Pow => apply2_i8_u32(Some(I8(a.wrapping_pow(c)))),
       I8Op::Shl => apply2_u32_i8(Some(I8(a.wrapping_shl(c)))),
       I8Op::Shr => apply2_u32_i8(Some(I8(a.wrapping_shr(c)))),
@@ -565,31 +667,31 @@ pub mod tests {
       I8Op::CountZeros => apply1_i8(Some(U32(a.count_zeros()))),
       I8Op::CountOnes => apply1_i8(Some(U32(a.count_ones()))),
       I8Op::ToU8 => from_bool(if a < u8::MIN.try_into().unwrap() {
-        I8Op::apply1(op, &I8(a)) == None
+        I8Op::apply(op, &[&I8(a)]) == None
       }
       else {
-        U8Op::apply1(U8Op::ToI8, &I8Op::apply1(op, &I8(a)).unwrap())
+        U8Op::apply(U8Op::ToI8, &[&I8Op::apply(op, &[&I8(a)]).unwrap()])
           == Some(I8(a))
       }),
       I8Op::ToU16 => from_bool(if a < u16::MIN.try_into().unwrap() {
-        I8Op::apply1(op, &I8(a)) == None
+        I8Op::apply(op, &[&I8(a)]) == None
       }
       else {
-        U16Op::apply1(U16Op::ToI8, &I8Op::apply1(op, &I8(a)).unwrap())
+        U16Op::apply(U16Op::ToI8, &[&I8Op::apply(op, &[&I8(a)]).unwrap()])
           == Some(I8(a))
       }),
       I8Op::ToU32 => from_bool(if a < u32::MIN.try_into().unwrap() {
-        I8Op::apply1(op, &I8(a)) == None
+        I8Op::apply(op, &[&I8(a)]) == None
       }
       else {
-        U32Op::apply1(U32Op::ToI8, &I8Op::apply1(op, &I8(a)).unwrap())
+        U32Op::apply(U32Op::ToI8, &[&I8Op::apply(op, &[&I8(a)]).unwrap()])
           == Some(I8(a))
       }),
       I8Op::ToU64 => from_bool(if a < u64::MIN.try_into().unwrap() {
-        I8Op::apply1(op, &I8(a)) == None
+        I8Op::apply(op, &[&I8(a)]) == None
       }
       else {
-        U64Op::apply1(U64Op::ToI8, &I8Op::apply1(op, &I8(a)).unwrap())
+        U64Op::apply(U64Op::ToI8, &[&I8Op::apply(op, &[&I8(a)]).unwrap()])
           == Some(I8(a))
       }),
       I8Op::ToU128 => TestResult::discard(),
@@ -602,15 +704,15 @@ pub mod tests {
         }
       }
       I8Op::ToI16 => from_bool(
-        I16Op::apply1(I16Op::ToI8, &I8Op::apply1(op, &I8(a)).unwrap())
+        I16Op::apply(I16Op::ToI8, &[&I8Op::apply(op, &[&I8(a)]).unwrap()])
           == Some(I8(a)),
       ),
       I8Op::ToI32 => from_bool(
-        I32Op::apply1(I32Op::ToI8, &I8Op::apply1(op, &I8(a)).unwrap())
+        I32Op::apply(I32Op::ToI8, &[&I8Op::apply(op, &[&I8(a)]).unwrap()])
           == Some(I8(a)),
       ),
       I8Op::ToI64 => from_bool(
-        I64Op::apply1(I64Op::ToI8, &I8Op::apply1(op, &I8(a)).unwrap())
+        I64Op::apply(I64Op::ToI8, &[&I8Op::apply(op, &[&I8(a)]).unwrap()])
           == Some(I8(a)),
       ),
       I8Op::ToI128 => TestResult::discard(),
@@ -635,13 +737,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(I8Op::apply1(op, &a) == None)
+        TestResult::from_bool(I8Op::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(I8Op::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            I8Op::apply(op, &[&a_, &b_]) == None
+              && I8Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -695,7 +802,17 @@ pub mod tests {
       | I8Op::Sub
       | I8Op::Mul
       | I8Op::Div
-      | I8Op::Mod => {
+      | I8Op::Mod
+      | I8Op::CheckedAdd
+      | I8Op::CheckedSub
+      | I8Op::CheckedMul
+      | I8Op::CheckedDiv
+      | I8Op::WrappingAdd
+      | I8Op::WrappingSub
+      | I8Op::WrappingMul
+      | I8Op::SaturatingAdd
+      | I8Op::SaturatingSub
+      | I8Op::SaturatingMul => {
         if test_arg_2 {
           test_apply2_none_on_invalid(I8(b), a, I8(b))
         }
@@ -6,6 +6,7 @@ use sp_ipld::Ipld;
 
 use sp_std::{
   borrow::ToOwned,
+  convert::TryFrom,
   fmt,
 };
 
@@ -16,11 +17,14 @@ use crate::{
   ipld_error::IpldError,
   literal::Literal,
   parse,
+  prim::nat::MAX_RESULT_BITS,
   term::Term,
   yatima,
 };
 
-/// Primitive integer operations
+/// Primitive integer operations. `div` and `mod` truncate toward zero, so
+/// the remainder takes the sign of the dividend, while `div_euclid` and
+/// `mod_euclid` keep the remainder non-negative.
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum IntOp {
   New,
@@ -36,6 +40,22 @@ pub enum IntOp {
   Mul,
   Div,
   Mod,
+  Signum,
+  DivEuclid,
+  ModEuclid,
+  Pow,
+}
+
+/// The Euclidean quotient and remainder of `x` by a non-zero `y`
+fn div_mod_euclid(x: &BigInt, y: &BigInt) -> (BigInt, BigInt) {
+  let r = x % y;
+  let r = if r.sign() == Sign::Minus {
+    r + BigInt::from(y.magnitude().clone())
+  }
+  else {
+    r
+  };
+  ((x - &r) / y, r)
 }
 
 impl IntOp {
@@ -55,6 +75,10 @@ impl IntOp {
       Self::Mul => "mul".to_owned(),
       Self::Div => "div".to_owned(),
       Self::Mod => "mod".to_owned(),
+      Self::Signum => "signum".to_owned(),
+      Self::DivEuclid => "div_euclid".to_owned(),
+      Self::ModEuclid => "mod_euclid".to_owned(),
+      Self::Pow => "pow".to_owned(),
     }
   }
 
@@ -74,6 +98,10 @@ impl IntOp {
       "mul" => Some(Self::Mul),
       "div" => Some(Self::Div),
       "mod" => Some(Self::Mod),
+      "signum" => Some(Self::Signum),
+      "div_euclid" => Some(Self::DivEuclid),
+      "mod_euclid" => Some(Self::ModEuclid),
+      "pow" => Some(Self::Pow),
       _ => None,
     }
   }
@@ -94,6 +122,10 @@ impl IntOp {
       Self::Mul => yatima!("∀ #Int #Int -> #Int"),
       Self::Div => yatima!("∀ #Int #Int -> #Int"),
       Self::Mod => yatima!("∀ #Int #Int -> #Int"),
+      Self::Signum => yatima!("∀ #Int -> #Int"),
+      Self::DivEuclid => yatima!("∀ #Int #Int -> #Int"),
+      Self::ModEuclid => yatima!("∀ #Int #Int -> #Nat"),
+      Self::Pow => yatima!("∀ #Int #Nat -> #Int"),
     }
   }
 
@@ -113,6 +145,10 @@ impl IntOp {
       Self::Mul => Ipld::Integer(10),
       Self::Div => Ipld::Integer(11),
       Self::Mod => Ipld::Integer(12),
+      Self::Signum => Ipld::Integer(13),
+      Self::DivEuclid => Ipld::Integer(14),
+      Self::ModEuclid => Ipld::Integer(15),
+      Self::Pow => Ipld::Integer(16),
     }
   }
 
@@ -132,6 +168,10 @@ impl IntOp {
       Ipld::Integer(10) => Ok(Self::Mul),
       Ipld::Integer(11) => Ok(Self::Div),
       Ipld::Integer(12) => Ok(Self::Mod),
+      Ipld::Integer(13) => Ok(Self::Signum),
+      Ipld::Integer(14) => Ok(Self::DivEuclid),
+      Ipld::Integer(15) => Ok(Self::ModEuclid),
+      Ipld::Integer(16) => Ok(Self::Pow),
       xs => Err(IpldError::IntOp(xs.to_owned())),
     }
   }
@@ -152,42 +192,59 @@ impl IntOp {
       Self::Mul => 2,
       Self::Div => 2,
       Self::Mod => 2,
+      Self::Signum => 1,
+      Self::DivEuclid => 2,
+      Self::ModEuclid => 2,
+      Self::Pow => 2,
     }
   }
 
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x) {
-      (Self::Sgn, Int(x)) => Some(Bool(matches!(x.sign(), Sign::Plus))),
-      (Self::Abs, Int(x)) => Some(Nat(x.clone().into_parts().1)),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
     let tt = Bool(true);
     let ff = Bool(false);
     let ite = |c| if c { tt } else { ff };
-    match (self, x, y) {
-      (Self::New, Bool(x), Nat(y)) => Some(Int(if *x {
+    match (self, args) {
+      (Self::Sgn, [Int(x)]) => Some(Bool(matches!(x.sign(), Sign::Plus))),
+      (Self::Abs, [Int(x)]) => Some(Nat(x.clone().into_parts().1)),
+      (Self::Signum, [Int(x)]) => Some(Int(match x.sign() {
+        Sign::Minus => BigInt::from(-1),
+        Sign::NoSign => BigInt::from(0),
+        Sign::Plus => BigInt::from(1),
+      })),
+      (Self::New, [Bool(x), Nat(y)]) => Some(Int(if *x {
         BigInt::from(y.clone())
       }
       else {
         BigInt::from(y.clone()) * -1
       })),
-      (Self::Eql, Int(x), Int(y)) => Some(ite(x == y)),
-      (Self::Lte, Int(x), Int(y)) => Some(ite(x <= y)),
-      (Self::Lth, Int(x), Int(y)) => Some(ite(x < y)),
-      (Self::Gte, Int(x), Int(y)) => Some(ite(x >= y)),
-      (Self::Gth, Int(x), Int(y)) => Some(ite(x > y)),
-      (Self::Add, Int(x), Int(y)) => Some(Int(x + y)),
-      (Self::Sub, Int(x), Int(y)) => Some(Int(x - y)),
-      (Self::Mul, Int(x), Int(y)) => Some(Int(x * y)),
-      (Self::Div, Int(x), Int(y)) if *y != 0.into() => Some(Int(x / y)),
-      (Self::Mod, Int(x), Int(y)) if *y != 0.into() => Some(Int(x % y)),
+      (Self::Eql, [Int(x), Int(y)]) => Some(ite(x == y)),
+      (Self::Lte, [Int(x), Int(y)]) => Some(ite(x <= y)),
+      (Self::Lth, [Int(x), Int(y)]) => Some(ite(x < y)),
+      (Self::Gte, [Int(x), Int(y)]) => Some(ite(x >= y)),
+      (Self::Gth, [Int(x), Int(y)]) => Some(ite(x > y)),
+      (Self::Add, [Int(x), Int(y)]) => Some(Int(x + y)),
+      (Self::Sub, [Int(x), Int(y)]) => Some(Int(x - y)),
+      (Self::Mul, [Int(x), Int(y)]) => Some(Int(x * y)),
+      (Self::Div, [Int(x), Int(y)]) if *y != 0.into() => Some(Int(x / y)),
+      (Self::Mod, [Int(x), Int(y)]) if *y != 0.into() => Some(Int(x % y)),
+      (Self::DivEuclid, [Int(x), Int(y)]) if *y != 0.into() => {
+        Some(Int(div_mod_euclid(x, y).0))
+      }
+      (Self::ModEuclid, [Int(x), Int(y)]) if *y != 0.into() => {
+        Some(Nat(div_mod_euclid(x, y).1.into_parts().1))
+      }
+      // Powers larger than `MAX_RESULT_BITS` are stuck. The powers of zero,
+      // one and minus one never grow
+      (Self::Pow, [Int(x), Nat(y)]) => {
+        let y = u32::try_from(y).ok()?;
+        if x.bits() > 1 && x.bits().saturating_mul(y.into()) > MAX_RESULT_BITS {
+          return None;
+        }
+        Some(Int(x.pow(y)))
+      }
       _ => None,
     }
   }
@@ -221,7 +278,7 @@ pub mod tests {
   impl Arbitrary for IntOp {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=12);
+      let gen: u32 = rng.gen_range(0..=16);
       match gen {
         0 => Self::New,
         1 => Self::Sgn,
@@ -235,7 +292,11 @@ pub mod tests {
         9 => Self::Sub,
         10 => Self::Mul,
         11 => Self::Div,
-        _ => Self::Mod,
+        12 => Self::Mod,
+        13 => Self::Signum,
+        14 => Self::DivEuclid,
+        15 => Self::ModEuclid,
+        _ => Self::Pow,
       }
     }
   }
@@ -253,18 +314,18 @@ pub mod tests {
     let big_int = BigInt::from;
     let big_uint = BigUint::from;
     let apply1_int = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(IntOp::apply1(op, &Int(big_int(a))) == expected)
+      TestResult::from_bool(IntOp::apply(op, &[&Int(big_int(a))]) == expected)
     };
 
     let apply2_bool_nat = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        IntOp::apply2(op, &Bool(c), &Nat(big_uint(d))) == expected,
+        IntOp::apply(op, &[&Bool(c), &Nat(big_uint(d))]) == expected,
       )
     };
 
     let apply2_int_int = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        IntOp::apply2(op, &Int(big_int(a)), &Int(big_int(b))) == expected,
+        IntOp::apply(op, &[&Int(big_int(a)), &Int(big_int(b))]) == expected,
       )
     };
 
@@ -297,6 +358,28 @@ pub mod tests {
       else {
         None
       }),
+      IntOp::Signum => apply1_int(Some(Int(big_int(a.signum())))),
+      IntOp::DivEuclid => apply2_int_int(if b != 0 {
+        Some(Int(BigInt::from(i128::from(a).div_euclid(i128::from(b)))))
+      }
+      else {
+        None
+      }),
+      IntOp::ModEuclid => apply2_int_int(if b != 0 {
+        Some(Nat(BigUint::from(
+          u128::try_from(i128::from(a).rem_euclid(i128::from(b))).unwrap(),
+        )))
+      }
+      else {
+        None
+      }),
+      IntOp::Pow => {
+        let d = d % 8;
+        TestResult::from_bool(
+          IntOp::apply(op, &[&Int(big_int(a)), &Nat(big_uint(d))])
+            == Some(Int(big_int(a).pow(d as u32))),
+        )
+      }
     }
   }
 
@@ -313,13 +396,14 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(IntOp::apply1(op, &a) == None)
+        TestResult::from_bool(IntOp::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(IntOp::apply2(op, &a_, &b_) == None);
+        let go =
+          || TestResult::from_bool(IntOp::apply(op, &[&a_, &b_]) == None);
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -340,7 +424,19 @@ pub mod tests {
 
     match op {
       // Arity 1, valid is Int.
-      IntOp::Sgn | IntOp::Abs => test_apply1_none_on_invalid(Int(big(b))),
+      IntOp::Sgn | IntOp::Abs | IntOp::Signum => {
+        test_apply1_none_on_invalid(Int(big(b)))
+      }
+      // Arity 2, valid are Int on a and Nat on b.
+      IntOp::Pow => {
+        let n = Nat(BigUint::from(b.unsigned_abs()));
+        if test_arg_2 {
+          test_apply2_none_on_invalid(Int(big(b)), a, n)
+        }
+        else {
+          test_apply2_none_on_invalid(n, Int(big(b)), a)
+        }
+      }
       // Arity 2, valid are Int on a and b.
       IntOp::New
       | IntOp::Eql
@@ -352,7 +448,9 @@ pub mod tests {
       | IntOp::Sub
       | IntOp::Mul
       | IntOp::Div
-      | IntOp::Mod => {
+      | IntOp::Mod
+      | IntOp::DivEuclid
+      | IntOp::ModEuclid => {
         if test_arg_2 {
           test_apply2_none_on_invalid(Int(big(b)), a, Int(big(b)))
         }
@@ -363,6 +461,15 @@ pub mod tests {
     }
   }
 
+  #[test]
+  fn test_apply_result_bits() {
+    let huge = Nat(BigUint::from(u32::MAX));
+    let apply = |x: BigInt| IntOp::apply(IntOp::Pow, &[&Int(x), &huge]);
+    assert_eq!(apply(BigInt::from(1u64) << 70), None);
+    assert_eq!(apply(BigInt::from(-1)), Some(Int(BigInt::from(-1))));
+    assert_eq!(apply(BigInt::from(0)), Some(Int(BigInt::from(0))));
+  }
+
   //#[test]
   // fn test_apply_bin_op() {
   //  assert_eq!(
@@ -13,9 +13,7 @@ pub struct IoOp {
   pub fun_symbol: fn() -> String,
   pub fun_type_of: fn() -> Term,
   pub fun_arity: fn() -> u64,
-  pub fun_apply0: fn() -> Option<Literal>,
-  pub fun_apply1: Rc<dyn Fn(Literal) -> Option<Literal>>,
-  pub fun_apply2: fn(Literal, Literal) -> Option<Literal>,
+  pub fun_apply: Rc<dyn Fn(&[&Literal]) -> Option<Literal>>,
 }
 
 impl PartialEq for IoOp {
@@ -49,18 +47,8 @@ impl IoOp {
     f()
   }
 
-  pub fn apply0(&self) -> Option<Literal> {
-    let f = self.fun_apply0;
-    f()
-  }
-
-  pub fn apply1(&self, x: &Literal) -> Option<Literal> {
-    let f = self.fun_apply1.clone();
-    f(x.clone())
-  }
-
-  pub fn apply2(&self, x: &Literal, y: &Literal) -> Option<Literal> {
-    let f = self.fun_apply2;
-    f(x.clone(), y.clone())
+  pub fn apply(&self, args: &[&Literal]) -> Option<Literal> {
+    let f = &self.fun_apply;
+    f(args)
   }
 }
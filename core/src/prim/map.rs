@@ -0,0 +1,493 @@
+use sp_ipld::Ipld;
+use sp_ropey::Rope;
+
+use sp_std::{
+  borrow::ToOwned,
+  boxed::Box,
+  collections::btree_map::BTreeMap,
+  fmt,
+  str,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  name::Name,
+  parse,
+  position::Pos,
+  prim::{
+    list_term,
+    option_term,
+  },
+  term::Term,
+  yatima,
+};
+
+/// Primitive operations on finite maps from text to bytes. Other values go
+/// through their conversions to bytes
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum MapOp {
+  Empty,
+  Insert,
+  Lookup,
+  Delete,
+  Member,
+  Size,
+  Keys,
+  Values,
+  Fold,
+  FromList,
+}
+
+impl MapOp {
+  /// Gets the syntax string of a map operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Empty => "empty".to_owned(),
+      Self::Insert => "insert".to_owned(),
+      Self::Lookup => "lookup".to_owned(),
+      Self::Delete => "delete".to_owned(),
+      Self::Member => "member".to_owned(),
+      Self::Size => "size".to_owned(),
+      Self::Keys => "keys".to_owned(),
+      Self::Values => "values".to_owned(),
+      Self::Fold => "fold".to_owned(),
+      Self::FromList => "from_list".to_owned(),
+    }
+  }
+
+  /// Gets a map operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "empty" => Some(Self::Empty),
+      "insert" => Some(Self::Insert),
+      "lookup" => Some(Self::Lookup),
+      "delete" => Some(Self::Delete),
+      "member" => Some(Self::Member),
+      "size" => Some(Self::Size),
+      "keys" => Some(Self::Keys),
+      "values" => Some(Self::Values),
+      "fold" => Some(Self::Fold),
+      "from_list" => Some(Self::FromList),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of a map operation. `keys`, `values` and `fold` go
+  /// through the entries in key order. `from_list` zips a vector of UTF-8
+  /// keys with a vector of values of the same length, later entries
+  /// replacing earlier ones with the same key
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Empty => yatima!("#Map"),
+      Self::Insert => yatima!("∀ #Text #Bytes #Map -> #Map"),
+      Self::Lookup => {
+        yatima!("∀ #Text #Map (0 P: Type) (none: P) (some: ∀ #Bytes -> P) -> P")
+      }
+      Self::Delete => yatima!("∀ #Text #Map -> #Map"),
+      Self::Member => yatima!("∀ #Text #Map -> #Bool"),
+      Self::Size => yatima!("∀ #Map -> #Nat"),
+      Self::Keys => {
+        yatima!("∀ #Map (0 P: Type) (nil: P) (cons: ∀ #Text P -> P) -> P")
+      }
+      Self::Values => {
+        yatima!("∀ #Map (0 P: Type) (nil: P) (cons: ∀ #Bytes P -> P) -> P")
+      }
+      Self::Fold => yatima!(
+        "∀ #Map (0 P: Type) (nil: P) (cons: ∀ #Text #Bytes P -> P) -> P"
+      ),
+      Self::FromList => yatima!("∀ #Vector #Vector -> #Map"),
+    }
+  }
+
+  /// Converts a map operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Empty => Ipld::Integer(0),
+      Self::Insert => Ipld::Integer(1),
+      Self::Lookup => Ipld::Integer(2),
+      Self::Delete => Ipld::Integer(3),
+      Self::Member => Ipld::Integer(4),
+      Self::Size => Ipld::Integer(5),
+      Self::Keys => Ipld::Integer(6),
+      Self::Values => Ipld::Integer(7),
+      Self::Fold => Ipld::Integer(8),
+      Self::FromList => Ipld::Integer(9),
+    }
+  }
+
+  /// Converts an IPLD object into a map operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Empty),
+      Ipld::Integer(1) => Ok(Self::Insert),
+      Ipld::Integer(2) => Ok(Self::Lookup),
+      Ipld::Integer(3) => Ok(Self::Delete),
+      Ipld::Integer(4) => Ok(Self::Member),
+      Ipld::Integer(5) => Ok(Self::Size),
+      Ipld::Integer(6) => Ok(Self::Keys),
+      Ipld::Integer(7) => Ok(Self::Values),
+      Ipld::Integer(8) => Ok(Self::Fold),
+      Ipld::Integer(9) => Ok(Self::FromList),
+      xs => Err(IpldError::MapOp(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Empty => 0,
+      Self::Insert => 3,
+      Self::Lookup => 2,
+      Self::Delete => 2,
+      Self::Member => 2,
+      Self::Size => 1,
+      Self::Keys => 1,
+      Self::Values => 1,
+      Self::Fold => 1,
+      Self::FromList => 2,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Empty, []) => Some(Literal::Map(BTreeMap::new())),
+      (Self::Size, [Map(xs)]) => Some(Nat(xs.len().into())),
+      (Self::Delete, [Text(k), Map(xs)]) => {
+        let mut xs = xs.clone();
+        xs.remove(&k.to_string());
+        Some(Map(xs))
+      }
+      (Self::Member, [Text(k), Map(xs)]) => {
+        Some(Bool(xs.contains_key(&k.to_string())))
+      }
+      (Self::Insert, [Text(k), Bytes(v), Map(xs)]) => {
+        let mut xs = xs.clone();
+        xs.insert(k.to_string(), v.clone());
+        Some(Map(xs))
+      }
+      (Self::FromList, [Vector(ks), Vector(vs)]) if ks.len() == vs.len() => {
+        let mut xs = BTreeMap::new();
+        for (k, v) in ks.iter().zip(vs.iter()) {
+          xs.insert(str::from_utf8(k).ok()?.to_owned(), v.clone());
+        }
+        Some(Map(xs))
+      }
+      _ => None,
+    }
+  }
+
+  /// Applies an operation whose result is a term rather than a literal to
+  /// as many literals as its arity and returns it if successful
+  pub fn apply_term(self, args: &[&Literal]) -> Option<Term> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Keys, [Map(xs)]) => Some(list_term(
+        xs.keys().map(|k| Text(Rope::from(k.as_str()))).collect(),
+      )),
+      (Self::Values, [Map(xs)]) => {
+        Some(list_term(xs.values().map(|v| Bytes(v.clone())).collect()))
+      }
+      (Self::Lookup, [Text(k), Map(xs)]) => {
+        Some(option_term(xs.get(&k.to_string()).map(|v| Bytes(v.clone()))))
+      }
+      (Self::Fold, [Map(xs)]) => Some(fold_term(xs)),
+      _ => None,
+    }
+  }
+}
+
+/// Church-encodes the entries of a map in key order as a lambda of type
+/// `∀ (0 P: Type) (nil: P) (cons: ∀ #Text #Bytes P -> P) -> P`
+pub fn fold_term(xs: &BTreeMap<String, Vec<u8>>) -> Term {
+  let var = |nam: &str, idx| Term::Var(Pos::None, Name::from(nam), idx);
+  let app = |fun, arg| Term::App(Pos::None, Box::new((fun, arg)));
+  let lam =
+    |nam: &str, bod| Term::Lam(Pos::None, Name::from(nam), Box::new(bod));
+  let lit = |x| Term::Lit(Pos::None, x);
+  // λ P nil cons => cons k v (... nil)
+  let bod = xs.iter().rev().fold(var("nil", 1), |acc, (k, v)| {
+    let cons = app(var("cons", 0), lit(Literal::Text(Rope::from(k.as_str()))));
+    app(app(cons, lit(Literal::Bytes(v.clone()))), acc)
+  });
+  lam("P", lam("nil", lam("cons", bod)))
+}
+
+impl fmt::Display for MapOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::prim::tests::TestArg3;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+    TestResult,
+  };
+  use rand::Rng;
+  use sp_std::{
+    mem,
+    vec::Vec,
+  };
+  use Literal::{
+    Bool,
+    Bytes,
+    Map,
+    Nat,
+    Text,
+    Vector,
+  };
+  impl Arbitrary for MapOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=9);
+      match gen {
+        0 => Self::Empty,
+        1 => Self::Insert,
+        2 => Self::Lookup,
+        3 => Self::Delete,
+        4 => Self::Member,
+        5 => Self::Size,
+        6 => Self::Keys,
+        7 => Self::Values,
+        8 => Self::Fold,
+        _ => Self::FromList,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn map_op_ipld(x: MapOp) -> bool {
+    match MapOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply(
+    op: MapOp,
+    a: String,
+    b: Vec<u8>,
+    c: BTreeMap<String, Vec<u8>>,
+  ) -> TestResult {
+    let text = |x: &str| Text(Rope::from(x));
+    let apply1_map = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(MapOp::apply(op, &[&Map(c.clone())]) == expected)
+    };
+
+    let apply1_term_map = |expected: Option<Term>| -> TestResult {
+      TestResult::from_bool(
+        MapOp::apply_term(op, &[&Map(c.clone())]) == expected,
+      )
+    };
+
+    let apply2_text_map = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        MapOp::apply(op, &[&text(&a), &Map(c.clone())]) == expected,
+      )
+    };
+
+    let apply2_term_text_map = |expected: Option<Term>| -> TestResult {
+      TestResult::from_bool(
+        MapOp::apply_term(op, &[&text(&a), &Map(c.clone())]) == expected,
+      )
+    };
+
+    let apply3_text_bytes_map = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        MapOp::apply(op, &[&text(&a), &Bytes(b.clone()), &Map(c.clone())])
+          == expected,
+      )
+    };
+
+    match op {
+      MapOp::Empty => TestResult::from_bool(
+        MapOp::apply(op, &[]) == Some(Map(BTreeMap::new())),
+      ),
+      MapOp::Insert => apply3_text_bytes_map(Some(Map({
+        let mut c = c.clone();
+        c.insert(a.clone(), b.clone());
+        c
+      }))),
+      MapOp::Lookup => apply2_term_text_map(Some(option_term(
+        c.get(&a).map(|v| Bytes(v.clone())),
+      ))),
+      MapOp::Delete => apply2_text_map(Some(Map({
+        let mut c = c.clone();
+        c.remove(&a);
+        c
+      }))),
+      MapOp::Member => apply2_text_map(Some(Bool(c.contains_key(&a)))),
+      MapOp::Size => apply1_map(Some(Nat(c.len().into()))),
+      MapOp::Keys => {
+        apply1_term_map(Some(list_term(c.keys().map(|k| text(k)).collect())))
+      }
+      MapOp::Values => apply1_term_map(Some(list_term(
+        c.values().map(|v| Bytes(v.clone())).collect(),
+      ))),
+      MapOp::Fold => apply1_term_map(Some(fold_term(&c))),
+      MapOp::FromList => {
+        let ks = c.keys().map(|k| k.as_bytes().to_vec()).collect();
+        let vs = c.values().cloned().collect();
+        TestResult::from_bool(
+          MapOp::apply(op, &[&Vector(ks), &Vector(vs)]) == Some(Map(c.clone())),
+        )
+      }
+    }
+  }
+
+  #[test]
+  fn test_from_list() {
+    let from_list = |ks: Vec<&[u8]>, vs: Vec<&[u8]>| {
+      MapOp::apply(MapOp::FromList, &[
+        &Vector(ks.into_iter().map(|k| k.to_vec()).collect()),
+        &Vector(vs.into_iter().map(|v| v.to_vec()).collect()),
+      ])
+    };
+    let mut map = BTreeMap::new();
+    map.insert("a".to_owned(), vec![2]);
+    assert_eq!(from_list(vec![b"a", b"a"], vec![&[1], &[2]]), Some(Map(map)));
+    assert_eq!(from_list(vec![b"a"], vec![]), None);
+    assert_eq!(from_list(vec![&[0xff]], vec![&[1]]), None);
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(
+    op: MapOp,
+    a: Literal,
+    b: String,
+    c: Vec<u8>,
+    d: BTreeMap<String, Vec<u8>>,
+    test_arg_2: bool,
+    test_arg_3: TestArg3,
+  ) -> TestResult {
+    let text = |x: &str| Text(Rope::from(x));
+    let test_apply1_none_on_invalid = |valid_arg: Literal| -> TestResult {
+      if mem::discriminant(&valid_arg) == mem::discriminant(&a) {
+        TestResult::discard()
+      }
+      else {
+        TestResult::from_bool(
+          MapOp::apply(op, &[&a]) == None
+            && MapOp::apply_term(op, &[&a]) == None,
+        )
+      }
+    };
+
+    let test_apply2_none_on_invalid =
+      |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
+        let go = || {
+          TestResult::from_bool(
+            MapOp::apply(op, &[&a_, &b_]) == None
+              && MapOp::apply_term(op, &[&a_, &b_]) == None,
+          )
+        };
+        if test_arg_2 {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        else {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      };
+
+    let test_apply3_none_on_invalid = |valid_arg: Literal,
+                                       a_: Literal,
+                                       b_: Literal,
+                                       c_: Literal|
+     -> TestResult {
+      let go =
+        || TestResult::from_bool(MapOp::apply(op, &[&a_, &b_, &c_]) == None);
+      match test_arg_3 {
+        TestArg3::A => {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        TestArg3::B => {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        TestArg3::C => {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&c_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      }
+    };
+
+    match op {
+      // Arity 0.
+      MapOp::Empty => TestResult::discard(),
+      // Arity 1, valid is Map.
+      MapOp::Size | MapOp::Keys | MapOp::Values | MapOp::Fold => {
+        test_apply1_none_on_invalid(Map(d))
+      }
+      // Arity 2, valid are Text on a and Map on b.
+      MapOp::Lookup | MapOp::Delete | MapOp::Member => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(text(&b), a, Map(d))
+        }
+        else {
+          test_apply2_none_on_invalid(Map(d), text(&b), a)
+        }
+      }
+      // Arity 2, valid are Vector on a and b.
+      MapOp::FromList => {
+        let xs = Vector(sp_im::Vector::new());
+        if test_arg_2 {
+          test_apply2_none_on_invalid(xs.clone(), a, xs)
+        }
+        else {
+          test_apply2_none_on_invalid(xs.clone(), xs, a)
+        }
+      }
+      // Arity 3, valid are Text on a, Bytes on b and Map on c.
+      MapOp::Insert => match test_arg_3 {
+        TestArg3::A => {
+          test_apply3_none_on_invalid(text(&b), a, Bytes(c), Map(d))
+        }
+        TestArg3::B => {
+          test_apply3_none_on_invalid(Bytes(c), text(&b), a, Map(d))
+        }
+        TestArg3::C => {
+          test_apply3_none_on_invalid(Map(d), text(&b), Bytes(c), a)
+        }
+      },
+    }
+  }
+}
@@ -3,6 +3,7 @@ use sp_ipld::Ipld;
 
 use sp_std::{
   borrow::ToOwned,
+  convert::TryFrom,
   fmt,
 };
 
@@ -13,10 +14,16 @@ use crate::{
   ipld_error::IpldError,
   literal::Literal,
   parse,
+  prim::pair_term,
   term::Term,
   yatima,
 };
 
+/// The largest result, in bits, of an operation whose result can be much
+/// larger than its arguments, such as `#Nat.pow`. Larger results would
+/// exhaust memory, so those operations are stuck instead
+pub const MAX_RESULT_BITS: u64 = 1 << 28;
+
 /// Primitive natural number operations
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum NatOp {
@@ -32,6 +39,26 @@ pub enum NatOp {
   Mul,
   Div,
   Mod,
+  Pow,
+  Gcd,
+  Lcm,
+  Log2,
+  Sqrt,
+  Shl,
+  Shr,
+  DivMod,
+}
+
+/// Greatest common divisor by Euclid's algorithm, with `gcd(0, 0) = 0`
+pub fn gcd(x: &BigUint, y: &BigUint) -> BigUint {
+  let mut x = x.clone();
+  let mut y = y.clone();
+  while y != BigUint::from(0u64) {
+    let r = &x % &y;
+    x = y;
+    y = r;
+  }
+  x
 }
 
 impl NatOp {
@@ -50,6 +77,14 @@ impl NatOp {
       Self::Mul => "mul".to_owned(),
       Self::Div => "div".to_owned(),
       Self::Mod => "mod".to_owned(),
+      Self::Pow => "pow".to_owned(),
+      Self::Gcd => "gcd".to_owned(),
+      Self::Lcm => "lcm".to_owned(),
+      Self::Log2 => "log2".to_owned(),
+      Self::Sqrt => "sqrt".to_owned(),
+      Self::Shl => "shl".to_owned(),
+      Self::Shr => "shr".to_owned(),
+      Self::DivMod => "div_mod".to_owned(),
     }
   }
 
@@ -68,6 +103,14 @@ impl NatOp {
       "mul" => Some(Self::Mul),
       "div" => Some(Self::Div),
       "mod" => Some(Self::Mod),
+      "pow" => Some(Self::Pow),
+      "gcd" => Some(Self::Gcd),
+      "lcm" => Some(Self::Lcm),
+      "log2" => Some(Self::Log2),
+      "sqrt" => Some(Self::Sqrt),
+      "shl" => Some(Self::Shl),
+      "shr" => Some(Self::Shr),
+      "div_mod" => Some(Self::DivMod),
       _ => None,
     }
   }
@@ -87,6 +130,16 @@ impl NatOp {
       Self::Mul => yatima!("∀ #Nat #Nat -> #Nat"),
       Self::Div => yatima!("∀ #Nat #Nat -> #Nat"),
       Self::Mod => yatima!("∀ #Nat #Nat -> #Nat"),
+      Self::Pow => yatima!("∀ #Nat #Nat -> #Nat"),
+      Self::Gcd => yatima!("∀ #Nat #Nat -> #Nat"),
+      Self::Lcm => yatima!("∀ #Nat #Nat -> #Nat"),
+      Self::Log2 => yatima!("∀ #Nat -> #Nat"),
+      Self::Sqrt => yatima!("∀ #Nat -> #Nat"),
+      Self::Shl => yatima!("∀ #Nat #Nat -> #Nat"),
+      Self::Shr => yatima!("∀ #Nat #Nat -> #Nat"),
+      Self::DivMod => {
+        yatima!("∀ #Nat #Nat (0 P: Type) (pair: ∀ #Nat #Nat -> P) -> P")
+      }
     }
   }
 
@@ -105,6 +158,14 @@ impl NatOp {
       Self::Mul => Ipld::Integer(9),
       Self::Div => Ipld::Integer(10),
       Self::Mod => Ipld::Integer(11),
+      Self::Pow => Ipld::Integer(12),
+      Self::Gcd => Ipld::Integer(13),
+      Self::Lcm => Ipld::Integer(14),
+      Self::Log2 => Ipld::Integer(15),
+      Self::Sqrt => Ipld::Integer(16),
+      Self::Shl => Ipld::Integer(17),
+      Self::Shr => Ipld::Integer(18),
+      Self::DivMod => Ipld::Integer(19),
     }
   }
 
@@ -123,6 +184,14 @@ impl NatOp {
       Ipld::Integer(9) => Ok(Self::Mul),
       Ipld::Integer(10) => Ok(Self::Div),
       Ipld::Integer(11) => Ok(Self::Mod),
+      Ipld::Integer(12) => Ok(Self::Pow),
+      Ipld::Integer(13) => Ok(Self::Gcd),
+      Ipld::Integer(14) => Ok(Self::Lcm),
+      Ipld::Integer(15) => Ok(Self::Log2),
+      Ipld::Integer(16) => Ok(Self::Sqrt),
+      Ipld::Integer(17) => Ok(Self::Shl),
+      Ipld::Integer(18) => Ok(Self::Shr),
+      Ipld::Integer(19) => Ok(Self::DivMod),
       xs => Err(IpldError::NatOp(xs.to_owned())),
     }
   }
@@ -142,15 +211,27 @@ impl NatOp {
       Self::Mul => 2,
       Self::Div => 2,
       Self::Mod => 2,
+      Self::Pow => 2,
+      Self::Gcd => 2,
+      Self::Lcm => 2,
+      Self::Log2 => 1,
+      Self::Sqrt => 1,
+      Self::Shl => 2,
+      Self::Shr => 2,
+      Self::DivMod => 2,
     }
   }
 
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::Suc, Nat(x)) => Some(Nat(x + BigUint::from(1u64))),
-      (Self::Pre, Nat(x)) => {
+    let tt = Bool(true);
+    let ff = Bool(false);
+    let ite = |c| if c { tt } else { ff };
+    match (self, args) {
+      (Self::Suc, [Nat(x)]) => Some(Nat(x + BigUint::from(1u64))),
+      (Self::Pre, [Nat(x)]) => {
         if *x != 0u64.into() {
           Some(Nat(x - BigUint::from(1u64)))
         }
@@ -158,27 +239,63 @@ impl NatOp {
           Some(Nat(BigUint::from(0u64)))
         }
       }
+      // The floor of the logarithm, which is stuck at zero
+      (Self::Log2, [Nat(x)]) if *x != 0u64.into() => {
+        Some(Nat(BigUint::from(x.bits() - 1)))
+      }
+      (Self::Sqrt, [Nat(x)]) => Some(Nat(x.sqrt())),
+      (Self::Eql, [Nat(x), Nat(y)]) => Some(ite(x == y)),
+      (Self::Lte, [Nat(x), Nat(y)]) => Some(ite(x <= y)),
+      (Self::Lth, [Nat(x), Nat(y)]) => Some(ite(x < y)),
+      (Self::Gte, [Nat(x), Nat(y)]) => Some(ite(x >= y)),
+      (Self::Gth, [Nat(x), Nat(y)]) => Some(ite(x > y)),
+      (Self::Add, [Nat(x), Nat(y)]) => Some(Nat(x + y)),
+      (Self::Sub, [Nat(x), Nat(y)]) if x >= y => Some(Nat(x - y)),
+      (Self::Mul, [Nat(x), Nat(y)]) => Some(Nat(x * y)),
+      (Self::Div, [Nat(x), Nat(y)]) if *y != (0u64).into() => Some(Nat(x / y)),
+      (Self::Mod, [Nat(x), Nat(y)]) if *y != (0u64).into() => Some(Nat(x % y)),
+      // Powers and left shifts larger than `MAX_RESULT_BITS` are stuck. The
+      // powers of zero and one never grow
+      (Self::Pow, [Nat(x), Nat(y)]) => {
+        let y = u32::try_from(y).ok()?;
+        if x.bits() > 1 && x.bits().saturating_mul(y.into()) > MAX_RESULT_BITS {
+          return None;
+        }
+        Some(Nat(x.pow(y)))
+      }
+      (Self::Gcd, [Nat(x), Nat(y)]) => Some(Nat(gcd(x, y))),
+      (Self::Lcm, [Nat(x), Nat(y)]) => {
+        if *x == (0u64).into() || *y == (0u64).into() {
+          Some(Nat(BigUint::from(0u64)))
+        }
+        else {
+          Some(Nat(x / gcd(x, y) * y))
+        }
+      }
+      (Self::Shl, [Nat(x), Nat(y)]) => {
+        let y = u32::try_from(y).ok()?;
+        if x.bits() > 0 && x.bits() + u64::from(y) > MAX_RESULT_BITS {
+          return None;
+        }
+        Some(Nat(x << y))
+      }
+      (Self::Shr, [Nat(x), Nat(y)]) => match u32::try_from(y) {
+        Ok(y) => Some(Nat(x >> y)),
+        Err(_) => Some(Nat(BigUint::from(0u64))),
+      },
       _ => None,
     }
   }
 
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
+  /// Applies an operation whose result is a pair rather than a literal to as
+  /// many literals as its arity and returns it if successful. Like `Div`,
+  /// dividing by zero is stuck
+  pub fn apply_term(self, args: &[&Literal]) -> Option<Term> {
     use Literal::*;
-    let tt = Bool(true);
-    let ff = Bool(false);
-    let ite = |c| if c { tt } else { ff };
-    match (self, x, y) {
-      (Self::Eql, Nat(x), Nat(y)) => Some(ite(x == y)),
-      (Self::Lte, Nat(x), Nat(y)) => Some(ite(x <= y)),
-      (Self::Lth, Nat(x), Nat(y)) => Some(ite(x < y)),
-      (Self::Gte, Nat(x), Nat(y)) => Some(ite(x >= y)),
-      (Self::Gth, Nat(x), Nat(y)) => Some(ite(x > y)),
-      (Self::Add, Nat(x), Nat(y)) => Some(Nat(x + y)),
-      (Self::Sub, Nat(x), Nat(y)) if x >= y => Some(Nat(x - y)),
-      (Self::Mul, Nat(x), Nat(y)) => Some(Nat(x * y)),
-      (Self::Div, Nat(x), Nat(y)) if *y != (0u64).into() => Some(Nat(x / y)),
-      (Self::Mod, Nat(x), Nat(y)) if *y != (0u64).into() => Some(Nat(x % y)),
+    match (self, args) {
+      (Self::DivMod, [Nat(x), Nat(y)]) if *y != (0u64).into() => {
+        Some(pair_term(Nat(x / y), Nat(x % y)))
+      }
       _ => None,
     }
   }
@@ -208,7 +325,7 @@ pub mod tests {
   impl Arbitrary for NatOp {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=11);
+      let gen: u32 = rng.gen_range(0..=19);
       match gen {
         0 => Self::Suc,
         1 => Self::Pre,
@@ -221,7 +338,15 @@ pub mod tests {
         8 => Self::Sub,
         9 => Self::Mul,
         10 => Self::Div,
-        _ => Self::Mod,
+        11 => Self::Mod,
+        12 => Self::Pow,
+        13 => Self::Gcd,
+        14 => Self::Lcm,
+        15 => Self::Log2,
+        16 => Self::Sqrt,
+        17 => Self::Shl,
+        18 => Self::Shr,
+        _ => Self::DivMod,
       }
     }
   }
@@ -238,12 +363,12 @@ pub mod tests {
   fn test_apply(op: NatOp, a: u64, b: u64) -> TestResult {
     let big = BigUint::from;
     let apply1_nat = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(NatOp::apply1(op, &Nat(big(a))) == expected)
+      TestResult::from_bool(NatOp::apply(op, &[&Nat(big(a))]) == expected)
     };
 
     let apply2_nat_nat = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        NatOp::apply2(op, &Nat(big(a)), &Nat(big(b))) == expected,
+        NatOp::apply(op, &[&Nat(big(a)), &Nat(big(b))]) == expected,
       )
     };
 
@@ -266,6 +391,61 @@ pub mod tests {
       NatOp::Mod => {
         apply2_nat_nat(if b != 0 { Some(Nat(big(a % b))) } else { None })
       }
+      NatOp::Pow => {
+        let b = b % 8;
+        TestResult::from_bool(
+          NatOp::apply(op, &[&Nat(big(a)), &Nat(big(b))])
+            == Some(Nat(big(a).pow(b as u32))),
+        )
+      }
+      NatOp::Gcd => {
+        let (mut x, mut y) = (a, b);
+        while y != 0 {
+          let r = x % y;
+          x = y;
+          y = r;
+        }
+        apply2_nat_nat(Some(Nat(big(x))))
+      }
+      NatOp::Lcm => {
+        match NatOp::apply(NatOp::Gcd, &[&Nat(big(a)), &Nat(big(b))]) {
+          Some(Nat(g)) if g != big(0) => {
+            apply2_nat_nat(Some(Nat(big(a) * big(b) / g)))
+          }
+          _ => apply2_nat_nat(Some(Nat(big(0)))),
+        }
+      }
+      NatOp::Log2 => apply1_nat(if a == 0 {
+        None
+      }
+      else {
+        Some(Nat(big(63 - u64::from(a.leading_zeros()))))
+      }),
+      NatOp::Sqrt => match NatOp::apply(op, &[&Nat(big(a))]) {
+        Some(Nat(r)) => TestResult::from_bool(
+          &r * &r <= big(a) && (&r + big(1)) * (&r + big(1)) > big(a),
+        ),
+        _ => TestResult::failed(),
+      },
+      NatOp::Shl => {
+        let b = b % 256;
+        TestResult::from_bool(
+          NatOp::apply(op, &[&Nat(big(a)), &Nat(big(b))])
+            == Some(Nat(big(a) * big(2).pow(b as u32))),
+        )
+      }
+      NatOp::Shr => {
+        apply2_nat_nat(Some(Nat(big(if b < 64 { a >> b } else { 0 }))))
+      }
+      NatOp::DivMod => TestResult::from_bool(
+        NatOp::apply_term(op, &[&Nat(big(a)), &Nat(big(b))])
+          == if b != 0 {
+            Some(pair_term(Nat(big(a / b)), Nat(big(a % b))))
+          }
+          else {
+            None
+          },
+      ),
     }
   }
 
@@ -282,13 +462,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(NatOp::apply1(op, &a) == None)
+        TestResult::from_bool(NatOp::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(NatOp::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            NatOp::apply(op, &[&a_, &b_]) == None
+              && NatOp::apply_term(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -309,7 +494,9 @@ pub mod tests {
 
     match op {
       // Arity 1, valid is Nat.
-      NatOp::Suc | NatOp::Pre => test_apply1_none_on_invalid(Nat(big(b))),
+      NatOp::Suc | NatOp::Pre | NatOp::Log2 | NatOp::Sqrt => {
+        test_apply1_none_on_invalid(Nat(big(b)))
+      }
       // Arity 2, valid are Nat on a and b.
       NatOp::Eql
       | NatOp::Lte
@@ -320,7 +507,13 @@ pub mod tests {
       | NatOp::Sub
       | NatOp::Mul
       | NatOp::Div
-      | NatOp::Mod => {
+      | NatOp::Mod
+      | NatOp::Pow
+      | NatOp::Gcd
+      | NatOp::Lcm
+      | NatOp::Shl
+      | NatOp::Shr
+      | NatOp::DivMod => {
         if test_arg_2 {
           test_apply2_none_on_invalid(Nat(big(b)), a, Nat(big(b)))
         }
@@ -331,6 +524,18 @@ pub mod tests {
     }
   }
 
+  #[test]
+  fn test_apply_result_bits() {
+    let big = BigUint::from;
+    let huge = Nat(big(u64::from(u32::MAX)));
+    let apply = |op, x: u64, y: &Literal| NatOp::apply(op, &[&Nat(big(x)), y]);
+    assert_eq!(apply(NatOp::Pow, 1 << 35, &huge), None);
+    assert_eq!(apply(NatOp::Shl, 1, &huge), None);
+    assert_eq!(apply(NatOp::Pow, 1, &huge), Some(Nat(big(1))));
+    assert_eq!(apply(NatOp::Shl, 0, &huge), Some(Nat(big(0))));
+    assert_eq!(apply(NatOp::Pow, 2, &Nat(big(64))), Some(Nat(big(1) << 64)));
+  }
+
   //#[test]
   // fn test_apply_bin_op() {
   //  assert_eq!(
@@ -0,0 +1,191 @@
+use sp_ipld::Ipld;
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  prim::pair_term,
+  term::Term,
+  yatima,
+};
+
+/// The increment of the SplitMix64 generator, an odd approximation of
+/// 2^64 divided by the golden ratio
+pub const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+/// Primitive operations on deterministic random number generators. A `#Rng`
+/// is the state of a SplitMix64 generator, so the same seed always gives the
+/// same numbers
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum RngOp {
+  New,
+  NextU64,
+  Split,
+}
+
+impl RngOp {
+  /// Gets the syntax string of a generator operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::New => "new".to_owned(),
+      Self::NextU64 => "next_u64".to_owned(),
+      Self::Split => "split".to_owned(),
+    }
+  }
+
+  /// Gets a generator operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "new" => Some(Self::New),
+      "next_u64" => Some(Self::NextU64),
+      "split" => Some(Self::Split),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of a generator operation
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::New => yatima!("∀ #U64 -> #Rng"),
+      Self::NextU64 => {
+        yatima!("∀ #Rng (0 P: Type) (pair: ∀ #U64 #Rng -> P) -> P")
+      }
+      Self::Split => {
+        yatima!("∀ #Rng (0 P: Type) (pair: ∀ #Rng #Rng -> P) -> P")
+      }
+    }
+  }
+
+  /// Converts a generator operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::New => Ipld::Integer(0),
+      Self::NextU64 => Ipld::Integer(1),
+      Self::Split => Ipld::Integer(2),
+    }
+  }
+
+  /// Converts an IPLD object into a generator operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::New),
+      Ipld::Integer(1) => Ok(Self::NextU64),
+      Ipld::Integer(2) => Ok(Self::Split),
+      xs => Err(IpldError::RngOp(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::New => 1,
+      Self::NextU64 => 1,
+      Self::Split => 1,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::New, [U64(seed)]) => Some(Rng(*seed)),
+      _ => None,
+    }
+  }
+
+  /// Applies an operation whose result is a term rather than a literal to
+  /// as many literals as its arity and returns it if successful
+  pub fn apply_term(self, args: &[&Literal]) -> Option<Term> {
+    use Literal::*;
+    match (self, args) {
+      (Self::NextU64, [Rng(state)]) => {
+        let (x, state) = next(*state);
+        Some(pair_term(U64(x), Rng(state)))
+      }
+      (Self::Split, [Rng(state)]) => {
+        let (x, y) = split(*state);
+        Some(pair_term(Rng(x), Rng(y)))
+      }
+      _ => None,
+    }
+  }
+}
+
+/// Advances a generator, returning a random number and the next state
+pub fn next(state: u64) -> (u64, u64) {
+  let state = state.wrapping_add(GOLDEN_GAMMA);
+  let mut z = state;
+  z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+  z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+  (z ^ (z >> 31), state)
+}
+
+/// Splits a generator into two, seeded by its next two numbers
+pub fn split(state: u64) -> (u64, u64) {
+  let (x, state) = next(state);
+  let (y, _) = next(state);
+  (x, y)
+}
+
+impl fmt::Display for RngOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  impl Arbitrary for RngOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=2);
+      match gen {
+        0 => Self::New,
+        1 => Self::NextU64,
+        _ => Self::Split,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn rng_op_ipld(x: RngOp) -> bool {
+    match RngOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[test]
+  fn test_next() {
+    // The first outputs of SplitMix64 seeded with zero
+    let (x, state) = next(0);
+    assert_eq!(x, 0xe220_a839_7b1d_cdaf);
+    let (y, _) = next(state);
+    assert_eq!(y, 0x6e78_9e6a_a1b9_65f4);
+    assert_eq!(split(0), (x, y));
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: RngOp, a: Literal) -> bool {
+    match (op, &a) {
+      (RngOp::New, Literal::U64(_)) => true,
+      (RngOp::NextU64, Literal::Rng(_)) => true,
+      (RngOp::Split, Literal::Rng(_)) => true,
+      _ => op.apply(&[&a]) == None && op.apply_term(&[&a]) == None,
+    }
+  }
+}
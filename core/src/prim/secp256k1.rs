@@ -0,0 +1,275 @@
+#[cfg(feature = "crypto")]
+use libsecp256k1::{
+  Message,
+  PublicKey,
+  RecoveryId,
+  SecretKey,
+  Signature,
+};
+use sp_ipld::Ipld;
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  term::Term,
+  yatima,
+};
+
+#[cfg(feature = "crypto")]
+use crate::prim::option_term;
+
+/// Primitive secp256k1 ECDSA operations. These work on 32-byte message
+/// digests rather than messages, since each chain hashes its transactions
+/// differently. Public keys are 33-byte compressed points, and signatures are
+/// 64 bytes followed by a recovery id byte
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Secp256k1Op {
+  PublicKey,
+  Sign,
+  Verify,
+  Recover,
+}
+
+// Without `crypto` the apply functions are left with only their fallback arm
+#[cfg_attr(not(feature = "crypto"), allow(clippy::match_single_binding))]
+impl Secp256k1Op {
+  /// Gets the syntax string of a secp256k1 operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::PublicKey => "public_key".to_owned(),
+      Self::Sign => "sign".to_owned(),
+      Self::Verify => "verify".to_owned(),
+      Self::Recover => "recover".to_owned(),
+    }
+  }
+
+  /// Gets a secp256k1 operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "public_key" => Some(Self::PublicKey),
+      "sign" => Some(Self::Sign),
+      "verify" => Some(Self::Verify),
+      "recover" => Some(Self::Recover),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of a secp256k1 operation. `sign` takes the secret key
+  /// and then the digest, `verify` takes the public key, digest and signature,
+  /// and `recover` takes the digest and signature
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::PublicKey => yatima!("∀ #Bytes -> #Bytes"),
+      Self::Sign => yatima!("∀ #Bytes #Bytes -> #Bytes"),
+      Self::Verify => yatima!("∀ #Bytes #Bytes #Bytes -> #Bool"),
+      Self::Recover => yatima!(
+        "∀ #Bytes #Bytes (0 P: Type) (none: P) (some: ∀ #Bytes -> P) -> P"
+      ),
+    }
+  }
+
+  /// Converts a secp256k1 operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::PublicKey => Ipld::Integer(0),
+      Self::Sign => Ipld::Integer(1),
+      Self::Verify => Ipld::Integer(2),
+      Self::Recover => Ipld::Integer(3),
+    }
+  }
+
+  /// Converts an IPLD object into a secp256k1 operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::PublicKey),
+      Ipld::Integer(1) => Ok(Self::Sign),
+      Ipld::Integer(2) => Ok(Self::Verify),
+      Ipld::Integer(3) => Ok(Self::Recover),
+      xs => Err(IpldError::Secp256k1Op(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::PublicKey => 1,
+      Self::Sign => 2,
+      Self::Verify => 3,
+      Self::Recover => 2,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    match (self, args) {
+      #[cfg(feature = "crypto")]
+      (Self::PublicKey, [Literal::Bytes(key)]) => {
+        let key = SecretKey::parse_slice(key).ok()?;
+        let key = PublicKey::from_secret_key(&key);
+        Some(Literal::Bytes(key.serialize_compressed().to_vec()))
+      }
+      #[cfg(feature = "crypto")]
+      (Self::Sign, [Literal::Bytes(key), Literal::Bytes(digest)]) => {
+        let key = SecretKey::parse_slice(key).ok()?;
+        let digest = Message::parse_slice(digest).ok()?;
+        let (sig, id) = libsecp256k1::sign(&digest, &key);
+        let mut sig = sig.serialize().to_vec();
+        sig.push(id.serialize());
+        Some(Literal::Bytes(sig))
+      }
+      #[cfg(feature = "crypto")]
+      (
+        Self::Verify,
+        [Literal::Bytes(key), Literal::Bytes(digest), Literal::Bytes(sig)],
+      ) => Some(Literal::Bool(verify(key, digest, sig))),
+      _ => None,
+    }
+  }
+
+  /// Applies an operation whose result is a term rather than a literal to
+  /// as many literals as its arity and returns it if successful
+  pub fn apply_term(self, args: &[&Literal]) -> Option<Term> {
+    match (self, args) {
+      #[cfg(feature = "crypto")]
+      (Self::Recover, [Literal::Bytes(digest), Literal::Bytes(sig)]) => {
+        let key = recover(digest, sig);
+        Some(option_term(key.map(|key| Literal::Bytes(key.to_vec()))))
+      }
+      _ => None,
+    }
+  }
+}
+
+/// Parses the first 64 bytes of a signature, ignoring any recovery id
+#[cfg(feature = "crypto")]
+fn signature(sig: &[u8]) -> Option<Signature> {
+  Signature::parse_standard_slice(sig.get(..64)?).ok()
+}
+
+/// Checks a signature over a digest, treating malformed input as invalid
+#[cfg(feature = "crypto")]
+pub fn verify(key: &[u8], digest: &[u8], sig: &[u8]) -> bool {
+  let key = PublicKey::parse_slice(key, None);
+  let digest = Message::parse_slice(digest);
+  match (key, digest, signature(sig)) {
+    (Ok(key), Ok(digest), Some(sig)) => {
+      libsecp256k1::verify(&digest, &sig, &key)
+    }
+    _ => false,
+  }
+}
+
+/// Recovers the compressed public key that made a 65-byte signature
+#[cfg(feature = "crypto")]
+pub fn recover(digest: &[u8], sig: &[u8]) -> Option<[u8; 33]> {
+  if sig.len() != 65 {
+    return None;
+  }
+  let digest = Message::parse_slice(digest).ok()?;
+  let id = RecoveryId::parse(sig[64]).ok()?;
+  let key = libsecp256k1::recover(&digest, &signature(sig)?, &id).ok()?;
+  Some(key.serialize_compressed())
+}
+
+impl fmt::Display for Secp256k1Op {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  impl Arbitrary for Secp256k1Op {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=3);
+      match gen {
+        0 => Self::PublicKey,
+        1 => Self::Sign,
+        2 => Self::Verify,
+        _ => Self::Recover,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn secp256k1_op_ipld(x: Secp256k1Op) -> bool {
+    match Secp256k1Op::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  // The public key of the secret key 1 is the generator point
+  #[cfg(feature = "crypto")]
+  #[test]
+  fn test_generator() {
+    use Literal::Bytes;
+    let mut key = vec![0; 32];
+    key[31] = 1;
+    let g =
+      "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    let g = (0..g.len())
+      .step_by(2)
+      .map(|i| u8::from_str_radix(&g[i..i + 2], 16).unwrap())
+      .collect();
+    assert_eq!(
+      Secp256k1Op::apply(Secp256k1Op::PublicKey, &[&Bytes(key)]),
+      Some(Bytes(g))
+    );
+    assert_eq!(
+      Secp256k1Op::apply(Secp256k1Op::PublicKey, &[&Bytes(vec![0; 32])]),
+      None
+    );
+  }
+
+  #[cfg(feature = "crypto")]
+  #[quickcheck]
+  fn test_sign_verify_recover(key: u64, digest: u64) -> bool {
+    use Literal::{
+      Bool,
+      Bytes,
+    };
+    let pad = |x: u64| {
+      let mut bytes = vec![0; 32];
+      bytes[24..].copy_from_slice(&x.to_be_bytes());
+      bytes
+    };
+    let key = Bytes(pad(key.max(1)));
+    let digest = Bytes(pad(digest));
+    let public = Secp256k1Op::apply(Secp256k1Op::PublicKey, &[&key]).unwrap();
+    let sig = Secp256k1Op::apply(Secp256k1Op::Sign, &[&key, &digest]).unwrap();
+    Secp256k1Op::apply(Secp256k1Op::Verify, &[&public, &digest, &sig])
+      == Some(Bool(true))
+      && Secp256k1Op::apply_term(Secp256k1Op::Recover, &[&digest, &sig])
+        == Some(option_term(Some(public)))
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: Secp256k1Op, a: Literal) -> bool {
+    match a {
+      Literal::Bytes(_) => true,
+      _ => {
+        Secp256k1Op::apply(op, &[&a]) == None
+          && Secp256k1Op::apply(op, &[&a, &a]) == None
+          && Secp256k1Op::apply_term(op, &[&a, &a]) == None
+          && Secp256k1Op::apply(op, &[&a, &a, &a]) == None
+      }
+    }
+  }
+}
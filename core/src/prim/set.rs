@@ -0,0 +1,344 @@
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  collections::btree_set::BTreeSet,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  prim::list_term,
+  term::Term,
+  yatima,
+};
+
+/// Primitive operations on finite sets of bytes, kept in ascending order
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum SetOp {
+  Empty,
+  Insert,
+  Delete,
+  Member,
+  Union,
+  Intersection,
+  Difference,
+  Size,
+  ToList,
+}
+
+impl SetOp {
+  /// Gets the syntax string of a set operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Empty => "empty".to_owned(),
+      Self::Insert => "insert".to_owned(),
+      Self::Delete => "delete".to_owned(),
+      Self::Member => "member".to_owned(),
+      Self::Union => "union".to_owned(),
+      Self::Intersection => "intersection".to_owned(),
+      Self::Difference => "difference".to_owned(),
+      Self::Size => "size".to_owned(),
+      Self::ToList => "to_list".to_owned(),
+    }
+  }
+
+  /// Gets a set operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "empty" => Some(Self::Empty),
+      "insert" => Some(Self::Insert),
+      "delete" => Some(Self::Delete),
+      "member" => Some(Self::Member),
+      "union" => Some(Self::Union),
+      "intersection" => Some(Self::Intersection),
+      "difference" => Some(Self::Difference),
+      "size" => Some(Self::Size),
+      "to_list" => Some(Self::ToList),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of a set operation
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Empty => yatima!("#Set"),
+      Self::Insert => yatima!("∀ #Bytes #Set -> #Set"),
+      Self::Delete => yatima!("∀ #Bytes #Set -> #Set"),
+      Self::Member => yatima!("∀ #Bytes #Set -> #Bool"),
+      Self::Union => yatima!("∀ #Set #Set -> #Set"),
+      Self::Intersection => yatima!("∀ #Set #Set -> #Set"),
+      Self::Difference => yatima!("∀ #Set #Set -> #Set"),
+      Self::Size => yatima!("∀ #Set -> #Nat"),
+      Self::ToList => {
+        yatima!("∀ #Set (0 P: Type) (nil: P) (cons: ∀ #Bytes P -> P) -> P")
+      }
+    }
+  }
+
+  /// Converts a set operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Empty => Ipld::Integer(0),
+      Self::Insert => Ipld::Integer(1),
+      Self::Delete => Ipld::Integer(2),
+      Self::Member => Ipld::Integer(3),
+      Self::Union => Ipld::Integer(4),
+      Self::Intersection => Ipld::Integer(5),
+      Self::Difference => Ipld::Integer(6),
+      Self::Size => Ipld::Integer(7),
+      Self::ToList => Ipld::Integer(8),
+    }
+  }
+
+  /// Converts an IPLD object into a set operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Empty),
+      Ipld::Integer(1) => Ok(Self::Insert),
+      Ipld::Integer(2) => Ok(Self::Delete),
+      Ipld::Integer(3) => Ok(Self::Member),
+      Ipld::Integer(4) => Ok(Self::Union),
+      Ipld::Integer(5) => Ok(Self::Intersection),
+      Ipld::Integer(6) => Ok(Self::Difference),
+      Ipld::Integer(7) => Ok(Self::Size),
+      Ipld::Integer(8) => Ok(Self::ToList),
+      xs => Err(IpldError::SetOp(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Empty => 0,
+      Self::Insert => 2,
+      Self::Delete => 2,
+      Self::Member => 2,
+      Self::Union => 2,
+      Self::Intersection => 2,
+      Self::Difference => 2,
+      Self::Size => 1,
+      Self::ToList => 1,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Empty, []) => Some(Literal::Set(BTreeSet::new())),
+      (Self::Size, [Set(xs)]) => Some(Nat(xs.len().into())),
+      (Self::Insert, [Bytes(x), Set(xs)]) => {
+        let mut xs = xs.clone();
+        xs.insert(x.clone());
+        Some(Set(xs))
+      }
+      (Self::Delete, [Bytes(x), Set(xs)]) => {
+        let mut xs = xs.clone();
+        xs.remove(x);
+        Some(Set(xs))
+      }
+      (Self::Member, [Bytes(x), Set(xs)]) => Some(Bool(xs.contains(x))),
+      (Self::Union, [Set(xs), Set(ys)]) => Some(Set(xs | ys)),
+      (Self::Intersection, [Set(xs), Set(ys)]) => Some(Set(xs & ys)),
+      (Self::Difference, [Set(xs), Set(ys)]) => Some(Set(xs - ys)),
+      _ => None,
+    }
+  }
+
+  /// Applies an operation whose result is a term rather than a literal to
+  /// as many literals as its arity and returns it if successful
+  pub fn apply_term(self, args: &[&Literal]) -> Option<Term> {
+    use Literal::*;
+    match (self, args) {
+      (Self::ToList, [Set(xs)]) => {
+        Some(list_term(xs.iter().map(|x| Bytes(x.clone())).collect()))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for SetOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+    TestResult,
+  };
+  use rand::Rng;
+  use sp_std::{
+    mem,
+    vec::Vec,
+  };
+  use Literal::{
+    Bool,
+    Bytes,
+    Nat,
+    Set,
+  };
+  impl Arbitrary for SetOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=8);
+      match gen {
+        0 => Self::Empty,
+        1 => Self::Insert,
+        2 => Self::Delete,
+        3 => Self::Member,
+        4 => Self::Union,
+        5 => Self::Intersection,
+        6 => Self::Difference,
+        7 => Self::Size,
+        _ => Self::ToList,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn set_op_ipld(x: SetOp) -> bool {
+    match SetOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply(
+    op: SetOp,
+    a: Vec<u8>,
+    b: BTreeSet<Vec<u8>>,
+    c: BTreeSet<Vec<u8>>,
+  ) -> TestResult {
+    let apply1_set = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(SetOp::apply(op, &[&Set(b.clone())]) == expected)
+    };
+
+    let apply1_term_set = |expected: Option<Term>| -> TestResult {
+      TestResult::from_bool(
+        SetOp::apply_term(op, &[&Set(b.clone())]) == expected,
+      )
+    };
+
+    let apply2_bytes_set = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        SetOp::apply(op, &[&Bytes(a.clone()), &Set(b.clone())]) == expected,
+      )
+    };
+
+    let apply2_set_set = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        SetOp::apply(op, &[&Set(b.clone()), &Set(c.clone())]) == expected,
+      )
+    };
+
+    match op {
+      SetOp::Empty => TestResult::from_bool(
+        SetOp::apply(op, &[]) == Some(Set(BTreeSet::new())),
+      ),
+      SetOp::Insert => apply2_bytes_set(Some(Set({
+        let mut b = b.clone();
+        b.insert(a.clone());
+        b
+      }))),
+      SetOp::Delete => apply2_bytes_set(Some(Set({
+        let mut b = b.clone();
+        b.remove(&a);
+        b
+      }))),
+      SetOp::Member => apply2_bytes_set(Some(Bool(b.contains(&a)))),
+      SetOp::Union => apply2_set_set(Some(Set(b.union(&c).cloned().collect()))),
+      SetOp::Intersection => {
+        apply2_set_set(Some(Set(b.intersection(&c).cloned().collect())))
+      }
+      SetOp::Difference => {
+        apply2_set_set(Some(Set(b.difference(&c).cloned().collect())))
+      }
+      SetOp::Size => apply1_set(Some(Nat(b.len().into()))),
+      SetOp::ToList => apply1_term_set(Some(list_term(
+        b.iter().map(|x| Bytes(x.clone())).collect(),
+      ))),
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(
+    op: SetOp,
+    a: Literal,
+    b: Vec<u8>,
+    c: BTreeSet<Vec<u8>>,
+    test_arg_2: bool,
+  ) -> TestResult {
+    let test_apply1_none_on_invalid = |valid_arg: Literal| -> TestResult {
+      if mem::discriminant(&valid_arg) == mem::discriminant(&a) {
+        TestResult::discard()
+      }
+      else {
+        TestResult::from_bool(
+          SetOp::apply(op, &[&a]) == None
+            && SetOp::apply_term(op, &[&a]) == None,
+        )
+      }
+    };
+
+    let test_apply2_none_on_invalid =
+      |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
+        let go =
+          || TestResult::from_bool(SetOp::apply(op, &[&a_, &b_]) == None);
+        if test_arg_2 {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        else {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      };
+
+    match op {
+      // Arity 0.
+      SetOp::Empty => TestResult::discard(),
+      // Arity 1, valid is Set.
+      SetOp::Size | SetOp::ToList => test_apply1_none_on_invalid(Set(c)),
+      // Arity 2, valid are Bytes on a and Set on b.
+      SetOp::Insert | SetOp::Delete | SetOp::Member => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(Bytes(b), a, Set(c))
+        }
+        else {
+          test_apply2_none_on_invalid(Set(c), Bytes(b), a)
+        }
+      }
+      // Arity 2, valid are Set on a and b.
+      SetOp::Union | SetOp::Intersection | SetOp::Difference => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(Set(c.clone()), a, Set(c))
+        }
+        else {
+          test_apply2_none_on_invalid(Set(c.clone()), Set(c), a)
+        }
+      }
+    }
+  }
+}
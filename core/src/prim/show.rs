@@ -0,0 +1,190 @@
+use sp_ipld::Ipld;
+use sp_ropey::Rope;
+
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::{
+    LitType,
+    Literal,
+  },
+  parse,
+  position::Pos,
+  prim::conv::type_name,
+  term::Term,
+  yatima,
+};
+
+/// Every literal type, each of which has a `show` operation
+pub const LIT_TYPES: [LitType; 24] = [
+  LitType::Nat,
+  LitType::Int,
+  LitType::Bits,
+  LitType::Bytes,
+  LitType::Text,
+  LitType::Char,
+  LitType::Bool,
+  LitType::U8,
+  LitType::U16,
+  LitType::U32,
+  LitType::U64,
+  LitType::U128,
+  LitType::U256,
+  LitType::I8,
+  LitType::I16,
+  LitType::I32,
+  LitType::I64,
+  LitType::I128,
+  LitType::F32,
+  LitType::F64,
+  LitType::Map,
+  LitType::Set,
+  LitType::Vector,
+  LitType::Cid,
+];
+
+/// Renders a literal of the given type as the source syntax that parses back
+/// into it, such as `#U8.show 3u8` giving `"3u8"`
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub struct ShowOp {
+  pub typ: LitType,
+}
+
+impl ShowOp {
+  /// Gets the syntax string of a show operation, without the prefix of its
+  /// type
+  pub fn symbol(self) -> String { "show".to_owned() }
+
+  /// Gets a show operation from its type and syntax string
+  pub fn from_symbol(typ: LitType, x: &str) -> Option<Self> {
+    match x {
+      "show" => Some(ShowOp { typ }),
+      _ => None,
+    }
+  }
+
+  /// Gets a show operation from a full name such as `U8.show`
+  pub fn from_name(x: &str) -> Option<Self> {
+    let mut parts = x.splitn(2, '.');
+    let typ = parts.next()?;
+    let typ = LIT_TYPES.iter().find(|t| type_name(**t) == typ)?;
+    Self::from_symbol(*typ, parts.next()?)
+  }
+
+  /// Returns the type of a show operation
+  pub fn type_of(self) -> Term {
+    yatima!("∀ #$0 -> #Text", Term::LTy(Pos::None, self.typ))
+  }
+
+  /// Converts a show operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld { self.typ.to_ipld() }
+
+  /// Converts an IPLD object into a show operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    LitType::from_ipld(ipld)
+      .map(|typ| ShowOp { typ })
+      .map_err(|_| IpldError::ShowOp(ipld.to_owned()))
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 { 1 }
+
+  /// Applies a show operation to a literal of its type and returns it if
+  /// successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    match args {
+      [x] if x.lit_type() == self.typ => {
+        Some(Literal::Text(Rope::from(x.to_string())))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for ShowOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  impl Arbitrary for ShowOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      ShowOp { typ: LIT_TYPES[rng.gen_range(0..LIT_TYPES.len())] }
+    }
+  }
+
+  #[quickcheck]
+  fn show_op_ipld(x: ShowOp) -> bool {
+    match ShowOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn show_op_symbol(x: ShowOp) -> bool {
+    ShowOp::from_name(&format!("{}.show", type_name(x.typ))) == Some(x)
+  }
+
+  #[test]
+  fn test_from_name_invalid() {
+    assert_eq!(ShowOp::from_name("U8"), None);
+    assert_eq!(ShowOp::from_name("U8.shows"), None);
+    assert_eq!(ShowOp::from_name("Type.show"), None);
+  }
+
+  // Showing a literal gives text that parses back into it
+  #[quickcheck]
+  fn test_show_parses(x: Literal) -> bool {
+    let op = ShowOp { typ: x.lit_type() };
+    let s = match op.apply(&[&x]) {
+      Some(Literal::Text(s)) => s.to_string(),
+      _ => return false,
+    };
+    match parse::term::parse(&s, defs::Defs::new()) {
+      Ok((_, y)) => y == Term::Lit(Pos::None, x),
+      Err(_) => false,
+    }
+  }
+
+  #[test]
+  fn test_show() {
+    use Literal::*;
+    let show = |typ, x: Literal| ShowOp { typ }.apply(&[&x]);
+    assert_eq!(show(LitType::U8, U8(3)), Some(Text(Rope::from("3u8"))));
+    assert_eq!(show(LitType::I8, I8(-3)), Some(Text(Rope::from("-3i8"))));
+    assert_eq!(
+      show(LitType::Text, Text(Rope::from("a\"b"))),
+      Some(Text(Rope::from("\"a\\\"b\"")))
+    );
+    assert_eq!(
+      show(LitType::Bool, Bool(true)),
+      Some(Text(Rope::from("#Bool.true")))
+    );
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: ShowOp, a: Literal) -> bool {
+    a.lit_type() == op.typ || op.apply(&[&a]) == None
+  }
+}
@@ -1,4 +1,5 @@
 use num_bigint::BigUint;
+use sp_im::Vector;
 use sp_ropey::Rope;
 use sp_ipld::Ipld;
 
@@ -18,6 +19,10 @@ use crate::{
   ipld_error::IpldError,
   literal::Literal,
   parse,
+  prim::{
+    list_term,
+    option_term,
+  },
   term::Term,
   yatima,
 };
@@ -27,7 +32,7 @@ use core::convert::{
   TryInto,
 };
 
-/// Primitive 8-bit signed integer operations
+/// Primitive text operations
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum TextOp {
   Cons,
@@ -54,6 +59,16 @@ pub enum TextOp {
   LineStartChar,
   LineStartByte,
   ToBytes,
+  SplitOn,
+  Find,
+  Replace,
+  ToUpper,
+  ToLower,
+  Trim,
+  StartsWith,
+  EndsWith,
+  Slice,
+  Format,
 }
 
 impl TextOp {
@@ -84,6 +99,16 @@ impl TextOp {
       Self::LineStartByte => "line_start_byte".to_owned(),
       Self::LineStartChar => "line_start_char".to_owned(),
       Self::ToBytes => "to_bytes".to_owned(),
+      Self::SplitOn => "split_on".to_owned(),
+      Self::Find => "find".to_owned(),
+      Self::Replace => "replace".to_owned(),
+      Self::ToUpper => "to_upper".to_owned(),
+      Self::ToLower => "to_lower".to_owned(),
+      Self::Trim => "trim".to_owned(),
+      Self::StartsWith => "starts_with".to_owned(),
+      Self::EndsWith => "ends_with".to_owned(),
+      Self::Slice => "slice".to_owned(),
+      Self::Format => "format".to_owned(),
     }
   }
 
@@ -114,11 +139,23 @@ impl TextOp {
       "line_start_byte" => Some(Self::LineStartByte),
       "line_start_char" => Some(Self::LineStartChar),
       "to_bytes" => Some(Self::ToBytes),
+      "split_on" => Some(Self::SplitOn),
+      "find" => Some(Self::Find),
+      "replace" => Some(Self::Replace),
+      "to_upper" => Some(Self::ToUpper),
+      "to_lower" => Some(Self::ToLower),
+      "trim" => Some(Self::Trim),
+      "starts_with" => Some(Self::StartsWith),
+      "ends_with" => Some(Self::EndsWith),
+      "slice" => Some(Self::Slice),
+      "format" => Some(Self::Format),
       _ => None,
     }
   }
 
-  /// Returns the type of an i8 operation
+  /// Returns the type of a text operation. Indices and lengths count Unicode
+  /// scalar values (`#Char`s) unless the op name says bytes or lines; grapheme
+  /// clusters are not segmented
   pub fn type_of(self) -> Term {
     match self {
       Self::Cons => yatima!("∀ #Char #Text -> #Text"),
@@ -145,6 +182,22 @@ impl TextOp {
       Self::LineStartChar => yatima!("∀ #Nat #Text -> #Nat"),
       Self::LineStartByte => yatima!("∀ #Nat #Text -> #Nat"),
       Self::ToBytes => yatima!("∀ #Text -> #Bytes"),
+      Self::SplitOn => {
+        yatima!(
+          "∀ #Text #Text (0 P: Type) (nil: P) (cons: ∀ #Text P -> P) -> P"
+        )
+      }
+      Self::Find => {
+        yatima!("∀ #Text #Text (0 P: Type) (none: P) (some: ∀ #Nat -> P) -> P")
+      }
+      Self::Replace => yatima!("∀ #Text #Text #Text -> #Text"),
+      Self::ToUpper => yatima!("∀ #Text -> #Text"),
+      Self::ToLower => yatima!("∀ #Text -> #Text"),
+      Self::Trim => yatima!("∀ #Text -> #Text"),
+      Self::StartsWith => yatima!("∀ #Text #Text -> #Bool"),
+      Self::EndsWith => yatima!("∀ #Text #Text -> #Bool"),
+      Self::Slice => yatima!("∀ #Nat #Nat #Text -> #Text"),
+      Self::Format => yatima!("∀ #Text #Vector -> #Text"),
     }
   }
 
@@ -175,6 +228,16 @@ impl TextOp {
       Self::LineStartChar => Ipld::Integer(21),
       Self::LineStartByte => Ipld::Integer(22),
       Self::ToBytes => Ipld::Integer(23),
+      Self::SplitOn => Ipld::Integer(24),
+      Self::Find => Ipld::Integer(25),
+      Self::Replace => Ipld::Integer(26),
+      Self::ToUpper => Ipld::Integer(27),
+      Self::ToLower => Ipld::Integer(28),
+      Self::Trim => Ipld::Integer(29),
+      Self::StartsWith => Ipld::Integer(30),
+      Self::EndsWith => Ipld::Integer(31),
+      Self::Slice => Ipld::Integer(32),
+      Self::Format => Ipld::Integer(33),
     }
   }
 
@@ -205,6 +268,16 @@ impl TextOp {
       Ipld::Integer(21) => Ok(Self::LineStartChar),
       Ipld::Integer(22) => Ok(Self::LineStartByte),
       Ipld::Integer(23) => Ok(Self::ToBytes),
+      Ipld::Integer(24) => Ok(Self::SplitOn),
+      Ipld::Integer(25) => Ok(Self::Find),
+      Ipld::Integer(26) => Ok(Self::Replace),
+      Ipld::Integer(27) => Ok(Self::ToUpper),
+      Ipld::Integer(28) => Ok(Self::ToLower),
+      Ipld::Integer(29) => Ok(Self::Trim),
+      Ipld::Integer(30) => Ok(Self::StartsWith),
+      Ipld::Integer(31) => Ok(Self::EndsWith),
+      Ipld::Integer(32) => Ok(Self::Slice),
+      Ipld::Integer(33) => Ok(Self::Format),
       xs => Err(IpldError::TextOp(xs.to_owned())),
     }
   }
@@ -236,57 +309,75 @@ impl TextOp {
       Self::LineStartChar => 2,
       Self::LineStartByte => 2,
       Self::ToBytes => 1,
+      Self::SplitOn => 2,
+      Self::Find => 2,
+      Self::Replace => 3,
+      Self::ToUpper => 1,
+      Self::ToLower => 1,
+      Self::Trim => 1,
+      Self::StartsWith => 2,
+      Self::EndsWith => 2,
+      Self::Slice => 3,
+      Self::Format => 2,
     }
   }
 
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x) {
-      (Self::LenChars, Text(xs)) => Some(Nat(xs.len_chars().into())),
-      (Self::LenBytes, Text(xs)) => Some(Nat(xs.len_bytes().into())),
-      (Self::LenLines, Text(xs)) => Some(Nat(xs.len_lines().into())),
-      (Self::ToBytes, Text(xs)) => Some(Bytes(xs.bytes().collect::<Vec<u8>>())),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x, y) {
-      (Self::Cons, Char(c), Text(cs)) => {
+    match (self, args) {
+      (Self::LenChars, [Text(xs)]) => Some(Nat(xs.len_chars().into())),
+      (Self::LenBytes, [Text(xs)]) => Some(Nat(xs.len_bytes().into())),
+      (Self::LenLines, [Text(xs)]) => Some(Nat(xs.len_lines().into())),
+      (Self::ToBytes, [Text(xs)]) => {
+        Some(Bytes(xs.bytes().collect::<Vec<u8>>()))
+      }
+      (Self::ToUpper, [Text(xs)]) => {
+        Some(Text(Rope::from(xs.to_string().to_uppercase())))
+      }
+      (Self::ToLower, [Text(xs)]) => {
+        Some(Text(Rope::from(xs.to_string().to_lowercase())))
+      }
+      (Self::Trim, [Text(xs)]) => Some(Text(Rope::from(xs.to_string().trim()))),
+      (Self::Cons, [Char(c), Text(cs)]) => {
         let mut cs = cs.clone();
         cs.insert_char(0, *c);
         Some(Text(cs))
       }
-      (Self::Append, Text(xs), Text(ys)) => {
+      (Self::Append, [Text(xs), Text(ys)]) => {
         let mut xs = xs.clone();
         xs.append(ys.clone());
         Some(Text(xs))
       }
-      (Self::Take, Nat(x), Text(xs)) => {
+      (Self::Take, [Nat(x), Text(xs)]) => {
         let (xs, _) = safe_split(x, xs.clone());
         Some(Text(xs))
       }
-      (Self::Drop, Nat(x), Text(xs)) => {
+      (Self::Drop, [Nat(x), Text(xs)]) => {
         let (_, ys) = safe_split(x, xs.clone());
         Some(Text(ys))
       }
-      (Self::Eql, Text(xs), Text(ys)) => Some(Bool(xs == ys)),
-      (Self::Lte, Text(xs), Text(ys)) => Some(Bool(xs <= ys)),
-      (Self::Lth, Text(xs), Text(ys)) => Some(Bool(xs < ys)),
-      (Self::Gte, Text(xs), Text(ys)) => Some(Bool(xs >= ys)),
-      (Self::Gth, Text(xs), Text(ys)) => Some(Bool(xs > ys)),
-      (Self::Char, Nat(idx), Text(ys)) => {
+      (Self::Eql, [Text(xs), Text(ys)]) => Some(Bool(xs == ys)),
+      (Self::Lte, [Text(xs), Text(ys)]) => Some(Bool(xs <= ys)),
+      (Self::Lth, [Text(xs), Text(ys)]) => Some(Bool(xs < ys)),
+      (Self::Gte, [Text(xs), Text(ys)]) => Some(Bool(xs >= ys)),
+      (Self::Gth, [Text(xs), Text(ys)]) => Some(Bool(xs > ys)),
+      (Self::StartsWith, [Text(xs), Text(ys)]) => {
+        Some(Bool(ys.to_string().starts_with(&xs.to_string())))
+      }
+      (Self::EndsWith, [Text(xs), Text(ys)]) => {
+        Some(Bool(ys.to_string().ends_with(&xs.to_string())))
+      }
+      (Self::Char, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_chars() { Some(Char(ys.char(idx))) } else { None }
       }
-      (Self::Byte, Nat(idx), Text(ys)) => {
+      (Self::Byte, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_chars() { Some(U8(ys.byte(idx))) } else { None }
       }
-      (Self::Line, Nat(idx), Text(ys)) => {
+      (Self::Line, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_lines() {
           Some(Text(ys.line(idx).into()))
@@ -295,7 +386,7 @@ impl TextOp {
           None
         }
       }
-      (Self::CharAtByte, Nat(idx), Text(ys)) => {
+      (Self::CharAtByte, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_bytes() {
           Some(Nat(ys.byte_to_char(idx).into()))
@@ -304,7 +395,7 @@ impl TextOp {
           None
         }
       }
-      (Self::ByteAtChar, Nat(idx), Text(ys)) => {
+      (Self::ByteAtChar, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_chars() {
           Some(Nat(ys.char_to_byte(idx).into()))
@@ -313,7 +404,7 @@ impl TextOp {
           None
         }
       }
-      (Self::LineAtChar, Nat(idx), Text(ys)) => {
+      (Self::LineAtChar, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_chars() {
           Some(Nat(ys.char_to_line(idx).into()))
@@ -322,7 +413,7 @@ impl TextOp {
           None
         }
       }
-      (Self::LineAtByte, Nat(idx), Text(ys)) => {
+      (Self::LineAtByte, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_bytes() {
           Some(Nat(ys.byte_to_line(idx).into()))
@@ -331,7 +422,7 @@ impl TextOp {
           None
         }
       }
-      (Self::LineStartChar, Nat(idx), Text(ys)) => {
+      (Self::LineStartChar, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_lines() {
           Some(Nat(ys.line_to_char(idx).into()))
@@ -340,7 +431,7 @@ impl TextOp {
           None
         }
       }
-      (Self::LineStartByte, Nat(idx), Text(ys)) => {
+      (Self::LineStartByte, [Nat(idx), Text(ys)]) => {
         let idx: usize = idx.clone().try_into().ok()?;
         if idx < ys.len_lines() {
           Some(Nat(ys.line_to_byte(idx).into()))
@@ -349,24 +440,49 @@ impl TextOp {
           None
         }
       }
+      (Self::Format, [Text(xs), Vector(args)]) => {
+        format(&xs.to_string(), args).map(|x| Text(Rope::from(x)))
+      }
+      (Self::Insert, [Nat(x), Text(y), Text(xs)]) => {
+        Some(Text(safe_insert(x, y.clone(), xs.clone())))
+      }
+      (Self::Remove, [Nat(x), Nat(y), Text(xs)]) => {
+        Some(Text(safe_remove(x, y, xs.clone())))
+      }
+      (Self::Replace, [Text(pat), Text(rep), Text(xs)]) => {
+        let (pat, rep) = (pat.to_string(), rep.to_string());
+        if pat.is_empty() {
+          None
+        }
+        else {
+          Some(Text(Rope::from(xs.to_string().replace(&pat, &rep))))
+        }
+      }
+      (Self::Slice, [Nat(from), Nat(upto), Text(xs)]) => {
+        Some(Text(safe_slice(from, upto, xs.clone())))
+      }
       _ => None,
     }
   }
 
-  /// Applies a ternary operation to a literal and returns it if successful
-  pub fn apply3(
-    self,
-    x: &Literal,
-    y: &Literal,
-    z: &Literal,
-  ) -> Option<Literal> {
+  /// Applies an operation whose result is a term rather than a literal to
+  /// as many literals as its arity and returns it if successful
+  pub fn apply_term(self, args: &[&Literal]) -> Option<Term> {
     use Literal::*;
-    match (self, x, y, z) {
-      (Self::Insert, Nat(x), Text(y), Text(xs)) => {
-        Some(Text(safe_insert(x, y.clone(), xs.clone())))
+    match (self, args) {
+      (Self::SplitOn, [Text(sep), Text(xs)]) => {
+        let (sep, xs) = (sep.to_string(), xs.to_string());
+        if sep.is_empty() {
+          None
+        }
+        else {
+          let parts = xs.split(sep.as_str()).map(|x| Text(Rope::from(x)));
+          Some(list_term(parts.collect()))
+        }
       }
-      (Self::Remove, Nat(x), Nat(y), Text(xs)) => {
-        Some(Text(safe_remove(x, y, xs.clone())))
+      (Self::Find, [Text(pat), Text(xs)]) => {
+        let idx = xs.to_string().find(&pat.to_string());
+        Some(option_term(idx.map(|idx| Nat(xs.byte_to_char(idx).into()))))
       }
       _ => None,
     }
@@ -407,6 +523,49 @@ pub fn safe_remove(from: &BigUint, upto: &BigUint, mut xs: Rope) -> Rope {
   }
 }
 
+/// Gets the text between two char indices, clamping both to the text's length
+pub fn safe_slice(from: &BigUint, upto: &BigUint, xs: Rope) -> Rope {
+  let (xs, _) = safe_split(upto, xs);
+  if from > upto { Rope::from_str("") } else { safe_split(from, xs).1 }
+}
+
+/// Fills the positional placeholders `{0}`, `{1}`, ... of a template with the
+/// UTF-8 text in `args`, where `{{` and `}}` stand for literal braces. Returns
+/// `None` if the template is malformed, an index is out of range or an
+/// argument isn't valid UTF-8
+pub fn format(template: &str, args: &Vector<Vec<u8>>) -> Option<String> {
+  let mut res = String::new();
+  let mut chars = template.chars();
+  while let Some(c) = chars.next() {
+    match c {
+      '{' => {
+        let mut idx = String::new();
+        loop {
+          match chars.next()? {
+            '{' if idx.is_empty() => {
+              res.push('{');
+              break;
+            }
+            '}' if !idx.is_empty() => {
+              let arg = args.get(idx.parse::<usize>().ok()?)?;
+              res.push_str(sp_std::str::from_utf8(arg).ok()?);
+              break;
+            }
+            d if d.is_ascii_digit() => idx.push(d),
+            _ => return None,
+          }
+        }
+      }
+      '}' => match chars.next()? {
+        '}' => res.push('}'),
+        _ => return None,
+      },
+      c => res.push(c),
+    }
+  }
+  Some(res)
+}
+
 /// Returns the first character if it exists
 pub fn safe_head(mut x: Rope) -> Option<(char, Rope)> {
   if x.len_chars() == 0 {
@@ -463,7 +622,7 @@ pub mod tests {
   impl Arbitrary for TextOp {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=23);
+      let gen: u32 = rng.gen_range(0..=33);
       match gen {
         0 => Self::Cons,
         1 => Self::LenChars,
@@ -488,7 +647,17 @@ pub mod tests {
         20 => Self::LineAtChar,
         21 => Self::LineStartChar,
         22 => Self::LineStartByte,
-        _ => Self::ToBytes,
+        23 => Self::ToBytes,
+        24 => Self::SplitOn,
+        25 => Self::Find,
+        26 => Self::Replace,
+        27 => Self::ToUpper,
+        28 => Self::ToLower,
+        29 => Self::Trim,
+        30 => Self::StartsWith,
+        31 => Self::EndsWith,
+        32 => Self::Slice,
+        _ => Self::Format,
       }
     }
   }
@@ -521,6 +690,38 @@ pub mod tests {
     assert_eq!(res, (Rope::from_str("foo"), Rope::from_str("")));
   }
 
+  #[test]
+  fn test_safe_slice() {
+    let rope: Rope = Rope::from_str("héllo");
+    let res = safe_slice(&1u64.into(), &3u64.into(), rope.clone());
+    assert_eq!(res, Rope::from_str("él"));
+    let res = safe_slice(&3u64.into(), &9u64.into(), rope.clone());
+    assert_eq!(res, Rope::from_str("lo"));
+    let res = safe_slice(&3u64.into(), &1u64.into(), rope.clone());
+    assert_eq!(res, Rope::from_str(""));
+  }
+
+  #[test]
+  fn test_apply2_term() {
+    let text = |x: &str| Text(Rope::from_str(x));
+    assert_eq!(
+      TextOp::apply_term(TextOp::SplitOn, &[&text(","), &text("a,,b")]),
+      Some(list_term(vec![text("a"), text(""), text("b")]))
+    );
+    assert_eq!(
+      TextOp::apply_term(TextOp::SplitOn, &[&text(""), &text("ab")]),
+      None
+    );
+    assert_eq!(
+      TextOp::apply_term(TextOp::Find, &[&text("lo"), &text("héllo")]),
+      Some(option_term(Some(Nat(3u64.into()))))
+    );
+    assert_eq!(
+      TextOp::apply_term(TextOp::Find, &[&text("x"), &text("héllo")]),
+      Some(option_term(None))
+    );
+  }
+
   #[quickcheck]
   fn test_apply(
     op: TextOp,
@@ -534,37 +735,54 @@ pub mod tests {
     let c = Rope::from(c);
     let big = BigUint::from;
     let apply1_text = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(TextOp::apply1(op, &Text(a.clone())) == expected)
+      TestResult::from_bool(TextOp::apply(op, &[&Text(a.clone())]) == expected)
     };
 
     let apply2_char_text = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        TextOp::apply2(op, &Char(b), &Text(a.clone())) == expected,
+        TextOp::apply(op, &[&Char(b), &Text(a.clone())]) == expected,
       )
     };
 
     let apply2_text_text = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        TextOp::apply2(op, &Text(a.clone()), &Text(c.clone())) == expected,
+        TextOp::apply(op, &[&Text(a.clone()), &Text(c.clone())]) == expected,
+      )
+    };
+
+    let apply2_term_text_text = |expected: Option<Term>| -> TestResult {
+      TestResult::from_bool(
+        TextOp::apply_term(op, &[&Text(c.clone()), &Text(a.clone())])
+          == expected,
       )
     };
 
     let apply2_nat_text = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        TextOp::apply2(op, &Nat(big(d)), &Text(a.clone())) == expected,
+        TextOp::apply(op, &[&Nat(big(d)), &Text(a.clone())]) == expected,
       )
     };
 
     let apply3_nat_text_text = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        TextOp::apply3(op, &Nat(big(d)), &Text(a.clone()), &Text(c.clone()))
+        TextOp::apply(op, &[&Nat(big(d)), &Text(a.clone()), &Text(c.clone())])
           == expected,
       )
     };
 
+    let apply3_text_text_text = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        TextOp::apply(op, &[
+          &Text(c.clone()),
+          &Text(a.clone()),
+          &Text(a.clone()),
+        ]) == expected,
+      )
+    };
+
     let apply3_nat_nat_text = |expected: Option<Literal>| -> TestResult {
       TestResult::from_bool(
-        TextOp::apply3(op, &Nat(big(d)), &Nat(big(e)), &Text(a.clone()))
+        TextOp::apply(op, &[&Nat(big(d)), &Nat(big(e)), &Text(a.clone())])
           == expected,
       )
     };
@@ -733,9 +951,74 @@ pub mod tests {
       TextOp::ToBytes => {
         apply1_text(Some(Bytes(a.bytes().collect::<Vec<u8>>())))
       }
+      TextOp::SplitOn => {
+        let (sep, xs) = (c.to_string(), a.to_string());
+        if sep.is_empty() {
+          apply2_term_text_text(None)
+        }
+        else {
+          let parts = xs.split(sep.as_str()).map(|x| Text(Rope::from(x)));
+          apply2_term_text_text(Some(list_term(parts.collect())))
+        }
+      }
+      TextOp::Find => {
+        let idx = a.to_string().find(&c.to_string());
+        let idx = idx.map(|idx| Nat(a.byte_to_char(idx).into()));
+        apply2_term_text_text(Some(option_term(idx)))
+      }
+      TextOp::Replace => {
+        let (pat, rep) = (c.to_string(), a.to_string());
+        if pat.is_empty() {
+          apply3_text_text_text(None)
+        }
+        else {
+          let res = rep.replace(&pat, &rep);
+          apply3_text_text_text(Some(Text(Rope::from(res))))
+        }
+      }
+      TextOp::ToUpper => {
+        apply1_text(Some(Text(Rope::from(a.to_string().to_uppercase()))))
+      }
+      TextOp::ToLower => {
+        apply1_text(Some(Text(Rope::from(a.to_string().to_lowercase()))))
+      }
+      TextOp::Trim => apply1_text(Some(Text(Rope::from(a.to_string().trim())))),
+      TextOp::StartsWith => {
+        apply2_text_text(Some(Bool(c.to_string().starts_with(&a.to_string()))))
+      }
+      TextOp::EndsWith => {
+        apply2_text_text(Some(Bool(c.to_string().ends_with(&a.to_string()))))
+      }
+      TextOp::Slice => {
+        apply3_nat_nat_text(Some(Text(safe_slice(&big(d), &big(e), a.clone()))))
+      }
+      TextOp::Format => {
+        let args = Vector::new();
+        let res = format(&a.to_string(), &args).map(|x| Text(Rope::from(x)));
+        TestResult::from_bool(
+          TextOp::apply(op, &[&Text(a.clone()), &Literal::Vector(args)]) == res,
+        )
+      }
     }
   }
 
+  #[test]
+  fn test_format() {
+    let args: Vector<Vec<u8>> =
+      vec![b"x".to_vec(), b"yz".to_vec()].into_iter().collect();
+    let fmt = |xs: &str| format(xs, &args);
+    assert_eq!(fmt("{1}-{0}-{1}"), Some("yz-x-yz".to_owned()));
+    assert_eq!(fmt("{{0}} {0}"), Some("{0} x".to_owned()));
+    assert_eq!(fmt("no args"), Some("no args".to_owned()));
+    assert_eq!(fmt("{2}"), None);
+    assert_eq!(fmt("{}"), None);
+    assert_eq!(fmt("{0"), None);
+    assert_eq!(fmt("}"), None);
+    assert_eq!(fmt("{a}"), None);
+    let bad: Vector<Vec<u8>> = vec![vec![0xff]].into_iter().collect();
+    assert_eq!(format("{0}", &bad), None);
+  }
+
   #[derive(Debug, Clone)]
   struct ArgsApplyNoneOnInvalid(
     TextOp,
@@ -777,13 +1060,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(TextOp::apply1(op, &a) == None)
+        TestResult::from_bool(TextOp::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(TextOp::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            TextOp::apply(op, &[&a_, &b_]) == None
+              && TextOp::apply_term(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -808,7 +1096,7 @@ pub mod tests {
                                        c_: Literal|
      -> TestResult {
       let go =
-        || TestResult::from_bool(TextOp::apply3(op, &a_, &b_, &c_) == None);
+        || TestResult::from_bool(TextOp::apply(op, &[&a_, &b_, &c_]) == None);
       match test_arg_3 {
         TestArg3::A => {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
@@ -842,7 +1130,10 @@ pub mod tests {
       TextOp::LenChars
       | TextOp::LenBytes
       | TextOp::LenLines
-      | TextOp::ToBytes => test_apply1_none_on_invalid(Text(b)),
+      | TextOp::ToBytes
+      | TextOp::ToUpper
+      | TextOp::ToLower
+      | TextOp::Trim => test_apply1_none_on_invalid(Text(b)),
       // Arity 2, valid are Char on a and Text on b.
       TextOp::Cons => {
         if test_arg_2 {
@@ -858,7 +1149,11 @@ pub mod tests {
       | TextOp::Lte
       | TextOp::Lth
       | TextOp::Gte
-      | TextOp::Gth => {
+      | TextOp::Gth
+      | TextOp::SplitOn
+      | TextOp::Find
+      | TextOp::StartsWith
+      | TextOp::EndsWith => {
         if test_arg_2 {
           test_apply2_none_on_invalid(Text(b.clone()), a, Text(b.clone()))
         }
@@ -885,6 +1180,16 @@ pub mod tests {
           test_apply2_none_on_invalid(Text(b.clone()), Nat(big(e)), a)
         }
       }
+      // Arity 2, valid are Text on a and Vector on b.
+      TextOp::Format => {
+        let args = Literal::Vector(Vector::new());
+        if test_arg_2 {
+          test_apply2_none_on_invalid(Text(b.clone()), a, args)
+        }
+        else {
+          test_apply2_none_on_invalid(args, Text(b.clone()), a)
+        }
+      }
       // Arity 3, valid are Nat on a, Text on b and Text on c.
       TextOp::Insert => match test_arg_3 {
         TestArg3::A => test_apply3_none_on_invalid(
@@ -906,8 +1211,29 @@ pub mod tests {
           a,
         ),
       },
+      // Arity 3, valid are Text on a, b and c.
+      TextOp::Replace => match test_arg_3 {
+        TestArg3::A => test_apply3_none_on_invalid(
+          Text(b.clone()),
+          a,
+          Text(b.clone()),
+          Text(d.clone()),
+        ),
+        TestArg3::B => test_apply3_none_on_invalid(
+          Text(b.clone()),
+          Text(b.clone()),
+          a,
+          Text(d.clone()),
+        ),
+        TestArg3::C => test_apply3_none_on_invalid(
+          Text(b.clone()),
+          Text(b.clone()),
+          Text(d.clone()),
+          a,
+        ),
+      },
       // Arity 3, valid are Nat on a, Nat on b and Text on c.
-      TextOp::Remove => match test_arg_3 {
+      TextOp::Remove | TextOp::Slice => match test_arg_3 {
         TestArg3::A => test_apply3_none_on_invalid(
           Nat(big(e)),
           a,
@@ -9,13 +9,17 @@ use sp_std::{
 use alloc::string::String;
 
 use crate::{
+  defs,
   ipld_error::IpldError,
   literal::Literal,
+  parse,
   prim::bits,
   term::Term,
   yatima,
 };
 
+use num_bigint::BigUint;
+
 /// Primitive 128-bit unsigned integer operations
 #[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub enum U128Op {
@@ -53,8 +57,22 @@ pub enum U128Op {
   ToI64,
   ToI128,
   ToInt,
-  ToBytes,
   ToBits,
+  ToBytes,
+  LeadingZeros,
+  TrailingZeros,
+  ReverseBits,
+  SwapBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl U128Op {
@@ -97,6 +115,20 @@ impl U128Op {
       Self::ToInt => "to_Int".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::LeadingZeros => "leading_zeros".to_owned(),
+      Self::TrailingZeros => "trailing_zeros".to_owned(),
+      Self::ReverseBits => "reverse_bits".to_owned(),
+      Self::SwapBytes => "swap_bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -139,6 +171,20 @@ impl U128Op {
       "to_Int" => Some(Self::ToInt),
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
+      "leading_zeros" => Some(Self::LeadingZeros),
+      "trailing_zeros" => Some(Self::TrailingZeros),
+      "reverse_bits" => Some(Self::ReverseBits),
+      "swap_bytes" => Some(Self::SwapBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -182,6 +228,22 @@ impl U128Op {
       Self::ToInt => yatima!("∀ #U128 -> #Int"),
       Self::ToBits => yatima!("∀ #U128 -> #Bits"),
       Self::ToBytes => yatima!("∀ #U128 -> #Bytes"),
+      Self::LeadingZeros => yatima!("∀ #U128 -> #U32"),
+      Self::TrailingZeros => yatima!("∀ #U128 -> #U32"),
+      Self::ReverseBits => yatima!("∀ #U128 -> #U128"),
+      Self::SwapBytes => yatima!("∀ #U128 -> #U128"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #U128 #U128 (0 P: Type) (none: P) (some: ∀ #U128 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #U128 #U128 -> #U128"),
+      Self::WrappingSub => yatima!("∀ #U128 #U128 -> #U128"),
+      Self::WrappingMul => yatima!("∀ #U128 #U128 -> #U128"),
+      Self::SaturatingAdd => yatima!("∀ #U128 #U128 -> #U128"),
+      Self::SaturatingSub => yatima!("∀ #U128 #U128 -> #U128"),
+      Self::SaturatingMul => yatima!("∀ #U128 #U128 -> #U128"),
     }
   }
 
@@ -224,6 +286,20 @@ impl U128Op {
       Self::ToInt => Ipld::Integer(33),
       Self::ToBits => Ipld::Integer(34),
       Self::ToBytes => Ipld::Integer(35),
+      Self::LeadingZeros => Ipld::Integer(36),
+      Self::TrailingZeros => Ipld::Integer(37),
+      Self::ReverseBits => Ipld::Integer(38),
+      Self::SwapBytes => Ipld::Integer(39),
+      Self::CheckedAdd => Ipld::Integer(40),
+      Self::CheckedSub => Ipld::Integer(41),
+      Self::CheckedMul => Ipld::Integer(42),
+      Self::CheckedDiv => Ipld::Integer(43),
+      Self::WrappingAdd => Ipld::Integer(44),
+      Self::WrappingSub => Ipld::Integer(45),
+      Self::WrappingMul => Ipld::Integer(46),
+      Self::SaturatingAdd => Ipld::Integer(47),
+      Self::SaturatingSub => Ipld::Integer(48),
+      Self::SaturatingMul => Ipld::Integer(49),
     }
   }
 
@@ -266,6 +342,20 @@ impl U128Op {
       Ipld::Integer(33) => Ok(Self::ToInt),
       Ipld::Integer(34) => Ok(Self::ToBits),
       Ipld::Integer(35) => Ok(Self::ToBytes),
+      Ipld::Integer(36) => Ok(Self::LeadingZeros),
+      Ipld::Integer(37) => Ok(Self::TrailingZeros),
+      Ipld::Integer(38) => Ok(Self::ReverseBits),
+      Ipld::Integer(39) => Ok(Self::SwapBytes),
+      Ipld::Integer(40) => Ok(Self::CheckedAdd),
+      Ipld::Integer(41) => Ok(Self::CheckedSub),
+      Ipld::Integer(42) => Ok(Self::CheckedMul),
+      Ipld::Integer(43) => Ok(Self::CheckedDiv),
+      Ipld::Integer(44) => Ok(Self::WrappingAdd),
+      Ipld::Integer(45) => Ok(Self::WrappingSub),
+      Ipld::Integer(46) => Ok(Self::WrappingMul),
+      Ipld::Integer(47) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(48) => Ok(Self::SaturatingSub),
+      Ipld::Integer(49) => Ok(Self::SaturatingMul),
       xs => Err(IpldError::NatOp(xs.to_owned())),
     }
   }
@@ -307,68 +397,119 @@ impl U128Op {
       Self::ToI64 => 1,
       Self::ToI128 => 1,
       Self::ToInt => 1,
-      Self::ToBytes => 1,
       Self::ToBits => 1,
+      Self::ToBytes => 1,
+      Self::LeadingZeros => 1,
+      Self::TrailingZeros => 1,
+      Self::ReverseBits => 1,
+      Self::SwapBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
-    use Literal::*;
-    match self {
-      Self::Max => Some(U64(u64::MAX)),
-      Self::Min => Some(U64(u64::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::CountZeros, U64(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, U64(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, U64(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU16, U64(x)) => u16::try_from(*x).ok().map(U16),
-      (Self::ToU32, U64(x)) => u32::try_from(*x).ok().map(U32),
-      (Self::ToU64, U64(x)) => u64::try_from(*x).ok().map(U64),
-      (Self::ToI8, U64(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI16, U64(x)) => i16::try_from(*x).ok().map(I16),
-      (Self::ToI32, U64(x)) => i32::try_from(*x).ok().map(I32),
-      (Self::ToI64, U64(x)) => i64::try_from(*x).ok().map(I64),
-      (Self::ToI128, U64(x)) => Some(I128((*x).into())),
-      (Self::Not, U64(x)) => Some(U64(!x)),
-      (Self::ToInt, U64(x)) => Some(Int((*x).into())),
-      (Self::ToBytes, U64(x)) => Some(Bytes(x.to_be_bytes().into())),
-      (Self::ToBits, U128(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(U128(u128::MAX)),
+      (Self::Min, []) => Some(U128(u128::MIN)),
+      (Self::CountZeros, [U128(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [U128(x)]) => Some(U32(x.count_ones())),
+      (Self::LeadingZeros, [U128(x)]) => Some(U32(x.leading_zeros())),
+      (Self::TrailingZeros, [U128(x)]) => Some(U32(x.trailing_zeros())),
+      (Self::ReverseBits, [U128(x)]) => Some(U128(x.reverse_bits())),
+      (Self::SwapBytes, [U128(x)]) => Some(U128(x.swap_bytes())),
+      (Self::ToU8, [U128(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU16, [U128(x)]) => u16::try_from(*x).ok().map(U16),
+      (Self::ToU32, [U128(x)]) => u32::try_from(*x).ok().map(U32),
+      (Self::ToU64, [U128(x)]) => u64::try_from(*x).ok().map(U64),
+      (Self::ToNat, [U128(x)]) => Some(Nat(BigUint::from(*x))),
+      (Self::ToI8, [U128(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI16, [U128(x)]) => i16::try_from(*x).ok().map(I16),
+      (Self::ToI32, [U128(x)]) => i32::try_from(*x).ok().map(I32),
+      (Self::ToI64, [U128(x)]) => i64::try_from(*x).ok().map(I64),
+      (Self::ToI128, [U128(x)]) => i128::try_from(*x).ok().map(I128),
+      (Self::Not, [U128(x)]) => Some(U128(!x)),
+      (Self::ToInt, [U128(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [U128(x)]) => {
         Some(Bits(bits::bytes_to_bits(128, &x.to_be_bytes().into())))
       }
+      (Self::ToBytes, [U128(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [U128(x), U128(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [U128(x), U128(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [U128(x), U128(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [U128(x), U128(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [U128(x), U128(y)]) => Some(Bool(x >= y)),
+      (Self::And, [U128(x), U128(y)]) => Some(U128(x & y)),
+      (Self::Or, [U128(x), U128(y)]) => Some(U128(x | y)),
+      (Self::Xor, [U128(x), U128(y)]) => Some(U128(x ^ y)),
+      (Self::Add, [U128(x), U128(y)]) => Some(U128(x.wrapping_add(*y))),
+      (Self::Sub, [U128(x), U128(y)]) => Some(U128(x.wrapping_sub(*y))),
+      (Self::Mul, [U128(x), U128(y)]) => Some(U128(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [U128(x), U128(y)]) => Some(U128(x.wrapping_add(*y))),
+      (Self::WrappingSub, [U128(x), U128(y)]) => Some(U128(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [U128(x), U128(y)]) => Some(U128(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [U128(x), U128(y)]) => {
+        Some(U128(x.saturating_add(*y)))
+      }
+      (Self::SaturatingSub, [U128(x), U128(y)]) => {
+        Some(U128(x.saturating_sub(*y)))
+      }
+      (Self::SaturatingMul, [U128(x), U128(y)]) => {
+        Some(U128(x.saturating_mul(*y)))
+      }
+      (Self::Div, [U128(x), U128(y)]) => {
+        if *y == 0 {
+          None
+        }
+        else {
+          Some(U128(x.wrapping_div(*y)))
+        }
+      }
+      (Self::Mod, [U128(x), U128(y)]) => {
+        if *y == 0 {
+          None
+        }
+        else {
+          Some(U128(x.wrapping_rem(*y)))
+        }
+      }
+      (Self::Pow, [U128(x), U32(y)]) => Some(U128(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), U128(y)]) => Some(U128(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), U128(y)]) => Some(U128(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), U128(y)]) => Some(U128(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), U128(y)]) => Some(U128(y.rotate_right(*x))),
       _ => None,
     }
   }
 
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
     use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, U32(x), U32(y)) => Some(Bool(x == y)),
-      (Self::Lte, U128(x), U128(y)) => Some(Bool(x <= y)),
-      (Self::Lth, U128(x), U128(y)) => Some(Bool(x < y)),
-      (Self::Gth, U128(x), U128(y)) => Some(Bool(x > y)),
-      (Self::Gte, U128(x), U128(y)) => Some(Bool(x >= y)),
-      (Self::And, U128(x), U128(y)) => Some(U128(x & y)),
-      (Self::Or, U128(x), U128(y)) => Some(U128(x | y)),
-      (Self::Xor, U128(x), U128(y)) => Some(U128(x ^ y)),
-      (Self::Add, U128(x), U128(y)) => Some(U128(x.wrapping_add(*y))),
-      (Self::Sub, U128(x), U128(y)) => Some(U128(x.wrapping_sub(*y))),
-      (Self::Mul, U128(x), U128(y)) => Some(U128(x.wrapping_mul(*y))),
-      (Self::Div, U128(x), U128(y)) => Some(U128(x.wrapping_div(*y))),
-      (Self::Mod, U128(x), U128(y)) => Some(U128(x.wrapping_rem(*y))),
-      (Self::Pow, U128(x), U32(y)) => Some(U128(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), U128(y)) => Some(U128(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), U128(y)) => Some(U128(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), U128(y)) => Some(U128(y.rotate_left(*x))),
-      (Self::Ror, U32(x), U128(y)) => Some(U128(y.rotate_right(*x))),
+    match (self, args) {
+      (Self::CheckedAdd, [U128(x), U128(y)]) => {
+        Some(x.checked_add(*y).map(U128))
+      }
+      (Self::CheckedSub, [U128(x), U128(y)]) => {
+        Some(x.checked_sub(*y).map(U128))
+      }
+      (Self::CheckedMul, [U128(x), U128(y)]) => {
+        Some(x.checked_mul(*y).map(U128))
+      }
+      (Self::CheckedDiv, [U128(x), U128(y)]) => {
+        Some(x.checked_div(*y).map(U128))
+      }
       _ => None,
     }
   }
@@ -383,15 +524,41 @@ impl fmt::Display for U128Op {
 #[cfg(test)]
 pub mod tests {
   use super::*;
+  use crate::prim::{
+    I128Op,
+    I16Op,
+    I32Op,
+    I64Op,
+    I8Op,
+    U16Op,
+    U32Op,
+    U64Op,
+    U8Op,
+  };
+  use num_bigint::BigUint;
   use quickcheck::{
     Arbitrary,
     Gen,
+    TestResult,
   };
   use rand::Rng;
+  use sp_std::{
+    convert::TryInto,
+    mem,
+  };
+  use Literal::{
+    Bits,
+    Bool,
+    Bytes,
+    Int,
+    Nat,
+    U128,
+    U32,
+  };
   impl Arbitrary for U128Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..35);
+      let gen: u32 = rng.gen_range(0..=49);
       match gen {
         0 => Self::Max,
         1 => Self::Min,
@@ -428,7 +595,21 @@ pub mod tests {
         32 => Self::ToI128,
         33 => Self::ToInt,
         34 => Self::ToBytes,
-        _ => Self::ToBits,
+        35 => Self::ToBits,
+        36 => Self::LeadingZeros,
+        37 => Self::TrailingZeros,
+        38 => Self::ReverseBits,
+        39 => Self::SwapBytes,
+        40 => Self::CheckedAdd,
+        41 => Self::CheckedSub,
+        42 => Self::CheckedMul,
+        43 => Self::CheckedDiv,
+        44 => Self::WrappingAdd,
+        45 => Self::WrappingSub,
+        46 => Self::WrappingMul,
+        47 => Self::SaturatingAdd,
+        48 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
       }
     }
   }
@@ -440,4 +621,283 @@ pub mod tests {
       _ => false,
     }
   }
+
+  #[quickcheck]
+  fn test_apply(op: U128Op, a: u128, b: u128, c: u32) -> TestResult {
+    let apply0_go = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(U128Op::apply(op, &[]) == expected)
+    };
+
+    let apply1_u128 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(U128Op::apply(op, &[&U128(a)]) == expected)
+    };
+
+    let apply2_u128_u128 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        U128Op::apply(op, &[&U128(a), &U128(b)]) == expected,
+      )
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        U128Op::apply_checked(op, &[&U128(a), &U128(b)]) == Some(expected),
+      )
+    };
+
+    let apply2_u128_u32 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(U128Op::apply(op, &[&U128(a), &U32(c)]) == expected)
+    };
+
+    let apply2_u32_u128 = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(U128Op::apply(op, &[&U32(c), &U128(a)]) == expected)
+    };
+
+    let from_bool = TestResult::from_bool;
+
+    match op {
+      U128Op::Max => apply0_go(Some(U128(u128::MAX))),
+      U128Op::Min => apply0_go(Some(U128(u128::MIN))),
+      U128Op::Eql => apply2_u128_u128(Some(Bool(a == b))),
+      U128Op::Lte => apply2_u128_u128(Some(Bool(a <= b))),
+      U128Op::Lth => apply2_u128_u128(Some(Bool(a < b))),
+      U128Op::Gth => apply2_u128_u128(Some(Bool(a > b))),
+      U128Op::Gte => apply2_u128_u128(Some(Bool(a >= b))),
+      U128Op::Not => apply1_u128(Some(U128(!a))),
+      U128Op::And => apply2_u128_u128(Some(U128(a & b))),
+      U128Op::Or => apply2_u128_u128(Some(U128(a | b))),
+      U128Op::Xor => apply2_u128_u128(Some(U128(a ^ b))),
+      U128Op::Add => apply2_u128_u128(Some(U128(a.wrapping_add(b)))),
+      U128Op::Sub => apply2_u128_u128(Some(U128(a.wrapping_sub(b)))),
+      U128Op::Mul => apply2_u128_u128(Some(U128(a.wrapping_mul(b)))),
+      U128Op::Div => apply2_u128_u128(if b == 0 {
+        None
+      }
+      else {
+        Some(U128(a.wrapping_div(b)))
+      }),
+      U128Op::Mod => apply2_u128_u128(if b == 0 {
+        None
+      }
+      else {
+        Some(U128(a.wrapping_rem(b)))
+      }),
+      U128Op::WrappingAdd => apply2_u128_u128(Some(U128(a.wrapping_add(b)))),
+      U128Op::WrappingSub => apply2_u128_u128(Some(U128(a.wrapping_sub(b)))),
+      U128Op::WrappingMul => apply2_u128_u128(Some(U128(a.wrapping_mul(b)))),
+      U128Op::SaturatingAdd => {
+        apply2_u128_u128(Some(U128(a.saturating_add(b))))
+      }
+      U128Op::SaturatingSub => {
+        apply2_u128_u128(Some(U128(a.saturating_sub(b))))
+      }
+      U128Op::SaturatingMul => {
+        apply2_u128_u128(Some(U128(a.saturating_mul(b))))
+      }
+      U128Op::CheckedAdd => apply2_checked(a.checked_add(b).map(U128)),
+      U128Op::CheckedSub => apply2_checked(a.checked_sub(b).map(U128)),
+      U128Op::CheckedMul => apply2_checked(a.checked_mul(b).map(U128)),
+      U128Op::CheckedDiv => apply2_checked(a.checked_div(b).map(U128)),
+      U128Op::Pow => apply2_u128_u32(Some(U128(a.wrapping_pow(c)))),
+      U128Op::Shl => apply2_u32_u128(Some(U128(a.wrapping_shl(c)))),
+      U128Op::Shr => apply2_u32_u128(Some(U128(a.wrapping_shr(c)))),
+      U128Op::Rol => apply2_u32_u128(Some(U128(a.rotate_left(c)))),
+      U128Op::Ror => apply2_u32_u128(Some(U128(a.rotate_right(c)))),
+      U128Op::CountZeros => apply1_u128(Some(U32(a.count_zeros()))),
+      U128Op::CountOnes => apply1_u128(Some(U32(a.count_ones()))),
+      U128Op::LeadingZeros => apply1_u128(Some(U32(a.leading_zeros()))),
+      U128Op::TrailingZeros => apply1_u128(Some(U32(a.trailing_zeros()))),
+      U128Op::ReverseBits => apply1_u128(Some(U128(a.reverse_bits()))),
+      U128Op::SwapBytes => apply1_u128(Some(U128(a.swap_bytes()))),
+      U128Op::ToU8 => from_bool(if a > u8::MAX.into() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        U8Op::apply(U8Op::ToU128, &[&U128Op::apply(op, &[&U128(a)]).unwrap()])
+          == Some(U128(a))
+      }),
+      U128Op::ToU16 => from_bool(if a > u16::MAX.into() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        U16Op::apply(U16Op::ToU128, &[&U128Op::apply(op, &[&U128(a)]).unwrap()])
+          == Some(U128(a))
+      }),
+      U128Op::ToU32 => from_bool(if a > u32::MAX.into() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        U32Op::apply(U32Op::ToU128, &[&U128Op::apply(op, &[&U128(a)]).unwrap()])
+          == Some(U128(a))
+      }),
+      U128Op::ToU64 => from_bool(if a > u64::MAX.into() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        U64Op::apply(U64Op::ToU128, &[&U128Op::apply(op, &[&U128(a)]).unwrap()])
+          == Some(U128(a))
+      }),
+      U128Op::ToNat => apply1_u128(Some(Nat(BigUint::from(a)))),
+      U128Op::ToI8 => from_bool(if a > i8::MAX.try_into().unwrap() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        I8Op::apply(I8Op::ToU128, &[&U128Op::apply(op, &[&U128(a)]).unwrap()])
+          == Some(U128(a))
+      }),
+      U128Op::ToI16 => from_bool(if a > i16::MAX.try_into().unwrap() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        I16Op::apply(I16Op::ToU128, &[&U128Op::apply(op, &[&U128(a)]).unwrap()])
+          == Some(U128(a))
+      }),
+      U128Op::ToI32 => from_bool(if a > i32::MAX.try_into().unwrap() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        I32Op::apply(I32Op::ToU128, &[&U128Op::apply(op, &[&U128(a)]).unwrap()])
+          == Some(U128(a))
+      }),
+      U128Op::ToI64 => from_bool(if a > i64::MAX.try_into().unwrap() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        I64Op::apply(I64Op::ToU128, &[&U128Op::apply(op, &[&U128(a)]).unwrap()])
+          == Some(U128(a))
+      }),
+      U128Op::ToI128 => from_bool(if a > i128::MAX.try_into().unwrap() {
+        U128Op::apply(op, &[&U128(a)]) == None
+      }
+      else {
+        I128Op::apply(I128Op::ToU128, &[
+          &U128Op::apply(op, &[&U128(a)]).unwrap()
+        ]) == Some(U128(a))
+      }),
+      U128Op::ToInt => apply1_u128(Some(Int(a.into()))),
+      U128Op::ToBits => apply1_u128(Some(Bits(bits::bytes_to_bits(
+        128,
+        &a.to_be_bytes().into(),
+      )))),
+      U128Op::ToBytes => apply1_u128(Some(Bytes(a.to_be_bytes().into()))),
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(
+    op: U128Op,
+    a: Literal,
+    b: u128,
+    c: u32,
+    test_arg_2: bool,
+  ) -> TestResult {
+    let test_apply1_none_on_invalid = |valid_arg: Literal| -> TestResult {
+      if mem::discriminant(&valid_arg) == mem::discriminant(&a) {
+        TestResult::discard()
+      }
+      else {
+        TestResult::from_bool(U128Op::apply(op, &[&a]) == None)
+      }
+    };
+
+    let test_apply2_none_on_invalid =
+      |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
+        let go = || {
+          TestResult::from_bool(
+            U128Op::apply(op, &[&a_, &b_]) == None
+              && U128Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
+        if test_arg_2 {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        else {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      };
+
+    match op {
+      // Arity 0.
+      U128Op::Max | U128Op::Min => TestResult::discard(),
+      // Arity 1, valid is U128.
+      U128Op::Not
+      | U128Op::CountZeros
+      | U128Op::CountOnes
+      | U128Op::LeadingZeros
+      | U128Op::TrailingZeros
+      | U128Op::ReverseBits
+      | U128Op::SwapBytes
+      | U128Op::ToU8
+      | U128Op::ToU16
+      | U128Op::ToU32
+      | U128Op::ToU64
+      | U128Op::ToNat
+      | U128Op::ToI8
+      | U128Op::ToI16
+      | U128Op::ToI32
+      | U128Op::ToI64
+      | U128Op::ToI128
+      | U128Op::ToInt
+      | U128Op::ToBytes
+      | U128Op::ToBits => test_apply1_none_on_invalid(U128(b)),
+      // Arity 2, valid are U128 on a and b.
+      U128Op::Eql
+      | U128Op::Lte
+      | U128Op::Lth
+      | U128Op::Gth
+      | U128Op::Gte
+      | U128Op::And
+      | U128Op::Or
+      | U128Op::Xor
+      | U128Op::Add
+      | U128Op::Sub
+      | U128Op::Mul
+      | U128Op::Div
+      | U128Op::Mod
+      | U128Op::CheckedAdd
+      | U128Op::CheckedSub
+      | U128Op::CheckedMul
+      | U128Op::CheckedDiv
+      | U128Op::WrappingAdd
+      | U128Op::WrappingSub
+      | U128Op::WrappingMul
+      | U128Op::SaturatingAdd
+      | U128Op::SaturatingSub
+      | U128Op::SaturatingMul => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(U128(b), a, U128(b))
+        }
+        else {
+          test_apply2_none_on_invalid(U128(b), U128(b), a)
+        }
+      }
+      // Arity 2, valid are U128 on a and U32 on b.
+      U128Op::Pow => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(U128(b), a, U32(c))
+        }
+        else {
+          test_apply2_none_on_invalid(U32(c), U128(b), a)
+        }
+      }
+      // Arity 2, valid are U32 on a and U128 on b.
+      U128Op::Shl | U128Op::Shr | U128Op::Rol | U128Op::Ror => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(U32(c), a, U128(b))
+        }
+        else {
+          test_apply2_none_on_invalid(U128(b), U32(c), a)
+        }
+      }
+    }
+  }
 }
@@ -59,6 +59,20 @@ pub enum U16Op {
   ToInt,
   ToBits,
   ToBytes,
+  LeadingZeros,
+  TrailingZeros,
+  ReverseBits,
+  SwapBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl U16Op {
@@ -101,6 +115,20 @@ impl U16Op {
       Self::ToInt => "to_Int".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::LeadingZeros => "leading_zeros".to_owned(),
+      Self::TrailingZeros => "trailing_zeros".to_owned(),
+      Self::ReverseBits => "reverse_bits".to_owned(),
+      Self::SwapBytes => "swap_bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -143,6 +171,20 @@ impl U16Op {
       "to_Int" => Some(Self::ToInt),
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
+      "leading_zeros" => Some(Self::LeadingZeros),
+      "trailing_zeros" => Some(Self::TrailingZeros),
+      "reverse_bits" => Some(Self::ReverseBits),
+      "swap_bytes" => Some(Self::SwapBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -186,6 +228,22 @@ impl U16Op {
       Self::ToInt => yatima!("∀ #U16 -> #Int"),
       Self::ToBits => yatima!("∀ #U8 -> #Bits"),
       Self::ToBytes => yatima!("∀ #U16 -> #Bytes"),
+      Self::LeadingZeros => yatima!("∀ #U16 -> #U32"),
+      Self::TrailingZeros => yatima!("∀ #U16 -> #U32"),
+      Self::ReverseBits => yatima!("∀ #U16 -> #U16"),
+      Self::SwapBytes => yatima!("∀ #U16 -> #U16"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #U16 #U16 (0 P: Type) (none: P) (some: ∀ #U16 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #U16 #U16 -> #U16"),
+      Self::WrappingSub => yatima!("∀ #U16 #U16 -> #U16"),
+      Self::WrappingMul => yatima!("∀ #U16 #U16 -> #U16"),
+      Self::SaturatingAdd => yatima!("∀ #U16 #U16 -> #U16"),
+      Self::SaturatingSub => yatima!("∀ #U16 #U16 -> #U16"),
+      Self::SaturatingMul => yatima!("∀ #U16 #U16 -> #U16"),
     }
   }
 
@@ -228,6 +286,20 @@ impl U16Op {
       Self::ToInt => Ipld::Integer(33),
       Self::ToBits => Ipld::Integer(34),
       Self::ToBytes => Ipld::Integer(35),
+      Self::LeadingZeros => Ipld::Integer(36),
+      Self::TrailingZeros => Ipld::Integer(37),
+      Self::ReverseBits => Ipld::Integer(38),
+      Self::SwapBytes => Ipld::Integer(39),
+      Self::CheckedAdd => Ipld::Integer(40),
+      Self::CheckedSub => Ipld::Integer(41),
+      Self::CheckedMul => Ipld::Integer(42),
+      Self::CheckedDiv => Ipld::Integer(43),
+      Self::WrappingAdd => Ipld::Integer(44),
+      Self::WrappingSub => Ipld::Integer(45),
+      Self::WrappingMul => Ipld::Integer(46),
+      Self::SaturatingAdd => Ipld::Integer(47),
+      Self::SaturatingSub => Ipld::Integer(48),
+      Self::SaturatingMul => Ipld::Integer(49),
     }
   }
 
@@ -270,6 +342,20 @@ impl U16Op {
       Ipld::Integer(33) => Ok(Self::ToInt),
       Ipld::Integer(34) => Ok(Self::ToBits),
       Ipld::Integer(35) => Ok(Self::ToBytes),
+      Ipld::Integer(36) => Ok(Self::LeadingZeros),
+      Ipld::Integer(37) => Ok(Self::TrailingZeros),
+      Ipld::Integer(38) => Ok(Self::ReverseBits),
+      Ipld::Integer(39) => Ok(Self::SwapBytes),
+      Ipld::Integer(40) => Ok(Self::CheckedAdd),
+      Ipld::Integer(41) => Ok(Self::CheckedSub),
+      Ipld::Integer(42) => Ok(Self::CheckedMul),
+      Ipld::Integer(43) => Ok(Self::CheckedDiv),
+      Ipld::Integer(44) => Ok(Self::WrappingAdd),
+      Ipld::Integer(45) => Ok(Self::WrappingSub),
+      Ipld::Integer(46) => Ok(Self::WrappingMul),
+      Ipld::Integer(47) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(48) => Ok(Self::SaturatingSub),
+      Ipld::Integer(49) => Ok(Self::SaturatingMul),
       xs => Err(IpldError::NatOp(xs.to_owned())),
     }
   }
@@ -313,63 +399,78 @@ impl U16Op {
       Self::ToInt => 1,
       Self::ToBits => 1,
       Self::ToBytes => 1,
+      Self::LeadingZeros => 1,
+      Self::TrailingZeros => 1,
+      Self::ReverseBits => 1,
+      Self::SwapBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match self {
-      Self::Max => Some(U16(u16::MAX)),
-      Self::Min => Some(U16(u16::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x) {
-      (Self::CountZeros, U16(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, U16(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, U16(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU32, U16(x)) => Some(U32((*x).into())),
-      (Self::ToU64, U16(x)) => Some(U64((*x).into())),
-      (Self::ToU128, U16(x)) => Some(U128((*x).into())),
-      (Self::ToNat, U16(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(U16(u16::MAX)),
+      (Self::Min, []) => Some(U16(u16::MIN)),
+      (Self::CountZeros, [U16(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [U16(x)]) => Some(U32(x.count_ones())),
+      (Self::LeadingZeros, [U16(x)]) => Some(U32(x.leading_zeros())),
+      (Self::TrailingZeros, [U16(x)]) => Some(U32(x.trailing_zeros())),
+      (Self::ReverseBits, [U16(x)]) => Some(U16(x.reverse_bits())),
+      (Self::SwapBytes, [U16(x)]) => Some(U16(x.swap_bytes())),
+      (Self::ToU8, [U16(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU32, [U16(x)]) => Some(U32((*x).into())),
+      (Self::ToU64, [U16(x)]) => Some(U64((*x).into())),
+      (Self::ToU128, [U16(x)]) => Some(U128((*x).into())),
+      (Self::ToNat, [U16(x)]) => {
         Some(Nat(BigUint::from(u64::try_from(*x).unwrap())))
       }
-      (Self::ToI8, U16(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI16, U16(x)) => i16::try_from(*x).ok().map(I16),
-      (Self::ToI32, U16(x)) => Some(I32((*x).into())),
-      (Self::ToI64, U16(x)) => Some(I64((*x).into())),
-      (Self::ToI128, U16(x)) => Some(I128((*x).into())),
-      (Self::Not, U16(x)) => Some(U16(!x)),
-      (Self::ToInt, U16(x)) => Some(Int((*x).into())),
-      (Self::ToBits, U16(x)) => {
+      (Self::ToI8, [U16(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI16, [U16(x)]) => i16::try_from(*x).ok().map(I16),
+      (Self::ToI32, [U16(x)]) => Some(I32((*x).into())),
+      (Self::ToI64, [U16(x)]) => Some(I64((*x).into())),
+      (Self::ToI128, [U16(x)]) => Some(I128((*x).into())),
+      (Self::Not, [U16(x)]) => Some(U16(!x)),
+      (Self::ToInt, [U16(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [U16(x)]) => {
         Some(Bits(bits::bytes_to_bits(16, &x.to_be_bytes().into())))
       }
-      (Self::ToBytes, U16(x)) => Some(Bytes(x.to_be_bytes().into())),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, U16(x), U16(y)) => Some(Bool(x == y)),
-      (Self::Lte, U16(x), U16(y)) => Some(Bool(x <= y)),
-      (Self::Lth, U16(x), U16(y)) => Some(Bool(x < y)),
-      (Self::Gth, U16(x), U16(y)) => Some(Bool(x > y)),
-      (Self::Gte, U16(x), U16(y)) => Some(Bool(x >= y)),
-      (Self::And, U16(x), U16(y)) => Some(U16(x & y)),
-      (Self::Or, U16(x), U16(y)) => Some(U16(x | y)),
-      (Self::Xor, U16(x), U16(y)) => Some(U16(x ^ y)),
-      (Self::Add, U16(x), U16(y)) => Some(U16(x.wrapping_add(*y))),
-      (Self::Sub, U16(x), U16(y)) => Some(U16(x.wrapping_sub(*y))),
-      (Self::Mul, U16(x), U16(y)) => Some(U16(x.wrapping_mul(*y))),
-      (Self::Div, U16(x), U16(y)) => {
+      (Self::ToBytes, [U16(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [U16(x), U16(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [U16(x), U16(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [U16(x), U16(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [U16(x), U16(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [U16(x), U16(y)]) => Some(Bool(x >= y)),
+      (Self::And, [U16(x), U16(y)]) => Some(U16(x & y)),
+      (Self::Or, [U16(x), U16(y)]) => Some(U16(x | y)),
+      (Self::Xor, [U16(x), U16(y)]) => Some(U16(x ^ y)),
+      (Self::Add, [U16(x), U16(y)]) => Some(U16(x.wrapping_add(*y))),
+      (Self::Sub, [U16(x), U16(y)]) => Some(U16(x.wrapping_sub(*y))),
+      (Self::Mul, [U16(x), U16(y)]) => Some(U16(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [U16(x), U16(y)]) => Some(U16(x.wrapping_add(*y))),
+      (Self::WrappingSub, [U16(x), U16(y)]) => Some(U16(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [U16(x), U16(y)]) => Some(U16(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [U16(x), U16(y)]) => {
+        Some(U16(x.saturating_add(*y)))
+      }
+      (Self::SaturatingSub, [U16(x), U16(y)]) => {
+        Some(U16(x.saturating_sub(*y)))
+      }
+      (Self::SaturatingMul, [U16(x), U16(y)]) => {
+        Some(U16(x.saturating_mul(*y)))
+      }
+      (Self::Div, [U16(x), U16(y)]) => {
         if *y == 0 {
           None
         }
@@ -377,7 +478,7 @@ impl U16Op {
           Some(U16(x.wrapping_div(*y)))
         }
       }
-      (Self::Mod, U16(x), U16(y)) => {
+      (Self::Mod, [U16(x), U16(y)]) => {
         if *y == 0 {
           None
         }
@@ -385,11 +486,24 @@ impl U16Op {
           Some(U16(x.wrapping_rem(*y)))
         }
       }
-      (Self::Pow, U16(x), U32(y)) => Some(U16(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), U16(y)) => Some(U16(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), U16(y)) => Some(U16(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), U16(y)) => Some(U16(y.rotate_left(*x))),
-      (Self::Ror, U32(x), U16(y)) => Some(U16(y.rotate_right(*x))),
+      (Self::Pow, [U16(x), U32(y)]) => Some(U16(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), U16(y)]) => Some(U16(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), U16(y)]) => Some(U16(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), U16(y)]) => Some(U16(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), U16(y)]) => Some(U16(y.rotate_right(*x))),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    match (self, args) {
+      (Self::CheckedAdd, [U16(x), U16(y)]) => Some(x.checked_add(*y).map(U16)),
+      (Self::CheckedSub, [U16(x), U16(y)]) => Some(x.checked_sub(*y).map(U16)),
+      (Self::CheckedMul, [U16(x), U16(y)]) => Some(x.checked_mul(*y).map(U16)),
+      (Self::CheckedDiv, [U16(x), U16(y)]) => Some(x.checked_div(*y).map(U16)),
       _ => None,
     }
   }
@@ -436,7 +550,7 @@ pub mod tests {
   impl Arbitrary for U16Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=33);
+      let gen: u32 = rng.gen_range(0..=47);
       match gen {
         0 => Self::Max,
         1 => Self::Min,
@@ -471,7 +585,21 @@ pub mod tests {
         30 => Self::ToI64,
         31 => Self::ToInt,
         32 => Self::ToBytes,
-        _ => Self::ToBits,
+        33 => Self::ToBits,
+        34 => Self::LeadingZeros,
+        35 => Self::TrailingZeros,
+        36 => Self::ReverseBits,
+        37 => Self::SwapBytes,
+        38 => Self::CheckedAdd,
+        39 => Self::CheckedSub,
+        40 => Self::CheckedMul,
+        41 => Self::CheckedDiv,
+        42 => Self::WrappingAdd,
+        43 => Self::WrappingSub,
+        44 => Self::WrappingMul,
+        45 => Self::SaturatingAdd,
+        46 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
       }
     }
   }
@@ -487,23 +615,29 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: U16Op, a: u16, b: u16, c: u32) -> TestResult {
     let apply0_go = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U16Op::apply0(op) == expected)
+      TestResult::from_bool(U16Op::apply(op, &[]) == expected)
     };
 
     let apply1_u16 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U16Op::apply1(op, &U16(a)) == expected)
+      TestResult::from_bool(U16Op::apply(op, &[&U16(a)]) == expected)
     };
 
     let apply2_u16_u16 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U16Op::apply2(op, &U16(a), &U16(b)) == expected)
+      TestResult::from_bool(U16Op::apply(op, &[&U16(a), &U16(b)]) == expected)
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        U16Op::apply_checked(op, &[&U16(a), &U16(b)]) == Some(expected),
+      )
     };
 
     let apply2_u16_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U16Op::apply2(op, &U16(a), &U32(c)) == expected)
+      TestResult::from_bool(U16Op::apply(op, &[&U16(a), &U32(c)]) == expected)
     };
 
     let apply2_u32_u16 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U16Op::apply2(op, &U32(c), &U16(a)) == expected)
+      TestResult::from_bool(U16Op::apply(op, &[&U32(c), &U16(a)]) == expected)
     };
 
     let from_bool = TestResult::from_bool;
@@ -529,6 +663,16 @@ pub mod tests {
       U16Op::Mod => {
         apply2_u16_u16(if b == 0 { None } else { Some(U16(a.wrapping_rem(b))) })
       }
+      U16Op::WrappingAdd => apply2_u16_u16(Some(U16(a.wrapping_add(b)))),
+      U16Op::WrappingSub => apply2_u16_u16(Some(U16(a.wrapping_sub(b)))),
+      U16Op::WrappingMul => apply2_u16_u16(Some(U16(a.wrapping_mul(b)))),
+      U16Op::SaturatingAdd => apply2_u16_u16(Some(U16(a.saturating_add(b)))),
+      U16Op::SaturatingSub => apply2_u16_u16(Some(U16(a.saturating_sub(b)))),
+      U16Op::SaturatingMul => apply2_u16_u16(Some(U16(a.saturating_mul(b)))),
+      U16Op::CheckedAdd => apply2_checked(a.checked_add(b).map(U16)),
+      U16Op::CheckedSub => apply2_checked(a.checked_sub(b).map(U16)),
+      U16Op::CheckedMul => apply2_checked(a.checked_mul(b).map(U16)),
+      U16Op::CheckedDiv => apply2_checked(a.checked_div(b).map(U16)),
       U16Op::Pow => apply2_u16_u32(Some(U16(a.wrapping_pow(c)))),
       U16Op::Shl => apply2_u32_u16(Some(U16(a.wrapping_shl(c)))),
       U16Op::Shr => apply2_u32_u16(Some(U16(a.wrapping_shr(c)))),
@@ -536,19 +680,23 @@ pub mod tests {
       U16Op::Ror => apply2_u32_u16(Some(U16(a.rotate_right(c)))),
       U16Op::CountZeros => apply1_u16(Some(U32(a.count_zeros()))),
       U16Op::CountOnes => apply1_u16(Some(U32(a.count_ones()))),
+      U16Op::LeadingZeros => apply1_u16(Some(U32(a.leading_zeros()))),
+      U16Op::TrailingZeros => apply1_u16(Some(U32(a.trailing_zeros()))),
+      U16Op::ReverseBits => apply1_u16(Some(U16(a.reverse_bits()))),
+      U16Op::SwapBytes => apply1_u16(Some(U16(a.swap_bytes()))),
       U16Op::ToU8 => from_bool(if a > u8::MAX.into() {
-        U16Op::apply1(op, &U16(a)) == None
+        U16Op::apply(op, &[&U16(a)]) == None
       }
       else {
-        U8Op::apply1(U8Op::ToU16, &U16Op::apply1(op, &U16(a)).unwrap())
+        U8Op::apply(U8Op::ToU16, &[&U16Op::apply(op, &[&U16(a)]).unwrap()])
           == Some(U16(a))
       }),
       U16Op::ToU32 => from_bool(
-        U32Op::apply1(U32Op::ToU16, &U16Op::apply1(op, &U16(a)).unwrap())
+        U32Op::apply(U32Op::ToU16, &[&U16Op::apply(op, &[&U16(a)]).unwrap()])
           == Some(U16(a)),
       ),
       U16Op::ToU64 => from_bool(
-        U64Op::apply1(U64Op::ToU16, &U16Op::apply1(op, &U16(a)).unwrap())
+        U64Op::apply(U64Op::ToU16, &[&U16Op::apply(op, &[&U16(a)]).unwrap()])
           == Some(U16(a)),
       ),
       U16Op::ToU128 => TestResult::discard(),
@@ -556,25 +704,25 @@ pub mod tests {
         apply1_u16(Some(Nat(BigUint::from(u64::try_from(a).unwrap()))))
       }
       U16Op::ToI8 => from_bool(if a > i8::MAX.try_into().unwrap() {
-        U16Op::apply1(op, &U16(a)) == None
+        U16Op::apply(op, &[&U16(a)]) == None
       }
       else {
-        I8Op::apply1(I8Op::ToU16, &U16Op::apply1(op, &U16(a)).unwrap())
+        I8Op::apply(I8Op::ToU16, &[&U16Op::apply(op, &[&U16(a)]).unwrap()])
           == Some(U16(a))
       }),
       U16Op::ToI16 => from_bool(if a > i16::MAX.try_into().unwrap() {
-        U16Op::apply1(op, &U16(a)) == None
+        U16Op::apply(op, &[&U16(a)]) == None
       }
       else {
-        I16Op::apply1(I16Op::ToU16, &U16Op::apply1(op, &U16(a)).unwrap())
+        I16Op::apply(I16Op::ToU16, &[&U16Op::apply(op, &[&U16(a)]).unwrap()])
           == Some(U16(a))
       }),
       U16Op::ToI32 => from_bool(
-        I32Op::apply1(I32Op::ToU16, &U16Op::apply1(op, &U16(a)).unwrap())
+        I32Op::apply(I32Op::ToU16, &[&U16Op::apply(op, &[&U16(a)]).unwrap()])
           == Some(U16(a)),
       ),
       U16Op::ToI64 => from_bool(
-        I64Op::apply1(I64Op::ToU16, &U16Op::apply1(op, &U16(a)).unwrap())
+        I64Op::apply(I64Op::ToU16, &[&U16Op::apply(op, &[&U16(a)]).unwrap()])
           == Some(U16(a)),
       ),
       U16Op::ToI128 => TestResult::discard(),
@@ -599,13 +747,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(U16Op::apply1(op, &a) == None)
+        TestResult::from_bool(U16Op::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(U16Op::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            U16Op::apply(op, &[&a_, &b_]) == None
+              && U16Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -631,6 +784,10 @@ pub mod tests {
       U16Op::Not
       | U16Op::CountZeros
       | U16Op::CountOnes
+      | U16Op::LeadingZeros
+      | U16Op::TrailingZeros
+      | U16Op::ReverseBits
+      | U16Op::SwapBytes
       | U16Op::ToU8
       | U16Op::ToU32
       | U16Op::ToU64
@@ -657,7 +814,17 @@ pub mod tests {
       | U16Op::Sub
       | U16Op::Mul
       | U16Op::Div
-      | U16Op::Mod => {
+      | U16Op::Mod
+      | U16Op::CheckedAdd
+      | U16Op::CheckedSub
+      | U16Op::CheckedMul
+      | U16Op::CheckedDiv
+      | U16Op::WrappingAdd
+      | U16Op::WrappingSub
+      | U16Op::WrappingMul
+      | U16Op::SaturatingAdd
+      | U16Op::SaturatingSub
+      | U16Op::SaturatingMul => {
         if test_arg_2 {
           test_apply2_none_on_invalid(U16(b), a, U16(b))
         }
@@ -0,0 +1,676 @@
+use sp_ipld::Ipld;
+
+use sp_std::{
+  borrow::ToOwned,
+  cmp::Ordering,
+  convert::TryFrom,
+  fmt,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  term::Term,
+  yatima,
+};
+
+use num_bigint::BigUint;
+
+/// An unsigned 256-bit integer, stored as four 64-bit limbs from the least to
+/// the most significant, so that it has a constant size unlike `#Nat`
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default)]
+pub struct U256(pub [u64; 4]);
+
+impl U256 {
+  pub const MAX: U256 = U256([u64::MAX; 4]);
+  pub const MIN: U256 = U256([0; 4]);
+
+  /// Makes a U256 from a u64
+  pub fn from_u64(x: u64) -> Self { U256([x, 0, 0, 0]) }
+
+  /// Makes a U256 from 32 big-endian bytes
+  pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+    let mut limbs = [0u64; 4];
+    for (i, limb) in limbs.iter_mut().enumerate() {
+      let mut x = [0u8; 8];
+      x.copy_from_slice(&bytes[24 - 8 * i..32 - 8 * i]);
+      *limb = u64::from_be_bytes(x);
+    }
+    U256(limbs)
+  }
+
+  /// Makes a U256 from at most 32 big-endian bytes
+  pub fn from_be_slice(xs: &[u8]) -> Option<Self> {
+    if xs.len() > 32 {
+      return None;
+    }
+    let mut bytes = [0u8; 32];
+    bytes[32 - xs.len()..].copy_from_slice(xs);
+    Some(Self::from_be_bytes(bytes))
+  }
+
+  /// Gets the 32 big-endian bytes of a U256
+  pub fn to_be_bytes(self) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    for (i, limb) in self.0.iter().enumerate() {
+      bytes[24 - 8 * i..32 - 8 * i].copy_from_slice(&limb.to_be_bytes());
+    }
+    bytes
+  }
+
+  /// Makes a U256 from a natural number, if it's less than 2^256
+  pub fn from_biguint(x: &BigUint) -> Option<Self> {
+    Self::from_be_slice(&x.to_bytes_be())
+  }
+
+  /// Gets the value of a U256 as a natural number
+  pub fn to_biguint(self) -> BigUint {
+    BigUint::from_bytes_be(&self.to_be_bytes())
+  }
+
+  /// Gets the bit at index `i` from the least significant
+  pub fn bit(self, i: usize) -> bool { (self.0[i / 64] >> (i % 64)) & 1 == 1 }
+
+  /// Adds, returning whether the sum wrapped around
+  pub fn overflowing_add(self, rhs: Self) -> (Self, bool) {
+    let mut res = [0u64; 4];
+    let mut carry = false;
+    for (r, (x, y)) in res.iter_mut().zip(self.0.iter().zip(rhs.0.iter())) {
+      let (x, c1) = x.overflowing_add(*y);
+      let (x, c2) = x.overflowing_add(carry as u64);
+      *r = x;
+      carry = c1 || c2;
+    }
+    (U256(res), carry)
+  }
+
+  /// Subtracts, returning whether the difference wrapped around
+  pub fn overflowing_sub(self, rhs: Self) -> (Self, bool) {
+    let mut res = [0u64; 4];
+    let mut borrow = false;
+    for (r, (x, y)) in res.iter_mut().zip(self.0.iter().zip(rhs.0.iter())) {
+      let (x, b1) = x.overflowing_sub(*y);
+      let (x, b2) = x.overflowing_sub(borrow as u64);
+      *r = x;
+      borrow = b1 || b2;
+    }
+    (U256(res), borrow)
+  }
+
+  /// Multiplies, returning whether the product wrapped around
+  pub fn overflowing_mul(self, rhs: Self) -> (Self, bool) {
+    let mut res = [0u64; 8];
+    for i in 0..4 {
+      let mut carry = 0u128;
+      for j in 0..4 {
+        let x = (self.0[i] as u128) * (rhs.0[j] as u128)
+          + (res[i + j] as u128)
+          + carry;
+        res[i + j] = x as u64;
+        carry = x >> 64;
+      }
+      res[i + 4] = carry as u64;
+    }
+    let overflow = res[4..].iter().any(|x| *x != 0);
+    (U256([res[0], res[1], res[2], res[3]]), overflow)
+  }
+
+  /// Raises to a power, wrapping around
+  pub fn wrapping_pow(self, exp: Self) -> Self {
+    let mut base = self;
+    let mut res = U256::from_u64(1);
+    for i in 0..(256 - exp.leading_zeros() as usize) {
+      if exp.bit(i) {
+        res = res.overflowing_mul(base).0;
+      }
+      base = base.overflowing_mul(base).0;
+    }
+    res
+  }
+
+  /// Divides, if the divisor isn't zero
+  pub fn checked_div(self, rhs: Self) -> Option<Self> {
+    if rhs == U256::MIN {
+      return None;
+    }
+    Self::from_biguint(&(self.to_biguint() / rhs.to_biguint()))
+  }
+
+  /// Gets the remainder of a division, if the divisor isn't zero
+  pub fn checked_rem(self, rhs: Self) -> Option<Self> {
+    if rhs == U256::MIN {
+      return None;
+    }
+    Self::from_biguint(&(self.to_biguint() % rhs.to_biguint()))
+  }
+
+  /// Shifts towards the most significant bit, giving zero from 256 bits on
+  pub fn shl_bits(self, n: u32) -> Self {
+    let (limbs, bits) = ((n / 64) as usize, n % 64);
+    let mut res = [0u64; 4];
+    for (i, r) in res.iter_mut().enumerate().skip(limbs) {
+      *r = self.0[i - limbs] << bits;
+      if bits > 0 && i > limbs {
+        *r |= self.0[i - limbs - 1] >> (64 - bits);
+      }
+    }
+    U256(res)
+  }
+
+  /// Shifts towards the least significant bit, giving zero from 256 bits on
+  pub fn shr_bits(self, n: u32) -> Self {
+    let (limbs, bits) = ((n / 64) as usize, n % 64);
+    let mut res = [0u64; 4];
+    let kept = 4usize.saturating_sub(limbs);
+    for (i, r) in res.iter_mut().take(kept).enumerate() {
+      *r = self.0[i + limbs] >> bits;
+      if bits > 0 && i + limbs + 1 < 4 {
+        *r |= self.0[i + limbs + 1] << (64 - bits);
+      }
+    }
+    U256(res)
+  }
+
+  /// Combines the limbs of two U256s
+  pub fn zip(self, rhs: Self, f: impl Fn(u64, u64) -> u64) -> Self {
+    let mut res = [0u64; 4];
+    for (r, (x, y)) in res.iter_mut().zip(self.0.iter().zip(rhs.0.iter())) {
+      *r = f(*x, *y);
+    }
+    U256(res)
+  }
+
+  /// Counts the bits that are set
+  pub fn count_ones(self) -> u32 { self.0.iter().map(|x| x.count_ones()).sum() }
+
+  /// Counts the unset bits above the most significant set bit
+  pub fn leading_zeros(self) -> u32 {
+    let mut res = 0;
+    for limb in self.0.iter().rev() {
+      res += limb.leading_zeros();
+      if *limb != 0 {
+        break;
+      }
+    }
+    res
+  }
+
+  /// Counts the unset bits below the least significant set bit
+  pub fn trailing_zeros(self) -> u32 {
+    let mut res = 0;
+    for limb in self.0.iter() {
+      res += limb.trailing_zeros();
+      if *limb != 0 {
+        break;
+      }
+    }
+    res
+  }
+}
+
+impl Ord for U256 {
+  fn cmp(&self, rhs: &Self) -> Ordering {
+    self.0.iter().rev().cmp(rhs.0.iter().rev())
+  }
+}
+
+impl PartialOrd for U256 {
+  fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> { Some(self.cmp(rhs)) }
+}
+
+impl fmt::Display for U256 {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.to_biguint())
+  }
+}
+
+/// Primitive 256-bit unsigned integer operations. Arithmetic wraps around,
+/// as in the EVM, with checked variants that return an option
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum U256Op {
+  Max,
+  Min,
+  Eql,
+  Lte,
+  Lth,
+  Gth,
+  Gte,
+  Not,
+  And,
+  Or,
+  Xor,
+  Add,
+  Sub,
+  Mul,
+  Div,
+  Mod,
+  Pow,
+  AddMod,
+  MulMod,
+  Shl,
+  Shr,
+  CountOnes,
+  LeadingZeros,
+  TrailingZeros,
+  ToBytes,
+  FromBytes,
+  FromLimbs,
+  Limb,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+}
+
+impl U256Op {
+  /// Gets the syntax string of a u256 operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Max => "max".to_owned(),
+      Self::Min => "min".to_owned(),
+      Self::Eql => "eql".to_owned(),
+      Self::Lte => "lte".to_owned(),
+      Self::Lth => "lth".to_owned(),
+      Self::Gth => "gth".to_owned(),
+      Self::Gte => "gte".to_owned(),
+      Self::Not => "not".to_owned(),
+      Self::And => "and".to_owned(),
+      Self::Or => "or".to_owned(),
+      Self::Xor => "xor".to_owned(),
+      Self::Add => "add".to_owned(),
+      Self::Sub => "sub".to_owned(),
+      Self::Mul => "mul".to_owned(),
+      Self::Div => "div".to_owned(),
+      Self::Mod => "mod".to_owned(),
+      Self::Pow => "pow".to_owned(),
+      Self::AddMod => "add_mod".to_owned(),
+      Self::MulMod => "mul_mod".to_owned(),
+      Self::Shl => "shl".to_owned(),
+      Self::Shr => "shr".to_owned(),
+      Self::CountOnes => "count_ones".to_owned(),
+      Self::LeadingZeros => "leading_zeros".to_owned(),
+      Self::TrailingZeros => "trailing_zeros".to_owned(),
+      Self::ToBytes => "to_Bytes".to_owned(),
+      Self::FromBytes => "from_Bytes".to_owned(),
+      Self::FromLimbs => "from_limbs".to_owned(),
+      Self::Limb => "limb".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+    }
+  }
+
+  /// Gets a u256 operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "max" => Some(Self::Max),
+      "min" => Some(Self::Min),
+      "eql" => Some(Self::Eql),
+      "lte" => Some(Self::Lte),
+      "lth" => Some(Self::Lth),
+      "gth" => Some(Self::Gth),
+      "gte" => Some(Self::Gte),
+      "not" => Some(Self::Not),
+      "and" => Some(Self::And),
+      "or" => Some(Self::Or),
+      "xor" => Some(Self::Xor),
+      "add" => Some(Self::Add),
+      "sub" => Some(Self::Sub),
+      "mul" => Some(Self::Mul),
+      "div" => Some(Self::Div),
+      "mod" => Some(Self::Mod),
+      "pow" => Some(Self::Pow),
+      "add_mod" => Some(Self::AddMod),
+      "mul_mod" => Some(Self::MulMod),
+      "shl" => Some(Self::Shl),
+      "shr" => Some(Self::Shr),
+      "count_ones" => Some(Self::CountOnes),
+      "leading_zeros" => Some(Self::LeadingZeros),
+      "trailing_zeros" => Some(Self::TrailingZeros),
+      "to_Bytes" => Some(Self::ToBytes),
+      "from_Bytes" => Some(Self::FromBytes),
+      "from_limbs" => Some(Self::FromLimbs),
+      "limb" => Some(Self::Limb),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of a u256 operation
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Max => yatima!("#U256"),
+      Self::Min => yatima!("#U256"),
+      Self::Eql => yatima!("∀ #U256 #U256 -> #Bool"),
+      Self::Lte => yatima!("∀ #U256 #U256 -> #Bool"),
+      Self::Lth => yatima!("∀ #U256 #U256 -> #Bool"),
+      Self::Gth => yatima!("∀ #U256 #U256 -> #Bool"),
+      Self::Gte => yatima!("∀ #U256 #U256 -> #Bool"),
+      Self::Not => yatima!("∀ #U256 -> #U256"),
+      Self::And => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::Or => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::Xor => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::Add => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::Sub => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::Mul => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::Div => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::Mod => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::Pow => yatima!("∀ #U256 #U256 -> #U256"),
+      Self::AddMod => yatima!("∀ #U256 #U256 #U256 -> #U256"),
+      Self::MulMod => yatima!("∀ #U256 #U256 #U256 -> #U256"),
+      Self::Shl => yatima!("∀ #U32 #U256 -> #U256"),
+      Self::Shr => yatima!("∀ #U32 #U256 -> #U256"),
+      Self::CountOnes => yatima!("∀ #U256 -> #U32"),
+      Self::LeadingZeros => yatima!("∀ #U256 -> #U32"),
+      Self::TrailingZeros => yatima!("∀ #U256 -> #U32"),
+      Self::ToBytes => yatima!("∀ #U256 -> #Bytes"),
+      Self::FromBytes => yatima!("∀ #Bytes -> #U256"),
+      Self::FromLimbs => yatima!("∀ #U64 #U64 #U64 #U64 -> #U256"),
+      Self::Limb => yatima!("∀ #U32 #U256 -> #U64"),
+      Self::CheckedAdd | Self::CheckedSub | Self::CheckedMul => {
+        yatima!("∀ #U256 #U256 (0 P: Type) (none: P) (some: ∀ #U256 -> P) -> P")
+      }
+    }
+  }
+
+  /// Converts a u256 operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Max => Ipld::Integer(0),
+      Self::Min => Ipld::Integer(1),
+      Self::Eql => Ipld::Integer(2),
+      Self::Lte => Ipld::Integer(3),
+      Self::Lth => Ipld::Integer(4),
+      Self::Gth => Ipld::Integer(5),
+      Self::Gte => Ipld::Integer(6),
+      Self::Not => Ipld::Integer(7),
+      Self::And => Ipld::Integer(8),
+      Self::Or => Ipld::Integer(9),
+      Self::Xor => Ipld::Integer(10),
+      Self::Add => Ipld::Integer(11),
+      Self::Sub => Ipld::Integer(12),
+      Self::Mul => Ipld::Integer(13),
+      Self::Div => Ipld::Integer(14),
+      Self::Mod => Ipld::Integer(15),
+      Self::Pow => Ipld::Integer(16),
+      Self::AddMod => Ipld::Integer(17),
+      Self::MulMod => Ipld::Integer(18),
+      Self::Shl => Ipld::Integer(19),
+      Self::Shr => Ipld::Integer(20),
+      Self::CountOnes => Ipld::Integer(21),
+      Self::LeadingZeros => Ipld::Integer(22),
+      Self::TrailingZeros => Ipld::Integer(23),
+      Self::ToBytes => Ipld::Integer(24),
+      Self::FromBytes => Ipld::Integer(25),
+      Self::FromLimbs => Ipld::Integer(26),
+      Self::Limb => Ipld::Integer(27),
+      Self::CheckedAdd => Ipld::Integer(28),
+      Self::CheckedSub => Ipld::Integer(29),
+      Self::CheckedMul => Ipld::Integer(30),
+    }
+  }
+
+  /// Converts an IPLD object into a u256 operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Max),
+      Ipld::Integer(1) => Ok(Self::Min),
+      Ipld::Integer(2) => Ok(Self::Eql),
+      Ipld::Integer(3) => Ok(Self::Lte),
+      Ipld::Integer(4) => Ok(Self::Lth),
+      Ipld::Integer(5) => Ok(Self::Gth),
+      Ipld::Integer(6) => Ok(Self::Gte),
+      Ipld::Integer(7) => Ok(Self::Not),
+      Ipld::Integer(8) => Ok(Self::And),
+      Ipld::Integer(9) => Ok(Self::Or),
+      Ipld::Integer(10) => Ok(Self::Xor),
+      Ipld::Integer(11) => Ok(Self::Add),
+      Ipld::Integer(12) => Ok(Self::Sub),
+      Ipld::Integer(13) => Ok(Self::Mul),
+      Ipld::Integer(14) => Ok(Self::Div),
+      Ipld::Integer(15) => Ok(Self::Mod),
+      Ipld::Integer(16) => Ok(Self::Pow),
+      Ipld::Integer(17) => Ok(Self::AddMod),
+      Ipld::Integer(18) => Ok(Self::MulMod),
+      Ipld::Integer(19) => Ok(Self::Shl),
+      Ipld::Integer(20) => Ok(Self::Shr),
+      Ipld::Integer(21) => Ok(Self::CountOnes),
+      Ipld::Integer(22) => Ok(Self::LeadingZeros),
+      Ipld::Integer(23) => Ok(Self::TrailingZeros),
+      Ipld::Integer(24) => Ok(Self::ToBytes),
+      Ipld::Integer(25) => Ok(Self::FromBytes),
+      Ipld::Integer(26) => Ok(Self::FromLimbs),
+      Ipld::Integer(27) => Ok(Self::Limb),
+      Ipld::Integer(28) => Ok(Self::CheckedAdd),
+      Ipld::Integer(29) => Ok(Self::CheckedSub),
+      Ipld::Integer(30) => Ok(Self::CheckedMul),
+      xs => Err(IpldError::U256Op(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Max | Self::Min => 0,
+      Self::Not
+      | Self::CountOnes
+      | Self::LeadingZeros
+      | Self::TrailingZeros
+      | Self::ToBytes
+      | Self::FromBytes => 1,
+      Self::AddMod | Self::MulMod => 3,
+      Self::FromLimbs => 4,
+      _ => 2,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Max, []) => Some(U256(self::U256::MAX)),
+      (Self::Min, []) => Some(U256(self::U256::MIN)),
+      (Self::Not, [U256(x)]) => Some(U256(x.zip(*x, |x, _| !x))),
+      (Self::CountOnes, [U256(x)]) => Some(U32(x.count_ones())),
+      (Self::LeadingZeros, [U256(x)]) => Some(U32(x.leading_zeros())),
+      (Self::TrailingZeros, [U256(x)]) => Some(U32(x.trailing_zeros())),
+      (Self::ToBytes, [U256(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::FromBytes, [Bytes(xs)]) => self::U256::from_be_slice(xs).map(U256),
+      (Self::Eql, [U256(x), U256(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [U256(x), U256(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [U256(x), U256(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [U256(x), U256(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [U256(x), U256(y)]) => Some(Bool(x >= y)),
+      (Self::And, [U256(x), U256(y)]) => Some(U256(x.zip(*y, |x, y| x & y))),
+      (Self::Or, [U256(x), U256(y)]) => Some(U256(x.zip(*y, |x, y| x | y))),
+      (Self::Xor, [U256(x), U256(y)]) => Some(U256(x.zip(*y, |x, y| x ^ y))),
+      (Self::Add, [U256(x), U256(y)]) => Some(U256(x.overflowing_add(*y).0)),
+      (Self::Sub, [U256(x), U256(y)]) => Some(U256(x.overflowing_sub(*y).0)),
+      (Self::Mul, [U256(x), U256(y)]) => Some(U256(x.overflowing_mul(*y).0)),
+      (Self::Div, [U256(x), U256(y)]) => x.checked_div(*y).map(U256),
+      (Self::Mod, [U256(x), U256(y)]) => x.checked_rem(*y).map(U256),
+      (Self::Pow, [U256(x), U256(y)]) => Some(U256(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), U256(y)]) => Some(U256(y.shl_bits(*x))),
+      (Self::Shr, [U32(x), U256(y)]) => Some(U256(y.shr_bits(*x))),
+      (Self::Limb, [U32(x), U256(y)]) => {
+        usize::try_from(*x).ok().and_then(|x| y.0.get(x)).map(|x| U64(*x))
+      }
+      (Self::AddMod, [U256(x), U256(y), U256(m)]) if *m != self::U256::MIN => {
+        let res = (x.to_biguint() + y.to_biguint()) % m.to_biguint();
+        self::U256::from_biguint(&res).map(U256)
+      }
+      (Self::MulMod, [U256(x), U256(y), U256(m)]) if *m != self::U256::MIN => {
+        let res = (x.to_biguint() * y.to_biguint()) % m.to_biguint();
+        self::U256::from_biguint(&res).map(U256)
+      }
+      (Self::FromLimbs, [U64(w), U64(x), U64(y), U64(z)]) => {
+        Some(U256(self::U256([*z, *y, *x, *w])))
+      }
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow
+  /// and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    let checked = |(x, overflow): (self::U256, bool)| {
+      if overflow {
+        None
+      }
+      else {
+        Some(U256(x))
+      }
+    };
+    match (self, args) {
+      (Self::CheckedAdd, [U256(x), U256(y)]) => {
+        Some(checked(x.overflowing_add(*y)))
+      }
+      (Self::CheckedSub, [U256(x), U256(y)]) => {
+        Some(checked(x.overflowing_sub(*y)))
+      }
+      (Self::CheckedMul, [U256(x), U256(y)]) => {
+        Some(checked(x.overflowing_mul(*y)))
+      }
+      _ => None,
+    }
+  }
+}
+
+impl fmt::Display for U256Op {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+  };
+  use rand::Rng;
+  impl Arbitrary for U256Op {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=30);
+      U256Op::from_ipld(&Ipld::Integer(gen.into())).unwrap()
+    }
+  }
+
+  impl Arbitrary for U256 {
+    fn arbitrary(g: &mut Gen) -> Self {
+      U256([
+        Arbitrary::arbitrary(g),
+        Arbitrary::arbitrary(g),
+        Arbitrary::arbitrary(g),
+        Arbitrary::arbitrary(g),
+      ])
+    }
+  }
+
+  #[quickcheck]
+  fn u256_op_ipld(x: U256Op) -> bool {
+    match U256Op::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[quickcheck]
+  fn u256_op_symbol(x: U256Op) -> bool {
+    U256Op::from_symbol(&x.symbol()) == Some(x)
+  }
+
+  fn modulus() -> BigUint { BigUint::from(1u8) << 256 }
+
+  // Limb arithmetic agrees with arithmetic on naturals modulo 2^256
+  #[quickcheck]
+  fn test_arith_agrees(x: U256, y: U256) -> bool {
+    let (a, b) = (x.to_biguint(), y.to_biguint());
+    let m = modulus();
+    let wrap = |z: BigUint| U256::from_biguint(&(z % &m)).unwrap();
+    x.overflowing_add(y) == (wrap(&a + &b), &a + &b >= m)
+      && x.overflowing_mul(y) == (wrap(&a * &b), &a * &b >= m)
+      && x.overflowing_sub(y).0 == wrap(&a + &m - &b)
+      && x.overflowing_sub(y).1 == (a < b)
+      && (x < y) == (a < b)
+  }
+
+  #[quickcheck]
+  fn test_shifts_agree(x: U256, n: u8) -> bool {
+    let a = x.to_biguint();
+    let n = u32::from(n) + 1;
+    x.shl_bits(n) == U256::from_biguint(&((&a << n) % modulus())).unwrap()
+      && x.shr_bits(n) == U256::from_biguint(&(&a >> n)).unwrap()
+      && x.shl_bits(256) == U256::MIN
+      && x.shr_bits(300) == U256::MIN
+  }
+
+  #[quickcheck]
+  fn test_bytes_roundtrip(x: U256) -> bool {
+    U256::from_be_bytes(x.to_be_bytes()) == x
+      && U256::from_biguint(&x.to_biguint()) == Some(x)
+  }
+
+  #[test]
+  fn test_pow() {
+    let two = U256::from_u64(2);
+    let exp = |x: u64| two.wrapping_pow(U256::from_u64(x));
+    assert_eq!(exp(10), U256::from_u64(1024));
+    assert_eq!(exp(255), U256([0, 0, 0, 1 << 63]));
+    assert_eq!(exp(256), U256::MIN);
+    assert_eq!(U256::MIN.wrapping_pow(U256::MIN), U256::from_u64(1));
+    assert_eq!(exp(255).leading_zeros(), 0);
+    assert_eq!(exp(255).trailing_zeros(), 255);
+    assert_eq!(U256::MIN.leading_zeros(), 256);
+  }
+
+  #[test]
+  fn test_apply() {
+    use Literal::*;
+    let x = |n: u64| U256(self::U256::from_u64(n));
+    assert_eq!(U256Op::Div.apply(&[&x(7), &x(2)]), Some(x(3)));
+    assert_eq!(U256Op::Div.apply(&[&x(7), &x(0)]), None);
+    assert_eq!(U256Op::Mod.apply(&[&x(7), &x(2)]), Some(x(1)));
+    assert_eq!(
+      // 2^256 - 1 is 5 modulo 10
+      U256Op::MulMod.apply(&[&U256(self::U256::MAX), &x(3), &x(10)]),
+      Some(x(5))
+    );
+    assert_eq!(U256Op::AddMod.apply(&[&x(1), &x(2), &x(0)]), None);
+    assert_eq!(
+      U256Op::FromLimbs.apply(&[&U64(1), &U64(0), &U64(0), &U64(2)]),
+      Some(U256(self::U256([2, 0, 0, 1])))
+    );
+    assert_eq!(U256Op::Limb.apply(&[&U32(3), &x(5)]), Some(U64(0)));
+    assert_eq!(U256Op::Limb.apply(&[&U32(4), &x(5)]), None);
+    assert_eq!(U256Op::FromBytes.apply(&[&Bytes(vec![1, 0])]), Some(x(256)));
+    assert_eq!(U256Op::FromBytes.apply(&[&Bytes(vec![0; 33])]), None);
+    assert_eq!(U256Op::CheckedSub.apply_checked(&[&x(1), &x(2)]), Some(None));
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(op: U256Op, a: Literal) -> bool {
+    match a {
+      Literal::U256(_)
+      | Literal::U32(_)
+      | Literal::U64(_)
+      | Literal::Bytes(_) => true,
+      _ => {
+        op.apply(&[&a]) == None
+          && op.apply(&[&a, &a]) == None
+          && op.apply_checked(&[&a, &a]) == None
+          && op.apply(&[&a, &a, &a]) == None
+          && op.apply(&[&a, &a, &a, &a]) == None
+      }
+    }
+  }
+}
@@ -60,6 +60,20 @@ pub enum U32Op {
   ToBits,
   ToBytes,
   ToChar,
+  LeadingZeros,
+  TrailingZeros,
+  ReverseBits,
+  SwapBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl U32Op {
@@ -103,6 +117,20 @@ impl U32Op {
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
       Self::ToChar => "to_Char".to_owned(),
+      Self::LeadingZeros => "leading_zeros".to_owned(),
+      Self::TrailingZeros => "trailing_zeros".to_owned(),
+      Self::ReverseBits => "reverse_bits".to_owned(),
+      Self::SwapBytes => "swap_bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -146,6 +174,20 @@ impl U32Op {
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
       "to_Char" => Some(Self::ToChar),
+      "leading_zeros" => Some(Self::LeadingZeros),
+      "trailing_zeros" => Some(Self::TrailingZeros),
+      "reverse_bits" => Some(Self::ReverseBits),
+      "swap_bytes" => Some(Self::SwapBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -190,6 +232,22 @@ impl U32Op {
       Self::ToBits => yatima!("∀ #U32 -> #Bits"),
       Self::ToBytes => yatima!("∀ #U32 -> #Bytes"),
       Self::ToChar => yatima!("∀ #U32 -> #Char"),
+      Self::LeadingZeros => yatima!("∀ #U32 -> #U32"),
+      Self::TrailingZeros => yatima!("∀ #U32 -> #U32"),
+      Self::ReverseBits => yatima!("∀ #U32 -> #U32"),
+      Self::SwapBytes => yatima!("∀ #U32 -> #U32"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #U32 #U32 (0 P: Type) (none: P) (some: ∀ #U32 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #U32 #U32 -> #U32"),
+      Self::WrappingSub => yatima!("∀ #U32 #U32 -> #U32"),
+      Self::WrappingMul => yatima!("∀ #U32 #U32 -> #U32"),
+      Self::SaturatingAdd => yatima!("∀ #U32 #U32 -> #U32"),
+      Self::SaturatingSub => yatima!("∀ #U32 #U32 -> #U32"),
+      Self::SaturatingMul => yatima!("∀ #U32 #U32 -> #U32"),
     }
   }
 
@@ -233,6 +291,20 @@ impl U32Op {
       Self::ToBits => Ipld::Integer(34),
       Self::ToBytes => Ipld::Integer(35),
       Self::ToChar => Ipld::Integer(36),
+      Self::LeadingZeros => Ipld::Integer(37),
+      Self::TrailingZeros => Ipld::Integer(38),
+      Self::ReverseBits => Ipld::Integer(39),
+      Self::SwapBytes => Ipld::Integer(40),
+      Self::CheckedAdd => Ipld::Integer(41),
+      Self::CheckedSub => Ipld::Integer(42),
+      Self::CheckedMul => Ipld::Integer(43),
+      Self::CheckedDiv => Ipld::Integer(44),
+      Self::WrappingAdd => Ipld::Integer(45),
+      Self::WrappingSub => Ipld::Integer(46),
+      Self::WrappingMul => Ipld::Integer(47),
+      Self::SaturatingAdd => Ipld::Integer(48),
+      Self::SaturatingSub => Ipld::Integer(49),
+      Self::SaturatingMul => Ipld::Integer(50),
     }
   }
 
@@ -276,6 +348,20 @@ impl U32Op {
       Ipld::Integer(34) => Ok(Self::ToBits),
       Ipld::Integer(35) => Ok(Self::ToBytes),
       Ipld::Integer(36) => Ok(Self::ToChar),
+      Ipld::Integer(37) => Ok(Self::LeadingZeros),
+      Ipld::Integer(38) => Ok(Self::TrailingZeros),
+      Ipld::Integer(39) => Ok(Self::ReverseBits),
+      Ipld::Integer(40) => Ok(Self::SwapBytes),
+      Ipld::Integer(41) => Ok(Self::CheckedAdd),
+      Ipld::Integer(42) => Ok(Self::CheckedSub),
+      Ipld::Integer(43) => Ok(Self::CheckedMul),
+      Ipld::Integer(44) => Ok(Self::CheckedDiv),
+      Ipld::Integer(45) => Ok(Self::WrappingAdd),
+      Ipld::Integer(46) => Ok(Self::WrappingSub),
+      Ipld::Integer(47) => Ok(Self::WrappingMul),
+      Ipld::Integer(48) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(49) => Ok(Self::SaturatingSub),
+      Ipld::Integer(50) => Ok(Self::SaturatingMul),
       xs => Err(IpldError::NatOp(xs.to_owned())),
     }
   }
@@ -320,64 +406,79 @@ impl U32Op {
       Self::ToBits => 1,
       Self::ToBytes => 1,
       Self::ToChar => 1,
+      Self::LeadingZeros => 1,
+      Self::TrailingZeros => 1,
+      Self::ReverseBits => 1,
+      Self::SwapBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
-    use Literal::*;
-    match self {
-      Self::Max => Some(U32(u32::MAX)),
-      Self::Min => Some(U32(u32::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::CountZeros, U32(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, U32(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, U32(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU16, U32(x)) => u16::try_from(*x).ok().map(U16),
-      (Self::ToU64, U32(x)) => Some(U64((*x).into())),
-      (Self::ToU128, U32(x)) => Some(U128((*x).into())),
-      (Self::ToNat, U32(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(U32(u32::MAX)),
+      (Self::Min, []) => Some(U32(u32::MIN)),
+      (Self::CountZeros, [U32(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [U32(x)]) => Some(U32(x.count_ones())),
+      (Self::LeadingZeros, [U32(x)]) => Some(U32(x.leading_zeros())),
+      (Self::TrailingZeros, [U32(x)]) => Some(U32(x.trailing_zeros())),
+      (Self::ReverseBits, [U32(x)]) => Some(U32(x.reverse_bits())),
+      (Self::SwapBytes, [U32(x)]) => Some(U32(x.swap_bytes())),
+      (Self::ToU8, [U32(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU16, [U32(x)]) => u16::try_from(*x).ok().map(U16),
+      (Self::ToU64, [U32(x)]) => Some(U64((*x).into())),
+      (Self::ToU128, [U32(x)]) => Some(U128((*x).into())),
+      (Self::ToNat, [U32(x)]) => {
         Some(Nat(BigUint::from(u64::try_from(*x).unwrap())))
       }
-      (Self::ToI8, U32(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI16, U32(x)) => i16::try_from(*x).ok().map(I16),
-      (Self::ToI32, U32(x)) => i32::try_from(*x).ok().map(I32),
-      (Self::ToI64, U32(x)) => Some(I64((*x).into())),
-      (Self::ToI128, U32(x)) => Some(I128((*x).into())),
-      (Self::Not, U32(x)) => Some(U32(!x)),
-      (Self::ToInt, U32(x)) => Some(Int((*x).into())),
-      (Self::ToBits, U32(x)) => {
+      (Self::ToI8, [U32(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI16, [U32(x)]) => i16::try_from(*x).ok().map(I16),
+      (Self::ToI32, [U32(x)]) => i32::try_from(*x).ok().map(I32),
+      (Self::ToI64, [U32(x)]) => Some(I64((*x).into())),
+      (Self::ToI128, [U32(x)]) => Some(I128((*x).into())),
+      (Self::Not, [U32(x)]) => Some(U32(!x)),
+      (Self::ToInt, [U32(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [U32(x)]) => {
         Some(Bits(bits::bytes_to_bits(32, &x.to_be_bytes().into())))
       }
-      (Self::ToBytes, U32(x)) => Some(Bytes(x.to_be_bytes().into())),
-      (Self::ToChar, U32(x)) => char::from_u32(*x).map(Char),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, U32(x), U32(y)) => Some(Bool(x == y)),
-      (Self::Lte, U32(x), U32(y)) => Some(Bool(x <= y)),
-      (Self::Lth, U32(x), U32(y)) => Some(Bool(x < y)),
-      (Self::Gth, U32(x), U32(y)) => Some(Bool(x > y)),
-      (Self::Gte, U32(x), U32(y)) => Some(Bool(x >= y)),
-      (Self::And, U32(x), U32(y)) => Some(U32(x & y)),
-      (Self::Or, U32(x), U32(y)) => Some(U32(x | y)),
-      (Self::Xor, U32(x), U32(y)) => Some(U32(x ^ y)),
-      (Self::Add, U32(x), U32(y)) => Some(U32(x.wrapping_add(*y))),
-      (Self::Sub, U32(x), U32(y)) => Some(U32(x.wrapping_sub(*y))),
-      (Self::Mul, U32(x), U32(y)) => Some(U32(x.wrapping_mul(*y))),
-      (Self::Div, U32(x), U32(y)) => {
+      (Self::ToBytes, [U32(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::ToChar, [U32(x)]) => char::from_u32(*x).map(Char),
+      (Self::Eql, [U32(x), U32(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [U32(x), U32(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [U32(x), U32(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [U32(x), U32(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [U32(x), U32(y)]) => Some(Bool(x >= y)),
+      (Self::And, [U32(x), U32(y)]) => Some(U32(x & y)),
+      (Self::Or, [U32(x), U32(y)]) => Some(U32(x | y)),
+      (Self::Xor, [U32(x), U32(y)]) => Some(U32(x ^ y)),
+      (Self::Add, [U32(x), U32(y)]) => Some(U32(x.wrapping_add(*y))),
+      (Self::Sub, [U32(x), U32(y)]) => Some(U32(x.wrapping_sub(*y))),
+      (Self::Mul, [U32(x), U32(y)]) => Some(U32(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [U32(x), U32(y)]) => Some(U32(x.wrapping_add(*y))),
+      (Self::WrappingSub, [U32(x), U32(y)]) => Some(U32(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [U32(x), U32(y)]) => Some(U32(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [U32(x), U32(y)]) => {
+        Some(U32(x.saturating_add(*y)))
+      }
+      (Self::SaturatingSub, [U32(x), U32(y)]) => {
+        Some(U32(x.saturating_sub(*y)))
+      }
+      (Self::SaturatingMul, [U32(x), U32(y)]) => {
+        Some(U32(x.saturating_mul(*y)))
+      }
+      (Self::Div, [U32(x), U32(y)]) => {
         if *y == 0 {
           None
         }
@@ -385,7 +486,7 @@ impl U32Op {
           Some(U32(x.wrapping_div(*y)))
         }
       }
-      (Self::Mod, U32(x), U32(y)) => {
+      (Self::Mod, [U32(x), U32(y)]) => {
         if *y == 0 {
           None
         }
@@ -393,11 +494,24 @@ impl U32Op {
           Some(U32(x.wrapping_rem(*y)))
         }
       }
-      (Self::Pow, U32(x), U32(y)) => Some(U32(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), U32(y)) => Some(U32(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), U32(y)) => Some(U32(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), U32(y)) => Some(U32(y.rotate_left(*x))),
-      (Self::Ror, U32(x), U32(y)) => Some(U32(y.rotate_right(*x))),
+      (Self::Pow, [U32(x), U32(y)]) => Some(U32(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), U32(y)]) => Some(U32(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), U32(y)]) => Some(U32(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), U32(y)]) => Some(U32(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), U32(y)]) => Some(U32(y.rotate_right(*x))),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    match (self, args) {
+      (Self::CheckedAdd, [U32(x), U32(y)]) => Some(x.checked_add(*y).map(U32)),
+      (Self::CheckedSub, [U32(x), U32(y)]) => Some(x.checked_sub(*y).map(U32)),
+      (Self::CheckedMul, [U32(x), U32(y)]) => Some(x.checked_mul(*y).map(U32)),
+      (Self::CheckedDiv, [U32(x), U32(y)]) => Some(x.checked_div(*y).map(U32)),
       _ => None,
     }
   }
@@ -444,7 +558,7 @@ pub mod tests {
   impl Arbitrary for U32Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=34);
+      let gen: u32 = rng.gen_range(0..=48);
       match gen {
         0 => Self::Max,
         1 => Self::Min,
@@ -480,7 +594,21 @@ pub mod tests {
         31 => Self::ToI64,
         32 => Self::ToInt,
         33 => Self::ToBytes,
-        _ => Self::ToBits,
+        34 => Self::ToBits,
+        35 => Self::LeadingZeros,
+        36 => Self::TrailingZeros,
+        37 => Self::ReverseBits,
+        38 => Self::SwapBytes,
+        39 => Self::CheckedAdd,
+        40 => Self::CheckedSub,
+        41 => Self::CheckedMul,
+        42 => Self::CheckedDiv,
+        43 => Self::WrappingAdd,
+        44 => Self::WrappingSub,
+        45 => Self::WrappingMul,
+        46 => Self::SaturatingAdd,
+        47 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
         /* 27 => Self::ToU128,
          * 33 => Self::ToI128, */
       }
@@ -498,23 +626,29 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: U32Op, a: u32, b: u32, c: u32) -> TestResult {
     let apply0_go = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U32Op::apply0(op) == expected)
+      TestResult::from_bool(U32Op::apply(op, &[]) == expected)
     };
 
     let apply1_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U32Op::apply1(op, &U32(a)) == expected)
+      TestResult::from_bool(U32Op::apply(op, &[&U32(a)]) == expected)
     };
 
     let apply2_u32_a_u32_b = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U32Op::apply2(op, &U32(a), &U32(b)) == expected)
+      TestResult::from_bool(U32Op::apply(op, &[&U32(a), &U32(b)]) == expected)
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        U32Op::apply_checked(op, &[&U32(a), &U32(b)]) == Some(expected),
+      )
     };
 
     let apply2_u32_a_u32_c = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U32Op::apply2(op, &U32(a), &U32(c)) == expected)
+      TestResult::from_bool(U32Op::apply(op, &[&U32(a), &U32(c)]) == expected)
     };
 
     let apply2_u32_c_u32_a = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U32Op::apply2(op, &U32(c), &U32(a)) == expected)
+      TestResult::from_bool(U32Op::apply(op, &[&U32(c), &U32(a)]) == expected)
     };
 
     let from_bool = TestResult::from_bool;
@@ -546,6 +680,22 @@ pub mod tests {
       else {
         Some(U32(a.wrapping_rem(b)))
       }),
+      U32Op::WrappingAdd => apply2_u32_a_u32_b(Some(U32(a.wrapping_add(b)))),
+      U32Op::WrappingSub => apply2_u32_a_u32_b(Some(U32(a.wrapping_sub(b)))),
+      U32Op::WrappingMul => apply2_u32_a_u32_b(Some(U32(a.wrapping_mul(b)))),
+      U32Op::SaturatingAdd => {
+        apply2_u32_a_u32_b(Some(U32(a.saturating_add(b))))
+      }
+      U32Op::SaturatingSub => {
+        apply2_u32_a_u32_b(Some(U32(a.saturating_sub(b))))
+      }
+      U32Op::SaturatingMul => {
+        apply2_u32_a_u32_b(Some(U32(a.saturating_mul(b))))
+      }
+      U32Op::CheckedAdd => apply2_checked(a.checked_add(b).map(U32)),
+      U32Op::CheckedSub => apply2_checked(a.checked_sub(b).map(U32)),
+      U32Op::CheckedMul => apply2_checked(a.checked_mul(b).map(U32)),
+      U32Op::CheckedDiv => apply2_checked(a.checked_div(b).map(U32)),
       U32Op::Pow => apply2_u32_a_u32_c(Some(U32(a.wrapping_pow(c)))),
       U32Op::Shl => apply2_u32_c_u32_a(Some(U32(a.wrapping_shl(c)))),
       U32Op::Shr => apply2_u32_c_u32_a(Some(U32(a.wrapping_shr(c)))),
@@ -553,22 +703,26 @@ pub mod tests {
       U32Op::Ror => apply2_u32_c_u32_a(Some(U32(a.rotate_right(c)))),
       U32Op::CountZeros => apply1_u32(Some(U32(a.count_zeros()))),
       U32Op::CountOnes => apply1_u32(Some(U32(a.count_ones()))),
+      U32Op::LeadingZeros => apply1_u32(Some(U32(a.leading_zeros()))),
+      U32Op::TrailingZeros => apply1_u32(Some(U32(a.trailing_zeros()))),
+      U32Op::ReverseBits => apply1_u32(Some(U32(a.reverse_bits()))),
+      U32Op::SwapBytes => apply1_u32(Some(U32(a.swap_bytes()))),
       U32Op::ToU8 => from_bool(if a > u8::MAX.into() {
-        U32Op::apply1(op, &U32(a)) == None
+        U32Op::apply(op, &[&U32(a)]) == None
       }
       else {
-        U8Op::apply1(U8Op::ToU32, &U32Op::apply1(op, &U32(a)).unwrap())
+        U8Op::apply(U8Op::ToU32, &[&U32Op::apply(op, &[&U32(a)]).unwrap()])
           == Some(U32(a))
       }),
       U32Op::ToU16 => from_bool(if a > u16::MAX.into() {
-        U32Op::apply1(op, &U32(a)) == None
+        U32Op::apply(op, &[&U32(a)]) == None
       }
       else {
-        U16Op::apply1(U16Op::ToU32, &U32Op::apply1(op, &U32(a)).unwrap())
+        U16Op::apply(U16Op::ToU32, &[&U32Op::apply(op, &[&U32(a)]).unwrap()])
           == Some(U32(a))
       }),
       U32Op::ToU64 => from_bool(
-        U64Op::apply1(U64Op::ToU32, &U32Op::apply1(op, &U32(a)).unwrap())
+        U64Op::apply(U64Op::ToU32, &[&U32Op::apply(op, &[&U32(a)]).unwrap()])
           == Some(U32(a)),
       ),
       U32Op::ToU128 => TestResult::discard(),
@@ -576,28 +730,28 @@ pub mod tests {
         apply1_u32(Some(Nat(BigUint::from(u64::try_from(a).unwrap()))))
       }
       U32Op::ToI8 => from_bool(if a > i8::MAX.try_into().unwrap() {
-        U32Op::apply1(op, &U32(a)) == None
+        U32Op::apply(op, &[&U32(a)]) == None
       }
       else {
-        I8Op::apply1(I8Op::ToU32, &U32Op::apply1(op, &U32(a)).unwrap())
+        I8Op::apply(I8Op::ToU32, &[&U32Op::apply(op, &[&U32(a)]).unwrap()])
           == Some(U32(a))
       }),
       U32Op::ToI16 => from_bool(if a > i16::MAX.try_into().unwrap() {
-        U32Op::apply1(op, &U32(a)) == None
+        U32Op::apply(op, &[&U32(a)]) == None
       }
       else {
-        I16Op::apply1(I16Op::ToU32, &U32Op::apply1(op, &U32(a)).unwrap())
+        I16Op::apply(I16Op::ToU32, &[&U32Op::apply(op, &[&U32(a)]).unwrap()])
           == Some(U32(a))
       }),
       U32Op::ToI32 => from_bool(if a > i32::MAX.try_into().unwrap() {
-        U32Op::apply1(op, &U32(a)) == None
+        U32Op::apply(op, &[&U32(a)]) == None
       }
       else {
-        I32Op::apply1(I32Op::ToU32, &U32Op::apply1(op, &U32(a)).unwrap())
+        I32Op::apply(I32Op::ToU32, &[&U32Op::apply(op, &[&U32(a)]).unwrap()])
           == Some(U32(a))
       }),
       U32Op::ToI64 => from_bool(
-        I64Op::apply1(I64Op::ToU32, &U32Op::apply1(op, &U32(a)).unwrap())
+        I64Op::apply(I64Op::ToU32, &[&U32Op::apply(op, &[&U32(a)]).unwrap()])
           == Some(U32(a)),
       ),
       U32Op::ToI128 => TestResult::discard(),
@@ -623,13 +777,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(U32Op::apply1(op, &a) == None)
+        TestResult::from_bool(U32Op::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(U32Op::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            U32Op::apply(op, &[&a_, &b_]) == None
+              && U32Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -655,6 +814,10 @@ pub mod tests {
       U32Op::Not
       | U32Op::CountZeros
       | U32Op::CountOnes
+      | U32Op::LeadingZeros
+      | U32Op::TrailingZeros
+      | U32Op::ReverseBits
+      | U32Op::SwapBytes
       | U32Op::ToU8
       | U32Op::ToU16
       | U32Op::ToU64
@@ -682,7 +845,17 @@ pub mod tests {
       | U32Op::Sub
       | U32Op::Mul
       | U32Op::Div
-      | U32Op::Mod => {
+      | U32Op::Mod
+      | U32Op::CheckedAdd
+      | U32Op::CheckedSub
+      | U32Op::CheckedMul
+      | U32Op::CheckedDiv
+      | U32Op::WrappingAdd
+      | U32Op::WrappingSub
+      | U32Op::WrappingMul
+      | U32Op::SaturatingAdd
+      | U32Op::SaturatingSub
+      | U32Op::SaturatingMul => {
         if test_arg_2 {
           test_apply2_none_on_invalid(U32(b), a, U32(b))
         }
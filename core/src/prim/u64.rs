@@ -59,6 +59,20 @@ pub enum U64Op {
   ToInt,
   ToBits,
   ToBytes,
+  LeadingZeros,
+  TrailingZeros,
+  ReverseBits,
+  SwapBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl U64Op {
@@ -101,6 +115,20 @@ impl U64Op {
       Self::ToInt => "to_Int".to_owned(),
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
+      Self::LeadingZeros => "leading_zeros".to_owned(),
+      Self::TrailingZeros => "trailing_zeros".to_owned(),
+      Self::ReverseBits => "reverse_bits".to_owned(),
+      Self::SwapBytes => "swap_bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -143,6 +171,20 @@ impl U64Op {
       "to_Int" => Some(Self::ToInt),
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
+      "leading_zeros" => Some(Self::LeadingZeros),
+      "trailing_zeros" => Some(Self::TrailingZeros),
+      "reverse_bits" => Some(Self::ReverseBits),
+      "swap_bytes" => Some(Self::SwapBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -186,6 +228,22 @@ impl U64Op {
       Self::ToInt => yatima!("∀ #U64 -> #Int"),
       Self::ToBits => yatima!("∀ #U64 -> #Bits"),
       Self::ToBytes => yatima!("∀ #U64 -> #Bytes"),
+      Self::LeadingZeros => yatima!("∀ #U64 -> #U32"),
+      Self::TrailingZeros => yatima!("∀ #U64 -> #U32"),
+      Self::ReverseBits => yatima!("∀ #U64 -> #U64"),
+      Self::SwapBytes => yatima!("∀ #U64 -> #U64"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #U64 #U64 (0 P: Type) (none: P) (some: ∀ #U64 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #U64 #U64 -> #U64"),
+      Self::WrappingSub => yatima!("∀ #U64 #U64 -> #U64"),
+      Self::WrappingMul => yatima!("∀ #U64 #U64 -> #U64"),
+      Self::SaturatingAdd => yatima!("∀ #U64 #U64 -> #U64"),
+      Self::SaturatingSub => yatima!("∀ #U64 #U64 -> #U64"),
+      Self::SaturatingMul => yatima!("∀ #U64 #U64 -> #U64"),
     }
   }
 
@@ -228,6 +286,20 @@ impl U64Op {
       Self::ToInt => Ipld::Integer(33),
       Self::ToBits => Ipld::Integer(34),
       Self::ToBytes => Ipld::Integer(35),
+      Self::LeadingZeros => Ipld::Integer(36),
+      Self::TrailingZeros => Ipld::Integer(37),
+      Self::ReverseBits => Ipld::Integer(38),
+      Self::SwapBytes => Ipld::Integer(39),
+      Self::CheckedAdd => Ipld::Integer(40),
+      Self::CheckedSub => Ipld::Integer(41),
+      Self::CheckedMul => Ipld::Integer(42),
+      Self::CheckedDiv => Ipld::Integer(43),
+      Self::WrappingAdd => Ipld::Integer(44),
+      Self::WrappingSub => Ipld::Integer(45),
+      Self::WrappingMul => Ipld::Integer(46),
+      Self::SaturatingAdd => Ipld::Integer(47),
+      Self::SaturatingSub => Ipld::Integer(48),
+      Self::SaturatingMul => Ipld::Integer(49),
     }
   }
 
@@ -270,6 +342,20 @@ impl U64Op {
       Ipld::Integer(33) => Ok(Self::ToInt),
       Ipld::Integer(34) => Ok(Self::ToBits),
       Ipld::Integer(35) => Ok(Self::ToBytes),
+      Ipld::Integer(36) => Ok(Self::LeadingZeros),
+      Ipld::Integer(37) => Ok(Self::TrailingZeros),
+      Ipld::Integer(38) => Ok(Self::ReverseBits),
+      Ipld::Integer(39) => Ok(Self::SwapBytes),
+      Ipld::Integer(40) => Ok(Self::CheckedAdd),
+      Ipld::Integer(41) => Ok(Self::CheckedSub),
+      Ipld::Integer(42) => Ok(Self::CheckedMul),
+      Ipld::Integer(43) => Ok(Self::CheckedDiv),
+      Ipld::Integer(44) => Ok(Self::WrappingAdd),
+      Ipld::Integer(45) => Ok(Self::WrappingSub),
+      Ipld::Integer(46) => Ok(Self::WrappingMul),
+      Ipld::Integer(47) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(48) => Ok(Self::SaturatingSub),
+      Ipld::Integer(49) => Ok(Self::SaturatingMul),
       xs => Err(IpldError::NatOp(xs.to_owned())),
     }
   }
@@ -313,61 +399,76 @@ impl U64Op {
       Self::ToInt => 1,
       Self::ToBits => 1,
       Self::ToBytes => 1,
+      Self::LeadingZeros => 1,
+      Self::TrailingZeros => 1,
+      Self::ReverseBits => 1,
+      Self::SwapBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match self {
-      Self::Max => Some(U64(u64::MAX)),
-      Self::Min => Some(U64(u64::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x) {
-      (Self::CountZeros, U64(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, U64(x)) => Some(U32(x.count_ones())),
-      (Self::ToU8, U64(x)) => u8::try_from(*x).ok().map(U8),
-      (Self::ToU16, U64(x)) => u16::try_from(*x).ok().map(U16),
-      (Self::ToU32, U64(x)) => u32::try_from(*x).ok().map(U32),
-      (Self::ToU128, U64(x)) => Some(U128((*x).into())),
-      (Self::ToNat, U64(x)) => Some(Nat(BigUint::from(*x))),
-      (Self::ToI8, U64(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI16, U64(x)) => i16::try_from(*x).ok().map(I16),
-      (Self::ToI32, U64(x)) => i32::try_from(*x).ok().map(I32),
-      (Self::ToI64, U64(x)) => i64::try_from(*x).ok().map(I64),
-      (Self::ToI128, U64(x)) => Some(I128((*x).into())),
-      (Self::Not, U64(x)) => Some(U64(!x)),
-      (Self::ToInt, U64(x)) => Some(Int((*x).into())),
-      (Self::ToBits, U64(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(U64(u64::MAX)),
+      (Self::Min, []) => Some(U64(u64::MIN)),
+      (Self::CountZeros, [U64(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [U64(x)]) => Some(U32(x.count_ones())),
+      (Self::LeadingZeros, [U64(x)]) => Some(U32(x.leading_zeros())),
+      (Self::TrailingZeros, [U64(x)]) => Some(U32(x.trailing_zeros())),
+      (Self::ReverseBits, [U64(x)]) => Some(U64(x.reverse_bits())),
+      (Self::SwapBytes, [U64(x)]) => Some(U64(x.swap_bytes())),
+      (Self::ToU8, [U64(x)]) => u8::try_from(*x).ok().map(U8),
+      (Self::ToU16, [U64(x)]) => u16::try_from(*x).ok().map(U16),
+      (Self::ToU32, [U64(x)]) => u32::try_from(*x).ok().map(U32),
+      (Self::ToU128, [U64(x)]) => Some(U128((*x).into())),
+      (Self::ToNat, [U64(x)]) => Some(Nat(BigUint::from(*x))),
+      (Self::ToI8, [U64(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI16, [U64(x)]) => i16::try_from(*x).ok().map(I16),
+      (Self::ToI32, [U64(x)]) => i32::try_from(*x).ok().map(I32),
+      (Self::ToI64, [U64(x)]) => i64::try_from(*x).ok().map(I64),
+      (Self::ToI128, [U64(x)]) => Some(I128((*x).into())),
+      (Self::Not, [U64(x)]) => Some(U64(!x)),
+      (Self::ToInt, [U64(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [U64(x)]) => {
         Some(Bits(bits::bytes_to_bits(64, &x.to_be_bytes().into())))
       }
-      (Self::ToBytes, U64(x)) => Some(Bytes(x.to_be_bytes().into())),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, U64(x), U64(y)) => Some(Bool(x == y)),
-      (Self::Lte, U64(x), U64(y)) => Some(Bool(x <= y)),
-      (Self::Lth, U64(x), U64(y)) => Some(Bool(x < y)),
-      (Self::Gth, U64(x), U64(y)) => Some(Bool(x > y)),
-      (Self::Gte, U64(x), U64(y)) => Some(Bool(x >= y)),
-      (Self::And, U64(x), U64(y)) => Some(U64(x & y)),
-      (Self::Or, U64(x), U64(y)) => Some(U64(x | y)),
-      (Self::Xor, U64(x), U64(y)) => Some(U64(x ^ y)),
-      (Self::Add, U64(x), U64(y)) => Some(U64(x.wrapping_add(*y))),
-      (Self::Sub, U64(x), U64(y)) => Some(U64(x.wrapping_sub(*y))),
-      (Self::Mul, U64(x), U64(y)) => Some(U64(x.wrapping_mul(*y))),
-      (Self::Div, U64(x), U64(y)) => {
+      (Self::ToBytes, [U64(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [U64(x), U64(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [U64(x), U64(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [U64(x), U64(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [U64(x), U64(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [U64(x), U64(y)]) => Some(Bool(x >= y)),
+      (Self::And, [U64(x), U64(y)]) => Some(U64(x & y)),
+      (Self::Or, [U64(x), U64(y)]) => Some(U64(x | y)),
+      (Self::Xor, [U64(x), U64(y)]) => Some(U64(x ^ y)),
+      (Self::Add, [U64(x), U64(y)]) => Some(U64(x.wrapping_add(*y))),
+      (Self::Sub, [U64(x), U64(y)]) => Some(U64(x.wrapping_sub(*y))),
+      (Self::Mul, [U64(x), U64(y)]) => Some(U64(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [U64(x), U64(y)]) => Some(U64(x.wrapping_add(*y))),
+      (Self::WrappingSub, [U64(x), U64(y)]) => Some(U64(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [U64(x), U64(y)]) => Some(U64(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [U64(x), U64(y)]) => {
+        Some(U64(x.saturating_add(*y)))
+      }
+      (Self::SaturatingSub, [U64(x), U64(y)]) => {
+        Some(U64(x.saturating_sub(*y)))
+      }
+      (Self::SaturatingMul, [U64(x), U64(y)]) => {
+        Some(U64(x.saturating_mul(*y)))
+      }
+      (Self::Div, [U64(x), U64(y)]) => {
         if *y == 0 {
           None
         }
@@ -375,7 +476,7 @@ impl U64Op {
           Some(U64(x.wrapping_div(*y)))
         }
       }
-      (Self::Mod, U64(x), U64(y)) => {
+      (Self::Mod, [U64(x), U64(y)]) => {
         if *y == 0 {
           None
         }
@@ -383,11 +484,24 @@ impl U64Op {
           Some(U64(x.wrapping_rem(*y)))
         }
       }
-      (Self::Pow, U64(x), U32(y)) => Some(U64(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), U64(y)) => Some(U64(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), U64(y)) => Some(U64(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), U64(y)) => Some(U64(y.rotate_left(*x))),
-      (Self::Ror, U32(x), U64(y)) => Some(U64(y.rotate_right(*x))),
+      (Self::Pow, [U64(x), U32(y)]) => Some(U64(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), U64(y)]) => Some(U64(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), U64(y)]) => Some(U64(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), U64(y)]) => Some(U64(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), U64(y)]) => Some(U64(y.rotate_right(*x))),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    match (self, args) {
+      (Self::CheckedAdd, [U64(x), U64(y)]) => Some(x.checked_add(*y).map(U64)),
+      (Self::CheckedSub, [U64(x), U64(y)]) => Some(x.checked_sub(*y).map(U64)),
+      (Self::CheckedMul, [U64(x), U64(y)]) => Some(x.checked_mul(*y).map(U64)),
+      (Self::CheckedDiv, [U64(x), U64(y)]) => Some(x.checked_div(*y).map(U64)),
       _ => None,
     }
   }
@@ -434,7 +548,7 @@ pub mod tests {
   impl Arbitrary for U64Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=33);
+      let gen: u32 = rng.gen_range(0..=47);
       match gen {
         0 => Self::Max,
         1 => Self::Min,
@@ -469,7 +583,21 @@ pub mod tests {
         30 => Self::ToI64,
         31 => Self::ToInt,
         32 => Self::ToBytes,
-        _ => Self::ToBits,
+        33 => Self::ToBits,
+        34 => Self::LeadingZeros,
+        35 => Self::TrailingZeros,
+        36 => Self::ReverseBits,
+        37 => Self::SwapBytes,
+        38 => Self::CheckedAdd,
+        39 => Self::CheckedSub,
+        40 => Self::CheckedMul,
+        41 => Self::CheckedDiv,
+        42 => Self::WrappingAdd,
+        43 => Self::WrappingSub,
+        44 => Self::WrappingMul,
+        45 => Self::SaturatingAdd,
+        46 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
         /* 26 => Self::ToU128,
          * 32 => Self::ToI128, */
       }
@@ -487,23 +615,29 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: U64Op, a: u64, b: u64, c: u32) -> TestResult {
     let apply0_go = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U64Op::apply0(op) == expected)
+      TestResult::from_bool(U64Op::apply(op, &[]) == expected)
     };
 
     let apply1_u64 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U64Op::apply1(op, &U64(a)) == expected)
+      TestResult::from_bool(U64Op::apply(op, &[&U64(a)]) == expected)
     };
 
     let apply2_u64_u64 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U64Op::apply2(op, &U64(a), &U64(b)) == expected)
+      TestResult::from_bool(U64Op::apply(op, &[&U64(a), &U64(b)]) == expected)
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        U64Op::apply_checked(op, &[&U64(a), &U64(b)]) == Some(expected),
+      )
     };
 
     let apply2_u64_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U64Op::apply2(op, &U64(a), &U32(c)) == expected)
+      TestResult::from_bool(U64Op::apply(op, &[&U64(a), &U32(c)]) == expected)
     };
 
     let apply2_u32_u64 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U64Op::apply2(op, &U32(c), &U64(a)) == expected)
+      TestResult::from_bool(U64Op::apply(op, &[&U32(c), &U64(a)]) == expected)
     };
 
     let from_bool = TestResult::from_bool;
@@ -529,6 +663,16 @@ pub mod tests {
       U64Op::Mod => {
         apply2_u64_u64(if b == 0 { None } else { Some(U64(a.wrapping_rem(b))) })
       }
+      U64Op::WrappingAdd => apply2_u64_u64(Some(U64(a.wrapping_add(b)))),
+      U64Op::WrappingSub => apply2_u64_u64(Some(U64(a.wrapping_sub(b)))),
+      U64Op::WrappingMul => apply2_u64_u64(Some(U64(a.wrapping_mul(b)))),
+      U64Op::SaturatingAdd => apply2_u64_u64(Some(U64(a.saturating_add(b)))),
+      U64Op::SaturatingSub => apply2_u64_u64(Some(U64(a.saturating_sub(b)))),
+      U64Op::SaturatingMul => apply2_u64_u64(Some(U64(a.saturating_mul(b)))),
+      U64Op::CheckedAdd => apply2_checked(a.checked_add(b).map(U64)),
+      U64Op::CheckedSub => apply2_checked(a.checked_sub(b).map(U64)),
+      U64Op::CheckedMul => apply2_checked(a.checked_mul(b).map(U64)),
+      U64Op::CheckedDiv => apply2_checked(a.checked_div(b).map(U64)),
       U64Op::Pow => apply2_u64_u32(Some(U64(a.wrapping_pow(c)))),
       U64Op::Shl => apply2_u32_u64(Some(U64(a.wrapping_shl(c)))),
       U64Op::Shr => apply2_u32_u64(Some(U64(a.wrapping_shr(c)))),
@@ -536,55 +680,59 @@ pub mod tests {
       U64Op::Ror => apply2_u32_u64(Some(U64(a.rotate_right(c)))),
       U64Op::CountZeros => apply1_u64(Some(U32(a.count_zeros()))),
       U64Op::CountOnes => apply1_u64(Some(U32(a.count_ones()))),
+      U64Op::LeadingZeros => apply1_u64(Some(U32(a.leading_zeros()))),
+      U64Op::TrailingZeros => apply1_u64(Some(U32(a.trailing_zeros()))),
+      U64Op::ReverseBits => apply1_u64(Some(U64(a.reverse_bits()))),
+      U64Op::SwapBytes => apply1_u64(Some(U64(a.swap_bytes()))),
       U64Op::ToU8 => from_bool(if a > u8::MAX.into() {
-        U64Op::apply1(op, &U64(a)) == None
+        U64Op::apply(op, &[&U64(a)]) == None
       }
       else {
-        U8Op::apply1(U8Op::ToU64, &U64Op::apply1(op, &U64(a)).unwrap())
+        U8Op::apply(U8Op::ToU64, &[&U64Op::apply(op, &[&U64(a)]).unwrap()])
           == Some(U64(a))
       }),
       U64Op::ToU16 => from_bool(if a > u16::MAX.into() {
-        U64Op::apply1(op, &U64(a)) == None
+        U64Op::apply(op, &[&U64(a)]) == None
       }
       else {
-        U16Op::apply1(U16Op::ToU64, &U64Op::apply1(op, &U64(a)).unwrap())
+        U16Op::apply(U16Op::ToU64, &[&U64Op::apply(op, &[&U64(a)]).unwrap()])
           == Some(U64(a))
       }),
       U64Op::ToU32 => from_bool(if a > u32::MAX.into() {
-        U64Op::apply1(op, &U64(a)) == None
+        U64Op::apply(op, &[&U64(a)]) == None
       }
       else {
-        U32Op::apply1(U32Op::ToU64, &U64Op::apply1(op, &U64(a)).unwrap())
+        U32Op::apply(U32Op::ToU64, &[&U64Op::apply(op, &[&U64(a)]).unwrap()])
           == Some(U64(a))
       }),
       U64Op::ToU128 => TestResult::discard(),
       U64Op::ToNat => apply1_u64(Some(Nat(BigUint::from(a)))),
       U64Op::ToI8 => from_bool(if a > i8::MAX.try_into().unwrap() {
-        U64Op::apply1(op, &U64(a)) == None
+        U64Op::apply(op, &[&U64(a)]) == None
       }
       else {
-        I8Op::apply1(I8Op::ToU64, &U64Op::apply1(op, &U64(a)).unwrap())
+        I8Op::apply(I8Op::ToU64, &[&U64Op::apply(op, &[&U64(a)]).unwrap()])
           == Some(U64(a))
       }),
       U64Op::ToI16 => from_bool(if a > i16::MAX.try_into().unwrap() {
-        U64Op::apply1(op, &U64(a)) == None
+        U64Op::apply(op, &[&U64(a)]) == None
       }
       else {
-        I16Op::apply1(I16Op::ToU64, &U64Op::apply1(op, &U64(a)).unwrap())
+        I16Op::apply(I16Op::ToU64, &[&U64Op::apply(op, &[&U64(a)]).unwrap()])
           == Some(U64(a))
       }),
       U64Op::ToI32 => from_bool(if a > i32::MAX.try_into().unwrap() {
-        U64Op::apply1(op, &U64(a)) == None
+        U64Op::apply(op, &[&U64(a)]) == None
       }
       else {
-        I32Op::apply1(I32Op::ToU64, &U64Op::apply1(op, &U64(a)).unwrap())
+        I32Op::apply(I32Op::ToU64, &[&U64Op::apply(op, &[&U64(a)]).unwrap()])
           == Some(U64(a))
       }),
       U64Op::ToI64 => from_bool(if a > i64::MAX.try_into().unwrap() {
-        U64Op::apply1(op, &U64(a)) == None
+        U64Op::apply(op, &[&U64(a)]) == None
       }
       else {
-        I64Op::apply1(I64Op::ToU64, &U64Op::apply1(op, &U64(a)).unwrap())
+        I64Op::apply(I64Op::ToU64, &[&U64Op::apply(op, &[&U64(a)]).unwrap()])
           == Some(U64(a))
       }),
       U64Op::ToI128 => TestResult::discard(),
@@ -609,13 +757,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(U64Op::apply1(op, &a) == None)
+        TestResult::from_bool(U64Op::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(U64Op::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            U64Op::apply(op, &[&a_, &b_]) == None
+              && U64Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -641,6 +794,10 @@ pub mod tests {
       U64Op::Not
       | U64Op::CountZeros
       | U64Op::CountOnes
+      | U64Op::LeadingZeros
+      | U64Op::TrailingZeros
+      | U64Op::ReverseBits
+      | U64Op::SwapBytes
       | U64Op::ToU8
       | U64Op::ToU16
       | U64Op::ToU32
@@ -667,7 +824,17 @@ pub mod tests {
       | U64Op::Sub
       | U64Op::Mul
       | U64Op::Div
-      | U64Op::Mod => {
+      | U64Op::Mod
+      | U64Op::CheckedAdd
+      | U64Op::CheckedSub
+      | U64Op::CheckedMul
+      | U64Op::CheckedDiv
+      | U64Op::WrappingAdd
+      | U64Op::WrappingSub
+      | U64Op::WrappingMul
+      | U64Op::SaturatingAdd
+      | U64Op::SaturatingSub
+      | U64Op::SaturatingMul => {
         if test_arg_2 {
           test_apply2_none_on_invalid(U64(b), a, U64(b))
         }
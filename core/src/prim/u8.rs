@@ -60,6 +60,20 @@ pub enum U8Op {
   ToBits,
   ToBytes,
   ToChar,
+  LeadingZeros,
+  TrailingZeros,
+  ReverseBits,
+  SwapBytes,
+  CheckedAdd,
+  CheckedSub,
+  CheckedMul,
+  CheckedDiv,
+  WrappingAdd,
+  WrappingSub,
+  WrappingMul,
+  SaturatingAdd,
+  SaturatingSub,
+  SaturatingMul,
 }
 
 impl U8Op {
@@ -103,6 +117,20 @@ impl U8Op {
       Self::ToBits => "to_Bits".to_owned(),
       Self::ToBytes => "to_Bytes".to_owned(),
       Self::ToChar => "to_Char".to_owned(),
+      Self::LeadingZeros => "leading_zeros".to_owned(),
+      Self::TrailingZeros => "trailing_zeros".to_owned(),
+      Self::ReverseBits => "reverse_bits".to_owned(),
+      Self::SwapBytes => "swap_bytes".to_owned(),
+      Self::CheckedAdd => "checked_add".to_owned(),
+      Self::CheckedSub => "checked_sub".to_owned(),
+      Self::CheckedMul => "checked_mul".to_owned(),
+      Self::CheckedDiv => "checked_div".to_owned(),
+      Self::WrappingAdd => "wrapping_add".to_owned(),
+      Self::WrappingSub => "wrapping_sub".to_owned(),
+      Self::WrappingMul => "wrapping_mul".to_owned(),
+      Self::SaturatingAdd => "saturating_add".to_owned(),
+      Self::SaturatingSub => "saturating_sub".to_owned(),
+      Self::SaturatingMul => "saturating_mul".to_owned(),
     }
   }
 
@@ -146,6 +174,20 @@ impl U8Op {
       "to_Bits" => Some(Self::ToBits),
       "to_Bytes" => Some(Self::ToBytes),
       "to_Char" => Some(Self::ToChar),
+      "leading_zeros" => Some(Self::LeadingZeros),
+      "trailing_zeros" => Some(Self::TrailingZeros),
+      "reverse_bits" => Some(Self::ReverseBits),
+      "swap_bytes" => Some(Self::SwapBytes),
+      "checked_add" => Some(Self::CheckedAdd),
+      "checked_sub" => Some(Self::CheckedSub),
+      "checked_mul" => Some(Self::CheckedMul),
+      "checked_div" => Some(Self::CheckedDiv),
+      "wrapping_add" => Some(Self::WrappingAdd),
+      "wrapping_sub" => Some(Self::WrappingSub),
+      "wrapping_mul" => Some(Self::WrappingMul),
+      "saturating_add" => Some(Self::SaturatingAdd),
+      "saturating_sub" => Some(Self::SaturatingSub),
+      "saturating_mul" => Some(Self::SaturatingMul),
       _ => None,
     }
   }
@@ -190,6 +232,22 @@ impl U8Op {
       Self::ToBits => yatima!("∀ #U8 -> #Bits"),
       Self::ToBytes => yatima!("∀ #U8 -> #Bytes"),
       Self::ToChar => yatima!("∀ #U8 -> #Char"),
+      Self::LeadingZeros => yatima!("∀ #U8 -> #U32"),
+      Self::TrailingZeros => yatima!("∀ #U8 -> #U32"),
+      Self::ReverseBits => yatima!("∀ #U8 -> #U8"),
+      Self::SwapBytes => yatima!("∀ #U8 -> #U8"),
+      Self::CheckedAdd
+      | Self::CheckedSub
+      | Self::CheckedMul
+      | Self::CheckedDiv => {
+        yatima!("∀ #U8 #U8 (0 P: Type) (none: P) (some: ∀ #U8 -> P) -> P")
+      }
+      Self::WrappingAdd => yatima!("∀ #U8 #U8 -> #U8"),
+      Self::WrappingSub => yatima!("∀ #U8 #U8 -> #U8"),
+      Self::WrappingMul => yatima!("∀ #U8 #U8 -> #U8"),
+      Self::SaturatingAdd => yatima!("∀ #U8 #U8 -> #U8"),
+      Self::SaturatingSub => yatima!("∀ #U8 #U8 -> #U8"),
+      Self::SaturatingMul => yatima!("∀ #U8 #U8 -> #U8"),
     }
   }
 
@@ -233,6 +291,20 @@ impl U8Op {
       Self::ToBits => Ipld::Integer(34),
       Self::ToBytes => Ipld::Integer(35),
       Self::ToChar => Ipld::Integer(36),
+      Self::LeadingZeros => Ipld::Integer(37),
+      Self::TrailingZeros => Ipld::Integer(38),
+      Self::ReverseBits => Ipld::Integer(39),
+      Self::SwapBytes => Ipld::Integer(40),
+      Self::CheckedAdd => Ipld::Integer(41),
+      Self::CheckedSub => Ipld::Integer(42),
+      Self::CheckedMul => Ipld::Integer(43),
+      Self::CheckedDiv => Ipld::Integer(44),
+      Self::WrappingAdd => Ipld::Integer(45),
+      Self::WrappingSub => Ipld::Integer(46),
+      Self::WrappingMul => Ipld::Integer(47),
+      Self::SaturatingAdd => Ipld::Integer(48),
+      Self::SaturatingSub => Ipld::Integer(49),
+      Self::SaturatingMul => Ipld::Integer(50),
     }
   }
 
@@ -276,6 +348,20 @@ impl U8Op {
       Ipld::Integer(34) => Ok(Self::ToBits),
       Ipld::Integer(35) => Ok(Self::ToBytes),
       Ipld::Integer(36) => Ok(Self::ToChar),
+      Ipld::Integer(37) => Ok(Self::LeadingZeros),
+      Ipld::Integer(38) => Ok(Self::TrailingZeros),
+      Ipld::Integer(39) => Ok(Self::ReverseBits),
+      Ipld::Integer(40) => Ok(Self::SwapBytes),
+      Ipld::Integer(41) => Ok(Self::CheckedAdd),
+      Ipld::Integer(42) => Ok(Self::CheckedSub),
+      Ipld::Integer(43) => Ok(Self::CheckedMul),
+      Ipld::Integer(44) => Ok(Self::CheckedDiv),
+      Ipld::Integer(45) => Ok(Self::WrappingAdd),
+      Ipld::Integer(46) => Ok(Self::WrappingSub),
+      Ipld::Integer(47) => Ok(Self::WrappingMul),
+      Ipld::Integer(48) => Ok(Self::SaturatingAdd),
+      Ipld::Integer(49) => Ok(Self::SaturatingSub),
+      Ipld::Integer(50) => Ok(Self::SaturatingMul),
       xs => Err(IpldError::NatOp(xs.to_owned())),
     }
   }
@@ -307,6 +393,20 @@ impl U8Op {
       Self::CountZeros => 1,
       Self::CountOnes => 1,
       Self::ToChar => 1,
+      Self::LeadingZeros => 1,
+      Self::TrailingZeros => 1,
+      Self::ReverseBits => 1,
+      Self::SwapBytes => 1,
+      Self::CheckedAdd => 2,
+      Self::CheckedSub => 2,
+      Self::CheckedMul => 2,
+      Self::CheckedDiv => 2,
+      Self::WrappingAdd => 2,
+      Self::WrappingSub => 2,
+      Self::WrappingMul => 2,
+      Self::SaturatingAdd => 2,
+      Self::SaturatingSub => 2,
+      Self::SaturatingMul => 2,
       Self::ToU16 => 1,
       Self::ToU32 => 1,
       Self::ToU64 => 1,
@@ -323,61 +423,56 @@ impl U8Op {
     }
   }
 
-  /// Applies a nullary operation to a literal and returns it if successful
-  pub fn apply0(self) -> Option<Literal> {
-    use Literal::*;
-    match self {
-      Self::Max => Some(U8(u8::MAX)),
-      Self::Min => Some(U8(u8::MIN)),
-      _ => None,
-    }
-  }
-
-  /// Applies a unary operation to a literal and returns it if successful
-  pub fn apply1(self, x: &Literal) -> Option<Literal> {
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
     use Literal::*;
-    match (self, x) {
-      (Self::CountZeros, U8(x)) => Some(U32(x.count_zeros())),
-      (Self::CountOnes, U8(x)) => Some(U32(x.count_ones())),
-      (Self::ToChar, U8(x)) => Some(Char((*x).into())),
-      (Self::ToU16, U8(x)) => Some(U16((*x).into())),
-      (Self::ToU32, U8(x)) => Some(U32((*x).into())),
-      (Self::ToU64, U8(x)) => Some(U64((*x).into())),
-      (Self::ToU128, U8(x)) => Some(U128((*x).into())),
-      (Self::ToNat, U8(x)) => {
+    match (self, args) {
+      (Self::Max, []) => Some(U8(u8::MAX)),
+      (Self::Min, []) => Some(U8(u8::MIN)),
+      (Self::CountZeros, [U8(x)]) => Some(U32(x.count_zeros())),
+      (Self::CountOnes, [U8(x)]) => Some(U32(x.count_ones())),
+      (Self::LeadingZeros, [U8(x)]) => Some(U32(x.leading_zeros())),
+      (Self::TrailingZeros, [U8(x)]) => Some(U32(x.trailing_zeros())),
+      (Self::ReverseBits, [U8(x)]) => Some(U8(x.reverse_bits())),
+      (Self::SwapBytes, [U8(x)]) => Some(U8(x.swap_bytes())),
+      (Self::ToChar, [U8(x)]) => Some(Char((*x).into())),
+      (Self::ToU16, [U8(x)]) => Some(U16((*x).into())),
+      (Self::ToU32, [U8(x)]) => Some(U32((*x).into())),
+      (Self::ToU64, [U8(x)]) => Some(U64((*x).into())),
+      (Self::ToU128, [U8(x)]) => Some(U128((*x).into())),
+      (Self::ToNat, [U8(x)]) => {
         Some(Nat(BigUint::from(u64::try_from(*x).unwrap())))
       }
-      (Self::ToI8, U8(x)) => i8::try_from(*x).ok().map(I8),
-      (Self::ToI16, U8(x)) => Some(I16((*x).into())),
-      (Self::ToI32, U8(x)) => Some(I32((*x).into())),
-      (Self::ToI64, U8(x)) => Some(I64((*x).into())),
-      (Self::ToI128, U8(x)) => Some(I128((*x).into())),
-      (Self::Not, U8(x)) => Some(U8(!x)),
-      (Self::ToInt, U8(x)) => Some(Int((*x).into())),
-      (Self::ToBits, U8(x)) => {
+      (Self::ToI8, [U8(x)]) => i8::try_from(*x).ok().map(I8),
+      (Self::ToI16, [U8(x)]) => Some(I16((*x).into())),
+      (Self::ToI32, [U8(x)]) => Some(I32((*x).into())),
+      (Self::ToI64, [U8(x)]) => Some(I64((*x).into())),
+      (Self::ToI128, [U8(x)]) => Some(I128((*x).into())),
+      (Self::Not, [U8(x)]) => Some(U8(!x)),
+      (Self::ToInt, [U8(x)]) => Some(Int((*x).into())),
+      (Self::ToBits, [U8(x)]) => {
         Some(Bits(bits::bytes_to_bits(8, &x.to_be_bytes().into())))
       }
-      (Self::ToBytes, U8(x)) => Some(Bytes(x.to_be_bytes().into())),
-      _ => None,
-    }
-  }
-
-  /// Applies a binary operation to a literal and returns it if successful
-  pub fn apply2(self, x: &Literal, y: &Literal) -> Option<Literal> {
-    use Literal::*;
-    match (self, x, y) {
-      (Self::Eql, U8(x), U8(y)) => Some(Bool(x == y)),
-      (Self::Lte, U8(x), U8(y)) => Some(Bool(x <= y)),
-      (Self::Lth, U8(x), U8(y)) => Some(Bool(x < y)),
-      (Self::Gth, U8(x), U8(y)) => Some(Bool(x > y)),
-      (Self::Gte, U8(x), U8(y)) => Some(Bool(x >= y)),
-      (Self::And, U8(x), U8(y)) => Some(U8(x & y)),
-      (Self::Or, U8(x), U8(y)) => Some(U8(x | y)),
-      (Self::Xor, U8(x), U8(y)) => Some(U8(x ^ y)),
-      (Self::Add, U8(x), U8(y)) => Some(U8(x.wrapping_add(*y))),
-      (Self::Sub, U8(x), U8(y)) => Some(U8(x.wrapping_sub(*y))),
-      (Self::Mul, U8(x), U8(y)) => Some(U8(x.wrapping_mul(*y))),
-      (Self::Div, U8(x), U8(y)) => {
+      (Self::ToBytes, [U8(x)]) => Some(Bytes(x.to_be_bytes().into())),
+      (Self::Eql, [U8(x), U8(y)]) => Some(Bool(x == y)),
+      (Self::Lte, [U8(x), U8(y)]) => Some(Bool(x <= y)),
+      (Self::Lth, [U8(x), U8(y)]) => Some(Bool(x < y)),
+      (Self::Gth, [U8(x), U8(y)]) => Some(Bool(x > y)),
+      (Self::Gte, [U8(x), U8(y)]) => Some(Bool(x >= y)),
+      (Self::And, [U8(x), U8(y)]) => Some(U8(x & y)),
+      (Self::Or, [U8(x), U8(y)]) => Some(U8(x | y)),
+      (Self::Xor, [U8(x), U8(y)]) => Some(U8(x ^ y)),
+      (Self::Add, [U8(x), U8(y)]) => Some(U8(x.wrapping_add(*y))),
+      (Self::Sub, [U8(x), U8(y)]) => Some(U8(x.wrapping_sub(*y))),
+      (Self::Mul, [U8(x), U8(y)]) => Some(U8(x.wrapping_mul(*y))),
+      (Self::WrappingAdd, [U8(x), U8(y)]) => Some(U8(x.wrapping_add(*y))),
+      (Self::WrappingSub, [U8(x), U8(y)]) => Some(U8(x.wrapping_sub(*y))),
+      (Self::WrappingMul, [U8(x), U8(y)]) => Some(U8(x.wrapping_mul(*y))),
+      (Self::SaturatingAdd, [U8(x), U8(y)]) => Some(U8(x.saturating_add(*y))),
+      (Self::SaturatingSub, [U8(x), U8(y)]) => Some(U8(x.saturating_sub(*y))),
+      (Self::SaturatingMul, [U8(x), U8(y)]) => Some(U8(x.saturating_mul(*y))),
+      (Self::Div, [U8(x), U8(y)]) => {
         if *y == 0 {
           None
         }
@@ -385,7 +480,7 @@ impl U8Op {
           Some(U8(x.wrapping_div(*y)))
         }
       }
-      (Self::Mod, U8(x), U8(y)) => {
+      (Self::Mod, [U8(x), U8(y)]) => {
         if *y == 0 {
           None
         }
@@ -393,11 +488,24 @@ impl U8Op {
           Some(U8(x.wrapping_rem(*y)))
         }
       }
-      (Self::Pow, U8(x), U32(y)) => Some(U8(x.wrapping_pow(*y))),
-      (Self::Shl, U32(x), U8(y)) => Some(U8(y.wrapping_shl(*x))),
-      (Self::Shr, U32(x), U8(y)) => Some(U8(y.wrapping_shr(*x))),
-      (Self::Rol, U32(x), U8(y)) => Some(U8(y.rotate_left(*x))),
-      (Self::Ror, U32(x), U8(y)) => Some(U8(y.rotate_right(*x))),
+      (Self::Pow, [U8(x), U32(y)]) => Some(U8(x.wrapping_pow(*y))),
+      (Self::Shl, [U32(x), U8(y)]) => Some(U8(y.wrapping_shl(*x))),
+      (Self::Shr, [U32(x), U8(y)]) => Some(U8(y.wrapping_shr(*x))),
+      (Self::Rol, [U32(x), U8(y)]) => Some(U8(y.rotate_left(*x))),
+      (Self::Ror, [U32(x), U8(y)]) => Some(U8(y.rotate_right(*x))),
+      _ => None,
+    }
+  }
+
+  /// Applies a checked binary operation, returning `Some(None)` on overflow or
+  /// division by zero and `None` if the arguments are invalid
+  pub fn apply_checked(self, args: &[&Literal]) -> Option<Option<Literal>> {
+    use Literal::*;
+    match (self, args) {
+      (Self::CheckedAdd, [U8(x), U8(y)]) => Some(x.checked_add(*y).map(U8)),
+      (Self::CheckedSub, [U8(x), U8(y)]) => Some(x.checked_sub(*y).map(U8)),
+      (Self::CheckedMul, [U8(x), U8(y)]) => Some(x.checked_mul(*y).map(U8)),
+      (Self::CheckedDiv, [U8(x), U8(y)]) => Some(x.checked_div(*y).map(U8)),
       _ => None,
     }
   }
@@ -445,7 +553,7 @@ pub mod tests {
   impl Arbitrary for U8Op {
     fn arbitrary(_g: &mut Gen) -> Self {
       let mut rng = rand::thread_rng();
-      let gen: u32 = rng.gen_range(0..=34);
+      let gen: u32 = rng.gen_range(0..=48);
       match gen {
         0 => Self::Max,
         1 => Self::Min,
@@ -481,7 +589,21 @@ pub mod tests {
         31 => Self::ToI64,
         32 => Self::ToInt,
         33 => Self::ToBytes,
-        _ => Self::ToBits,
+        34 => Self::ToBits,
+        35 => Self::LeadingZeros,
+        36 => Self::TrailingZeros,
+        37 => Self::ReverseBits,
+        38 => Self::SwapBytes,
+        39 => Self::CheckedAdd,
+        40 => Self::CheckedSub,
+        41 => Self::CheckedMul,
+        42 => Self::CheckedDiv,
+        43 => Self::WrappingAdd,
+        44 => Self::WrappingSub,
+        45 => Self::WrappingMul,
+        46 => Self::SaturatingAdd,
+        47 => Self::SaturatingSub,
+        _ => Self::SaturatingMul,
         /* 27 => Self::ToU128,
          * 33 => Self::ToI128, */
       }
@@ -499,23 +621,29 @@ pub mod tests {
   #[quickcheck]
   fn test_apply(op: U8Op, a: u8, b: u8, c: u32) -> TestResult {
     let apply0_go = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U8Op::apply0(op) == expected)
+      TestResult::from_bool(U8Op::apply(op, &[]) == expected)
     };
 
     let apply1_u8 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U8Op::apply1(op, &U8(a)) == expected)
+      TestResult::from_bool(U8Op::apply(op, &[&U8(a)]) == expected)
     };
 
     let apply2_u8_u8 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U8Op::apply2(op, &U8(a), &U8(b)) == expected)
+      TestResult::from_bool(U8Op::apply(op, &[&U8(a), &U8(b)]) == expected)
+    };
+
+    let apply2_checked = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        U8Op::apply_checked(op, &[&U8(a), &U8(b)]) == Some(expected),
+      )
     };
 
     let apply2_u8_u32 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U8Op::apply2(op, &U8(a), &U32(c)) == expected)
+      TestResult::from_bool(U8Op::apply(op, &[&U8(a), &U32(c)]) == expected)
     };
 
     let apply2_u32_u8 = |expected: Option<Literal>| -> TestResult {
-      TestResult::from_bool(U8Op::apply2(op, &U32(c), &U8(a)) == expected)
+      TestResult::from_bool(U8Op::apply(op, &[&U32(c), &U8(a)]) == expected)
     };
 
     let from_bool = TestResult::from_bool;
@@ -541,6 +669,16 @@ pub mod tests {
       U8Op::Mod => {
         apply2_u8_u8(if b == 0 { None } else { Some(U8(a.wrapping_rem(b))) })
       }
+      U8Op::WrappingAdd => apply2_u8_u8(Some(U8(a.wrapping_add(b)))),
+      U8Op::WrappingSub => apply2_u8_u8(Some(U8(a.wrapping_sub(b)))),
+      U8Op::WrappingMul => apply2_u8_u8(Some(U8(a.wrapping_mul(b)))),
+      U8Op::SaturatingAdd => apply2_u8_u8(Some(U8(a.saturating_add(b)))),
+      U8Op::SaturatingSub => apply2_u8_u8(Some(U8(a.saturating_sub(b)))),
+      U8Op::SaturatingMul => apply2_u8_u8(Some(U8(a.saturating_mul(b)))),
+      U8Op::CheckedAdd => apply2_checked(a.checked_add(b).map(U8)),
+      U8Op::CheckedSub => apply2_checked(a.checked_sub(b).map(U8)),
+      U8Op::CheckedMul => apply2_checked(a.checked_mul(b).map(U8)),
+      U8Op::CheckedDiv => apply2_checked(a.checked_div(b).map(U8)),
       U8Op::Pow => apply2_u8_u32(Some(U8(a.wrapping_pow(c)))),
       U8Op::Shl => apply2_u32_u8(Some(U8(a.wrapping_shl(c)))),
       U8Op::Shr => apply2_u32_u8(Some(U8(a.wrapping_shr(c)))),
@@ -548,16 +686,20 @@ pub mod tests {
       U8Op::Ror => apply2_u32_u8(Some(U8(a.rotate_right(c)))),
       U8Op::CountZeros => apply1_u8(Some(U32(a.count_zeros()))),
       U8Op::CountOnes => apply1_u8(Some(U32(a.count_ones()))),
+      U8Op::LeadingZeros => apply1_u8(Some(U32(a.leading_zeros()))),
+      U8Op::TrailingZeros => apply1_u8(Some(U32(a.trailing_zeros()))),
+      U8Op::ReverseBits => apply1_u8(Some(U8(a.reverse_bits()))),
+      U8Op::SwapBytes => apply1_u8(Some(U8(a.swap_bytes()))),
       U8Op::ToU16 => from_bool(
-        U16Op::apply1(U16Op::ToU8, &U8Op::apply1(op, &U8(a)).unwrap())
+        U16Op::apply(U16Op::ToU8, &[&U8Op::apply(op, &[&U8(a)]).unwrap()])
           == Some(U8(a)),
       ),
       U8Op::ToU32 => from_bool(
-        U32Op::apply1(U32Op::ToU8, &U8Op::apply1(op, &U8(a)).unwrap())
+        U32Op::apply(U32Op::ToU8, &[&U8Op::apply(op, &[&U8(a)]).unwrap()])
           == Some(U8(a)),
       ),
       U8Op::ToU64 => from_bool(
-        U64Op::apply1(U64Op::ToU8, &U8Op::apply1(op, &U8(a)).unwrap())
+        U64Op::apply(U64Op::ToU8, &[&U8Op::apply(op, &[&U8(a)]).unwrap()])
           == Some(U8(a)),
       ),
       U8Op::ToU128 => TestResult::discard(),
@@ -565,22 +707,22 @@ pub mod tests {
         apply1_u8(Some(Nat(BigUint::from(u64::try_from(a).unwrap()))))
       }
       U8Op::ToI8 => from_bool(if a > i8::MAX.try_into().unwrap() {
-        U8Op::apply1(op, &U8(a)) == None
+        U8Op::apply(op, &[&U8(a)]) == None
       }
       else {
-        I8Op::apply1(I8Op::ToU8, &U8Op::apply1(op, &U8(a)).unwrap())
+        I8Op::apply(I8Op::ToU8, &[&U8Op::apply(op, &[&U8(a)]).unwrap()])
           == Some(U8(a))
       }),
       U8Op::ToI16 => from_bool(
-        I16Op::apply1(I16Op::ToU8, &U8Op::apply1(op, &U8(a)).unwrap())
+        I16Op::apply(I16Op::ToU8, &[&U8Op::apply(op, &[&U8(a)]).unwrap()])
           == Some(U8(a)),
       ),
       U8Op::ToI32 => from_bool(
-        I32Op::apply1(I32Op::ToU8, &U8Op::apply1(op, &U8(a)).unwrap())
+        I32Op::apply(I32Op::ToU8, &[&U8Op::apply(op, &[&U8(a)]).unwrap()])
           == Some(U8(a)),
       ),
       U8Op::ToI64 => from_bool(
-        I64Op::apply1(I64Op::ToU8, &U8Op::apply1(op, &U8(a)).unwrap())
+        I64Op::apply(I64Op::ToU8, &[&U8Op::apply(op, &[&U8(a)]).unwrap()])
           == Some(U8(a)),
       ),
       U8Op::ToI128 => TestResult::discard(),
@@ -606,13 +748,18 @@ pub mod tests {
         TestResult::discard()
       }
       else {
-        TestResult::from_bool(U8Op::apply1(op, &a) == None)
+        TestResult::from_bool(U8Op::apply(op, &[&a]) == None)
       }
     };
 
     let test_apply2_none_on_invalid =
       |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
-        let go = || TestResult::from_bool(U8Op::apply2(op, &a_, &b_) == None);
+        let go = || {
+          TestResult::from_bool(
+            U8Op::apply(op, &[&a_, &b_]) == None
+              && U8Op::apply_checked(op, &[&a_, &b_]) == None,
+          )
+        };
         if test_arg_2 {
           if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
             TestResult::discard()
@@ -638,6 +785,10 @@ pub mod tests {
       U8Op::Not
       | U8Op::CountZeros
       | U8Op::CountOnes
+      | U8Op::LeadingZeros
+      | U8Op::TrailingZeros
+      | U8Op::ReverseBits
+      | U8Op::SwapBytes
       | U8Op::ToU16
       | U8Op::ToU32
       | U8Op::ToU64
@@ -665,7 +816,17 @@ pub mod tests {
       | U8Op::Sub
       | U8Op::Mul
       | U8Op::Div
-      | U8Op::Mod => {
+      | U8Op::Mod
+      | U8Op::CheckedAdd
+      | U8Op::CheckedSub
+      | U8Op::CheckedMul
+      | U8Op::CheckedDiv
+      | U8Op::WrappingAdd
+      | U8Op::WrappingSub
+      | U8Op::WrappingMul
+      | U8Op::SaturatingAdd
+      | U8Op::SaturatingSub
+      | U8Op::SaturatingMul => {
         if test_arg_2 {
           test_apply2_none_on_invalid(U8(b), a, U8(b))
         }
@@ -0,0 +1,445 @@
+use num_bigint::BigUint;
+use sp_im::Vector;
+use sp_ipld::Ipld;
+use sp_std::{
+  borrow::ToOwned,
+  fmt,
+  vec::Vec,
+};
+
+use alloc::string::String;
+
+use crate::{
+  defs,
+  ipld_error::IpldError,
+  literal::Literal,
+  parse,
+  prim::list_term,
+  term::Term,
+  yatima,
+};
+
+use core::convert::TryFrom;
+
+/// Primitive operations on persistent vectors of bytes. Vectors are RRB
+/// trees, so indexing and updates don't copy the whole vector
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum VecOp {
+  Empty,
+  Get,
+  Set,
+  Push,
+  Len,
+  Slice,
+  ToList,
+}
+
+impl VecOp {
+  /// Gets the syntax string of a vector operation
+  pub fn symbol(self) -> String {
+    match self {
+      Self::Empty => "empty".to_owned(),
+      Self::Get => "get".to_owned(),
+      Self::Set => "set".to_owned(),
+      Self::Push => "push".to_owned(),
+      Self::Len => "len".to_owned(),
+      Self::Slice => "slice".to_owned(),
+      Self::ToList => "to_list".to_owned(),
+    }
+  }
+
+  /// Gets a vector operation from a syntax string
+  pub fn from_symbol(x: &str) -> Option<Self> {
+    match x {
+      "empty" => Some(Self::Empty),
+      "get" => Some(Self::Get),
+      "set" => Some(Self::Set),
+      "push" => Some(Self::Push),
+      "len" => Some(Self::Len),
+      "slice" => Some(Self::Slice),
+      "to_list" => Some(Self::ToList),
+      _ => None,
+    }
+  }
+
+  /// Returns the type of a vector operation
+  pub fn type_of(self) -> Term {
+    match self {
+      Self::Empty => yatima!("#Vector"),
+      Self::Get => yatima!("∀ #Nat #Vector -> #Bytes"),
+      Self::Set => yatima!("∀ #Nat #Bytes #Vector -> #Vector"),
+      Self::Push => yatima!("∀ #Bytes #Vector -> #Vector"),
+      Self::Len => yatima!("∀ #Vector -> #Nat"),
+      Self::Slice => yatima!("∀ #Nat #Nat #Vector -> #Vector"),
+      Self::ToList => {
+        yatima!("∀ #Vector (0 P: Type) (nil: P) (cons: ∀ #Bytes P -> P) -> P")
+      }
+    }
+  }
+
+  /// Converts a vector operation into an IPLD object
+  pub fn to_ipld(self) -> Ipld {
+    match self {
+      Self::Empty => Ipld::Integer(0),
+      Self::Get => Ipld::Integer(1),
+      Self::Set => Ipld::Integer(2),
+      Self::Push => Ipld::Integer(3),
+      Self::Len => Ipld::Integer(4),
+      Self::Slice => Ipld::Integer(5),
+      Self::ToList => Ipld::Integer(6),
+    }
+  }
+
+  /// Converts an IPLD object into a vector operation
+  pub fn from_ipld(ipld: &Ipld) -> Result<Self, IpldError> {
+    match ipld {
+      Ipld::Integer(0) => Ok(Self::Empty),
+      Ipld::Integer(1) => Ok(Self::Get),
+      Ipld::Integer(2) => Ok(Self::Set),
+      Ipld::Integer(3) => Ok(Self::Push),
+      Ipld::Integer(4) => Ok(Self::Len),
+      Ipld::Integer(5) => Ok(Self::Slice),
+      Ipld::Integer(6) => Ok(Self::ToList),
+      xs => Err(IpldError::VecOp(xs.to_owned())),
+    }
+  }
+
+  /// Returns the number of parameters used in the operation
+  pub fn arity(self) -> u64 {
+    match self {
+      Self::Empty => 0,
+      Self::Get => 2,
+      Self::Set => 3,
+      Self::Push => 2,
+      Self::Len => 1,
+      Self::Slice => 3,
+      Self::ToList => 1,
+    }
+  }
+
+  /// Applies an operation to as many literals as its arity and returns the
+  /// result if successful
+  pub fn apply(self, args: &[&Literal]) -> Option<Literal> {
+    use Literal::*;
+    match (self, args) {
+      (Self::Empty, []) => Some(Literal::Vector(sp_im::Vector::new())),
+      (Self::Len, [Vector(xs)]) => Some(Nat(xs.len().into())),
+      (Self::Get, [Nat(idx), Vector(xs)]) => {
+        let idx = usize::try_from(idx).ok()?;
+        xs.get(idx).map(|x| Bytes(x.clone()))
+      }
+      (Self::Push, [Bytes(x), Vector(xs)]) => {
+        let mut xs = xs.clone();
+        xs.push_back(x.clone());
+        Some(Vector(xs))
+      }
+      (Self::Set, [Nat(idx), Bytes(x), Vector(xs)]) => {
+        match usize::try_from(idx) {
+          Ok(idx) if idx < xs.len() => Some(Vector(xs.update(idx, x.clone()))),
+          _ => None,
+        }
+      }
+      (Self::Slice, [Nat(from), Nat(upto), Vector(xs)]) => {
+        Some(Vector(safe_slice(from, upto, xs)))
+      }
+      _ => None,
+    }
+  }
+
+  /// Applies an operation whose result is a term rather than a literal to
+  /// as many literals as its arity and returns it if successful
+  pub fn apply_term(self, args: &[&Literal]) -> Option<Term> {
+    use Literal::*;
+    match (self, args) {
+      (Self::ToList, [Vector(xs)]) => {
+        Some(list_term(xs.iter().map(|x| Bytes(x.clone())).collect()))
+      }
+      _ => None,
+    }
+  }
+}
+
+/// Gets the elements between two indices, clamping both to the length
+pub fn safe_slice(
+  from: &BigUint,
+  upto: &BigUint,
+  xs: &Vector<Vec<u8>>,
+) -> Vector<Vec<u8>> {
+  let clamp =
+    |x: &BigUint| usize::try_from(x).map_or(xs.len(), |x| x.min(xs.len()));
+  let (from, upto) = (clamp(from), clamp(upto));
+  if from > upto {
+    Vector::new()
+  }
+  else {
+    xs.skip(from).take(upto - from)
+  }
+}
+
+impl fmt::Display for VecOp {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.symbol())
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::prim::tests::TestArg3;
+  use quickcheck::{
+    Arbitrary,
+    Gen,
+    TestResult,
+  };
+  use rand::Rng;
+  use sp_std::mem;
+  use Literal::{
+    Bytes,
+    Nat,
+    Vector,
+  };
+  impl Arbitrary for VecOp {
+    fn arbitrary(_g: &mut Gen) -> Self {
+      let mut rng = rand::thread_rng();
+      let gen: u32 = rng.gen_range(0..=6);
+      match gen {
+        0 => Self::Empty,
+        1 => Self::Get,
+        2 => Self::Set,
+        3 => Self::Push,
+        4 => Self::Len,
+        5 => Self::Slice,
+        _ => Self::ToList,
+      }
+    }
+  }
+
+  #[quickcheck]
+  fn vec_op_ipld(x: VecOp) -> bool {
+    match VecOp::from_ipld(&x.to_ipld()) {
+      Ok(y) => x == y,
+      _ => false,
+    }
+  }
+
+  #[test]
+  fn test_safe_slice() {
+    let xs: sp_im::Vector<Vec<u8>> = (0..5u8).map(|x| vec![x]).collect();
+    let slice = |from: u64, upto: u64| -> Vec<Vec<u8>> {
+      safe_slice(&from.into(), &upto.into(), &xs).into_iter().collect()
+    };
+    assert_eq!(slice(1, 3), vec![vec![1], vec![2]]);
+    assert_eq!(slice(3, 9), vec![vec![3], vec![4]]);
+    assert_eq!(slice(3, 1), Vec::<Vec<u8>>::new());
+    assert_eq!(slice(7, 9), Vec::<Vec<u8>>::new());
+  }
+
+  #[quickcheck]
+  fn test_apply(
+    op: VecOp,
+    a: Vec<u8>,
+    b: Vec<Vec<u8>>,
+    c: u64,
+    d: u64,
+  ) -> TestResult {
+    let xs: sp_im::Vector<Vec<u8>> = b.iter().cloned().collect();
+    let apply1_vec = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        VecOp::apply(op, &[&Vector(xs.clone())]) == expected,
+      )
+    };
+
+    let apply1_term_vec = |expected: Option<Term>| -> TestResult {
+      TestResult::from_bool(
+        VecOp::apply_term(op, &[&Vector(xs.clone())]) == expected,
+      )
+    };
+
+    let apply2_nat_vec = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        VecOp::apply(op, &[&Nat(c.into()), &Vector(xs.clone())]) == expected,
+      )
+    };
+
+    let apply2_bytes_vec = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        VecOp::apply(op, &[&Bytes(a.clone()), &Vector(xs.clone())]) == expected,
+      )
+    };
+
+    let apply3_nat_bytes_vec = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        VecOp::apply(op, &[
+          &Nat(c.into()),
+          &Bytes(a.clone()),
+          &Vector(xs.clone()),
+        ]) == expected,
+      )
+    };
+
+    let apply3_nat_nat_vec = |expected: Option<Literal>| -> TestResult {
+      TestResult::from_bool(
+        VecOp::apply(op, &[
+          &Nat(c.into()),
+          &Nat(d.into()),
+          &Vector(xs.clone()),
+        ]) == expected,
+      )
+    };
+
+    let idx = usize::try_from(c).ok().filter(|idx| *idx < b.len());
+    match op {
+      VecOp::Empty => TestResult::from_bool(
+        VecOp::apply(op, &[]) == Some(Vector(sp_im::Vector::new())),
+      ),
+      VecOp::Get => apply2_nat_vec(idx.map(|idx| Bytes(b[idx].clone()))),
+      VecOp::Set => apply3_nat_bytes_vec(idx.map(|idx| {
+        let mut b = b.clone();
+        b[idx] = a.clone();
+        Vector(b.into_iter().collect())
+      })),
+      VecOp::Push => apply2_bytes_vec(Some(Vector({
+        let mut b = b.clone();
+        b.push(a.clone());
+        b.into_iter().collect()
+      }))),
+      VecOp::Len => apply1_vec(Some(Nat(b.len().into()))),
+      VecOp::Slice => {
+        apply3_nat_nat_vec(Some(Vector(safe_slice(&c.into(), &d.into(), &xs))))
+      }
+      VecOp::ToList => apply1_term_vec(Some(list_term(
+        b.iter().map(|x| Bytes(x.clone())).collect(),
+      ))),
+    }
+  }
+
+  #[quickcheck]
+  fn test_apply_none_on_invalid(
+    op: VecOp,
+    a: Literal,
+    b: Vec<u8>,
+    c: Vec<Vec<u8>>,
+    d: u64,
+    test_arg_2: bool,
+    test_arg_3: TestArg3,
+  ) -> TestResult {
+    let xs = || Vector(c.iter().cloned().collect());
+    let test_apply1_none_on_invalid = |valid_arg: Literal| -> TestResult {
+      if mem::discriminant(&valid_arg) == mem::discriminant(&a) {
+        TestResult::discard()
+      }
+      else {
+        TestResult::from_bool(
+          VecOp::apply(op, &[&a]) == None
+            && VecOp::apply_term(op, &[&a]) == None,
+        )
+      }
+    };
+
+    let test_apply2_none_on_invalid =
+      |valid_arg: Literal, a_: Literal, b_: Literal| -> TestResult {
+        let go =
+          || TestResult::from_bool(VecOp::apply(op, &[&a_, &b_]) == None);
+        if test_arg_2 {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        else {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      };
+
+    let test_apply3_none_on_invalid = |valid_arg: Literal,
+                                       a_: Literal,
+                                       b_: Literal,
+                                       c_: Literal|
+     -> TestResult {
+      let go =
+        || TestResult::from_bool(VecOp::apply(op, &[&a_, &b_, &c_]) == None);
+      match test_arg_3 {
+        TestArg3::A => {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&a_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        TestArg3::B => {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&b_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+        TestArg3::C => {
+          if mem::discriminant(&valid_arg) == mem::discriminant(&c_) {
+            TestResult::discard()
+          }
+          else {
+            go()
+          }
+        }
+      }
+    };
+
+    match op {
+      // Arity 0.
+      VecOp::Empty => TestResult::discard(),
+      // Arity 1, valid is Vector.
+      VecOp::Len | VecOp::ToList => test_apply1_none_on_invalid(xs()),
+      // Arity 2, valid are Nat on a and Vector on b.
+      VecOp::Get => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(Nat(d.into()), a, xs())
+        }
+        else {
+          test_apply2_none_on_invalid(xs(), Nat(d.into()), a)
+        }
+      }
+      // Arity 2, valid are Bytes on a and Vector on b.
+      VecOp::Push => {
+        if test_arg_2 {
+          test_apply2_none_on_invalid(Bytes(b.clone()), a, xs())
+        }
+        else {
+          test_apply2_none_on_invalid(xs(), Bytes(b.clone()), a)
+        }
+      }
+      // Arity 3, valid are Nat on a, Bytes on b and Vector on c.
+      VecOp::Set => match test_arg_3 {
+        TestArg3::A => {
+          test_apply3_none_on_invalid(Nat(d.into()), a, Bytes(b.clone()), xs())
+        }
+        TestArg3::B => {
+          test_apply3_none_on_invalid(Bytes(b.clone()), Nat(d.into()), a, xs())
+        }
+        TestArg3::C => {
+          test_apply3_none_on_invalid(xs(), Nat(d.into()), Bytes(b.clone()), a)
+        }
+      },
+      // Arity 3, valid are Nat on a and b and Vector on c.
+      VecOp::Slice => match test_arg_3 {
+        TestArg3::A => {
+          test_apply3_none_on_invalid(Nat(d.into()), a, Nat(d.into()), xs())
+        }
+        TestArg3::B => {
+          test_apply3_none_on_invalid(Nat(d.into()), Nat(d.into()), a, xs())
+        }
+        TestArg3::C => {
+          test_apply3_none_on_invalid(xs(), Nat(d.into()), Nat(d.into()), a)
+        }
+      },
+    }
+  }
+}
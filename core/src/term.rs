@@ -2,6 +2,7 @@ pub use crate::{
   anon::Anon,
   defs,
   embed_error::EmbedError,
+  level::Level,
   literal::{
     LitType,
     Literal,
@@ -37,8 +38,8 @@ pub enum Term {
   Lam(Pos, Name, Box<Term>),
   /// Application of a function to an argument
   App(Pos, Box<(Term, Term)>),
-  /// Forall
-  All(Pos, Uses, Name, Box<(Term, Term)>),
+  /// Forall, which is implicit if the flag is set
+  All(Pos, bool, Uses, Name, Box<(Term, Term)>),
   /// Self type
   Slf(Pos, Name, Box<Term>),
   /// Self type constructor
@@ -49,8 +50,8 @@ pub enum Term {
   Ref(Pos, Name, Cid, Cid),
   /// Inline local definition
   Let(Pos, bool, Uses, Name, Box<(Term, Term, Term)>),
-  /// Type of types
-  Typ(Pos),
+  /// Type of types, at a universe level
+  Typ(Pos, Level),
   /// Type annotation
   Ann(Pos, Box<(Term, Term)>),
   /// Primitive literal
@@ -69,8 +70,8 @@ impl fmt::Debug for Term {
       Self::Var(_, n, i) => fmt.debug_tuple("Var").field(&n).field(i).finish(),
       Self::Lam(_, n, b) => fmt.debug_tuple("Lam").field(&n).field(&b).finish(),
       Self::App(_, t) => fmt.debug_tuple("App").field(&t).finish(),
-      Self::All(_, u, n, t) => {
-        fmt.debug_tuple("All").field(&u).field(&n).field(&t).finish()
+      Self::All(_, i, u, n, t) => {
+        fmt.debug_tuple("All").field(i).field(&u).field(&n).field(&t).finish()
       }
       Self::Slf(_, n, b) => fmt.debug_tuple("Slf").field(&n).field(&b).finish(),
       Self::Dat(_, b) => fmt.debug_tuple("Dat").field(&b).finish(),
@@ -81,7 +82,7 @@ impl fmt::Debug for Term {
       Self::Let(_, r, u, n, t) => {
         fmt.debug_tuple("Let").field(r).field(&u).field(&n).field(&t).finish()
       }
-      Self::Typ(_) => write!(fmt, "Typ(..)"),
+      Self::Typ(_, l) => fmt.debug_tuple("Typ").field(&l).finish(),
       Self::Ann(_, t) => fmt.debug_tuple("Ann").field(&t).finish(),
       Self::Lit(_, a) => fmt.debug_tuple("Lit").field(&a).finish(),
       Self::LTy(_, a) => fmt.debug_tuple("LTy").field(&a).finish(),
@@ -97,8 +98,8 @@ impl PartialEq for Term {
       (Self::Var(_, na, ia), Self::Var(_, nb, ib)) => na == nb && ia == ib,
       (Self::Lam(_, na, ba), Self::Lam(_, nb, bb)) => na == nb && ba == bb,
       (Self::App(_, ta), Self::App(_, tb)) => ta.0 == tb.0 && ta.1 == tb.1,
-      (Self::All(_, ua, na, ta), Self::All(_, ub, nb, tb)) => {
-        ua == ub && na == nb && ta.0 == tb.0 && ta.1 == tb.1
+      (Self::All(_, ia, ua, na, ta), Self::All(_, ib, ub, nb, tb)) => {
+        ia == ib && ua == ub && na == nb && ta.0 == tb.0 && ta.1 == tb.1
       }
       (Self::Slf(_, na, ba), Self::Slf(_, nb, bb)) => na == nb && ba == bb,
       (Self::Dat(_, ba), Self::Dat(_, bb)) => ba == bb,
@@ -114,7 +115,7 @@ impl PartialEq for Term {
           && ta.1 == tb.1
           && ta.2 == tb.2
       }
-      (Self::Typ(_), Self::Typ(_)) => true,
+      (Self::Typ(_, la), Self::Typ(_, lb)) => la == lb,
       (Self::Rec(_), Self::Rec(_)) => true,
       (Self::Ann(_, ta), Self::Ann(_, tb)) => ta.0 == tb.0 && ta.1 == tb.1,
       (Self::Lit(_, a), Self::Lit(_, b)) => a == b,
@@ -139,7 +140,7 @@ impl Term {
       Term::Dat(pos, _) => *pos,
       Term::Cse(pos, _) => *pos,
       Term::Let(pos, ..) => *pos,
-      Term::Typ(pos) => *pos,
+      Term::Typ(pos, _) => *pos,
       Term::LTy(pos, _) => *pos,
       Term::Lit(pos, _) => *pos,
       Term::Opr(pos, _) => *pos,
@@ -170,10 +171,11 @@ impl Term {
         let (typ, exp) = *typ_exp;
         Self::Ann(pos, Box::new((typ.shift(inc, dep), exp.shift(inc, dep))))
       }
-      Self::All(pos, uses, nam, dom_img) => {
+      Self::All(pos, imp, uses, nam, dom_img) => {
         let (dom, img) = *dom_img;
         Self::All(
           pos,
+          imp,
           uses,
           nam,
           Box::new((dom.shift(inc, dep), img.shift(inc, dep.map(|x| x + 1)))),
@@ -217,10 +219,11 @@ impl Term {
         let (typ, exp) = *typ_exp;
         Self::Ann(pos, Box::new((typ.un_rec(trm.clone()), exp.un_rec(trm))))
       }
-      Self::All(pos, uses, nam, dom_img) => {
+      Self::All(pos, imp, uses, nam, dom_img) => {
         let (dom, img) = *dom_img;
         Self::All(
           pos,
+          imp,
           uses,
           nam,
           Box::new((dom.un_rec(trm.clone()), img.un_rec(trm))),
@@ -258,7 +261,7 @@ impl Term {
       Self::LTy(pos, lty) => (Anon::LTy(*lty), Meta::LTy(*pos)),
       Self::Opr(pos, opr) => (Anon::Opr(opr.clone()), Meta::Opr(*pos)),
       Self::Rec(pos) => (Anon::Rec, Meta::Rec(*pos)),
-      Self::Typ(pos) => (Anon::Typ, Meta::Typ(*pos)),
+      Self::Typ(pos, lvl) => (Anon::Typ(lvl.clone()), Meta::Typ(*pos)),
       Self::Lam(pos, name, body) => {
         let (anon, meta) = (*body).embed();
         (
@@ -297,12 +300,12 @@ impl Term {
         let (anon, meta) = (*body).embed();
         (Anon::Cse(Box::new(anon)), Meta::Cse(*pos, Box::new(meta)))
       }
-      Self::All(pos, uses, name, terms) => {
+      Self::All(pos, imp, uses, name, terms) => {
         let (typ_anon, typ_meta) = terms.0.embed();
         let (bod_anon, bod_meta) = terms.1.embed();
         (
           Anon::All(*uses, Box::new((typ_anon, bod_anon))),
-          Meta::All(*pos, name.clone(), Box::new((typ_meta, bod_meta))),
+          Meta::All(*pos, *imp, name.clone(), Box::new((typ_meta, bod_meta))),
         )
       }
       Self::Let(pos, rec, uses, name, terms) => {
@@ -333,7 +336,7 @@ impl Term {
       (Anon::Lit(lit), Meta::Lit(pos)) => Ok(Self::Lit(*pos, lit.clone())),
       (Anon::LTy(lty), Meta::LTy(pos)) => Ok(Self::LTy(*pos, *lty)),
       (Anon::Opr(opr), Meta::Opr(pos)) => Ok(Self::Opr(*pos, opr.clone())),
-      (Anon::Typ, Meta::Typ(pos)) => Ok(Self::Typ(*pos)),
+      (Anon::Typ(lvl), Meta::Typ(pos)) => Ok(Self::Typ(*pos, lvl.clone())),
       (Anon::Rec, Meta::Rec(pos)) => Ok(Self::Rec(*pos)),
       (Anon::Lam(anon_bod), Meta::Lam(pos, nam, meta_bod)) => {
         let bod = Term::unembed(anon_bod, meta_bod)?;
@@ -365,12 +368,12 @@ impl Term {
         let exp = Term::unembed(exp_anon, exp_meta)?;
         Ok(Self::Ann(*pos, Box::new((typ, exp))))
       }
-      (Anon::All(uses, anon), Meta::All(pos, name, meta)) => {
+      (Anon::All(uses, anon), Meta::All(pos, imp, name, meta)) => {
         let (dom_anon, img_anon) = anon.as_ref();
         let (dom_meta, img_meta) = meta.as_ref();
         let dom = Term::unembed(dom_anon, dom_meta)?;
         let img = Term::unembed(img_anon, img_meta)?;
-        Ok(Self::All(*pos, *uses, name.clone(), Box::new((dom, img))))
+        Ok(Self::All(*pos, *imp, *uses, name.clone(), Box::new((dom, img))))
       }
       (Anon::Let(rec, uses, anon), Meta::Let(pos, name, meta)) => {
         let (typ_anon, exp_anon, bod_anon) = anon.as_ref();
@@ -419,31 +422,42 @@ impl Term {
       }
     }
 
+    fn binder(
+      rec: Option<&String>,
+      ind: bool,
+      imp: bool,
+      use_: &Uses,
+      nam: &str,
+      typ: &Term,
+    ) -> String {
+      let (open, close) = if imp { ("{", "}") } else { ("(", ")") };
+      format!(
+        "{}{}{}: {}{}",
+        open,
+        uses(use_),
+        name(nam),
+        typ.pretty(rec, ind),
+        close
+      )
+    }
+
     fn alls(
       rec: Option<&String>,
       ind: bool,
+      imp: bool,
       use_: &Uses,
       nam: &str,
       typ: &Term,
       bod: &Term,
     ) -> String {
+      let bind = binder(rec, ind, imp, use_, nam, typ);
       match bod {
-        All(_, bod_use, bod_nam, bod) => {
-          format!(
-            " ({}{}: {}){}",
-            uses(use_),
-            name(nam),
-            typ.pretty(rec, ind),
-            alls(rec, ind, bod_use, bod_nam, &bod.0, &bod.1)
-          )
-        }
-        _ => format!(
-          " ({}{}: {}) -> {}",
-          uses(use_),
-          name(nam),
-          typ.pretty(rec, ind),
-          bod.pretty(rec, ind)
+        All(_, bod_imp, bod_use, bod_nam, bod) => format!(
+          " {}{}",
+          bind,
+          alls(rec, ind, *bod_imp, bod_use, bod_nam, &bod.0, &bod.1)
         ),
+        _ => format!(" {} -> {}", bind, bod.pretty(rec, ind)),
       }
     }
 
@@ -506,8 +520,8 @@ impl Term {
         )
       }
       Slf(_, nam, bod) => format!("@{} {}", name(nam), bod.pretty(rec, ind)),
-      All(_, us_, nam, terms) => {
-        format!("∀{}", alls(rec, ind, us_, nam, &terms.0, &terms.1))
+      All(_, imp, us_, nam, terms) => {
+        format!("∀{}", alls(rec, ind, *imp, us_, nam, &terms.0, &terms.1))
       }
       Ann(_, terms) => {
         format!(
@@ -518,7 +532,8 @@ impl Term {
       }
       Dat(_, bod) => format!("data {}", bod.pretty(rec, ind)),
       Cse(_, bod) => format!("case {}", bod.pretty(rec, ind)),
-      Typ(_) => "Type".to_string(),
+      Typ(_, lvl) if lvl.is_zero() => "Type".to_string(),
+      Typ(_, lvl) => format!("Type{{{}}}", lvl),
       Lit(_, lit) => format!("{}", lit),
       LTy(_, lty) => format!("{}", lty),
       Opr(_, opr) => format!("{}", opr),
@@ -604,7 +619,7 @@ pub mod tests {
       .collect();
     let len = refs.len();
     if len == 0 {
-      return Tree::Typ;
+      return Tree::Typ(Level::zero());
     };
     let gen = gen_range(g, 0..(len - 1));
     let (n, _) = refs[gen].clone();
@@ -615,7 +630,7 @@ pub mod tests {
   #[derive(Debug, Clone)]
   pub enum Tree {
     Var(Name, u64),
-    Typ,
+    Typ(Level),
     Rec,
     Ref(Name, Cid, Cid),
     Opr(Op),
@@ -636,7 +651,7 @@ pub mod tests {
       match self {
         Self::Var(n, i) => Term::Var(Pos::None, n.clone(), *i),
         Self::Rec => Term::Rec(Pos::None),
-        Self::Typ => Term::Typ(Pos::None),
+        Self::Typ(lvl) => Term::Typ(Pos::None, lvl.clone()),
         Self::Ref(n, d, a) => Term::Ref(Pos::None, n.clone(), *d, *a),
         Self::Opr(x) => Term::Opr(Pos::None, x.clone()),
         Self::Lit(x) => Term::Lit(Pos::None, x.clone()),
@@ -670,7 +685,7 @@ pub mod tests {
         Self::All(uses, n, dom, img) => {
           let dom = arena[*dom].into_term(&arena);
           let img = arena[*img].into_term(&arena);
-          Term::All(Pos::None, *uses, n.clone(), Box::new((dom, img)))
+          Term::All(Pos::None, false, *uses, n.clone(), Box::new((dom, img)))
         }
         Self::Let(rec, uses, n, typ, trm, bod) => {
           let typ = arena[*typ].into_term(&arena);
@@ -764,7 +779,7 @@ pub mod tests {
 
       match next_case(g, &gens) {
         Case::TYP => {
-          arena[idx] = Some(Tree::Typ);
+          arena[idx] = Some(Tree::Typ(Arbitrary::arbitrary(g)));
         }
         Case::REC => {
           arena[idx] = Some(Tree::Rec);
@@ -0,0 +1,371 @@
+use crate::{
+  defs::Def,
+  name::Name,
+  position::Pos,
+  term::Term,
+};
+
+use sp_std::{
+  cmp::max,
+  collections::btree_set::BTreeSet,
+  fmt,
+  vec::Vec,
+};
+
+/// An error raised by the termination check
+#[derive(PartialEq, Clone, Debug)]
+pub enum TerminationError {
+  /// Along some sequence of the named function's recursive calls, none of
+  /// its arguments keeps getting smaller
+  NoDecrease(Pos, Name),
+}
+
+impl TerminationError {
+  pub fn pos(&self) -> Pos {
+    match self {
+      Self::NoDecrease(pos, _) => *pos,
+    }
+  }
+}
+
+impl fmt::Display for TerminationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::NoDecrease(_, nam) => write!(
+        f,
+        "{} may not terminate, as no argument decreases along some sequence \
+         of its recursive calls. Mark it `partial` if it isn't meant to be \
+         total",
+        nam
+      ),
+    }
+  }
+}
+
+/// How an argument of a call compares in size to a parameter of the caller
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+enum Change {
+  Same,
+  Less,
+}
+
+/// A size-change graph of a call, with an arc `(i, change, j)` when the
+/// argument in position `j` is no larger than the parameter in position `i`,
+/// or strictly smaller if `change` is `Less`
+type Graph = BTreeSet<(usize, Change, usize)>;
+
+/// Drops the arcs implied by a decreasing arc between the same positions
+fn prune(arcs: Graph) -> Graph {
+  arcs
+    .iter()
+    .filter(|(i, c, j)| {
+      *c == Change::Less || !arcs.contains(&(*i, Change::Less, *j))
+    })
+    .copied()
+    .collect()
+}
+
+/// The graph of a call described by `g` followed by one described by `h`
+fn compose(g: &Graph, h: &Graph) -> Graph {
+  let mut arcs = Graph::new();
+  for (i, c, j) in g {
+    for (k, d, l) in h {
+      if j == k {
+        arcs.insert((*i, max(*c, *d), *l));
+      }
+    }
+  }
+  prune(arcs)
+}
+
+/// Whether every infinite sequence of the given calls would decrease some
+/// argument infinitely often, which can't happen to well-founded data. By
+/// the size-change principle it suffices that each idempotent graph in the
+/// closure of the calls under composition decreases some parameter into
+/// itself.
+fn decreases(calls: &[Graph]) -> bool {
+  let mut closure: BTreeSet<Graph> = calls.iter().cloned().collect();
+  let mut todo: Vec<Graph> = closure.iter().cloned().collect();
+  while let Some(g) = todo.pop() {
+    for h in calls {
+      let gh = compose(&g, h);
+      if closure.insert(gh.clone()) {
+        todo.push(gh);
+      }
+    }
+  }
+  closure.iter().all(|g| {
+    compose(g, g) != *g
+      || g.iter().any(|(i, c, j)| i == j && *c == Change::Less)
+  })
+}
+
+/// The parameters a term is known to be no larger than, as the function,
+/// the parameter's position and how the term compares to it
+type Sizes = Vec<(usize, usize, Change)>;
+
+/// What the termination check knows of a variable in scope
+#[derive(Clone, Debug)]
+enum Bound {
+  Sized(Sizes),
+  /// A function defined by `letrec`, within its own definition
+  Fun(usize),
+}
+
+type Ctx = Vec<Bound>;
+
+fn lookup(ctx: &[Bound], idx: u64) -> Option<&Bound> {
+  ctx.len().checked_sub(1 + idx as usize).and_then(|lvl| ctx.get(lvl))
+}
+
+fn sizes(ctx: &[Bound], term: &Term) -> Sizes {
+  match term {
+    Term::Var(_, _, idx) => match lookup(ctx, *idx) {
+      Some(Bound::Sized(sizes)) => sizes.clone(),
+      _ => vec![],
+    },
+    Term::Ann(_, typ_exp) => sizes(ctx, &typ_exp.1),
+    _ => vec![],
+  }
+}
+
+/// Splits an application into its head and its arguments
+fn spine(term: &Term) -> (&Term, Vec<&Term>) {
+  let mut args = vec![];
+  let mut head = term;
+  while let Term::App(_, fun_arg) = head {
+    args.push(&fun_arg.1);
+    head = &fun_arg.0;
+  }
+  args.reverse();
+  (head, args)
+}
+
+/// A recursive function and the graphs of its recursive calls
+struct Fun {
+  pos: Pos,
+  nam: Name,
+  arity: usize,
+  calls: Vec<Graph>,
+}
+
+/// Collects the recursive calls of a definition. The definition itself is
+/// function 0, called through `Term::Rec`, and each `letrec` within it adds
+/// another. A variable bound by a lambda in an argument of `case x` is a
+/// field of `x`, and so smaller than it. Types aren't walked: a recursive
+/// occurrence in one, as in the fields of a datatype, builds a type rather
+/// than making a call.
+struct Calls {
+  funs: Vec<Fun>,
+}
+
+impl Calls {
+  /// Walks the body of function `fun`, whose leading lambdas bind its
+  /// parameters
+  fn function(&mut self, ctx: &mut Ctx, fun: usize, term: &Term) {
+    let mut body = term;
+    let mut arity = 0;
+    while let Term::Lam(_, _, bod) = body {
+      ctx.push(Bound::Sized(vec![(fun, arity, Change::Same)]));
+      arity += 1;
+      body = bod;
+    }
+    self.funs[fun].arity = arity;
+    self.term(ctx, body);
+    ctx.truncate(ctx.len() - arity);
+  }
+
+  fn callee(&self, ctx: &[Bound], head: &Term) -> Option<usize> {
+    match head {
+      Term::Rec(_) => Some(0),
+      Term::Var(_, _, idx) => match lookup(ctx, *idx) {
+        Some(Bound::Fun(fun)) => Some(*fun),
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  /// Records a call of `fun`. Arguments past its parameters can't decrease
+  /// them, and missing ones leave them unrelated.
+  fn call(&mut self, ctx: &[Bound], fun: usize, args: &[&Term]) {
+    let mut graph = Graph::new();
+    for (j, arg) in args.iter().take(self.funs[fun].arity).enumerate() {
+      for (f, i, c) in sizes(ctx, arg) {
+        if f == fun {
+          graph.insert((i, c, j));
+        }
+      }
+    }
+    self.funs[fun].calls.push(prune(graph));
+  }
+
+  /// Walks an argument of an application, binding its leading lambdas to
+  /// the given sizes
+  fn branch(&mut self, ctx: &mut Ctx, term: &Term, fields: &Sizes) {
+    let mut body = term;
+    let mut n = 0;
+    while let Term::Lam(_, _, bod) = body {
+      ctx.push(Bound::Sized(fields.clone()));
+      n += 1;
+      body = bod;
+    }
+    self.term(ctx, body);
+    ctx.truncate(ctx.len() - n);
+  }
+
+  fn term(&mut self, ctx: &mut Ctx, term: &Term) {
+    match term {
+      Term::Rec(_) | Term::Var(..) => {
+        if let Some(fun) = self.callee(ctx, term) {
+          self.call(ctx, fun, &[])
+        }
+      }
+      Term::App(..) => {
+        let (head, args) = spine(term);
+        let fields = match head {
+          Term::Cse(_, exp) => sizes(ctx, exp)
+            .into_iter()
+            .map(|(fun, i, _)| (fun, i, Change::Less))
+            .collect(),
+          _ => vec![],
+        };
+        match self.callee(ctx, head) {
+          Some(fun) => self.call(ctx, fun, &args),
+          None => self.term(ctx, head),
+        }
+        for arg in args {
+          self.branch(ctx, arg, &fields);
+        }
+      }
+      Term::Lam(_, _, bod) => {
+        ctx.push(Bound::Sized(vec![]));
+        self.term(ctx, bod);
+        ctx.pop();
+      }
+      Term::Dat(_, bod) | Term::Cse(_, bod) => self.term(ctx, bod),
+      Term::Ann(_, typ_exp) => self.term(ctx, &typ_exp.1),
+      Term::Let(_, false, _, _, typ_exp_bod) => {
+        let (_, exp, bod) = typ_exp_bod.as_ref();
+        self.term(ctx, exp);
+        ctx.push(Bound::Sized(sizes(ctx, exp)));
+        self.term(ctx, bod);
+        ctx.pop();
+      }
+      Term::Let(pos, true, _, nam, typ_exp_bod) => {
+        let (_, exp, bod) = typ_exp_bod.as_ref();
+        let fun = self.funs.len();
+        self.funs.push(Fun {
+          pos: *pos,
+          nam: nam.clone(),
+          arity: 0,
+          calls: vec![],
+        });
+        ctx.push(Bound::Fun(fun));
+        self.function(ctx, fun, exp);
+        ctx.pop();
+        ctx.push(Bound::Sized(vec![]));
+        self.term(ctx, bod);
+        ctx.pop();
+      }
+      _ => (),
+    }
+  }
+}
+
+/// Checks that a term defining the function `nam` terminates, along with any
+/// function it defines with `letrec`
+pub fn check_term(
+  pos: Pos,
+  nam: &Name,
+  term: &Term,
+) -> Result<(), TerminationError> {
+  let fun = Fun { pos, nam: nam.clone(), arity: 0, calls: vec![] };
+  let mut calls = Calls { funs: vec![fun] };
+  calls.function(&mut Vec::new(), 0, term);
+  for fun in calls.funs {
+    if !decreases(&fun.calls) {
+      return Err(TerminationError::NoDecrease(fun.pos, fun.nam));
+    }
+  }
+  Ok(())
+}
+
+/// Checks that a def terminates, unless it's marked `partial`. Total defs
+/// only recurse structurally, on the fields of their arguments, so they can
+/// be unfolded freely while checking conversion.
+pub fn check_def(nam: &Name, def: &Def) -> Result<(), TerminationError> {
+  if def.attrs.is_partial() {
+    return Ok(());
+  }
+  check_term(def.pos, nam, &def.term)
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    defs::Defs,
+    parse::{
+      package,
+      span::Span,
+      term::input_cid,
+    },
+  };
+
+  fn check_src(src: &str, nam: &str) -> Result<(), TerminationError> {
+    let src = format!(
+      "type Nat {{ Z, S (pred: Nat) }}\ntype List (A: Type) {{ Nil, Cons A \
+       (List A) }}\n{}",
+      src
+    );
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(&src), Defs::new())(Span::new(&src))
+        .unwrap();
+    let nam = Name::from(nam);
+    check_def(&nam, defs.get(&nam).unwrap())
+  }
+
+  #[test]
+  fn test_structural() {
+    let add = "def add (m n: Nat): Nat = (case m) (λ _ => Nat) n (λ p => \
+               Nat.S (add p n))";
+    assert_eq!(check_src(add, "add"), Ok(()));
+    let len = "def len (A: Type) (xs: List A): Nat = (case xs) (λ _ => Nat) \
+               Nat.Z (λ _ xs => Nat.S (len A xs))";
+    assert_eq!(check_src(len, "len"), Ok(()));
+    // Arguments that trade places still decrease every other call
+    let swap =
+      "def swap (m n: Nat): Nat = (case m) (λ _ => Nat) n (λ p => swap n p)";
+    assert_eq!(check_src(swap, "swap"), Ok(()));
+    let ack = "def ack (m n: Nat): Nat = (case m) (λ _ => Nat) (Nat.S n) (λ p \
+               => (case n) (λ _ => Nat) (ack p (Nat.S Nat.Z)) (λ q => ack p \
+               (ack m q)))";
+    assert_eq!(check_src(ack, "ack"), Ok(()));
+    // Datatypes refer to themselves only in their types
+    assert_eq!(check_src("", "Nat"), Ok(()));
+    assert_eq!(check_src("", "List"), Ok(()));
+  }
+
+  #[test]
+  fn test_nonterminating() {
+    match check_src("def loop (n: Nat): Nat = loop n", "loop") {
+      Err(TerminationError::NoDecrease(_, nam)) => {
+        assert_eq!(nam, Name::from("loop"))
+      }
+      res => panic!("{:?}", res),
+    }
+    let up = "def up (n: Nat): Nat = (case n) (λ _ => Nat) Nat.Z (λ p => up \
+              (Nat.S n))";
+    assert!(check_src(up, "up").is_err());
+    let flip =
+      "def flip (m n: Nat): Nat = (case m) (λ _ => Nat) n (λ p => flip n m)";
+    assert!(check_src(flip, "flip").is_err());
+    let partial = "partial def loop (n: Nat): Nat = loop n";
+    assert_eq!(check_src(partial, "loop"), Ok(()));
+    let opaque = "partial opaque def loop (n: Nat): Nat = loop n";
+    assert_eq!(check_src(opaque, "loop"), Ok(()));
+    let total = "total def loop (n: Nat): Nat = loop n";
+    assert!(check_src(total, "loop").is_err());
+  }
+}
@@ -0,0 +1,142 @@
+use crate::{
+  defs::Def,
+  name::Name,
+  prim::Op,
+};
+
+use sp_std::{
+  collections::{
+    btree_map::BTreeMap,
+    btree_set::BTreeSet,
+  },
+  fmt,
+  vec::Vec,
+};
+
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// A kind of reduction step reported by the evaluator
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum StepKind {
+  /// A lambda applied to its argument, or a let substituted into its body
+  Beta,
+  /// A primitive operation applied to its arguments
+  Opr(Op),
+  /// A definition unfolded, either from a reference or by recursion
+  Unfold(Name),
+}
+
+impl fmt::Display for StepKind {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Beta => write!(f, "beta"),
+      Self::Opr(opr) => write!(f, "primop {}", opr),
+      Self::Unfold(nam) => write!(f, "unfold {}", nam),
+    }
+  }
+}
+
+/// Receives the steps taken by `DAG::norm_traced` and the other traced
+/// evaluation functions. Each step comes with the definition most recently
+/// unfolded at the head of the term being reduced, if any
+pub trait TraceSink {
+  fn step(&mut self, kind: &StepKind, def: Option<&Name>);
+
+  /// Whether the evaluator may unfold a reference to `def`. It unfolds every
+  /// reference unless the sink says otherwise.
+  fn unfolds(&mut self, _def: &Def) -> bool { true }
+}
+
+/// Discards every step
+pub struct NoTrace;
+
+impl TraceSink for NoTrace {
+  fn step(&mut self, _: &StepKind, _: Option<&Name>) {}
+}
+
+/// Counts the steps of each kind
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct TraceCounts {
+  pub beta: u64,
+  /// Applications by operation symbol
+  pub opr: BTreeMap<String, u64>,
+  pub unfold: BTreeMap<Name, u64>,
+}
+
+impl TraceCounts {
+  pub fn new() -> Self { Self::default() }
+
+  pub fn total(&self) -> u64 {
+    self.beta
+      + self.opr.values().sum::<u64>()
+      + self.unfold.values().sum::<u64>()
+  }
+}
+
+impl TraceSink for TraceCounts {
+  fn step(&mut self, kind: &StepKind, _: Option<&Name>) {
+    match kind {
+      StepKind::Beta => self.beta += 1,
+      StepKind::Opr(opr) => *self.opr.entry(opr.symbol()).or_insert(0) += 1,
+      StepKind::Unfold(nam) => {
+        *self.unfold.entry(nam.clone()).or_insert(0) += 1
+      }
+    }
+  }
+}
+
+impl fmt::Display for TraceCounts {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{} steps", self.total())?;
+    writeln!(f, "  beta: {}", self.beta)?;
+    for (opr, count) in self.opr.iter() {
+      writeln!(f, "  primop {}: {}", opr, count)?;
+    }
+    for (nam, count) in self.unfold.iter() {
+      writeln!(f, "  unfold {}: {}", nam, count)?;
+    }
+    Ok(())
+  }
+}
+
+/// Records a line for each step, such as `unfold Nat.add` or
+/// `beta in Nat.add`
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct TraceLog {
+  pub lines: Vec<String>,
+}
+
+impl TraceSink for TraceLog {
+  fn step(&mut self, kind: &StepKind, def: Option<&Name>) {
+    match def {
+      Some(def) => self.lines.push(format!("{} in {}", kind, def)),
+      None => self.lines.push(kind.to_string()),
+    }
+  }
+}
+
+/// Passes on only the steps within a whitelist of definitions, along with
+/// the unfoldings of those definitions
+pub struct Only<S: TraceSink> {
+  pub names: BTreeSet<Name>,
+  pub sink: S,
+}
+
+impl<S: TraceSink> Only<S> {
+  pub fn new(names: BTreeSet<Name>, sink: S) -> Self { Only { names, sink } }
+}
+
+impl<S: TraceSink> TraceSink for Only<S> {
+  fn step(&mut self, kind: &StepKind, def: Option<&Name>) {
+    let unfolded = match kind {
+      StepKind::Unfold(nam) => Some(nam),
+      _ => None,
+    };
+    if unfolded.or(def).map_or(false, |nam| self.names.contains(nam)) {
+      self.sink.step(kind, def)
+    }
+  }
+}
@@ -6,11 +6,94 @@ use crate::{
 };
 use sp_std::{
   boxed::Box,
+  fmt,
   iter::once,
   rc::Rc,
   vec::Vec,
 };
 
+use alloc::string::{
+  String,
+  ToString,
+};
+
+/// A recursive occurrence of a datatype in the field of one of its
+/// constructors that isn't strictly positive. Each gives the type, the
+/// constructor, the field and the field's type.
+#[derive(PartialEq, Clone, Debug)]
+pub enum PositivityError {
+  /// The type occurs to the left of an arrow
+  Negative(Name, Name, Name, String),
+  /// The type occurs in an argument of a type
+  Nested(Name, Name, Name, String),
+}
+
+impl fmt::Display for PositivityError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Negative(typ, var, field, field_typ) => write!(
+        f,
+        "{} occurs to the left of an arrow in the field ({}: {}) of {}.{}. A \
+         datatype taking functions on itself as arguments can be used to \
+         prove anything, so it may only occur to the right of arrows",
+        typ, field, field_typ, typ, var
+      ),
+      Self::Nested(typ, var, field, field_typ) => write!(
+        f,
+        "{} occurs in an argument of a type in the field ({}: {}) of {}.{}. \
+         Only direct recursive occurrences such as `{}` or `∀ A -> {}` are \
+         allowed, as the argument may be used negatively",
+        typ, field, field_typ, typ, var, typ, typ
+      ),
+    }
+  }
+}
+
+/// Whether a term mentions the type being defined
+fn mentions_rec(term: &Term) -> bool {
+  match term {
+    Term::Rec(_) => true,
+    Term::Lam(_, _, bod)
+    | Term::Slf(_, _, bod)
+    | Term::Dat(_, bod)
+    | Term::Cse(_, bod) => mentions_rec(bod),
+    Term::App(_, xs) | Term::All(_, _, _, _, xs) | Term::Ann(_, xs) => {
+      mentions_rec(&xs.0) || mentions_rec(&xs.1)
+    }
+    Term::Let(_, _, _, _, xs) => {
+      mentions_rec(&xs.0) || mentions_rec(&xs.1) || mentions_rec(&xs.2)
+    }
+    _ => false,
+  }
+}
+
+/// How the type being defined occurs in the type of a field, if not
+/// strictly positively
+enum Occurrence {
+  Negative,
+  Nested,
+}
+
+/// Finds an occurrence of the type being defined that isn't strictly
+/// positive. The type may only appear as the head of the field's type,
+/// possibly under `∀` binders whose domains don't mention it, applied to
+/// arguments that don't mention it.
+fn occurrence(term: &Term) -> Option<Occurrence> {
+  match term {
+    Term::All(_, _, _, _, dom_img) if mentions_rec(&dom_img.0) => {
+      Some(Occurrence::Negative)
+    }
+    Term::All(_, _, _, _, dom_img) => occurrence(&dom_img.1),
+    Term::App(_, fun_arg) if mentions_rec(&fun_arg.1) => {
+      Some(Occurrence::Nested)
+    }
+    Term::App(_, fun_arg) => occurrence(&fun_arg.0),
+    Term::Rec(_) => None,
+    term if mentions_rec(term) => Some(Occurrence::Nested),
+    _ => None,
+  }
+}
+
 /// A type declaration syntax that allows for convenient expression of Yatima's
 /// lambda encoded datatypes.
 ///
@@ -93,9 +176,9 @@ impl TypeDef {
   /// e.g. `Vector : ∀ (0 A: Type) (0 k: Nat) -> Type`
   pub fn type_of(&self) -> Term {
     self.typ_params.iter().chain(self.typ_indices.iter()).rev().fold(
-      Term::Typ(Pos::None),
+      Term::Typ(Pos::None, Level::zero()),
       |acc, (u, n, t)| {
-        Term::All(Pos::None, *u, n.clone(), Box::new((t.clone(), acc)))
+        Term::All(Pos::None, false, *u, n.clone(), Box::new((t.clone(), acc)))
       },
     )
   }
@@ -108,7 +191,7 @@ impl TypeDef {
     // We proceed by constructing terms from the inside out. The motive is
     // always a telescope of `∀` (a.k.a. `forall`) binders whose final image is
     // `Type`.
-    let img: Term = Term::Typ(Pos::None);
+    let img: Term = Term::Typ(Pos::None, Level::zero());
     // We then proceed with the rightmost/innermost binder, which is always
     // the dependent `self` binder. This is structurally a recursion on the self
     // type saturated with variables binding to all the types parameters and
@@ -164,7 +247,7 @@ impl TypeDef {
     // Finally, fold the index binders plus the self binder over the `img` into
     // a telescope of `forall` quantifiers
     index_binders.chain(once(slf)).rev().fold(img, |acc, (u, n, t)| {
-      Term::All(Pos::None, u, n, Box::new((t, acc)))
+      Term::All(Pos::None, false, u, n, Box::new((t, acc)))
     })
   }
 
@@ -272,7 +355,7 @@ impl TypeDef {
       //∀ (0 k: Nat) (x: A) (xs: Vector A k) ->
       // ```
       let typ = v.bind.iter().rev().fold(img, |acc, (u, n, t)| {
-        Term::All(Pos::None, *u, n.clone(), Box::new((t.clone(), acc)))
+        Term::All(Pos::None, false, *u, n.clone(), Box::new((t.clone(), acc)))
       });
       // And then we push on to the vector with the affine usage multiplicty and
       // the variant name
@@ -308,7 +391,7 @@ impl TypeDef {
       .fold(mot, |acc, arg| Term::App(Pos::None, Box::new((acc, arg.clone()))));
     // And fold the foralls over that image
     let forall = alls.iter().rev().fold(img, |acc, (u, n, t)| {
-      Term::All(Pos::None, *u, n.clone(), Box::new((t.clone(), acc)))
+      Term::All(Pos::None, false, *u, n.clone(), Box::new((t.clone(), acc)))
     });
     // Then add the outer `@self` binder
     let bod = Term::Slf(Pos::None, slf_name, Box::new(forall));
@@ -323,6 +406,24 @@ impl TypeDef {
       })
   }
 
+  /// Checks that the type occurs only strictly positively in the fields of
+  /// its constructors, e.g. that `Cons (x: A) (xs: List A)` is fine but not
+  /// `Lam (f: ∀ Term -> Term)`
+  pub fn check_positivity(&self) -> Result<(), PositivityError> {
+    for v in self.cons_variants.iter() {
+      for (_, field, typ) in v.bind.iter() {
+        let err = match occurrence(typ) {
+          None => continue,
+          Some(Occurrence::Negative) => PositivityError::Negative,
+          Some(Occurrence::Nested) => PositivityError::Nested,
+        };
+        let typ = typ.pretty(Some(&self.name.to_string()), false);
+        return Err(err(self.name.clone(), v.name.clone(), field.clone(), typ));
+      }
+    }
+    Ok(())
+  }
+
   /// Construct the definition of the `type` declaration's type out of the
   /// `term_of()` and `type_of()` functions and the `name` field.
   pub fn type_def(&self) -> (Name, Def, Entry) {
@@ -384,7 +485,7 @@ impl TypeDef {
         .chain(v.bind.clone().into_iter())
         .rev()
         .fold(img, |acc, (u, n, t)| {
-          Term::All(Pos::None, u, n, Box::new((t, acc)))
+          Term::All(Pos::None, false, u, n, Box::new((t, acc)))
         });
       // Replace `Term::Rec` with the reference to the type definition
       let typ = typ.un_rec(Rc::new(self.type_ref()));
@@ -0,0 +1,218 @@
+use std::{
+  fmt,
+  ptr::NonNull,
+  rc::Rc,
+};
+
+use yatima_core::{
+  defs::Defs,
+  fuel::{
+    Fuel,
+    OutOfFuel,
+  },
+  literal::Literal,
+  prim::{
+    action::ActionOp,
+    list_term,
+  },
+  term::Op,
+};
+
+use crate::{
+  runtime::{
+    add_to_parents,
+    alloc_app,
+    alloc_val,
+    free_dead_node,
+    from_term,
+    replace_child,
+    whnf_fueled,
+    App,
+    Lit,
+    DAG,
+  },
+  transform::RunIO,
+};
+
+/// Why an `#IO` program stopped before `#IO.done`
+#[derive(Debug)]
+pub enum RunError {
+  /// An action applied to too few arguments, or to arguments that aren't
+  /// literals of its types
+  Stuck(ActionOp),
+  OutOfFuel(OutOfFuel),
+}
+
+impl fmt::Display for RunError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Stuck(op) => write!(f, "Stuck action #IO.{}", op),
+      Self::OutOfFuel(err) => write!(f, "{}", err),
+    }
+  }
+}
+
+impl From<OutOfFuel> for RunError {
+  fn from(err: OutOfFuel) -> Self { Self::OutOfFuel(err) }
+}
+
+/// Carries out the `#IO` program a DAG reduces to, one action at a time.
+/// Each action is reduced to weak head normal form, performed with
+/// `runtime`, and replaced by the continuation its outcome picks. Stops at
+/// `#IO.done`, or at anything that isn't an action, as a program whose
+/// value is a literal.
+pub fn perform(
+  dag: &mut DAG,
+  runtime: &RunIO,
+  fuel: &mut Fuel,
+) -> Result<(), RunError> {
+  loop {
+    whnf_fueled(dag, false, fuel);
+    if let Some(err) = fuel.out_of_fuel() {
+      return Err(err.into());
+    }
+    let mut args = vec![];
+    let mut head = *dag;
+    while let DAG::App(link) = head {
+      let App { fun, arg, .. } = unsafe { link.as_ref() };
+      args.push(*arg);
+      head = *fun;
+    }
+    args.reverse();
+    let op = match head {
+      DAG::Opr(link) => match unsafe { &link.as_ref().opr } {
+        Op::Action(op) => *op,
+        _ => return Ok(()),
+      },
+      _ => return Ok(()),
+    };
+    if op == ActionOp::Done && args.is_empty() {
+      return Ok(());
+    }
+    let arity = op.arity() as usize;
+    if args.len() != arity + op.continuations() {
+      return Err(RunError::Stuck(op));
+    }
+    let (lits, conts) = args.split_at(arity);
+    let lits: Option<Vec<&Literal>> = lits
+      .iter()
+      .map(|arg| match arg {
+        DAG::Lit(link) => Some(unsafe { &(*link.as_ptr()).lit }),
+        _ => None,
+      })
+      .collect();
+    let new_node = match (op, lits.as_deref()) {
+      (ActionOp::Print, Some([Literal::Text(msg)])) => {
+        runtime.print(&msg.to_string());
+        conts[0]
+      }
+      (ActionOp::ReadLine, Some([])) => match runtime.read_line() {
+        Some(line) => give(conts[1], text(line)),
+        None => conts[0],
+      },
+      (ActionOp::ReadFile, Some([Literal::Text(path)])) => {
+        match runtime.read_file(&path.to_string()) {
+          Ok(bytes) => give(conts[1], Literal::Bytes(bytes)),
+          Err(msg) => give(conts[0], text(msg)),
+        }
+      }
+      (
+        ActionOp::WriteFile,
+        Some([Literal::Text(path), Literal::Bytes(bytes)]),
+      ) => match runtime.write_file(&path.to_string(), bytes) {
+        Ok(()) => conts[1],
+        Err(msg) => give(conts[0], text(msg)),
+      },
+      (ActionOp::GetArgs, Some([])) => {
+        let args = runtime.args().into_iter().map(text).collect();
+        let list = from_term(Rc::new(Defs::new()), &list_term(args), None);
+        apply(conts[0], list)
+      }
+      (ActionOp::GetEnv, Some([Literal::Text(name)])) => {
+        match runtime.env(&name.to_string()) {
+          Some(val) => give(conts[1], text(val)),
+          None => conts[0],
+        }
+      }
+      _ => return Err(RunError::Stuck(op)),
+    };
+    let top = *dag;
+    replace_child(top, new_node);
+    free_dead_node(top);
+    *dag = new_node;
+  }
+}
+
+fn text(x: String) -> Literal { Literal::Text(x.as_str().into()) }
+
+/// Applies a continuation to the literal an action gives it
+fn give(cont: DAG, lit: Literal) -> DAG {
+  apply(cont, DAG::Lit(alloc_val(Lit { lit, parents: None })))
+}
+
+/// Applies a function already in the DAG to a new argument
+fn apply(fun: DAG, arg: DAG) -> DAG {
+  let app = alloc_app(fun, arg, None);
+  unsafe {
+    let App { fun_ref, arg_ref, .. } = &mut *app.as_ptr();
+    add_to_parents(fun, NonNull::new_unchecked(fun_ref));
+    add_to_parents(arg, NonNull::new_unchecked(arg_ref));
+  }
+  DAG::App(app)
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    run_io,
+    transform::RuntimeIO,
+  };
+  use std::cell::RefCell;
+  use yatima_core::{
+    fuel::Limits,
+    parse,
+  };
+
+  /// Records what a program prints, without touching the outside world
+  #[derive(Default)]
+  struct Recorder {
+    out: RefCell<String>,
+  }
+
+  impl RuntimeIO for Recorder {
+    fn print(&self, text: &str) { self.out.borrow_mut().push_str(text) }
+
+    fn args(&self) -> Vec<String> { vec!["a".to_owned(), "b".to_owned()] }
+
+    fn env(&self, _name: &str) -> Option<String> { None }
+  }
+
+  fn run(src: &str) -> (Result<(), RunError>, String) {
+    let (_, mut term) = parse::term::parse(src, Defs::new()).unwrap();
+    let io = Rc::new(Recorder::default());
+    let res =
+      run_io(&mut term, Rc::new(Defs::new()), io.clone(), Limits::none());
+    let out = io.out.borrow().clone();
+    (res, out)
+  }
+
+  #[test]
+  fn test_perform() {
+    let (res, out) = run(
+      "#IO.print \"hi \" (#IO.get_env \"HOME\" (#IO.print \"none\" #IO.done) \
+       (λ x => #IO.done))",
+    );
+    assert!(res.is_ok());
+    assert_eq!(out, "hi none");
+    let (res, out) = run(
+      "#IO.get_args (λ args => args #IO #IO.done (λ x rest => #IO.print x \
+       rest))",
+    );
+    assert!(res.is_ok());
+    assert_eq!(out, "ab");
+    let (res, out) = run("#IO.print 1u64 #IO.done");
+    assert!(matches!(res, Err(RunError::Stuck(ActionOp::Print))));
+    assert_eq!(out, "");
+  }
+}
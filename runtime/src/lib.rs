@@ -4,17 +4,70 @@ use yatima_core::{
   defs::{
     Defs,
   },
+  fuel::{
+    Limits,
+    OutOfFuel,
+  },
   term::Term,
 };
 
+pub mod action;
 mod runtime;
 pub mod transform;
+pub mod vm;
+pub mod wasm;
+use action::RunError;
 use transform::RunIO;
 
 /// Reduce terms to WHNF and execute any IoOp with the provided RuntimeIO
 pub fn run(term: &mut Term, checked: Rc<Defs>, runtime: RunIO) {
+  // Without limits the fuel never runs out
+  let _ = run_with_limit(term, checked, runtime, Limits::none());
+}
+
+/// Like `run`, but stops once the reduction goes past `limits`
+pub fn run_with_limit(
+  term: &mut Term,
+  checked: Rc<Defs>,
+  runtime: RunIO,
+  limits: Limits,
+) -> Result<(), OutOfFuel> {
   let root = runtime::alloc_val(yatima_core::dll::DLL::singleton(runtime::ParentPtr::Root));
   transform::transform(checked.clone(), term, runtime);
-  let mut dag = runtime::from_term(checked, &term, Some(root));
-  runtime::whnf(&mut dag, false);
+  let mut dag = runtime::from_term(checked, term, Some(root));
+  let mut fuel = limits.fuel();
+  runtime::whnf_fueled(&mut dag, false, &mut fuel);
+  match fuel.out_of_fuel() {
+    Some(err) => Err(err),
+    None => Ok(()),
+  }
+}
+
+/// Runs a term as an `#IO` program, carrying out each primitive action it
+/// reduces to with the provided RuntimeIO until it's done. A term that isn't
+/// a program is only reduced to WHNF, as by `run_with_limit`
+pub fn run_io(
+  term: &mut Term,
+  checked: Rc<Defs>,
+  runtime: RunIO,
+  limits: Limits,
+) -> Result<(), RunError> {
+  let root = runtime::alloc_val(yatima_core::dll::DLL::singleton(runtime::ParentPtr::Root));
+  transform::transform(checked.clone(), term, runtime.clone());
+  let mut dag = runtime::from_term(checked, term, Some(root));
+  action::perform(&mut dag, &runtime, &mut limits.fuel())
+}
+
+/// Compile terms to bytecode and reduce them to WHNF on the stack machine,
+/// executing any IoOp with the provided RuntimeIO
+pub fn run_vm(
+  term: &Term,
+  checked: Rc<Defs>,
+  runtime: RunIO,
+  limits: Limits,
+) -> Result<(), vm::VmError> {
+  let mut machine = vm::Vm::new(checked, runtime);
+  machine.set_limits(limits);
+  machine.eval_term(term)?;
+  Ok(())
 }
@@ -1,13 +1,13 @@
 use yatima_core::{
   defs::Defs,
   dll::*,
+  fuel::Fuel,
   literal::Literal,
   prim::Op,
   term::Term,
 };
 
 use std::{
-  boxed::Box,
   collections::BTreeSet,
   fmt,
   mem,
@@ -290,7 +290,7 @@ pub fn free_dead_node(node: DAG) {
         if new_bod_parents.is_none() {
           free_dead_node(*bod)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAG::Fix(mut link) => {
         let Fix { bod, bod_ref, .. } = &link.as_mut();
@@ -299,7 +299,7 @@ pub fn free_dead_node(node: DAG) {
         if new_bod_parents.is_none() {
           free_dead_node(*bod)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAG::App(link) => {
         let App { fun, arg, fun_ref, arg_ref, .. } = link.as_ref();
@@ -313,13 +313,13 @@ pub fn free_dead_node(node: DAG) {
         if new_arg_parents.is_none() {
           free_dead_node(*arg)
         }
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAG::Lit(link) => {
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAG::Opr(link) => {
-        Box::from_raw(link.as_ptr());
+        free_val(link);
       }
       DAG::Var(_) => (),
     }
@@ -363,11 +363,12 @@ pub fn clean_up(cc: &ParentPtr) {
   }
 }
 
-/// Auxiliary allocation functions
-#[inline]
-pub fn alloc_val<T>(val: T) -> NonNull<T> {
-  NonNull::new(Box::leak(Box::new(val))).unwrap()
-}
+/// Auxiliary allocation functions. Nodes are allocated and freed as in the
+/// core DAG, so they count towards the same memory budget
+pub use yatima_core::dag::{
+  alloc_val,
+  free_val,
+};
 
 #[inline]
 pub fn alloc_lam(bod: DAG, parents: Option<NonNull<Parents>>) -> NonNull<Lam> {
@@ -618,147 +619,162 @@ pub fn reduce_lam(
 
 /// Compute the Weak head normal form of the given DAG
 pub fn whnf(dag: &mut DAG, should_count: bool) {
-  let mut node = *dag;
-  let mut trail: Vec<NonNull<App>> = vec![];
+  whnf_fueled(dag, should_count, &mut Fuel::unlimited())
+}
+
+/// Computes the weak head normal form of the given DAG as in `whnf`, taking
+/// each reduction step only if `fuel` has some left. The arguments of
+/// operations are reduced in frames kept on the heap rather than by
+/// recursion, so deeply nested arithmetic doesn't overflow the stack.
+pub fn whnf_fueled(dag: &mut DAG, should_count: bool, fuel: &mut Fuel) {
+  let mut stack: Vec<Frame> = vec![];
+  let mut frame = Frame::new(dag);
   loop {
-    match node {
-      DAG::App(link) => {
-        let App { fun, .. } = unsafe { link.as_ref() };
-        trail.push(link);
-        node = *fun;
-      }
-      DAG::Lam(link) => {
-        if let Some(app_link) = trail.pop() {
-          node = reduce_lam(app_link, link, should_count);
-        }
-        else {
-          break;
+    match frame.run(should_count, fuel) {
+      Some(arg) => stack.push(mem::replace(&mut frame, arg)),
+      None => {
+        unsafe { *frame.slot = frame.head() };
+        match stack.pop() {
+          Some(outer) => frame = outer,
+          None => break,
         }
       }
-      DAG::Fix(link) => unsafe {
-        let Fix { var, bod, .. } = &mut *link.as_ptr();
-        replace_child(node, *bod);
-        if var.parents.is_some() {
-          let new_fix = alloc_fix(mem::zeroed(), None).as_mut();
-          let result = subst(
-            *bod,
-            var,
-            DAG::Var(NonNull::new_unchecked(&mut new_fix.var)),
-            true,
-            should_count,
-          );
-          new_fix.bod = result;
-          add_to_parents(result, NonNull::new_unchecked(&mut new_fix.bod_ref));
-          replace_child(
-            DAG::Var(NonNull::new(var).unwrap()),
-            DAG::Fix(NonNull::new_unchecked(new_fix)),
-          );
+    }
+  }
+}
+
+/// A reduction to weak head normal form in progress
+struct Frame {
+  node: DAG,
+  trail: Vec<NonNull<App>>,
+  /// Where the head goes once reduced: the argument of an application in
+  /// the frame below, or the DAG being reduced
+  slot: *mut DAG,
+  /// How many arguments of the operation at the head have been reduced
+  reduced: usize,
+}
+
+impl Frame {
+  fn new(slot: *mut DAG) -> Self {
+    Frame { node: unsafe { *slot }, trail: vec![], slot, reduced: 0 }
+  }
+
+  fn head(&self) -> DAG {
+    match self.trail.first() {
+      Some(link) => DAG::App(*link),
+      None => self.node,
+    }
+  }
+
+  /// Reduces until the head is stuck, or until an argument of an operation
+  /// needs reducing first, returning the frame for it
+  fn run(&mut self, should_count: bool, fuel: &mut Fuel) -> Option<Frame> {
+    let trail = &mut self.trail;
+    loop {
+      match self.node {
+        DAG::App(link) => {
+          let App { fun, .. } = unsafe { link.as_ref() };
+          trail.push(link);
+          self.node = *fun;
         }
-        free_dead_node(node);
-        node = *bod;
-      },
-      DAG::Opr(link) => {
-        let opr = unsafe { (*link.as_ptr()).opr.clone() };
-        let len = trail.len();
-        if len == 0 && opr.arity() == 0 {
-          let res = opr.apply0();
-          if let Some(res) = res {
-            node = DAG::Lit(alloc_val(Lit { lit: res, parents: None }));
+        DAG::Lam(_) if !trail.is_empty() && !fuel.burn() => break,
+        DAG::Fix(_) if !fuel.burn() => break,
+        DAG::Lam(link) => {
+          if let Some(app_link) = trail.pop() {
+            self.node = reduce_lam(app_link, link, should_count);
           }
           else {
             break;
           }
         }
-        else if len >= 1 && opr.arity() == 1 {
-          let arg = unsafe { &mut (*trail[len - 1].as_ptr()).arg };
-          whnf(arg, should_count);
-          match *arg {
-            DAG::Lit(link) => {
-              let x = unsafe { &(*link.as_ptr()).lit };
-              let res = opr.apply1(x);
-              if let Some(res) = res {
-                let top = DAG::App(trail.pop().unwrap());
-                let new_node =
-                  DAG::Lit(alloc_val(Lit { lit: res, parents: None }));
-                replace_child(top, new_node);
-                free_dead_node(top);
-                node = new_node;
-              }
-              else {
-                break;
-              }
-            }
-            _ => break,
+        DAG::Fix(link) => unsafe {
+          let Fix { var, bod, .. } = &mut *link.as_ptr();
+          let bod = *bod;
+          replace_child(self.node, bod);
+          if var.parents.is_some() {
+            let new_fix = alloc_fix(mem::zeroed(), None).as_mut();
+            let result = subst(
+              bod,
+              var,
+              DAG::Var(NonNull::new_unchecked(&mut new_fix.var)),
+              true,
+              should_count,
+            );
+            new_fix.bod = result;
+            add_to_parents(
+              result,
+              NonNull::new_unchecked(&mut new_fix.bod_ref),
+            );
+            replace_child(
+              DAG::Var(NonNull::new(var).unwrap()),
+              DAG::Fix(NonNull::new_unchecked(new_fix)),
+            );
           }
-        }
-        else if len >= 2 && opr.arity() == 2 {
-          let arg1 = unsafe { &mut (*trail[len - 1].as_ptr()).arg };
-          let arg2 = unsafe { &mut (*trail[len - 2].as_ptr()).arg };
-          whnf(arg1, should_count);
-          whnf(arg2, should_count);
-          match (*arg1, *arg2) {
-            (DAG::Lit(x_link), DAG::Lit(y_link)) => {
-              let x = unsafe { &(*x_link.as_ptr()).lit };
-              let y = unsafe { &(*y_link.as_ptr()).lit };
-              let res = opr.apply2(x, y);
-              if let Some(res) = res {
-                trail.pop();
-                let top = DAG::App(trail.pop().unwrap());
-                let new_node =
-                  DAG::Lit(alloc_val(Lit { lit: res, parents: None }));
-                replace_child(top, new_node);
-                free_dead_node(top);
-                node = new_node;
-              }
-              else {
-                break;
-              }
+          free_dead_node(self.node);
+          self.node = bod;
+        },
+        DAG::Opr(link) => {
+          let opr = unsafe { (*link.as_ptr()).opr.clone() };
+          let len = trail.len();
+          if len == 0 && opr.arity() == 0 {
+            let res = opr.apply(&[]);
+            if let Some(res) = res {
+              self.node = DAG::Lit(alloc_val(Lit { lit: res, parents: None }));
+            }
+            else {
+              break;
             }
-            _ => break,
           }
-        }
-        else if len >= 3 && opr.arity() == 3 {
-          let arg1 = unsafe { &mut (*trail[len - 1].as_ptr()).arg };
-          let arg2 = unsafe { &mut (*trail[len - 2].as_ptr()).arg };
-          let arg3 = unsafe { &mut (*trail[len - 3].as_ptr()).arg };
-          whnf(arg1, should_count);
-          whnf(arg2, should_count);
-          whnf(arg3, should_count);
-          match (*arg1, *arg2, *arg3) {
-            (DAG::Lit(x_link), DAG::Lit(y_link), DAG::Lit(z_link)) => {
-              let x = unsafe { &(*x_link.as_ptr()).lit };
-              let y = unsafe { &(*y_link.as_ptr()).lit };
-              let z = unsafe { &(*z_link.as_ptr()).lit };
-              let res = opr.apply3(x, y, z);
-              if let Some(res) = res {
-                trail.pop();
-                trail.pop();
-                let top = DAG::App(trail.pop().unwrap());
-                let new_node =
-                  DAG::Lit(alloc_val(Lit { lit: res, parents: None }));
-                replace_child(top, new_node);
-                free_dead_node(top);
-                node = new_node;
+          else if opr.arity() > 0 && len as u64 >= opr.arity() {
+            let arity = opr.arity() as usize;
+            let mut lits = Vec::with_capacity(arity);
+            for i in 1..=self.reduced {
+              let arg = unsafe { (*trail[len - i].as_ptr()).arg };
+              match arg {
+                DAG::Lit(link) => lits.push(unsafe { &(*link.as_ptr()).lit }),
+                _ => break,
               }
-              else {
-                break;
+            }
+            if lits.len() < self.reduced {
+              break;
+            }
+            if self.reduced < arity {
+              self.reduced += 1;
+              let arg =
+                unsafe { &mut (*trail[len - self.reduced].as_ptr()).arg };
+              return Some(Frame::new(arg));
+            }
+            self.reduced = 0;
+            if !fuel.burn() {
+              break;
+            }
+            let res = match opr.apply(&lits) {
+              Some(res) => {
+                Some(DAG::Lit(alloc_val(Lit { lit: res, parents: None })))
               }
+              None => opr
+                .apply_term(&lits)
+                .map(|res| from_term(Rc::new(Defs::new()), &res, None)),
+            };
+            if let Some(new_node) = res {
+              trail.truncate(len - arity + 1);
+              let top = DAG::App(trail.pop().unwrap());
+              replace_child(top, new_node);
+              free_dead_node(top);
+              self.node = new_node;
+            }
+            else {
+              break;
             }
-            _ => break,
+          }
+          else {
+            break;
           }
         }
-        else {
-          break;
-        }
+        _ => break,
       }
-      _ => break,
     }
-  }
-  if trail.is_empty() {
-    *dag = node;
-  }
-  else {
-    *dag = DAG::App(trail[0]);
+    None
   }
 }
 
@@ -1,3 +1,4 @@
+use sp_cid::Cid;
 use sp_std::rc::Rc;
 
 use yatima_core::{
@@ -39,7 +40,7 @@ pub fn transform(defs: Rc<Defs>, term: &mut Term, runtime: RunIO) {
     Term::App(_pos, boxed) => {
       transform_boxed2(defs, boxed, runtime);
     }
-    Term::All(_pos, _uses, _name, boxed) => {
+    Term::All(_pos, _imp, _uses, _name, boxed) => {
       transform_boxed2(defs, boxed, runtime);
     }
     Term::Slf(_pos, _name, boxed) => {
@@ -51,40 +52,20 @@ pub fn transform(defs: Rc<Defs>, term: &mut Term, runtime: RunIO) {
     Term::Cse(_pos, boxed) => {
       transform_boxed(defs, boxed, runtime);
     }
-    Term::Ref(_pos, _name, exp, _cid2) => match exp.to_string().as_ref() {
-      IO_RETURN => {
-        *term = yatima!("lambda x => x");
+    Term::Ref(_pos, _name, exp, _cid2) => {
+      if let Some(io) = io_ref(exp, &runtime) {
+        *term = io;
         transform(defs, term, runtime);
       }
-      IO_PRINT => {
-        *term = yatima!(
-          "lambda _type x => #$0 x",
-          Term::Opr(Pos::None, Op::Io(runtime.write_stdout_op()))
-        );
+      else if let Some(def) = defs.defs.get(exp) {
+        *term = def.term.clone();
         transform(defs, term, runtime);
       }
-      IO_READ => {
-        *term = yatima!(
-          "lambda _type => #$0",
-          Term::Opr(Pos::None, Op::Io(runtime.read_stdin_op()))
-        );
-        transform(defs, term, runtime);
-      }
-      IO_BIND => {
-        *term = yatima!("lambda _type1 _type2 io fun => fun io");
-        transform(defs, term, runtime);
-      }
-      _ => {
-        if let Some(def) = defs.defs.get(exp) {
-          *term = def.term.clone();
-          transform(defs, term, runtime);
-        }
-      }
-    },
+    }
     Term::Let(_pos, _bool, _uses, _name, boxed) => {
       transform_boxed3(defs, boxed, runtime);
     }
-    Term::Typ(_pos) => {}
+    Term::Typ(..) => {}
     Term::Ann(_pos, boxed) => {
       transform_boxed2(defs, boxed, runtime);
     }
@@ -95,6 +76,24 @@ pub fn transform(defs: Rc<Defs>, term: &mut Term, runtime: RunIO) {
   }
 }
 
+/// The runtime implementation of a reference to one of the IO primitives, if
+/// `exp` is one
+pub fn io_ref(exp: &Cid, runtime: &RunIO) -> Option<Term> {
+  match exp.to_string().as_ref() {
+    IO_RETURN => Some(yatima!("lambda x => x")),
+    IO_PRINT => Some(yatima!(
+      "lambda _type x => #$0 x",
+      Term::Opr(Pos::None, Op::Io(runtime.write_stdout_op()))
+    )),
+    IO_READ => Some(yatima!(
+      "lambda _type => #$0",
+      Term::Opr(Pos::None, Op::Io(runtime.read_stdin_op()))
+    )),
+    IO_BIND => Some(yatima!("lambda _type1 _type2 io fun => fun io")),
+    _ => None,
+  }
+}
+
 fn transform_boxed(
   defs: Rc<Defs>,
   boxed: &mut Box<Term>,
@@ -132,17 +131,41 @@ pub trait RuntimeIO {
   fn read_stdin_op(&self) -> IoOp {
     todo!("read_stdin not implemented for this runtime")
   }
+  /// Carries out `#IO.print`. Without an output the text is dropped
+  fn print(&self, _text: &str) {}
+  /// Carries out `#IO.read_line`, with `None` at the end of the input
+  fn read_line(&self) -> Option<String> { None }
+  /// Carries out `#IO.read_file`, with the message the program's error
+  /// continuation gets on failure
+  fn read_file(&self, _path: &str) -> Result<Vec<u8>, String> {
+    Err("Files aren't available in this runtime".to_owned())
+  }
+  /// Carries out `#IO.write_file`, failing like `read_file`
+  fn write_file(&self, _path: &str, _bytes: &[u8]) -> Result<(), String> {
+    Err("Files aren't available in this runtime".to_owned())
+  }
+  /// The arguments `#IO.get_args` gives the program
+  fn args(&self) -> Vec<String> { vec![] }
+  /// Looks up an environment variable for `#IO.get_env`
+  fn env(&self, _name: &str) -> Option<String> { None }
 }
 /// An Rc wrapper for a RuntimeIO instance
 pub type RunIO = Rc<dyn RuntimeIO>;
 
 /// A RuntimeIO using std::io
 #[derive(Clone)]
-pub struct StdIORuntime {}
+pub struct StdIORuntime {
+  args: Vec<String>,
+}
 
 impl StdIORuntime {
   pub fn new() -> Self {
-    StdIORuntime {}
+    StdIORuntime { args: vec![] }
+  }
+
+  /// Gives the program the arguments it was run with
+  pub fn with_args(args: Vec<String>) -> Self {
+    StdIORuntime { args }
   }
 }
 
@@ -151,47 +174,64 @@ impl RuntimeIO for StdIORuntime {
     fn fun_symbol() -> String { "write_stdout".to_string() }
     fn fun_type_of() -> Term { yatima!("#Bytes -> #Bool") }
     fn fun_arity() -> u64 { 1 }
-    fn fun_apply0() -> Option<Literal> { None }
-    let fun_apply1 = Rc::new(|lit: Literal| -> Option<Literal> {
-      match lit {
-        Literal::Bytes(bytes) => {
+    let fun_apply = Rc::new(|args: &[&Literal]| -> Option<Literal> {
+      match args {
+        [Literal::Bytes(bytes)] => {
           use std::io::Write;
           let mut stdout = std::io::stdout();
-          Some(Literal::Bool(stdout.write_all(&bytes).is_ok()))
+          Some(Literal::Bool(stdout.write_all(bytes).is_ok()))
         }
         _ => None,
       }
     });
-    fn fun_apply2(_: Literal, _: Literal) -> Option<Literal> { None }
-    IoOp {
-      fun_symbol,
-      fun_type_of,
-      fun_arity,
-      fun_apply0,
-      fun_apply1,
-      fun_apply2,
-    }
+    IoOp { fun_symbol, fun_type_of, fun_arity, fun_apply }
   }
 
   fn read_stdin_op(&self) -> IoOp {
     fn fun_symbol() -> String { "read_stdin".to_string() }
     fn fun_type_of() -> Term { yatima!("#Bytes") }
     fn fun_arity() -> u64 { 0 }
-    fn fun_apply0() -> Option<Literal> {
-      let stdin = std::io::stdin();
-      let mut buf = String::new();
-      let _ = stdin.read_line(&mut buf).unwrap();
-      Some(Literal::Bytes(buf.as_bytes().to_vec()))
-    }
-    let fun_apply1 = Rc::new(|_: Literal| -> Option<Literal> { None });
-    fn fun_apply2(_: Literal, _: Literal) -> Option<Literal> { None }
-    IoOp {
-      fun_symbol,
-      fun_type_of,
-      fun_arity,
-      fun_apply0,
-      fun_apply1,
-      fun_apply2,
+    let fun_apply = Rc::new(|args: &[&Literal]| -> Option<Literal> {
+      match args {
+        [] => {
+          let stdin = std::io::stdin();
+          let mut buf = String::new();
+          let _ = stdin.read_line(&mut buf).unwrap();
+          Some(Literal::Bytes(buf.as_bytes().to_vec()))
+        }
+        _ => None,
+      }
+    });
+    IoOp { fun_symbol, fun_type_of, fun_arity, fun_apply }
+  }
+
+  fn print(&self, text: &str) {
+    use std::io::Write;
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(text.as_bytes()).and_then(|_| stdout.flush());
+  }
+
+  fn read_line(&self) -> Option<String> {
+    let mut buf = String::new();
+    match std::io::stdin().read_line(&mut buf) {
+      Ok(0) | Err(_) => None,
+      Ok(_) => {
+        let len = buf.trim_end_matches(&['\n', '\r'][..]).len();
+        buf.truncate(len);
+        Some(buf)
+      }
     }
   }
+
+  fn read_file(&self, path: &str) -> Result<Vec<u8>, String> {
+    std::fs::read(path).map_err(|e| e.to_string())
+  }
+
+  fn write_file(&self, path: &str, bytes: &[u8]) -> Result<(), String> {
+    std::fs::write(path, bytes).map_err(|e| e.to_string())
+  }
+
+  fn args(&self) -> Vec<String> { self.args.clone() }
+
+  fn env(&self, name: &str) -> Option<String> { std::env::var(name).ok() }
 }
@@ -0,0 +1,610 @@
+//! A bytecode compiler and stack machine for erased terms, as a faster
+//! alternative to graph reduction for code that runs to a value. Terms and
+//! defs are erased before they're compiled, so types and the arguments of
+//! binders with no uses are never evaluated.
+//!
+//! Terms compile to blocks of instructions for a lazy Krivine machine:
+//! arguments are pushed onto the stack as suspended thunks, which are shared
+//! and updated with their value when first forced, lambdas pop them into the
+//! environment, and saturated primitive operations force their arguments to
+//! literals. The machine never reduces under a lambda, so it can't normalize
+//! proofs.
+
+use std::{
+  cell::RefCell,
+  collections::BTreeMap,
+  fmt,
+  rc::Rc,
+};
+
+use sp_cid::Cid;
+use yatima_core::{
+  defs::Defs,
+  erase::{
+    erase_def,
+    erase_term,
+    Erased,
+  },
+  fuel::{
+    Fuel,
+    Limits,
+    OutOfFuel,
+  },
+  literal::Literal,
+  name::Name,
+  prim::exception::ExceptionOp,
+  term::{
+    Op,
+    Term,
+  },
+};
+
+use crate::transform::{
+  io_ref,
+  RunIO,
+};
+
+/// A bytecode instruction. Every block ends with one of the instructions
+/// from `Var` on, which enters a value with the arguments on the stack
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Instr {
+  /// Pushes the block at the given index as a suspended argument
+  PushBlock(usize),
+  /// Pushes the environment entry at the given de Bruijn index as an argument
+  PushVar(usize),
+  /// Pushes the constant at the given pool index as an argument
+  PushConst(usize),
+  /// Pushes an erased type or irrelevant argument
+  PushErased,
+  /// Pops an argument into the environment, or returns the enclosing lambda
+  /// as a closure if the stack is empty
+  Grab,
+  /// Binds the block at the given index, suspended, in the environment
+  Let(usize),
+  /// Binds the block at the given index like `Let`, but within its own
+  /// environment as well
+  LetRec(usize),
+  /// Enters the environment entry at the given de Bruijn index
+  Var(usize),
+  /// Enters the definition at the given global index
+  Global(usize),
+  /// Enters the constant at the given pool index
+  Const(usize),
+  /// Enters the primitive operation at the given pool index
+  Opr(usize),
+  /// Enters an erased type
+  Erased,
+}
+
+/// Compiled bytecode, along with the pools of literals and primitive
+/// operations its instructions refer to
+#[derive(Clone, Debug, Default)]
+pub struct Program {
+  pub blocks: Vec<Vec<Instr>>,
+  pub consts: Vec<Literal>,
+  pub oprs: Vec<Op>,
+}
+
+/// A value in weak head normal form
+#[derive(Clone, Debug)]
+pub enum Value {
+  Lit(Literal),
+  /// The lambda at the given block and instruction, closed over an
+  /// environment
+  Closure(usize, usize, Env),
+  /// A primitive operation applied to fewer arguments than its arity
+  Opr(Op, Vec<Thunk>),
+  Erased,
+}
+
+/// The evaluation state of an argument or binding
+#[derive(Clone, Debug)]
+pub enum State {
+  Delayed(usize, Env),
+  Forcing,
+  Done(Value),
+}
+
+pub type Thunk = Rc<RefCell<State>>;
+
+fn delayed(block: usize, env: Env) -> Thunk {
+  Rc::new(RefCell::new(State::Delayed(block, env)))
+}
+
+fn done(val: Value) -> Thunk { Rc::new(RefCell::new(State::Done(val))) }
+
+/// A persistent environment, with de Bruijn index 0 at its head
+#[derive(Clone, Debug, Default)]
+pub struct Env(Option<Rc<(Thunk, Env)>>);
+
+impl Env {
+  pub fn extend(&self, thunk: Thunk) -> Self {
+    Env(Some(Rc::new((thunk, self.clone()))))
+  }
+
+  pub fn get(&self, idx: usize) -> Result<Thunk, VmError> {
+    let mut env = self;
+    for _ in 0..idx {
+      match &env.0 {
+        Some(node) => env = &node.1,
+        None => return Err(VmError::FreeVariable(idx)),
+      }
+    }
+    match &env.0 {
+      Some(node) => Ok(node.0.clone()),
+      None => Err(VmError::FreeVariable(idx)),
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub enum VmError {
+  UndefinedReference(Name, Cid),
+  FreeVariable(usize),
+  FreeRecursion,
+  NotAFunction(Literal),
+  ErasedApplied,
+  StuckOperation(Op),
+  InfiniteLoop,
+  OutOfFuel(OutOfFuel),
+}
+
+impl fmt::Display for VmError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UndefinedReference(nam, exp) => {
+        write!(f, "Undefined runtime reference: {}, {}", nam, exp)
+      }
+      Self::FreeVariable(idx) => write!(f, "Free variable at index {}", idx),
+      Self::FreeRecursion => {
+        write!(f, "Recursive reference outside of a definition")
+      }
+      Self::NotAFunction(lit) => {
+        write!(f, "Literal {} applied to an argument", lit)
+      }
+      Self::ErasedApplied => write!(f, "Erased type applied to an argument"),
+      Self::StuckOperation(opr) => {
+        write!(f, "Operation {} is stuck on its arguments", opr)
+      }
+      Self::InfiniteLoop => write!(f, "Value depends on itself"),
+      Self::OutOfFuel(err) => write!(f, "{}", err),
+    }
+  }
+}
+
+/// Where the machine goes after entering a value
+enum Next {
+  Return(Value),
+  Jump(usize, usize, Env),
+}
+
+/// Compiles terms and definitions on demand and runs the resulting bytecode
+pub struct Vm {
+  pub program: Program,
+  defs: Rc<Defs>,
+  runtime: RunIO,
+  globals: Vec<Thunk>,
+  global_idx: BTreeMap<Cid, usize>,
+  fuel: Fuel,
+}
+
+impl Vm {
+  pub fn new(defs: Rc<Defs>, runtime: RunIO) -> Self {
+    Vm {
+      program: Program::default(),
+      defs,
+      runtime,
+      globals: vec![],
+      global_idx: BTreeMap::new(),
+      fuel: Fuel::unlimited(),
+    }
+  }
+
+  /// Bounds the evaluations from now on, each entry of a value being a step
+  pub fn set_limits(&mut self, limits: Limits) { self.fuel = limits.fuel(); }
+
+  /// Reduces a closed term to weak head normal form, where `Rec` refers to
+  /// the term itself
+  pub fn eval_term(&mut self, term: &Term) -> Result<Value, VmError> {
+    let idx = self.new_global();
+    let block = self.compile_term(term, Some(idx))?;
+    let global = self.globals[idx].clone();
+    *global.borrow_mut() = State::Delayed(block, Env::default());
+    self.force(&global)
+  }
+
+  /// Erases a term and compiles it to a new block
+  pub fn compile_term(
+    &mut self,
+    term: &Term,
+    rec: Option<usize>,
+  ) -> Result<usize, VmError> {
+    let erased = erase_term(&self.defs, term);
+    self.compile(&erased, rec)
+  }
+
+  /// Compiles an erased term to a new block and returns its index. `rec` is
+  /// the global index of the definition the term belongs to, if any
+  pub fn compile(
+    &mut self,
+    term: &Erased,
+    rec: Option<usize>,
+  ) -> Result<usize, VmError> {
+    let mut code = vec![];
+    self.emit(term, rec, &mut code)?;
+    self.program.blocks.push(code);
+    Ok(self.program.blocks.len() - 1)
+  }
+
+  fn emit(
+    &mut self,
+    term: &Erased,
+    rec: Option<usize>,
+    code: &mut Vec<Instr>,
+  ) -> Result<(), VmError> {
+    match term {
+      Erased::Var(_, idx) => code.push(Instr::Var(*idx as usize)),
+      Erased::Lit(lit) => code.push(Instr::Const(self.constant(lit))),
+      Erased::Opr(opr) => code.push(Instr::Opr(self.operation(opr))),
+      Erased::Rec => {
+        code.push(Instr::Global(rec.ok_or(VmError::FreeRecursion)?))
+      }
+      Erased::Ref(nam, exp) => match io_ref(exp, &self.runtime) {
+        Some(io) => {
+          let io = erase_term(&self.defs, &io);
+          self.emit(&io, None, code)?
+        }
+        None => code.push(Instr::Global(self.global(nam, exp)?)),
+      },
+      Erased::Lam(_, bod) => {
+        code.push(Instr::Grab);
+        self.emit(bod, rec, code)?;
+      }
+      Erased::App(fun_arg) => {
+        let (fun, arg) = &**fun_arg;
+        let push = match arg {
+          Erased::Var(_, idx) => Instr::PushVar(*idx as usize),
+          Erased::Lit(lit) => Instr::PushConst(self.constant(lit)),
+          Erased::Erased => Instr::PushErased,
+          _ => Instr::PushBlock(self.compile(arg, rec)?),
+        };
+        code.push(push);
+        self.emit(fun, rec, code)?;
+      }
+      Erased::Let(is_rec, _, exp_bod) => {
+        let (exp, bod) = &**exp_bod;
+        let block = self.compile(exp, rec)?;
+        code.push(if *is_rec {
+          Instr::LetRec(block)
+        }
+        else {
+          Instr::Let(block)
+        });
+        self.emit(bod, rec, code)?;
+      }
+      Erased::Erased => code.push(Instr::Erased),
+    }
+    Ok(())
+  }
+
+  fn constant(&mut self, lit: &Literal) -> usize {
+    self.program.consts.push(lit.clone());
+    self.program.consts.len() - 1
+  }
+
+  fn operation(&mut self, opr: &Op) -> usize {
+    self.program.oprs.push(self.defs.host.resolve(opr.clone()));
+    self.program.oprs.len() - 1
+  }
+
+  fn new_global(&mut self) -> usize {
+    self.globals.push(Rc::new(RefCell::new(State::Forcing)));
+    self.globals.len() - 1
+  }
+
+  /// Gets the global index of a definition, compiling it the first time it
+  /// is referenced
+  fn global(&mut self, nam: &Name, exp: &Cid) -> Result<usize, VmError> {
+    if let Some(idx) = self.global_idx.get(exp) {
+      return Ok(*idx);
+    }
+    let term = match self.defs.defs.get(exp) {
+      Some(def) => erase_def(&self.defs, def),
+      None => return Err(VmError::UndefinedReference(nam.clone(), *exp)),
+    };
+    let idx = self.new_global();
+    self.global_idx.insert(*exp, idx);
+    let block = self.compile(&term, Some(idx))?;
+    *self.globals[idx].borrow_mut() = State::Delayed(block, Env::default());
+    Ok(idx)
+  }
+
+  /// Evaluates a thunk, or returns its value if it was already evaluated
+  pub fn force(&mut self, thunk: &Thunk) -> Result<Value, VmError> {
+    let (block, env) = match &*thunk.borrow() {
+      State::Done(val) => return Ok(val.clone()),
+      State::Forcing => return Err(VmError::InfiniteLoop),
+      State::Delayed(block, env) => (*block, env.clone()),
+    };
+    *thunk.borrow_mut() = State::Forcing;
+    match self.eval(block, 0, env.clone(), vec![]) {
+      Ok(val) => {
+        *thunk.borrow_mut() = State::Done(val.clone());
+        Ok(val)
+      }
+      Err(err) => {
+        *thunk.borrow_mut() = State::Delayed(block, env);
+        Err(err)
+      }
+    }
+  }
+
+  fn eval(
+    &mut self,
+    mut block: usize,
+    mut pc: usize,
+    mut env: Env,
+    mut stack: Vec<Thunk>,
+  ) -> Result<Value, VmError> {
+    loop {
+      let instr = self.program.blocks[block][pc];
+      pc += 1;
+      let head = match instr {
+        Instr::PushBlock(idx) => {
+          stack.push(delayed(idx, env.clone()));
+          continue;
+        }
+        Instr::PushVar(idx) => {
+          stack.push(env.get(idx)?);
+          continue;
+        }
+        Instr::PushConst(idx) => {
+          stack.push(done(Value::Lit(self.program.consts[idx].clone())));
+          continue;
+        }
+        Instr::PushErased => {
+          stack.push(done(Value::Erased));
+          continue;
+        }
+        Instr::Grab => match stack.pop() {
+          Some(arg) => {
+            env = env.extend(arg);
+            continue;
+          }
+          None => return Ok(Value::Closure(block, pc - 1, env)),
+        },
+        Instr::Let(idx) => {
+          env = env.extend(delayed(idx, env.clone()));
+          continue;
+        }
+        Instr::LetRec(idx) => {
+          let thunk = Rc::new(RefCell::new(State::Forcing));
+          env = env.extend(thunk.clone());
+          *thunk.borrow_mut() = State::Delayed(idx, env.clone());
+          continue;
+        }
+        Instr::Var(idx) => self.force(&env.get(idx)?)?,
+        Instr::Global(idx) => {
+          let global = self.globals[idx].clone();
+          self.force(&global)?
+        }
+        Instr::Const(idx) => Value::Lit(self.program.consts[idx].clone()),
+        Instr::Opr(idx) => Value::Opr(self.program.oprs[idx].clone(), vec![]),
+        Instr::Erased => Value::Erased,
+      };
+      if !self.fuel.burn() {
+        let err = self.fuel.out_of_fuel().unwrap();
+        return Err(VmError::OutOfFuel(err));
+      }
+      match self.enter(head, &mut stack)? {
+        Next::Return(val) => return Ok(val),
+        Next::Jump(new_block, new_pc, new_env) => {
+          block = new_block;
+          pc = new_pc;
+          env = new_env;
+        }
+      }
+    }
+  }
+
+  /// Applies a value to the arguments on the stack
+  fn enter(
+    &mut self,
+    mut head: Value,
+    stack: &mut Vec<Thunk>,
+  ) -> Result<Next, VmError> {
+    loop {
+      match head {
+        Value::Closure(block, pc, env) => {
+          return Ok(Next::Jump(block, pc, env))
+        }
+        Value::Lit(lit) if !stack.is_empty() => match lit.clone().expand() {
+          Some(term) => {
+            let block = self.compile_term(&term, None)?;
+            return Ok(Next::Jump(block, 0, Env::default()));
+          }
+          None => return Err(VmError::NotAFunction(lit)),
+        },
+        Value::Erased if !stack.is_empty() => {
+          return Err(VmError::ErasedApplied);
+        }
+        Value::Opr(opr, mut args) => {
+          while (args.len() as u64) < opr.arity() {
+            match stack.pop() {
+              Some(arg) => args.push(arg),
+              None => return Ok(Next::Return(Value::Opr(opr, args))),
+            }
+          }
+          match opr {
+            Op::Exception(ExceptionOp::Raise) => {
+              let tag = self.force_lit(&opr, &args[1])?;
+              let payload = self.force_lit(&opr, &args[2])?;
+              match ExceptionOp::raise(&tag, &payload) {
+                Some(exc) => head = Value::Lit(exc),
+                None => return Err(VmError::StuckOperation(opr)),
+              }
+            }
+            Op::Exception(ExceptionOp::Catch) => {
+              let (tag, payload) = match self.force(&args[1]) {
+                Ok(Value::Lit(Literal::Exception(tag, payload))) => {
+                  (Literal::Text(tag), Literal::Bytes(payload))
+                }
+                Err(VmError::StuckOperation(stuck)) => {
+                  (Literal::Text(stuck.symbol().into()), Literal::Bytes(vec![]))
+                }
+                res => {
+                  head = res?;
+                  continue;
+                }
+              };
+              stack.push(done(Value::Lit(payload)));
+              stack.push(done(Value::Lit(tag)));
+              head = self.force(&args[2])?;
+            }
+            _ => {
+              let mut lits = Vec::with_capacity(args.len());
+              for arg in args.iter() {
+                lits.push(self.force_lit(&opr, arg)?);
+              }
+              let lits: Vec<&Literal> = lits.iter().collect();
+              if let Some(res) = opr.raised(&lits).or_else(|| opr.apply(&lits))
+              {
+                head = Value::Lit(res);
+              }
+              else if let Some(term) = opr.apply_term(&lits) {
+                let block = self.compile_term(&term, None)?;
+                return Ok(Next::Jump(block, 0, Env::default()));
+              }
+              else {
+                return Err(VmError::StuckOperation(opr));
+              }
+            }
+          }
+        }
+        val => return Ok(Next::Return(val)),
+      }
+    }
+  }
+
+  fn force_lit(&mut self, opr: &Op, arg: &Thunk) -> Result<Literal, VmError> {
+    match self.force(arg)? {
+      Value::Lit(lit) => Ok(lit),
+      _ => Err(VmError::StuckOperation(opr.clone())),
+    }
+  }
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::{
+    Value,
+    Vm,
+    VmError,
+  };
+  use crate::transform::StdIORuntime;
+  use std::rc::Rc;
+  use yatima_core::{
+    dag::DAG,
+    defs::Defs,
+    fuel::Limits,
+    literal::Literal,
+    parse::{
+      self,
+      span::Span,
+      term::input_cid,
+    },
+  };
+
+  fn eval(src: &str) -> Result<Value, VmError> {
+    let (_, term) = parse::term::parse(src, Defs::new()).unwrap();
+    let mut vm = Vm::new(Rc::new(Defs::new()), Rc::new(StdIORuntime::new()));
+    vm.eval_term(&term)
+  }
+
+  fn eval_lit(src: &str) -> Option<Literal> {
+    match eval(src) {
+      Ok(Value::Lit(lit)) => Some(lit),
+      _ => None,
+    }
+  }
+
+  #[test]
+  fn vm_test_opr() {
+    assert_eq!(
+      eval_lit("(λ x => #U64.add x 2u64) 40u64"),
+      Some(Literal::U64(42))
+    );
+    assert_eq!(
+      eval_lit("let f: ∀ #U64 -> #U64 = #U64.mul 3u64; f (f 2u64)"),
+      Some(Literal::U64(18))
+    );
+    assert!(matches!(
+      eval("#U64.div 1u64 0u64"),
+      Err(VmError::StuckOperation(_))
+    ));
+  }
+
+  #[test]
+  fn vm_test_lazy() {
+    assert_eq!(
+      eval_lit("(λ x y => y) (#U64.div 1u64 0u64) 1u64"),
+      Some(Literal::U64(1))
+    );
+    assert!(matches!(
+      eval("λ x => #U64.div 1u64 0u64"),
+      Ok(Value::Closure(..))
+    ));
+  }
+
+  #[test]
+  fn vm_test_letrec() {
+    assert_eq!(
+      eval_lit(
+        "letrec sum (n: #U64): #U64 = (case (#U64.eql n 0u64)) Type 0u64 \
+         (#U64.add n (sum (#U64.sub n 1u64))); sum 20u64"
+      ),
+      Some(Literal::U64(210))
+    );
+  }
+
+  #[test]
+  fn vm_test_fuel() {
+    let src = "letrec f (n: #U64): #U64 = f n; f 0u64";
+    let (_, term) = parse::term::parse(src, Defs::new()).unwrap();
+    let mut vm = Vm::new(Rc::new(Defs::new()), Rc::new(StdIORuntime::new()));
+    vm.set_limits(Limits::steps(100));
+    assert!(matches!(
+      vm.eval_term(&term),
+      Err(VmError::OutOfFuel(err)) if err.steps == 100
+    ));
+  }
+
+  #[test]
+  fn vm_test_erased() {
+    let src = "def id (0 A: Type) (x: A): A = x\n\
+               def const (0 n: #U64) (x: #U64): #U64 = x\n\
+               def Fun: Type = ∀ (0 n: #U64) (x: #U64) -> #U64\n\
+               def apply (f: Fun): #U64 = f (#U64.div 1u64 0u64) 2u64";
+    let (_, (defs, _)) =
+      parse::package::parse_defs(input_cid(src), Defs::new())(Span::new(src))
+        .unwrap();
+    let defs = Rc::new(defs);
+    for (input, expected) in [
+      ("id #U64 3u64", 3),
+      ("const (#U64.div 1u64 0u64) 4u64", 4),
+      ("apply const", 2),
+      ("#U64.add (id #U64 5u64) (apply (λ n x => x))", 7),
+    ] {
+      let (_, term) = parse::term::parse(input, (*defs).clone()).unwrap();
+      // Graph reduction of the unerased term agrees with the VM
+      let mut dag = DAG::from_term(&term);
+      dag.norm(&defs, false);
+      assert_eq!(format!("{}", dag.to_term(false)), format!("{}u64", expected));
+      let mut vm = Vm::new(defs.clone(), Rc::new(StdIORuntime::new()));
+      assert!(matches!(
+        vm.eval_term(&term),
+        Ok(Value::Lit(Literal::U64(x))) if x == expected
+      ));
+    }
+  }
+}
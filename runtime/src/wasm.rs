@@ -0,0 +1,588 @@
+//! WebAssembly code generation for first-order numeric definitions.
+//!
+//! A definition compiles to a wasm function when its type takes and returns
+//! `#Bool`, `#U32`, `#I32`, `#U64` or `#I64` literals, and its body is built
+//! from variables, literals, non-recursive lets, fully applied primitive
+//! operations that have a wasm counterpart, calls to other such definitions
+//! and `case` on a `#Bool`. Types are erased, `case` becomes `if`, and
+//! operations that would be stuck in the interpreter, like division by zero,
+//! trap instead.
+
+use std::{
+  collections::BTreeMap,
+  fmt,
+};
+
+use sp_cid::Cid;
+use yatima_core::{
+  defs::Defs,
+  literal::{
+    LitType,
+    Literal,
+  },
+  name::Name,
+  term::{
+    Op,
+    Term,
+  },
+};
+
+/// A wasm value type
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum ValType {
+  I32,
+  I64,
+}
+
+impl ValType {
+  pub fn from_lit_type(lty: LitType) -> Option<Self> {
+    match lty {
+      LitType::Bool | LitType::U32 | LitType::I32 => Some(Self::I32),
+      LitType::U64 | LitType::I64 => Some(Self::I64),
+      _ => None,
+    }
+  }
+
+  fn code(self) -> u8 {
+    match self {
+      Self::I32 => 0x7f,
+      Self::I64 => 0x7e,
+    }
+  }
+}
+
+#[derive(Clone, Debug)]
+pub enum WasmError {
+  UndefinedReference(Name),
+  UnsupportedType(Name),
+  NotFirstOrder(Name),
+  UnsupportedOp(Op),
+  UnsupportedTerm(Name, String),
+  TypeMismatch(Name, String),
+}
+
+impl fmt::Display for WasmError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::UndefinedReference(nam) => {
+        write!(f, "Undefined reference {}", nam)
+      }
+      Self::UnsupportedType(nam) => write!(
+        f,
+        "The type of {} must be a function of #Bool, #U32, #I32, #U64 or #I64 \
+         literals",
+        nam
+      ),
+      Self::NotFirstOrder(nam) => {
+        write!(f, "{} must be a lambda over each of its arguments", nam)
+      }
+      Self::UnsupportedOp(opr) => {
+        write!(f, "Operation {} has no wasm counterpart", opr)
+      }
+      Self::UnsupportedTerm(nam, term) => {
+        write!(f, "Can't compile {} in {} to wasm", term, nam)
+      }
+      Self::TypeMismatch(nam, term) => {
+        write!(f, "Unexpected wasm type for {} in {}", term, nam)
+      }
+    }
+  }
+}
+
+/// How a primitive operation compiles: the types of its arguments, its
+/// result type, whether its two arguments are pushed in reverse, and the
+/// instructions that follow them
+struct Prim {
+  args: Vec<ValType>,
+  ret: ValType,
+  swap: bool,
+  code: Vec<u8>,
+}
+
+impl Prim {
+  fn new(args: Vec<ValType>, ret: ValType, code: Vec<u8>) -> Self {
+    Prim { args, ret, swap: false, code }
+  }
+}
+
+fn bool_prim(sym: &str) -> Option<Prim> {
+  use ValType::I32;
+  let code = match sym {
+    "not" => return Some(Prim::new(vec![I32], I32, vec![0x45])),
+    "eql" => 0x46,
+    "lth" => 0x49,
+    "gth" => 0x4b,
+    "lte" => 0x4d,
+    "gte" => 0x4f,
+    "and" => 0x71,
+    "or" => 0x72,
+    "xor" => 0x73,
+    _ => return None,
+  };
+  Some(Prim::new(vec![I32, I32], I32, vec![code]))
+}
+
+fn prim(opr: &Op) -> Option<Prim> {
+  use ValType::*;
+  let (ty, signed, sym) = match opr {
+    Op::Bool(op) => return bool_prim(&op.symbol()),
+    Op::U32(op) => (I32, false, op.symbol()),
+    Op::I32(op) => (I32, true, op.symbol()),
+    Op::U64(op) => (I64, false, op.symbol()),
+    Op::I64(op) => (I64, true, op.symbol()),
+    _ => return None,
+  };
+  // The i64 opcodes are laid out like the i32 ones, from `eq` for
+  // comparisons and from `clz` for arithmetic
+  let (cmp, num) = match ty {
+    I32 => (0x46, 0x67),
+    I64 => (0x51, 0x79),
+  };
+  let unsigned = if signed { 0 } else { 1 };
+  let cmp = match sym.as_str() {
+    "eql" => Some(cmp),
+    "lth" => Some(cmp + 2 + unsigned),
+    "gth" => Some(cmp + 4 + unsigned),
+    "lte" => Some(cmp + 6 + unsigned),
+    "gte" => Some(cmp + 8 + unsigned),
+    _ => None,
+  };
+  if let Some(code) = cmp {
+    return Some(Prim::new(vec![ty, ty], I32, vec![code]));
+  }
+  let bin = match sym.as_str() {
+    "add" | "wrapping_add" => Some(num + 3),
+    "sub" | "wrapping_sub" => Some(num + 4),
+    "mul" | "wrapping_mul" => Some(num + 5),
+    "div" if !signed => Some(num + 7),
+    "mod" => Some(num + 8 + unsigned),
+    "and" => Some(num + 10),
+    "or" => Some(num + 11),
+    "xor" => Some(num + 12),
+    _ => None,
+  };
+  if let Some(code) = bin {
+    return Some(Prim::new(vec![ty, ty], ty, vec![code]));
+  }
+  // The shift amount comes first and is always a #U32
+  let shift = match sym.as_str() {
+    "shl" => Some(num + 13),
+    "shr" => Some(num + 14 + unsigned),
+    "rol" => Some(num + 16),
+    "ror" => Some(num + 17),
+    _ => None,
+  };
+  if let Some(code) = shift {
+    let code = if ty == I64 { vec![0xad, code] } else { vec![code] };
+    return Some(Prim { args: vec![I32, ty], ret: ty, swap: true, code });
+  }
+  let count = match sym.as_str() {
+    "leading_zeros" if !signed => Some(num),
+    "trailing_zeros" if !signed => Some(num + 1),
+    "count_ones" => Some(num + 2),
+    _ => None,
+  };
+  if let Some(code) = count {
+    let code = if ty == I64 { vec![code, 0xa7] } else { vec![code] };
+    return Some(Prim::new(vec![ty], I32, code));
+  }
+  match (sym.as_str(), ty, signed) {
+    ("not", I32, _) => Some(Prim::new(vec![ty], ty, vec![0x41, 0x7f, 0x73])),
+    ("not", I64, _) => Some(Prim::new(vec![ty], ty, vec![0x42, 0x7f, 0x85])),
+    ("to_U64", I32, false) | ("to_I64", I32, false) => {
+      Some(Prim::new(vec![I32], I64, vec![0xad]))
+    }
+    ("to_I64", I32, true) => Some(Prim::new(vec![I32], I64, vec![0xac])),
+    _ => None,
+  }
+}
+
+fn uleb(out: &mut Vec<u8>, mut x: u64) {
+  loop {
+    let byte = (x & 0x7f) as u8;
+    x >>= 7;
+    if x == 0 {
+      out.push(byte);
+      return;
+    }
+    out.push(byte | 0x80);
+  }
+}
+
+fn sleb(out: &mut Vec<u8>, mut x: i64) {
+  loop {
+    let byte = (x & 0x7f) as u8;
+    x >>= 7;
+    if (x == 0 && byte & 0x40 == 0) || (x == -1 && byte & 0x40 != 0) {
+      out.push(byte);
+      return;
+    }
+    out.push(byte | 0x80);
+  }
+}
+
+fn section(out: &mut Vec<u8>, id: u8, content: Vec<u8>) {
+  out.push(id);
+  uleb(out, content.len() as u64);
+  out.extend(content);
+}
+
+struct Func {
+  name: Name,
+  params: Vec<ValType>,
+  ret: ValType,
+  locals: Vec<ValType>,
+  code: Vec<u8>,
+}
+
+/// The state of the function being compiled. `ctx` maps each binder in
+/// scope, innermost last, to its local
+struct Body {
+  func: u32,
+  locals: Vec<ValType>,
+  ctx: Vec<u32>,
+  code: Vec<u8>,
+}
+
+struct Compiler<'a> {
+  defs: &'a Defs,
+  funcs: Vec<Func>,
+  index: BTreeMap<Cid, u32>,
+}
+
+impl<'a> Compiler<'a> {
+  /// Gets the index of the function for a definition, compiling it the
+  /// first time it is referenced
+  fn func(&mut self, nam: &Name, exp: &Cid) -> Result<u32, WasmError> {
+    if let Some(idx) = self.index.get(exp) {
+      return Ok(*idx);
+    }
+    let defs = self.defs;
+    let def = match defs.defs.get(exp) {
+      Some(def) => def,
+      None => return Err(WasmError::UndefinedReference(nam.clone())),
+    };
+    let mut params = vec![];
+    let mut typ = &def.typ_;
+    while let Term::All(_, _, _, _, dom_img) = typ {
+      let (dom, img) = &**dom_img;
+      match dom {
+        Term::LTy(_, lty) => match ValType::from_lit_type(*lty) {
+          Some(ty) => params.push(ty),
+          None => return Err(WasmError::UnsupportedType(nam.clone())),
+        },
+        _ => return Err(WasmError::UnsupportedType(nam.clone())),
+      }
+      typ = img;
+    }
+    let ret = match typ {
+      Term::LTy(_, lty) => ValType::from_lit_type(*lty)
+        .ok_or_else(|| WasmError::UnsupportedType(nam.clone()))?,
+      _ => return Err(WasmError::UnsupportedType(nam.clone())),
+    };
+    let idx = self.funcs.len() as u32;
+    self.index.insert(*exp, idx);
+    self.funcs.push(Func {
+      name: nam.clone(),
+      params: params.clone(),
+      ret,
+      locals: vec![],
+      code: vec![],
+    });
+    let mut term = &def.term;
+    for _ in 0..params.len() {
+      match term {
+        Term::Lam(_, _, bod) => term = bod,
+        _ => return Err(WasmError::NotFirstOrder(nam.clone())),
+      }
+    }
+    let mut body = Body {
+      func: idx,
+      locals: params.clone(),
+      ctx: (0..params.len() as u32).collect(),
+      code: vec![],
+    };
+    self.expr(&mut body, term, ret)?;
+    body.code.push(0x0b);
+    let func = &mut self.funcs[idx as usize];
+    func.locals = body.locals.split_off(params.len());
+    func.code = body.code;
+    Ok(idx)
+  }
+
+  fn mismatch(&self, body: &Body, term: &Term) -> WasmError {
+    let nam = self.funcs[body.func as usize].name.clone();
+    WasmError::TypeMismatch(nam, term.to_string())
+  }
+
+  fn unsupported(&self, body: &Body, term: &Term) -> WasmError {
+    let nam = self.funcs[body.func as usize].name.clone();
+    WasmError::UnsupportedTerm(nam, term.to_string())
+  }
+
+  /// Compiles a term whose value has type `ty`
+  fn expr(
+    &mut self,
+    body: &mut Body,
+    term: &Term,
+    ty: ValType,
+  ) -> Result<(), WasmError> {
+    match term {
+      Term::Var(_, _, idx) => {
+        let local = body.ctx.len().checked_sub(1 + *idx as usize);
+        let local = match local.map(|i| body.ctx[i]) {
+          Some(local) => local,
+          None => return Err(self.unsupported(body, term)),
+        };
+        if body.locals[local as usize] != ty {
+          return Err(self.mismatch(body, term));
+        }
+        body.code.push(0x20);
+        uleb(&mut body.code, local as u64);
+      }
+      Term::Lit(_, lit) => match (lit, ty) {
+        (Literal::Bool(x), ValType::I32) => {
+          body.code.push(0x41);
+          sleb(&mut body.code, *x as i64);
+        }
+        (Literal::U32(x), ValType::I32) => {
+          body.code.push(0x41);
+          sleb(&mut body.code, *x as i32 as i64);
+        }
+        (Literal::I32(x), ValType::I32) => {
+          body.code.push(0x41);
+          sleb(&mut body.code, *x as i64);
+        }
+        (Literal::U64(x), ValType::I64) => {
+          body.code.push(0x42);
+          sleb(&mut body.code, *x as i64);
+        }
+        (Literal::I64(x), ValType::I64) => {
+          body.code.push(0x42);
+          sleb(&mut body.code, *x);
+        }
+        _ => return Err(self.mismatch(body, term)),
+      },
+      Term::Ann(_, typ_exp) => self.expr(body, &typ_exp.1, ty)?,
+      Term::Let(_, false, _, _, typ_exp_bod) => {
+        let (typ, exp, bod) = &**typ_exp_bod;
+        let exp_ty = match typ {
+          Term::LTy(_, lty) => ValType::from_lit_type(*lty),
+          _ => None,
+        };
+        let exp_ty = match exp_ty {
+          Some(exp_ty) => exp_ty,
+          None => return Err(self.unsupported(body, term)),
+        };
+        self.expr(body, exp, exp_ty)?;
+        let local = body.locals.len() as u32;
+        body.locals.push(exp_ty);
+        body.code.push(0x21);
+        uleb(&mut body.code, local as u64);
+        body.ctx.push(local);
+        self.expr(body, bod, ty)?;
+        body.ctx.pop();
+      }
+      Term::App(..) | Term::Opr(..) | Term::Ref(..) | Term::Rec(_) => {
+        self.app(body, term, ty)?
+      }
+      _ => return Err(self.unsupported(body, term)),
+    }
+    Ok(())
+  }
+
+  fn app(
+    &mut self,
+    body: &mut Body,
+    term: &Term,
+    ty: ValType,
+  ) -> Result<(), WasmError> {
+    let mut head = term;
+    let mut args = vec![];
+    while let Term::App(_, fun_arg) = head {
+      let (fun, arg) = &**fun_arg;
+      args.push(arg);
+      head = fun;
+    }
+    args.reverse();
+    match head {
+      Term::Opr(_, opr) => {
+        let prim =
+          prim(opr).ok_or_else(|| WasmError::UnsupportedOp(opr.clone()))?;
+        if args.len() != prim.args.len() {
+          return Err(self.unsupported(body, term));
+        }
+        if prim.ret != ty {
+          return Err(self.mismatch(body, term));
+        }
+        if prim.swap {
+          self.expr(body, args[1], prim.args[1])?;
+          self.expr(body, args[0], prim.args[0])?;
+        }
+        else {
+          for (arg, arg_ty) in args.iter().zip(prim.args.iter()) {
+            self.expr(body, arg, *arg_ty)?;
+          }
+        }
+        body.code.extend(prim.code);
+      }
+      Term::Ref(..) | Term::Rec(_) => {
+        let idx = match head {
+          Term::Ref(_, nam, exp, _) => self.func(nam, exp)?,
+          _ => body.func,
+        };
+        let func = &self.funcs[idx as usize];
+        let (params, ret) = (func.params.clone(), func.ret);
+        if args.len() != params.len() {
+          return Err(self.unsupported(body, term));
+        }
+        if ret != ty {
+          return Err(self.mismatch(body, term));
+        }
+        for (arg, arg_ty) in args.iter().zip(params.iter()) {
+          self.expr(body, arg, *arg_ty)?;
+        }
+        body.code.push(0x10);
+        uleb(&mut body.code, idx as u64);
+      }
+      // A `#Bool` expands to `λ P t f => t` or `λ P t f => f`, so after the
+      // motive come the true and false branches
+      Term::Cse(_, bod) if args.len() == 3 => {
+        self.expr(body, bod, ValType::I32)?;
+        body.code.push(0x04);
+        body.code.push(ty.code());
+        self.expr(body, args[1], ty)?;
+        body.code.push(0x05);
+        self.expr(body, args[2], ty)?;
+        body.code.push(0x0b);
+      }
+      _ => return Err(self.unsupported(body, term)),
+    }
+    Ok(())
+  }
+
+  fn module(&self, exports: &[(Name, u32)]) -> Vec<u8> {
+    let mut out = b"\0asm".to_vec();
+    out.extend(&[1, 0, 0, 0]);
+    let mut types = vec![];
+    uleb(&mut types, self.funcs.len() as u64);
+    for func in self.funcs.iter() {
+      types.push(0x60);
+      uleb(&mut types, func.params.len() as u64);
+      types.extend(func.params.iter().map(|ty| ty.code()));
+      types.push(1);
+      types.push(func.ret.code());
+    }
+    section(&mut out, 1, types);
+    // Function `i` has type `i`
+    let mut funcs = vec![];
+    uleb(&mut funcs, self.funcs.len() as u64);
+    for idx in 0..self.funcs.len() {
+      uleb(&mut funcs, idx as u64);
+    }
+    section(&mut out, 3, funcs);
+    let mut exps = vec![];
+    uleb(&mut exps, exports.len() as u64);
+    for (nam, idx) in exports {
+      let nam = nam.to_string();
+      uleb(&mut exps, nam.len() as u64);
+      exps.extend(nam.as_bytes());
+      exps.push(0x00);
+      uleb(&mut exps, *idx as u64);
+    }
+    section(&mut out, 7, exps);
+    let mut code = vec![];
+    uleb(&mut code, self.funcs.len() as u64);
+    for func in self.funcs.iter() {
+      let mut groups: Vec<(u64, ValType)> = vec![];
+      for ty in func.locals.iter() {
+        match groups.last_mut() {
+          Some((count, last)) if *last == *ty => *count += 1,
+          _ => groups.push((1, *ty)),
+        }
+      }
+      let mut fun = vec![];
+      uleb(&mut fun, groups.len() as u64);
+      for (count, ty) in groups {
+        uleb(&mut fun, count);
+        fun.push(ty.code());
+      }
+      fun.extend(&func.code);
+      uleb(&mut code, fun.len() as u64);
+      code.extend(fun);
+    }
+    section(&mut out, 10, code);
+    out
+  }
+}
+
+/// Compiles the definitions named in `entries`, and those they reference,
+/// into a wasm module exporting each entry under its name
+pub fn compile(defs: &Defs, entries: &[Name]) -> Result<Vec<u8>, WasmError> {
+  let mut compiler = Compiler { defs, funcs: vec![], index: BTreeMap::new() };
+  let mut exports = vec![];
+  for nam in entries {
+    let exp = match defs.names.get(nam) {
+      Some(exp) => *exp,
+      None => return Err(WasmError::UndefinedReference(nam.clone())),
+    };
+    exports.push((nam.clone(), compiler.func(nam, &exp)?));
+  }
+  Ok(compiler.module(&exports))
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::{
+    compile,
+    WasmError,
+  };
+  use yatima_core::{
+    defs::Defs,
+    name::Name,
+    parse::{
+      package,
+      span::Span,
+      term::input_cid,
+    },
+  };
+
+  fn parse_defs(src: &str) -> Defs {
+    let (_, (defs, _)) =
+      package::parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    defs
+  }
+
+  #[test]
+  fn wasm_test_module() {
+    let defs = parse_defs("def inc (n: #U32): #U32 = #U32.add n 1u32");
+    let module = compile(&defs, &[Name::from("inc")]).unwrap();
+    #[rustfmt::skip]
+    let expected = vec![
+      0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00,
+      // type section: (i32) -> i32
+      0x01, 0x06, 0x01, 0x60, 0x01, 0x7f, 0x01, 0x7f,
+      // function section
+      0x03, 0x02, 0x01, 0x00,
+      // export section: "inc"
+      0x07, 0x07, 0x01, 0x03, 0x69, 0x6e, 0x63, 0x00, 0x00,
+      // code section: local.get 0, i32.const 1, i32.add
+      0x0a, 0x09, 0x01, 0x07, 0x00, 0x20, 0x00, 0x41, 0x01, 0x6a, 0x0b,
+    ];
+    assert_eq!(module, expected);
+  }
+
+  #[test]
+  fn wasm_test_compile() {
+    let defs = parse_defs(
+      "def sum (n: #U64): #U64 = (case (#U64.eql n 0u64)) (λ _ => #U64) 0u64 \
+       (#U64.add n (sum (#U64.sub n 1u64)))
+       def twice (f: ∀ #U64 -> #U64) (n: #U64): #U64 = f (f n)",
+    );
+    assert!(compile(&defs, &[Name::from("sum")]).is_ok());
+    assert!(matches!(
+      compile(&defs, &[Name::from("twice")]),
+      Err(WasmError::UnsupportedType(_))
+    ));
+  }
+}
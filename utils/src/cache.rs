@@ -0,0 +1,405 @@
+use sp_cid::Cid;
+use sp_ipld::{
+  dag_cbor::cid,
+  Ipld,
+};
+use std::{
+  cell::RefCell,
+  collections::{
+    BTreeMap,
+    BTreeSet,
+    HashMap,
+  },
+  fmt,
+  mem,
+  rc::Rc,
+};
+use yatima_core::{
+  check::{
+    check_def,
+    error::CheckError,
+  },
+  defs::{
+    Def,
+    Defs,
+  },
+  literal::Literal,
+  memo::NormCache,
+  name::Name,
+  term::Term,
+};
+
+/// Default memory budget of the session cache, in bytes
+pub const DEFAULT_BUDGET: usize = 64 * 1024 * 1024;
+
+thread_local! {
+  static SESSION: Rc<RefCell<DefCache>> =
+    Rc::new(RefCell::new(DefCache::new(DEFAULT_BUDGET)));
+  static NORMS: Rc<RefCell<NormCache>> =
+    Rc::new(RefCell::new(NormCache::new()));
+}
+
+/// The cache shared by everything running in this session, whether the REPL,
+/// the language server or a CLI command
+pub fn session() -> Rc<RefCell<DefCache>> { SESSION.with(|s| s.clone()) }
+
+/// The normal forms computed in this session. Frontends with a persistent
+/// store can attach it with `NormCache::set_store`
+pub fn session_norms() -> Rc<RefCell<NormCache>> { NORMS.with(|n| n.clone()) }
+
+/// A record of checked defs that outlives the session, keyed by
+/// `check_key`
+pub trait CheckStore: fmt::Debug {
+  fn is_checked(&self, key: &Cid) -> bool;
+
+  fn set_checked(&self, key: Cid);
+}
+
+#[derive(Clone, Debug)]
+struct Cached {
+  def: Def,
+  checked: bool,
+  size: usize,
+  used: u64,
+}
+
+/// A least recently used cache of decoded defs, keyed by the CID of their
+/// package entry, which evicts defs once their estimated size exceeds a
+/// memory budget
+#[derive(Clone, Debug)]
+pub struct DefCache {
+  entries: HashMap<Cid, Cached>,
+  recency: BTreeMap<u64, Cid>,
+  budget: usize,
+  size: usize,
+  clock: u64,
+  hits: u64,
+  misses: u64,
+  store: Option<Rc<dyn CheckStore>>,
+}
+
+impl DefCache {
+  pub fn new(budget: usize) -> Self {
+    DefCache {
+      entries: HashMap::new(),
+      recency: BTreeMap::new(),
+      budget,
+      size: 0,
+      clock: 0,
+      hits: 0,
+      misses: 0,
+      store: None,
+    }
+  }
+
+  /// Attaches a persistent record of checked defs, which `check_def_cached`
+  /// consults on a miss and writes every newly checked def to
+  pub fn set_store(&mut self, store: Option<Rc<dyn CheckStore>>) {
+    self.store = store;
+  }
+
+  /// Looks up a def, marking it as the most recently used
+  pub fn get(&mut self, cid: &Cid) -> Option<Def> {
+    match self.touch(cid) {
+      Some(cached) => {
+        let def = cached.def.clone();
+        self.hits += 1;
+        Some(def)
+      }
+      None => {
+        self.misses += 1;
+        None
+      }
+    }
+  }
+
+  /// Whether a def with this CID has already passed type checking
+  pub fn is_checked(&self, cid: &Cid) -> bool {
+    self.entries.get(cid).map_or(false, |c| c.checked)
+  }
+
+  /// Adds a decoded def, evicting the least recently used ones if the budget
+  /// is exceeded
+  pub fn insert(&mut self, def: Def) {
+    let cid = def.cid();
+    if self.touch(&cid).is_some() {
+      return;
+    }
+    let size = def_size(&def);
+    self.clock += 1;
+    self.recency.insert(self.clock, cid);
+    self.entries.insert(cid, Cached {
+      def,
+      checked: false,
+      size,
+      used: self.clock,
+    });
+    self.size += size;
+    self.evict();
+  }
+
+  /// Records that a def has passed type checking, adding it if needed
+  pub fn set_checked(&mut self, def: &Def) {
+    self.insert(def.clone());
+    if let Some(cached) = self.entries.get_mut(&def.cid()) {
+      cached.checked = true;
+    }
+  }
+
+  /// Changes the memory budget, evicting defs if it shrinks
+  pub fn set_budget(&mut self, budget: usize) {
+    self.budget = budget;
+    self.evict();
+  }
+
+  pub fn clear(&mut self) {
+    self.entries.clear();
+    self.recency.clear();
+    self.size = 0;
+  }
+
+  pub fn len(&self) -> usize { self.entries.len() }
+
+  pub fn is_empty(&self) -> bool { self.entries.is_empty() }
+
+  /// The estimated memory held by the cached defs, in bytes
+  pub fn size(&self) -> usize { self.size }
+
+  fn touch(&mut self, cid: &Cid) -> Option<&Cached> {
+    let cached = self.entries.get_mut(cid)?;
+    self.clock += 1;
+    self.recency.remove(&cached.used);
+    self.recency.insert(self.clock, *cid);
+    cached.used = self.clock;
+    Some(cached)
+  }
+
+  fn evict(&mut self) {
+    while self.size > self.budget {
+      let oldest = match self.recency.keys().next() {
+        Some(used) => *used,
+        None => break,
+      };
+      if let Some(cid) = self.recency.remove(&oldest) {
+        if let Some(cached) = self.entries.remove(&cid) {
+          self.size -= cached.size;
+        }
+      }
+    }
+  }
+}
+
+impl fmt::Display for DefCache {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(
+      f,
+      "{} defs, {} of {} bytes, {} hits, {} misses",
+      self.len(),
+      self.size,
+      self.budget,
+      self.hits,
+      self.misses
+    )
+  }
+}
+
+/// Estimates the memory held by a def from the size of its terms
+pub fn def_size(def: &Def) -> usize {
+  mem::size_of::<Def>() + term_size(&def.typ_) + term_size(&def.term)
+}
+
+fn name_size(name: &Name) -> usize { name.len() }
+
+fn term_size(term: &Term) -> usize {
+  let node = mem::size_of::<Term>();
+  match term {
+    Term::Var(_, n, _) | Term::Ref(_, n, ..) => node + name_size(n),
+    Term::Lam(_, n, b) | Term::Slf(_, n, b) => {
+      node + name_size(n) + term_size(b)
+    }
+    Term::Dat(_, b) | Term::Cse(_, b) => node + term_size(b),
+    Term::App(_, b) | Term::Ann(_, b) => {
+      node + term_size(&b.0) + term_size(&b.1)
+    }
+    Term::All(_, _, _, n, b) => {
+      node + name_size(n) + term_size(&b.0) + term_size(&b.1)
+    }
+    Term::Let(_, _, _, n, b) => {
+      node + name_size(n) + term_size(&b.0) + term_size(&b.1) + term_size(&b.2)
+    }
+    Term::Lit(_, lit) => node + literal_size(lit),
+    Term::Typ(..) | Term::LTy(..) | Term::Opr(..) | Term::Rec(_) => node,
+  }
+}
+
+fn literal_size(lit: &Literal) -> usize {
+  match lit {
+    Literal::Nat(x) => x.bits() as usize / 8,
+    Literal::Int(x) => x.bits() as usize / 8,
+    Literal::Bits(x) => x.len() / 8,
+    Literal::Bytes(x) => x.len(),
+    Literal::Text(x) => x.len_bytes(),
+    _ => 0,
+  }
+}
+
+/// The key a checked def is persisted under: the def's CID, the CIDs of the
+/// types of the defs it refers to, and the version of the checker. Any
+/// change to the def or to what it relies on from its dependencies gives a
+/// new key.
+pub fn check_key(defs: &Defs, def: &Def) -> Cid {
+  let mut deps = BTreeSet::new();
+  refs(&def.typ_, &mut deps);
+  refs(&def.term, &mut deps);
+  let deps = deps
+    .into_iter()
+    .filter_map(|dep| defs.defs.get(&dep))
+    .map(|dep| Ipld::Link(dep.typ_.embed().0.cid()))
+    .collect();
+  cid(&Ipld::List(vec![
+    Ipld::String(env!("CARGO_PKG_VERSION").to_owned()),
+    Ipld::Link(def.cid()),
+    Ipld::List(deps),
+  ]))
+}
+
+fn refs(term: &Term, cids: &mut BTreeSet<Cid>) {
+  match term {
+    Term::Ref(_, _, def_cid, _) => {
+      cids.insert(*def_cid);
+    }
+    Term::Lam(_, _, bod)
+    | Term::Slf(_, _, bod)
+    | Term::Dat(_, bod)
+    | Term::Cse(_, bod) => refs(bod, cids),
+    Term::App(_, xs) | Term::All(_, _, _, _, xs) | Term::Ann(_, xs) => {
+      refs(&xs.0, cids);
+      refs(&xs.1, cids);
+    }
+    Term::Let(_, _, _, _, xs) => {
+      refs(&xs.0, cids);
+      refs(&xs.1, cids);
+      refs(&xs.2, cids);
+    }
+    _ => (),
+  }
+}
+
+/// Checks the def `name` in `defs`, skipping the check if a def with the same
+/// CID has already been checked in this session, or with the same
+/// `check_key` in an earlier one. Since a def's CID covers the CIDs of
+/// everything it references, a def checks the same way wherever it appears.
+pub fn check_def_cached(
+  cache: &RefCell<DefCache>,
+  defs: Rc<Defs>,
+  name: &str,
+) -> Result<Term, CheckError> {
+  let def = defs.get(&Name::from(name));
+  let store = cache.borrow().store.clone();
+  let key = match (def, store.as_ref()) {
+    (Some(def), Some(_)) => Some(check_key(&defs, def)),
+    _ => None,
+  };
+  if let Some(def) = def {
+    if cache.borrow().is_checked(&def.cid()) {
+      return Ok(def.typ_.clone());
+    }
+    if let (Some(store), Some(key)) = (store.as_ref(), key.as_ref()) {
+      if store.is_checked(key) {
+        cache.borrow_mut().set_checked(def);
+        return Ok(def.typ_.clone());
+      }
+    }
+  }
+  let typ_ = check_def(defs.clone(), name, false)?;
+  if let Some(def) = def {
+    cache.borrow_mut().set_checked(def);
+  }
+  if let (Some(store), Some(key)) = (store, key) {
+    store.set_checked(key);
+  }
+  Ok(typ_)
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use yatima_core::parse::{
+    package::parse_defs,
+    span::Span,
+    term::input_cid,
+  };
+
+  fn defs(src: &str) -> Defs {
+    let (_, (defs, _)) =
+      parse_defs(input_cid(src), Defs::new())(Span::new(src)).unwrap();
+    defs
+  }
+
+  #[test]
+  fn test_eviction() {
+    let ds = defs("def a: Type = Type\ndef b: Type = ∀ Type -> Type\n");
+    let a = ds.get(&Name::from("a")).unwrap().clone();
+    let b = ds.get(&Name::from("b")).unwrap().clone();
+    let mut cache = DefCache::new(def_size(&a) + def_size(&b));
+    cache.insert(a.clone());
+    cache.insert(b.clone());
+    assert_eq!(cache.len(), 2);
+    assert!(cache.get(&a.cid()).is_some());
+    cache.set_budget(def_size(&a));
+    assert!(cache.get(&b.cid()).is_none());
+    assert_eq!(cache.size(), def_size(&a));
+    assert_eq!(cache.get(&a.cid()), Some(a));
+  }
+
+  #[test]
+  fn test_check_cached() {
+    let cache = RefCell::new(DefCache::new(DEFAULT_BUDGET));
+    let ds = Rc::new(defs("def id (A: Type) (x: A): A = x\n"));
+    let id = ds.get(&Name::from("id")).unwrap();
+    assert!(!cache.borrow().is_checked(&id.cid()));
+    let typ_ = check_def_cached(&cache, ds.clone(), "id").unwrap();
+    assert!(cache.borrow().is_checked(&id.cid()));
+    assert_eq!(check_def_cached(&cache, ds, "id").unwrap(), typ_);
+  }
+
+  #[derive(Debug, Default)]
+  struct MemChecks {
+    keys: RefCell<BTreeSet<Cid>>,
+    found: RefCell<u64>,
+  }
+
+  impl CheckStore for MemChecks {
+    fn is_checked(&self, key: &Cid) -> bool {
+      let found = self.keys.borrow().contains(key);
+      *self.found.borrow_mut() += found as u64;
+      found
+    }
+
+    fn set_checked(&self, key: Cid) { self.keys.borrow_mut().insert(key); }
+  }
+
+  #[test]
+  fn test_check_store() {
+    let store = Rc::new(MemChecks::default());
+    let src = "def Nat: Type = #Nat\ndef two: Nat = 2\n";
+    let ds = Rc::new(defs(src));
+    let session = |ds: &Rc<Defs>| {
+      let cache = RefCell::new(DefCache::new(DEFAULT_BUDGET));
+      cache.borrow_mut().set_store(Some(store.clone()));
+      check_def_cached(&cache, ds.clone(), "two").unwrap();
+    };
+    session(&ds);
+    assert_eq!(store.keys.borrow().len(), 1);
+    assert_eq!(*store.found.borrow(), 0);
+    // A later session finds the def checked without checking it again
+    session(&ds);
+    assert_eq!(*store.found.borrow(), 1);
+    // A change to a dependency gives a new key
+    let changed = Rc::new(defs("def Nat: Type = #U64\ndef two: Nat = 2u64\n"));
+    let two = changed.get(&Name::from("two")).unwrap();
+    assert!(!store.keys.borrow().contains(&check_key(&changed, two)));
+    session(&changed);
+    assert_eq!(store.keys.borrow().len(), 2);
+  }
+}
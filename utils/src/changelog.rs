@@ -0,0 +1,275 @@
+use crate::{
+  diff::{
+    diff_packages,
+    get_package,
+    Change,
+  },
+  store::{
+    load_package_defs,
+    CallbackResult,
+    Store,
+  },
+};
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+use std::{
+  fmt,
+  rc::Rc,
+};
+use yatima_core::{
+  check::check_def,
+  defs::{
+    Def,
+    Defs,
+  },
+  name::Name,
+  package::{
+    Entry,
+    Package,
+  },
+  position::Pos,
+};
+
+/// How a change affects code depending on the old package, in the usual
+/// semantic versioning terms
+#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Debug)]
+pub enum Impact {
+  /// Only definitions' implementations changed
+  Patch,
+  /// Definitions were added, or their types changed in a way that the new
+  /// definitions still check against the old types
+  Minor,
+  /// Definitions were removed, renamed, or given incompatible types
+  Major,
+}
+
+impl fmt::Display for Impact {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Patch => write!(f, "patch"),
+      Self::Minor => write!(f, "minor"),
+      Self::Major => write!(f, "major"),
+    }
+  }
+}
+
+/// A change together with what can be derived about it from the packages'
+/// content
+#[derive(PartialEq, Clone, Debug)]
+pub struct ChangelogEntry {
+  pub change: Change,
+  pub impact: Impact,
+  /// The type in the old package, if the definition existed there
+  pub old_type: Option<String>,
+  /// The type in the new package, if the definition exists there
+  pub new_type: Option<String>,
+  /// The comment preceding the definition in its source
+  pub doc: Option<String>,
+}
+
+impl ChangelogEntry {
+  fn summary(&self) -> String {
+    let typed = |name: &Name, typ_: &Option<String>| match typ_ {
+      Some(t) => format!("`{} : {}`", name, t),
+      None => format!("`{}`", name),
+    };
+    match &self.change {
+      Change::Added(n, _) => format!("Added {}", typed(n, &self.new_type)),
+      Change::Removed(n, _) => format!("Removed {}", typed(n, &self.old_type)),
+      Change::Renamed(o, n, _) => format!("Renamed `{}` to `{}`", o, n),
+      Change::Term(n, ..) => format!("Changed the definition of `{}`", n),
+      Change::Type(n, ..) => format!(
+        "Changed the type of `{}` from `{}` to `{}`{}",
+        n,
+        self.old_type.as_deref().unwrap_or("?"),
+        self.new_type.as_deref().unwrap_or("?"),
+        if self.impact == Impact::Minor {
+          " (the old type still checks)"
+        }
+        else {
+          ""
+        }
+      ),
+    }
+  }
+}
+
+/// Release notes for the changes between two versions of a package
+#[derive(PartialEq, Clone, Debug)]
+pub struct Changelog {
+  pub package: Name,
+  pub old: Cid,
+  pub new: Cid,
+  pub entries: Vec<ChangelogEntry>,
+}
+
+impl Changelog {
+  /// The largest impact of any change, or `None` if nothing changed
+  pub fn impact(&self) -> Option<Impact> {
+    self.entries.iter().map(|e| e.impact).max()
+  }
+
+  /// Renders the changelog as markdown, grouped by impact
+  pub fn to_markdown(&self) -> String {
+    let mut md = format!("# {}\n\n", self.package);
+    md.push_str(&format!("`{}` → `{}`\n\n", self.old, self.new));
+    match self.impact() {
+      None => md.push_str("No changes.\n"),
+      Some(impact) => md.push_str(&format!("Release: **{}**\n", impact)),
+    }
+    let sections = [
+      (Impact::Major, "Breaking changes"),
+      (Impact::Minor, "Additions"),
+      (Impact::Patch, "Fixes"),
+    ];
+    for (impact, title) in sections.iter() {
+      let entries: Vec<&ChangelogEntry> =
+        self.entries.iter().filter(|e| e.impact == *impact).collect();
+      if entries.is_empty() {
+        continue;
+      }
+      md.push_str(&format!("\n## {}\n\n", title));
+      for entry in entries {
+        md.push_str(&format!("- {}\n", entry.summary()));
+        if let Some(doc) = &entry.doc {
+          for line in doc.lines() {
+            md.push_str(&format!("  > {}\n", line));
+          }
+        }
+      }
+    }
+    md
+  }
+}
+
+impl fmt::Display for Changelog {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{} {} -> {}", self.package, self.old, self.new)?;
+    for entry in &self.entries {
+      writeln!(f, "  [{}] {}", entry.impact, entry.summary())?;
+    }
+    Ok(())
+  }
+}
+
+/// The `//` comment lines directly above `offset` in `source`
+pub fn comment_above(source: &str, offset: usize) -> Option<String> {
+  let mut lines: Vec<&str> = source
+    .get(..offset)?
+    .lines()
+    .rev()
+    .map(str::trim)
+    .skip_while(|l| l.is_empty())
+    .take_while(|l| l.starts_with("//"))
+    .map(|l| l.trim_start_matches('/').trim())
+    .collect();
+  if lines.is_empty() {
+    return None;
+  }
+  lines.reverse();
+  Some(lines.join("\n"))
+}
+
+/// The doc comment of the definition at `entry`, read from its source block
+pub fn doc_comment(store: &Rc<dyn Store>, entry: &Entry) -> Option<String> {
+  match entry.pos {
+    Pos::Some(pos) => match store.get(pos.input) {
+      Some(Ipld::String(source)) => {
+        comment_above(&source, pos.from_offset as usize)
+      }
+      _ => None,
+    },
+    Pos::None => None,
+  }
+}
+
+/// Loads every def reachable from a package, including imported ones
+fn load_defs(store: &Rc<dyn Store>, package: Package) -> Result<Defs, String> {
+  match load_package_defs(store.clone(), Rc::new(package), None)? {
+    CallbackResult::Sync(defs) => Ok(defs),
+    CallbackResult::Callback => Err("Unexpected asynchronous store".to_owned()),
+  }
+}
+
+/// Whether the new version of a def still checks against its old type, in
+/// which case code relying on the old type keeps working
+fn keeps_old_type(
+  old_defs: &Defs,
+  new_defs: &Defs,
+  name: &Name,
+  old: &Def,
+  new: &Def,
+) -> bool {
+  // References are resolved by CID, so both versions' dependencies can live
+  // in one map
+  let mut defs = old_defs.clone().flat_merge(new_defs.clone());
+  let (probe, _) = Def::make(new.pos, old.typ_.clone(), new.term.clone());
+  defs.insert(name.clone(), probe);
+  check_def(Rc::new(defs), name, false).is_ok()
+}
+
+/// Derives a changelog from the content of two versions of a package
+pub fn changelog(
+  store: Rc<dyn Store>,
+  old: Cid,
+  new: Cid,
+) -> Result<Changelog, String> {
+  let diff = diff_packages(store.clone(), old, new)?;
+  let old_package = get_package(&store, old)?;
+  let new_package = get_package(&store, new)?;
+  let package = new_package.name.clone();
+  let old_defs = load_defs(&store, old_package)?;
+  let new_defs = load_defs(&store, new_package)?;
+  let type_of = |defs: &Defs, name: &Name| {
+    defs.get(name).map(|d| d.typ_.pretty(Some(&name.to_string()), false))
+  };
+  let mut entries = Vec::new();
+  for change in diff.changes {
+    let (old_name, new_name, entry) = match &change {
+      Change::Added(n, e) => (None, Some(n), e),
+      Change::Removed(n, e) => (Some(n), None, e),
+      Change::Renamed(o, n, e) => (Some(o), Some(n), e),
+      Change::Term(n, _, e) | Change::Type(n, _, e) => (Some(n), Some(n), e),
+    };
+    let impact = match &change {
+      Change::Added(..) => Impact::Minor,
+      Change::Term(..) => Impact::Patch,
+      Change::Removed(..) | Change::Renamed(..) => Impact::Major,
+      Change::Type(n, ..) => match (old_defs.get(n), new_defs.get(n)) {
+        (Some(o), Some(d)) if keeps_old_type(&old_defs, &new_defs, n, o, d) => {
+          Impact::Minor
+        }
+        _ => Impact::Major,
+      },
+    };
+    entries.push(ChangelogEntry {
+      impact,
+      old_type: old_name.and_then(|n| type_of(&old_defs, n)),
+      new_type: new_name.and_then(|n| type_of(&new_defs, n)),
+      doc: doc_comment(&store, entry),
+      change,
+    });
+  }
+  entries.sort_by(|a, b| b.impact.cmp(&a.impact));
+  Ok(Changelog { package, old, new, entries })
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+
+  #[test]
+  fn test_comment_above() {
+    let src = "package P where\n\n// The identity\n//   function\n\ndef id \
+               (A: Type) (x: A): A = x\n// trailing\ndef x: Type = Type\n";
+    let id = src.find("def id").unwrap();
+    assert_eq!(
+      comment_above(src, id),
+      Some("The identity\nfunction".to_owned())
+    );
+    let x = src.find("def x").unwrap();
+    assert_eq!(comment_above(src, x), Some("trailing".to_owned()));
+    assert_eq!(comment_above(src, 0), None);
+    assert_eq!(comment_above(src, src.find("where").unwrap()), None);
+  }
+}
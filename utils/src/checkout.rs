@@ -0,0 +1,229 @@
+use crate::{
+  diff::{
+    get_entries,
+    get_package,
+  },
+  store::Store,
+};
+use sp_cid::Cid;
+use sp_ipld::Ipld;
+use std::{
+  collections::BTreeSet,
+  path::PathBuf,
+  rc::Rc,
+};
+use yatima_core::{
+  name::Name,
+  package::Package,
+  position::Pos,
+};
+
+/// Source for a subset of the definitions of a package, recovered from the
+/// source positions recorded in its entries
+#[derive(PartialEq, Clone, Debug)]
+pub struct Checkout {
+  /// Where the file belongs, relative to a package root
+  pub path: PathBuf,
+  /// The reconstructed package source
+  pub source: String,
+  /// Definitions whose source was materialized
+  pub local: Vec<Name>,
+  /// Definitions left to be imported from the original package
+  pub imported: Vec<Name>,
+}
+
+/// The relative path of the source file of a package. The parser only
+/// accepts a package from a file named after all of its name, dots included.
+pub fn package_path(package: &Package) -> PathBuf {
+  PathBuf::from(format!("{}.ya", package.name))
+}
+
+/// Renders an import with its link, so that it resolves through the store
+/// rather than the file system
+fn import_source(name: &Name, alias: &Name, with: &[Name], cid: Cid) -> String {
+  let with: Vec<String> = with.iter().map(|n| n.to_string()).collect();
+  if alias.is_empty() {
+    format!("import {} ({}) {}", name, with.join(", "), cid)
+  }
+  else {
+    format!("import {} as {} ({}) {}", name, alias, with.join(", "), cid)
+  }
+}
+
+/// Materializes the source of the definitions `names` of the package at
+/// `cid`, or of every definition if `names` is empty. The other definitions
+/// of the package are imported by CID from the package itself, so that edits
+/// to the checked out definitions can be checked against the unchanged rest.
+pub fn checkout(
+  store: Rc<dyn Store>,
+  cid: Cid,
+  names: &[Name],
+) -> Result<Checkout, String> {
+  let package = get_package(&store, cid)?;
+  let entries = get_entries(&store, &package)?;
+  for n in names {
+    if !entries.contains_key(n) {
+      return Err(format!("Package {} has no definition {}", package.name, n));
+    }
+  }
+  let position = |name: &Name| match entries[name].pos {
+    Pos::Some(pos) => Ok(pos),
+    Pos::None => Err(format!("Definition {} has no source position", name)),
+  };
+  let selected: Vec<Name> =
+    if names.is_empty() { package.index.keys() } else { names.to_vec() };
+  // A type declaration defines several entries from one span of source, so
+  // spans are deduplicated and everything defined inside them becomes local
+  let mut spans = BTreeSet::new();
+  for n in &selected {
+    let pos = position(n)?;
+    spans.insert((pos.input, pos.from_offset, pos.upto_offset));
+  }
+  let mut local = Vec::new();
+  let mut imported = Vec::new();
+  for n in package.index.keys() {
+    let pos = position(&n)?;
+    let inside = spans.iter().any(|(input, from, upto)| {
+      *input == pos.input
+        && *from <= pos.from_offset
+        && pos.upto_offset <= *upto
+    });
+    if inside {
+      local.push(n)
+    }
+    else {
+      imported.push(n)
+    }
+  }
+
+  let mut source = format!("package {}\n", package.name);
+  for import in &package.imports {
+    source.push_str(&import_source(
+      &import.name,
+      &import.alias,
+      &import.with,
+      import.cid,
+    ));
+    source.push('\n');
+  }
+  if !imported.is_empty() {
+    source.push_str(&import_source(
+      &package.name,
+      &Name::from(""),
+      &imported,
+      cid,
+    ));
+    source.push('\n');
+  }
+  source.push_str("where\n");
+  for (input, from, upto) in spans {
+    let text = match store.get(input) {
+      Some(Ipld::String(text)) => text,
+      _ => return Err(format!("Missing source block {}", input)),
+    };
+    let def = text
+      .get(from as usize..upto as usize)
+      .ok_or_else(|| format!("Source block {} is too short", input))?;
+    source.push('\n');
+    source.push_str(def);
+    source.push('\n');
+  }
+  Ok(Checkout { path: package_path(&package), source, local, imported })
+}
+
+#[cfg(test)]
+pub mod tests {
+  use super::*;
+  use crate::{
+    file::parse::{
+      parse_file,
+      parse_text,
+      PackageEnv,
+    },
+    store::Callback,
+  };
+  use multiaddr::Multiaddr;
+  use sp_ipld::dag_cbor::cid;
+  use std::{
+    cell::RefCell,
+    collections::HashMap,
+    fs,
+  };
+  use yatima_core::defs::Defs;
+
+  #[derive(Debug, Default)]
+  pub struct MemStore {
+    blocks: RefCell<HashMap<Cid, Ipld>>,
+  }
+
+  impl Store for MemStore {
+    fn get_by_multiaddr(&self, _addr: Multiaddr) -> Result<Ipld, String> {
+      Err("Not implemented".to_owned())
+    }
+
+    fn load_by_name(&self, _path: Vec<&str>) -> Result<Ipld, String> {
+      Err("Not implemented".to_owned())
+    }
+
+    fn load_by_name_with_callback(
+      &self,
+      _path: Vec<&str>,
+      _callback: Callback<Ipld, Defs>,
+    ) {
+      panic!("Not implemented")
+    }
+
+    fn put(&self, expr: Ipld) -> Cid {
+      let link = cid(&expr);
+      self.blocks.borrow_mut().insert(link, expr);
+      link
+    }
+
+    fn get(&self, link: Cid) -> Option<Ipld> {
+      self.blocks.borrow().get(&link).cloned()
+    }
+
+    fn get_with_callback(&self, _link: Cid, _callback: Callback<Ipld, Defs>) {
+      panic!("Not implemented")
+    }
+
+    fn needs_callback(&self) -> bool { false }
+  }
+
+  #[test]
+  fn test_checkout() {
+    let store: Rc<dyn Store> = Rc::new(MemStore::default());
+    let src = "package Num.Small where\n\ndef two: #Nat = 2\n\n// \
+               Doubled\ndef four: #Nat = #Nat.add two two\n";
+    let root = std::env::temp_dir()
+      .join(format!("yatima-checkout-{}", std::process::id()));
+    let path = PathBuf::from("Num.Small.ya");
+    let env = PackageEnv::new(root.clone(), path.clone(), store.clone());
+    let (cid, _, defs) = parse_text(src, env).unwrap();
+
+    let out = checkout(store.clone(), cid, &[Name::from("four")]).unwrap();
+    assert_eq!(out.path, path);
+    assert_eq!(out.local, vec![Name::from("four")]);
+    assert_eq!(out.imported, vec![Name::from("two")]);
+
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join(&out.path), &out.source).unwrap();
+    let env = PackageEnv::new(root.clone(), out.path, store);
+    let parsed = parse_file(env);
+    fs::remove_dir_all(&root).unwrap();
+    let (_, package, checked_out) = parsed.unwrap();
+    assert_eq!(package.name, Name::from("Num.Small"));
+    assert_eq!(package.index.keys(), vec![Name::from("four")]);
+    // The imported def is the original, and the checked out one is the same
+    // up to its source position
+    let anon = |defs: &Defs, name: &str| {
+      let (_, typ, trm) = defs.get(&Name::from(name)).unwrap().clone().embed();
+      (typ.cid(), trm.cid())
+    };
+    assert_eq!(
+      checked_out.get(&Name::from("two")).map(|d| d.def_cid),
+      defs.get(&Name::from("two")).map(|d| d.def_cid)
+    );
+    assert_eq!(anon(&checked_out, "four"), anon(&defs, "four"));
+  }
+}
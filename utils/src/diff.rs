@@ -0,0 +1,213 @@
+use crate::store::Store;
+use sp_cid::Cid;
+use std::{
+  collections::BTreeMap,
+  fmt,
+  rc::Rc,
+};
+use yatima_core::{
+  name::Name,
+  package::{
+    import_alias,
+    Entry,
+    Package,
+  },
+};
+
+/// A change to a single definition between two versions of a package
+#[derive(PartialEq, Clone, Debug)]
+pub enum Change {
+  /// A definition only present in the new package
+  Added(Name, Entry),
+  /// A definition only present in the old package
+  Removed(Name, Entry),
+  /// A definition whose type and term are unchanged but whose name differs
+  Renamed(Name, Name, Entry),
+  /// A definition whose term changed but whose type did not
+  Term(Name, Entry, Entry),
+  /// A definition whose type changed
+  Type(Name, Entry, Entry),
+}
+
+impl Change {
+  /// The name of the definition in the old package, if it existed there
+  pub fn old_name(&self) -> Option<&Name> {
+    match self {
+      Self::Added(..) => None,
+      Self::Removed(n, _)
+      | Self::Renamed(n, ..)
+      | Self::Term(n, ..)
+      | Self::Type(n, ..) => Some(n),
+    }
+  }
+
+  /// Whether code depending on the old definition may stop typechecking
+  pub fn is_breaking(&self) -> bool {
+    matches!(self, Self::Removed(..) | Self::Renamed(..) | Self::Type(..))
+  }
+}
+
+impl fmt::Display for Change {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Added(n, _) => write!(f, "+ {}", n),
+      Self::Removed(n, _) => write!(f, "- {}", n),
+      Self::Renamed(o, n, _) => write!(f, "~ {} -> {}", o, n),
+      Self::Term(n, ..) => write!(f, "~ {} (term)", n),
+      Self::Type(n, ..) => write!(f, "~ {} (type)", n),
+    }
+  }
+}
+
+/// The definition-level differences between two packages
+#[derive(PartialEq, Clone, Debug)]
+pub struct PackageDiff {
+  pub old: Cid,
+  pub new: Cid,
+  pub changes: Vec<Change>,
+}
+
+impl PackageDiff {
+  /// Returns the renames as `(old, new)` pairs
+  pub fn renames(&self) -> Vec<(Name, Name)> {
+    self
+      .changes
+      .iter()
+      .filter_map(|c| match c {
+        Change::Renamed(o, n, _) => Some((o.clone(), n.clone())),
+        _ => None,
+      })
+      .collect()
+  }
+
+  /// Returns true if no definition changed
+  pub fn is_empty(&self) -> bool { self.changes.is_empty() }
+}
+
+impl fmt::Display for PackageDiff {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(f, "{} -> {}", self.old, self.new)?;
+    for change in &self.changes {
+      writeln!(f, "  {}", change)?;
+    }
+    Ok(())
+  }
+}
+
+/// Loads a package from the store
+pub fn get_package(store: &Rc<dyn Store>, cid: Cid) -> Result<Package, String> {
+  let ipld =
+    store.get(cid).ok_or_else(|| format!("Unknown package {}", cid))?;
+  Ok(Package::from_ipld(&ipld)?)
+}
+
+/// Loads the entries of a package from the store, keyed by name
+pub fn get_entries(
+  store: &Rc<dyn Store>,
+  package: &Package,
+) -> Result<BTreeMap<Name, Entry>, String> {
+  let mut entries = BTreeMap::new();
+  for (name, cid) in &package.index.0 {
+    let ipld =
+      store.get(*cid).ok_or_else(|| format!("Unknown entry {}", cid))?;
+    entries.insert(name.clone(), Entry::from_ipld(&ipld)?);
+  }
+  Ok(entries)
+}
+
+/// Loads the entries a package re-exports, i.e. those its imports bring into
+/// scope, keyed by the name they are in scope under
+pub fn get_reexports(
+  store: &Rc<dyn Store>,
+  package: &Package,
+) -> Result<BTreeMap<Name, Entry>, String> {
+  let mut entries = BTreeMap::new();
+  for import in &package.imports {
+    let dep = get_package(store, import.cid)?;
+    let mut scope = get_entries(store, &dep)?;
+    scope.append(&mut get_reexports(store, &dep)?);
+    for name in &import.with {
+      if let Some(entry) = scope.remove(name) {
+        entries.insert(import_alias(name.clone(), import), entry);
+      }
+    }
+  }
+  Ok(entries)
+}
+
+/// Pairs each name the old package re-exports but which the new one no longer
+/// has in scope with a name newly re-exported for an identical definition,
+/// as `(old, new)` pairs
+pub fn reexport_renames(
+  store: &Rc<dyn Store>,
+  old: Cid,
+  new: Cid,
+) -> Result<Vec<(Name, Name)>, String> {
+  let new_package = get_package(store, new)?;
+  let new_local = get_entries(store, &new_package)?;
+  let new_entries = get_reexports(store, &new_package)?;
+  let old_entries = get_reexports(store, &get_package(store, old)?)?;
+  let mut renames: Vec<(Name, Name)> = Vec::new();
+  for (name, old_entry) in &old_entries {
+    if new_local.contains_key(name) || new_entries.contains_key(name) {
+      continue;
+    }
+    let renamed = new_entries.iter().find(|(n, e)| {
+      !old_entries.contains_key(*n)
+        && !renames.iter().any(|(_, r)| r == *n)
+        && e.type_anon == old_entry.type_anon
+        && e.term_anon == old_entry.term_anon
+    });
+    if let Some((new_name, _)) = renamed {
+      renames.push((name.clone(), new_name.clone()));
+    }
+  }
+  Ok(renames)
+}
+
+/// Compares the definitions exported by two packages. Entries are compared by
+/// the content ids of their anonymous type and term, so that differences in
+/// source positions or binder names alone are not reported. A removed and an
+/// added definition with identical content are reported as a rename.
+pub fn diff_packages(
+  store: Rc<dyn Store>,
+  old: Cid,
+  new: Cid,
+) -> Result<PackageDiff, String> {
+  let old_entries = get_entries(&store, &get_package(&store, old)?)?;
+  let mut new_entries = get_entries(&store, &get_package(&store, new)?)?;
+  let mut changes = Vec::new();
+  let mut removed = Vec::new();
+  for (name, old_entry) in old_entries {
+    match new_entries.remove(&name) {
+      Some(new_entry) => {
+        if old_entry.type_anon != new_entry.type_anon {
+          changes.push(Change::Type(name, old_entry, new_entry));
+        }
+        else if old_entry.term_anon != new_entry.term_anon {
+          changes.push(Change::Term(name, old_entry, new_entry));
+        }
+      }
+      None => removed.push((name, old_entry)),
+    }
+  }
+  for (name, old_entry) in removed {
+    let renamed = new_entries
+      .iter()
+      .find(|(_, e)| {
+        e.type_anon == old_entry.type_anon && e.term_anon == old_entry.term_anon
+      })
+      .map(|(n, _)| n.clone());
+    match renamed {
+      Some(new_name) => {
+        let new_entry = new_entries.remove(&new_name).unwrap();
+        changes.push(Change::Renamed(name, new_name, new_entry));
+      }
+      None => changes.push(Change::Removed(name, old_entry)),
+    }
+  }
+  for (name, new_entry) in new_entries {
+    changes.push(Change::Added(name, new_entry));
+  }
+  Ok(PackageDiff { old, new, changes })
+}
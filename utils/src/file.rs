@@ -1,5 +1,6 @@
 use sp_ipld::Ipld;
 use std::{
+  fmt,
   io::{
     self,
     Error,
@@ -11,11 +12,14 @@ use std::{
 use yatima_core::{
   check::error::CheckError,
   defs::Defs,
+  name::Name,
   package::Package,
   position::Pos,
+  termination,
 };
 
 use crate::{
+  cache,
   debug,
   log,
   store::{
@@ -58,13 +62,14 @@ pub fn check_all_in_ipld(
 }
 
 pub fn check_all(p: Rc<Package>, ds: Rc<Defs>, store: Rc<dyn Store>) -> Result<Rc<Defs>, String> {
+  let session = cache::session();
   for i in &p.imports {
     debug!("Checking import {} at {}", i.name, i.cid);
     for n in &i.with {
-      match yatima_core::check::check_def(
+      match cache::check_def_cached(
+        &session,
         ds.clone(),
         &yatima_core::package::import_alias(n.to_owned(), &i),
-        false,
       ) {
         Ok(ty) => {
           log!("✓ {}: {}", n, ty.pretty(Some(&n.to_string()), false))
@@ -72,37 +77,51 @@ pub fn check_all(p: Rc<Package>, ds: Rc<Defs>, store: Rc<dyn Store>) -> Result<R
         Err(e @ CheckError::UndefinedReference(Pos::None, _)) => {
           log!("✕ {}: {}", n, e);
         }
-        Err(err) => {
-          let def = ds.get(n).unwrap();
-          log!("✕ {}: {}", n, def.typ_.pretty(Some(&n.to_string()), false));
-          if let Pos::Some(pos) = err.pos() {
-            if let Some(Ipld::String(input)) = store.get(pos.input) {
-              log!("{}", pos.range(input))
-            }
-          }
-          log!("Error: {}", err);
-        }
+        Err(err) => log_failure(n, &ds, err.pos(), &err, &*store),
       }
     }
   }
   log!("Checking definitions:");
   for (n, _) in &p.index.0 {
-    match yatima_core::check::check_def(ds.clone(), n, false) {
-      Ok(ty) => log!("✓ {}: {}", n, ty.pretty(Some(&n.to_string()), false)),
+    match cache::check_def_cached(&session, ds.clone(), n) {
+      Ok(ty) => match termination::check_def(n, ds.get(n).unwrap()) {
+        Ok(()) => log!("✓ {}: {}", n, ty.pretty(Some(&n.to_string()), false)),
+        Err(err) => log_failure(n, &ds, err.pos(), &err, &*store),
+      },
       Err(e @ CheckError::UndefinedReference(Pos::None, _)) => {
         log!("✕ {}: {}", n, e);
       }
-      Err(err) => {
-        let def = ds.get(n).unwrap();
-        log!("✕ {}: {}", n, def.typ_.pretty(Some(&n.to_string()), false));
-        if let Pos::Some(pos) = err.pos() {
-          if let Some(Ipld::String(input)) = store.get(pos.input) {
-            log!("{}", pos.range(input))
-          }
-        }
-        log!("Error: {}", err);
-      }
+      Err(err) => log_failure(n, &ds, err.pos(), &err, &*store),
     }
   }
   Ok(ds)
 }
+
+/// Logs a def that failed to check, along with the file, span and source of
+/// the error when they are known
+fn log_failure(
+  n: &Name,
+  ds: &Defs,
+  pos: Pos,
+  err: &dyn fmt::Display,
+  store: &dyn Store,
+) {
+  let def = ds.get(n).unwrap();
+  log!("✕ {}: {}", n, def.typ_.pretty(Some(&n.to_string()), false));
+  if let Pos::Some(pos) = pos {
+    let file = parse::source_path(&pos.input)
+      .map_or_else(|| pos.input.to_string(), |p| p.display().to_string());
+    log!(
+      "--> {}:{}:{}-{}:{}",
+      file,
+      pos.from_line,
+      pos.from_column,
+      pos.upto_line,
+      pos.upto_column
+    );
+    if let Some(Ipld::String(input)) = store.get(pos.input) {
+      log!("{}", pos.range(input))
+    }
+  }
+  log!("Error: {}", err);
+}
@@ -5,12 +5,14 @@ use yatima_core::{
   name::Name,
   parse::{
     self,
+    error::write_expected,
     span::Span,
   },
 };
 
 use std::{
   cmp::Ordering,
+  collections::BTreeSet,
   fmt,
   fmt::Write,
   string::String,
@@ -39,6 +41,9 @@ pub enum FileErrorKind {
   Nom(ErrorKind),
   /// For OS errors or general failed side effect errors
   SystemError(String),
+  /// The errors of every def of a file that failed to parse, each rendered
+  /// with its location
+  DefErrors(Vec<String>),
 }
 
 impl FileErrorKind {
@@ -106,6 +111,7 @@ impl<'a> fmt::Display for FileErrorKind {
           imp = imp_name,
         )
       }
+      Self::DefErrors(errs) => write!(f, "{}", errs.join("\n")),
       e => write!(f, "internal parser error: {:?}", e),
     }
   }
@@ -114,19 +120,19 @@ impl<'a> fmt::Display for FileErrorKind {
 #[derive(PartialEq, Debug, Clone)]
 pub struct FileError<I: AsBytes> {
   pub input: I,
-  pub expected: Option<&'static str>,
+  pub expected: BTreeSet<&'static str>,
   pub errors: Vec<FileErrorKind>,
 }
 
 impl<I: AsBytes> FileError<I> {
   pub fn new(input: I, error: FileErrorKind) -> Self {
-    FileError { input, expected: None, errors: vec![error] }
+    FileError { input, expected: BTreeSet::new(), errors: vec![error] }
   }
 
   pub fn from_core_error(x: parse::error::ParseError<I>) -> Self {
     FileError {
       input: x.input,
-      expected: None,
+      expected: x.expected,
       errors: x
         .errors
         .iter()
@@ -157,21 +163,19 @@ impl<'a> fmt::Display for FileError<Span<'a>> {
     }
     writeln!(&mut res, "^")?;
 
-    if let Some(exp) = self.expected {
-      writeln!(&mut res, "Expected {}", exp)?;
-    }
+    write_expected(&mut res, &self.expected)?;
 
     let mut errs = self.errors.iter().filter(|x| !x.is_nom_err()).peekable();
-    if errs.peek() == None {
-      // TODO: Nom verbose mode
-      writeln!(&mut res, "Internal parser error")?;
-    }
-    else {
+    if errs.peek().is_some() {
       writeln!(&mut res, "Reported errors:")?;
       for kind in errs {
         writeln!(&mut res, "- {}", kind)?;
       }
     }
+    else if self.expected.is_empty() {
+      // TODO: Nom verbose mode
+      writeln!(&mut res, "Internal parser error")?;
+    }
 
     write!(f, "{}", res)
   }
@@ -204,6 +208,7 @@ where
         for x in self.errors {
           other.errors.push(x);
         }
+        other.expected.extend(self.expected);
         other
       }
       Ordering::Greater => other,
@@ -216,15 +221,17 @@ where
   I: InputLength,
   I: Clone,
 {
-  fn add_context(input: I, ctx: &'static str, other: Self) -> Self {
+  fn add_context(input: I, ctx: &'static str, mut other: Self) -> Self {
     match input.input_len().cmp(&other.input.input_len()) {
       Ordering::Less => {
-        FileError { input, expected: Some(ctx), errors: vec![] }
+        let mut expected = BTreeSet::new();
+        expected.insert(ctx);
+        FileError { input, expected, errors: vec![] }
+      }
+      Ordering::Equal => {
+        other.expected.insert(ctx);
+        other
       }
-      Ordering::Equal => match other.expected {
-        None => FileError { input, expected: Some(ctx), errors: other.errors },
-        _ => other,
-      },
       Ordering::Greater => other,
     }
   }
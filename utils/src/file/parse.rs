@@ -1,4 +1,5 @@
 use crate::{
+  cache,
   file::{
     error,
     error::{
@@ -26,7 +27,7 @@ use yatima_core::{
   parse::{
     package::{
       parse_alias,
-      parse_defs,
+      parse_defs_recovering,
       parse_link,
       parse_with,
     },
@@ -62,6 +63,15 @@ use std::{
   rc::Rc,
 };
 
+thread_local! {
+  static SOURCES: RefCell<HashMap<Cid, PathBuf>> = RefCell::new(HashMap::new());
+}
+
+/// The file that the source with CID `cid` was parsed from in this session
+pub fn source_path(cid: &Cid) -> Option<PathBuf> {
+  SOURCES.with(|sources| sources.borrow().get(cid).cloned())
+}
+
 #[derive(Debug, Clone)]
 pub struct PackageEnv {
   root: PathBuf,
@@ -120,10 +130,20 @@ pub fn parse_text(
 ) -> Result<(Cid, Package, Defs), String> {
   let path = env.path.clone();
   let input_cid = env.store.put(Ipld::String(txt.to_owned()));
+  SOURCES.with(|sources| sources.borrow_mut().insert(input_cid, path.clone()));
   match parse_package(input_cid, env)(Span::new(&txt)) {
     Ok((_, p)) => Ok(p),
     Err(e) => match e {
       Err::Incomplete(_) => Err("Incomplete".to_owned()),
+      Err::Failure(FileError { errors, .. })
+        if matches!(errors.as_slice(), [FileErrorKind::DefErrors(_)]) =>
+      {
+        Err(format!(
+          "Parse Errors in {}:\n{}",
+          path.to_string_lossy(),
+          errors[0]
+        ))
+      }
       Err::Failure(e) => {
         Err(format!("Parse Failure in {}:\n{}", path.to_string_lossy(), e))
       }
@@ -157,17 +177,40 @@ pub fn index_to_defs(
 ) -> Result<Defs, FileErrorKind> {
   use FileErrorKind::*;
   let mut defs = Defs::new();
+  let session = cache::session();
   for (n, cid) in &i.0 {
+    if let Some(def) = session.borrow_mut().get(cid) {
+      defs.insert(n.clone(), def);
+      continue;
+    }
     let entry_ipld: Ipld =
       env.store.get(*cid).map_or_else(|| Err(UnknownLink(*cid)), Ok)?;
     let entry: Entry =
       Entry::from_ipld(&entry_ipld).map_or_else(|e| Err(IpldError(e)), Ok)?;
     let def = entry_to_def(entry, env.store.clone())?;
+    session.borrow_mut().insert(def.clone());
     defs.insert(n.clone(), def);
   }
   Ok(defs)
 }
 
+/// Loads the defs in scope in a stored package: those of its imports it
+/// brings into scope, which it re-exports, and its own
+pub fn package_to_defs(
+  pack: &Package,
+  env: PackageEnv,
+) -> Result<Defs, FileErrorKind> {
+  use FileErrorKind::*;
+  let mut defs = Defs::new();
+  for imp in &pack.imports {
+    let ipld =
+      env.store.get(imp.cid).map_or_else(|| Err(UnknownLink(imp.cid)), Ok)?;
+    let dep = Package::from_ipld(&ipld).map_or_else(|e| Err(IpldError(e)), Ok)?;
+    defs = defs.merge(package_to_defs(&dep, env.clone())?, imp);
+  }
+  Ok(defs.flat_merge(index_to_defs(&pack.index, env)?))
+}
+
 pub fn parse_import(
   env: PackageEnv,
 ) -> impl Fn(Span) -> IResult<Span, (Cid, Import, Defs), FileError<Span>> {
@@ -204,11 +247,11 @@ pub fn parse_import(
         |e| Err(Err::Error(FileError::new(i, IpldError(e)))),
         |v| Ok((i, v)),
       )?;
-      let (_, defs) = index_to_defs(&pack.index, env.clone()).map_or_else(
+      let (_, defs) = package_to_defs(&pack, env.clone()).map_or_else(
         |e| Err(Err::Error(FileError::new(i, e))),
         |v| Ok((i, v)),
       )?;
-      let with: Vec<Name> = with.unwrap_or_else(|| defs.names());
+      let with: Vec<Name> = with.unwrap_or_else(|| pack.index.keys());
       Ok((i, (from, Import { cid: from, name, alias, with }, defs)))
     }
     else {
@@ -308,8 +351,15 @@ pub fn parse_package(
     }
     let (i, (imports, defs)) = parse_imports(env.clone())(i)?;
     let (i, _) = parse_space(i).map_err(error::convert)?;
-    let (upto, (defs, index)) =
-      parse_defs(input, defs)(i).map_err(error::convert)?;
+    let (upto, (defs, index), errs) = parse_defs_recovering(input, defs)(i);
+    if let Some(first) = errs.first() {
+      // Every def that failed is reported, at the position of the first
+      let errs = errs.iter().map(|e| e.to_string()).collect();
+      return Err(Err::Failure(FileError::new(
+        first.input,
+        FileErrorKind::DefErrors(errs),
+      )));
+    }
     for (n, _) in index.0.iter() {
       let d = defs.get(n).unwrap();
       let (entry, typ, trm) = d.clone().embed();
@@ -1,30 +1,87 @@
 use crate::{
   log,
 };
-use bytecursor::ByteCursor;
 use reqwest::{
   self,
   multipart,
   Client,
 };
 use serde_json;
+use sp_cid::Cid;
 use sp_ipld::{
-  dag_cbor::{
-    cid,
-    DagCborCodec,
-  },
+  dag_cbor::DagCborCodec,
   Codec,
   Ipld,
 };
-use std::sync::{
-  Arc,
-  Mutex,
+use std::{
+  fmt,
+  sync::{
+    Arc,
+    Mutex,
+  },
+  time::Duration,
+};
+use yatima_core::{
+  ipld::{
+    block::BlockError,
+    hash::{
+      cid,
+      Hasher,
+      DAG_CBOR,
+    },
+    Block,
+    IpldRef,
+  },
+  parse::parse_cid_str,
 };
 
+/// Ways a call to the IPFS HTTP API can fail
+#[derive(Debug)]
+pub enum IpfsError {
+  /// The request failed, timed out or got an error status
+  Http(reqwest::Error),
+  /// The response didn't have the expected shape
+  Response(String),
+  /// A CID argument that couldn't be parsed
+  InvalidCid(String),
+  Codec(String),
+  /// The block IPFS returned doesn't hash to the CID that was asked for
+  Block(BlockError),
+  /// IPFS addressed a put block by another CID than Yatima did
+  CidMismatch { ipfs: String, local: String },
+}
+
+impl fmt::Display for IpfsError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Http(e) => write!(f, "IPFS request failed: {}", e),
+      Self::Response(r) => write!(f, "Unexpected IPFS response: {}", r),
+      Self::InvalidCid(e) => write!(f, "{}", e),
+      Self::Codec(e) => write!(f, "Codec error: {}", e),
+      Self::Block(e) => write!(f, "Invalid block from IPFS: {}", e),
+      Self::CidMismatch { ipfs, local } => {
+        write!(f, "CIDs are different {} != {}", ipfs, local)
+      }
+    }
+  }
+}
+
+impl From<reqwest::Error> for IpfsError {
+  fn from(e: reqwest::Error) -> Self { Self::Http(e) }
+}
+
+impl From<IpfsError> for String {
+  fn from(e: IpfsError) -> String { e.to_string() }
+}
+
 /// Settings for how to connect to an IPFS Http API
 #[derive(Debug, Clone)]
 pub struct IpfsApi {
   host: String,
+  /// The hash function used to address blocks put into IPFS
+  hasher: Hasher,
+  /// How long a request may take. Not supported when targeting wasm.
+  timeout: Option<Duration>,
 }
 
 fn log_err<T, E: std::fmt::Debug>(e: E) -> Result<T, ()> {
@@ -33,7 +90,17 @@ fn log_err<T, E: std::fmt::Debug>(e: E) -> Result<T, ()> {
 }
 
 impl IpfsApi {
-  pub fn new(host: String) -> Self { IpfsApi { host } }
+  pub fn new(host: String) -> Self {
+    IpfsApi { host, hasher: Hasher::default(), timeout: None }
+  }
+
+  /// Uses a different hash function when putting blocks
+  pub fn with_hasher(self, hasher: Hasher) -> Self { IpfsApi { hasher, ..self } }
+
+  /// Gives up on requests that take longer than `timeout`
+  pub fn with_timeout(self, timeout: Duration) -> Self {
+    IpfsApi { timeout: Some(timeout), ..self }
+  }
 
   pub fn ipfs_yatima_io() -> Self { Self::new("ipfs.yatima.io:5001".to_owned()) }
 
@@ -42,8 +109,11 @@ impl IpfsApi {
   /// Pin an Ipld using the IPFS API
   pub fn dag_put_with_callback(&self, dag: Ipld) -> Result<String, String> {
     let url = format!(
-      "http://{}{}?{}",
-      self.host, "/api/v0/dag/put", "format=cbor&pin=true&input-enc=cbor&hash=blake2b-256"
+      "http://{}{}?{}{}",
+      self.host,
+      "/api/v0/dag/put",
+      "format=cbor&pin=true&input-enc=cbor&hash=",
+      self.hasher.name()
     );
     let cbor =
       DagCborCodec.encode(&dag).map_err(|e| format!("encoding error: {:?}", e))?.into_inner();
@@ -67,7 +137,7 @@ impl IpfsApi {
 
     let response = ptr.lock().unwrap();
     let ipfs_cid: String = response["Cid"]["/"].as_str().unwrap().to_string();
-    let local_cid: String = cid(&dag).to_string();
+    let local_cid: String = cid(DAG_CBOR, self.hasher, &dag)?.to_string();
 
     if ipfs_cid == local_cid {
       Ok(ipfs_cid)
@@ -93,43 +163,90 @@ impl IpfsApi {
       }
       let response = response.unwrap().bytes().await.or_else(log_err).unwrap().to_vec();
 
-      let ipld_res = DagCborCodec
-        .decode(ByteCursor::new(response.to_vec()))
+      let ipld_res = IpldRef::decode(&response)
+        .map(|ipld| ipld.to_ipld())
         .map_err(|e| format!("Invalid ipld cbor: {}", e));
 
       callback(ipld_res);
     });
   }
 
-  pub async fn dag_put(&self, dag: Ipld) -> Result<String, reqwest::Error> {
-    let url = format!(
-      "http://{}{}?{}",
-      self.host, "/api/v0/dag/put", "format=cbor&pin=true&input-enc=cbor&hash=blake2b-256"
-    );
-    let cbor = DagCborCodec.encode(&dag).unwrap().into_inner();
-    let client = Client::new();
-    let form = multipart::Form::new().part("file", multipart::Part::bytes(cbor));
-    let response: serde_json::Value =
-      client.post(url).multipart(form).send().await?.json().await?;
+  /// The URL of an API command. Hosts without a scheme are reached over
+  /// plain HTTP.
+  fn url(&self, command: &str, args: &str) -> String {
+    let scheme = if self.host.contains("://") { "" } else { "http://" };
+    format!("{}{}/api/v0/{}?{}", scheme, self.host, command, args)
+  }
 
-    let ipfs_cid: String = response["Cid"]["/"].as_str().unwrap().to_string();
-    let local_cid: String = cid(&dag).to_string();
+  fn client(&self) -> Result<Client, IpfsError> {
+    let builder = Client::builder();
+    #[cfg(not(target_arch = "wasm32"))]
+    let builder = match self.timeout {
+      Some(timeout) => builder.timeout(timeout),
+      None => builder,
+    };
+    Ok(builder.build()?)
+  }
 
+  /// Puts and pins an Ipld, checking that IPFS addresses it by the same CID
+  pub async fn dag_put(&self, dag: Ipld) -> Result<String, IpfsError> {
+    let args =
+      format!("format=cbor&pin=true&input-enc=cbor&hash={}", self.hasher.name());
+    let cbor = DagCborCodec
+      .encode(&dag)
+      .map_err(|e| IpfsError::Codec(e.to_string()))?
+      .into_inner();
+    let form = multipart::Form::new().part("file", multipart::Part::bytes(cbor));
+    let response: serde_json::Value = self
+      .client()?
+      .post(self.url("dag/put", &args))
+      .multipart(form)
+      .send()
+      .await?
+      .error_for_status()?
+      .json()
+      .await?;
+    let ipfs_cid = response["Cid"]["/"]
+      .as_str()
+      .ok_or_else(|| IpfsError::Response(response.to_string()))?
+      .to_string();
+    let local_cid = cid(DAG_CBOR, self.hasher, &dag).map_err(IpfsError::Codec)?.to_string();
     if ipfs_cid == local_cid {
       Ok(ipfs_cid)
     }
     else {
-      panic!("CIDs are different {} != {}", ipfs_cid, local_cid);
+      Err(IpfsError::CidMismatch { ipfs: ipfs_cid, local: local_cid })
     }
   }
 
-  pub async fn dag_get(&self, cid: String) -> Result<Ipld, reqwest::Error> {
-    let url = format!("http://{}{}?arg={}", self.host, "/api/v0/block/get", cid);
-    let client = Client::new();
-    let response = client.post(url).send().await?.bytes().await?;
-    let response = response.to_vec();
-    let ipld = DagCborCodec.decode(ByteCursor::new(response)).expect("invalid ipld cbor.");
+  /// Fetches a raw block, checking that it hashes to `cid`
+  pub async fn block_get(&self, cid: Cid) -> Result<Block, IpfsError> {
+    let response = self
+      .client()?
+      .post(self.url("block/get", &format!("arg={}", cid)))
+      .send()
+      .await?
+      .error_for_status()?
+      .bytes()
+      .await?;
+    Block::new(cid, response.to_vec()).map_err(IpfsError::Block)
+  }
+
+  /// Loads and decodes a verified block
+  pub async fn dag_get(&self, cid: String) -> Result<Ipld, IpfsError> {
+    let cid = parse_cid_str(&cid).map_err(IpfsError::InvalidCid)?;
+    let block = self.block_get(cid).await?;
+    block.decode().map_err(|e| IpfsError::Codec(e.to_string()))
+  }
 
-    Ok(ipld)
+  /// Pins a block and everything it links to
+  pub async fn pin_add(&self, cid: Cid) -> Result<(), IpfsError> {
+    self
+      .client()?
+      .post(self.url("pin/add", &format!("arg={}", cid)))
+      .send()
+      .await?
+      .error_for_status()?;
+    Ok(())
   }
 }
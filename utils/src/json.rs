@@ -0,0 +1,257 @@
+use multibase::Base;
+use serde_json::{
+  Map,
+  Number,
+  Value,
+};
+use sp_ipld::Ipld;
+use std::{
+  collections::BTreeMap,
+  convert::TryFrom,
+  fmt,
+};
+use yatima_core::{
+  ipld::link::cid_to_base,
+  parse::parse_cid_str,
+};
+
+/// The largest integer a JavaScript number holds exactly
+const MAX_SAFE_INTEGER: i128 = (1 << 53) - 1;
+
+/// How bytes are written as JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BytesPolicy {
+  /// `{"/": {"bytes": "<base64>"}}`, as in DAG-JSON
+  DagJson,
+  /// An array of numbers, which reads back as a list of integers
+  Array,
+}
+
+/// How integers are written as JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IntPolicy {
+  /// Always as numbers. Integers outside of the 64 bit range are an error.
+  Number,
+  /// As numbers when JavaScript can hold them exactly and as decimal strings
+  /// otherwise. These read back as strings.
+  SafeNumber,
+}
+
+/// How links are written as JSON
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkPolicy {
+  /// `{"/": "<cid>"}`, as in DAG-JSON
+  DagJson,
+  /// The CID as a plain string in the given base, which reads back as a
+  /// string
+  String(Base),
+}
+
+/// Choices for the IPLD values that JSON has no native form for. The
+/// default follows DAG-JSON, so that the output reads back to the same IPLD.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JsonPolicy {
+  pub bytes: BytesPolicy,
+  pub ints: IntPolicy,
+  pub links: LinkPolicy,
+}
+
+impl Default for JsonPolicy {
+  fn default() -> Self {
+    JsonPolicy {
+      bytes: BytesPolicy::DagJson,
+      ints: IntPolicy::Number,
+      links: LinkPolicy::DagJson,
+    }
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonError {
+  IntegerOutOfRange(i128),
+  NonFiniteFloat(f64),
+  /// A `{"/": ...}` object holding something other than a CID or bytes
+  InvalidLink(String),
+  InvalidBytes(String),
+}
+
+impl fmt::Display for JsonError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::IntegerOutOfRange(i) => {
+        write!(f, "Integer {} doesn't fit in a JSON number", i)
+      }
+      Self::NonFiniteFloat(x) => write!(f, "JSON has no float {}", x),
+      Self::InvalidLink(e) => write!(f, "Invalid link: {}", e),
+      Self::InvalidBytes(e) => write!(f, "Invalid bytes: {}", e),
+    }
+  }
+}
+
+impl From<JsonError> for String {
+  fn from(e: JsonError) -> String { e.to_string() }
+}
+
+fn slash(value: Value) -> Value {
+  let mut map = Map::new();
+  map.insert("/".to_owned(), value);
+  Value::Object(map)
+}
+
+/// Converts an IPLD object to JSON
+pub fn ipld_to_json(
+  ipld: &Ipld,
+  policy: &JsonPolicy,
+) -> Result<Value, JsonError> {
+  Ok(match ipld {
+    Ipld::Null => Value::Null,
+    Ipld::Bool(b) => Value::Bool(*b),
+    Ipld::Integer(i) => {
+      let unsafe_int = *i > MAX_SAFE_INTEGER || *i < -MAX_SAFE_INTEGER;
+      if policy.ints == IntPolicy::SafeNumber && unsafe_int {
+        Value::String(i.to_string())
+      }
+      else if let Ok(n) = i64::try_from(*i) {
+        Value::Number(n.into())
+      }
+      else if let Ok(n) = u64::try_from(*i) {
+        Value::Number(n.into())
+      }
+      else {
+        return Err(JsonError::IntegerOutOfRange(*i));
+      }
+    }
+    Ipld::Float(x) => {
+      Value::Number(Number::from_f64(*x).ok_or(JsonError::NonFiniteFloat(*x))?)
+    }
+    Ipld::String(s) => Value::String(s.clone()),
+    Ipld::Bytes(b) => match policy.bytes {
+      BytesPolicy::DagJson => {
+        // DAG-JSON uses unpadded base64 without the multibase prefix
+        let encoded = multibase::encode(Base::Base64, b);
+        let mut bytes = Map::new();
+        bytes
+          .insert("bytes".to_owned(), Value::String(encoded[1..].to_owned()));
+        slash(Value::Object(bytes))
+      }
+      BytesPolicy::Array => {
+        Value::Array(b.iter().map(|x| Value::Number((*x).into())).collect())
+      }
+    },
+    Ipld::List(xs) => Value::Array(
+      xs.iter().map(|x| ipld_to_json(x, policy)).collect::<Result<_, _>>()?,
+    ),
+    Ipld::StringMap(m) => {
+      let mut map = Map::new();
+      for (k, v) in m {
+        map.insert(k.clone(), ipld_to_json(v, policy)?);
+      }
+      Value::Object(map)
+    }
+    Ipld::Link(cid) => match policy.links {
+      LinkPolicy::DagJson => slash(Value::String(cid.to_string())),
+      LinkPolicy::String(base) => Value::String(cid_to_base(cid, base)),
+    },
+  })
+}
+
+/// Converts JSON to an IPLD object. Objects of the DAG-JSON forms
+/// `{"/": "<cid>"}` and `{"/": {"bytes": "<base64>"}}` become links and bytes.
+/// Numbers with a fraction or exponent become floats.
+pub fn json_to_ipld(value: &Value) -> Result<Ipld, JsonError> {
+  Ok(match value {
+    Value::Null => Ipld::Null,
+    Value::Bool(b) => Ipld::Bool(*b),
+    Value::Number(n) => {
+      if let Some(i) = n.as_i64() {
+        Ipld::Integer(i.into())
+      }
+      else if let Some(u) = n.as_u64() {
+        Ipld::Integer(u.into())
+      }
+      else {
+        Ipld::Float(n.as_f64().unwrap_or(f64::NAN))
+      }
+    }
+    Value::String(s) => Ipld::String(s.clone()),
+    Value::Array(xs) => {
+      Ipld::List(xs.iter().map(json_to_ipld).collect::<Result<_, _>>()?)
+    }
+    Value::Object(map) => match (map.len(), map.get("/")) {
+      (1, Some(Value::String(cid))) => {
+        Ipld::Link(parse_cid_str(cid).map_err(JsonError::InvalidLink)?)
+      }
+      (1, Some(Value::Object(inner))) => match inner.get("bytes") {
+        Some(Value::String(b)) if inner.len() == 1 => {
+          let (_, bytes) = multibase::decode(format!("m{}", b))
+            .map_err(|e| JsonError::InvalidBytes(e.to_string()))?;
+          Ipld::Bytes(bytes)
+        }
+        _ => {
+          return Err(JsonError::InvalidLink(
+            Value::Object(inner.clone()).to_string(),
+          ))
+        }
+      },
+      _ => {
+        let mut m = BTreeMap::new();
+        for (k, v) in map {
+          m.insert(k.clone(), json_to_ipld(v)?);
+        }
+        Ipld::StringMap(m)
+      }
+    },
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use yatima_core::ipld::hash::{
+    cid,
+    Hasher,
+    DAG_CBOR,
+  };
+
+  fn example() -> Ipld {
+    let link = cid(DAG_CBOR, Hasher::default(), &Ipld::Null).unwrap();
+    let mut map = BTreeMap::new();
+    map.insert("name".to_owned(), Ipld::String("id".to_owned()));
+    map.insert("bytes".to_owned(), Ipld::Bytes(vec![0, 1, 254, 255]));
+    map.insert("link".to_owned(), Ipld::Link(link));
+    map.insert(
+      "numbers".to_owned(),
+      Ipld::List(vec![
+        Ipld::Integer(-1),
+        Ipld::Integer(u64::MAX.into()),
+        Ipld::Float(1.5),
+      ]),
+    );
+    Ipld::StringMap(map)
+  }
+
+  #[test]
+  fn test_json_roundtrip() {
+    let ipld = example();
+    let json = ipld_to_json(&ipld, &JsonPolicy::default()).unwrap();
+    assert_eq!(json["bytes"]["/"]["bytes"], Value::String("AAH+/w".to_owned()));
+    assert_eq!(json_to_ipld(&json), Ok(ipld));
+  }
+
+  #[test]
+  fn test_json_policies() {
+    let policy = JsonPolicy {
+      bytes: BytesPolicy::Array,
+      ints: IntPolicy::SafeNumber,
+      links: LinkPolicy::String(Base::Base58Btc),
+    };
+    let json = ipld_to_json(&example(), &policy).unwrap();
+    assert_eq!(json["bytes"], serde_json::json!([0, 1, 254, 255]));
+    assert_eq!(json["numbers"][1], Value::String(u64::MAX.to_string()));
+    assert!(json["link"].as_str().unwrap().starts_with('z'));
+    assert_eq!(
+      ipld_to_json(&Ipld::Integer(i128::MAX), &JsonPolicy::default()),
+      Err(JsonError::IntegerOutOfRange(i128::MAX))
+    );
+  }
+}
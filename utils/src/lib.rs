@@ -1,7 +1,14 @@
+pub mod cache;
+pub mod changelog;
+pub mod checkout;
+pub mod diff;
 pub mod file;
 pub mod repl;
 pub mod store;
 #[macro_use]
 pub mod logging;
 pub mod graph;
+pub mod json;
+#[cfg(feature = "net")]
 pub mod ipfs;
+pub mod upgrade;
@@ -2,6 +2,7 @@ pub mod command;
 pub mod error;
 
 use crate::{
+  cache,
   debug,
   file,
   log,
@@ -20,7 +21,7 @@ use sp_std::{
   sync::Arc,
 };
 use yatima_runtime::{
-  run,
+  run_with_limit,
   transform::{
     RunIO,
     StdIORuntime,
@@ -29,17 +30,25 @@ use yatima_runtime::{
 
 use std::sync::Mutex;
 use yatima_core::{
-  check::{
-    check_def,
-    infer_term,
-  },
+  check::infer_term,
   dag::DAG,
+  debugger::{
+    Debugger,
+    DEFAULT_HISTORY,
+  },
   defs::Defs,
+  eval::EvalStrategy,
+  fuel::{
+    Limits,
+    OutOfFuel,
+  },
   package::Package,
   parse::{
     span::Span,
     term::input_cid,
   },
+  prim::cost::CostModel,
+  trace::TraceCounts,
 };
 
 use command::{
@@ -54,6 +63,12 @@ pub struct ReplEnv {
   var_index: bool,
   defs: Defs,
   runtime_io: RunIO,
+  debugger: Option<Debugger>,
+  history: usize,
+  limits: Limits,
+  trace: bool,
+  #[cfg_attr(not(feature = "parallel"), allow(dead_code))]
+  parallel: bool,
 }
 
 pub enum LineResult {
@@ -68,7 +83,12 @@ impl Default for ReplEnv {
       type_system: true,
       var_index: false,
       defs: Defs::new(),
-      runtime_io: Rc::new(StdIORuntime {}),
+      runtime_io: Rc::new(StdIORuntime::new()),
+      debugger: None,
+      history: DEFAULT_HISTORY,
+      limits: Limits::none(),
+      trace: false,
+      parallel: false,
     }
   }
 }
@@ -213,6 +233,16 @@ pub trait Repl {
                 self.println(format!("var-index: {}", if setting { "on" } else { "off" }))?;
                 Ok(LineResult::Success)
               }
+              "trace" => {
+                env.trace = setting;
+                self.println(format!("trace: {}", if setting { "on" } else { "off" }))?;
+                Ok(LineResult::Success)
+              }
+              "parallel" if cfg!(feature = "parallel") => {
+                env.parallel = setting;
+                self.println(format!("parallel: {}", if setting { "on" } else { "off" }))?;
+                Ok(LineResult::Success)
+              }
               _ => {
                 self.println(format!("Error: Unknown setting {}", field))?;
                 Err("".to_owned())
@@ -225,10 +255,19 @@ pub trait Repl {
                 match res {
                   Ok(typ) => {
                     let mut mterm = term;
-                    run(&mut mterm, Rc::new(env.defs.clone()), env.runtime_io.clone());
-                    dag.norm(&env.defs, false);
+                    let defs = Rc::new(env.defs.clone());
+                    let io = env.runtime_io.clone();
+                    if let Err(e) = run_with_limit(&mut mterm, defs, io, env.limits) {
+                      self.println(format!("{} running effects", e))?;
+                    }
+                    let counts = norm(&mut dag, &env);
                     self.println(format!("{}", dag))?;
                     self.println(format!(": {}", typ))?;
+                    match counts {
+                      Ok(Some(counts)) => self.println(counts.to_string().trim_end().to_owned())?,
+                      Ok(None) => (),
+                      Err(e) => self.println(e.to_string())?,
+                    }
                     Ok(LineResult::Success)
                   }
                   Err(e) => {
@@ -238,8 +277,13 @@ pub trait Repl {
                 }
               }
               else {
-                dag.norm(&env.defs, false);
+                let counts = norm(&mut dag, &env);
                 self.println(format!("{}", dag))?;
+                match counts {
+                  Ok(Some(counts)) => self.println(counts.to_string().trim_end().to_owned())?,
+                  Ok(None) => (),
+                  Err(e) => self.println(e.to_string())?,
+                }
                 Ok(LineResult::Success)
               }
             }
@@ -256,7 +300,7 @@ pub trait Repl {
               let mut tmp_defs = env.defs.clone();
               tmp_defs.insert(n.clone(), def);
               let re = Rc::new(tmp_defs);
-              let res = check_def(re.clone(), &n, false);
+              let res = cache::check_def_cached(&cache::session(), re.clone(), &n);
               match res {
                 Ok(res) => {
                   env.defs.flat_merge_mut(re);
@@ -274,6 +318,65 @@ pub trait Repl {
               }
               Ok(LineResult::Success)
             }
+            Command::Debug(term) => {
+              let debugger = Debugger::new(&term, env.history);
+              self.println(format!("0: {}", debugger.current()))?;
+              env.debugger = Some(debugger);
+              Ok(LineResult::Success)
+            }
+            Command::Forward => {
+              let defs = env.defs.clone();
+              match env.debugger.as_mut() {
+                Some(debugger) => {
+                  match debugger.forward(&defs) {
+                    Some(term) => {
+                      let term = format!("{}", term);
+                      let step = debugger.steps() - debugger.offset() as u64;
+                      self.println(format!("{}: {}", step, term))?
+                    }
+                    None => self.println("No further reductions".to_owned())?,
+                  }
+                  Ok(LineResult::Success)
+                }
+                None => {
+                  self.println("Error: Nothing to debug, use :debug".to_owned())?;
+                  Err("".to_owned())
+                }
+              }
+            }
+            Command::Back => match env.debugger.as_mut() {
+              Some(debugger) => {
+                match debugger.back() {
+                  Some(term) => {
+                    let term = format!("{}", term);
+                    let step = debugger.steps() - debugger.offset() as u64;
+                    self.println(format!("{}: {}", step, term))?
+                  }
+                  None => self.println("Start of recorded history".to_owned())?,
+                }
+                Ok(LineResult::Success)
+              }
+              None => {
+                self.println("Error: Nothing to debug, use :debug".to_owned())?;
+                Err("".to_owned())
+              }
+            },
+            Command::History(len) => {
+              env.history = len;
+              if let Some(debugger) = env.debugger.as_mut() {
+                debugger.set_capacity(len);
+              }
+              self.println(format!("history: {}", len))?;
+              Ok(LineResult::Success)
+            }
+            Command::MaxSteps(steps) => {
+              env.limits.steps = steps;
+              match steps {
+                Some(steps) => self.println(format!("max-steps: {}", steps))?,
+                None => self.println("max-steps: off".to_owned())?,
+              }
+              Ok(LineResult::Success)
+            }
             Command::Quit => {
               self.println(format!("Goodbye."))?;
               Ok(LineResult::Quit)
@@ -313,6 +416,37 @@ pub trait Repl {
   }
 }
 
+/// Normalizes a DAG as the REPL's settings say, returning its reduction
+/// counts if tracing. Otherwise normalization reuses the session's normal
+/// forms of definitions, unless it is parallel. With limits set, it stops
+/// once it runs out of fuel instead.
+fn norm(dag: &mut DAG, env: &ReplEnv) -> Result<Option<TraceCounts>, OutOfFuel> {
+  let defs = &env.defs;
+  if !env.limits.is_none() {
+    let mut fuel = env.limits.fuel();
+    dag.norm_limited(defs, false, &mut fuel);
+    return fuel.out_of_fuel().map_or(Ok(None), Err);
+  }
+  #[cfg(feature = "parallel")]
+  if env.parallel && !env.trace {
+    dag.norm_par(defs);
+    return Ok(None);
+  }
+  if env.trace {
+    let mut counts = TraceCounts::new();
+    let costs = CostModel::unit();
+    let lazy = EvalStrategy::Lazy;
+    let mut steps = u64::MAX;
+    dag.norm_traced(defs, false, &mut steps, &costs, lazy, &mut counts);
+    Ok(Some(counts))
+  }
+  else {
+    let norms = cache::session_norms();
+    dag.norm_cached(defs, false, &mut norms.borrow_mut());
+    Ok(None)
+  }
+}
+
 pub fn run_repl(rl: &mut dyn Repl) {
   rl.load_history();
   loop {
@@ -1,6 +1,9 @@
 use crate::file::{
   error,
-  error::FileError,
+  error::{
+    FileError,
+    FileErrorKind,
+  },
 };
 use sp_im::conslist::ConsList;
 use yatima_core::{
@@ -8,6 +11,7 @@ use yatima_core::{
     Def,
     Defs,
   },
+  elab::elaborate_term,
   name::Name,
   package::Entry,
   parse::{
@@ -45,6 +49,7 @@ use nom::{
     tag,
     take_till1,
   },
+  character::complete::digit1,
   combinator::value,
   Err,
   IResult,
@@ -71,6 +76,22 @@ pub enum Command {
   Load(Reference),
   // Import,
   Quit,
+  Debug(Box<Term>),
+  Back,
+  Forward,
+  History(usize),
+  MaxSteps(Option<u64>),
+}
+
+/// Inserts the implicit arguments of an expression
+fn elab<'a>(
+  i: Span<'a>,
+  defs: &Defs,
+  trm: Term,
+) -> Result<Term, Err<FileError<Span<'a>>>> {
+  elaborate_term(defs, trm).map_err(|e| {
+    error::convert(Err::Failure(ParseError::new(i, ParseErrorKind::Elab(e))))
+  })
 }
 
 pub fn parse_eval(
@@ -86,6 +107,7 @@ pub fn parse_eval(
       Rc::new(VecDeque::new()),
     )(i)
     .map_err(error::convert)?;
+    let trm = elab(i, &defs.borrow(), trm)?;
     Ok((i, Command::Eval(Box::new(trm))))
   }
 }
@@ -122,6 +144,7 @@ pub fn parse_type(
       Rc::new(VecDeque::new()),
     )(i)
     .map_err(error::convert)?;
+    let trm = elab(i, &defs.borrow(), trm)?;
     Ok((i, Command::Type(Box::new(trm))))
   }
 }
@@ -146,6 +169,83 @@ pub fn parse_quit() -> impl Fn(Span) -> IResult<Span, Command, FileError<Span>>
   }
 }
 
+/// Parse the :debug command, which starts stepping through a term
+pub fn parse_debug(
+  input: Cid,
+  defs: Rc<RefCell<Defs>>,
+) -> impl Fn(Span) -> IResult<Span, Command, FileError<Span>> {
+  move |i: Span| {
+    let (i, _) = alt((tag(":debug"), tag(":d")))(i)?;
+    let (i, _) = parse_space1(i).map_err(error::convert)?;
+    let (i, trm) = parse_expression(
+      input,
+      defs.clone(),
+      None,
+      ConsList::new(),
+      Rc::new(VecDeque::new()),
+    )(i)
+    .map_err(error::convert)?;
+    let trm = elab(i, &defs.borrow(), trm)?;
+    Ok((i, Command::Debug(Box::new(trm))))
+  }
+}
+
+pub fn parse_back() -> impl Fn(Span) -> IResult<Span, Command, FileError<Span>>
+{
+  move |i: Span| {
+    let (i, _) = tag(":back")(i)?;
+    Ok((i, Command::Back))
+  }
+}
+
+pub fn parse_forward()
+-> impl Fn(Span) -> IResult<Span, Command, FileError<Span>> {
+  move |i: Span| {
+    let (i, _) = alt((tag(":forward"), tag(":f")))(i)?;
+    Ok((i, Command::Forward))
+  }
+}
+
+/// Parse the :history command, which sets how many debugger steps are kept
+pub fn parse_history()
+-> impl Fn(Span) -> IResult<Span, Command, FileError<Span>> {
+  move |i: Span| {
+    let (i, _) = tag(":history")(i)?;
+    let (i, _) = parse_space1(i).map_err(error::convert)?;
+    let (i, n) = digit1(i)?;
+    match n.fragment().parse::<usize>() {
+      Ok(n) => Ok((i, Command::History(n))),
+      Err(_) => Err(Err::Error(FileError::new(
+        i,
+        FileErrorKind::CoreError(ParseErrorKind::InvalidSymbol(
+          n.fragment().to_string(),
+        )),
+      ))),
+    }
+  }
+}
+
+/// Parse the :max-steps command, which bounds the reduction steps of each
+/// evaluation, or lifts the bound when given 0
+pub fn parse_max_steps()
+-> impl Fn(Span) -> IResult<Span, Command, FileError<Span>> {
+  move |i: Span| {
+    let (i, _) = tag(":max-steps")(i)?;
+    let (i, _) = parse_space1(i).map_err(error::convert)?;
+    let (i, n) = digit1(i)?;
+    match n.fragment().parse::<u64>() {
+      Ok(0) => Ok((i, Command::MaxSteps(None))),
+      Ok(n) => Ok((i, Command::MaxSteps(Some(n)))),
+      Err(_) => Err(Err::Error(FileError::new(
+        i,
+        FileErrorKind::CoreError(ParseErrorKind::InvalidSymbol(
+          n.fragment().to_string(),
+        )),
+      ))),
+    }
+  }
+}
+
 pub fn parse_browse() -> impl Fn(Span) -> IResult<Span, Command, FileError<Span>>
 {
   move |i: Span| {
@@ -226,7 +326,12 @@ pub fn parse_command(
   move |i: Span| {
     alt((
       parse_quit(),
+      parse_back(),
       parse_browse(),
+      parse_forward(),
+      parse_history(),
+      parse_max_steps(),
+      parse_debug(input, defs.clone()),
       parse_set(),
       parse_load(),
       parse_show(),
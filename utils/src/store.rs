@@ -1,4 +1,5 @@
 use crate::{
+  cache,
   debug,
   file,
   graph::PackageGraph,
@@ -234,8 +235,12 @@ pub fn load_package_defs(
         return Err(format!("Failed to load {} at {}", import.name, import.cid));
       }
     }
+    let session = cache::session();
     for (name, cid) in def_refs {
-      if let Some(entry_ipld) = store.get(cid.clone()) {
+      if let Some(def) = session.borrow_mut().get(cid) {
+        defs.insert(name.clone(), def);
+      }
+      else if let Some(entry_ipld) = store.get(cid.clone()) {
         let entry = Entry::from_ipld(&entry_ipld).map_err(|e| format!("{:?}", e))?;
         if let Some(type_anon_ipld) = store.get(entry.type_anon) {
           let type_anon =
@@ -243,10 +248,10 @@ pub fn load_package_defs(
           if let Some(term_anon_ipld) = store.get(entry.term_anon) {
             let term_anon =
               anon::Anon::from_ipld(&term_anon_ipld).map_err(|e| format!("{:?}", e))?;
-            defs.insert(
-              name.clone(),
-              Def::unembed(entry, type_anon, term_anon).map_err(|e| format!("{:?}", e))?,
-            );
+            let def =
+              Def::unembed(entry, type_anon, term_anon).map_err(|e| format!("{:?}", e))?;
+            session.borrow_mut().insert(def.clone());
+            defs.insert(name.clone(), def);
           }
           else {
             return Err(format!("Failed to term_anon at {}", &entry.term_anon));
@@ -0,0 +1,357 @@
+use crate::{
+  diff::{
+    diff_packages,
+    reexport_renames,
+    PackageDiff,
+  },
+  file::parse::{
+    parse_text,
+    PackageEnv,
+  },
+  store::Store,
+};
+use nom::{
+  bytes::complete::tag,
+  combinator::opt,
+  IResult,
+  Slice,
+};
+use sp_cid::Cid;
+use std::{
+  fmt,
+  fs,
+  path::PathBuf,
+  rc::Rc,
+};
+use yatima_core::{
+  check::check_def,
+  name::Name,
+  package::{
+    import_alias,
+    Import,
+  },
+  parse::{
+    error::ParseError,
+    package::{
+      parse_alias,
+      parse_link,
+      parse_with,
+    },
+    span::Span,
+    term::{
+      is_valid_symbol_char,
+      parse_name,
+      parse_space,
+      parse_space1,
+    },
+  },
+};
+
+/// The location and contents of an `import` statement in a source file
+#[derive(PartialEq, Clone, Debug)]
+pub struct ImportSource {
+  pub start: usize,
+  pub end: usize,
+  pub name: Name,
+  pub alias: Option<Name>,
+  pub with: Option<Vec<Name>>,
+  pub link: Option<Cid>,
+}
+
+impl fmt::Display for ImportSource {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "import {}", self.name)?;
+    if let Some(alias) = &self.alias {
+      write!(f, " as {}", alias)?;
+    }
+    if let Some(with) = &self.with {
+      let with: Vec<String> = with.iter().map(|n| n.to_string()).collect();
+      write!(f, " ({})", with.join(", "))?;
+    }
+    if let Some(link) = &self.link {
+      write!(f, " {}", link)?;
+    }
+    Ok(())
+  }
+}
+
+/// Parses an import statement, recording the offset just past its last token
+/// so that the whitespace and comments following it are left untouched
+fn parse_import_source(
+  from: Span,
+) -> IResult<Span, ImportSource, ParseError<Span>> {
+  let start = from.location_offset();
+  let (i, _) = tag("import")(from)?;
+  let (i, _) = parse_space1(i)?;
+  let (i, name) = parse_name(i)?;
+  let mut end = i.location_offset();
+  let (i, _) = parse_space(i)?;
+  let (i, alias) = opt(parse_alias)(i)?;
+  let i = if alias.is_some() {
+    end = i.location_offset();
+    parse_space(i)?.0
+  }
+  else {
+    i
+  };
+  let (i, with) = opt(parse_with)(i)?;
+  let i = if with.is_some() {
+    end = i.location_offset();
+    parse_space(i)?.0
+  }
+  else {
+    i
+  };
+  let (i, link) = opt(parse_link)(i)?;
+  if link.is_some() {
+    end = i.location_offset();
+  }
+  Ok((i, ImportSource { start, end, name, alias, with, link }))
+}
+
+/// Finds the import statement for `dep` in a package source
+pub fn find_import(txt: &str, dep: &Name) -> Option<ImportSource> {
+  let mut offset = 0;
+  while let Some(idx) = txt[offset..].find("import") {
+    let start = offset + idx;
+    let at_boundary = txt[..start]
+      .chars()
+      .next_back()
+      .map_or(true, |c| !is_valid_symbol_char(c));
+    if at_boundary {
+      if let Ok((_, import)) =
+        parse_import_source(Span::new(txt).slice(start..))
+      {
+        if import.name == *dep || import.alias.as_ref() == Some(dep) {
+          return Some(import);
+        }
+      }
+    }
+    offset = start + "import".len();
+  }
+  None
+}
+
+/// Replaces every occurrence of `old` in `txt` which is a whole symbol, i.e.
+/// not part of a longer name, with `new`
+pub fn rename_symbol(txt: &str, old: &str, new: &str) -> String {
+  let mut res = String::with_capacity(txt.len());
+  let mut rest = txt;
+  let mut prev: Option<char> = None;
+  while let Some(idx) = rest.find(old) {
+    let before = rest[..idx].chars().next_back().or(prev);
+    let after = rest[idx + old.len()..].chars().next();
+    let whole = before.map_or(true, |c| !is_valid_symbol_char(c))
+      && after.map_or(true, |c| !is_valid_symbol_char(c));
+    res.push_str(&rest[..idx]);
+    res.push_str(if whole { new } else { old });
+    prev = old.chars().next_back();
+    rest = &rest[idx + old.len()..];
+  }
+  res.push_str(rest);
+  res
+}
+
+/// The outcome of upgrading a dependency of a package
+#[derive(Clone, Debug)]
+pub struct Upgrade {
+  /// The import being upgraded, as it was before the upgrade
+  pub import: Import,
+  /// The changes between the old and new versions of the dependency
+  pub diff: PackageDiff,
+  /// References in the source that were mechanically rewritten
+  pub renames: Vec<(Name, Name)>,
+  /// The rewritten package source
+  pub source: String,
+  /// Local definitions that no longer typecheck, with the reason
+  pub broken: Vec<(Name, String)>,
+}
+
+impl Upgrade {
+  /// Returns true if every local definition still typechecks
+  pub fn is_clean(&self) -> bool { self.broken.is_empty() }
+}
+
+impl fmt::Display for Upgrade {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    writeln!(
+      f,
+      "Upgrading {} {} -> {}",
+      self.import.name, self.diff.old, self.diff.new
+    )?;
+    for change in &self.diff.changes {
+      writeln!(f, "  {}", change)?;
+    }
+    if !self.renames.is_empty() {
+      writeln!(f, "Rewrote references:")?;
+      for (old, new) in &self.renames {
+        writeln!(f, "  {} -> {}", old, new)?;
+      }
+    }
+    if self.broken.is_empty() {
+      writeln!(f, "All definitions check against the new version")?;
+    }
+    else {
+      writeln!(f, "Broken definitions:")?;
+      for (name, err) in &self.broken {
+        writeln!(f, "✕ {}: {}", name, err)?;
+      }
+    }
+    Ok(())
+  }
+}
+
+/// Bumps the import of `dep` in the package at `path` to the package at `to`.
+/// References to definitions that were renamed in the new version, whether
+/// defined there or re-exported from its own imports, are rewritten, and the
+/// resulting source is rechecked to report which local definitions break. The
+/// source file itself is not modified; callers decide whether to write
+/// `Upgrade::source` back.
+pub fn upgrade(
+  root: PathBuf,
+  path: PathBuf,
+  dep: &Name,
+  to: Cid,
+  store: Rc<dyn Store>,
+) -> Result<Upgrade, String> {
+  let mut file = root.clone();
+  file.push(path.clone());
+  let txt = fs::read_to_string(&file)
+    .map_err(|e| format!("file {:?} not found {:?}", &file, e))?;
+  let env = PackageEnv::new(root.clone(), path.clone(), store.clone());
+  let (_, package, _) = parse_text(&txt, env)?;
+  let import = package
+    .imports
+    .iter()
+    .find(|i| i.name == *dep || i.alias == *dep)
+    .cloned()
+    .ok_or_else(|| {
+      format!("Package {} does not import {}", package.name, dep)
+    })?;
+  let diff = diff_packages(store.clone(), import.cid, to)?;
+  let mut renamed = diff.renames();
+  renamed.extend(reexport_renames(&store, import.cid, to)?);
+  let source = find_import(&txt, &import.name)
+    .ok_or_else(|| format!("Cannot locate the import of {}", import.name))?;
+
+  let mut new_import = import.clone();
+  new_import.cid = to;
+  let mut new_source = source.clone();
+  new_source.link = Some(to);
+  let mut renames = Vec::new();
+  for (old, new) in renamed {
+    let old_local = import_alias(old.clone(), &import);
+    if let Some(with) = &mut new_source.with {
+      for n in with.iter_mut().filter(|n| **n == old) {
+        *n = new.clone();
+      }
+    }
+    for n in new_import.with.iter_mut().filter(|n| **n == old) {
+      *n = new.clone();
+    }
+    let new_local = import_alias(new, &new_import);
+    renames.push((old_local, new_local));
+  }
+
+  let mut body = txt[source.end..].to_owned();
+  for (old, new) in &renames {
+    body = rename_symbol(&body, old, new);
+  }
+  let new_txt = format!("{}{}{}", &txt[..source.start], new_source, body);
+
+  let mut broken = Vec::new();
+  let env = PackageEnv::new(root, path, store);
+  match parse_text(&new_txt, env) {
+    Ok((_, package, defs)) => {
+      let defs = Rc::new(defs);
+      for (name, _) in &package.index.0 {
+        if let Err(e) = check_def(defs.clone(), name, false) {
+          broken.push((name.clone(), e.to_string()));
+        }
+      }
+    }
+    Err(e) => broken.push((package.name.clone(), e)),
+  }
+  Ok(Upgrade { import, diff, renames, source: new_txt, broken })
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use crate::checkout::tests::MemStore;
+
+  #[test]
+  fn test_rename_symbol() {
+    assert_eq!(rename_symbol("f (f.x) ff f", "f", "g"), "g (f.x) ff g");
+    assert_eq!(
+      rename_symbol("Nat.add Nat.add2", "Nat.add", "Nat.plus"),
+      "Nat.plus Nat.add2"
+    );
+  }
+
+  #[test]
+  fn test_find_import() {
+    let txt = "package Foo\nimport Nat as N (add) where\ndef x = N.add";
+    let import = find_import(txt, &Name::from("Nat")).unwrap();
+    assert_eq!(import.alias, Some(Name::from("N")));
+    assert_eq!(import.with, Some(vec![Name::from("add")]));
+    assert_eq!(&txt[import.start..import.end], "import Nat as N (add)");
+    assert_eq!(find_import(txt, &Name::from("Bool")), None);
+  }
+
+  #[test]
+  fn test_upgrade_reexport() {
+    let store: Rc<dyn Store> = Rc::new(MemStore::default());
+    let root = std::env::temp_dir()
+      .join(format!("yatima-upgrade-{}", std::process::id()));
+    let nat = |name: &str| {
+      format!(
+        "package Nat where\n\ndef {} (x y: #Nat): #Nat = #Nat.add x y\n",
+        name
+      )
+    };
+    fs::create_dir_all(&root).unwrap();
+    fs::write(root.join("Nat.ya"), nat("add")).unwrap();
+    fs::write(
+      root.join("Prelude.ya"),
+      "package Prelude\nimport Nat (add)\nwhere\n\ndef two: #Nat = add 1 1\n",
+    )
+    .unwrap();
+    fs::write(
+      root.join("Calc.ya"),
+      "package Calc\nimport Prelude (two, add)\nwhere\n\ndef four: #Nat = add \
+       two two\n",
+    )
+    .unwrap();
+    // The new Prelude re-exports `add` from Nat under its new name
+    let env =
+      |path: &str| PackageEnv::new(root.clone(), path.into(), store.clone());
+    let (nat_cid, ..) = parse_text(&nat("plus"), env("Nat.ya")).unwrap();
+    let prelude = format!(
+      "package Prelude\nimport Nat (plus) {}\nwhere\n\ndef two: #Nat = plus 1 \
+       1\n",
+      nat_cid
+    );
+    let (to, ..) = parse_text(&prelude, env("Prelude.ya")).unwrap();
+
+    let res = upgrade(
+      root.clone(),
+      PathBuf::from("Calc.ya"),
+      &Name::from("Prelude"),
+      to,
+      store.clone(),
+    );
+    fs::remove_dir_all(&root).unwrap();
+    let res = res.unwrap();
+    assert_eq!(res.renames, vec![(Name::from("add"), Name::from("plus"))]);
+    assert_eq!(
+      res.source,
+      format!(
+        "package Calc\nimport Prelude (two, plus) {}\nwhere\n\ndef four: #Nat \
+         = plus two two\n",
+        to
+      )
+    );
+    assert!(res.is_clean(), "{}", res);
+  }
+}
@@ -17,7 +17,7 @@ use web_sys::{
 };
 use yatima_core::{
   defs::Defs,
-  parse::parse_cid,
+  parse::parse_cid_str,
 };
 use yatima_utils::{
   debug,
@@ -79,7 +79,7 @@ impl Store for WebStore {
 
   fn load_by_name_with_callback(&self, path: Vec<&str>, callback: Callback<Ipld, Defs>) {
     debug!("load_by_name: {:?}", path);
-    if let Some(Ok(link)) = path.last().map(|s| parse_cid(s)) {
+    if let Some(Ok(link)) = path.last().map(|s| parse_cid_str(s)) {
       let monitor = callback.monitor;
       let id = callback.id;
       let monitor_c = monitor.clone();